@@ -13,11 +13,31 @@
 // limitations under the License.
 
 //! decimal-rs benchmark
-
-use bencher::{benchmark_group, benchmark_main, black_box, Bencher};
-use decimal_rs::{Decimal, DecimalConvertError, DECIMAL128, MAX_BINARY_SIZE};
+//!
+//! Benchmarks are organized in the groups `parse`, `parse_worst_case`, `format`, `arith`, `convert`, `encode`,
+//! `agg`, `batch`, `batch_scalar` and `batch_vector`, a single group can be run with e.g. `cargo bench -- format/`.
+//! The inputs are drawn from `decimal_rs::testing`, enabled through the dev-dependency on this crate.
+//!
+//! To compare a change against the current code, save a baseline first and then
+//! compare with it:
+//!
+//! ```text
+//! git checkout master && cargo bench -- --save-baseline master
+//! git checkout my-branch && cargo bench -- --baseline master
+//! ```
+//!
+//! Criterion reports the changes that are statistically significant.
+
+mod corpus;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use decimal_rs::{
+    agg, batch, testing, Decimal, DecimalConvertError, DecimalFormatter, FormatOptions, ParseLimits, PreparedDivisor,
+    RoundingMode, DECIMAL128, MAX_BINARY_SIZE,
+};
 use std::collections::hash_map::DefaultHasher;
 use std::convert::{TryFrom, TryInto};
+use std::fmt::Write;
 use std::hash::Hash;
 
 #[inline(always)]
@@ -25,377 +45,544 @@ fn parse(s: &str) -> Decimal {
     s.parse().unwrap()
 }
 
-fn decimal_parse(bench: &mut Bencher) {
-    bench.iter(|| {
-        let _n = parse(black_box("12345678901.23456789"));
-    })
-}
-
-fn decimal_to_string(bench: &mut Bencher) {
-    let val = parse("12345678901.23456789");
-    bench.iter(|| {
-        let _n = black_box(&val).to_string();
-    })
-}
-
-fn decimal_precision(bench: &mut Bencher) {
-    let val = parse("12345678901.23456789");
-    bench.iter(|| {
-        let _n = black_box(&val).precision();
-        black_box(_n);
-    })
-}
-
 #[inline(always)]
 fn try_from<T: TryInto<Decimal, Error = DecimalConvertError>>(val: T) -> Decimal {
     val.try_into().unwrap()
 }
 
-#[allow(clippy::excessive_precision)]
-fn decimal_from_f64(bench: &mut Bencher) {
-    bench.iter(|| {
-        let _n = try_from(black_box(12345678901.23456789_f64));
-    })
-}
+fn parse_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+
+    group.bench_function("normal", |b| b.iter(|| parse(black_box("12345678901.23456789"))));
+    group.bench_function("small_int", |b| b.iter(|| parse(black_box("42"))));
+    group.bench_function("long", |b| {
+        b.iter(|| parse(black_box("1234567890123456789012345678901234567890.123456789012345")))
+    });
+    group.bench_function("sci", |b| {
+        b.iter(|| parse(black_box("-1.2345678901234567890123456789E-100")))
+    });
+    group.bench_function("whitespace", |b| b.iter(|| parse(black_box("   12345.6789   "))));
+    group.bench_function("from_str_14", |b| b.iter(|| parse(black_box("00001234.56780"))));
+    group.bench_function("fixed_point_14", |b| {
+        b.iter(|| Decimal::parse_fixed_point(black_box(b"00001234.56780"), 8).unwrap())
+    });
+    group.bench_function("from_str_20", |b| b.iter(|| parse(black_box("0001234567.123456789"))));
+    group.bench_function("fixed_point_20", |b| {
+        b.iter(|| Decimal::parse_fixed_point(black_box(b"0001234567.123456789"), 10).unwrap())
+    });
+    group.bench_function("digits_iter_20", |b| {
+        b.iter(|| Decimal::from_digits_iter(black_box(b"0001234567123456789").iter().copied(), 9, false).unwrap())
+    });
+
+    let strings = corpus::to_strings(&corpus::mixed_batch());
+    group.throughput(Throughput::Elements(strings.len() as u64));
+    group.bench_function("batch", |b| {
+        b.iter(|| {
+            for s in strings.iter() {
+                black_box(parse(black_box(s)));
+            }
+        })
+    });
+
+    group.finish();
+
+    // The inputs shaped to maximize the work of the parser, measured per byte.
+    let mut group = c.benchmark_group("parse_worst_case");
+    let inputs = [
+        ("leading_zeros_10k", "0".repeat(10_000) + "1.5"),
+        ("fractional_10k", "0.".to_string() + &"1".repeat(10_000)),
+        ("exponent_zeros_10k", "1e".to_string() + &"0".repeat(10_000) + "1"),
+    ];
+    let limits = ParseLimits::default();
+    for (name, s) in inputs.iter() {
+        group.throughput(Throughput::Bytes(s.len() as u64));
+        group.bench_function(*name, |b| b.iter(|| black_box(s).parse::<Decimal>()));
+        group.bench_function(format!("{}_limits", name), |b| {
+            b.iter(|| Decimal::from_str_with_limits(black_box(s), &limits))
+        });
+    }
+    group.finish();
+}
+
+fn format_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("format");
 
-fn decimal_into_f64(bench: &mut Bencher) {
     let val = parse("12345678901.23456789");
-    bench.iter(|| {
-        black_box(f64::from(black_box(&val)));
-    })
-}
-
-fn decimal_into_u64(bench: &mut Bencher) {
-    let val = parse("12345678901.23456789");
-    bench.iter(|| {
-        let _n = u64::try_from(black_box(&val)).unwrap();
-    })
-}
-
-#[inline(always)]
-fn add(x: &Decimal, y: &Decimal) -> Decimal {
-    x + y
-}
-
-fn decimal_add(bench: &mut Bencher) {
-    let x = parse("12345678901.23456789");
-    let y = parse("123456.7890123456789");
-    bench.iter(|| {
-        let _n = add(black_box(&x), black_box(&y));
-    })
-}
-
-#[inline(always)]
-fn sub(x: &Decimal, y: &Decimal) -> Decimal {
-    x - y
-}
-
-fn decimal_sub(bench: &mut Bencher) {
-    let x = parse("12345678901.23456789");
-    let y = parse("123456.7890123456789");
-    bench.iter(|| {
-        let _n = sub(black_box(&x), black_box(&y));
-    })
-}
-
-#[inline(always)]
-fn mul(x: &Decimal, y: &Decimal) -> Decimal {
-    x * y
-}
-
-fn decimal_mul(bench: &mut Bencher) {
-    let x = parse("12345678901.23456789");
-    let y = parse("123456.7890123456789");
-    bench.iter(|| {
-        let _n = mul(black_box(&x), black_box(&y));
-    })
-}
-
-#[inline(always)]
-fn div(x: &Decimal, y: &Decimal) -> Decimal {
-    x / y
-}
+    group.bench_function("to_string", |b| b.iter(|| black_box(&val).to_string()));
+
+    let mut s = String::with_capacity(256);
+    let mut sci = |name: &str, val: &str, max_width: u16| {
+        let x = parse(val);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                s.clear();
+                black_box(&x).format_with_sci(max_width, &mut s)
+            })
+        });
+    };
+    // "0"
+    sci("sci_zero", "0.0", 1);
+    // "1000"
+    sci("sci_normal", "1000", 4);
+    // ".000012346"
+    sci("sci_normal_round", ".0000123456789", 10);
+    // "1.2E+09"
+    sci("sci_int", "1234567890.123456789", 7);
+    // "1.2E-09"
+    sci("sci_fraction", ".00000000123456789", 7);
+    sci("sci_supply_zero", "0.1E-126", 127);
+    sci("sci_max", "9.9999999999999999999999999999999999999E125", 40);
+    sci("sci_min", "1E-130", 40);
+    sci("sci_max_narrow", "-9.9999999999999999999999999999999999999E125", 10);
 
-fn decimal_div(bench: &mut Bencher) {
-    let x = parse("12345678901.23456789");
-    let y = parse("123456.7890123456789");
-    bench.iter(|| {
-        let _n = div(black_box(&x), black_box(&y));
-    })
-}
+    let x = parse("3534.33");
+    group.bench_function("hex", |b| {
+        b.iter(|| {
+            s.clear();
+            black_box(&x).format_to_hex(true, &mut s)
+        })
+    });
+
+    for &(name, val) in [("json", "12345678901.23456789"), ("json_extreme", "1E-130")].iter() {
+        let x = parse(val);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                s.clear();
+                black_box(&x).format_to_json(&mut s)
+            })
+        });
+    }
+
+    let values = corpus::mixed_batch();
+    group.throughput(Throughput::Elements(values.len() as u64));
+    group.bench_function("batch", |b| {
+        b.iter(|| {
+            for v in values.iter() {
+                s.clear();
+                black_box(v).format_with_sci(40, &mut s).unwrap();
+            }
+        })
+    });
+
+    // the same options for many values, as `{:>16.2}`
+    let values = corpus::vector(0xf0a7);
+    let (width, precision) = (16, 2);
+    let mut out = String::with_capacity(values.len() * width);
+    group.throughput(Throughput::Elements(values.len() as u64));
+    group.bench_function("display_options", |b| {
+        b.iter(|| {
+            out.clear();
+            for v in values.iter() {
+                write!(out, "{:>w$.p$}", black_box(v), w = width, p = precision).unwrap();
+            }
+        })
+    });
+    let formatter = DecimalFormatter::new(FormatOptions {
+        precision: Some(precision),
+        width: Some(width),
+        ..FormatOptions::default()
+    });
+    group.bench_function("formatter", |b| {
+        b.iter(|| {
+            out.clear();
+            for v in values.iter() {
+                formatter.format_into(black_box(v), &mut out).unwrap();
+            }
+        })
+    });
+
+    // a column of large round numbers, whose formatting is mostly zeros
+    let values: Vec<Decimal> = (0..1000)
+        .map(|i| {
+            parse(&format!(
+                "{}e{}",
+                i % 9 + 1,
+                if i % 2 == 0 { 100 + i % 26 } else { -(105 + i % 26) }
+            ))
+        })
+        .collect();
+    group.throughput(Throughput::Elements(values.len() as u64));
+    group.bench_function("round_display", |b| {
+        b.iter(|| {
+            out.clear();
+            for v in values.iter() {
+                write!(out, "{}", black_box(v)).unwrap();
+            }
+        })
+    });
+    group.bench_function("round_json", |b| {
+        b.iter(|| {
+            out.clear();
+            for v in values.iter() {
+                black_box(v).format_to_json(&mut out).unwrap();
+            }
+        })
+    });
+    group.bench_function("round_sci", |b| {
+        b.iter(|| {
+            out.clear();
+            for v in values.iter() {
+                black_box(v).format_with_sci(40, &mut out).unwrap();
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn arith_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("arith");
 
-fn decimal_rem(bench: &mut Bencher) {
     let x = parse("12345678901.23456789");
     let y = parse("123456.7890123456789");
-    bench.iter(|| {
-        let _n = black_box(&x) % black_box(&y);
-    })
-}
+    group.bench_function("add", |b| b.iter(|| black_box(&x) + black_box(&y)));
+    group.bench_function("sub", |b| b.iter(|| black_box(&x) - black_box(&y)));
+    group.bench_function("mul", |b| b.iter(|| black_box(&x) * black_box(&y)));
+    group.bench_function("div", |b| b.iter(|| black_box(&x) / black_box(&y)));
+    group.bench_function("rem", |b| b.iter(|| black_box(&x) % black_box(&y)));
+    group.bench_function("cmp", |b| b.iter(|| black_box(&x) > black_box(&y)));
+
+    let m1 = parse("1901.23");
+    let m2 = parse("8901.45");
+    group.bench_function("add_same_scale", |b| b.iter(|| black_box(&m1) + black_box(&m2)));
+    group.bench_function("div_small", |b| b.iter(|| black_box(&m2) / black_box(&m1)));
+    let (s1, s2) = (parse("7.5"), parse("2.5"));
+    group.bench_function("div_small_exact", |b| b.iter(|| black_box(&s1) / black_box(&s2)));
+    group.bench_function("mul_small_int", |b| b.iter(|| black_box(&m1) * black_box(7)));
+
+    let x1 = parse("901.23456789");
+    let y1 = parse("8901.23456789");
+    group.bench_function("add_same_scale_unchecked", |b| {
+        b.iter(|| unsafe { black_box(&x1).add_with_same_scale_unchecked::<DECIMAL128>(black_box(&y1), 8) })
+    });
+    group.bench_function("add_same_scale_negative_unchecked", |b| {
+        b.iter(|| unsafe {
+            black_box(&x1).add_with_same_scale_and_negative_unchecked::<DECIMAL128>(black_box(&y1), 8, true)
+        })
+    });
+    group.bench_function("sub_same_scale_unchecked", |b| {
+        b.iter(|| unsafe { black_box(&x1).sub_with_same_scale_unchecked::<DECIMAL128>(black_box(&y1), 8) })
+    });
+    group.bench_function("mul_unchecked", |b| {
+        b.iter(|| unsafe { black_box(&x1).mul_unchecked::<DECIMAL128>(black_box(&y1), 16) })
+    });
+
+    group.bench_function("ceil", |b| b.iter(|| black_box(&x).ceil()));
+    group.bench_function("floor", |b| b.iter(|| black_box(&x).floor()));
+    group.bench_function("round", |b| b.iter(|| black_box(&x).round(2)));
+    group.bench_function("normalize", |b| b.iter(|| black_box(&x).normalize()));
+    group.bench_function("sqrt", |b| b.iter(|| black_box(&x).sqrt()));
+    group.bench_function("sqrt_with_scale_4", |b| b.iter(|| black_box(&x).sqrt_with_scale(4)));
+
+    let base = parse("12.3456");
+    let exp = parse("50.123456");
+    group.bench_function("pow", |b| b.iter(|| black_box(&base).checked_pow(black_box(&exp))));
+    let ln = parse("1234567890123456.789123456");
+    group.bench_function("ln", |b| b.iter(|| black_box(&ln).ln()));
+    let e = parse("259.123456789");
+    group.bench_function("exp", |b| b.iter(|| black_box(&e).exp()));
+
+    // reduced working precision bounds the latency of the transcendental functions
+    group.bench_function("pow_16", |b| {
+        b.iter(|| black_box(&base).pow_with_precision(black_box(&exp), 16))
+    });
+    group.bench_function("ln_16", |b| b.iter(|| black_box(&ln).ln_with_precision(16)));
+    group.bench_function("exp_16", |b| b.iter(|| black_box(&e).exp_with_precision(16)));
+
+    let money = corpus::money_batch();
+    let mixed = corpus::mixed_batch();
+    group.throughput(Throughput::Elements(money.len() as u64));
+    group.bench_function("batch_sum_money", |b| {
+        b.iter(|| black_box(&money).iter().sum::<Decimal>())
+    });
+    group.bench_function("batch_sum_mixed", |b| {
+        b.iter(|| {
+            black_box(&mixed)
+                .iter()
+                .fold(Decimal::ZERO, |acc, v| acc.checked_add(v).unwrap_or(acc))
+        })
+    });
+    group.bench_function("batch_mul_mixed", |b| {
+        b.iter(|| {
+            for (x, y) in black_box(&mixed).iter().zip(money.iter()) {
+                black_box(x.checked_mul(y));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn convert_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("convert");
 
-fn decimal_encode(bench: &mut Bencher) {
-    let x = parse("12345678901.23456789");
-    let mut buf = [0; MAX_BINARY_SIZE];
-    bench.iter(|| {
-        let _n = black_box(black_box(&x).encode(&mut buf[..]).unwrap());
-    })
-}
-
-fn decimal_decode(bench: &mut Bencher) {
-    let mut buf = Vec::new();
-    parse("12345678901.23456789").encode(&mut buf).unwrap();
-    bench.iter(|| {
-        let _n = black_box(Decimal::decode(black_box(&buf)));
-    })
-}
-
-fn decimal_normalize(bench: &mut Bencher) {
-    let x = parse("12345678901.23456789");
-    bench.iter(|| {
-        let _n = black_box(black_box(&x).normalize());
-    })
-}
+    let val = parse("12345678901.23456789");
+    #[allow(clippy::excessive_precision)]
+    group.bench_function("from_f64", |b| b.iter(|| try_from(black_box(12345678901.23456789_f64))));
+    group.bench_function("from_i64", |b| b.iter(|| Decimal::from(black_box(-1234567890123_i64))));
+    group.bench_function("into_f64", |b| b.iter(|| f64::from(black_box(&val))));
+    group.bench_function("into_u64", |b| b.iter(|| u64::try_from(black_box(&val)).unwrap()));
+    group.bench_function("precision", |b| b.iter(|| black_box(&val).precision()));
 
-fn decimal_hash(bench: &mut Bencher) {
-    let x = parse("12345678901.23456789");
     let mut hasher = DefaultHasher::new();
-    bench.iter(|| {
-        black_box(&x).hash(&mut hasher);
-    })
-}
+    group.bench_function("hash", |b| b.iter(|| black_box(&val).hash(&mut hasher)));
 
-fn decimal_cmp(bench: &mut Bencher) {
-    let x = parse("12345678901.23456789");
-    let y = parse("12345.67890123456789");
-    bench.iter(|| {
-        let _n = black_box(x > y);
-    })
-}
-
-fn decimal_sqrt(bench: &mut Bencher) {
-    let x = parse("12345678901.23456789");
-    bench.iter(|| {
-        let _n = black_box(&x).sqrt();
-    })
-}
-
-fn decimal_sci_zero(bench: &mut Bencher) {
-    let x = parse("0.0");
-    let mut s = String::with_capacity(100);
-    bench.iter(|| {
-        s.clear();
-        // "0"
-        let _n = black_box(&x).format_with_sci(1, &mut s);
-    })
-}
+    let values = corpus::mixed_batch();
+    group.throughput(Throughput::Elements(values.len() as u64));
+    group.bench_function("batch_into_f64", |b| {
+        b.iter(|| {
+            for v in black_box(&values).iter() {
+                black_box(f64::from(v));
+            }
+        })
+    });
 
-fn decimal_sci_normal(bench: &mut Bencher) {
-    let x = parse("1000");
-    let mut s = String::with_capacity(100);
-    bench.iter(|| {
-        s.clear();
-        // "1000"
-        let _n = black_box(&x).format_with_sci(4, &mut s);
-    })
+    group.finish();
 }
 
-fn decimal_sci_normal_round(bench: &mut Bencher) {
-    let x = parse(".0000123456789");
-    let mut s = String::with_capacity(100);
-    bench.iter(|| {
-        s.clear();
-        // ".000012346"
-        let _n = black_box(&x).format_with_sci(10, &mut s);
-    })
-}
-
-fn decimal_sci_int(bench: &mut Bencher) {
-    let x = parse("1234567890.123456789");
-    let mut s = String::with_capacity(100);
-    bench.iter(|| {
-        s.clear();
-        // "1.2E+09"
-        let _n = black_box(&x).format_with_sci(7, &mut s);
-    })
-}
-
-fn decimal_sci_fraction(bench: &mut Bencher) {
-    let x = parse(".00000000123456789");
-    let mut s = String::with_capacity(100);
-    bench.iter(|| {
-        s.clear();
-        // "1.2E-09"
-        let _n = black_box(&x).format_with_sci(7, &mut s);
-    })
-}
-
-fn decimal_sci_supply_zero(bench: &mut Bencher) {
-    let x = parse("0.1E-126");
-    let mut s = String::with_capacity(100);
-    bench.iter(|| {
-        s.clear();
-        let _n = black_box(&x).format_with_sci(127, &mut s);
-    })
-}
+fn encode_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode");
 
-fn decimal_hex(bench: &mut Bencher) {
-    let x = parse("3534.33");
-    let mut s = String::with_capacity(64);
-    bench.iter(|| {
-        s.clear();
-        let _n = black_box(&x).format_to_hex(true, &mut s);
-    })
-}
-
-fn decimal_pow(bench: &mut Bencher) {
-    let x = parse("12.3456");
-    let y = parse("50.123456");
-    bench.iter(|| {
-        let _n = black_box(&x).checked_pow(&y);
-    })
-}
-
-fn decimal_ln(bench: &mut Bencher) {
-    let x = parse("1234567890123456.789123456");
-    bench.iter(|| {
-        let _n = black_box(&x).ln();
-    })
-}
-
-fn decimal_exp(bench: &mut Bencher) {
-    let x = parse("259.123456789");
-    bench.iter(|| {
-        let _n = black_box(&x).exp();
-    })
-}
-
-fn decimal_ceil_100_times(bench: &mut Bencher) {
-    let x = parse("12345678901.23456789");
-    bench.iter(|| {
-        for _ in 0..100 {
-            let _n = black_box(&x).ceil();
-        }
-    })
-}
-
-fn decimal_floor_100_times(bench: &mut Bencher) {
     let x = parse("12345678901.23456789");
-    bench.iter(|| {
-        for _ in 0..100 {
-            let _n = black_box(&x).floor();
-        }
-    })
-}
-
-#[inline(always)]
-fn add_with_same_scale(x: &Decimal, y: &Decimal) -> Decimal {
-    unsafe { x.add_with_same_scale_unchecked::<DECIMAL128>(y, 8) }
-}
-
-fn decimal_uncheck_add_same_scale_100_times(bench: &mut Bencher) {
-    let x = parse("901.23456789");
-    let y = parse("8901.23456789");
-    bench.iter(|| {
-        for _ in 0..100 {
-            let _n = add_with_same_scale(black_box(&x), black_box(&y));
-        }
-    })
-}
-
-#[inline(always)]
-fn add_with_same_scale_negative(x: &Decimal, y: &Decimal) -> Decimal {
-    unsafe { x.add_with_same_scale_and_negative_unchecked::<DECIMAL128>(y, 8, true) }
-}
-
-fn decimal_uncheck_add_same_scale_negative_100_times(bench: &mut Bencher) {
-    let x = parse("1891.23456789");
-    let y = parse("6701.23456789");
-    bench.iter(|| {
-        for _ in 0..100 {
-            let _n = add_with_same_scale_negative(black_box(&x), black_box(&y));
-        }
-    })
-}
-
-#[inline(always)]
-fn sub_with_same_scale(x: &Decimal, y: &Decimal) -> Decimal {
-    unsafe { x.sub_with_same_scale_unchecked::<DECIMAL128>(y, 8) }
-}
-
-fn decimal_uncheck_sub_100_times(bench: &mut Bencher) {
-    let x = parse("11.23456789");
-    let y = parse("71.23456789");
-    bench.iter(|| {
-        for _ in 0..100 {
-            let _n = sub_with_same_scale(black_box(&x), black_box(&y));
-        }
-    })
-}
-
-#[inline(always)]
-fn mull_unchecked(x: &Decimal, y: &Decimal) -> Decimal {
-    unsafe { x.mul_unchecked::<DECIMAL128>(y, 16) }
-}
-
-fn decimal_uncheck_mul_100_times(bench: &mut Bencher) {
-    let x = parse("1901.23456789");
-    let y = parse("7901.23456789");
-    bench.iter(|| {
-        for _ in 0..100 {
-            let _n = mull_unchecked(black_box(&x), black_box(&y));
-        }
-    })
-}
-
-#[inline(always)]
-fn cmp_zero(x: i128) -> bool {
-    x != 0
-}
-
-fn i128_cmp_zero_100_times(bench: &mut Bencher) {
-    let x = 12345678901;
-    bench.iter(|| {
-        for _ in 0..100 {
-            let _n = cmp_zero(black_box(x));
-        }
-    })
-}
-
-benchmark_group!(
+    let mut buf = [0; MAX_BINARY_SIZE];
+    group.bench_function("encode", |b| b.iter(|| black_box(&x).encode(&mut buf[..]).unwrap()));
+    group.bench_function("compact_encode", |b| {
+        b.iter(|| black_box(&x).compact_encode(&mut buf[..]).unwrap())
+    });
+
+    let mut encoded = Vec::new();
+    x.encode(&mut encoded).unwrap();
+    group.bench_function("decode", |b| b.iter(|| Decimal::decode(black_box(&encoded))));
+
+    let boundary = testing::corpus();
+    group.throughput(Throughput::Elements(boundary.len() as u64));
+    group.bench_function("encode_boundary", |b| {
+        b.iter(|| {
+            for v in black_box(&boundary).iter() {
+                black_box(v.encode(&mut buf[..]).unwrap());
+            }
+        })
+    });
+
+    let values = corpus::mixed_batch();
+    group.throughput(Throughput::Elements(values.len() as u64));
+    group.bench_function("batch_encode", |b| {
+        b.iter_batched_ref(
+            || Vec::with_capacity(values.len() * MAX_BINARY_SIZE),
+            |out| {
+                for v in values.iter() {
+                    v.compact_encode(&mut *out).unwrap();
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn agg_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("agg");
+
+    let (quantities, prices) = corpus::fills();
+    group.throughput(Throughput::Elements(quantities.len() as u64));
+    group.bench_function("notional_naive", |b| {
+        b.iter(|| {
+            black_box(&quantities)
+                .iter()
+                .zip(black_box(&prices).iter())
+                .try_fold(Decimal::ZERO, |sum, (q, p)| {
+                    sum.checked_add(Decimal::from(*q).checked_mul(p)?)
+                })
+        })
+    });
+    group.bench_function("notional_dot_u64", |b| {
+        b.iter(|| agg::dot_u64(black_box(&quantities), black_box(&prices)))
+    });
+    group.bench_function("notional_accumulator", |b| {
+        b.iter(|| {
+            let mut acc = agg::NotionalAccumulator::new();
+            for (q, p) in black_box(&quantities).iter().zip(black_box(&prices).iter()) {
+                acc.push(*q, p);
+            }
+            acc.sum()
+        })
+    });
+
+    group.finish();
+}
+
+fn batch_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch");
+    group.throughput(Throughput::Elements(corpus::COLUMN_SIZE as u64));
+
+    let columns = [
+        ("uniform", corpus::column(0xc01a, 4, 4), corpus::column(0xc01b, 4, 4)),
+        ("mixed", corpus::column(0xc01a, -4, 8), corpus::column(0xc01b, -4, 8)),
+    ];
+    for (name, lhs, rhs) in columns.iter() {
+        group.bench_function(format!("cmp_naive_{}", name), |b| {
+            b.iter(|| {
+                black_box(lhs)
+                    .iter()
+                    .zip(black_box(rhs).iter())
+                    .map(|(l, r)| l.cmp(r) as i8)
+                    .collect::<Vec<i8>>()
+            })
+        });
+        let mut out = Vec::new();
+        group.bench_function(format!("cmp_{}", name), |b| {
+            b.iter(|| batch::cmp(black_box(lhs), black_box(rhs), &mut out))
+        });
+        let mut mask = Vec::new();
+        group.bench_function(format!("lt_{}", name), |b| {
+            b.iter(|| batch::lt(black_box(lhs), black_box(rhs), &mut mask))
+        });
+        let scalar = rhs[0];
+        group.bench_function(format!("lt_scalar_{}", name), |b| {
+            b.iter(|| batch::lt_scalar(black_box(lhs), black_box(&scalar), &mut mask))
+        });
+    }
+
+    group.finish();
+}
+
+fn batch_scalar_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch_scalar");
+    group.throughput(Throughput::Elements(corpus::LARGE_COLUMN_SIZE as u64));
+    group.sample_size(20);
+
+    let values = corpus::large_column(0x5ca1);
+    let scalars = [("general", parse("1.0873456789")), ("pow10", parse("0.01"))];
+    for (name, scalar) in scalars.iter() {
+        group.bench_function(format!("mul_naive_{}", name), |b| {
+            b.iter(|| {
+                black_box(&values)
+                    .iter()
+                    .map(|v| v.checked_mul(black_box(scalar)))
+                    .collect::<Option<Vec<Decimal>>>()
+            })
+        });
+        group.bench_function(format!("mul_in_place_{}", name), |b| {
+            b.iter_batched_ref(
+                || values.clone(),
+                |values| batch::mul_scalar_in_place(values, black_box(scalar)),
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_function(format!("div_naive_{}", name), |b| {
+            b.iter(|| {
+                black_box(&values)
+                    .iter()
+                    .map(|v| v.checked_div(black_box(scalar)))
+                    .collect::<Option<Vec<Decimal>>>()
+            })
+        });
+        group.bench_function(format!("div_in_place_{}", name), |b| {
+            b.iter_batched_ref(
+                || values.clone(),
+                |values| batch::div_scalar_in_place(values, black_box(scalar)),
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_function(format!("div_prepared_{}", name), |b| {
+            let prepared = PreparedDivisor::new(scalar).unwrap();
+            b.iter(|| {
+                black_box(&values)
+                    .iter()
+                    .map(|v| black_box(&prepared).divide(v))
+                    .collect::<Option<Vec<Decimal>>>()
+            })
+        });
+    }
+
+    let scalar = parse("0.0725");
+    group.bench_function("add_naive", |b| {
+        b.iter(|| {
+            black_box(&values)
+                .iter()
+                .map(|v| v.checked_add(black_box(&scalar)))
+                .collect::<Option<Vec<Decimal>>>()
+        })
+    });
+    group.bench_function("add_in_place", |b| {
+        b.iter_batched_ref(
+            || values.clone(),
+            |values| batch::add_scalar_in_place(values, black_box(&scalar)),
+            BatchSize::LargeInput,
+        )
+    });
+
+    // a DECIMAL(18, 4) column kept as `i64`
+    let factor = parse("10000");
+    group.bench_function("to_fixed_i64_naive", |b| {
+        b.iter(|| {
+            black_box(&values)
+                .iter()
+                .map(|v| i64::try_from(v.round_with_mode(4, RoundingMode::HalfEven) * factor).ok())
+                .collect::<Option<Vec<i64>>>()
+        })
+    });
+    let mut fixed = Vec::new();
+    group.bench_function("to_fixed_i64", |b| {
+        b.iter(|| batch::to_fixed_i64(black_box(&values), 4, RoundingMode::HalfEven, &mut fixed))
+    });
+    let mut decimals = Vec::new();
+    batch::to_fixed_i64(&values, 4, RoundingMode::HalfEven, &mut fixed).unwrap();
+    group.bench_function("from_fixed_i64", |b| {
+        b.iter(|| batch::from_fixed_i64(black_box(&fixed), 4, &mut decimals))
+    });
+
+    group.finish();
+
+    let mut group = c.benchmark_group("batch_vector");
+    group.throughput(Throughput::Elements(corpus::VECTOR_SIZE as u64));
+
+    let x = corpus::vector(0xa1b2);
+    let y = corpus::vector(0xc3d4);
+    let a = parse("1.0873456789012345678");
+    group.bench_function("axpy_naive", |b| {
+        b.iter_batched_ref(
+            || y.clone(),
+            |y| {
+                for (x, y) in black_box(&x).iter().zip(y.iter_mut()) {
+                    *y = black_box(&a).checked_mul(x)?.checked_add(*y)?;
+                }
+                Some(())
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("axpy", |b| {
+        b.iter_batched_ref(
+            || y.clone(),
+            |y| batch::axpy(black_box(&a), black_box(&x), y),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("dot_naive", |b| {
+        b.iter(|| {
+            black_box(&x)
+                .iter()
+                .zip(black_box(&y).iter())
+                .try_fold(Decimal::ZERO, |sum, (x, y)| sum.checked_add(x.checked_mul(y)?))
+        })
+    });
+    group.bench_function("dot", |b| b.iter(|| batch::dot(black_box(&x), black_box(&y))));
+
+    group.finish();
+}
+
+criterion_group!(
     decimal_benches,
-    decimal_parse,
-    decimal_to_string,
-    decimal_precision,
-    decimal_into_f64,
-    decimal_from_f64,
-    decimal_into_u64,
-    decimal_add,
-    decimal_sub,
-    decimal_mul,
-    decimal_div,
-    decimal_rem,
-    decimal_encode,
-    decimal_decode,
-    decimal_normalize,
-    decimal_hash,
-    decimal_cmp,
-    decimal_sqrt,
-    decimal_sci_zero,
-    decimal_sci_normal,
-    decimal_sci_normal_round,
-    decimal_sci_int,
-    decimal_sci_fraction,
-    decimal_sci_supply_zero,
-    decimal_hex,
-    decimal_pow,
-    decimal_ln,
-    decimal_exp,
-    decimal_ceil_100_times,
-    decimal_floor_100_times,
-    decimal_uncheck_add_same_scale_100_times,
-    decimal_uncheck_add_same_scale_negative_100_times,
-    decimal_uncheck_sub_100_times,
-    decimal_uncheck_mul_100_times,
-    i128_cmp_zero_100_times
+    parse_benches,
+    format_benches,
+    arith_benches,
+    convert_benches,
+    encode_benches,
+    agg_benches,
+    batch_benches,
+    batch_scalar_benches
 );
 
-benchmark_main!(decimal_benches);
+criterion_main!(decimal_benches);
@@ -0,0 +1,76 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmark input data, drawn from the corpus shared with the tests, see `decimal_rs::testing`.
+
+use decimal_rs::{testing, Decimal};
+use std::convert::TryFrom;
+
+/// Number of elements in a batch benchmark.
+pub const BATCH_SIZE: usize = 1000;
+
+/// Generates `BATCH_SIZE` decimals that look like money amounts.
+pub fn money_batch() -> Vec<Decimal> {
+    testing::corpus_bounded(0x5eed, BATCH_SIZE, 12, 2, 2)
+}
+
+/// Generates `BATCH_SIZE` decimals biased toward the edges of the precision and the exponent range.
+pub fn mixed_batch() -> Vec<Decimal> {
+    testing::corpus_random(0xdec1, BATCH_SIZE)
+}
+
+/// Number of fills in the notional benchmarks.
+pub const FILLS_SIZE: usize = 100_000;
+
+/// Generates `FILLS_SIZE` order fills, i.e. quantities and prices with 8 fractional digits.
+pub fn fills() -> (Vec<u64>, Vec<Decimal>) {
+    let quantities = testing::corpus_bounded(0xf111, FILLS_SIZE, 6, 0, 0)
+        .iter()
+        .map(|q| u64::try_from(&q.abs()).unwrap())
+        .collect();
+    let prices = testing::corpus_bounded(0xf112, FILLS_SIZE, 12, 8, 8)
+        .iter()
+        .map(Decimal::abs)
+        .collect();
+    (quantities, prices)
+}
+
+/// Number of elements in a column of the batch benchmarks.
+pub const COLUMN_SIZE: usize = 65_536;
+
+/// Generates `COLUMN_SIZE` decimals with at most 12 digits and a scale in `min_scale..=max_scale`.
+pub fn column(seed: u64, min_scale: i16, max_scale: i16) -> Vec<Decimal> {
+    testing::corpus_bounded(seed, COLUMN_SIZE, 12, min_scale, max_scale)
+}
+
+/// Number of elements in a column of the scalar benchmarks.
+pub const LARGE_COLUMN_SIZE: usize = 1_000_000;
+
+/// Generates `LARGE_COLUMN_SIZE` decimals with at most 12 digits and a scale in `0..=4`.
+pub fn large_column(seed: u64) -> Vec<Decimal> {
+    testing::corpus_bounded(seed, LARGE_COLUMN_SIZE, 12, 0, 4)
+}
+
+/// Number of elements in a vector of the linear algebra benchmarks.
+pub const VECTOR_SIZE: usize = 100_000;
+
+/// Generates `VECTOR_SIZE` decimals with at most 20 digits and a scale in `0..=10`.
+pub fn vector(seed: u64) -> Vec<Decimal> {
+    testing::corpus_bounded(seed, VECTOR_SIZE, 20, 0, 10)
+}
+
+/// Formats a batch to strings, for the parsing benchmarks.
+pub fn to_strings(values: &[Decimal]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
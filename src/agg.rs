@@ -0,0 +1,940 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Aggregations over many decimals.
+
+use crate::decimal::Decimal;
+use crate::u256::{POWERS_10, U256};
+use core::convert::TryFrom;
+
+/// An exact sum of `quantity * mantissa` products which share one scale.
+#[derive(Debug, Copy, Clone)]
+struct WideSum {
+    positive: U256,
+    negative: U256,
+}
+
+impl WideSum {
+    const ZERO: WideSum = WideSum {
+        positive: U256::ZERO,
+        negative: U256::ZERO,
+    };
+
+    #[inline]
+    fn add(&mut self, quantity: u64, price: &Decimal) -> Option<()> {
+        // `quantity * mantissa < 2^191`, so the accumulator overflows after more than 2^64 products.
+        let product = U256::mul128(quantity as u128, price.int_val());
+        if price.is_sign_negative() {
+            self.negative = self.negative.checked_add(product)?;
+        } else {
+            self.positive = self.positive.checked_add(product)?;
+        }
+        Some(())
+    }
+
+    /// Adds `val * 10^e`.
+    #[inline]
+    fn add_scaled(&mut self, val: &Decimal, e: u32) -> Option<()> {
+        let product = U256::from(val.int_val()).checked_mul(*POWERS_10.get(e as usize)?)?;
+        if val.is_sign_negative() {
+            self.negative = self.negative.checked_add(product)?;
+        } else {
+            self.positive = self.positive.checked_add(product)?;
+        }
+        Some(())
+    }
+
+    /// Returns the sum multiplied by `10^e`, after cancelling the positive and negative parts.
+    #[inline]
+    fn rescale(&self, e: u32) -> Option<WideSum> {
+        let factor = *POWERS_10.get(e as usize)?;
+        let (abs, negative) = self.net();
+        let abs = abs.checked_mul(factor)?;
+        Some(if negative {
+            WideSum {
+                positive: U256::ZERO,
+                negative: abs,
+            }
+        } else {
+            WideSum {
+                positive: abs,
+                negative: U256::ZERO,
+            }
+        })
+    }
+
+    /// Returns the sum of `val` and this sum at `scale`, at the greater scale of both, or `None` if it
+    /// doesn't fit in 256 bits.
+    #[inline]
+    fn add_exact(&self, scale: i16, val: &Decimal) -> Option<(WideSum, i16)> {
+        let (mut sum, scale) = if val.scale() > scale {
+            (self.rescale((val.scale() - scale) as u32)?, val.scale())
+        } else {
+            (*self, scale)
+        };
+        sum.add_scaled(val, (scale - val.scale()) as u32)?;
+        Some((sum, scale))
+    }
+
+    /// Returns the sum of two sums at their scales, at the greater one, or `None` if it doesn't fit in
+    /// 256 bits.
+    #[inline]
+    fn merge(&self, scale: i16, other: &WideSum, other_scale: i16) -> Option<(WideSum, i16)> {
+        let max_scale = scale.max(other_scale);
+        let left = self.rescale((max_scale - scale) as u32)?;
+        let right = other.rescale((max_scale - other_scale) as u32)?;
+        let sum = WideSum {
+            positive: left.positive.checked_add(right.positive)?,
+            negative: left.negative.checked_add(right.negative)?,
+        };
+        Some((sum, max_scale))
+    }
+
+    /// Returns the absolute value of the sum and whether it's negative.
+    #[inline]
+    fn net(&self) -> (U256, bool) {
+        match self.positive.checked_sub(self.negative) {
+            Some(abs) => (abs, false),
+            None => (self.negative.checked_sub(self.positive).unwrap_or(U256::ZERO), true),
+        }
+    }
+
+    #[inline]
+    fn to_decimal(self, scale: i16) -> Option<Decimal> {
+        let (abs, negative) = self.net();
+        Decimal::adjust_scale(abs, scale, negative)
+    }
+}
+
+/// Computes the notional `Σ quantities[i] * prices[i]`, returning `None` if the result overflowed.
+///
+/// If all the non-zero prices have the same scale, the products are accumulated exactly in a 256-bit
+/// integer and the sum is rounded once at the end. Otherwise, the products are accumulated one by one
+/// with [`Decimal::checked_mul`] and [`Decimal::checked_add`].
+///
+/// # Panics
+///
+/// Panics if `quantities` and `prices` have different lengths.
+#[inline]
+pub fn dot_u64(quantities: &[u64], prices: &[Decimal]) -> Option<Decimal> {
+    assert_eq!(
+        quantities.len(),
+        prices.len(),
+        "quantities and prices have different lengths"
+    );
+
+    let scale = match prices.iter().find(|p| !p.is_zero()) {
+        Some(p) => p.scale(),
+        None => return Some(Decimal::ZERO),
+    };
+
+    if prices.iter().all(|p| p.scale() == scale || p.is_zero()) {
+        let mut sum = WideSum::ZERO;
+        for (quantity, price) in quantities.iter().zip(prices.iter()) {
+            sum.add(*quantity, price)?;
+        }
+        sum.to_decimal(scale)
+    } else {
+        let mut acc = NotionalAccumulator::new();
+        for (quantity, price) in quantities.iter().zip(prices.iter()) {
+            acc.push(*quantity, price);
+        }
+        acc.sum()
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum State {
+    Empty,
+    Wide(WideSum, i16),
+    Narrow(Decimal),
+    Overflowed,
+}
+
+impl State {
+    /// Returns the state of the sum of two rounded sums.
+    #[inline]
+    fn narrow(left: Option<Decimal>, right: Option<Decimal>) -> State {
+        match left.and_then(|l| l.checked_add(right?)) {
+            Some(s) => State::Narrow(s),
+            None => State::Overflowed,
+        }
+    }
+
+    /// Returns the state after adding a non-zero `val`, exactly if possible.
+    #[inline]
+    fn add(self, val: &Decimal) -> State {
+        match self {
+            State::Empty => State::Wide(WideSum::ZERO, val.scale()).add(val),
+            State::Wide(sum, scale) => match sum.add_exact(scale, val) {
+                Some((sum, scale)) => State::Wide(sum, scale),
+                None => State::narrow(sum.to_decimal(scale), Some(*val)),
+            },
+            State::Narrow(sum) => State::narrow(Some(sum), Some(*val)),
+            State::Overflowed => State::Overflowed,
+        }
+    }
+
+    #[inline]
+    fn finish(&self) -> Option<Decimal> {
+        match *self {
+            State::Empty => Some(Decimal::ZERO),
+            State::Wide(sum, scale) => sum.to_decimal(scale),
+            State::Narrow(sum) => Some(sum),
+            State::Overflowed => None,
+        }
+    }
+}
+
+/// An incremental version of [`dot_u64`], which accumulates `quantity * price` one by one.
+///
+/// While all the non-zero prices have the same scale, the products are accumulated exactly and rounded
+/// once in [`NotionalAccumulator::sum`]. At the first price with another scale, the exact sum is rounded
+/// and the following products are accumulated with [`Decimal::checked_mul`] and [`Decimal::checked_add`].
+///
+/// ```
+/// use decimal_rs::agg::NotionalAccumulator;
+/// use decimal_rs::Decimal;
+///
+/// let mut acc = NotionalAccumulator::new();
+/// acc.push(100, &"12.50".parse::<Decimal>().unwrap());
+/// acc.push(3, &"12.25".parse::<Decimal>().unwrap());
+/// assert_eq!(acc.sum(), Some("1286.75".parse().unwrap()));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct NotionalAccumulator {
+    state: State,
+}
+
+impl NotionalAccumulator {
+    /// Creates an empty accumulator, whose sum is zero.
+    #[inline]
+    pub const fn new() -> NotionalAccumulator {
+        NotionalAccumulator { state: State::Empty }
+    }
+
+    /// Adds `quantity * price` to the sum.
+    #[inline]
+    pub fn push(&mut self, quantity: u64, price: &Decimal) {
+        if price.is_zero() || quantity == 0 {
+            return;
+        }
+
+        self.state = match self.state {
+            State::Empty => {
+                let mut sum = WideSum::ZERO;
+                match sum.add(quantity, price) {
+                    Some(_) => State::Wide(sum, price.scale()),
+                    None => State::Overflowed,
+                }
+            }
+            State::Wide(mut sum, scale) if scale == price.scale() => match sum.add(quantity, price) {
+                Some(_) => State::Wide(sum, scale),
+                None => State::Overflowed,
+            },
+            State::Wide(sum, scale) => match sum.to_decimal(scale).and_then(|s| mul_add(s, quantity, price)) {
+                Some(s) => State::Narrow(s),
+                None => State::Overflowed,
+            },
+            State::Narrow(s) => match mul_add(s, quantity, price) {
+                Some(s) => State::Narrow(s),
+                None => State::Overflowed,
+            },
+            State::Overflowed => State::Overflowed,
+        };
+    }
+
+    /// Returns the sum, or `None` if it overflowed.
+    #[inline]
+    pub fn sum(&self) -> Option<Decimal> {
+        self.state.finish()
+    }
+}
+
+impl Default for NotionalAccumulator {
+    #[inline]
+    fn default() -> Self {
+        NotionalAccumulator::new()
+    }
+}
+
+/// An accumulator of the exact sum of decimals, e.g. for the partial aggregations of a parallel
+/// query, which are combined with [`DecimalSum::merge`].
+///
+/// The mantissas are accumulated in a 256-bit integer at the greatest scale so far, so there is no
+/// intermediate rounding as long as the sum fits in 77 digits at that scale, and the sum is rounded
+/// once in [`DecimalSum::finish`]. Otherwise, the exact sum so far is rounded and the following values
+/// are added with [`Decimal::checked_add`].
+///
+/// ```
+/// use decimal_rs::agg::DecimalSum;
+/// use decimal_rs::Decimal;
+///
+/// let mut sum = DecimalSum::new();
+/// for s in ["1e30", "1", "-1e30"].iter() {
+///     sum.push(&s.parse().unwrap());
+/// }
+/// assert_eq!(sum.finish(), Some(Decimal::ONE));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct DecimalSum {
+    state: State,
+}
+
+impl DecimalSum {
+    /// Creates an empty accumulator, whose sum is zero.
+    #[inline]
+    pub const fn new() -> DecimalSum {
+        DecimalSum { state: State::Empty }
+    }
+
+    /// Adds `val` to the sum.
+    #[inline]
+    pub fn push(&mut self, val: &Decimal) {
+        if val.is_zero() {
+            return;
+        }
+
+        self.state = self.state.add(val);
+    }
+
+    /// Adds the sum of `other` to the sum.
+    #[inline]
+    pub fn merge(&mut self, other: &DecimalSum) {
+        self.state = match (self.state, other.state) {
+            (state, State::Empty) => state,
+            (State::Empty, state) => state,
+            (State::Overflowed, _) | (_, State::Overflowed) => State::Overflowed,
+            (State::Wide(sum, scale), State::Wide(other_sum, other_scale)) => {
+                match sum.merge(scale, &other_sum, other_scale) {
+                    Some((sum, scale)) => State::Wide(sum, scale),
+                    None => State::narrow(sum.to_decimal(scale), other_sum.to_decimal(other_scale)),
+                }
+            }
+            (left, right) => State::narrow(left.finish(), right.finish()),
+        };
+    }
+
+    /// Returns the sum, or `None` if it overflowed.
+    #[inline]
+    pub fn finish(&self) -> Option<Decimal> {
+        self.state.finish()
+    }
+}
+
+impl Default for DecimalSum {
+    #[inline]
+    fn default() -> Self {
+        DecimalSum::new()
+    }
+}
+
+/// An accumulator of the mean of decimals, which keeps the exact sum as [`DecimalSum`] does and
+/// divides it once in [`DecimalAvg::finish`].
+///
+/// ```
+/// use decimal_rs::agg::DecimalAvg;
+/// use decimal_rs::Decimal;
+///
+/// let mut left = DecimalAvg::new();
+/// left.push(&Decimal::from(1));
+/// let mut right = DecimalAvg::new();
+/// right.push(&Decimal::from(2));
+/// right.push(&Decimal::from(2));
+/// left.merge(&right);
+/// assert_eq!(left.finish(), Some("1.6666666666666666666666666666666666667".parse().unwrap()));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct DecimalAvg {
+    sum: DecimalSum,
+    count: u64,
+}
+
+impl DecimalAvg {
+    /// Creates an empty accumulator, which has no mean.
+    #[inline]
+    pub const fn new() -> DecimalAvg {
+        DecimalAvg {
+            sum: DecimalSum::new(),
+            count: 0,
+        }
+    }
+
+    /// Adds `val` to the values.
+    #[inline]
+    pub fn push(&mut self, val: &Decimal) {
+        self.sum.push(val);
+        self.count += 1;
+    }
+
+    /// Adds the values of `other` to the values.
+    #[inline]
+    pub fn merge(&mut self, other: &DecimalAvg) {
+        self.sum.merge(&other.sum);
+        self.count += other.count;
+    }
+
+    /// Returns the count of the values.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the mean rounded to 38 digits, or `None` if there is no value or the sum overflowed.
+    #[inline]
+    pub fn finish(&self) -> Option<Decimal> {
+        if self.count == 0 {
+            return None;
+        }
+
+        match self.sum.state {
+            State::Empty => Some(Decimal::ZERO),
+            State::Wide(sum, scale) => {
+                // Widens the sum to 76 digits, so the truncated quotient has more than 38 digits and is
+                // rounded correctly.
+                let (abs, negative) = sum.net();
+                let e = 76 - abs.count_digits().min(76);
+                let (quotient, _) = abs.checked_mul(POWERS_10[e as usize])?.div_rem(self.count as u128);
+                Decimal::adjust_scale(quotient, scale + e as i16, negative)
+            }
+            State::Narrow(sum) => sum.checked_div(Decimal::from(self.count)),
+            State::Overflowed => None,
+        }
+    }
+}
+
+impl Default for DecimalAvg {
+    #[inline]
+    fn default() -> Self {
+        DecimalAvg::new()
+    }
+}
+
+impl Decimal {
+    /// Sums the decimals, which may be owned or borrowed, returning `None` as soon as the sum
+    /// overflows.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let values = [Decimal::from(1), Decimal::from(2)];
+    /// assert_eq!(Decimal::checked_sum(&values), Some(Decimal::from(3)));
+    /// assert_eq!(Decimal::checked_sum(vec![Decimal::MAX, Decimal::MAX]), None);
+    /// ```
+    #[inline]
+    pub fn checked_sum<I>(iter: I) -> Option<Decimal>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<Decimal>,
+    {
+        iter.into_iter()
+            .try_fold(Decimal::ZERO, |sum, val| sum.checked_add(val))
+    }
+
+    /// Multiplies the decimals, which may be owned or borrowed, returning `None` as soon as the
+    /// product overflows.
+    #[inline]
+    pub fn checked_product<I>(iter: I) -> Option<Decimal>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<Decimal>,
+    {
+        iter.into_iter()
+            .try_fold(Decimal::ONE, |product, val| product.checked_mul(val))
+    }
+
+    /// Sums the decimals exactly and rounds the sum once, returning `None` if it overflowed.
+    ///
+    /// The values are accumulated as [`DecimalSum`] does, so there is no intermediate rounding as long
+    /// as the sum fits in 77 digits at the greatest scale, e.g. when summing many values with the same
+    /// scale.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let big: Decimal = "1e37".parse().unwrap();
+    /// let values = [big, "0.4".parse().unwrap(), "0.4".parse().unwrap(), -big];
+    /// assert_eq!(Decimal::checked_sum(&values), Some(Decimal::ZERO));
+    /// assert_eq!(Decimal::sum_exact(&values), Some("0.8".parse().unwrap()));
+    /// ```
+    #[inline]
+    pub fn sum_exact<I>(iter: I) -> Option<Decimal>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<Decimal>,
+    {
+        let mut sum = DecimalSum::new();
+        for val in iter {
+            sum.push(val.as_ref());
+        }
+        sum.finish()
+    }
+}
+
+/// Computes the `q`-quantile of `values` exactly, returning `None` if `values` is empty
+/// or `q` is outside of `[0, 1]`.
+///
+/// The quantile is interpolated linearly between the two closest order statistics: with
+/// `h = q * (n - 1)`, the result is `lo + (h - floor(h)) * (hi - lo)`, where `lo` and `hi` are the
+/// `floor(h)`-th and `ceil(h)`-th smallest values. If `hi - lo` overflows, `(1 - f) * lo + f * hi`
+/// is used instead. The result is rounded to 38 digits, and never outside of `[lo, hi]`.
+///
+/// `values` is reordered by the selection, which takes linear time on average.
+///
+/// ```
+/// use decimal_rs::agg::quantile;
+/// use decimal_rs::Decimal;
+///
+/// let mut values: Vec<Decimal> = ["4", "1", "3", "2"].iter().map(|s| s.parse().unwrap()).collect();
+/// assert_eq!(quantile(&mut values, &"0.5".parse().unwrap()), Some("2.5".parse().unwrap()));
+/// ```
+#[inline]
+pub fn quantile(values: &mut [Decimal], q: &Decimal) -> Option<Decimal> {
+    if values.is_empty() || q.is_sign_negative() || *q > Decimal::ONE {
+        return None;
+    }
+
+    // h = q * (n - 1), f = h - floor(h)
+    let h = q.checked_mul(Decimal::from(values.len() - 1))?;
+    let index = h.floor();
+    let f = h.checked_sub(index)?;
+    let index = usize::try_from(&index).ok()?;
+
+    let (_, &mut lo, greater) = values.select_nth_unstable(index);
+    if f.is_zero() {
+        return Some(lo);
+    }
+
+    // `f != 0`, so `index + 1 < n`, and the next order statistic is the minimum of the greater values.
+    let hi = *greater.iter().min()?;
+    if lo == hi {
+        return Some(lo);
+    }
+
+    let result = match hi.checked_sub(lo) {
+        Some(diff) => lo.checked_add(f.checked_mul(diff)?)?,
+        // lo and hi have different signs, so the products can't overflow
+        None => Decimal::ONE
+            .checked_sub(f)?
+            .checked_mul(lo)?
+            .checked_add(f.checked_mul(hi)?)?,
+    };
+
+    Some(result.max(lo).min(hi))
+}
+
+#[inline]
+fn mul_add(sum: Decimal, quantity: u64, price: &Decimal) -> Option<Decimal> {
+    sum.checked_add(Decimal::from(quantity).checked_mul(price)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lcg;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn naive(quantities: &[u64], prices: &[Decimal]) -> Option<Decimal> {
+        quantities
+            .iter()
+            .zip(prices.iter())
+            .try_fold(Decimal::ZERO, |sum, (q, p)| mul_add(sum, *q, p))
+    }
+
+    fn accumulate(quantities: &[u64], prices: &[Decimal]) -> Option<Decimal> {
+        let mut acc = NotionalAccumulator::new();
+        for (q, p) in quantities.iter().zip(prices.iter()) {
+            acc.push(*q, p);
+        }
+        acc.sum()
+    }
+
+    fn assert_dot(quantities: &[u64], prices: &[Decimal], expected: Option<Decimal>) {
+        assert_eq!(dot_u64(quantities, prices), expected);
+        assert_eq!(accumulate(quantities, prices), expected);
+    }
+
+    #[test]
+    fn test_dot_same_scale() {
+        let mut rng = Lcg::new(0x1234_5678_9abc_def0u64);
+
+        let mut quantities = Vec::new();
+        let mut prices = Vec::new();
+        let mut reference = 0i128;
+        for _ in 0..1000 {
+            let quantity = rng.next() % 1_000_000;
+            let mantissa = (rng.next() % 1_000_000_000_000) as i128 * if rng.next().is_multiple_of(2) { 1 } else { -1 };
+            quantities.push(quantity);
+            prices.push(Decimal::from_scaled_i128(mantissa, 8).unwrap());
+            reference += quantity as i128 * mantissa;
+        }
+
+        let expected = Decimal::from_scaled_i128(reference, 8).unwrap();
+        assert_dot(&quantities, &prices, Some(expected));
+        assert_eq!(naive(&quantities, &prices), Some(expected));
+    }
+
+    #[test]
+    fn test_dot_exact() {
+        // The naive loop rounds `u64::MAX * price` to 38 digits, so that the small product is lost.
+        let price = parse("1.2345678901234567890123456789012345678");
+        let quantities = [u64::MAX, 3, u64::MAX];
+        let prices = [price, parse("2.0000000000000000000000000000000000001"), -price];
+        assert_dot(
+            &quantities,
+            &prices,
+            Some(parse("6.0000000000000000000000000000000000003")),
+        );
+        assert_ne!(naive(&quantities, &prices), dot_u64(&quantities, &prices));
+
+        // Rounded once at the end.
+        let quantities = [u64::MAX, u64::MAX];
+        let prices = [price, price];
+        assert_dot(
+            &quantities,
+            &prices,
+            Some(parse("45547515821453962802.498680980249868095")),
+        );
+    }
+
+    #[test]
+    fn test_dot_mixed_scale() {
+        let quantities = [100, 3, 7, 20];
+        let prices = [parse("12.5"), parse("12.25"), parse("-0.125"), parse("1E-100")];
+        assert_dot(&quantities, &prices, naive(&quantities, &prices));
+        assert_dot(&quantities, &prices, Some(parse("1285.875")));
+
+        let quantities = [u64::MAX, 3, 1, u64::MAX];
+        let price = parse("1.2345678901234567890123456789012345678");
+        let prices = [
+            price,
+            parse("2.0000000000000000000000000000000000001"),
+            parse("0.5"),
+            -price,
+        ];
+        assert_dot(&quantities, &prices, naive(&quantities, &prices));
+    }
+
+    #[test]
+    fn test_dot_zero() {
+        assert_dot(&[], &[], Some(Decimal::ZERO));
+        assert_dot(&[0, 0], &[parse("1.5"), parse("-2.25")], Some(Decimal::ZERO));
+        assert_dot(&[5, 6], &[Decimal::ZERO, Decimal::ZERO], Some(Decimal::ZERO));
+        // zero prices don't break the exact path
+        assert_dot(
+            &[5, 6, 7],
+            &[parse("1.25"), Decimal::ZERO, parse("-0.75")],
+            Some(parse("1")),
+        );
+        assert_dot(&[5, 5], &[parse("1.25"), parse("-1.25")], Some(Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_dot_overflow() {
+        let max = parse("9.9999999999999999999999999999999999999E125");
+        assert_dot(&[1], &[max], Some(max));
+        assert_dot(&[2], &[max], None);
+        assert_dot(&[u64::MAX, u64::MAX], &[max, -max], Some(Decimal::ZERO));
+        assert_dot(&[2, 1], &[max, Decimal::ONE], None);
+
+        let mut acc = NotionalAccumulator::new();
+        acc.push(2, &max);
+        assert_eq!(acc.sum(), None);
+        acc.push(1, &parse("1.5"));
+        assert_eq!(acc.sum(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dot_length_mismatch() {
+        let _ = dot_u64(&[1, 2], &[Decimal::ONE]);
+    }
+
+    #[test]
+    fn test_checked_sum_product() {
+        let values = [parse("1.5"), parse("-2.25"), parse("4")];
+        assert_eq!(Decimal::checked_sum(&values[..]), Some(parse("3.25")));
+        assert_eq!(Decimal::checked_sum(values.iter()), Some(parse("3.25")));
+        assert_eq!(Decimal::checked_sum(values.to_vec()), Some(parse("3.25")));
+        assert_eq!(Decimal::checked_product(&values[..]), Some(parse("-13.5")));
+        assert_eq!(Decimal::checked_product(values.to_vec()), Some(parse("-13.5")));
+        assert_eq!(Decimal::checked_sum(Vec::<Decimal>::new()), Some(Decimal::ZERO));
+        assert_eq!(Decimal::checked_product(Vec::<Decimal>::new()), Some(Decimal::ONE));
+        assert_eq!(values.iter().sum::<Decimal>(), parse("3.25"));
+        assert_eq!(values.iter().product::<Decimal>(), parse("-13.5"));
+
+        // short-circuits at the first overflow
+        let mut count = 0;
+        let values = [Decimal::MAX, Decimal::MAX, Decimal::MIN, Decimal::MIN];
+        let sum = Decimal::checked_sum(values.iter().inspect(|_| count += 1));
+        assert_eq!((sum, count), (None, 2));
+
+        let mut count = 0;
+        let values = [parse("1e100"), parse("1e100"), parse("1e-100"), parse("1e-100")];
+        let product = Decimal::checked_product(values.iter().inspect(|_| count += 1));
+        assert_eq!((product, count), (None, 2));
+    }
+
+    #[test]
+    fn test_sum_exact() {
+        fn assert_sum_exact(values: &[&str], expected: Option<&str>) {
+            let values = values.iter().map(|s| parse(s)).collect::<Vec<_>>();
+            let sum = Decimal::sum_exact(&values);
+            assert_eq!(sum, expected.map(parse), "{:?}", values);
+            if let (Some(sum), Some(expected)) = (sum, expected) {
+                assert_eq!(sum.scale(), parse(expected).scale(), "{:?}", values);
+            }
+        }
+
+        assert_sum_exact(&[], Some("0"));
+        assert_sum_exact(&["0", "0"], Some("0"));
+        assert_sum_exact(&["1.5", "-2.25", "4"], Some("3.25"));
+        assert_sum_exact(&["0.1", "0.25", "1e-20", "-3"], Some("-2.64999999999999999999"));
+        assert_sum_exact(&["1e37", "0.4", "0.4", "-1e37"], Some("0.8"));
+        let values = [
+            parse("99999999999999999999999999999999999999"),
+            parse("0.5"),
+            parse("0.5"),
+        ];
+        assert_eq!(
+            Decimal::sum_exact(values.iter()).unwrap().into_parts(),
+            (10_u128.pow(37), -1, false)
+        );
+        assert_sum_exact(
+            &[
+                "0.3333333333333333333333333333333333333",
+                "0.3333333333333333333333333333333333333",
+                "1",
+            ],
+            Some("1.6666666666666666666666666666666666666"),
+        );
+        assert_sum_exact(&["9e125", "9e125"], None);
+        assert_sum_exact(&["9e125", "9e125", "-9e125"], Some("9e125"));
+        assert_sum_exact(&["-9e125", "-1e125"], None);
+
+        // the scales are too far apart for 256 bits, so the tiny value is rounded off
+        assert_sum_exact(&["1e100", "1e-100", "-1e100"], Some("0"));
+        assert_sum_exact(&["1e-100", "1e100", "1e-100"], Some("1e100"));
+
+        let tenth = parse("0.1");
+        let sum = Decimal::sum_exact((0..10_000_000).map(|_| tenth)).unwrap();
+        assert_eq!(sum, parse("1000000"));
+        assert_eq!(sum.to_string(), "1000000");
+
+        let cent = parse("0.01");
+        let sum = Decimal::sum_exact((0..1_000_000).map(|_| &cent)).unwrap();
+        assert_eq!(sum, parse("10000"));
+    }
+
+    fn push_all<'a, I: IntoIterator<Item = &'a Decimal>>(values: I) -> (DecimalSum, DecimalAvg) {
+        let mut sum = DecimalSum::new();
+        let mut avg = DecimalAvg::new();
+        for val in values {
+            sum.push(val);
+            avg.push(val);
+        }
+        (sum, avg)
+    }
+
+    #[test]
+    fn test_decimal_sum() {
+        fn assert_sum(values: &[&str], expected: Option<&str>) {
+            let values = values.iter().map(|s| parse(s)).collect::<Vec<_>>();
+            let expected = expected.map(parse);
+            let (sum, _) = push_all(values.iter());
+            assert_eq!(sum.finish(), expected, "{:?}", values);
+
+            // partial sums are merged into the same sum
+            for i in 0..=values.len() {
+                let (mut left, _) = push_all(values[..i].iter());
+                let (right, _) = push_all(values[i..].iter());
+                left.merge(&right);
+                assert_eq!(left.finish(), expected, "{:?} at {}", values, i);
+            }
+        }
+
+        assert_sum(&[], Some("0"));
+        assert_sum(&["0", "0.000"], Some("0"));
+        assert_sum(&["1e30", "1", "-1e30"], Some("1"));
+        assert_sum(&["-1e30", "1", "1e30"], Some("1"));
+        assert_sum(&["1e37", "0.4", "0.4", "-1e37"], Some("0.8"));
+        assert_sum(&["1e37", "0.4", "0.4", "-1e37", "-0.8"], Some("0"));
+        assert_sum(
+            &["1.5", "-2.25", "4", "1e-30"],
+            Some("3.250000000000000000000000000001"),
+        );
+        assert_sum(&["9e125", "9e125"], None);
+        assert_sum(&["9e125", "9e125", "-9e125"], Some("9e125"));
+        assert_sum(&["1e125", "1e-130", "-1e125"], Some("0"));
+        assert_sum(&["1e-100", "1e100", "1e-100"], Some("1e100"));
+        assert_sum(&["1e100", "1e-100", "1e100", "9e125", "9e125"], None);
+
+        let mut sum = DecimalSum::default();
+        assert_eq!(sum.finish(), Some(Decimal::ZERO));
+        sum.push(&Decimal::MAX);
+        sum.push(&Decimal::MAX);
+        assert_eq!(sum.finish(), None);
+        sum.push(&Decimal::MIN);
+        assert_eq!(sum.finish(), Some(Decimal::MAX));
+    }
+
+    #[test]
+    fn test_decimal_avg() {
+        fn assert_avg(values: &[&str], expected: Option<&str>) {
+            let values = values.iter().map(|s| parse(s)).collect::<Vec<_>>();
+            let expected = expected.map(parse);
+            let (_, avg) = push_all(values.iter());
+            assert_eq!(avg.finish(), expected, "{:?}", values);
+            assert_eq!(avg.count(), values.len() as u64);
+
+            for i in 0..=values.len() {
+                let (_, mut left) = push_all(values[..i].iter());
+                let (_, right) = push_all(values[i..].iter());
+                left.merge(&right);
+                assert_eq!(left.finish(), expected, "{:?} at {}", values, i);
+                assert_eq!(left.count(), values.len() as u64);
+            }
+        }
+
+        assert_avg(&[], None);
+        assert_avg(&["0", "0"], Some("0"));
+        assert_avg(&["1", "2", "2"], Some("1.6666666666666666666666666666666666667"));
+        assert_avg(&["1", "0", "0"], Some("0.33333333333333333333333333333333333333"));
+        assert_avg(&["-1", "0", "0"], Some("-0.33333333333333333333333333333333333333"));
+        assert_avg(&["1e30", "3", "-1e30"], Some("1"));
+        assert_avg(&["1.5", "2.5"], Some("2"));
+        // a magnitude below `1e-130` is zero, as in the division
+        assert_eq!(parse("1e-130").checked_div(Decimal::from(2)), Some(Decimal::ZERO));
+        assert_avg(&["1e-130", "0"], Some("0"));
+        assert_avg(&["3e-130", "0"], Some("1.5e-130"));
+        assert_avg(&["0.1", "0.25", "1e-20"], Some("0.11666666666666666667"));
+        assert_avg(&["2e-130", "2e-130", "0", "0"], Some("1e-130"));
+
+        // the sum overflows a decimal, but not the mean
+        let values = [Decimal::MAX, Decimal::MAX, Decimal::MAX];
+        let (sum, avg) = push_all(values.iter());
+        assert_eq!(sum.finish(), None);
+        assert_eq!(avg.finish(), Some(Decimal::MAX));
+
+        // the scales are too far apart for 256 bits
+        let values = [parse("1e100"), parse("1e-100"), parse("2e100")];
+        let (_, avg) = push_all(values.iter());
+        assert_eq!(avg.finish(), Some(parse("1e100")));
+
+        let tenth = parse("0.1");
+        let mut avg = DecimalAvg::default();
+        for _ in 0..1_000_000 {
+            avg.push(&tenth);
+        }
+        assert_eq!(avg.finish(), Some(tenth));
+    }
+
+    #[test]
+    fn test_accumulators_are_send() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<DecimalSum>();
+        assert_send_sync::<DecimalAvg>();
+        assert_send_sync::<NotionalAccumulator>();
+    }
+
+    #[test]
+    fn test_quantile() {
+        fn assert_quantile(values: &[&str], q: &str, expected: Option<&str>) {
+            let mut values: Vec<Decimal> = values.iter().map(|v| parse(v)).collect();
+            assert_eq!(quantile(&mut values, &parse(q)), expected.map(parse), "{}", q);
+        }
+
+        let data = ["4", "1", "3", "2"];
+        assert_quantile(&data, "0", Some("1"));
+        assert_quantile(&data, "0.25", Some("1.75"));
+        assert_quantile(&data, "0.5", Some("2.5"));
+        assert_quantile(&data, "0.75", Some("3.25"));
+        assert_quantile(&data, "1", Some("4"));
+        assert_quantile(&data, "0.99", Some("3.97"));
+        assert_quantile(&["3", "1", "2"], "0.5", Some("2"));
+        assert_quantile(&["5"], "0.3", Some("5"));
+        assert_quantile(&["-2.5", "7", "-2.5", "-2.5"], "0.5", Some("-2.5"));
+        assert_quantile(&["1", "1", "2", "2"], "0.5", Some("1.5"));
+        assert_quantile(&["0.1", "0.2", "0.3"], "0.1", Some("0.12"));
+        assert_quantile(
+            &["1", "2"],
+            "0.3333333333333333333333333333333333333",
+            Some("1.3333333333333333333333333333333333333"),
+        );
+
+        // extreme values
+        let max = "9.9999999999999999999999999999999999999E125";
+        let min = "-9.9999999999999999999999999999999999999E125";
+        assert_quantile(&[max, min], "0.5", Some("0"));
+        // the exact result has 39 digits, and both products are rounded
+        assert_quantile(
+            &[max, min],
+            "0.25",
+            Some("-4.9999999999999999999999999999999999999E125"),
+        );
+        assert_quantile(&[max, max], "0.5", Some(max));
+        assert_quantile(
+            &[max, "1E-130"],
+            "0.5",
+            Some("4.99999999999999999999999999999999999995E125"),
+        );
+
+        // invalid input
+        assert_quantile(&[], "0.5", None);
+        assert_quantile(&data, "-0.1", None);
+        assert_quantile(&data, "1.0000000000000000000000000000000000001", None);
+    }
+
+    #[test]
+    fn test_quantile_reference() {
+        fn reference(values: &[Decimal], q: &Decimal) -> Decimal {
+            let mut sorted = values.to_vec();
+            sorted.sort();
+            let h = *q * Decimal::from(sorted.len() - 1);
+            let index = u64::try_from(h.floor()).unwrap() as usize;
+            let f = h - h.floor();
+            if f.is_zero() {
+                sorted[index]
+            } else {
+                sorted[index] + f * (sorted[index + 1] - sorted[index])
+            }
+        }
+
+        let mut rng = Lcg::new(0x0bad_cafe_dead_beefu64);
+
+        for _ in 0..500 {
+            let n = (rng.next() % 50) as usize + 1;
+            let mut values: Vec<Decimal> = (0..n)
+                .map(|_| {
+                    // few distinct values, so that there are ties
+                    let int_val = (rng.next() % 20) as u128 * (rng.next() % 1_000_000_000) as u128;
+                    Decimal::from_parts(int_val, (rng.next() % 10) as i16, rng.next() % 2 == 1).unwrap()
+                })
+                .collect();
+            let q = Decimal::from_parts((rng.next() % 10001) as u128, 4, false).unwrap();
+
+            let min = *values.iter().min().unwrap();
+            let max = *values.iter().max().unwrap();
+            let expected = reference(&values, &q);
+
+            let result = quantile(&mut values, &q).unwrap();
+            assert_eq!(result, expected);
+            assert!(result >= min && result <= max);
+        }
+    }
+}
@@ -0,0 +1,289 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Proportional allocation with the largest remainder method.
+
+use crate::convert::MAX_I128_REPR;
+use crate::decimal::{Decimal, MAX_PRECISION, MAX_SCALE, MIN_SCALE};
+use crate::u256::{POWERS_10, U256};
+use alloc::vec::Vec;
+
+impl Decimal {
+    /// Returns `self * 10^scale` as an integer, i.e. the count of units at `scale`,
+    /// returning `None` if `self` can't be represented exactly at `scale`.
+    #[inline]
+    fn units_at_scale(&self, scale: i16) -> Option<u128> {
+        if !(MIN_SCALE..=MAX_SCALE).contains(&scale) {
+            return None;
+        }
+
+        if self.is_zero() {
+            return Some(0);
+        }
+
+        let e = scale as i32 - self.scale() as i32;
+        if e >= 0 {
+            if e > MAX_PRECISION as i32 {
+                return None;
+            }
+            let units = self.int_val().checked_mul(POWERS_10[e as usize].low())?;
+            if units > MAX_I128_REPR as u128 {
+                return None;
+            }
+            Some(units)
+        } else {
+            let divisor = *POWERS_10.get(-e as usize)?;
+            let (units, rem) = U256::from(self.int_val()).div_rem(divisor);
+            if rem != 0 {
+                return None;
+            }
+            Some(units.low())
+        }
+    }
+
+    /// Builds the shares from the counts of units at `scale`.
+    #[inline]
+    fn shares_from_units(&self, units: Vec<u128>, scale: i16) -> Option<Vec<Decimal>> {
+        units
+            .into_iter()
+            .map(|u| Decimal::from_parts(u, scale, self.is_sign_negative()).ok())
+            .collect()
+    }
+
+    /// Allocates `self` proportionally to `weights` with the largest remainder method, returning
+    /// `None` if `weights` is empty, a weight is negative, the weights sum to zero, `self`
+    /// can't be represented exactly at `scale`, or the weights span too many digits to be
+    /// computed exactly.
+    ///
+    /// Each share is first rounded toward zero to `scale`, then the leftover units of `10^-scale`
+    /// are given one by one to the shares with the largest remainders, ties broken by index.
+    /// So the shares sum exactly to `self`, and each share differs from its exact proportion by less
+    /// than one unit at `scale`.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let total: Decimal = "100".parse().unwrap();
+    /// let weights: Vec<Decimal> = ["1", "1", "1"].iter().map(|s| s.parse().unwrap()).collect();
+    /// let shares = total.allocate(&weights, 2).unwrap();
+    /// assert_eq!(shares, ["33.34", "33.33", "33.33"].iter().map(|s| s.parse().unwrap()).collect::<Vec<Decimal>>());
+    /// ```
+    pub fn allocate(&self, weights: &[Decimal], scale: i16) -> Option<Vec<Decimal>> {
+        if weights.is_empty() || weights.iter().any(|w| w.is_sign_negative()) {
+            return None;
+        }
+
+        let total = self.units_at_scale(scale)?;
+
+        // Brings the weights to a common scale, so that they are integers.
+        let weights: Vec<Decimal> = weights.iter().map(|w| w.normalize()).collect();
+        let common_scale = weights.iter().filter(|w| !w.is_zero()).map(|w| w.scale()).max()?;
+        let mut int_weights = Vec::with_capacity(weights.len());
+        let mut sum = U256::ZERO;
+        for w in weights.iter() {
+            let int_weight = if w.is_zero() {
+                U256::ZERO
+            } else {
+                let e = (common_scale - w.scale()) as usize;
+                POWERS_10.get(e)?.checked_mul(w.int_val())?
+            };
+            sum = sum.checked_add(int_weight)?;
+            int_weights.push(int_weight);
+        }
+
+        let mut units = Vec::with_capacity(weights.len());
+        let mut remainders = Vec::with_capacity(weights.len());
+        let mut allocated = 0u128;
+        for int_weight in int_weights {
+            // total * weight / sum <= total, so the quotient fits in u128
+            let (quotient, rem) = int_weight.checked_mul(total)?.div_rem(sum);
+            allocated += quotient.low();
+            units.push(quotient.low());
+            remainders.push(rem);
+        }
+
+        // The leftover is less than the count of weights.
+        let leftover = (total - allocated) as usize;
+        if leftover > 0 {
+            let mut indexes: Vec<usize> = (0..units.len()).collect();
+            indexes.sort_by(|&a, &b| remainders[b].cmp(&remainders[a]).then(a.cmp(&b)));
+            for &i in indexes[..leftover].iter() {
+                units[i] += 1;
+            }
+        }
+
+        self.shares_from_units(units, scale)
+    }
+
+    /// Splits `self` into `n` shares as even as possible, returning `None` if `n` is zero
+    /// or `self` can't be represented exactly at `scale`.
+    ///
+    /// This is the same as [`Decimal::allocate`] with `n` equal weights: the first shares are
+    /// one unit of `10^-scale` larger than the others.
+    pub fn split_evenly(&self, n: usize, scale: i16) -> Option<Vec<Decimal>> {
+        if n == 0 {
+            return None;
+        }
+
+        let total = self.units_at_scale(scale)?;
+        let quotient = total / n as u128;
+        let leftover = (total % n as u128) as usize;
+        let units = (0..n)
+            .map(|i| if i < leftover { quotient + 1 } else { quotient })
+            .collect();
+
+        self.shares_from_units(units, scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lcg;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn parse_all(values: &[&str]) -> Vec<Decimal> {
+        values.iter().map(|v| parse(v)).collect()
+    }
+
+    fn assert_sum(total: &Decimal, shares: &[Decimal]) {
+        let sum = shares.iter().fold(Decimal::ZERO, |sum, s| sum.checked_add(s).unwrap());
+        assert_eq!(sum, *total);
+    }
+
+    #[test]
+    fn test_allocate() {
+        fn assert_allocate(total: &str, weights: &[&str], scale: i16, expected: Option<&[&str]>) {
+            let total = parse(total);
+            let shares = total.allocate(&parse_all(weights), scale);
+            assert_eq!(shares, expected.map(parse_all), "{} {:?}", total, weights);
+            if let Some(shares) = shares {
+                assert_sum(&total, &shares);
+            }
+        }
+
+        assert_allocate("100.01", &["1", "1", "1"], 2, Some(&["33.34", "33.34", "33.33"]));
+        assert_allocate("-100.01", &["1", "1", "1"], 2, Some(&["-33.34", "-33.34", "-33.33"]));
+        assert_allocate("100", &["0.5", "0.3", "0.2"], 2, Some(&["50", "30", "20"]));
+        assert_allocate("10", &["3", "3", "3"], 0, Some(&["4", "3", "3"]));
+        assert_allocate(
+            "0.05",
+            &["1", "2", "3", "4"],
+            2,
+            Some(&["0.01", "0.01", "0.01", "0.02"]),
+        );
+        assert_allocate("100", &["1", "0", "2"], 1, Some(&["33.3", "0", "66.7"]));
+        assert_allocate("1", &["1E-10", "3E+10"], 2, Some(&["0", "1"]));
+        assert_allocate("7", &["42"], 2, Some(&["7"]));
+        assert_allocate("0", &["1", "2"], 2, Some(&["0", "0"]));
+        assert_allocate("1500", &["1"], -2, Some(&["1500"]));
+
+        // the largest remainders win, ties are broken by index
+        assert_allocate(
+            "1",
+            &["1"; 7],
+            1,
+            Some(&["0.2", "0.2", "0.2", "0.1", "0.1", "0.1", "0.1"]),
+        );
+        assert_allocate(
+            "0.1",
+            &["2", "1", "2", "1", "2", "1"],
+            2,
+            Some(&["0.03", "0.01", "0.02", "0.01", "0.02", "0.01"]),
+        );
+
+        // invalid input
+        assert_allocate("100", &[], 2, None);
+        assert_allocate("100", &["0", "0"], 2, None);
+        assert_allocate("100", &["1", "-1", "1"], 2, None);
+        assert_allocate("100.001", &["1", "1"], 2, None);
+        assert_allocate("100", &["1", "1"], 131, None);
+        assert_allocate("1", &["1E-100", "3E+100"], 2, None);
+    }
+
+    #[test]
+    fn test_allocate_extreme() {
+        // a total near the precision limit
+        let total = parse("9999999999999999999999999999999999.9999");
+        let weights = parse_all(&["1", "2", "3", "5", "7", "11", "13"]);
+        let shares = total.allocate(&weights, 4).unwrap();
+        assert_sum(&total, &shares);
+
+        // 38 digits at `scale` is the limit
+        assert!(parse("99999999999999999999999999999999999999")
+            .allocate(&weights, 0)
+            .is_some());
+        assert!(parse("99999999999999999999999999999999999999")
+            .allocate(&weights, 1)
+            .is_none());
+        assert!(parse("1E+125").allocate(&weights, -125).is_some());
+
+        // weights of very different scales
+        let weights = parse_all(&["99999999999999999999", "1E-18"]);
+        let shares = total.allocate(&weights, 4).unwrap();
+        assert_sum(&total, &shares);
+        assert_eq!(shares[1], parse("0.0001"));
+        let weights = parse_all(&["99999999999999999999999999999999999999", "1E-38"]);
+        assert!(total.allocate(&weights, 4).is_none());
+        let weights = parse_all(&["9.9999999999999999999999999999999999999E125", "1E-130"]);
+        assert!(total.allocate(&weights, 4).is_none());
+    }
+
+    #[test]
+    fn test_allocate_proportion() {
+        let mut rng = Lcg::new(0x5eed_1234_abcd_0001u64);
+
+        for _ in 0..200 {
+            let total = Decimal::from_parts((rng.next() % 10_000_000_000) as u128, 2, rng.next() % 2 == 1).unwrap();
+            let n = (rng.next() % 10) as usize + 1;
+            let weights: Vec<Decimal> = (0..n)
+                .map(|_| Decimal::from_parts((rng.next() % 1000) as u128 + 1, (rng.next() % 4) as i16, false).unwrap())
+                .collect();
+            let shares = total.allocate(&weights, 2).unwrap();
+            assert_sum(&total, &shares);
+
+            // each share is within one unit of its exact proportion
+            let sum = weights.iter().fold(Decimal::ZERO, |sum, w| sum + w);
+            let unit = parse("0.01");
+            for (share, weight) in shares.iter().zip(weights.iter()) {
+                let exact = total * weight / sum;
+                assert!((*share - exact).abs() < unit, "{} {} {}", share, exact, total);
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_evenly() {
+        fn assert_split(total: &str, n: usize, scale: i16, expected: Option<&[&str]>) {
+            let total = parse(total);
+            let shares = total.split_evenly(n, scale);
+            assert_eq!(shares, expected.map(parse_all));
+            if let Some(shares) = shares {
+                assert_sum(&total, &shares);
+                assert_eq!(Some(shares), total.allocate(&vec![Decimal::ONE; n], scale));
+            }
+        }
+
+        assert_split("100.01", 3, 2, Some(&["33.34", "33.34", "33.33"]));
+        assert_split("-0.05", 3, 2, Some(&["-0.02", "-0.02", "-0.01"]));
+        assert_split("1", 1, 0, Some(&["1"]));
+        assert_split("0.03", 5, 2, Some(&["0.01", "0.01", "0.01", "0", "0"]));
+        assert_split("0", 2, 2, Some(&["0", "0"]));
+        assert_split("100", 0, 2, None);
+        assert_split("100.001", 2, 2, None);
+    }
+}
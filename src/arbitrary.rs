@@ -0,0 +1,160 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! arbitrary and proptest implementation.
+
+use crate::convert::MAX_I128_REPR;
+use crate::decimal::{Decimal, MAX_PRECISION, MAX_SCALE, MIN_SCALE};
+
+/// Builds a valid decimal from raw random parts.
+///
+/// Three in eight decimals are at a boundary: zero, `MAX`, `MIN`, `±MIN_POSITIVE`, a power of ten
+/// or the largest mantissa with any scale. The others have a uniform mantissa and scale.
+#[inline]
+fn generate(kind: u8, int_val: u128, scale: u16, negative: bool) -> Decimal {
+    const SCALES: u16 = (MAX_SCALE - MIN_SCALE + 1) as u16;
+
+    let scale = MIN_SCALE + (scale % SCALES) as i16;
+    let (int_val, scale) = match kind % 16 {
+        0 => return Decimal::ZERO,
+        1 => return Decimal::MAX,
+        2 => return Decimal::MIN,
+        3 => (1, MAX_SCALE),
+        4 => (10_u128.pow((int_val % MAX_PRECISION as u128) as u32), scale),
+        5 => (MAX_I128_REPR as u128, scale),
+        _ => (int_val % (MAX_I128_REPR as u128 + 1), scale),
+    };
+
+    // Safety: `int_val` has at most 38 digits and `scale` is in `[MIN_SCALE, MAX_SCALE]`.
+    unsafe { Decimal::from_parts_unchecked(int_val, scale, negative) }
+}
+
+#[cfg(feature = "arbitrary")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arbitrary")))]
+impl<'a> arbitrary::Arbitrary<'a> for Decimal {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let kind = u8::arbitrary(u)?;
+        let int_val = u128::arbitrary(u)?;
+        let scale = u16::arbitrary(u)?;
+        let negative = bool::arbitrary(u)?;
+        Ok(generate(kind, int_val, scale, negative))
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <(u8, u128, u16, bool) as arbitrary::Arbitrary>::size_hint(depth)
+    }
+}
+
+#[cfg(feature = "proptest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+impl proptest::arbitrary::Arbitrary for Decimal {
+    type Parameters = ();
+    type Strategy = proptest::strategy::Map<
+        proptest::arbitrary::StrategyFor<(u8, u128, u16, bool)>,
+        fn((u8, u128, u16, bool)) -> Decimal,
+    >;
+
+    #[inline]
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+
+        proptest::arbitrary::any::<(u8, u128, u16, bool)>()
+            .prop_map(|(kind, int_val, scale, negative)| generate(kind, int_val, scale, negative))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_valid(values: &[Decimal]) {
+        for &val in values.iter() {
+            let (int_val, scale, negative) = val.into_parts();
+            assert!(int_val <= MAX_I128_REPR as u128, "{:?}", val);
+            assert!((MIN_SCALE..=MAX_SCALE).contains(&scale), "{:?}", val);
+            let dec = Decimal::from_parts(int_val, scale, negative).unwrap();
+            assert_eq!(dec, val);
+            assert_eq!(dec.into_parts(), (int_val, scale, negative));
+        }
+
+        // the boundaries are generated
+        for boundary in [Decimal::ZERO, Decimal::MAX, Decimal::MIN, Decimal::MIN_POSITIVE].iter() {
+            assert!(values.contains(boundary), "{}", boundary);
+        }
+        assert!(values
+            .iter()
+            .any(|v| v.into_parts().0 == 10_u128.pow(MAX_PRECISION - 1)));
+        assert!(values.iter().any(|v| v.scale() < 0) && values.iter().any(|v| v.scale() > 38));
+        assert!(values.iter().any(|v| v.is_sign_negative()) && values.iter().any(|v| v.is_sign_positive()));
+    }
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(0, 123, 456, true), Decimal::ZERO);
+        assert_eq!(generate(1, 0, 0, false), Decimal::MAX);
+        assert_eq!(generate(2, 0, 0, false), Decimal::MIN);
+        assert_eq!(generate(3, 0, 0, false), Decimal::MIN_POSITIVE);
+        assert_eq!(generate(3, 0, 0, true), -Decimal::MIN_POSITIVE);
+        assert_eq!(generate(4, 40, 126, false).into_parts(), (100, 0, false));
+        assert_eq!(
+            generate(5, 0, 0, true).into_parts(),
+            (MAX_I128_REPR as u128, MIN_SCALE, true)
+        );
+        assert_eq!(generate(6, u128::MAX, u16::MAX, false).into_parts().1, MIN_SCALE);
+        assert_eq!(generate(16, 0, 0, false), Decimal::ZERO);
+        assert_eq!(generate(255, 0, 256, false), Decimal::ZERO);
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let bytes = (0..10000 * 20)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 32) as u8
+            })
+            .collect::<Vec<_>>();
+
+        let mut u = Unstructured::new(&bytes);
+        let values = (0..10000)
+            .map(|_| Decimal::arbitrary(&mut u).unwrap())
+            .collect::<Vec<_>>();
+        assert_valid(&values);
+
+        // runs out of data
+        let mut u = Unstructured::new(&[]);
+        assert_eq!(Decimal::arbitrary(&mut u).unwrap(), Decimal::ZERO);
+    }
+
+    #[cfg(feature = "proptest")]
+    #[test]
+    fn test_proptest() {
+        use proptest::strategy::{Strategy, ValueTree};
+        use proptest::test_runner::TestRunner;
+
+        let strategy = proptest::arbitrary::any::<Decimal>();
+        let mut runner = TestRunner::deterministic();
+        let values = (0..10000)
+            .map(|_| strategy.new_tree(&mut runner).unwrap().current())
+            .collect::<Vec<_>>();
+        assert_valid(&values);
+    }
+}
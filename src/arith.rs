@@ -0,0 +1,341 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Arithmetic behind trait objects.
+//!
+//! [`DecimalOps`] is object-safe, so an evaluator can call the arithmetic through a
+//! `dyn DecimalOps` and leave the policy, e.g. the rounding mode, to the caller.
+//! [`with_ops`] injects an implementation for the duration of a closure on the current thread,
+//! and [`current_ops`] returns it, or [`DefaultOps`] outside of such a scope. Both need the `std`
+//! feature for the thread-local scope:
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # {
+//! use decimal_rs::arith::{self, DecimalOps};
+//! use decimal_rs::{Decimal, DecimalContext, RoundingMode};
+//! use std::rc::Rc;
+//!
+//! let eval = || {
+//!     let ops = arith::current_ops();
+//!     ops.div(&Decimal::from(-1), &Decimal::from(3))
+//! };
+//!
+//! let default = eval().unwrap();
+//! let floor = arith::with_ops(Rc::new(DecimalContext::new(RoundingMode::Floor)), eval).unwrap();
+//! assert_eq!(default.to_string(), "-0.33333333333333333333333333333333333333");
+//! assert_eq!(floor.to_string(), "-0.33333333333333333333333333333333333334");
+//! # }
+//! ```
+
+use crate::context::{DecimalContext, NumericContext};
+use crate::decimal::Decimal;
+use crate::error::DecimalArithError;
+#[cfg(feature = "std")]
+use alloc::rc::Rc;
+#[cfg(feature = "std")]
+use core::cell::RefCell;
+
+/// Arithmetic operations on decimals, callable through a trait object.
+pub trait DecimalOps {
+    /// Computes `a + b`.
+    fn add(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError>;
+
+    /// Computes `a - b`.
+    fn sub(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError>;
+
+    /// Computes `a * b`.
+    fn mul(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError>;
+
+    /// Computes `a / b`.
+    fn div(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError>;
+}
+
+/// The arithmetic of the checked methods of [`Decimal`], e.g. [`Decimal::checked_add`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DefaultOps;
+
+impl DecimalOps for DefaultOps {
+    #[inline]
+    fn add(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+        a.checked_add(b).ok_or(DecimalArithError::Overflow)
+    }
+
+    #[inline]
+    fn sub(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+        a.checked_sub(b).ok_or(DecimalArithError::Overflow)
+    }
+
+    #[inline]
+    fn mul(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+        a.checked_mul(b).ok_or(DecimalArithError::Overflow)
+    }
+
+    #[inline]
+    fn div(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+        if b.is_zero() {
+            return Err(DecimalArithError::DivisionByZero);
+        }
+        a.checked_div(b).ok_or(DecimalArithError::Overflow)
+    }
+}
+
+/// Rounds every result with the rounding mode of the context.
+impl DecimalOps for DecimalContext {
+    #[inline]
+    fn add(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+        DecimalContext::add(self, a, b).ok_or(DecimalArithError::Overflow)
+    }
+
+    #[inline]
+    fn sub(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+        DecimalContext::sub(self, a, b).ok_or(DecimalArithError::Overflow)
+    }
+
+    #[inline]
+    fn mul(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+        DecimalContext::mul(self, a, b).ok_or(DecimalArithError::Overflow)
+    }
+
+    #[inline]
+    fn div(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+        if b.is_zero() {
+            return Err(DecimalArithError::DivisionByZero);
+        }
+        DecimalContext::div(self, a, b).ok_or(DecimalArithError::Overflow)
+    }
+}
+
+/// Rounds every result to the precision and scale of the context.
+impl DecimalOps for NumericContext {
+    #[inline]
+    fn add(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+        NumericContext::add(self, a, b).map_err(|_| DecimalArithError::Overflow)
+    }
+
+    #[inline]
+    fn sub(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+        NumericContext::sub(self, a, b).map_err(|_| DecimalArithError::Overflow)
+    }
+
+    #[inline]
+    fn mul(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+        NumericContext::mul(self, a, b).map_err(|_| DecimalArithError::Overflow)
+    }
+
+    #[inline]
+    fn div(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+        if b.is_zero() {
+            return Err(DecimalArithError::DivisionByZero);
+        }
+        NumericContext::div(self, a, b).map_err(|_| DecimalArithError::Overflow)
+    }
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+    static CURRENT_OPS: RefCell<Option<Rc<dyn DecimalOps>>> = RefCell::new(None);
+}
+
+/// Restores the previous operations when a scope of [`with_ops`] ends, even by a panic.
+#[cfg(feature = "std")]
+struct Restore(Option<Rc<dyn DecimalOps>>);
+
+#[cfg(feature = "std")]
+impl Drop for Restore {
+    #[inline]
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT_OPS.with(|ops| *ops.borrow_mut() = previous);
+    }
+}
+
+/// Calls `f` with `ops` as the current operations of this thread, restoring the previous ones afterwards.
+///
+/// Scopes can be nested, and the innermost one wins.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[inline]
+pub fn with_ops<R, F: FnOnce() -> R>(ops: Rc<dyn DecimalOps>, f: F) -> R {
+    let previous = CURRENT_OPS.with(|current| current.borrow_mut().replace(ops));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Returns the current operations of this thread, i.e. the ones of the innermost [`with_ops`]
+/// scope, or [`DefaultOps`] outside of any scope.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[inline]
+pub fn current_ops() -> Rc<dyn DecimalOps> {
+    CURRENT_OPS
+        .with(|current| current.borrow().clone())
+        .unwrap_or_else(|| Rc::new(DefaultOps))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::decimal::RoundingMode;
+    use std::cell::Cell;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    /// A small expression tree evaluated with the current operations.
+    enum Expr {
+        Lit(Decimal),
+        Add(Box<Expr>, Box<Expr>),
+        Sub(Box<Expr>, Box<Expr>),
+        Mul(Box<Expr>, Box<Expr>),
+        Div(Box<Expr>, Box<Expr>),
+    }
+
+    impl Expr {
+        fn lit(s: &str) -> Box<Expr> {
+            Box::new(Expr::Lit(parse(s)))
+        }
+
+        fn eval(&self) -> Result<Decimal, DecimalArithError> {
+            self.eval_with(&*current_ops())
+        }
+
+        fn eval_with(&self, ops: &dyn DecimalOps) -> Result<Decimal, DecimalArithError> {
+            match self {
+                Expr::Lit(val) => Ok(*val),
+                Expr::Add(a, b) => ops.add(&a.eval_with(ops)?, &b.eval_with(ops)?),
+                Expr::Sub(a, b) => ops.sub(&a.eval_with(ops)?, &b.eval_with(ops)?),
+                Expr::Mul(a, b) => ops.mul(&a.eval_with(ops)?, &b.eval_with(ops)?),
+                Expr::Div(a, b) => ops.div(&a.eval_with(ops)?, &b.eval_with(ops)?),
+            }
+        }
+    }
+
+    /// Counts the operations and delegates them to `DefaultOps`.
+    #[derive(Default)]
+    struct CountingOps {
+        count: Cell<usize>,
+    }
+
+    impl CountingOps {
+        fn tick(&self) -> DefaultOps {
+            self.count.set(self.count.get() + 1);
+            DefaultOps
+        }
+    }
+
+    impl DecimalOps for CountingOps {
+        fn add(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+            self.tick().add(a, b)
+        }
+
+        fn sub(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+            self.tick().sub(a, b)
+        }
+
+        fn mul(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+            self.tick().mul(a, b)
+        }
+
+        fn div(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalArithError> {
+            self.tick().div(a, b)
+        }
+    }
+
+    #[test]
+    fn test_default_ops() {
+        let ops = DefaultOps;
+        assert_eq!(ops.add(&parse("1.5"), &parse("2.25")), Ok(parse("3.75")));
+        assert_eq!(ops.sub(&parse("1.5"), &parse("2.25")), Ok(parse("-0.75")));
+        assert_eq!(ops.mul(&parse("1.5"), &parse("2.25")), Ok(parse("3.375")));
+        assert_eq!(ops.div(&parse("1.5"), &parse("2.5")), Ok(parse("0.6")));
+        assert_eq!(
+            ops.div(&Decimal::ONE, &Decimal::ZERO),
+            Err(DecimalArithError::DivisionByZero)
+        );
+        assert_eq!(
+            ops.mul(&parse("1E100"), &parse("1E100")),
+            Err(DecimalArithError::Overflow)
+        );
+        assert_eq!(
+            ops.div(&parse("1E100"), &parse("1E-100")),
+            Err(DecimalArithError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_expression_tree() {
+        // (99999999999999999999999999999999999997 / 2 - 1) * 2, where the division is a tie
+        let tree = Expr::Mul(
+            Box::new(Expr::Sub(
+                Box::new(Expr::Div(
+                    Expr::lit("99999999999999999999999999999999999997"),
+                    Expr::lit("2"),
+                )),
+                Expr::lit("1"),
+            )),
+            Expr::lit("2"),
+        );
+
+        let half_up = parse("99999999999999999999999999999999999996");
+        let half_even = parse("99999999999999999999999999999999999994");
+        assert_eq!(tree.eval(), Ok(half_up));
+        assert_eq!(tree.eval_with(&DefaultOps), Ok(half_up));
+
+        let half_even_ops: Rc<dyn DecimalOps> = Rc::new(DecimalContext::new(RoundingMode::HalfEven));
+        assert_eq!(with_ops(half_even_ops.clone(), || tree.eval()), Ok(half_even));
+        assert_eq!(tree.eval_with(&*half_even_ops), Ok(half_even));
+
+        // the scope is restored afterwards
+        assert_eq!(tree.eval(), Ok(half_up));
+
+        let failing = Expr::Div(Expr::lit("1"), Box::new(Expr::Sub(Expr::lit("2"), Expr::lit("2"))));
+        assert_eq!(failing.eval(), Err(DecimalArithError::DivisionByZero));
+        assert_eq!(
+            with_ops(half_even_ops, || failing.eval()),
+            Err(DecimalArithError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_nested_scopes() {
+        let counting = Rc::new(CountingOps::default());
+        let tree = Expr::Add(Expr::lit("1"), Box::new(Expr::Div(Expr::lit("2"), Expr::lit("3"))));
+
+        let result = with_ops(counting.clone(), || {
+            let outer = tree.eval();
+            let inner = with_ops(Rc::new(DecimalContext::new(RoundingMode::Down)), || tree.eval());
+            assert_eq!(inner, Ok(parse("1.6666666666666666666666666666666666666")));
+            assert_eq!(tree.eval(), outer);
+            outer
+        });
+        assert_eq!(result, Ok(parse("1.6666666666666666666666666666666666667")));
+        assert_eq!(counting.count.get(), 4);
+    }
+
+    #[test]
+    fn test_scope_restored_after_panic() {
+        let result = std::panic::catch_unwind(|| {
+            with_ops(Rc::new(DecimalContext::new(RoundingMode::Down)), || {
+                panic!("evaluation failed")
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(
+            current_ops().div(&Decimal::from(2), &Decimal::from(3)),
+            Ok(parse("0.66666666666666666666666666666666666667"))
+        );
+    }
+}
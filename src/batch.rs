@@ -0,0 +1,1592 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Operations over slices of decimals.
+//!
+//! A fallible operation returns the index of the first element which failed, together with the reason.
+
+use crate::convert::MAX_I128_REPR;
+use crate::decimal::{Decimal, RoundingMode, MAX_PRECISION, MAX_SCALE, MIN_SCALE};
+use crate::u256::{POWERS_10, ROUNDINGS, U256};
+use crate::uint::Uint;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+
+/// The reason a batch operation failed at an element.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BatchError {
+    /// The result is too large to be represented.
+    Overflow,
+    /// The divisor is zero.
+    DivisionByZero,
+}
+
+impl fmt::Display for BatchError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            BatchError::Overflow => write!(f, "numeric overflow"),
+            BatchError::DivisionByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+/// The significant digits kept by a running sum.
+const SUM_PRECISION: u32 = MAX_PRECISION * 2;
+
+/// A running sum, which keeps up to 76 significant digits.
+#[derive(Debug, Copy, Clone)]
+struct RunningSum {
+    int_val: U256,
+    scale: i16,
+    negative: bool,
+}
+
+impl RunningSum {
+    const ZERO: RunningSum = RunningSum {
+        int_val: U256::ZERO,
+        scale: 0,
+        negative: false,
+    };
+
+    /// Rescales `int_val` from scale `from` to scale `to`, rounding half away from zero.
+    #[inline]
+    fn rescale(int_val: U256, from: i32, to: i32) -> U256 {
+        if to >= from {
+            int_val * POWERS_10[(to - from) as usize]
+        } else if from - to > SUM_PRECISION as i32 {
+            // `int_val < 10^76`, which rounds to zero
+            U256::ZERO
+        } else {
+            let e = (from - to) as usize;
+            (int_val + ROUNDINGS[e]) / POWERS_10[e]
+        }
+    }
+
+    #[inline]
+    fn add(&mut self, val: &Decimal) {
+        let (val_int_val, val_scale, val_negative) = val.into_parts();
+        self.add_wide(U256::from(val_int_val), val.precision() as u32, val_scale, val_negative);
+    }
+
+    /// Adds `val_int_val * 10^-val_scale`, which has `val_digits` digits, at most 76.
+    #[inline]
+    fn add_wide(&mut self, val_int_val: U256, val_digits: u32, val_scale: i16, val_negative: bool) {
+        if val_int_val == 0 {
+            return;
+        }
+
+        if self.int_val == 0 {
+            *self = RunningSum {
+                int_val: val_int_val,
+                scale: val_scale,
+                negative: val_negative,
+            };
+            return;
+        }
+
+        // The common scale, at which both operands have at most 76 digits.
+        let self_digits = self.int_val.count_digits() as i32;
+        let scale = (self.scale.max(val_scale) as i32)
+            .min(self.scale as i32 + SUM_PRECISION as i32 - self_digits)
+            .min(val_scale as i32 + SUM_PRECISION as i32 - val_digits as i32);
+
+        let lhs = RunningSum::rescale(self.int_val, self.scale as i32, scale);
+        let rhs = RunningSum::rescale(val_int_val, val_scale as i32, scale);
+
+        let (mut int_val, negative) = if self.negative == val_negative {
+            (lhs + rhs, self.negative)
+        } else if lhs >= rhs {
+            (lhs.checked_sub(rhs).expect("lhs >= rhs"), self.negative)
+        } else {
+            (rhs.checked_sub(lhs).expect("rhs > lhs"), val_negative)
+        };
+
+        if int_val == 0 {
+            *self = RunningSum::ZERO;
+            return;
+        }
+
+        // The sum carries into the 77th digit.
+        let mut scale = scale as i16;
+        if int_val >= POWERS_10[SUM_PRECISION as usize] {
+            int_val = (int_val + 5u128) / 10u128;
+            scale -= 1;
+        }
+
+        *self = RunningSum {
+            int_val,
+            scale,
+            negative,
+        };
+    }
+
+    #[inline]
+    fn to_decimal(self) -> Option<Decimal> {
+        Decimal::adjust_scale(self.int_val, self.scale, self.negative)
+    }
+}
+
+/// The significant digits kept by a running product.
+const PRODUCT_PRECISION: u32 = MAX_PRECISION + MAX_PRECISION / 2;
+
+/// A running product, which keeps up to 57 significant digits.
+///
+/// The scale isn't limited, so that an intermediate product which underflows can come back into range.
+#[derive(Debug, Copy, Clone)]
+struct RunningProduct {
+    int_val: U256,
+    scale: i64,
+    negative: bool,
+}
+
+impl RunningProduct {
+    const ONE: RunningProduct = RunningProduct {
+        int_val: U256::ONE,
+        scale: 0,
+        negative: false,
+    };
+
+    #[inline]
+    fn mul(&mut self, val: &Decimal) {
+        if self.int_val == 0 {
+            return;
+        }
+
+        let (val_int_val, val_scale, val_negative) = val.into_parts();
+        if val_int_val == 0 {
+            self.int_val = U256::ZERO;
+            return;
+        }
+
+        // The product has at most `57 + 38` digits, so at most 38 digits are dropped.
+        let digits = self.int_val.count_digits() + val.precision() as u32;
+        let e = digits.saturating_sub(PRODUCT_PRECISION);
+
+        self.int_val = mul_round(self.int_val, val_int_val, e);
+        self.scale += val_scale as i64 - e as i64;
+        self.negative ^= val_negative;
+    }
+
+    #[inline]
+    fn to_decimal(self) -> Option<Decimal> {
+        if self.int_val == 0 {
+            return Some(Decimal::ZERO);
+        }
+
+        let s = self.scale - self.int_val.count_digits() as i64;
+        if s >= MAX_SCALE as i64 {
+            return Some(Decimal::ZERO);
+        }
+        if s < MIN_SCALE as i64 {
+            return None;
+        }
+
+        Decimal::adjust_scale(self.int_val, self.scale as i16, self.negative)
+    }
+}
+
+/// Computes `a * b / 10^e`, rounding half away from zero, where the result has at most 58 digits and `e <= 38`.
+#[inline]
+fn mul_round(a: U256, b: u128, e: u32) -> U256 {
+    debug_assert!(e <= MAX_PRECISION);
+
+    // The product has at most `57 + 38` digits, which fit in 384 bits.
+    let product = Uint::<6>::from_u256(a).checked_mul(b).expect("95 digits at most");
+    let mut rounded = product.add(&Uint::from_u256(ROUNDINGS[e as usize]));
+    rounded.div_pow10(e);
+    rounded.to_u256().expect("58 digits at most")
+}
+
+/// Computes the prefix sums of `values` into `out`, failing at the first prefix sum which overflows.
+///
+/// The sum is accumulated with 76 significant digits, and only the emitted prefix sums are rounded to
+/// 38 digits. So the results may differ from a fold of [`Decimal::checked_add`], which rounds each
+/// partial sum before adding the next value: they are more accurate, e.g. the last prefix sum of
+/// `[1E+37, 0.4, 0.4, -1E+37]` is `0.8` rather than `0`.
+///
+/// `out` is cleared first. On failure, it holds the prefix sums before the failing index.
+///
+/// ```
+/// use decimal_rs::batch;
+/// use decimal_rs::Decimal;
+///
+/// let values: Vec<Decimal> = ["1.5", "2", "-0.25"].iter().map(|s| s.parse().unwrap()).collect();
+/// let mut sums = Vec::new();
+/// batch::cumsum(&values, &mut sums).unwrap();
+/// assert_eq!(sums, ["1.5", "3.5", "3.25"].iter().map(|s| s.parse().unwrap()).collect::<Vec<Decimal>>());
+/// ```
+#[inline]
+pub fn cumsum(values: &[Decimal], out: &mut Vec<Decimal>) -> Result<(), (usize, BatchError)> {
+    out.clear();
+    out.reserve(values.len());
+
+    let mut sum = RunningSum::ZERO;
+    for (i, val) in values.iter().enumerate() {
+        sum.add(val);
+        out.push(sum.to_decimal().ok_or((i, BatchError::Overflow))?);
+    }
+
+    Ok(())
+}
+
+/// Computes the prefix products of `values` into `out`, failing at the first prefix product which overflows.
+///
+/// The product is accumulated with 57 significant digits and an unlimited scale, and only the emitted
+/// prefix products are rounded to 38 digits. So the results may differ from a fold of [`Decimal::checked_mul`],
+/// which rounds each partial product before multiplying the next value: they are more accurate, and an
+/// intermediate product which is too small to be represented, and so emitted as zero, doesn't make the
+/// following products zero.
+///
+/// `out` is cleared first. On failure, it holds the prefix products before the failing index.
+///
+/// ```
+/// use decimal_rs::batch;
+/// use decimal_rs::Decimal;
+///
+/// let values: Vec<Decimal> = ["1.5", "2", "-0.25"].iter().map(|s| s.parse().unwrap()).collect();
+/// let mut products = Vec::new();
+/// batch::cumprod(&values, &mut products).unwrap();
+/// assert_eq!(products, ["1.5", "3", "-0.75"].iter().map(|s| s.parse().unwrap()).collect::<Vec<Decimal>>());
+/// ```
+#[inline]
+pub fn cumprod(values: &[Decimal], out: &mut Vec<Decimal>) -> Result<(), (usize, BatchError)> {
+    out.clear();
+    out.reserve(values.len());
+
+    let mut product = RunningProduct::ONE;
+    for (i, val) in values.iter().enumerate() {
+        product.mul(val);
+        out.push(product.to_decimal().ok_or((i, BatchError::Overflow))?);
+    }
+
+    Ok(())
+}
+
+/// Replaces each value with `f(value)`, failing at the first value for which `f` returns `None`.
+#[inline]
+fn update_in_place<F: Fn(&Decimal) -> Option<Decimal>>(
+    values: &mut [Decimal],
+    err: BatchError,
+    f: F,
+) -> Result<(), (usize, BatchError)> {
+    for (i, val) in values.iter_mut().enumerate() {
+        *val = f(val).ok_or((i, err))?;
+    }
+    Ok(())
+}
+
+/// Multiplies each value by `scalar` in place, as [`Decimal::checked_mul`] does, failing at the first
+/// product which overflows.
+///
+/// The bounds under which a product needs no rounding are computed from `scalar` once, so most products
+/// are a single `u128` multiplication, and a scalar of the form `1E-n`, e.g. `0.01`, only shifts the
+/// scale. The results are the same as those of `checked_mul`, scales included.
+///
+/// On failure, the values before the failing index are updated, and the others are left unchanged.
+///
+/// ```
+/// use decimal_rs::batch;
+/// use decimal_rs::Decimal;
+///
+/// let mut values: Vec<Decimal> = ["1.5", "2", "-0.25"].iter().map(|s| s.parse().unwrap()).collect();
+/// batch::mul_scalar_in_place(&mut values, &"1.1".parse().unwrap()).unwrap();
+/// assert_eq!(values, ["1.65", "2.2", "-0.275"].iter().map(|s| s.parse().unwrap()).collect::<Vec<Decimal>>());
+/// ```
+#[inline]
+pub fn mul_scalar_in_place(values: &mut [Decimal], scalar: &Decimal) -> Result<(), (usize, BatchError)> {
+    if scalar.is_zero() {
+        values.iter_mut().for_each(|v| *v = Decimal::ZERO);
+        return Ok(());
+    }
+
+    let (scalar_int_val, scalar_scale, scalar_negative) = scalar.into_parts();
+    // A product of at most 38 digits, whose scale is in `-88..=130`, is kept as is by `adjust_scale`.
+    let max_int_val = MAX_I128_REPR as u128 / scalar_int_val;
+    let min_scale = MIN_SCALE + MAX_PRECISION as i16 - scalar_scale;
+    let max_scale = MAX_SCALE - scalar_scale;
+    let in_bounds = |v: &Decimal| v.int_val() <= max_int_val && v.scale() >= min_scale && v.scale() <= max_scale;
+
+    if scalar_int_val == 1 {
+        update_in_place(values, BatchError::Overflow, |v| {
+            if in_bounds(v) {
+                let negative = v.is_sign_negative() ^ scalar_negative;
+                Some(unsafe { Decimal::from_parts_unchecked(v.int_val(), v.scale() + scalar_scale, negative) })
+            } else {
+                v.checked_mul(scalar)
+            }
+        })
+    } else {
+        update_in_place(values, BatchError::Overflow, |v| {
+            if in_bounds(v) {
+                let negative = v.is_sign_negative() ^ scalar_negative;
+                let int_val = v.int_val() * scalar_int_val;
+                Some(unsafe { Decimal::from_parts_unchecked(int_val, v.scale() + scalar_scale, negative) })
+            } else {
+                v.checked_mul(scalar)
+            }
+        })
+    }
+}
+
+/// Adds `scalar` to each value in place, as [`Decimal::checked_add`] does, failing at the first sum
+/// which overflows.
+///
+/// A value with the scale of `scalar` is added as a mantissa, if that scale isn't negative. The results
+/// are the same as those of `checked_add`, scales included.
+///
+/// On failure, the values before the failing index are updated, and the others are left unchanged.
+#[inline]
+pub fn add_scalar_in_place(values: &mut [Decimal], scalar: &Decimal) -> Result<(), (usize, BatchError)> {
+    let (scalar_int_val, scalar_scale, scalar_negative) = scalar.into_parts();
+    let shared_scale = if scalar_scale >= 0 { scalar_scale } else { i16::MIN };
+    let max_int_val = MAX_I128_REPR as u128 - scalar_int_val;
+
+    update_in_place(values, BatchError::Overflow, |v| {
+        let (int_val, scale, negative) = v.into_parts();
+        if scale != shared_scale {
+            return v.checked_add(scalar);
+        }
+
+        let (int_val, negative) = if negative == scalar_negative {
+            if int_val > max_int_val {
+                return v.checked_add(scalar);
+            }
+            (int_val + scalar_int_val, negative)
+        } else if int_val >= scalar_int_val {
+            (int_val - scalar_int_val, negative)
+        } else {
+            (scalar_int_val - int_val, scalar_negative)
+        };
+        Some(unsafe { Decimal::from_parts_unchecked(int_val, scale, negative) })
+    })
+}
+
+/// Divides each value by `scalar` in place, as [`Decimal::checked_div`] does, failing at the first
+/// quotient which overflows, or at index 0 if `scalar` is zero.
+///
+/// The precision of `scalar` is computed once, and dividing by a power of ten, e.g. `100` or `0.01`, only
+/// shifts the scale. The results are the same as those of `checked_div`, scales included.
+///
+/// On failure, the values before the failing index are updated, and the others are left unchanged.
+#[inline]
+pub fn div_scalar_in_place(values: &mut [Decimal], scalar: &Decimal) -> Result<(), (usize, BatchError)> {
+    if scalar.is_zero() {
+        return if values.is_empty() {
+            Ok(())
+        } else {
+            Err((0, BatchError::DivisionByZero))
+        };
+    }
+
+    let scalar_precision = scalar.precision();
+    let (scalar_int_val, scalar_scale, scalar_negative) = scalar.into_parts();
+    let trailing_zeros = scalar_precision as i16 - 1;
+    if scalar_int_val != POWERS_10[trailing_zeros as usize].low() {
+        return update_in_place(values, BatchError::Overflow, |v| {
+            v.div_internal(scalar, scalar_precision)
+        });
+    }
+
+    // The quotient is exact, so `checked_div` scales the mantissa up to 38 digits.
+    let scalar_scale = scalar_scale - trailing_zeros;
+    update_in_place(values, BatchError::Overflow, |v| {
+        if v.is_zero() {
+            return Some(Decimal::ZERO);
+        }
+
+        let precision = v.precision() as i16;
+        let s = v.scale() - scalar_scale - precision;
+        if s >= MAX_SCALE {
+            return Some(Decimal::ZERO);
+        }
+        if s < MIN_SCALE {
+            return None;
+        }
+
+        let e = MAX_PRECISION as i16 - precision;
+        let int_val = v.int_val() * POWERS_10[e as usize].low();
+        let negative = v.is_sign_negative() ^ scalar_negative;
+        Some(unsafe { Decimal::from_parts_unchecked(int_val, s + MAX_PRECISION as i16, negative) })
+    })
+}
+
+/// The exact product of two decimals, with up to 76 digits.
+#[inline]
+fn wide_product(lhs: (u128, i16, bool), rhs: &Decimal) -> (U256, i16, bool) {
+    let (rhs_int_val, rhs_scale, rhs_negative) = rhs.into_parts();
+    (
+        U256::mul128(lhs.0, rhs_int_val),
+        lhs.1 + rhs_scale,
+        lhs.2 ^ rhs_negative,
+    )
+}
+
+/// Computes `y[i] = a * x[i] + y[i]` in place, failing at the first result which overflows.
+///
+/// The product is kept exact, and the sum is computed with 76 significant digits and rounded to 38 digits
+/// once. So the results may differ from `a.checked_mul(x[i])?.checked_add(y[i])`, which rounds the product
+/// first: they are more accurate, e.g. `1.0000000000000000001 * 1.0000000000000000001 - 1` is
+/// `2.0000000000000000001E-19` rather than `2E-19`. A zero `a` leaves `y` unchanged.
+///
+/// The bounds under which a product fits in a decimal are computed from `a` once, so most products are
+/// a single `u128` multiplication.
+///
+/// On failure, the values before the failing index are updated, and the others are left unchanged.
+///
+/// # Panics
+///
+/// Panics if `x` and `y` have different lengths.
+///
+/// ```
+/// use decimal_rs::batch;
+/// use decimal_rs::Decimal;
+///
+/// let x: Vec<Decimal> = ["1.5", "2", "-0.25"].iter().map(|s| s.parse().unwrap()).collect();
+/// let mut y: Vec<Decimal> = ["1", "1", "1"].iter().map(|s| s.parse().unwrap()).collect();
+/// batch::axpy(&"2".parse().unwrap(), &x, &mut y).unwrap();
+/// assert_eq!(y, ["4", "5", "0.5"].iter().map(|s| s.parse().unwrap()).collect::<Vec<Decimal>>());
+/// ```
+#[inline]
+pub fn axpy(a: &Decimal, x: &[Decimal], y: &mut [Decimal]) -> Result<(), (usize, BatchError)> {
+    assert_eq!(x.len(), y.len(), "x and y have different lengths");
+
+    if a.is_zero() {
+        return Ok(());
+    }
+
+    let a_parts = a.into_parts();
+    // A product of at most 38 digits, whose scale is in `-88..=130`, is exact as a decimal.
+    let max_int_val = MAX_I128_REPR as u128 / a_parts.0;
+    let min_scale = MIN_SCALE + MAX_PRECISION as i16 - a_parts.1;
+    let max_scale = MAX_SCALE - a_parts.1;
+
+    for (i, (x, y)) in x.iter().zip(y.iter_mut()).enumerate() {
+        let (x_int_val, x_scale, x_negative) = x.into_parts();
+        let result = if x_int_val <= max_int_val && x_scale >= min_scale && x_scale <= max_scale {
+            let product = unsafe {
+                Decimal::from_parts_unchecked(x_int_val * a_parts.0, x_scale + a_parts.1, x_negative ^ a_parts.2)
+            };
+            product.checked_add(*y)
+        } else {
+            let (int_val, scale, negative) = wide_product(a_parts, x);
+            let mut sum = RunningSum::ZERO;
+            sum.add_wide(int_val, int_val.count_digits(), scale, negative);
+            sum.add(y);
+            sum.to_decimal()
+        };
+        *y = result.ok_or((i, BatchError::Overflow))?;
+    }
+
+    Ok(())
+}
+
+/// Computes the dot product of `x` and `y`, returning `None` if it overflowed.
+///
+/// The exact products are accumulated with 76 significant digits, and only the result is rounded to
+/// 38 digits. So the result may differ from a fold of [`Decimal::checked_mul`] and
+/// [`Decimal::checked_add`]: it's more accurate, and an intermediate sum which overflows doesn't make
+/// the result overflow.
+///
+/// # Panics
+///
+/// Panics if `x` and `y` have different lengths.
+///
+/// ```
+/// use decimal_rs::batch;
+/// use decimal_rs::Decimal;
+///
+/// let x: Vec<Decimal> = ["1.5", "2", "-0.25"].iter().map(|s| s.parse().unwrap()).collect();
+/// let y: Vec<Decimal> = ["2", "0.5", "4"].iter().map(|s| s.parse().unwrap()).collect();
+/// assert_eq!(batch::dot(&x, &y), Some("3".parse().unwrap()));
+/// ```
+#[inline]
+pub fn dot(x: &[Decimal], y: &[Decimal]) -> Option<Decimal> {
+    assert_eq!(x.len(), y.len(), "x and y have different lengths");
+
+    let mut sum = RunningSum::ZERO;
+    for (x, y) in x.iter().zip(y.iter()) {
+        let (int_val, scale, negative) = wide_product(x.into_parts(), y);
+        sum.add_wide(int_val, int_val.count_digits(), scale, negative);
+    }
+
+    sum.to_decimal()
+}
+
+/// The count of elements compared at once, which is also the count of bits in a word of a mask.
+const BLOCK: usize = 64;
+
+#[inline]
+fn mantissa(val: &Decimal) -> i128 {
+    val.to_scaled_i128().0
+}
+
+/// Returns `true` if the non-zero values of a block share one scale, so that they compare as their
+/// signed mantissas.
+#[inline]
+fn has_shared_scale<'a, I: Iterator<Item = &'a Decimal> + Clone>(block: I) -> bool {
+    match block.clone().find(|v| !v.is_zero()) {
+        Some(first) => has_scale(block, first.scale()),
+        None => true,
+    }
+}
+
+/// Returns `true` if the non-zero values of a block have the scale `scale`.
+#[inline]
+fn has_scale<'a, I: Iterator<Item = &'a Decimal>>(mut block: I, scale: i16) -> bool {
+    block.all(|v| v.scale() == scale || v.is_zero())
+}
+
+/// Compares two values with a shared scale as their mantissas, returning `-1`, `0` or `1`.
+#[inline]
+fn cmp_mantissa(lhs: &Decimal, rhs: &Decimal) -> i8 {
+    let (lhs, rhs) = (mantissa(lhs), mantissa(rhs));
+    (lhs > rhs) as i8 - (lhs < rhs) as i8
+}
+
+/// Packs the bits given by `bit` for the indexes of a block into a word, the first bit into the lowest bit.
+#[inline]
+fn pack<F: Fn(usize) -> bool>(len: usize, bit: F) -> u64 {
+    (0..len).fold(0u64, |word, i| word | (bit(i) as u64) << i)
+}
+
+/// Compares `lhs` and `rhs` element-wise into `out`, as [`Decimal::cmp_i8`] does.
+///
+/// `out` is cleared first. The values are compared by blocks of 64: if the non-zero values of a block
+/// share one scale, their mantissas are compared directly, without branches.
+///
+/// # Panics
+///
+/// Panics if `lhs` and `rhs` have different lengths.
+#[inline]
+pub fn cmp(lhs: &[Decimal], rhs: &[Decimal], out: &mut Vec<i8>) {
+    assert_eq!(lhs.len(), rhs.len(), "lhs and rhs have different lengths");
+
+    out.clear();
+    out.reserve(lhs.len());
+
+    for (lhs, rhs) in lhs.chunks(BLOCK).zip(rhs.chunks(BLOCK)) {
+        let pairs = lhs.iter().zip(rhs.iter());
+        if has_shared_scale(lhs.iter().chain(rhs.iter())) {
+            out.extend(pairs.map(|(l, r)| cmp_mantissa(l, r)));
+        } else {
+            out.extend(pairs.map(|(l, r)| l.cmp_i8(r)));
+        }
+    }
+}
+
+#[inline]
+fn mask<F: Fn(i8) -> bool>(lhs: &[Decimal], rhs: &[Decimal], out: &mut Vec<u64>, pred: F) {
+    assert_eq!(lhs.len(), rhs.len(), "lhs and rhs have different lengths");
+
+    out.clear();
+    out.reserve(lhs.len().div_ceil(BLOCK));
+
+    for (lhs, rhs) in lhs.chunks(BLOCK).zip(rhs.chunks(BLOCK)) {
+        if has_shared_scale(lhs.iter().chain(rhs.iter())) {
+            out.push(pack(lhs.len(), |i| pred(cmp_mantissa(&lhs[i], &rhs[i]))));
+        } else {
+            out.push(pack(lhs.len(), |i| pred(lhs[i].cmp_i8(&rhs[i]))));
+        }
+    }
+}
+
+#[inline]
+fn mask_scalar<F: Fn(i8) -> bool>(lhs: &[Decimal], rhs: &Decimal, out: &mut Vec<u64>, pred: F) {
+    out.clear();
+    out.reserve(lhs.len().div_ceil(BLOCK));
+
+    for lhs in lhs.chunks(BLOCK) {
+        if rhs.is_zero() || has_scale(lhs.iter(), rhs.scale()) {
+            out.push(pack(lhs.len(), |i| pred(cmp_mantissa(&lhs[i], rhs))));
+        } else {
+            out.push(pack(lhs.len(), |i| pred(lhs[i].cmp_i8(rhs))));
+        }
+    }
+}
+
+/// Computes the mask of `lhs[i] == rhs[i]` into `out`.
+///
+/// The mask is packed into 64-bit words: the bit of index `i` is the bit `i % 64` of `out[i / 64]`,
+/// and the unused high bits of the last word are zero. `out` is cleared first.
+///
+/// # Panics
+///
+/// Panics if `lhs` and `rhs` have different lengths.
+///
+/// ```
+/// use decimal_rs::batch;
+/// use decimal_rs::Decimal;
+///
+/// let lhs: Vec<Decimal> = ["1", "2.5", "3"].iter().map(|s| s.parse().unwrap()).collect();
+/// let rhs: Vec<Decimal> = ["1.0", "2", "3"].iter().map(|s| s.parse().unwrap()).collect();
+/// let mut mask = Vec::new();
+/// batch::eq(&lhs, &rhs, &mut mask);
+/// assert_eq!(mask, [0b101]);
+/// ```
+#[inline]
+pub fn eq(lhs: &[Decimal], rhs: &[Decimal], out: &mut Vec<u64>) {
+    mask(lhs, rhs, out, |o| o == 0)
+}
+
+/// Computes the mask of `lhs[i] < rhs[i]` into `out`, packed as in [`eq`].
+///
+/// The mask of `lhs[i] > rhs[i]` is `lt(rhs, lhs)`.
+///
+/// # Panics
+///
+/// Panics if `lhs` and `rhs` have different lengths.
+#[inline]
+pub fn lt(lhs: &[Decimal], rhs: &[Decimal], out: &mut Vec<u64>) {
+    mask(lhs, rhs, out, |o| o < 0)
+}
+
+/// Computes the mask of `lhs[i] <= rhs[i]` into `out`, packed as in [`eq`].
+///
+/// The mask of `lhs[i] >= rhs[i]` is `le(rhs, lhs)`.
+///
+/// # Panics
+///
+/// Panics if `lhs` and `rhs` have different lengths.
+#[inline]
+pub fn le(lhs: &[Decimal], rhs: &[Decimal], out: &mut Vec<u64>) {
+    mask(lhs, rhs, out, |o| o <= 0)
+}
+
+/// Computes the mask of `lhs[i] == rhs` into `out`, packed as in [`eq`].
+#[inline]
+pub fn eq_scalar(lhs: &[Decimal], rhs: &Decimal, out: &mut Vec<u64>) {
+    mask_scalar(lhs, rhs, out, |o| o == 0)
+}
+
+/// Computes the mask of `lhs[i] < rhs` into `out`, packed as in [`eq`].
+///
+/// The mask of `lhs[i] >= rhs` is the complement, whose unused high bits of the last word must be cleared.
+#[inline]
+pub fn lt_scalar(lhs: &[Decimal], rhs: &Decimal, out: &mut Vec<u64>) {
+    mask_scalar(lhs, rhs, out, |o| o < 0)
+}
+
+/// Computes the mask of `lhs[i] <= rhs` into `out`, packed as in [`eq`].
+///
+/// The mask of `lhs[i] > rhs` is the complement, whose unused high bits of the last word must be cleared.
+#[inline]
+pub fn le_scalar(lhs: &[Decimal], rhs: &Decimal, out: &mut Vec<u64>) {
+    mask_scalar(lhs, rhs, out, |o| o <= 0)
+}
+
+/// Computes [`Decimal::partition_key`] of each value into `out`.
+///
+/// # Panics
+///
+/// Panics if `buckets` is zero.
+#[inline]
+pub fn partition_keys(values: &[Decimal], buckets: u32, out: &mut Vec<u32>) {
+    assert_ne!(buckets, 0, "buckets is zero");
+
+    out.clear();
+    out.reserve(values.len());
+    out.extend(values.iter().map(|v| v.partition_key(buckets)));
+}
+
+/// The reason a conversion to fixed-point integers failed at an element.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FixedPointError {
+    /// The value at the scale is out of the range of the integer.
+    Overflow,
+    /// The value has a non-zero digit below the scale.
+    Inexact,
+}
+
+impl fmt::Display for FixedPointError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            FixedPointError::Overflow => write!(f, "numeric overflow"),
+            FixedPointError::Inexact => write!(f, "inexact fixed-point value"),
+        }
+    }
+}
+
+/// The rescaling of the values of one scale to the target scale.
+#[derive(Copy, Clone, Debug)]
+enum Rescale {
+    /// Multiplies by the factor, where the magnitude must be at most the limits, for positive and negative
+    /// values respectively.
+    Mul { factor: u128, limit: u128, neg_limit: u128 },
+    /// Divides by `10^e`, where `e <= MAX_PRECISION`.
+    Div { e: u32 },
+    /// Drops all digits.
+    Drop,
+}
+
+impl Rescale {
+    #[inline]
+    fn new(from: i16, to: i16) -> Rescale {
+        let e = to as i32 - from as i32;
+        if e < -(MAX_PRECISION as i32) {
+            Rescale::Drop
+        } else if e < 0 {
+            Rescale::Div { e: (-e) as u32 }
+        } else {
+            // `10^19` is out of the range of `i64`, so any non-zero value overflows.
+            let factor = POWERS_10[e.min(19) as usize].low();
+            Rescale::Mul {
+                factor,
+                limit: i64::MAX as u128 / factor,
+                neg_limit: (i64::MAX as u128 + 1) / factor,
+            }
+        }
+    }
+
+    /// Returns the magnitude of `int_val * 10^(to - from)`, rounded with `mode`, or failing with
+    /// `FixedPointError::Inexact` if there is no mode.
+    #[inline]
+    fn apply(self, int_val: u128, negative: bool, mode: Option<RoundingMode>) -> Result<u64, FixedPointError> {
+        let max = if negative {
+            i64::MAX as u128 + 1
+        } else {
+            i64::MAX as u128
+        };
+        let magnitude = match self {
+            Rescale::Mul {
+                factor,
+                limit,
+                neg_limit,
+            } => {
+                if int_val > if negative { neg_limit } else { limit } {
+                    return Err(FixedPointError::Overflow);
+                }
+                int_val * factor
+            }
+            Rescale::Div { e } => match mode {
+                Some(mode) => mode.div_pow10(int_val, e, negative),
+                None if int_val.is_multiple_of(POWERS_10[e as usize].low()) => int_val / POWERS_10[e as usize].low(),
+                None => return Err(FixedPointError::Inexact),
+            },
+            // The whole value is less than half a unit.
+            Rescale::Drop => match mode {
+                Some(mode) => mode.round_up(Ordering::Less, false, false, negative) as u128,
+                None => return Err(FixedPointError::Inexact),
+            },
+        };
+
+        if magnitude > max {
+            return Err(FixedPointError::Overflow);
+        }
+        Ok(magnitude as u64)
+    }
+}
+
+/// Converts each value into a fixed-point `i64` of `scale`, i.e. `value * 10^scale`, rounding with `mode`.
+///
+/// The values may have any scales, and the rescaling is only computed again when the scale changes from
+/// one value to the next, so a column of values with one scale is a multiplication or a division each.
+///
+/// `out` is cleared first. On failure, it holds the integers before the failing index, which fails with
+/// `FixedPointError::Overflow`.
+///
+/// ```
+/// use decimal_rs::batch;
+/// use decimal_rs::{Decimal, RoundingMode};
+///
+/// let values: Vec<Decimal> = ["1.5", "2", "-0.255"].iter().map(|s| s.parse().unwrap()).collect();
+/// let mut fixed = Vec::new();
+/// batch::to_fixed_i64(&values, 2, RoundingMode::HalfEven, &mut fixed).unwrap();
+/// assert_eq!(fixed, [150, 200, -26]);
+/// ```
+#[inline]
+pub fn to_fixed_i64(
+    values: &[Decimal],
+    scale: i16,
+    mode: RoundingMode,
+    out: &mut Vec<i64>,
+) -> Result<(), (usize, FixedPointError)> {
+    to_fixed(values, scale, Some(mode), out)
+}
+
+/// Converts each value into a fixed-point `i64` of `scale`, i.e. `value * 10^scale`, failing at the first
+/// value which has a non-zero digit below `scale` or is out of range.
+///
+/// `out` is cleared first. On failure, it holds the integers before the failing index.
+///
+/// ```
+/// use decimal_rs::batch::{self, FixedPointError};
+/// use decimal_rs::Decimal;
+///
+/// let values: Vec<Decimal> = ["1.5", "2", "-0.255"].iter().map(|s| s.parse().unwrap()).collect();
+/// let mut fixed = Vec::new();
+/// assert_eq!(batch::to_fixed_i64_exact(&values, 2, &mut fixed), Err((2, FixedPointError::Inexact)));
+/// assert_eq!(fixed, [150, 200]);
+/// ```
+#[inline]
+pub fn to_fixed_i64_exact(values: &[Decimal], scale: i16, out: &mut Vec<i64>) -> Result<(), (usize, FixedPointError)> {
+    to_fixed(values, scale, None, out)
+}
+
+#[inline]
+fn to_fixed(
+    values: &[Decimal],
+    scale: i16,
+    mode: Option<RoundingMode>,
+    out: &mut Vec<i64>,
+) -> Result<(), (usize, FixedPointError)> {
+    out.clear();
+    out.reserve(values.len());
+
+    let mut val_scale = 0;
+    let mut rescale = Rescale::new(val_scale, scale);
+    for (i, val) in values.iter().enumerate() {
+        let (int_val, s, negative) = val.into_parts();
+        if int_val == 0 {
+            out.push(0);
+            continue;
+        }
+
+        if s != val_scale {
+            val_scale = s;
+            rescale = Rescale::new(val_scale, scale);
+        }
+
+        let magnitude = rescale.apply(int_val, negative, mode).map_err(|e| (i, e))?;
+        // `-2^63` wraps to `i64::MIN`
+        out.push(if negative {
+            0i64.wrapping_sub(magnitude as i64)
+        } else {
+            magnitude as i64
+        });
+    }
+
+    Ok(())
+}
+
+/// Converts each fixed-point `i64` of `scale` into a decimal, i.e. `value * 10^-scale`.
+///
+/// `out` is cleared first.
+///
+/// # Panics
+///
+/// Panics if `scale` is out of the range of [`Decimal::from_parts`].
+///
+/// ```
+/// use decimal_rs::batch;
+/// use decimal_rs::Decimal;
+///
+/// let mut values = Vec::new();
+/// batch::from_fixed_i64(&[150, 200, -26], 2, &mut values);
+/// assert_eq!(values, ["1.5", "2", "-0.26"].iter().map(|s| s.parse().unwrap()).collect::<Vec<Decimal>>());
+/// ```
+#[inline]
+pub fn from_fixed_i64(values: &[i64], scale: i16, out: &mut Vec<Decimal>) {
+    assert!(Decimal::from_parts(1, scale, false).is_ok(), "scale is out of range");
+
+    out.clear();
+    out.reserve(values.len());
+    out.extend(
+        values
+            .iter()
+            // Safety: the magnitude has at most 19 digits, and `scale` is in range.
+            .map(|&v| unsafe { Decimal::from_parts_unchecked(v.unsigned_abs() as u128, scale, v < 0) }),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lcg;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn parse_all(values: &[&str]) -> Vec<Decimal> {
+        values.iter().map(|v| parse(v)).collect()
+    }
+
+    fn naive_cumsum(values: &[Decimal]) -> Vec<Decimal> {
+        values
+            .iter()
+            .scan(Decimal::ZERO, |sum, val| {
+                *sum = sum.checked_add(val)?;
+                Some(*sum)
+            })
+            .collect()
+    }
+
+    fn naive_cumprod(values: &[Decimal]) -> Vec<Decimal> {
+        values
+            .iter()
+            .scan(Decimal::ONE, |product, val| {
+                *product = product.checked_mul(val)?;
+                Some(*product)
+            })
+            .collect()
+    }
+
+    fn random_values(seed: u64, n: usize) -> Vec<Decimal> {
+        let mut rng = Lcg::new(seed);
+
+        (0..n)
+            .map(|_| {
+                Decimal::from_parts(
+                    (rng.next() % 100_000) as u128,
+                    (rng.next() % 4) as i16,
+                    rng.next().is_multiple_of(2),
+                )
+                .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cumsum() {
+        fn assert_cumsum(values: &[&str], expected: Result<&[&str], (usize, BatchError)>) {
+            let mut out = vec![Decimal::ONE];
+            let result = cumsum(&parse_all(values), &mut out);
+            match expected {
+                Ok(sums) => {
+                    assert_eq!(result, Ok(()));
+                    assert_eq!(out, parse_all(sums));
+                }
+                Err((index, err)) => {
+                    assert_eq!(result, Err((index, err)));
+                    assert_eq!(out.len(), index);
+                }
+            }
+        }
+
+        assert_cumsum(&[], Ok(&[]));
+        assert_cumsum(&["1.5", "2", "-0.25"], Ok(&["1.5", "3.5", "3.25"]));
+        assert_cumsum(&["1", "-1", "0", "1E-130"], Ok(&["1", "0", "0", "1E-130"]));
+        assert_cumsum(
+            &["1E+30", "1", "-1E+30"],
+            Ok(&["1E+30", "1000000000000000000000000000001", "1"]),
+        );
+        assert_cumsum(&["1E+125", "1E-130", "-1E+125"], Ok(&["1E+125", "1E+125", "0"]));
+        assert_cumsum(
+            &[
+                "9.9999999999999999999999999999999999999E125",
+                "-1",
+                "9.9999999999999999999999999999999999999E125",
+                "1",
+            ],
+            Err((2, BatchError::Overflow)),
+        );
+        assert_cumsum(
+            &["99999999999999999999999999999999999999", "0.5", "-1"],
+            Ok(&[
+                "99999999999999999999999999999999999999",
+                "100000000000000000000000000000000000000",
+                "99999999999999999999999999999999999998.5",
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_cumsum_naive() {
+        for seed in 0..10 {
+            let values = random_values(seed, 1000);
+            let mut out = Vec::new();
+            cumsum(&values, &mut out).unwrap();
+            assert_eq!(out, naive_cumsum(&values));
+        }
+    }
+
+    #[test]
+    fn test_cumsum_accuracy() {
+        // The naive fold loses `0.4` twice.
+        let values = parse_all(&["1E+37", "0.4", "0.4", "-1E+37"]);
+        let mut out = Vec::new();
+        cumsum(&values, &mut out).unwrap();
+        assert_eq!(
+            out,
+            parse_all(&["1E+37", "1E+37", "10000000000000000000000000000000000001", "0.8"])
+        );
+        assert_eq!(naive_cumsum(&values)[3], Decimal::ZERO);
+
+        // The last prefix sum is the sum of the exact total.
+        let mut values = vec![parse("0.12345678901234567890123456789012345678"); 100];
+        values.push(parse("-12.345678901234567890123456789012345678"));
+        cumsum(&values, &mut out).unwrap();
+        assert_eq!(out[100], Decimal::ZERO);
+        assert_ne!(naive_cumsum(&values)[100], Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_cumprod() {
+        fn assert_cumprod(values: &[&str], expected: Result<&[&str], (usize, BatchError)>) {
+            let mut out = vec![Decimal::ONE];
+            let result = cumprod(&parse_all(values), &mut out);
+            match expected {
+                Ok(products) => {
+                    assert_eq!(result, Ok(()));
+                    assert_eq!(out, parse_all(products));
+                }
+                Err((index, err)) => {
+                    assert_eq!(result, Err((index, err)));
+                    assert_eq!(out.len(), index);
+                }
+            }
+        }
+
+        assert_cumprod(&[], Ok(&[]));
+        assert_cumprod(&["1.5", "2", "-0.25"], Ok(&["1.5", "3", "-0.75"]));
+        assert_cumprod(&["-2", "-2", "0", "5"], Ok(&["-2", "4", "0", "0"]));
+        assert_cumprod(&["1E+100", "1E+25", "10"], Err((2, BatchError::Overflow)));
+        assert_cumprod(&["1E+100", "1E+25", "10", "0.1"], Err((2, BatchError::Overflow)));
+
+        // A product which underflows comes back into range.
+        assert_cumprod(
+            &["1E-100", "1E-100", "1E+100", "1E+100"],
+            Ok(&["1E-100", "0", "1E-100", "1"]),
+        );
+        assert_eq!(
+            naive_cumprod(&parse_all(&["1E-100", "1E-100", "1E+100", "1E+100"])),
+            parse_all(&["1E-100", "0", "0", "0"])
+        );
+    }
+
+    #[test]
+    fn test_cumprod_naive() {
+        for seed in 0..10 {
+            let values: Vec<Decimal> = random_values(seed, 7).into_iter().filter(|v| !v.is_zero()).collect();
+            let mut out = Vec::new();
+            cumprod(&values, &mut out).unwrap();
+            assert_eq!(out, naive_cumprod(&values));
+        }
+    }
+
+    #[test]
+    fn test_cumprod_accuracy() {
+        // (1 + 1E-19)^n, whose exact digits are the binomial coefficients.
+        let values = vec![parse("1.0000000000000000001"); 10];
+        let mut out = Vec::new();
+        cumprod(&values, &mut out).unwrap();
+        assert_eq!(out[1], parse("1.0000000000000000002"));
+        assert_eq!(out[3], parse("1.0000000000000000004000000000000000001"));
+        assert_eq!(out[5], parse("1.0000000000000000006000000000000000002"));
+        assert_eq!(out[9], parse("1.0000000000000000010000000000000000005"));
+
+        // The naive fold loses the digits of `C(n, 2) * 1E-38` on the way.
+        let naive = naive_cumprod(&values);
+        assert_eq!(naive[3], parse("1.0000000000000000004"));
+        assert_eq!(naive[5], parse("1.0000000000000000006000000000000000001"));
+    }
+
+    fn assert_in_place<F, G>(op: F, naive: G, scalars: &[&str])
+    where
+        F: Fn(&mut [Decimal], &Decimal) -> Result<(), (usize, BatchError)>,
+        G: Fn(&Decimal, &Decimal) -> Option<Decimal>,
+    {
+        let parts = |values: &[Decimal]| values.iter().map(|v| v.into_parts()).collect::<Vec<_>>();
+
+        let mut corpus = crate::testing::corpus();
+        corpus.extend(random_values(17, 1000));
+        for scalar in scalars.iter().map(|s| parse(s)) {
+            for chunk in corpus.chunks(97) {
+                let expected: Vec<Option<Decimal>> = chunk.iter().map(|v| naive(v, &scalar)).collect();
+                let mut values = chunk.to_vec();
+                let result = op(&mut values, &scalar);
+                match expected.iter().position(|e| e.is_none()) {
+                    None => {
+                        assert_eq!(result, Ok(()), "{}", scalar);
+                        let expected: Vec<Decimal> = expected.into_iter().map(Option::unwrap).collect();
+                        assert_eq!(parts(&values), parts(&expected), "{}", scalar);
+                    }
+                    Some(index) => {
+                        // The values before the failing index are updated, and the others are left unchanged.
+                        assert_eq!(result, Err((index, BatchError::Overflow)), "{}", scalar);
+                        let updated: Vec<Decimal> = expected[..index].iter().map(|e| e.unwrap()).collect();
+                        assert_eq!(parts(&values[..index]), parts(&updated), "{}", scalar);
+                        assert_eq!(parts(&values[index..]), parts(&chunk[index..]), "{}", scalar);
+                    }
+                }
+            }
+        }
+    }
+
+    const SCALARS: &[&str] = &[
+        "0",
+        "1",
+        "-1",
+        "0.01",
+        "-1E-20",
+        "100",
+        "1E+10",
+        "1E+100",
+        "1E-100",
+        "1.1",
+        "-0.0725",
+        "1.0873456789012345678901234567890123457",
+        "99999999999999999999999999999999999999",
+        "9.9999999999999999999999999999999999999E125",
+        "1E-130",
+    ];
+
+    #[test]
+    fn test_mul_scalar_in_place() {
+        assert_in_place(mul_scalar_in_place, |v, s| v.checked_mul(s), SCALARS);
+        let mut values = parse_all(&["1.5", "1E+100", "2"]);
+        assert_eq!(
+            mul_scalar_in_place(&mut values, &parse("1E+26")),
+            Err((1, BatchError::Overflow))
+        );
+        assert_eq!(values, parse_all(&["1.5E+26", "1E+100", "2"]));
+    }
+
+    #[test]
+    fn test_add_scalar_in_place() {
+        assert_in_place(add_scalar_in_place, |v, s| v.checked_add(s), SCALARS);
+        let mut values = parse_all(&["1.5", "0.25", "-1.5"]);
+        add_scalar_in_place(&mut values, &parse("1.5")).unwrap();
+        assert_eq!(values, parse_all(&["3", "1.75", "0"]));
+    }
+
+    #[test]
+    fn test_div_scalar_in_place() {
+        assert_in_place(div_scalar_in_place, |v, s| v.checked_div(s), &SCALARS[1..]);
+        let mut values = parse_all(&["1.5", "3"]);
+        div_scalar_in_place(&mut values, &parse("100")).unwrap();
+        assert_eq!(values, parse_all(&["0.015", "0.03"]));
+
+        assert_eq!(
+            div_scalar_in_place(&mut values, &Decimal::ZERO),
+            Err((0, BatchError::DivisionByZero))
+        );
+        assert_eq!(values, parse_all(&["0.015", "0.03"]));
+        assert_eq!(div_scalar_in_place(&mut [], &Decimal::ZERO), Ok(()));
+    }
+
+    #[test]
+    fn test_axpy() {
+        let x = parse_all(&["1.5", "1E+100", "2"]);
+        let mut y = parse_all(&["1", "1", "1"]);
+        assert_eq!(axpy(&parse("1E+26"), &x, &mut y), Err((1, BatchError::Overflow)));
+        assert_eq!(y, parse_all(&["150000000000000000000000001", "1", "1"]));
+
+        let mut y = parse_all(&["1", "-1", "0.5"]);
+        axpy(&Decimal::ZERO, &x, &mut y).unwrap();
+        assert_eq!(y, parse_all(&["1", "-1", "0.5"]));
+        axpy(&Decimal::ONE, &[], &mut []).unwrap();
+
+        // The same as the naive loop, if the products are exact.
+        let mut corpus = crate::testing::corpus();
+        corpus.extend(random_values(19, 1000));
+        // A zero `a` leaves `y` unchanged.
+        for a in SCALARS.iter().map(|s| parse(s)).filter(|a| !a.is_zero()) {
+            let x = &corpus[..corpus.len() / 2];
+            let mut y = corpus[corpus.len() / 2..corpus.len() / 2 * 2].to_vec();
+            let naive: Vec<Option<Decimal>> = x
+                .iter()
+                .zip(y.iter())
+                .map(|(x, y)| a.checked_mul(x)?.checked_add(y))
+                .collect();
+            // `checked_add` keeps an exact sum below `1E-130`, rather than rounding it.
+            let exact: Vec<bool> = x
+                .iter()
+                .zip(naive.iter())
+                .map(|(x, n)| {
+                    a.checked_mul_stable(x).is_some() && n.iter().all(|n| n.is_zero() || n.logb() >= Some(-130))
+                })
+                .collect();
+
+            let result = axpy(&a, x, &mut y);
+            let len = match result {
+                Ok(()) => y.len(),
+                Err((index, _)) => index,
+            };
+            for i in (0..len).filter(|&i| exact[i]) {
+                assert_eq!(Some(y[i]), naive[i], "{} * {}", a, x[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_axpy_accuracy() {
+        // The naive loop rounds the product to `1.0000000000000000002` first.
+        let a = parse("1.0000000000000000001");
+        let mut y = parse_all(&["-1"]);
+        axpy(&a, &[a], &mut y).unwrap();
+        assert_eq!(y, parse_all(&["2.0000000000000000001E-19"]));
+        assert_eq!(a.checked_mul(a).unwrap().checked_add(parse("-1")), Some(parse("2E-19")));
+    }
+
+    #[test]
+    #[should_panic(expected = "x and y have different lengths")]
+    fn test_axpy_length() {
+        axpy(&Decimal::ONE, &[Decimal::ONE], &mut []).unwrap();
+    }
+
+    #[test]
+    fn test_dot() {
+        assert_eq!(dot(&[], &[]), Some(Decimal::ZERO));
+        assert_eq!(
+            dot(&parse_all(&["1.5", "2", "-0.25"]), &parse_all(&["2", "0.5", "4"])),
+            Some(parse("3"))
+        );
+        assert_eq!(
+            dot(&parse_all(&["1E-100"]), &parse_all(&["1E-100"])),
+            Some(Decimal::ZERO)
+        );
+        assert_eq!(dot(&parse_all(&["1E+100"]), &parse_all(&["1E+26"])), None);
+
+        // The same as the naive fold, if nothing is rounded.
+        for seed in 0..10 {
+            let x = random_values(seed, 1000);
+            let y = random_values(seed + 100, 1000);
+            let naive = x
+                .iter()
+                .zip(y.iter())
+                .try_fold(Decimal::ZERO, |sum, (x, y)| sum.checked_add(x.checked_mul(y)?));
+            assert_eq!(dot(&x, &y), naive);
+        }
+    }
+
+    #[test]
+    fn test_dot_accuracy() {
+        let ones = vec![Decimal::ONE; 4];
+
+        // The naive fold loses `0.4` twice.
+        let x = parse_all(&["1E+37", "0.4", "0.4", "-1E+37"]);
+        assert_eq!(dot(&x, &ones), Some(parse("0.8")));
+
+        // An intermediate sum which overflows.
+        let x = parse_all(&[
+            "9.9999999999999999999999999999999999999E125",
+            "9.9999999999999999999999999999999999999E125",
+            "-9.9999999999999999999999999999999999999E125",
+            "0",
+        ]);
+        assert_eq!(dot(&x, &ones), Some(x[0]));
+
+        // The products aren't rounded either.
+        let a = parse("1.0000000000000000001");
+        assert_eq!(
+            dot(&[a, Decimal::ONE], &[a, parse("-1")]),
+            Some(parse("2.0000000000000000001E-19"))
+        );
+    }
+
+    fn corpus() -> Vec<Decimal> {
+        let mut values = parse_all(&[
+            "0",
+            "1",
+            "-1",
+            "1.0",
+            "0.5",
+            "-0.5",
+            "1E-130",
+            "-1E-130",
+            "9.9999999999999999999999999999999999999E125",
+            "-9.9999999999999999999999999999999999999E125",
+            "99999999999999999999999999999999999999",
+            "-99999999999999999999999999999999999999",
+            "0.99999999999999999999999999999999999999",
+            "1E+38",
+            "1.2345678901234567890123456789012345678E-100",
+        ]);
+        values.push(Decimal::from_parts(10, 1, false).unwrap());
+        values.push(Decimal::from_parts(100, 2, true).unwrap());
+        values.push(Decimal::from_parts(1, 167, false).unwrap());
+        values
+    }
+
+    fn unpack(mask: &[u64], len: usize) -> Vec<bool> {
+        assert_eq!(mask.len(), len.div_ceil(64));
+        if !len.is_multiple_of(64) {
+            assert_eq!(mask[len / 64] >> (len % 64), 0);
+        }
+        (0..len).map(|i| mask[i / 64] >> (i % 64) & 1 == 1).collect()
+    }
+
+    fn assert_masks(lhs: &[Decimal], rhs: &[Decimal]) {
+        let mut out = Vec::new();
+        cmp(lhs, rhs, &mut out);
+        let expected: Vec<i8> = lhs.iter().zip(rhs.iter()).map(|(l, r)| l.cmp(r) as i8).collect();
+        assert_eq!(out, expected);
+
+        let mut mask = Vec::new();
+        eq(lhs, rhs, &mut mask);
+        assert_eq!(
+            unpack(&mask, lhs.len()),
+            expected.iter().map(|&o| o == 0).collect::<Vec<_>>()
+        );
+        lt(lhs, rhs, &mut mask);
+        assert_eq!(
+            unpack(&mask, lhs.len()),
+            expected.iter().map(|&o| o < 0).collect::<Vec<_>>()
+        );
+        le(lhs, rhs, &mut mask);
+        assert_eq!(
+            unpack(&mask, lhs.len()),
+            expected.iter().map(|&o| o <= 0).collect::<Vec<_>>()
+        );
+
+        for r in rhs.iter().take(20) {
+            let expected: Vec<i8> = lhs.iter().map(|l| l.cmp(r) as i8).collect();
+            eq_scalar(lhs, r, &mut mask);
+            assert_eq!(
+                unpack(&mask, lhs.len()),
+                expected.iter().map(|&o| o == 0).collect::<Vec<_>>()
+            );
+            lt_scalar(lhs, r, &mut mask);
+            assert_eq!(
+                unpack(&mask, lhs.len()),
+                expected.iter().map(|&o| o < 0).collect::<Vec<_>>()
+            );
+            le_scalar(lhs, r, &mut mask);
+            assert_eq!(
+                unpack(&mask, lhs.len()),
+                expected.iter().map(|&o| o <= 0).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_cmp_boundary() {
+        let corpus = corpus();
+        let mut lhs = Vec::new();
+        let mut rhs = Vec::new();
+        for l in corpus.iter() {
+            for r in corpus.iter() {
+                lhs.push(*l);
+                rhs.push(*r);
+            }
+        }
+        assert_masks(&lhs, &rhs);
+        assert_masks(&[], &[]);
+    }
+
+    #[test]
+    fn test_cmp_corpus() {
+        // The closest values are adjacent once sorted.
+        let mut corpus = crate::testing::corpus();
+        corpus.sort();
+        assert_masks(&corpus[1..], &corpus[..corpus.len() - 1]);
+        assert_masks(&corpus[..corpus.len() - 1], &corpus[1..]);
+        assert_masks(&corpus, &corpus);
+
+        let mut rotated = crate::testing::corpus();
+        for _ in 0..8 {
+            rotated.rotate_left(331);
+            assert_masks(&corpus, &rotated);
+        }
+    }
+
+    #[test]
+    fn test_cmp_shared_scale() {
+        for &len in [1, 63, 64, 65, 200].iter() {
+            let mut lhs = random_values(len as u64, len);
+            let mut rhs = random_values(len as u64 + 1000, len);
+            for v in lhs.iter_mut().chain(rhs.iter_mut()) {
+                *v = Decimal::from_parts(v.into_parts().0 % 100, 2, v.is_sign_negative()).unwrap();
+            }
+            assert_masks(&lhs, &rhs);
+
+            // The shared scale of a scalar differs.
+            let mut mask = Vec::new();
+            eq_scalar(&lhs, &parse("0.5"), &mut mask);
+            let expected: Vec<bool> = lhs.iter().map(|l| *l == parse("0.5")).collect();
+            assert_eq!(unpack(&mask, len), expected);
+
+            // A block with another scale among blocks with a shared scale.
+            lhs[len / 2] = parse("0.5");
+            rhs[len - 1] = parse("-12.345");
+            assert_masks(&lhs, &rhs);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "lhs and rhs have different lengths")]
+    fn test_cmp_length() {
+        cmp(&[Decimal::ONE], &[], &mut Vec::new());
+    }
+
+    #[test]
+    fn test_partition_keys() {
+        let values = random_values(7, 100);
+        let mut keys = vec![42];
+        partition_keys(&values, 13, &mut keys);
+        let expected: Vec<u32> = values.iter().map(|v| v.partition_key(13)).collect();
+        assert_eq!(keys, expected);
+
+        partition_keys(&[], 13, &mut keys);
+        assert!(keys.is_empty());
+    }
+
+    #[test]
+    fn test_to_fixed_i64() {
+        fn assert_fixed(
+            values: &[&str],
+            scale: i16,
+            mode: RoundingMode,
+            expected: Result<&[i64], (usize, FixedPointError)>,
+        ) {
+            let mut out = vec![42];
+            let result = to_fixed_i64(&parse_all(values), scale, mode, &mut out);
+            match expected {
+                Ok(expected) => {
+                    assert_eq!(result, Ok(()));
+                    assert_eq!(out, expected);
+                }
+                Err((i, err)) => {
+                    assert_eq!(result, Err((i, err)));
+                    assert_eq!(out.len(), i);
+                }
+            }
+        }
+
+        use RoundingMode::*;
+
+        // mixed input scales
+        assert_fixed(
+            &["1.5", "-2", "0.125", "1e3", "0", "-1e-130"],
+            2,
+            HalfUp,
+            Ok(&[150, -200, 13, 100000, 0, 0]),
+        );
+        assert_fixed(&["0.125", "-0.125", "0.135"], 2, HalfEven, Ok(&[12, -12, 14]));
+        assert_fixed(&["0.121", "-0.121"], 2, Ceiling, Ok(&[13, -12]));
+        assert_fixed(&["0.129", "-0.129"], 2, Floor, Ok(&[12, -13]));
+        assert_fixed(&["1e-130", "-1e-130"], 2, Up, Ok(&[1, -1]));
+        assert_fixed(&["123456", "-1.5"], -3, HalfUp, Ok(&[123, -0]));
+        assert_fixed(&[], 2, HalfUp, Ok(&[]));
+
+        // the boundaries of `i64`
+        assert_fixed(
+            &["9223372036854775807", "-9223372036854775808"],
+            0,
+            Down,
+            Ok(&[i64::MAX, i64::MIN]),
+        );
+        assert_fixed(
+            &["92233720368547758.07", "-92233720368547758.08"],
+            2,
+            Down,
+            Ok(&[i64::MAX, i64::MIN]),
+        );
+        assert_fixed(
+            &["9223372036854775807.4", "-9223372036854775808.4"],
+            0,
+            HalfUp,
+            Ok(&[i64::MAX, i64::MIN]),
+        );
+        assert_fixed(
+            &["1", "9223372036854775808"],
+            0,
+            HalfUp,
+            Err((1, FixedPointError::Overflow)),
+        );
+        assert_fixed(
+            &["-9223372036854775809"],
+            0,
+            HalfUp,
+            Err((0, FixedPointError::Overflow)),
+        );
+        assert_fixed(
+            &["9223372036854775807.5"],
+            0,
+            HalfUp,
+            Err((0, FixedPointError::Overflow)),
+        );
+        assert_fixed(&["-9223372036854775808.5"], 0, HalfDown, Ok(&[i64::MIN]));
+        assert_fixed(
+            &["-9223372036854775808.5"],
+            0,
+            HalfUp,
+            Err((0, FixedPointError::Overflow)),
+        );
+        assert_fixed(
+            &["92233720368547758.08"],
+            2,
+            HalfUp,
+            Err((0, FixedPointError::Overflow)),
+        );
+        assert_fixed(&["0", "1e-19", "1"], 19, HalfUp, Err((2, FixedPointError::Overflow)));
+        assert_fixed(&["1e20"], 0, HalfUp, Err((0, FixedPointError::Overflow)));
+        assert_fixed(&["1e-100"], 100, HalfUp, Ok(&[1]));
+        assert_fixed(&["1e-90"], 110, HalfUp, Err((0, FixedPointError::Overflow)));
+    }
+
+    #[test]
+    fn test_to_fixed_i64_exact() {
+        let mut out = vec![];
+        assert_eq!(
+            to_fixed_i64_exact(&parse_all(&["1.5", "-2.000", "1e3"]), 2, &mut out),
+            Ok(())
+        );
+        assert_eq!(out, [150, -200, 100000]);
+
+        let values = parse_all(&["1.5", "0.125"]);
+        assert_eq!(
+            to_fixed_i64_exact(&values, 2, &mut out),
+            Err((1, FixedPointError::Inexact))
+        );
+        assert_eq!(out, [150]);
+        let values = parse_all(&["1e-130"]);
+        assert_eq!(
+            to_fixed_i64_exact(&values, 2, &mut out),
+            Err((0, FixedPointError::Inexact))
+        );
+        let values = parse_all(&["92233720368547758.08"]);
+        assert_eq!(
+            to_fixed_i64_exact(&values, 2, &mut out),
+            Err((0, FixedPointError::Overflow))
+        );
+        let values = parse_all(&["-92233720368547758.08"]);
+        assert_eq!(to_fixed_i64_exact(&values, 2, &mut out), Ok(()));
+        assert_eq!(out, [i64::MIN]);
+    }
+
+    #[test]
+    fn test_from_fixed_i64() {
+        let mut out = vec![Decimal::ONE];
+        let fixed = [0, 1, -1, 150, i64::MAX, i64::MIN];
+        from_fixed_i64(&fixed, 2, &mut out);
+        let expected = parse_all(&[
+            "0",
+            "0.01",
+            "-0.01",
+            "1.5",
+            "92233720368547758.07",
+            "-92233720368547758.08",
+        ]);
+        assert_eq!(out, expected);
+        assert_eq!(out[0].into_parts(), (0, 0, false));
+
+        from_fixed_i64(&fixed, -3, &mut out);
+        assert_eq!(out[5], parse("-9223372036854775808e3"));
+
+        // round trip
+        let values = random_values(11, 1000);
+        let mut fixed = vec![];
+        to_fixed_i64_exact(&values, 3, &mut fixed).unwrap();
+        from_fixed_i64(&fixed, 3, &mut out);
+        assert_eq!(out, values);
+        for &mode in [RoundingMode::HalfEven, RoundingMode::Floor].iter() {
+            let mut rounded = vec![];
+            to_fixed_i64(&values, 1, mode, &mut rounded).unwrap();
+            from_fixed_i64(&rounded, 1, &mut out);
+            let expected: Vec<Decimal> = values.iter().map(|v| v.round_with_mode(1, mode)).collect();
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "scale is out of range")]
+    fn test_from_fixed_i64_scale() {
+        from_fixed_i64(&[1], MAX_SCALE + MAX_PRECISION as i16, &mut vec![]);
+    }
+}
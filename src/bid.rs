@@ -0,0 +1,358 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion to and from the IEEE 754-2008 decimal interchange formats in the binary integer decimal
+//! (BID) encoding.
+
+use crate::decimal::{Decimal, RoundingMode, MAX_PRECISION};
+use crate::error::DecimalConvertError;
+use crate::u256::{POWERS_10, U256};
+
+/// The parameters of a decimal interchange format.
+struct BidFormat {
+    /// The count of bits of the format.
+    bits: u32,
+    /// The count of bits of the biased exponent.
+    exp_bits: u32,
+    /// The count of digits of the coefficient.
+    digits: u32,
+    /// The bias of the exponent.
+    bias: i32,
+}
+
+const DECIMAL128: BidFormat = BidFormat {
+    bits: 128,
+    exp_bits: 14,
+    digits: 34,
+    bias: 6176,
+};
+
+const DECIMAL64: BidFormat = BidFormat {
+    bits: 64,
+    exp_bits: 10,
+    digits: 16,
+    bias: 398,
+};
+
+impl BidFormat {
+    /// The count of bits of the coefficient when it is stored after the exponent, i.e. if its top
+    /// bits aren't `100`.
+    #[inline]
+    const fn coef_bits(&self) -> u32 {
+        self.bits - 1 - self.exp_bits
+    }
+
+    /// Encodes `val`, rounding its coefficient half to even to the digits of the format.
+    ///
+    /// The exponent of a decimal is always in the range of both formats.
+    #[inline]
+    fn encode(&self, val: &Decimal) -> u128 {
+        let (mut coef, mut scale, negative) = val.into_parts();
+        let digits = val.precision() as u32;
+        if digits > self.digits {
+            let e = digits - self.digits;
+            coef = RoundingMode::HalfEven.div_pow10(coef, e, negative);
+            scale -= e as i16;
+            // rounding may carry into one more digit, e.g. 99..9.5 -> 100..0
+            if coef == POWERS_10[self.digits as usize].low() {
+                coef /= 10;
+                scale -= 1;
+            }
+        }
+
+        let exp = (self.bias - scale as i32) as u128;
+        debug_assert!(exp < 3 << (self.exp_bits - 2));
+
+        let coef_bits = self.coef_bits();
+        let word = if coef >> coef_bits == 0 {
+            exp << coef_bits | coef
+        } else {
+            // The coefficient starts with `100`, which is implied by the `11` before the exponent.
+            3 << (self.bits - 3) | exp << (coef_bits - 2) | (coef & ((1 << (coef_bits - 2)) - 1))
+        };
+        word | (negative as u128) << (self.bits - 1)
+    }
+
+    /// Decodes `word`, failing with `Invalid` for a NaN, and with `Overflow` for an infinity or a
+    /// non-zero magnitude out of the range of a decimal.
+    #[inline]
+    fn decode(&self, word: u128) -> Result<Decimal, DecimalConvertError> {
+        let negative = (word >> (self.bits - 1)) & 1 == 1;
+        let combination = (word >> (self.bits - 6)) & 0x1f;
+        let coef_bits = self.coef_bits();
+        let exp_mask = (1 << self.exp_bits) - 1;
+
+        let (exp, coef) = match combination {
+            0x1f => return Err(DecimalConvertError::Invalid),
+            0x1e => return Err(DecimalConvertError::Overflow),
+            _ if combination >> 3 == 3 => {
+                // The `11` before the exponent stands for a coefficient starting with `100`.
+                let coef = 4 << (coef_bits - 2) | (word & ((1 << (coef_bits - 2)) - 1));
+                ((word >> (coef_bits - 2)) & exp_mask, coef)
+            }
+            _ => ((word >> coef_bits) & exp_mask, word & ((1 << coef_bits) - 1)),
+        };
+
+        // A coefficient larger than the largest one isn't canonical, and is zero.
+        if coef == 0 || coef >= POWERS_10[self.digits as usize].low() {
+            return Ok(Decimal::ZERO);
+        }
+
+        let scale = (self.bias - exp as i32) as i16;
+        match Decimal::adjust_scale(U256::from(coef), scale, negative) {
+            Some(val) if !val.is_zero() => {
+                debug_assert!(val.precision() as u32 <= MAX_PRECISION);
+                Ok(val)
+            }
+            _ => Err(DecimalConvertError::Overflow),
+        }
+    }
+}
+
+impl Decimal {
+    /// Encodes `self` as an IEEE 754-2008 decimal128 in the BID encoding, in little-endian byte order.
+    ///
+    /// The coefficient is rounded half to even to 34 digits. A zero is encoded as the positive zero
+    /// with exponent 0.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n: Decimal = "-12.5".parse().unwrap();
+    /// let bytes = n.to_decimal128_bid();
+    /// assert_eq!(u128::from_le_bytes(bytes), 0xB03E_0000_0000_0000_0000_0000_0000_007D);
+    /// assert_eq!(Decimal::from_decimal128_bid(bytes), Ok(n));
+    /// ```
+    #[inline]
+    pub fn to_decimal128_bid(&self) -> [u8; 16] {
+        DECIMAL128.encode(self).to_le_bytes()
+    }
+
+    /// Decodes an IEEE 754-2008 decimal128 in the BID encoding, in little-endian byte order.
+    ///
+    /// Fails with `Invalid` for a NaN, and with `Overflow` for an infinity or a non-zero magnitude out of
+    /// the range of a decimal, i.e. from `1E+126` or below `1E-130`. A zero is [`Decimal::ZERO`],
+    /// whatever its sign and exponent, and so is a non-canonical coefficient.
+    #[inline]
+    pub fn from_decimal128_bid(bytes: [u8; 16]) -> Result<Decimal, DecimalConvertError> {
+        DECIMAL128.decode(u128::from_le_bytes(bytes))
+    }
+
+    /// Encodes `self` as an IEEE 754-2008 decimal64 in the BID encoding, in little-endian byte order.
+    ///
+    /// The coefficient is rounded half to even to 16 digits, as [`Decimal::to_decimal128_bid`] does.
+    #[inline]
+    pub fn to_decimal64_bid(&self) -> [u8; 8] {
+        (DECIMAL64.encode(self) as u64).to_le_bytes()
+    }
+
+    /// Decodes an IEEE 754-2008 decimal64 in the BID encoding, in little-endian byte order, as
+    /// [`Decimal::from_decimal128_bid`] does.
+    #[inline]
+    pub fn from_decimal64_bid(bytes: [u8; 8]) -> Result<Decimal, DecimalConvertError> {
+        DECIMAL64.decode(u64::from_le_bytes(bytes) as u128)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_decimal128_bid() {
+        fn assert_bid(val: &str, bits: u128) {
+            let val = parse(val);
+            assert_eq!(u128::from_le_bytes(val.to_decimal128_bid()), bits, "{}", val);
+            assert_eq!(Decimal::from_decimal128_bid(bits.to_le_bytes()), Ok(val), "{:x}", bits);
+        }
+
+        fn assert_encode(val: &str, bits: u128) {
+            let val = parse(val);
+            assert_eq!(u128::from_le_bytes(val.to_decimal128_bid()), bits, "{}", val);
+        }
+
+        fn assert_decode(bits: u128, expected: Result<&str, DecimalConvertError>) {
+            assert_eq!(
+                Decimal::from_decimal128_bid(bits.to_le_bytes()),
+                expected.map(parse),
+                "{:x}",
+                bits
+            );
+        }
+
+        assert_bid("0", 0x3040_0000_0000_0000_0000_0000_0000_0000);
+        assert_bid("1", 0x3040_0000_0000_0000_0000_0000_0000_0001);
+        assert_bid("-1", 0xB040_0000_0000_0000_0000_0000_0000_0001);
+        assert_bid("0.1", 0x303E_0000_0000_0000_0000_0000_0000_0001);
+        assert_bid("-12.5", 0xB03E_0000_0000_0000_0000_0000_0000_007D);
+        assert_bid("1e100", 0x3108_0000_0000_0000_0000_0000_0000_0001);
+        assert_bid("1e-130", 0x2F3C_0000_0000_0000_0000_0000_0000_0001);
+        assert_bid(
+            "9.999999999999999999999999999999999",
+            0x2FFF_ED09_BEAD_87C0_378D_8E63_FFFF_FFFF,
+        );
+        assert_bid(
+            "9.999999999999999999999999999999999e125",
+            0x30F9_ED09_BEAD_87C0_378D_8E63_FFFF_FFFF,
+        );
+
+        // rounded half to even to 34 digits
+        assert_encode(
+            "1.23456789012345678901234567890123450",
+            0x3040_0000_0000_0000_0000_0000_0000_0000 - (33 << 113) + 1234567890123456789012345678901234,
+        );
+        assert_encode(
+            "1.2345678901234567890123456789012345",
+            0x3040_0000_0000_0000_0000_0000_0000_0000 - (33 << 113) + 1234567890123456789012345678901234,
+        );
+        assert_encode(
+            "1.2345678901234567890123456789012335",
+            0x3040_0000_0000_0000_0000_0000_0000_0000 - (33 << 113) + 1234567890123456789012345678901234,
+        );
+        assert_encode(
+            "1.23456789012345678901234567890123451",
+            0x3040_0000_0000_0000_0000_0000_0000_0000 - (33 << 113) + 1234567890123456789012345678901235,
+        );
+        assert_encode(
+            "-99999999999999999999999999999999999999",
+            0xB040_0000_0000_0000_0000_0000_0000_0000 + (5 << 113) + 1000000000000000000000000000000000,
+        );
+        let max = Decimal::MAX.to_decimal128_bid();
+        assert_eq!(Decimal::from_decimal128_bid(max), Err(DecimalConvertError::Overflow));
+
+        // zeros, whatever the sign and the exponent
+        assert_decode(0xB040_0000_0000_0000_0000_0000_0000_0000, Ok("0"));
+        assert_decode(0x0000_0000_0000_0000_0000_0000_0000_0000, Ok("0"));
+        assert_decode(0xDFFE_0000_0000_0000_0000_0000_0000_0000, Ok("0"));
+        // non-canonical coefficients
+        assert_decode(0x2FFF_ED09_BEAD_87C0_378D_8E64_0000_0000, Ok("0"));
+        assert_decode(0x6C10_0000_0000_0000_0000_0000_0000_0001, Ok("0"));
+
+        // out of range
+        assert_decode(
+            0x5FFF_ED09_BEAD_87C0_378D_8E63_FFFF_FFFF,
+            Err(DecimalConvertError::Overflow),
+        );
+        assert_decode(
+            0xDFFF_ED09_BEAD_87C0_378D_8E63_FFFF_FFFF,
+            Err(DecimalConvertError::Overflow),
+        );
+        assert_decode(
+            0x313C_0000_0000_0000_0000_0000_0000_000A,
+            Err(DecimalConvertError::Overflow),
+        );
+        assert_decode(
+            0x0000_0000_0000_0000_0000_0000_0000_0001,
+            Err(DecimalConvertError::Overflow),
+        );
+        assert_decode(
+            0x2F3A_0000_0000_0000_0000_0000_0000_0009,
+            Err(DecimalConvertError::Overflow),
+        );
+        assert_decode(0x2F3A_0000_0000_0000_0000_0000_0000_000A, Ok("1e-130"));
+        assert_decode(
+            0x313A_0000_0000_0000_0000_0000_0000_000A,
+            Err(DecimalConvertError::Overflow),
+        );
+
+        // special values
+        assert_decode(
+            0x7800_0000_0000_0000_0000_0000_0000_0000,
+            Err(DecimalConvertError::Overflow),
+        );
+        assert_decode(
+            0xF800_0000_0000_0000_0000_0000_0000_0000,
+            Err(DecimalConvertError::Overflow),
+        );
+        assert_decode(
+            0x7C00_0000_0000_0000_0000_0000_0000_0000,
+            Err(DecimalConvertError::Invalid),
+        );
+        assert_decode(
+            0x7E00_0000_0000_0000_0000_0000_0000_0000,
+            Err(DecimalConvertError::Invalid),
+        );
+
+        // Every decimal of at most 34 digits round-trips.
+        for val in crate::testing::corpus().iter() {
+            let bytes = val.to_decimal128_bid();
+            let back = Decimal::from_decimal128_bid(bytes);
+            if val.precision() <= 34 {
+                assert_eq!(back, Ok(*val), "{}", val);
+            } else if let Ok(back) = back {
+                let scale = val.scale() - (val.precision() as i16 - 34);
+                assert_eq!(back, val.round_with_mode(scale, RoundingMode::HalfEven), "{}", val);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decimal64_bid() {
+        fn assert_bid(val: &str, bits: u64) {
+            let val = parse(val);
+            assert_eq!(u64::from_le_bytes(val.to_decimal64_bid()), bits, "{}", val);
+            assert_eq!(Decimal::from_decimal64_bid(bits.to_le_bytes()), Ok(val), "{:x}", bits);
+        }
+
+        fn assert_decode(bits: u64, expected: Result<&str, DecimalConvertError>) {
+            assert_eq!(
+                Decimal::from_decimal64_bid(bits.to_le_bytes()),
+                expected.map(parse),
+                "{:x}",
+                bits
+            );
+        }
+
+        assert_bid("0", 0x31C0_0000_0000_0000);
+        assert_bid("1", 0x31C0_0000_0000_0001);
+        assert_bid("-1", 0xB1C0_0000_0000_0001);
+        assert_bid("-12.5", 0xB1A0_0000_0000_007D);
+        assert_bid("9007199254740991", 0x31DF_FFFF_FFFF_FFFF);
+        // coefficients from 2^53 start with `100`
+        assert_bid("9007199254740992", 0x6C70_0000_0000_0000);
+        assert_bid("9999999999999999", 0x6C73_86F2_6FC0_FFFF);
+        assert_bid("9.999999999999999e125", 0x6FE3_86F2_6FC0_FFFF);
+
+        assert_eq!(
+            u64::from_le_bytes(parse("3.14159265358979323846").to_decimal64_bid()),
+            0x2FEB_2943_0A25_6D21
+        );
+        assert_eq!(
+            Decimal::from_decimal64_bid(parse("0.12345678901234565").to_decimal64_bid()),
+            Ok(parse("0.1234567890123456"))
+        );
+        assert_eq!(
+            Decimal::from_decimal64_bid(parse("0.99999999999999995").to_decimal64_bid()),
+            Ok(parse("1"))
+        );
+
+        assert_decode(0xB1C0_0000_0000_0000, Ok("0"));
+        assert_decode(0x6C7F_FFFF_FFFF_FFFF, Ok("0"));
+        assert_decode(0x77FB_86F2_6FC0_FFFF, Err(DecimalConvertError::Overflow));
+        assert_decode(0x0000_0000_0000_0001, Err(DecimalConvertError::Overflow));
+        assert_decode(0x7800_0000_0000_0000, Err(DecimalConvertError::Overflow));
+        assert_decode(0x7C00_0000_0000_0000, Err(DecimalConvertError::Invalid));
+
+        for val in crate::testing::corpus().iter() {
+            let back = Decimal::from_decimal64_bid(val.to_decimal64_bid());
+            if val.precision() <= 16 {
+                assert_eq!(back, Ok(*val), "{}", val);
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions from `bigdecimal::BigDecimal`.
+
+use crate::decimal::{Decimal, MAX_SCALE, MIN_SCALE};
+use crate::error::DecimalConvertError;
+use crate::u256::U256;
+use bigdecimal::num_bigint::{BigUint, Sign};
+use bigdecimal::{BigDecimal, Zero};
+use core::convert::TryFrom;
+
+/// The count of bits of the mantissa kept before rounding, which makes at most 76 digits.
+const MAX_BITS: u64 = 250;
+
+/// Rounds half away from zero to 38 significant digits, as parsing does.
+///
+/// Fails with `Overflow` if the magnitude is from `1E+126`, or if it is non-zero and below `1E-130`.
+#[cfg_attr(docsrs, doc(cfg(feature = "bigdecimal")))]
+impl TryFrom<&BigDecimal> for Decimal {
+    type Error = DecimalConvertError;
+
+    #[inline]
+    fn try_from(val: &BigDecimal) -> Result<Self, Self::Error> {
+        let (int_val, mut scale) = val.as_bigint_and_exponent();
+        let (sign, mut mag) = int_val.into_parts();
+        if mag.is_zero() {
+            return Ok(Decimal::ZERO);
+        }
+
+        let bits = mag.bits();
+        if bits > MAX_BITS {
+            // `10^shift > 10 * 2^(bits - MAX_BITS)`, so the quotient and a sticky digit standing for
+            // the remainder still fit in `MAX_BITS` bits.
+            let shift = (bits - MAX_BITS) * 30103 / 100000 + 2;
+            let divisor = BigUint::from(10u32).pow(shift as u32);
+            let rem = &mag % &divisor;
+            mag = mag / divisor * 10u32 + BigUint::from(!rem.is_zero() as u32);
+            scale -= shift as i64 - 1;
+        }
+
+        let mut words = [0u64; 4];
+        for (word, digit) in words.iter_mut().zip(mag.to_u64_digits()) {
+            *word = digit;
+        }
+        let int_val = U256::from_u128(
+            words[0] as u128 | (words[1] as u128) << 64,
+            words[2] as u128 | (words[3] as u128) << 64,
+        );
+
+        let s = scale - int_val.count_digits() as i64;
+        if s >= MAX_SCALE as i64 || s < MIN_SCALE as i64 {
+            return Err(DecimalConvertError::Overflow);
+        }
+
+        match Decimal::adjust_scale(int_val, scale as i16, sign == Sign::Minus) {
+            Some(val) if !val.is_zero() => Ok(val),
+            _ => Err(DecimalConvertError::Overflow),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigdecimal::num_bigint::BigInt;
+    use std::str::FromStr;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn assert_from(val: &BigDecimal, expected: Result<&str, DecimalConvertError>) {
+        assert_eq!(Decimal::try_from(val), expected.map(parse), "{}", val);
+    }
+
+    fn assert_from_str(val: &str, expected: Result<&str, DecimalConvertError>) {
+        assert_from(&BigDecimal::from_str(val).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_from_bigdecimal() {
+        assert_from_str("0", Ok("0"));
+        assert_from_str("-0.000", Ok("0"));
+        assert_from_str("123.456", Ok("123.456"));
+        assert_from_str("-123.456", Ok("-123.456"));
+        assert_from_str("1e125", Ok("1e125"));
+        assert_from_str("-1e-130", Ok("-1e-130"));
+        assert_from_str(
+            "-99999999999999999999999999999999999999e88",
+            Ok("-99999999999999999999999999999999999999e88"),
+        );
+        assert_from(&BigDecimal::new(BigInt::from(-15), -3), Ok("-15000"));
+
+        // rounded half away from zero to 38 digits
+        assert_from_str(
+            "123456789012345678901234567890123456785",
+            Ok("123456789012345678901234567890123456790"),
+        );
+        assert_from_str(
+            "-123456789012345678901234567890123456784.999",
+            Ok("-123456789012345678901234567890123456780"),
+        );
+        assert_from_str(
+            "0.333333333333333333333333333333333333333333333333",
+            Ok("0.33333333333333333333333333333333333333"),
+        );
+
+        // out of range
+        assert_from_str("1e126", Err(DecimalConvertError::Overflow));
+        assert_from_str("-1e126", Err(DecimalConvertError::Overflow));
+        assert_from_str(
+            "99999999999999999999999999999999999999.5e88",
+            Err(DecimalConvertError::Overflow),
+        );
+        assert_from_str("1e-131", Err(DecimalConvertError::Overflow));
+        assert_from_str("-1e-131", Err(DecimalConvertError::Overflow));
+        assert_from_str("1e100000", Err(DecimalConvertError::Overflow));
+        assert_from_str("1e-100000", Err(DecimalConvertError::Overflow));
+    }
+
+    #[test]
+    fn test_from_long_bigdecimal() {
+        fn big(s: &str, scale: i64) -> BigDecimal {
+            BigDecimal::new(BigInt::from_str(s).unwrap(), scale)
+        }
+
+        let one = format!("1{}", "0".repeat(1000));
+        assert_from(&big(&one, 1000), Ok("1"));
+        assert_from(&big(&format!("-{}", one), 1100), Ok("-1e-100"));
+        assert_from(&big(&one, 874), Err(DecimalConvertError::Overflow));
+        assert_from(&big(&one, 875), Ok("1e125"));
+
+        // The digits far after the 38th one still round.
+        let half = format!("1{}5{}", "0".repeat(37), "0".repeat(300));
+        assert_from(&big(&half, 300), Ok("10000000000000000000000000000000000001e1"));
+        let below_half = format!("1{}4{}", "0".repeat(37), "9".repeat(300));
+        assert_from(&big(&below_half, 300), Ok("1e38"));
+        let below_half = format!("-1{}44{}", "0".repeat(36), "9".repeat(300));
+        assert_from(&big(&below_half, 301), Ok("-10000000000000000000000000000000000004"));
+        let nines = "9".repeat(500);
+        assert_from(&big(&nines, 500), Ok("1"));
+        assert_from(&big(&nines, 375), Ok("1e125"));
+        assert_from(&big(&nines, 374), Err(DecimalConvertError::Overflow));
+    }
+}
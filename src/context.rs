@@ -0,0 +1,518 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Arithmetic under a chosen rounding mode and precision.
+
+use crate::decimal::{Decimal, RoundingMode, MAX_PRECISION, MAX_SCALE, MIN_SCALE};
+use crate::error::DecimalConvertError;
+use crate::u256::{POWERS_10, U256};
+use core::cmp::Ordering;
+
+/// Runs a sequence of arithmetic operations under one rounding mode.
+///
+/// Each operation rounds its exact result once to `MAX_PRECISION` significant digits with the
+/// rounding mode of the context, whereas the operators of [`Decimal`] always round half up.
+///
+/// ```
+/// use decimal_rs::{Decimal, DecimalContext, RoundingMode};
+///
+/// let ctx = DecimalContext::new(RoundingMode::Down);
+/// let third = ctx.div(&Decimal::from(1), &Decimal::from(3)).unwrap();
+/// let two_thirds = ctx.add(&third, &third).unwrap();
+/// assert_eq!(two_thirds.to_string(), "0.66666666666666666666666666666666666666");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DecimalContext {
+    rounding: RoundingMode,
+}
+
+impl Default for DecimalContext {
+    /// Returns a context rounding half up, the same as the operators of [`Decimal`].
+    #[inline]
+    fn default() -> Self {
+        DecimalContext::new(RoundingMode::HalfUp)
+    }
+}
+
+impl DecimalContext {
+    /// Creates a context rounding with `rounding`.
+    #[inline]
+    pub const fn new(rounding: RoundingMode) -> DecimalContext {
+        DecimalContext { rounding }
+    }
+
+    /// Returns the rounding mode of the context.
+    #[inline]
+    pub fn rounding(&self) -> RoundingMode {
+        self.rounding
+    }
+
+    /// Computes `a + b`, returning `None` if overflow occurred.
+    #[inline]
+    pub fn add(&self, a: &Decimal, b: &Decimal) -> Option<Decimal> {
+        a.add_with_mode(b, self.rounding)
+    }
+
+    /// Computes `a - b`, returning `None` if overflow occurred.
+    #[inline]
+    pub fn sub(&self, a: &Decimal, b: &Decimal) -> Option<Decimal> {
+        a.add_with_mode(&-*b, self.rounding)
+    }
+
+    /// Computes `a * b`, returning `None` if overflow occurred.
+    #[inline]
+    pub fn mul(&self, a: &Decimal, b: &Decimal) -> Option<Decimal> {
+        a.mul_with_mode(b, self.rounding)
+    }
+
+    /// Computes `a / b`, returning `None` if `b == 0` or overflow occurred.
+    #[inline]
+    pub fn div(&self, a: &Decimal, b: &Decimal) -> Option<Decimal> {
+        a.checked_div_with_rounding(b, self.rounding)
+    }
+
+    /// Rounds `val` to have `scale` digits after the decimal point.
+    #[inline]
+    pub fn round(&self, val: &Decimal, scale: i16) -> Decimal {
+        val.round_with_mode(scale, self.rounding)
+    }
+}
+
+/// Runs a sequence of arithmetic operations on values of the SQL type `NUMBER(precision, scale)`.
+///
+/// Each result is rounded once to `scale` digits after the decimal point with the rounding mode of
+/// the context, like [`Decimal::round_with_precision`] rounds half up, and an error is returned if
+/// it then has more than `precision` significant digits.
+///
+/// ```
+/// use decimal_rs::{Decimal, DecimalConvertError, NumericContext, RoundingMode};
+///
+/// let ctx = NumericContext::new(5, 2, RoundingMode::HalfEven);
+/// let price: Decimal = "12.25".parse().unwrap();
+/// assert_eq!(ctx.div(&price, &Decimal::from(2)).unwrap().to_string(), "6.12");
+/// assert_eq!(ctx.mul(&price, &Decimal::from(10)).unwrap().scale(), 2);
+/// assert_eq!(ctx.mul(&price, &Decimal::from(10000)), Err(DecimalConvertError::Overflow));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NumericContext {
+    precision: u8,
+    scale: i16,
+    rounding: RoundingMode,
+}
+
+impl NumericContext {
+    /// Creates a context for `NUMBER(precision, scale)` rounding with `rounding`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `precision` is not in `1..=MAX_PRECISION` or `scale` is not in `MIN_SCALE..=MAX_SCALE`.
+    #[inline]
+    pub fn new(precision: u8, scale: i16, rounding: RoundingMode) -> NumericContext {
+        assert!(
+            precision >= 1 && precision as u32 <= MAX_PRECISION,
+            "precision is out of range"
+        );
+        assert!((MIN_SCALE..=MAX_SCALE).contains(&scale), "scale is out of range");
+        NumericContext {
+            precision,
+            scale,
+            rounding,
+        }
+    }
+
+    /// Returns the maximum number of significant digits of a result.
+    #[inline]
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Returns the number of digits after the decimal point of a result.
+    #[inline]
+    pub fn scale(&self) -> i16 {
+        self.scale
+    }
+
+    /// Returns the rounding mode of the context.
+    #[inline]
+    pub fn rounding(&self) -> RoundingMode {
+        self.rounding
+    }
+
+    /// Rounds `val` to the precision and scale of the context,
+    /// returning an error if it has too many significant digits.
+    #[inline]
+    pub fn round(&self, val: &Decimal) -> Result<Decimal, DecimalConvertError> {
+        if val.is_zero() {
+            return Ok(Decimal::ZERO);
+        }
+        let (int_val, scale, negative) = val.into_parts();
+        self.round_parts(U256::from(int_val), scale, negative)
+    }
+
+    /// Computes `a + b`, returning an error if the result overflows.
+    #[inline]
+    pub fn add(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalConvertError> {
+        if a.is_zero() {
+            return self.round(b);
+        }
+        if b.is_zero() {
+            return self.round(a);
+        }
+        let (int_val, scale, negative) = a.add_parts(b).ok_or(DecimalConvertError::Overflow)?;
+        self.round_parts(int_val, scale, negative)
+    }
+
+    /// Computes `a - b`, returning an error if the result overflows.
+    #[inline]
+    pub fn sub(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalConvertError> {
+        self.add(a, &-*b)
+    }
+
+    /// Computes `a * b`, returning an error if the result overflows.
+    #[inline]
+    pub fn mul(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalConvertError> {
+        if a.is_zero() || b.is_zero() {
+            return Ok(Decimal::ZERO);
+        }
+        let int_val = U256::mul128(a.int_val(), b.int_val());
+        let negative = a.is_sign_negative() ^ b.is_sign_negative();
+        self.round_parts(int_val, a.scale() + b.scale(), negative)
+    }
+
+    /// Computes `a / b`, returning [`DecimalConvertError::Invalid`] if `b == 0`,
+    /// or an error if the result overflows.
+    #[inline]
+    pub fn div(&self, a: &Decimal, b: &Decimal) -> Result<Decimal, DecimalConvertError> {
+        if b.is_zero() {
+            return Err(DecimalConvertError::Invalid);
+        }
+        if a.is_zero() {
+            return Ok(Decimal::ZERO);
+        }
+        let (int_val, scale, negative) = a.div_parts(b);
+        self.round_parts(int_val, scale, negative)
+    }
+
+    /// Rounds `int_val * 10^-scale` once to the scale of the context.
+    #[inline]
+    fn round_parts(&self, int_val: U256, scale: i16, negative: bool) -> Result<Decimal, DecimalConvertError> {
+        round_to_scale(int_val, scale, negative, self.precision, self.scale, self.rounding)
+    }
+}
+
+/// Rounds `int_val * 10^-scale` once to `target` digits after the decimal point with `mode`,
+/// returning an error if the result has more than `precision` digits.
+#[inline]
+pub(crate) fn round_to_scale(
+    mut int_val: U256,
+    scale: i16,
+    negative: bool,
+    precision: u8,
+    target: i16,
+    mode: RoundingMode,
+) -> Result<Decimal, DecimalConvertError> {
+    debug_assert!(precision as u32 <= MAX_PRECISION);
+
+    if int_val == 0 {
+        return Ok(Decimal::ZERO);
+    }
+
+    // At most 38 digits are kept, so the digits after the 41st only matter as a sticky digit.
+    let mut scale = scale as i32;
+    let digits = int_val.count_digits();
+    if digits > 41 {
+        let shift = digits - 41;
+        let (quotient, rem) = int_val.div_rem(POWERS_10[shift as usize]);
+        int_val = quotient * 10 + (rem != 0) as u128;
+        scale -= shift as i32 - 1;
+    }
+
+    let drop = scale - target as i32;
+    let mantissa = if drop <= 0 {
+        let e = (-drop) as u32;
+        if int_val.count_digits() + e > precision as u32 {
+            return Err(DecimalConvertError::Overflow);
+        }
+        int_val * POWERS_10[e as usize].low()
+    } else {
+        let (quotient, rem, half) = if drop as usize >= POWERS_10.len() {
+            (U256::ZERO, int_val, Ordering::Less)
+        } else {
+            let divisor = POWERS_10[drop as usize];
+            let (quotient, rem) = int_val.div_rem(divisor);
+            (quotient, rem, (rem * 2).cmp(&divisor))
+        };
+        if mode.round_up(half, rem == 0, quotient.low() % 2 == 1, negative) {
+            quotient + 1
+        } else {
+            quotient
+        }
+    };
+
+    if mantissa >= POWERS_10[precision as usize] {
+        return Err(DecimalConvertError::Overflow);
+    }
+    if mantissa == 0 {
+        return Ok(Decimal::ZERO);
+    }
+    Ok(unsafe { Decimal::from_parts_unchecked(mantissa.low(), target, negative) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_div_with_rounding() {
+        fn assert_div(val1: &str, val2: &str, mode: RoundingMode, expected: &str) {
+            let result = parse(val1).checked_div_with_rounding(parse(val2), mode).unwrap();
+            assert_eq!(result, parse(expected), "{} / {} {:?}", val1, val2, mode);
+        }
+
+        assert_div("1", "3", RoundingMode::Down, "0.33333333333333333333333333333333333333");
+        assert_div("1", "3", RoundingMode::Up, "0.33333333333333333333333333333333333334");
+        assert_div(
+            "1",
+            "3",
+            RoundingMode::HalfEven,
+            "0.33333333333333333333333333333333333333",
+        );
+        assert_div(
+            "-2",
+            "3",
+            RoundingMode::Down,
+            "-0.66666666666666666666666666666666666666",
+        );
+        assert_div(
+            "-2",
+            "3",
+            RoundingMode::Floor,
+            "-0.66666666666666666666666666666666666667",
+        );
+        assert_div(
+            "-2",
+            "3",
+            RoundingMode::Ceiling,
+            "-0.66666666666666666666666666666666666666",
+        );
+        assert_div("7.5", "2.5", RoundingMode::Up, "3");
+        assert_div("0", "3", RoundingMode::Up, "0");
+
+        // the discarded part is exactly one half
+        let tie = "99999999999999999999999999999999999997";
+        assert_div(tie, "2", RoundingMode::HalfUp, "49999999999999999999999999999999999999");
+        assert_div(
+            tie,
+            "2",
+            RoundingMode::HalfEven,
+            "49999999999999999999999999999999999998",
+        );
+        assert_div(
+            tie,
+            "2",
+            RoundingMode::HalfDown,
+            "49999999999999999999999999999999999998",
+        );
+        assert_div(
+            tie,
+            "-2",
+            RoundingMode::HalfEven,
+            "-49999999999999999999999999999999999998",
+        );
+        assert_div(
+            "99999999999999999999999999999999999999",
+            "2",
+            RoundingMode::HalfEven,
+            "50000000000000000000000000000000000000",
+        );
+
+        // the discarded part is just below one half, which must not be rounded twice
+        assert_div(
+            "99E98",
+            "254",
+            RoundingMode::HalfUp,
+            "3.8976377952755905511811023622047244094E97",
+        );
+
+        // rounding to the smallest magnitude
+        assert_div("1E-130", "3", RoundingMode::Down, "0");
+        assert_div("1E-130", "3", RoundingMode::Up, "1E-130");
+        assert_div("-1E-130", "3", RoundingMode::Floor, "-1E-130");
+
+        assert!(Decimal::ONE
+            .checked_div_with_rounding(Decimal::ZERO, RoundingMode::Down)
+            .is_none());
+        assert!(parse("1E125")
+            .checked_div_with_rounding(parse("1E-10"), RoundingMode::Down)
+            .is_none());
+    }
+
+    #[test]
+    fn test_div_with_rounding_bounds() {
+        let values = crate::testing::corpus_random(7, 300);
+        for a in values.iter() {
+            for b in values.iter().filter(|b| !b.is_zero()) {
+                let floor = a.checked_div_with_rounding(b, RoundingMode::Floor);
+                let ceiling = a.checked_div_with_rounding(b, RoundingMode::Ceiling);
+                let (floor, ceiling) = match (floor, ceiling) {
+                    (Some(floor), Some(ceiling)) => (floor, ceiling),
+                    _ => continue,
+                };
+                assert!(floor <= ceiling, "{} / {}", a, b);
+                for &mode in [RoundingMode::HalfUp, RoundingMode::HalfEven, RoundingMode::Down].iter() {
+                    let result = a.checked_div_with_rounding(b, mode).unwrap();
+                    assert!(floor <= result && result <= ceiling, "{} / {} {:?}", a, b, mode);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_context() {
+        let ctx = DecimalContext::default();
+        assert_eq!(ctx.rounding(), RoundingMode::HalfUp);
+
+        // rounding half up is the same as the operators, except that the context always flushes
+        // a magnitude below `1E-130` to zero
+        let min = Decimal::from_parts(1, MAX_SCALE, false).unwrap();
+        let flushed = |expected: Option<Decimal>| expected.map(|v| if v.abs() < min { Decimal::ZERO } else { v });
+        let values = crate::testing::corpus_random(11, 300);
+        for a in values.iter() {
+            for b in values.iter() {
+                assert_eq!(ctx.add(a, b), flushed(a.checked_add(b)), "{} + {}", a, b);
+                assert_eq!(ctx.sub(a, b), flushed(a.checked_sub(b)), "{} - {}", a, b);
+                assert_eq!(ctx.mul(a, b), a.checked_mul(b), "{} * {}", a, b);
+            }
+        }
+
+        let ctx = DecimalContext::new(RoundingMode::Down);
+        let third = ctx.div(&Decimal::ONE, &parse("3")).unwrap();
+        assert_eq!(
+            ctx.mul(&third, &parse("3")).unwrap().to_string(),
+            "0.99999999999999999999999999999999999999"
+        );
+        assert_eq!(
+            ctx.sub(&Decimal::ONE, &third).unwrap().to_string(),
+            "0.66666666666666666666666666666666666667"
+        );
+        assert_eq!(
+            ctx.add(&parse("1E20"), &parse("-1E-20")).unwrap().to_string(),
+            "99999999999999999999.999999999999999999"
+        );
+        assert_eq!(ctx.round(&parse("2.9"), 0), parse("2"));
+        assert_eq!(ctx.div(&Decimal::ONE, &Decimal::ZERO), None);
+
+        let ctx = DecimalContext::new(RoundingMode::HalfEven);
+        assert_eq!(ctx.round(&parse("2.5"), 0), parse("2"));
+        assert_eq!(
+            ctx.mul(&parse("99999999999999999999999999999999999997"), &parse("0.5"))
+                .unwrap()
+                .to_string(),
+            "49999999999999999999999999999999999998"
+        );
+    }
+
+    #[test]
+    fn test_numeric_context() {
+        let ctx = NumericContext::new(20, 4, RoundingMode::Down);
+        assert_eq!(
+            (ctx.precision(), ctx.scale(), ctx.rounding()),
+            (20, 4, RoundingMode::Down)
+        );
+        assert_eq!(ctx.div(&Decimal::ONE, &parse("3")), Ok(parse("0.3333")));
+        assert_eq!(ctx.div(&parse("-2"), &parse("3")), Ok(parse("-0.6666")));
+        assert_eq!(ctx.add(&parse("1.00005"), &parse("2")), Ok(parse("3.0000")));
+        assert_eq!(ctx.sub(&parse("1"), &parse("0.00001")), Ok(parse("0.9999")));
+        assert_eq!(ctx.mul(&parse("1.5"), &parse("1.5")), Ok(parse("2.2500")));
+        assert_eq!(ctx.mul(&parse("1.5"), &parse("1.5")).unwrap().scale(), 4);
+        assert_eq!(ctx.add(&parse("0.00001"), &parse("-0.00001")), Ok(Decimal::ZERO));
+        assert_eq!(ctx.mul(&parse("0.001"), &parse("0.01")), Ok(Decimal::ZERO));
+
+        // at most 16 digits before the decimal point
+        let max = parse("9999999999999999.9999");
+        assert_eq!(ctx.round(&max), Ok(max));
+        assert_eq!(ctx.add(&max, &parse("0.0001")), Err(DecimalConvertError::Overflow));
+        assert_eq!(
+            ctx.mul(&parse("1E15"), &parse("10")),
+            Err(DecimalConvertError::Overflow)
+        );
+        assert_eq!(ctx.round(&parse("1E16")), Err(DecimalConvertError::Overflow));
+        assert_eq!(
+            ctx.div(&Decimal::ONE, &Decimal::ZERO),
+            Err(DecimalConvertError::Invalid)
+        );
+
+        let ctx = NumericContext::new(20, 4, RoundingMode::HalfEven);
+        assert_eq!(ctx.div(&parse("0.0001"), &parse("2")), Ok(Decimal::ZERO));
+        assert_eq!(ctx.div(&parse("0.0003"), &parse("2")), Ok(parse("0.0002")));
+        assert_eq!(ctx.round(&parse("-2.00025")), Ok(parse("-2.0002")));
+        assert_eq!(ctx.div(&parse("0.0001"), &parse("3")), Ok(Decimal::ZERO));
+
+        // rounded once, not at 38 digits first
+        let ctx = NumericContext::new(5, 0, RoundingMode::HalfUp);
+        let a = parse("0.49999999999999999999999999999999999999");
+        assert_eq!(ctx.add(&a, &parse("6E-39")), Ok(Decimal::ZERO));
+        assert_eq!(ctx.add(&a, &parse("1E-38")), Ok(parse("1")));
+        assert_eq!(
+            ctx.div(&parse("99999999999999999999999999999999999999"), &parse("2E38")),
+            Ok(Decimal::ZERO)
+        );
+
+        // negative scale and the smallest magnitudes
+        let ctx = NumericContext::new(3, -2, RoundingMode::Up);
+        assert_eq!(ctx.mul(&parse("1234"), &parse("1")), Ok(parse("1300")));
+        assert_eq!(ctx.div(&parse("1E-130"), &parse("3")), Ok(parse("100")));
+        assert_eq!(
+            ctx.mul(&parse("1000"), &parse("100")),
+            Err(DecimalConvertError::Overflow)
+        );
+        let ctx = NumericContext::new(38, MAX_SCALE, RoundingMode::Up);
+        assert_eq!(ctx.div(&parse("1E-130"), &parse("3")), Ok(parse("1E-130")));
+        assert_eq!(
+            ctx.add(&parse("1E100"), &parse("1")),
+            Err(DecimalConvertError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_numeric_context_round_with_precision() {
+        // rounding half up is the same as `Decimal::round_with_precision`
+        let values = crate::testing::corpus();
+        for &(precision, scale) in [(38, 0), (20, 4), (10, -5), (38, 100), (1, 130), (5, -126)].iter() {
+            let ctx = NumericContext::new(precision, scale, RoundingMode::HalfUp);
+            for val in values.iter() {
+                let mut expected = *val;
+                let expected = if expected.round_with_precision(precision, scale) {
+                    Err(DecimalConvertError::Overflow)
+                } else if expected.is_zero() {
+                    // `round_with_precision` may keep the sign of a zero
+                    Ok(Decimal::ZERO)
+                } else {
+                    Ok(expected)
+                };
+                assert_eq!(ctx.round(val), expected, "{} NUMBER({}, {})", val, precision, scale);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "precision is out of range")]
+    fn test_numeric_context_zero_precision() {
+        NumericContext::new(0, 0, RoundingMode::HalfUp);
+    }
+}
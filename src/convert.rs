@@ -14,12 +14,15 @@
 
 //! Conversion between `Decimal` and primitive number types.
 
-use crate::decimal::{Buf, Decimal, MAX_PRECISION, MAX_SCALE, MIN_SCALE};
-use crate::u256::POWERS_10;
+use crate::decimal::{Buf, Decimal, RoundingMode, MAX_PRECISION, MAX_SCALE, MIN_SCALE};
+use crate::u256::{POWERS_10, U256};
 use crate::DecimalConvertError;
-use std::convert::TryFrom;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt::Write;
 
-pub(crate) const MAX_I128_REPR: i128 = 99_9999_9999_9999_9999_9999_9999_9999_9999_9999_i128;
+/// The largest mantissa, i.e. `10^MAX_PRECISION - 1`.
+pub(crate) const MAX_I128_REPR: i128 = 10i128.pow(MAX_PRECISION) - 1;
 
 macro_rules! impl_from_small_int {
     ($ty: ty) => {
@@ -70,7 +73,7 @@ impl TryFrom<i128> for Decimal {
     type Error = DecimalConvertError;
 
     #[inline]
-    fn try_from(val: i128) -> std::result::Result<Self, Self::Error> {
+    fn try_from(val: i128) -> core::result::Result<Self, Self::Error> {
         if !(-MAX_I128_REPR..=MAX_I128_REPR).contains(&val) {
             Err(DecimalConvertError::Overflow)
         } else {
@@ -89,7 +92,7 @@ impl TryFrom<u128> for Decimal {
     type Error = DecimalConvertError;
 
     #[inline]
-    fn try_from(value: u128) -> std::result::Result<Self, Self::Error> {
+    fn try_from(value: u128) -> core::result::Result<Self, Self::Error> {
         if value > MAX_I128_REPR as u128 {
             Err(DecimalConvertError::Overflow)
         } else {
@@ -102,7 +105,7 @@ impl TryFrom<f32> for Decimal {
     type Error = DecimalConvertError;
 
     #[inline]
-    fn try_from(value: f32) -> std::result::Result<Self, Self::Error> {
+    fn try_from(value: f32) -> core::result::Result<Self, Self::Error> {
         if value.is_infinite() {
             return Err(DecimalConvertError::Overflow);
         }
@@ -153,11 +156,226 @@ impl TryFrom<f32> for Decimal {
     }
 }
 
+impl Decimal {
+    /// Converts a `f32` to the decimal with the fewest significant digits that converts back to the same `f32`.
+    ///
+    /// Unlike `TryFrom<f32>`, which expands the binary value to 9 significant digits, this gives
+    /// `0.000001` rather than `0.000000999999997` for `1e-6f32`.
+    #[inline]
+    pub fn from_f32_shortest(value: f32) -> core::result::Result<Decimal, DecimalConvertError> {
+        if value.is_infinite() {
+            return Err(DecimalConvertError::Overflow);
+        }
+
+        if value.is_nan() {
+            return Err(DecimalConvertError::Invalid);
+        }
+
+        parse_shortest(value)
+    }
+
+    /// Converts a `f64` to the decimal with the fewest significant digits that converts back to the same `f64`,
+    /// failing with `DecimalConvertError::TooManyDigits` if that decimal has more than `max_digits` significant digits.
+    ///
+    /// Zero has no significant digits, so it always passes. This tells the floats that were written as short decimals, e.g. `0.1` or `2.35`, from the results of
+    /// binary arithmetic, e.g. `0.1 + 0.2`, which is `0.30000000000000004`:
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, DecimalConvertError};
+    ///
+    /// assert_eq!(Decimal::try_from_f64_strict(0.1, 15).unwrap().to_string(), "0.1");
+    /// assert_eq!(Decimal::try_from_f64_strict(0.1 + 0.2, 15), Err(DecimalConvertError::TooManyDigits));
+    /// ```
+    #[inline]
+    pub fn try_from_f64_strict(value: f64, max_digits: u8) -> core::result::Result<Decimal, DecimalConvertError> {
+        if value.is_infinite() {
+            return Err(DecimalConvertError::Overflow);
+        }
+
+        if value.is_nan() {
+            return Err(DecimalConvertError::Invalid);
+        }
+
+        let dec = parse_shortest(value)?;
+        if !dec.is_zero() && dec.precision() > max_digits {
+            return Err(DecimalConvertError::TooManyDigits);
+        }
+
+        Ok(dec)
+    }
+
+    /// Converts `self` to the `f64` to be written to an Excel cell, returning `(value, lossy)`.
+    ///
+    /// If `self` [fits Excel](Decimal::fits_excel), `value` is the nearest `f64`. Otherwise Excel would
+    /// drop the digits after the 15th, so `value` is the nearest `f64` of `self` rounded half up to 15
+    /// significant digits. `lossy` is `true` if `value` isn't exactly `self`, which is also the case for
+    /// e.g. `0.1`, as no `f64` is.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n: Decimal = "0.12345678901234567".parse().unwrap();
+    /// assert_eq!(n.to_excel_number(), (0.123456789012346, true));
+    /// assert_eq!(Decimal::from(1u64 << 60).to_excel_number(), (2f64.powi(60), false));
+    /// ```
+    #[inline]
+    pub fn to_excel_number(&self) -> (f64, bool) {
+        let value = to_f64_nearest(self);
+        if from_f64_exact(value).as_ref() == Some(self) {
+            return (value, false);
+        }
+
+        let (mantissa, exponent, _) = self.factor_pow10();
+        let digits = U256::from(mantissa).count_digits();
+        if digits <= EXCEL_PRECISION {
+            return (value, true);
+        }
+
+        let rounded = self.round(-(exponent as i16) - (digits - EXCEL_PRECISION) as i16);
+        (to_f64_nearest(&rounded), true)
+    }
+
+    /// Returns `true` if Excel keeps `self` unchanged, i.e. it has at most 15 significant digits, or it's
+    /// exactly a `f64`, e.g. `0.00000095367431640625`, which is `2^-20`.
+    ///
+    /// Every decimal is within the range of Excel, which is about `1E-308` to `1E+308`.
+    #[inline]
+    pub fn fits_excel(&self) -> bool {
+        let (mantissa, _, _) = self.factor_pow10();
+        U256::from(mantissa).count_digits() <= EXCEL_PRECISION
+            || from_f64_exact(to_f64_nearest(self)).as_ref() == Some(self)
+    }
+
+    /// Converts `self` to the `f64` of exactly the same value, failing with `DecimalConvertError::Inexact`
+    /// if there is no such `f64`.
+    ///
+    /// Every decimal is within the range of `f64`, so this never overflows, while `From<&Decimal>` rounds
+    /// silently:
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, DecimalConvertError};
+    ///
+    /// let n: Decimal = "0.5".parse().unwrap();
+    /// assert_eq!(n.to_f64_exact(), Ok(0.5));
+    /// let n: Decimal = "0.1".parse().unwrap();
+    /// assert_eq!(n.to_f64_exact(), Err(DecimalConvertError::Inexact));
+    /// ```
+    #[inline]
+    pub fn to_f64_exact(&self) -> core::result::Result<f64, DecimalConvertError> {
+        let value = to_f64_nearest(self);
+        if from_f64_exact(value).as_ref() == Some(self) {
+            Ok(value)
+        } else {
+            Err(DecimalConvertError::Inexact)
+        }
+    }
+
+    /// Converts `self` to the `f32` of exactly the same value, failing with `DecimalConvertError::Overflow`
+    /// if `self` is beyond the range of `f32`, or `DecimalConvertError::Inexact` if there is no such `f32`.
+    #[inline]
+    pub fn to_f32_exact(&self) -> core::result::Result<f32, DecimalConvertError> {
+        let value = to_f64_nearest(self);
+        if (value as f32).is_infinite() {
+            return Err(DecimalConvertError::Overflow);
+        }
+
+        if (value as f32) as f64 == value && from_f64_exact(value).as_ref() == Some(self) {
+            Ok(value as f32)
+        } else {
+            Err(DecimalConvertError::Inexact)
+        }
+    }
+}
+
+/// The significant digits of a number which Excel keeps.
+const EXCEL_PRECISION: u32 = 15;
+
+/// Converts `val` to the nearest `f64`, rounding half to even.
+#[inline]
+fn to_f64_nearest(val: &Decimal) -> f64 {
+    const POWERS_10: [f64; 23] = [
+        1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16, 1e17, 1e18, 1e19,
+        1e20, 1e21, 1e22,
+    ];
+
+    let (mantissa, exponent, negative) = val.factor_pow10();
+
+    // Both operands are exact, so the result is rounded once.
+    if mantissa <= 1 << 53 && exponent.abs() < POWERS_10.len() as i32 {
+        let v = if exponent < 0 {
+            mantissa as f64 / POWERS_10[-exponent as usize]
+        } else {
+            mantissa as f64 * POWERS_10[exponent as usize]
+        };
+        return if negative { -v } else { v };
+    }
+
+    let mut buf = Buf::new();
+    val.fmt_internal(true, false, false, None, &mut buf)
+        .expect("failed to format decimal");
+    let str = unsafe { core::str::from_utf8_unchecked(&buf) };
+    str.parse::<f64>().unwrap()
+}
+
+/// Converts the finite float `value` to the decimal of exactly the same value, returning `None` if that
+/// needs more than 38 significant digits.
+#[inline]
+fn from_f64_exact(value: f64) -> Option<Decimal> {
+    let raw = value.to_bits();
+    let negative = (raw >> 63) == 1;
+    let biased_exponent = ((raw >> 52) & 0x7FF) as i32;
+    let mut mantissa = raw & 0x000F_FFFF_FFFF_FFFF;
+
+    if biased_exponent == 0 && mantissa == 0 {
+        return Some(Decimal::ZERO);
+    }
+
+    let mut exponent2 = if biased_exponent == 0 {
+        -1074
+    } else {
+        mantissa |= 0x0010_0000_0000_0000;
+        biased_exponent - 1075
+    };
+    let zeros = mantissa.trailing_zeros();
+    let mut mantissa = (mantissa >> zeros) as u128;
+    exponent2 += zeros as i32;
+
+    if exponent2 < 0 {
+        // `mantissa / 2^e == mantissa * 5^e / 10^e`
+        let e = -exponent2 as u32;
+        let int_val = 5u128.checked_pow(e)?.checked_mul(mantissa)?;
+        return Decimal::from_parts(int_val, e as i16, negative).ok();
+    }
+
+    // Pairs the factors of 5 of the mantissa with factors of 2 into a negative scale.
+    let mut scale = 0;
+    while exponent2 > 0 && mantissa.is_multiple_of(5) {
+        mantissa /= 5;
+        exponent2 -= 1;
+        scale -= 1;
+    }
+    if exponent2 >= 128 {
+        return None;
+    }
+    let int_val = mantissa.checked_mul(1 << exponent2)?;
+    Decimal::from_parts(int_val, scale, negative).ok()
+}
+
+/// Parses the shortest digits that round-trip to the finite float `value`.
+#[inline]
+fn parse_shortest<T: core::fmt::LowerExp>(value: T) -> core::result::Result<Decimal, DecimalConvertError> {
+    // The standard library formats floats with the shortest digits that round-trip.
+    let mut buf = Buf::new();
+    write!(&mut buf, "{:e}", value).expect("failed to format float");
+    let str = unsafe { core::str::from_utf8_unchecked(&buf) };
+    Ok(str.parse::<Decimal>()?)
+}
+
 impl TryFrom<f64> for Decimal {
     type Error = DecimalConvertError;
 
     #[inline]
-    fn try_from(value: f64) -> std::result::Result<Self, Self::Error> {
+    fn try_from(value: f64) -> core::result::Result<Self, Self::Error> {
         if value.is_infinite() {
             return Err(DecimalConvertError::Overflow);
         }
@@ -396,7 +614,7 @@ impl From<&Decimal> for f64 {
             let mut buf = Buf::new();
             val.fmt_internal(true, false, false, None, &mut buf)
                 .expect("failed to format decimal");
-            let str = unsafe { std::str::from_utf8_unchecked(&*buf) };
+            let str = unsafe { core::str::from_utf8_unchecked(&buf) };
             str.parse::<f64>().unwrap()
         }
     }
@@ -409,6 +627,8 @@ impl From<Decimal> for f64 {
     }
 }
 
+/// Rounds half away from zero, e.g. `2.5` to `3` and `-2.5` to `-3`. Use [`Decimal::to_u128_trunc`] to
+/// truncate like the `as` casts of floats, or [`Decimal::to_u128_with_mode`] for the other rounding modes.
 impl TryFrom<&Decimal> for u128 {
     type Error = DecimalConvertError;
 
@@ -468,6 +688,8 @@ fn to_i128(int_val: u128, negative: bool) -> Result<i128, DecimalConvertError> {
     }
 }
 
+/// Rounds half away from zero, e.g. `2.5` to `3` and `-2.5` to `-3`. Use [`Decimal::to_i128_trunc`] to
+/// truncate like the `as` casts of floats, or [`Decimal::to_i128_with_mode`] for the other rounding modes.
 impl TryFrom<&Decimal> for i128 {
     type Error = DecimalConvertError;
 
@@ -511,6 +733,7 @@ impl TryFrom<Decimal> for i128 {
 
 macro_rules! impl_into_small_int {
     ($ty: ty) => {
+        /// Rounds half away from zero, see `TryFrom<&Decimal> for u128`.
         impl TryFrom<&Decimal> for $ty {
             type Error = DecimalConvertError;
 
@@ -534,6 +757,7 @@ macro_rules! impl_into_small_int {
         }
     };
     (SIGNED $ty: ty) => {
+        /// Rounds half away from zero, see `TryFrom<&Decimal> for i128`.
         impl TryFrom<&Decimal> for $ty {
             type Error = DecimalConvertError;
 
@@ -567,6 +791,137 @@ macro_rules! impl_into_small_int {
 impl_into_small_int!(u8, u16, u32, u64, usize);
 impl_into_small_int!(SIGNED i8, i16, i32, i64, isize);
 
+impl Decimal {
+    /// Returns the magnitude and sign of the integral value, rounded with `mode`,
+    /// returning `None` if the magnitude overflows `u128`.
+    #[inline]
+    fn integral_parts(&self, mode: RoundingMode) -> Option<(u128, bool)> {
+        let scale = self.scale();
+        let negative = self.is_sign_negative();
+
+        let int_val = if scale <= 0 {
+            if self.is_zero() {
+                return Some((0, false));
+            }
+            if -scale > MAX_PRECISION as i16 {
+                return None;
+            }
+            self.int_val().checked_mul(POWERS_10[-scale as usize].low())?
+        } else if scale > MAX_PRECISION as i16 {
+            // The whole value is less than half a unit.
+            mode.round_up(Ordering::Less, false, false, negative) as u128
+        } else {
+            mode.div_pow10(self.int_val(), scale as u32, negative)
+        };
+
+        Some((int_val, negative && int_val != 0))
+    }
+}
+
+macro_rules! impl_to_int_with_mode {
+    (@convert u128, $int_val: ident, $negative: ident) => {
+        if $negative {
+            None
+        } else {
+            Some($int_val)
+        }
+    };
+    (@convert i128, $int_val: ident, $negative: ident) => {
+        to_i128($int_val, $negative).ok()
+    };
+    (@convert $ty: ty, $int_val: ident, $negative: ident) => {
+        <$ty>::try_from(to_i128($int_val, $negative).ok()?).ok()
+    };
+    ($ty: ident, $with_mode: ident, $trunc: ident, $round: ident, $floor: ident, $ceil: ident) => {
+        impl Decimal {
+            /// Converts to an integer by rounding with `mode`,
+            /// returning `None` if overflow occurred.
+            #[inline]
+            pub fn $with_mode(&self, mode: RoundingMode) -> Option<$ty> {
+                let (int_val, negative) = self.integral_parts(mode)?;
+                impl_to_int_with_mode!(@convert $ty, int_val, negative)
+            }
+
+            /// Converts to an integer by rounding toward zero, like the `as` casts of floats,
+            /// returning `None` if overflow occurred.
+            #[inline]
+            pub fn $trunc(&self) -> Option<$ty> {
+                self.$with_mode(RoundingMode::Down)
+            }
+
+            /// Converts to an integer by rounding half away from zero, like `TryFrom`,
+            /// returning `None` if overflow occurred.
+            #[inline]
+            pub fn $round(&self) -> Option<$ty> {
+                self.$with_mode(RoundingMode::HalfUp)
+            }
+
+            /// Converts to an integer by rounding toward negative infinity,
+            /// returning `None` if overflow occurred.
+            #[inline]
+            pub fn $floor(&self) -> Option<$ty> {
+                self.$with_mode(RoundingMode::Floor)
+            }
+
+            /// Converts to an integer by rounding toward positive infinity,
+            /// returning `None` if overflow occurred.
+            #[inline]
+            pub fn $ceil(&self) -> Option<$ty> {
+                self.$with_mode(RoundingMode::Ceiling)
+            }
+        }
+    };
+}
+
+impl_to_int_with_mode!(
+    i32,
+    to_i32_with_mode,
+    to_i32_trunc,
+    to_i32_round,
+    to_i32_floor,
+    to_i32_ceil
+);
+impl_to_int_with_mode!(
+    u32,
+    to_u32_with_mode,
+    to_u32_trunc,
+    to_u32_round,
+    to_u32_floor,
+    to_u32_ceil
+);
+impl_to_int_with_mode!(
+    i64,
+    to_i64_with_mode,
+    to_i64_trunc,
+    to_i64_round,
+    to_i64_floor,
+    to_i64_ceil
+);
+impl_to_int_with_mode!(
+    u64,
+    to_u64_with_mode,
+    to_u64_trunc,
+    to_u64_round,
+    to_u64_floor,
+    to_u64_ceil
+);
+impl_to_int_with_mode!(
+    i128,
+    to_i128_with_mode,
+    to_i128_trunc,
+    to_i128_round,
+    to_i128_floor,
+    to_i128_ceil
+);
+impl_to_int_with_mode!(
+    u128,
+    to_u128_with_mode,
+    to_u128_trunc,
+    to_u128_round,
+    to_u128_floor,
+    to_u128_ceil
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -732,6 +1087,77 @@ mod tests {
         assert_try_from(1.401298E-45f32, "140129846E-53");
     }
 
+    fn assert_f32_round_trip(bits: u32) {
+        let val = f32::from_bits(bits);
+        if !val.is_finite() {
+            return;
+        }
+
+        let decimal = Decimal::try_from(val).unwrap();
+        let shortest = Decimal::from_f32_shortest(val).unwrap();
+        assert_eq!(f32::from(&decimal), val, "{:e}", val);
+        assert_eq!(f32::from(&shortest), val, "{:e}", val);
+        assert!(shortest.precision() <= decimal.precision(), "{:e}", val);
+    }
+
+    #[test]
+    #[allow(clippy::excessive_precision)]
+    fn test_from_f32_shortest() {
+        fn assert_shortest(val: f32, expected: &str) {
+            let decimal = Decimal::from_f32_shortest(val).unwrap();
+            assert_eq!(decimal, expected.parse::<Decimal>().unwrap());
+            assert_eq!(f32::from(&decimal), val);
+        }
+
+        assert_eq!(
+            Decimal::from_f32_shortest(f32::INFINITY).unwrap_err(),
+            DecimalConvertError::Overflow
+        );
+        assert_eq!(
+            Decimal::from_f32_shortest(f32::NEG_INFINITY).unwrap_err(),
+            DecimalConvertError::Overflow
+        );
+        assert_eq!(
+            Decimal::from_f32_shortest(f32::NAN).unwrap_err(),
+            DecimalConvertError::Invalid
+        );
+
+        assert_shortest(0.0f32, "0");
+        assert_shortest(-0.0f32, "0");
+        assert_shortest(0.000001f32, "0.000001");
+        assert_shortest(0.0000001f32, "0.0000001");
+        assert_shortest(0.555555f32, "0.555555");
+        assert_shortest(0.9999999f32, "0.9999999");
+        assert_shortest(1.00001f32, "1.00001");
+        assert_shortest(0.1f32, "0.1");
+        assert_shortest(0.25f32, "0.25");
+        assert_shortest(0.5f32, "0.5");
+        assert_shortest(-0.75f32, "-0.75");
+        assert_shortest(1024f32, "1024");
+        assert_shortest(0.0009765625f32, "0.0009765625");
+        assert_shortest(16777216f32, "16777216");
+        assert_shortest(1.23456789e10f32, "12345679000");
+        assert_shortest(std::f32::consts::PI, "3.1415927");
+        assert_shortest(f32::MAX, "3.4028235E38");
+        assert_shortest(f32::MIN, "-3.4028235E38");
+        assert_shortest(f32::MIN_POSITIVE, "1.1754944E-38");
+        assert_shortest(1.1754942E-38f32, "1.1754942E-38");
+        assert_shortest(1.401298E-45f32, "1E-45");
+        assert_shortest(-1.401298E-45f32, "-1E-45");
+
+        for bits in (0..=u32::MAX).step_by(65521) {
+            assert_f32_round_trip(bits);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "heavy-tests")]
+    fn test_from_f32_exhaustive() {
+        for bits in 0..=u32::MAX {
+            assert_f32_round_trip(bits);
+        }
+    }
+
     #[test]
     #[allow(clippy::excessive_precision)]
     fn test_try_from_f64() {
@@ -759,6 +1185,60 @@ mod tests {
         assert_try_from(std::f64::consts::PI, "3.1415926535897931");
     }
 
+    #[test]
+    #[allow(clippy::excessive_precision)]
+    fn test_try_from_f64_strict() {
+        fn assert_strict(val: f64, max_digits: u8, expected: &str) {
+            let decimal = Decimal::try_from_f64_strict(val, max_digits).unwrap();
+            assert_eq!(decimal, expected.parse::<Decimal>().unwrap());
+            assert_eq!(decimal.to_string().parse::<f64>().unwrap(), val);
+        }
+
+        fn assert_strict_err(val: f64, max_digits: u8, expected: DecimalConvertError) {
+            assert_eq!(Decimal::try_from_f64_strict(val, max_digits).unwrap_err(), expected);
+        }
+
+        // clean doubles
+        assert_strict(0.0, 15, "0");
+        assert_strict(-0.0, 0, "0");
+        assert_strict(0.1, 15, "0.1");
+        assert_strict(2.35, 15, "2.35");
+        assert_strict(-19.99, 15, "-19.99");
+        assert_strict(1e-6, 1, "0.000001");
+        assert_strict(1.5e100, 2, "1.5E100");
+        assert_strict_err(1.5e300, 2, DecimalConvertError::Overflow);
+        assert_strict(123456789012345.0, 15, "123456789012345");
+        assert_strict(0.123456789012345, 15, "0.123456789012345");
+        assert_strict(1e-130, 1, "1E-130");
+
+        // dirty doubles
+        assert_strict_err(0.1 + 0.2, 15, DecimalConvertError::TooManyDigits);
+        assert_strict(0.1 + 0.2, 17, "0.30000000000000004");
+        assert_strict_err(1.1 * 1.1, 15, DecimalConvertError::TooManyDigits);
+        assert_strict(1.1 * 1.1, 17, "1.2100000000000002");
+        assert_strict_err(std::f64::consts::PI, 15, DecimalConvertError::TooManyDigits);
+        assert_strict(std::f64::consts::PI, 16, "3.141592653589793");
+        assert_strict_err(1.0 / 3.0, 15, DecimalConvertError::TooManyDigits);
+        assert_strict_err(f64::MIN_POSITIVE, 17, DecimalConvertError::Overflow);
+
+        // boundary counts around `max_digits`
+        assert_strict(1234567.0, 7, "1234567");
+        assert_strict_err(1234567.0, 6, DecimalConvertError::TooManyDigits);
+        assert_strict(0.001234, 4, "0.001234");
+        assert_strict_err(0.001234, 3, DecimalConvertError::TooManyDigits);
+        assert_strict(1200.0, 2, "1200");
+        assert_strict_err(1200.0, 1, DecimalConvertError::TooManyDigits);
+        assert_strict(0.1234567890123456, 16, "0.1234567890123456");
+        assert_strict_err(0.1234567890123456, 15, DecimalConvertError::TooManyDigits);
+        assert_strict_err(0.5, 0, DecimalConvertError::TooManyDigits);
+
+        // not a number
+        assert_strict_err(f64::NAN, 38, DecimalConvertError::Invalid);
+        assert_strict_err(f64::INFINITY, 38, DecimalConvertError::Overflow);
+        assert_strict_err(f64::NEG_INFINITY, 38, DecimalConvertError::Overflow);
+        assert_strict_err(5e-324, 38, DecimalConvertError::Overflow);
+    }
+
     fn assert_into<S: AsRef<str>, T: From<Decimal> + PartialEq + Debug>(s: S, expected: T) {
         let decimal = s.as_ref().parse::<Decimal>().unwrap();
         let val = T::from(decimal);
@@ -800,6 +1280,134 @@ mod tests {
         assert_into("1.17549435e-38", 1.1754944e-38f32);
     }
 
+    #[test]
+    fn test_to_excel_number() {
+        fn assert_excel(val: &str, expected: f64, lossy: bool, fits: bool) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            assert_eq!(decimal.to_excel_number(), (expected, lossy), "{}", val);
+            assert_eq!(decimal.fits_excel(), fits, "{}", val);
+            assert_eq!((-decimal).to_excel_number(), (-expected, lossy), "{}", val);
+        }
+
+        assert_excel("0", 0.0, false, true);
+        assert_excel("0.5", 0.5, false, true);
+        assert_excel("0.1", 0.1, true, true);
+        assert_excel("1.2000", 1.2, true, true);
+
+        // 15 significant digits
+        assert_excel("123456789012345", 123456789012345.0, false, true);
+        assert_excel("0.123456789012345", 0.123456789012345, true, true);
+        assert_excel("999999999999999E+100", 9.99999999999999e114, true, true);
+
+        // 16 significant digits
+        assert_excel("1234567890123456", 1234567890123456.0, false, true);
+        assert_excel("0.1234567890123456", 0.123456789012346, true, false);
+        assert_excel("9007199254740993", 9007199254740990.0, true, false);
+
+        // 17 significant digits
+        assert_excel("12345678901234567", 12345678901234600.0, true, false);
+        assert_excel("0.99999999999999999", 1.0, true, false);
+        assert_excel("0.30000000000000004", 0.3, true, false);
+
+        // powers of two are exact despite many digits
+        assert_excel("9007199254740992", 9007199254740992.0, false, true);
+        assert_excel("0.00000095367431640625", 2f64.powi(-20), false, true);
+        assert_excel("1267650600228229401496703205376", 2f64.powi(100), false, true);
+        assert_excel("8.8817841970012523233890533447265625E-16", 2f64.powi(-50), false, true);
+        assert_excel("1.5E+22", 1.5e22, false, true);
+        assert_excel("3E+23", 3e23, true, true);
+
+        // every decimal is within the range of Excel
+        assert_excel("1E-130", 1e-130, true, true);
+        assert_excel(
+            "1.2345678901234567890123456789012345678E-100",
+            1.23456789012346e-100,
+            true,
+            false,
+        );
+        assert_excel("9.9999999999999999999999999999999999999E125", 1e126, true, false);
+        assert_excel("1.7976931348623157E+108", 1.79769313486232e108, true, false);
+    }
+
+    #[test]
+    fn test_to_float_exact() {
+        fn parse(s: &str) -> Decimal {
+            s.parse().unwrap()
+        }
+
+        fn assert_exact(val: &str, expected: f64) {
+            let decimal = parse(val);
+            assert_eq!(decimal.to_f64_exact(), Ok(expected), "{}", val);
+            assert_eq!((-decimal).to_f64_exact(), Ok(-expected), "{}", val);
+            if expected as f32 as f64 == expected {
+                assert_eq!(decimal.to_f32_exact(), Ok(expected as f32), "{}", val);
+                assert_eq!((-decimal).to_f32_exact(), Ok(-expected as f32), "{}", val);
+            } else {
+                assert_eq!(decimal.to_f32_exact(), Err(DecimalConvertError::Inexact), "{}", val);
+            }
+        }
+
+        fn assert_err(decimal: Decimal, f64_err: Option<DecimalConvertError>, f32_err: DecimalConvertError) {
+            for d in [decimal, -decimal].iter() {
+                assert_eq!(d.to_f64_exact().err(), f64_err, "{}", d);
+                assert_eq!(d.to_f32_exact().unwrap_err(), f32_err, "{}", d);
+            }
+        }
+
+        assert_exact("0", 0.0);
+        assert_exact("0.5", 0.5);
+        assert_exact("123456", 123456.0);
+        assert_exact("16777216", 16777216.0);
+        assert_exact("16777217", 16777217.0);
+        assert_exact("9007199254740992", 9007199254740992.0);
+        assert_exact("0.00000095367431640625", 2f64.powi(-20));
+        assert_exact("1267650600228229401496703205376", 2f64.powi(100));
+        assert_exact("1E+22", 1e22);
+        assert_exact("340282346638528859811704183484516925440", f32::MAX as f64);
+
+        assert_err(
+            parse("0.1"),
+            Some(DecimalConvertError::Inexact),
+            DecimalConvertError::Inexact,
+        );
+        assert_err(
+            parse("9007199254740993"),
+            Some(DecimalConvertError::Inexact),
+            DecimalConvertError::Inexact,
+        );
+        assert_err(
+            parse("1E-130"),
+            Some(DecimalConvertError::Inexact),
+            DecimalConvertError::Inexact,
+        );
+        assert_err(
+            parse("1E+23"),
+            Some(DecimalConvertError::Inexact),
+            DecimalConvertError::Inexact,
+        );
+
+        // beyond the range of `f32`, but not `f64`
+        // 1E+160, beyond the range reachable by parsing
+        let big = Decimal::from_parts(10u128.pow(34), -126, false).unwrap();
+        assert_err(big, Some(DecimalConvertError::Inexact), DecimalConvertError::Overflow);
+        assert_eq!(f64::from(big), 1e160);
+        assert_err(
+            parse("1E+39"),
+            Some(DecimalConvertError::Inexact),
+            DecimalConvertError::Overflow,
+        );
+        assert_err(
+            parse("3.4028236E+38"),
+            Some(DecimalConvertError::Inexact),
+            DecimalConvertError::Overflow,
+        );
+        assert_err(
+            parse("3.4028235E+38"),
+            Some(DecimalConvertError::Inexact),
+            DecimalConvertError::Inexact,
+        );
+    }
+
     #[test]
     #[allow(clippy::excessive_precision)]
     fn test_into_f64() {
@@ -931,4 +1539,167 @@ mod tests {
         assert_try_into_overflow::<i64>("9223372036854775808");
         assert_try_into_overflow::<i64>("-9223372036854775809");
     }
+
+    #[test]
+    fn test_to_int_with_mode() {
+        fn assert_i64(val: &str, trunc: Option<i64>, floor: Option<i64>, ceil: Option<i64>) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            assert_eq!(decimal.to_i64_trunc(), trunc, "{}", val);
+            assert_eq!(decimal.to_i64_floor(), floor, "{}", val);
+            assert_eq!(decimal.to_i64_ceil(), ceil, "{}", val);
+        }
+
+        assert_i64("0", Some(0), Some(0), Some(0));
+        assert_i64("1.5", Some(1), Some(1), Some(2));
+        assert_i64("-1.5", Some(-1), Some(-2), Some(-1));
+        assert_i64("2.0", Some(2), Some(2), Some(2));
+        assert_i64("-2.0", Some(-2), Some(-2), Some(-2));
+        assert_i64("-0.1", Some(0), Some(-1), Some(0));
+        assert_i64("0.1", Some(0), Some(0), Some(1));
+        assert_i64("12e3", Some(12000), Some(12000), Some(12000));
+        assert_i64("9223372036854775807.9", Some(i64::MAX), Some(i64::MAX), None);
+        assert_i64("-9223372036854775808.9", Some(i64::MIN), None, Some(i64::MIN));
+        assert_i64("9223372036854775808", None, None, None);
+        assert_i64("-9223372036854775809", None, None, None);
+        assert_i64("1e-100", Some(0), Some(0), Some(1));
+        assert_i64("-1e-100", Some(0), Some(-1), Some(0));
+        assert_i64("0.99999999999999999999999999999999999999", Some(0), Some(0), Some(1));
+        assert_i64("1e100", None, None, None);
+
+        fn assert_u64(val: &str, trunc: Option<u64>, floor: Option<u64>, ceil: Option<u64>) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            assert_eq!(decimal.to_u64_trunc(), trunc, "{}", val);
+            assert_eq!(decimal.to_u64_floor(), floor, "{}", val);
+            assert_eq!(decimal.to_u64_ceil(), ceil, "{}", val);
+        }
+
+        assert_u64("1.5", Some(1), Some(1), Some(2));
+        assert_u64("-0.5", Some(0), None, Some(0));
+        assert_u64("-1", None, None, None);
+        assert_u64("18446744073709551615.5", Some(u64::MAX), Some(u64::MAX), None);
+        assert_u64("1e-100", Some(0), Some(0), Some(1));
+
+        fn assert_i128(val: &str, trunc: Option<i128>, floor: Option<i128>, ceil: Option<i128>) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            assert_eq!(decimal.to_i128_trunc(), trunc, "{}", val);
+            assert_eq!(decimal.to_i128_floor(), floor, "{}", val);
+            assert_eq!(decimal.to_i128_ceil(), ceil, "{}", val);
+        }
+
+        assert_i128("-1.5", Some(-1), Some(-2), Some(-1));
+        assert_i128(
+            "1e38",
+            Some(100000000000000000000000000000000000000),
+            Some(100000000000000000000000000000000000000),
+            Some(100000000000000000000000000000000000000),
+        );
+        assert_i128(
+            "-1.7e38",
+            Some(-170000000000000000000000000000000000000),
+            Some(-170000000000000000000000000000000000000),
+            Some(-170000000000000000000000000000000000000),
+        );
+        assert_i128("1.8e38", None, None, None);
+        assert_i128("1e39", None, None, None);
+
+        fn assert_u128(val: &str, trunc: Option<u128>, floor: Option<u128>, ceil: Option<u128>) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            assert_eq!(decimal.to_u128_trunc(), trunc, "{}", val);
+            assert_eq!(decimal.to_u128_floor(), floor, "{}", val);
+            assert_eq!(decimal.to_u128_ceil(), ceil, "{}", val);
+        }
+
+        assert_u128(
+            "3.4e38",
+            Some(340000000000000000000000000000000000000),
+            Some(340000000000000000000000000000000000000),
+            Some(340000000000000000000000000000000000000),
+        );
+        assert_u128("3.5e38", None, None, None);
+        assert_u128("-0.5", Some(0), None, Some(0));
+        assert_u128(
+            "9999999999999999999999999999999999999.5",
+            Some(9999999999999999999999999999999999999),
+            Some(9999999999999999999999999999999999999),
+            Some(10000000000000000000000000000000000000),
+        );
+    }
+
+    #[test]
+    fn test_to_int_trunc_round() {
+        fn assert_i64(val: &str, trunc: Option<i64>, round: Option<i64>) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            assert_eq!(decimal.to_i64_trunc(), trunc, "{}", val);
+            assert_eq!(decimal.to_i64_round(), round, "{}", val);
+            assert_eq!(decimal.to_i64_with_mode(RoundingMode::Down), trunc, "{}", val);
+            assert_eq!(decimal.to_i64_with_mode(RoundingMode::HalfUp), round, "{}", val);
+            assert_eq!(i64::try_from(decimal).ok(), round, "{}", val);
+        }
+
+        assert_i64("2.5", Some(2), Some(3));
+        assert_i64("-2.5", Some(-2), Some(-3));
+        assert_i64("2.4999", Some(2), Some(2));
+        assert_i64("-2.4999", Some(-2), Some(-2));
+        assert_i64("0.5", Some(0), Some(1));
+        assert_i64("-0.5", Some(0), Some(-1));
+        assert_i64("25e3", Some(25000), Some(25000));
+        assert_i64("-25e3", Some(-25000), Some(-25000));
+        assert_i64("9223372036854775807.5", Some(i64::MAX), None);
+        assert_i64("-9223372036854775808.5", Some(i64::MIN), None);
+        assert_i64("1e19", None, None);
+
+        fn assert_i32(val: &str, trunc: Option<i32>, round: Option<i32>) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            assert_eq!(decimal.to_i32_trunc(), trunc, "{}", val);
+            assert_eq!(decimal.to_i32_round(), round, "{}", val);
+            assert_eq!(i32::try_from(decimal).ok(), round, "{}", val);
+        }
+
+        assert_i32("-7.5", Some(-7), Some(-8));
+        assert_i32("25e3", Some(25000), Some(25000));
+        assert_i32("2147483647.4", Some(i32::MAX), Some(i32::MAX));
+        assert_i32("2147483647.5", Some(i32::MAX), None);
+        assert_i32("-2147483648.5", Some(i32::MIN), None);
+        assert_i32("3e9", None, None);
+
+        fn assert_u32(val: &str, trunc: Option<u32>, round: Option<u32>) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            assert_eq!(decimal.to_u32_trunc(), trunc, "{}", val);
+            assert_eq!(decimal.to_u32_round(), round, "{}", val);
+        }
+
+        assert_u32("7.5", Some(7), Some(8));
+        assert_u32("-0.4", Some(0), Some(0));
+        assert_u32("-0.5", Some(0), None);
+        assert_u32("4294967295.5", Some(u32::MAX), None);
+        assert_u32("25e3", Some(25000), Some(25000));
+
+        assert_eq!("2.5".parse::<Decimal>().unwrap().to_u64_round(), Some(3));
+        assert_eq!("-2.5".parse::<Decimal>().unwrap().to_i128_round(), Some(-3));
+        assert_eq!("2.5".parse::<Decimal>().unwrap().to_u128_round(), Some(3));
+        assert_eq!(
+            "2.5"
+                .parse::<Decimal>()
+                .unwrap()
+                .to_i64_with_mode(RoundingMode::HalfEven),
+            Some(2)
+        );
+        assert_eq!(
+            "-3.5"
+                .parse::<Decimal>()
+                .unwrap()
+                .to_i32_with_mode(RoundingMode::HalfEven),
+            Some(-4)
+        );
+        assert_eq!(
+            "2.1".parse::<Decimal>().unwrap().to_u32_with_mode(RoundingMode::Up),
+            Some(3)
+        );
+
+        for val in crate::testing::corpus().iter() {
+            assert_eq!(val.to_i64_round(), i64::try_from(val).ok(), "{}", val);
+            assert_eq!(val.to_i128_round(), i128::try_from(val).ok(), "{}", val);
+            assert_eq!(val.to_i64_trunc(), i64::try_from(val.trunc(0)).ok(), "{}", val);
+        }
+    }
 }
@@ -0,0 +1,161 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Constant-time comparison.
+//!
+//! Every function here is free of branches on the values, so that a timing review only has to check this
+//! file: a value only flows through bitwise operations, wrapping arithmetic and the divisions of
+//! [`canonical_parts`].
+
+use crate::decimal::Decimal;
+use crate::u256::POWERS_10;
+
+/// Returns `x` through a volatile read, so that the optimizer can't reason about its value and
+/// reintroduce a branch on it.
+#[inline(always)]
+fn opaque<T: Copy>(x: T) -> T {
+    unsafe { core::ptr::read_volatile(&x) }
+}
+
+/// Returns all ones if `x` is zero, otherwise all zeros.
+#[inline(always)]
+fn zero_mask(x: u128) -> u128 {
+    // The top bit of `x | -x` is set if and only if `x` isn't zero.
+    ((x | x.wrapping_neg()) >> 127).wrapping_sub(1)
+}
+
+/// Returns `a` if `mask` is all ones, or `b` if `mask` is all zeros.
+#[inline(always)]
+fn select(mask: u128, a: u128, b: u128) -> u128 {
+    b ^ (mask & (a ^ b))
+}
+
+/// Returns the canonical form of `val` as `(mantissa, exponent, negative)`, where the mantissa has no
+/// trailing zeros, the exponent is a wrapping `i128`, and zero is `(0, 0, 0)`.
+///
+/// The trailing zeros are stripped in a fixed sequence of steps, but each step divides a `u128`, and
+/// the time of a `u128` division depends on its operands.
+#[inline(always)]
+fn canonical_parts(val: &Decimal) -> (u128, u128, u128) {
+    let (int_val, scale, negative) = val.into_parts();
+    let mut mantissa = int_val;
+    let mut exponent = (-(scale as i128)) as u128;
+    for &k in [32, 16, 8, 4, 2, 1].iter() {
+        let divisor = POWERS_10[k].low();
+        let quotient = mantissa / divisor;
+        let mask = zero_mask(mantissa.wrapping_sub(quotient.wrapping_mul(divisor)));
+        mantissa = select(mask, quotient, mantissa);
+        exponent = exponent.wrapping_add(mask & k as u128);
+    }
+
+    let non_zero = !zero_mask(int_val);
+    (mantissa, exponent & non_zero, negative as u128 & non_zero)
+}
+
+/// Returns `1` if `lhs` and `rhs` have the same value, otherwise `0`.
+#[inline(always)]
+fn ct_eq_u8(lhs: &Decimal, rhs: &Decimal) -> u8 {
+    let (lhs_mantissa, lhs_exponent, lhs_negative) = canonical_parts(lhs);
+    let (rhs_mantissa, rhs_exponent, rhs_negative) = canonical_parts(rhs);
+    let diff = (lhs_mantissa ^ rhs_mantissa) | (lhs_exponent ^ rhs_exponent) | (lhs_negative ^ rhs_negative);
+    (zero_mask(opaque(diff)) & 1) as u8
+}
+
+impl Decimal {
+    /// Checks if `self` and `other` have the same value, as `==` does, in a time which doesn't depend on
+    /// where they differ.
+    ///
+    /// # Timing
+    ///
+    /// Both operands are brought to their canonical form first, i.e. the mantissa without trailing zeros
+    /// and the exponent. This takes a fixed sequence of steps without branches, but each step divides a
+    /// `u128`, whose time depends on the operands, e.g. on the magnitude of the mantissa. So it isn't
+    /// constant-time with regard to the mantissa and the scale.
+    ///
+    /// The canonical forms are then compared without early exit, by accumulating the differences of all
+    /// their bits, which is constant-time. The accumulated difference goes through a volatile read, so
+    /// that the optimizer can't turn the comparison back into branches.
+    ///
+    /// Nothing else on `Decimal` is constant-time, e.g. `==`, `Ord` and `Hash` return as soon as the
+    /// result is known.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let a: Decimal = "1.50".parse().unwrap();
+    /// let b = Decimal::from_parts(150000, 5, false).unwrap();
+    /// assert!(a.ct_eq(&b));
+    /// assert!(!a.ct_eq(&-b));
+    /// ```
+    #[inline]
+    pub fn ct_eq(&self, other: &Decimal) -> bool {
+        opaque(ct_eq_u8(self, other)) == 1
+    }
+}
+
+#[cfg(feature = "subtle")]
+#[cfg_attr(docsrs, doc(cfg(feature = "subtle")))]
+impl subtle::ConstantTimeEq for Decimal {
+    /// Checks if `self` and `other` have the same value, with the timing of [`Decimal::ct_eq`].
+    #[inline]
+    fn ct_eq(&self, other: &Decimal) -> subtle::Choice {
+        subtle::Choice::from(ct_eq_u8(self, other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_ct_eq(lhs: &Decimal, rhs: &Decimal) {
+        assert_eq!(lhs.ct_eq(rhs), lhs == rhs, "{:?} {:?}", lhs, rhs);
+        #[cfg(feature = "subtle")]
+        assert_eq!(bool::from(subtle::ConstantTimeEq::ct_eq(lhs, rhs)), lhs == rhs);
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        let corpus = crate::testing::corpus_random(0xc7, 200);
+        for lhs in corpus.iter() {
+            for rhs in corpus.iter() {
+                assert_ct_eq(lhs, rhs);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ct_eq_scales() {
+        // The same values with trailing zeros, i.e. with more digits and a larger scale.
+        for val in crate::testing::corpus().iter() {
+            let (int_val, scale, negative) = val.into_parts();
+            for zeros in 0..=38 {
+                let int_val = match int_val.checked_mul(10u128.pow(zeros)) {
+                    Some(v) if v <= crate::convert::MAX_I128_REPR as u128 => v,
+                    _ => break,
+                };
+                if let Ok(other) = Decimal::from_parts(int_val, scale + zeros as i16, negative) {
+                    assert!(val.ct_eq(&other), "{:?} {:?}", val, other);
+                    assert_ct_eq(&-*val, &other);
+                }
+            }
+        }
+
+        assert_ct_eq(&Decimal::ZERO, &Decimal::from_parts(0, 10, true).unwrap());
+        assert_ct_eq(&Decimal::ONE, &-Decimal::ONE);
+        assert_ct_eq(
+            &Decimal::from_parts(1, 10, false).unwrap(),
+            &Decimal::from_parts(1, -10, false).unwrap(),
+        );
+    }
+}
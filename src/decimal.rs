@@ -14,20 +14,32 @@
 
 //! Decimal implementation.
 
+use crate::context::round_to_scale;
 use crate::convert::MAX_I128_REPR;
-use crate::error::{DecimalConvertError, DecimalFormatError};
+use crate::error::{DecimalConvertError, DecimalFormatError, DecimalMathError, NormalizeError};
 use crate::u256::{POWERS_10, ROUNDINGS, U256};
+use crate::uint::Uint;
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use core::cmp::Ordering;
+#[cfg(feature = "std")]
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::RangeInclusive;
 use stack_buf::StackVec;
-use std::cmp::Ordering;
-use std::fmt;
-use std::hash::{Hash, Hasher};
-use std::io;
 
 /// Maximum precision of `Decimal`.
 pub const MAX_PRECISION: u32 = 38;
+/// Maximum precision of the intermediates, which hold the product of two mantissas in a `U256`.
+pub(crate) const WIDE_MAX_PRECISION: u32 = 2 * MAX_PRECISION;
 /// Maximum binary data size of `Decimal`.
 pub const MAX_BINARY_SIZE: usize = 18;
+/// Maximum scale of `Decimal`, i.e. [`Decimal::MIN_POSITIVE`] is `1e-130`.
+///
+/// [`Decimal::from_parts`] accepts a scale up to `MAX_SCALE + MAX_PRECISION - 1`, see there.
 pub const MAX_SCALE: i16 = 130;
+/// Minimum scale of `Decimal`.
 pub const MIN_SCALE: i16 = -126;
 
 const SIGN_MASK: u8 = 0x01;
@@ -41,379 +53,818 @@ pub const DECIMAL64: u8 = 2;
 /// Maximum precision of `Decimal64`.
 pub const DECIMAL64_MAX_PRECISION: u8 = 19;
 
-/// Computes by Taylor series, not accurate values.
+/// `e^0` to `e^290`, correctly rounded to 38 significant digits.
+///
+/// The values are computed with 120 significant digits and then rounded half up,
+/// so that each entry is off by at most half an ulp.
 static NATURAL_EXP: [Decimal; 291] = [
     // e^0
     unsafe { Decimal::from_raw_parts(1, 0, false) },
-    unsafe { Decimal::from_raw_parts(27182818284590452353602874713526624975, 37, false) },
-    unsafe { Decimal::from_raw_parts(73890560989306502272304274605750078133, 37, false) },
-    unsafe { Decimal::from_raw_parts(20085536923187667740928529654581717900, 36, false) },
-    unsafe { Decimal::from_raw_parts(54598150033144239078110261202860878404, 36, false) },
+    unsafe { Decimal::from_raw_parts(27182818284590452353602874713526624978, 37, false) },
+    unsafe { Decimal::from_raw_parts(73890560989306502272304274605750078132, 37, false) },
+    unsafe { Decimal::from_raw_parts(20085536923187667740928529654581717897, 36, false) },
+    unsafe { Decimal::from_raw_parts(54598150033144239078110261202860878403, 36, false) },
     // e^5
-    unsafe { Decimal::from_raw_parts(14841315910257660342111558004055227960, 35, false) },
-    unsafe { Decimal::from_raw_parts(40342879349273512260838718054338827962, 35, false) },
-    unsafe { Decimal::from_raw_parts(10966331584284585992637202382881214326, 34, false) },
-    unsafe { Decimal::from_raw_parts(29809579870417282747435920994528886736, 34, false) },
-    unsafe { Decimal::from_raw_parts(81030839275753840077099966894327599646, 34, false) },
+    unsafe { Decimal::from_raw_parts(14841315910257660342111558004055227962, 35, false) },
+    unsafe { Decimal::from_raw_parts(40342879349273512260838718054338827961, 35, false) },
+    unsafe { Decimal::from_raw_parts(10966331584284585992637202382881214324, 34, false) },
+    unsafe { Decimal::from_raw_parts(29809579870417282747435920994528886738, 34, false) },
+    unsafe { Decimal::from_raw_parts(81030839275753840077099966894327599650, 34, false) },
     // e^10
     unsafe { Decimal::from_raw_parts(22026465794806716516957900645284244366, 33, false) },
-    unsafe { Decimal::from_raw_parts(59874141715197818455326485792257781616, 33, false) },
-    unsafe { Decimal::from_raw_parts(16275479141900392080800520489848678316, 32, false) },
-    unsafe { Decimal::from_raw_parts(44241339200892050332610277594908828183, 32, false) },
-    unsafe { Decimal::from_raw_parts(12026042841647767777492367707678594496, 31, false) },
+    unsafe { Decimal::from_raw_parts(59874141715197818455326485792257781614, 33, false) },
+    unsafe { Decimal::from_raw_parts(16275479141900392080800520489848678317, 32, false) },
+    unsafe { Decimal::from_raw_parts(44241339200892050332610277594908828178, 32, false) },
+    unsafe { Decimal::from_raw_parts(12026042841647767777492367707678594494, 31, false) },
     // e^15
-    unsafe { Decimal::from_raw_parts(32690173724721106393018550460917213156, 31, false) },
-    unsafe { Decimal::from_raw_parts(88861105205078726367630237407814503509, 31, false) },
-    unsafe { Decimal::from_raw_parts(24154952753575298214775435180385823883, 30, false) },
-    unsafe { Decimal::from_raw_parts(65659969137330511138786503259060033570, 30, false) },
-    unsafe { Decimal::from_raw_parts(17848230096318726084491003378872270387, 29, false) },
+    unsafe { Decimal::from_raw_parts(32690173724721106393018550460917213155, 31, false) },
+    unsafe { Decimal::from_raw_parts(88861105205078726367630237407814503508, 31, false) },
+    unsafe { Decimal::from_raw_parts(24154952753575298214775435180385823880, 30, false) },
+    unsafe { Decimal::from_raw_parts(65659969137330511138786503259060033569, 30, false) },
+    unsafe { Decimal::from_raw_parts(17848230096318726084491003378872270388, 29, false) },
     // e^20
-    unsafe { Decimal::from_raw_parts(48516519540979027796910683054154055870, 29, false) },
-    unsafe { Decimal::from_raw_parts(13188157344832146972099988837453027850, 28, false) },
-    unsafe { Decimal::from_raw_parts(35849128461315915616811599459784206894, 28, false) },
-    unsafe { Decimal::from_raw_parts(97448034462489026000346326848229752775, 28, false) },
-    unsafe { Decimal::from_raw_parts(26489122129843472294139162152811882340, 27, false) },
+    unsafe { Decimal::from_raw_parts(48516519540979027796910683054154055868, 29, false) },
+    unsafe { Decimal::from_raw_parts(13188157344832146972099988837453027851, 28, false) },
+    unsafe { Decimal::from_raw_parts(35849128461315915616811599459784206892, 28, false) },
+    unsafe { Decimal::from_raw_parts(97448034462489026000346326848229752776, 28, false) },
+    unsafe { Decimal::from_raw_parts(26489122129843472294139162152811882341, 27, false) },
     // e^25
-    unsafe { Decimal::from_raw_parts(72004899337385872524161351466126157931, 27, false) },
-    unsafe { Decimal::from_raw_parts(19572960942883876426977639787609534281, 26, false) },
-    unsafe { Decimal::from_raw_parts(53204824060179861668374730434117744164, 26, false) },
-    unsafe { Decimal::from_raw_parts(14462570642914751736770474229969288564, 25, false) },
-    unsafe { Decimal::from_raw_parts(39313342971440420743886205808435276867, 25, false) },
+    unsafe { Decimal::from_raw_parts(72004899337385872524161351466126157915, 27, false) },
+    unsafe { Decimal::from_raw_parts(19572960942883876426977639787609534279, 26, false) },
+    unsafe { Decimal::from_raw_parts(53204824060179861668374730434117744166, 26, false) },
+    unsafe { Decimal::from_raw_parts(14462570642914751736770474229969288569, 25, false) },
+    unsafe { Decimal::from_raw_parts(39313342971440420743886205808435276858, 25, false) },
     // e^30
-    unsafe { Decimal::from_raw_parts(10686474581524462146990468650741401654, 24, false) },
-    unsafe { Decimal::from_raw_parts(29048849665247425231085682111679825660, 24, false) },
-    unsafe { Decimal::from_raw_parts(78962960182680695160978022635108224222, 24, false) },
+    unsafe { Decimal::from_raw_parts(10686474581524462146990468650741401650, 24, false) },
+    unsafe { Decimal::from_raw_parts(29048849665247425231085682111679825667, 24, false) },
+    unsafe { Decimal::from_raw_parts(78962960182680695160978022635108224220, 24, false) },
     unsafe { Decimal::from_raw_parts(21464357978591606462429776153126088037, 23, false) },
-    unsafe { Decimal::from_raw_parts(58346174252745488140290273461039101919, 23, false) },
+    unsafe { Decimal::from_raw_parts(58346174252745488140290273461039101900, 23, false) },
     // e^35
-    unsafe { Decimal::from_raw_parts(15860134523134307281296446257746601247, 22, false) },
-    unsafe { Decimal::from_raw_parts(43112315471151952271134222928569253911, 22, false) },
-    unsafe { Decimal::from_raw_parts(11719142372802611308772939791190194524, 21, false) },
-    unsafe { Decimal::from_raw_parts(31855931757113756220328671701298645997, 21, false) },
-    unsafe { Decimal::from_raw_parts(86593400423993746953606932719264934249, 21, false) },
+    unsafe { Decimal::from_raw_parts(15860134523134307281296446257746601252, 22, false) },
+    unsafe { Decimal::from_raw_parts(43112315471151952271134222928569253908, 22, false) },
+    unsafe { Decimal::from_raw_parts(11719142372802611308772939791190194522, 21, false) },
+    unsafe { Decimal::from_raw_parts(31855931757113756220328671701298646000, 21, false) },
+    unsafe { Decimal::from_raw_parts(86593400423993746953606932719264934250, 21, false) },
     // e^40
-    unsafe { Decimal::from_raw_parts(23538526683701998540789991074903480449, 20, false) },
-    unsafe { Decimal::from_raw_parts(63984349353005494922266340351557081880, 20, false) },
-    unsafe { Decimal::from_raw_parts(17392749415205010473946813036112352260, 19, false) },
-    unsafe { Decimal::from_raw_parts(47278394682293465614744575627442803712, 19, false) },
-    unsafe { Decimal::from_raw_parts(12851600114359308275809299632143099259, 18, false) },
+    unsafe { Decimal::from_raw_parts(23538526683701998540789991074903480451, 20, false) },
+    unsafe { Decimal::from_raw_parts(63984349353005494922266340351557081888, 20, false) },
+    unsafe { Decimal::from_raw_parts(17392749415205010473946813036112352261, 19, false) },
+    unsafe { Decimal::from_raw_parts(47278394682293465614744575627442803708, 19, false) },
+    unsafe { Decimal::from_raw_parts(12851600114359308275809299632143099258, 18, false) },
     // e^45
-    unsafe { Decimal::from_raw_parts(34934271057485095348034797233406099546, 18, false) },
-    unsafe { Decimal::from_raw_parts(94961194206024488745133649117118323116, 18, false) },
+    unsafe { Decimal::from_raw_parts(34934271057485095348034797233406099533, 18, false) },
+    unsafe { Decimal::from_raw_parts(94961194206024488745133649117118323102, 18, false) },
     unsafe { Decimal::from_raw_parts(25813128861900673962328580021527338043, 17, false) },
-    unsafe { Decimal::from_raw_parts(70167359120976317386547159988611740555, 17, false) },
-    unsafe { Decimal::from_raw_parts(19073465724950996905250998409538484479, 16, false) },
+    unsafe { Decimal::from_raw_parts(70167359120976317386547159988611740546, 17, false) },
+    unsafe { Decimal::from_raw_parts(19073465724950996905250998409538484474, 16, false) },
     // e^50
-    unsafe { Decimal::from_raw_parts(51847055285870724640874533229334853872, 16, false) },
+    unsafe { Decimal::from_raw_parts(51847055285870724640874533229334853848, 16, false) },
     unsafe { Decimal::from_raw_parts(14093490824269387964492143312370168789, 15, false) },
-    unsafe { Decimal::from_raw_parts(38310080007165768493035695487861993900, 15, false) },
-    unsafe { Decimal::from_raw_parts(10413759433029087797183472933493796442, 14, false) },
-    unsafe { Decimal::from_raw_parts(28307533032746939004420635480140745403, 14, false) },
+    unsafe { Decimal::from_raw_parts(38310080007165768493035695487861993899, 15, false) },
+    unsafe { Decimal::from_raw_parts(10413759433029087797183472933493796440, 14, false) },
+    unsafe { Decimal::from_raw_parts(28307533032746939004420635480140745409, 14, false) },
     // e^55
-    unsafe { Decimal::from_raw_parts(76947852651420171381827455901293939935, 14, false) },
-    unsafe { Decimal::from_raw_parts(20916594960129961539070711572146737783, 13, false) },
-    unsafe { Decimal::from_raw_parts(56857199993359322226403488206332533049, 13, false) },
-    unsafe { Decimal::from_raw_parts(15455389355901039303530766911174620071, 12, false) },
-    unsafe { Decimal::from_raw_parts(42012104037905142549565934307191617692, 12, false) },
+    unsafe { Decimal::from_raw_parts(76947852651420171381827455901293939921, 14, false) },
+    unsafe { Decimal::from_raw_parts(20916594960129961539070711572146737782, 13, false) },
+    unsafe { Decimal::from_raw_parts(56857199993359322226403488206332533034, 13, false) },
+    unsafe { Decimal::from_raw_parts(15455389355901039303530766911174620068, 12, false) },
+    unsafe { Decimal::from_raw_parts(42012104037905142549565934307191617684, 12, false) },
     // e^60
-    unsafe { Decimal::from_raw_parts(11420073898156842836629571831447656295, 11, false) },
-    unsafe { Decimal::from_raw_parts(31042979357019199087073421411071003730, 11, false) },
-    unsafe { Decimal::from_raw_parts(84383566687414544890733294803731179603, 11, false) },
+    unsafe { Decimal::from_raw_parts(11420073898156842836629571831447656302, 11, false) },
+    unsafe { Decimal::from_raw_parts(31042979357019199087073421411071003721, 11, false) },
+    unsafe { Decimal::from_raw_parts(84383566687414544890733294803731179601, 11, false) },
     unsafe { Decimal::from_raw_parts(22937831594696098790993528402686136005, 10, false) },
-    unsafe { Decimal::from_raw_parts(62351490808116168829092387089284697469, 10, false) },
+    unsafe { Decimal::from_raw_parts(62351490808116168829092387089284697448, 10, false) },
     // e^65
-    unsafe { Decimal::from_raw_parts(16948892444103337141417836114371974954, 9, false) },
-    unsafe { Decimal::from_raw_parts(46071866343312915426773184428060086892, 9, false) },
-    unsafe { Decimal::from_raw_parts(12523631708422137805135219607443657677, 8, false) },
+    unsafe { Decimal::from_raw_parts(16948892444103337141417836114371974949, 9, false) },
+    unsafe { Decimal::from_raw_parts(46071866343312915426773184428060086893, 9, false) },
+    unsafe { Decimal::from_raw_parts(12523631708422137805135219607443657675, 8, false) },
     unsafe { Decimal::from_raw_parts(34042760499317405213769071870043505954, 8, false) },
-    unsafe { Decimal::from_raw_parts(92537817255877876002423979166873458740, 8, false) },
+    unsafe { Decimal::from_raw_parts(92537817255877876002423979166873458735, 8, false) },
     // e^70
-    unsafe { Decimal::from_raw_parts(25154386709191670062657811742521129623, 7, false) },
-    unsafe { Decimal::from_raw_parts(68376712297627438667558928266777109561, 7, false) },
-    unsafe { Decimal::from_raw_parts(18586717452841279803403701812545411949, 6, false) },
-    unsafe { Decimal::from_raw_parts(50523936302761041945570383321857646506, 6, false) },
-    unsafe { Decimal::from_raw_parts(13733829795401761877841885298085389320, 5, false) },
+    unsafe { Decimal::from_raw_parts(25154386709191670062657811742521129614, 7, false) },
+    unsafe { Decimal::from_raw_parts(68376712297627438667558928266777109559, 7, false) },
+    unsafe { Decimal::from_raw_parts(18586717452841279803403701812545411947, 6, false) },
+    unsafe { Decimal::from_raw_parts(50523936302761041945570383321857646485, 6, false) },
+    unsafe { Decimal::from_raw_parts(13733829795401761877841885298085389316, 5, false) },
     // e^75
-    unsafe { Decimal::from_raw_parts(37332419967990016402549083172647001445, 5, false) },
-    unsafe { Decimal::from_raw_parts(10148003881138887278324617841317169760, 4, false) },
-    unsafe { Decimal::from_raw_parts(27585134545231702062864698199026619434, 4, false) },
-    unsafe { Decimal::from_raw_parts(74984169969901204346756305912240604567, 4, false) },
-    unsafe { Decimal::from_raw_parts(20382810665126687668323137537172632374, 3, false) },
+    unsafe { Decimal::from_raw_parts(37332419967990016402549083172647001434, 5, false) },
+    unsafe { Decimal::from_raw_parts(10148003881138887278324617841317169758, 4, false) },
+    unsafe { Decimal::from_raw_parts(27585134545231702062864698199026619433, 4, false) },
+    unsafe { Decimal::from_raw_parts(74984169969901204346756305912240604547, 4, false) },
+    unsafe { Decimal::from_raw_parts(20382810665126687668323137537172632375, 3, false) },
     // e^80
-    unsafe { Decimal::from_raw_parts(55406223843935100525711733958316612937, 3, false) },
-    unsafe { Decimal::from_raw_parts(15060973145850305483525941301676749817, 2, false) },
-    unsafe { Decimal::from_raw_parts(40939969621274546966609142293278290448, 2, false) },
-    unsafe { Decimal::from_raw_parts(11128637547917594120870714781839408062, 1, false) },
-    unsafe { Decimal::from_raw_parts(30250773222011423382665663964434287432, 1, false) },
+    unsafe { Decimal::from_raw_parts(55406223843935100525711733958316612925, 3, false) },
+    unsafe { Decimal::from_raw_parts(15060973145850305483525941301676749819, 2, false) },
+    unsafe { Decimal::from_raw_parts(40939969621274546966609142293278290432, 2, false) },
+    unsafe { Decimal::from_raw_parts(11128637547917594120870714781839408057, 1, false) },
+    unsafe { Decimal::from_raw_parts(30250773222011423382665663964434287425, 1, false) },
     // e^85
-    unsafe { Decimal::from_raw_parts(82230127146229135103043280164077746957, 1, false) },
+    unsafe { Decimal::from_raw_parts(82230127146229135103043280164077746955, 1, false) },
     unsafe { Decimal::from_raw_parts(22352466037347150474430657323327147399, 0, false) },
-    unsafe { Decimal::from_raw_parts(60760302250568721495223289381302760758, 0, false) },
-    unsafe { Decimal::from_raw_parts(16516362549940018555283297962648587672, -1, false) },
-    unsafe { Decimal::from_raw_parts(44896128191743452462842455796453162784, -1, false) },
+    unsafe { Decimal::from_raw_parts(60760302250568721495223289381302760753, 0, false) },
+    unsafe { Decimal::from_raw_parts(16516362549940018555283297962648587671, -1, false) },
+    unsafe { Decimal::from_raw_parts(44896128191743452462842455796453162778, -1, false) },
     // e^90
-    unsafe { Decimal::from_raw_parts(12204032943178408020027100351363697548, -2, false) },
-    unsafe { Decimal::from_raw_parts(33174000983357426257555161078525919101, -2, false) },
-    unsafe { Decimal::from_raw_parts(90176284050342989314009959821709052567, -2, false) },
-    unsafe { Decimal::from_raw_parts(24512455429200857855527729431109153420, -3, false) },
-    unsafe { Decimal::from_raw_parts(66631762164108958342448140502408732643, -3, false) },
+    unsafe { Decimal::from_raw_parts(12204032943178408020027100351363697540, -2, false) },
+    unsafe { Decimal::from_raw_parts(33174000983357426257555161078525919096, -2, false) },
+    unsafe { Decimal::from_raw_parts(90176284050342989314009959821709052591, -2, false) },
+    unsafe { Decimal::from_raw_parts(24512455429200857855527729431109153423, -3, false) },
+    unsafe { Decimal::from_raw_parts(66631762164108958342448140502408732627, -3, false) },
     // e^95
-    unsafe { Decimal::from_raw_parts(18112390828890232821937987580988159254, -4, false) },
-    unsafe { Decimal::from_raw_parts(49234582860120583997548620591133044956, -4, false) },
-    unsafe { Decimal::from_raw_parts(13383347192042695004617364087061150290, -5, false) },
-    unsafe { Decimal::from_raw_parts(36379709476088045792877438267601857313, -5, false) },
-    unsafe { Decimal::from_raw_parts(98890303193469467705600309671380371021, -5, false) },
+    unsafe { Decimal::from_raw_parts(18112390828890232821937987580988159250, -4, false) },
+    unsafe { Decimal::from_raw_parts(49234582860120583997548620591133044948, -4, false) },
+    unsafe { Decimal::from_raw_parts(13383347192042695004617364087061150291, -5, false) },
+    unsafe { Decimal::from_raw_parts(36379709476088045792877438267601857299, -5, false) },
+    unsafe { Decimal::from_raw_parts(98890303193469467705600309671380371014, -5, false) },
     // e^100
-    unsafe { Decimal::from_raw_parts(26881171418161354484126255515800135886, -6, false) },
-    unsafe { Decimal::from_raw_parts(73070599793680672726476826340615135883, -6, false) },
+    unsafe { Decimal::from_raw_parts(26881171418161354484126255515800135874, -6, false) },
+    unsafe { Decimal::from_raw_parts(73070599793680672726476826340615135890, -6, false) },
     unsafe { Decimal::from_raw_parts(19862648361376543258740468906137709930, -7, false) },
-    unsafe { Decimal::from_raw_parts(53992276105801688697616842371936818967, -7, false) },
-    unsafe { Decimal::from_raw_parts(14676622301554423285107021120870470922, -8, false) },
+    unsafe { Decimal::from_raw_parts(53992276105801688697616842371936818953, -7, false) },
+    unsafe { Decimal::from_raw_parts(14676622301554423285107021120870470927, -8, false) },
     // e^105
-    unsafe { Decimal::from_raw_parts(39895195705472158507637572787300953989, -8, false) },
-    unsafe { Decimal::from_raw_parts(10844638552900230813361001028568739551, -9, false) },
-    unsafe { Decimal::from_raw_parts(29478783914555093773878202487079276618, -9, false) },
-    unsafe { Decimal::from_raw_parts(80131642640005911410561058362935555141, -9, false) },
-    unsafe { Decimal::from_raw_parts(21782038807290206355539393313936824934, -10, false) },
+    unsafe { Decimal::from_raw_parts(39895195705472158507637572787300953987, -8, false) },
+    unsafe { Decimal::from_raw_parts(10844638552900230813361001028568739550, -9, false) },
+    unsafe { Decimal::from_raw_parts(29478783914555093773878202487079276619, -9, false) },
+    unsafe { Decimal::from_raw_parts(80131642640005911410561058362935555143, -9, false) },
+    unsafe { Decimal::from_raw_parts(21782038807290206355539393313936824929, -10, false) },
     // e^110
-    unsafe { Decimal::from_raw_parts(59209720276646702989552288155880397734, -10, false) },
-    unsafe { Decimal::from_raw_parts(16094870669615180549262332993373505801, -11, false) },
-    unsafe { Decimal::from_raw_parts(43750394472613410734625746750879389186, -11, false) },
-    unsafe { Decimal::from_raw_parts(11892590228282008819681954096389267312, -12, false) },
-    unsafe { Decimal::from_raw_parts(32327411910848593114262354205829189194, -12, false) },
+    unsafe { Decimal::from_raw_parts(59209720276646702989552288155880397723, -10, false) },
+    unsafe { Decimal::from_raw_parts(16094870669615180549262332993373505796, -11, false) },
+    unsafe { Decimal::from_raw_parts(43750394472613410734625746750879389181, -11, false) },
+    unsafe { Decimal::from_raw_parts(11892590228282008819681954096389267309, -12, false) },
+    unsafe { Decimal::from_raw_parts(32327411910848593114262354205829189192, -12, false) },
     // e^115
-    unsafe { Decimal::from_raw_parts(87875016358370231131069738030496383831, -12, false) },
-    unsafe { Decimal::from_raw_parts(23886906014249914254626392949441611667, -13, false) },
-    unsafe { Decimal::from_raw_parts(64931342556644621362249507087712085619, -13, false) },
-    unsafe { Decimal::from_raw_parts(17650168856917655832911782056447182390, -14, false) },
-    unsafe { Decimal::from_raw_parts(47978133272993021860034882895011331584, -14, false) },
+    unsafe { Decimal::from_raw_parts(87875016358370231131069738030496383797, -12, false) },
+    unsafe { Decimal::from_raw_parts(23886906014249914254626392949441611661, -13, false) },
+    unsafe { Decimal::from_raw_parts(64931342556644621362249507087712085620, -13, false) },
+    unsafe { Decimal::from_raw_parts(17650168856917655832911782056447182387, -14, false) },
+    unsafe { Decimal::from_raw_parts(47978133272993021860034882895011331564, -14, false) },
     // e^120
-    unsafe { Decimal::from_raw_parts(13041808783936322797338790280986488115, -15, false) },
-    unsafe { Decimal::from_raw_parts(35451311827611664751894074212478186941, -15, false) },
-    unsafe { Decimal::from_raw_parts(96366656736032012717638730141942241231, -15, false) },
+    unsafe { Decimal::from_raw_parts(13041808783936322797338790280986488113, -15, false) },
+    unsafe { Decimal::from_raw_parts(35451311827611664751894074212478186938, -15, false) },
+    unsafe { Decimal::from_raw_parts(96366656736032012717638730141942241234, -15, false) },
     unsafe { Decimal::from_raw_parts(26195173187490626761889810253746390880, -16, false) },
-    unsafe { Decimal::from_raw_parts(71205863268893377088330680682701942197, -16, false) },
+    unsafe { Decimal::from_raw_parts(71205863268893377088330680682701942199, -16, false) },
     // e^125
-    unsafe { Decimal::from_raw_parts(19355760420357225687206244905274872200, -17, false) },
-    unsafe { Decimal::from_raw_parts(52614411826663857451767767041616346183, -17, false) },
-    unsafe { Decimal::from_raw_parts(14302079958348104463583671072905261088, -18, false) },
-    unsafe { Decimal::from_raw_parts(38877084059945950922226736883574780745, -18, false) },
-    unsafe { Decimal::from_raw_parts(10567887114362588125648834960427354587, -19, false) },
+    unsafe { Decimal::from_raw_parts(19355760420357225687206244905274872189, -17, false) },
+    unsafe { Decimal::from_raw_parts(52614411826663857451767767041616346176, -17, false) },
+    unsafe { Decimal::from_raw_parts(14302079958348104463583671072905261081, -18, false) },
+    unsafe { Decimal::from_raw_parts(38877084059945950922226736883574780727, -18, false) },
+    unsafe { Decimal::from_raw_parts(10567887114362588125648834960427354591, -19, false) },
     // e^130
-    unsafe { Decimal::from_raw_parts(28726495508178319332673332249621538192, -19, false) },
-    unsafe { Decimal::from_raw_parts(78086710735191511717214963161789844250, -19, false) },
-    unsafe { Decimal::from_raw_parts(21226168683560893890870118295564590878, -20, false) },
-    unsafe { Decimal::from_raw_parts(57698708620330031794130831485493325609, -20, false) },
-    unsafe { Decimal::from_raw_parts(15684135116819639406725212333317378882, -21, false) },
+    unsafe { Decimal::from_raw_parts(28726495508178319332673332249621538189, -19, false) },
+    unsafe { Decimal::from_raw_parts(78086710735191511717214963161789844266, -19, false) },
+    unsafe { Decimal::from_raw_parts(21226168683560893890870118295564590884, -20, false) },
+    unsafe { Decimal::from_raw_parts(57698708620330031794130831485493325608, -20, false) },
+    unsafe { Decimal::from_raw_parts(15684135116819639406725212333317378881, -21, false) },
     // e^135
-    unsafe { Decimal::from_raw_parts(42633899483147210448936866880765989362, -21, false) },
-    unsafe { Decimal::from_raw_parts(11589095424138854283480495676005460415, -22, false) },
-    unsafe { Decimal::from_raw_parts(31502427499714519184111642911336978953, -22, false) },
-    unsafe { Decimal::from_raw_parts(85632476224822491931954909086237584537, -22, false) },
-    unsafe { Decimal::from_raw_parts(23277320404788620254741750385140984218, -23, false) },
+    unsafe { Decimal::from_raw_parts(42633899483147210448936866880765989356, -21, false) },
+    unsafe { Decimal::from_raw_parts(11589095424138854283480495676005460420, -22, false) },
+    unsafe { Decimal::from_raw_parts(31502427499714519184111642911336978946, -22, false) },
+    unsafe { Decimal::from_raw_parts(85632476224822491931954909086237584545, -22, false) },
+    unsafe { Decimal::from_raw_parts(23277320404788620254741750385140984221, -23, false) },
     // e^140
-    unsafe { Decimal::from_raw_parts(63274317071555853643430245123511451556, -23, false) },
-    unsafe { Decimal::from_raw_parts(17199742630376622641833783925547830056, -24, false) },
-    unsafe { Decimal::from_raw_parts(46753747846325154027207734100637066905, -24, false) },
-    unsafe { Decimal::from_raw_parts(12708986318302188795555166499146091281, -25, false) },
-    unsafe { Decimal::from_raw_parts(34546606567175463231258517866889865270, -25, false) },
+    unsafe { Decimal::from_raw_parts(63274317071555853643430245123511451531, -23, false) },
+    unsafe { Decimal::from_raw_parts(17199742630376622641833783925547830057, -24, false) },
+    unsafe { Decimal::from_raw_parts(46753747846325154027207734100637066908, -24, false) },
+    unsafe { Decimal::from_raw_parts(12708986318302188795555166499146091278, -25, false) },
+    unsafe { Decimal::from_raw_parts(34546606567175463231258517866889865273, -25, false) },
     // e^145
-    unsafe { Decimal::from_raw_parts(93907412866476978131540504016909901172, -25, false) },
-    unsafe { Decimal::from_raw_parts(25526681395254551047668755808654353440, -26, false) },
-    unsafe { Decimal::from_raw_parts(69388714177584033016228037440452491187, -26, false) },
+    unsafe { Decimal::from_raw_parts(93907412866476978131540504016909901150, -25, false) },
+    unsafe { Decimal::from_raw_parts(25526681395254551047668755808654353450, -26, false) },
+    unsafe { Decimal::from_raw_parts(69388714177584033016228037440452491146, -26, false) },
     unsafe { Decimal::from_raw_parts(18861808084906520052196148181812219044, -27, false) },
-    unsafe { Decimal::from_raw_parts(51271710169083297668258887684658163998, -27, false) },
+    unsafe { Decimal::from_raw_parts(51271710169083297668258887684658163977, -27, false) },
     // e^150
-    unsafe { Decimal::from_raw_parts(13937095806663796973183419371414574787, -28, false) },
-    unsafe { Decimal::from_raw_parts(37884954272746958042494750441949388081, -28, false) },
-    unsafe { Decimal::from_raw_parts(10298198277160991943993878773913738166, -29, false) },
-    unsafe { Decimal::from_raw_parts(27993405242674970683739228910895090969, -29, false) },
-    unsafe { Decimal::from_raw_parts(76093964787853542218200718174787272690, -29, false) },
+    unsafe { Decimal::from_raw_parts(13937095806663796973183419371414574775, -28, false) },
+    unsafe { Decimal::from_raw_parts(37884954272746958042494750441949388085, -28, false) },
+    unsafe { Decimal::from_raw_parts(10298198277160991943993878773913738157, -29, false) },
+    unsafe { Decimal::from_raw_parts(27993405242674970683739228910895090966, -29, false) },
+    unsafe { Decimal::from_raw_parts(76093964787853542218200718174787272677, -29, false) },
     // e^155
     unsafe { Decimal::from_raw_parts(20684484173822473091270347966282423297, -30, false) },
-    unsafe { Decimal::from_raw_parts(56226257460750335807897650819666306371, -30, false) },
+    unsafe { Decimal::from_raw_parts(56226257460750335807897650819666306380, -30, false) },
     unsafe { Decimal::from_raw_parts(15283881393781745666100414040841103028, -31, false) },
-    unsafe { Decimal::from_raw_parts(41545897061040224373905771068319348361, -31, false) },
+    unsafe { Decimal::from_raw_parts(41545897061040224373905771068319348353, -31, false) },
     unsafe { Decimal::from_raw_parts(11293345702805569478727022021871312858, -32, false) },
     // e^160
-    unsafe { Decimal::from_raw_parts(30698496406442424667364570301654957343, -32, false) },
-    unsafe { Decimal::from_raw_parts(83447164942647743609658358092023252638, -32, false) },
+    unsafe { Decimal::from_raw_parts(30698496406442424667364570301654957356, -32, false) },
+    unsafe { Decimal::from_raw_parts(83447164942647743609658358092023252622, -32, false) },
     unsafe { Decimal::from_raw_parts(22683291210002404713058390312611402982, -33, false) },
-    unsafe { Decimal::from_raw_parts(61659578305794325320049670543781654770, -33, false) },
-    unsafe { Decimal::from_raw_parts(16760811125908827725861073497722332472, -34, false) },
+    unsafe { Decimal::from_raw_parts(61659578305794325320049670543781654736, -33, false) },
+    unsafe { Decimal::from_raw_parts(16760811125908827725861073497722332471, -34, false) },
     // e^165
-    unsafe { Decimal::from_raw_parts(45560608313792156880112864411796691453, -34, false) },
-    unsafe { Decimal::from_raw_parts(12384657367292132198269856467846840036, -35, false) },
-    unsafe { Decimal::from_raw_parts(33664989073201642477955778901752989037, -35, false) },
-    unsafe { Decimal::from_raw_parts(91510928052956339360089438336198973142, -35, false) },
-    unsafe { Decimal::from_raw_parts(24875249283177429446603994479964329509, -36, false) },
+    unsafe { Decimal::from_raw_parts(45560608313792156880112864411796691440, -34, false) },
+    unsafe { Decimal::from_raw_parts(12384657367292132198269856467846840039, -35, false) },
+    unsafe { Decimal::from_raw_parts(33664989073201642477955778901752989050, -35, false) },
+    unsafe { Decimal::from_raw_parts(91510928052956339360089438336198973100, -35, false) },
+    unsafe { Decimal::from_raw_parts(24875249283177429446603994479964329505, -36, false) },
     // e^170
-    unsafe { Decimal::from_raw_parts(67617938104850097226297739817614724043, -36, false) },
-    unsafe { Decimal::from_raw_parts(18380461242828247026619661332259011810, -37, false) },
-    unsafe { Decimal::from_raw_parts(49963273795075782374799992291440821058, -37, false) },
-    unsafe { Decimal::from_raw_parts(13581425924747849789093255011954118328, -38, false) },
-    unsafe { Decimal::from_raw_parts(36918143295804664423920014322334714971, -38, false) },
+    unsafe { Decimal::from_raw_parts(67617938104850097226297739817614724025, -36, false) },
+    unsafe { Decimal::from_raw_parts(18380461242828247026619661332259011809, -37, false) },
+    unsafe { Decimal::from_raw_parts(49963273795075782374799992291440821061, -37, false) },
+    unsafe { Decimal::from_raw_parts(13581425924747849789093255011954118318, -38, false) },
+    unsafe { Decimal::from_raw_parts(36918143295804664423920014322334714981, -38, false) },
     // e^175
-    unsafe { Decimal::from_raw_parts(10035391806143294571946733464755740501, -39, false) },
-    unsafe { Decimal::from_raw_parts(27279023188106115192557593199527116730, -39, false) },
-    unsafe { Decimal::from_raw_parts(74152073030341784283386937576609008214, -39, false) },
-    unsafe { Decimal::from_raw_parts(20156623266094612066329318409141309108, -40, false) },
-    unsafe { Decimal::from_raw_parts(54791382747319794379865564450966140139, -40, false) },
+    unsafe { Decimal::from_raw_parts(10035391806143294571946733464755740495, -39, false) },
+    unsafe { Decimal::from_raw_parts(27279023188106115192557593199527116721, -39, false) },
+    unsafe { Decimal::from_raw_parts(74152073030341784283386937576609008174, -39, false) },
+    unsafe { Decimal::from_raw_parts(20156623266094612066329318409141309115, -40, false) },
+    unsafe { Decimal::from_raw_parts(54791382747319794379865564450966140144, -40, false) },
     // e^180
-    unsafe { Decimal::from_raw_parts(14893842007818383595644410230322886973, -41, false) },
-    unsafe { Decimal::from_raw_parts(40485660085792693262271426689569678698, -41, false) },
-    unsafe { Decimal::from_raw_parts(11005143412437994843280976031210742493, -42, false) },
-    unsafe { Decimal::from_raw_parts(29915081357615969207184701601447122427, -42, false) },
-    unsafe { Decimal::from_raw_parts(81317622051281434061126712044925707902, -42, false) },
+    unsafe { Decimal::from_raw_parts(14893842007818383595644410230322886974, -41, false) },
+    unsafe { Decimal::from_raw_parts(40485660085792693262271426689569678681, -41, false) },
+    unsafe { Decimal::from_raw_parts(11005143412437994843280976031210742489, -42, false) },
+    unsafe { Decimal::from_raw_parts(29915081357615969207184701601447122430, -42, false) },
+    unsafe { Decimal::from_raw_parts(81317622051281434061126712044925707887, -42, false) },
     // e^185
-    unsafe { Decimal::from_raw_parts(22104421435549887327561037093210488312, -43, false) },
-    unsafe { Decimal::from_raw_parts(60086047116855861250341632178539649714, -43, false) },
-    unsafe { Decimal::from_raw_parts(16333081002168329377271943881088378495, -44, false) },
-    unsafe { Decimal::from_raw_parts(44397917290943821356155881988414973276, -44, false) },
-    unsafe { Decimal::from_raw_parts(12068605179340023095364473314473432497, -45, false) },
+    unsafe { Decimal::from_raw_parts(22104421435549887327561037093210488315, -43, false) },
+    unsafe { Decimal::from_raw_parts(60086047116855861250341632178539649688, -43, false) },
+    unsafe { Decimal::from_raw_parts(16333081002168329377271943881088378488, -44, false) },
+    unsafe { Decimal::from_raw_parts(44397917290943821356155881988414973293, -44, false) },
+    unsafe { Decimal::from_raw_parts(12068605179340023095364473314473432498, -45, false) },
     // e^190
-    unsafe { Decimal::from_raw_parts(32805870153846701518250084137059135841, -45, false) },
-    unsafe { Decimal::from_raw_parts(89175600705988431420770803324912086042, -45, false) },
-    unsafe { Decimal::from_raw_parts(24240441494100795852378097352461489720, -46, false) },
-    unsafe { Decimal::from_raw_parts(65892351627238821736753930934534639373, -46, false) },
-    unsafe { Decimal::from_raw_parts(17911398206275708900431827624144225532, -47, false) },
+    unsafe { Decimal::from_raw_parts(32805870153846701518250084137059135827, -45, false) },
+    unsafe { Decimal::from_raw_parts(89175600705988431420770803324912086063, -45, false) },
+    unsafe { Decimal::from_raw_parts(24240441494100795852378097352461489713, -46, false) },
+    unsafe { Decimal::from_raw_parts(65892351627238821736753930934534639366, -46, false) },
+    unsafe { Decimal::from_raw_parts(17911398206275708900431827624144225529, -47, false) },
     // e^195
-    unsafe { Decimal::from_raw_parts(48688228266413197067093362018659672146, -47, false) },
-    unsafe { Decimal::from_raw_parts(13234832615645703553069383005626040404, -48, false) },
-    unsafe { Decimal::from_raw_parts(35976005001806811307586628488491091980, -48, false) },
-    unsafe { Decimal::from_raw_parts(97792920656963176027414937748815917871, -48, false) },
-    unsafe { Decimal::from_raw_parts(26582871917376019734003283472389741150, -49, false) },
+    unsafe { Decimal::from_raw_parts(48688228266413197067093362018659672139, -47, false) },
+    unsafe { Decimal::from_raw_parts(13234832615645703553069383005626040403, -48, false) },
+    unsafe { Decimal::from_raw_parts(35976005001806811307586628488491091977, -48, false) },
+    unsafe { Decimal::from_raw_parts(97792920656963176027414937748815917878, -48, false) },
+    unsafe { Decimal::from_raw_parts(26582871917376019734003283472389741152, -49, false) },
     // e^200
-    unsafe { Decimal::from_raw_parts(72259737681257492581774770421893056951, -49, false) },
-    unsafe { Decimal::from_raw_parts(19642233186817958656484864137420231201, -50, false) },
-    unsafe { Decimal::from_raw_parts(53393125542082459716222599802082679919, -50, false) },
-    unsafe { Decimal::from_raw_parts(14513756292567525940523654914390132839, -51, false) },
+    unsafe { Decimal::from_raw_parts(72259737681257492581774770421893056974, -49, false) },
+    unsafe { Decimal::from_raw_parts(19642233186817958656484864137420231202, -50, false) },
+    unsafe { Decimal::from_raw_parts(53393125542082459716222599802082679886, -50, false) },
+    unsafe { Decimal::from_raw_parts(14513756292567525940523654914390132843, -51, false) },
     unsafe { Decimal::from_raw_parts(39452479992769427900327573211143818566, -51, false) },
     // e^205
-    unsafe { Decimal::from_raw_parts(10724295945198918021924451209369968217, -52, false) },
-    unsafe { Decimal::from_raw_parts(29151658790851239660496155224556382547, -52, false) },
-    unsafe { Decimal::from_raw_parts(79242424360609307491188688802264059684, -52, false) },
-    unsafe { Decimal::from_raw_parts(21540324218248465690209815988756000148, -53, false) },
-    unsafe { Decimal::from_raw_parts(58552671901581093475081587475320346051, -53, false) },
+    unsafe { Decimal::from_raw_parts(10724295945198918021924451209369968212, -52, false) },
+    unsafe { Decimal::from_raw_parts(29151658790851239660496155224556382539, -52, false) },
+    unsafe { Decimal::from_raw_parts(79242424360609307491188688802264059687, -52, false) },
+    unsafe { Decimal::from_raw_parts(21540324218248465690209815988756000150, -53, false) },
+    unsafe { Decimal::from_raw_parts(58552671901581093475081587475320346009, -53, false) },
     // e^210
-    unsafe { Decimal::from_raw_parts(15916266403779241591571863407774423364, -54, false) },
-    unsafe { Decimal::from_raw_parts(43264897742306309199371472477969207063, -54, false) },
+    unsafe { Decimal::from_raw_parts(15916266403779241591571863407774423360, -54, false) },
+    unsafe { Decimal::from_raw_parts(43264897742306309199371472477969207059, -54, false) },
     unsafe { Decimal::from_raw_parts(11760618534305001227335647241278102208, -55, false) },
-    unsafe { Decimal::from_raw_parts(31968675653239935348846785115930182070, -55, false) },
-    unsafe { Decimal::from_raw_parts(86899870108103213822063274684049309002, -55, false) },
+    unsafe { Decimal::from_raw_parts(31968675653239935348846785115930182080, -55, false) },
+    unsafe { Decimal::from_raw_parts(86899870108103213822063274684049308987, -55, false) },
     // e^215
-    unsafe { Decimal::from_raw_parts(23621833781030833300746567469515129092, -56, false) },
-    unsafe { Decimal::from_raw_parts(64210801521856135516771541362226454717, -56, false) },
-    unsafe { Decimal::from_raw_parts(17454305496765194050281862479081601620, -57, false) },
-    unsafe { Decimal::from_raw_parts(47445721460229655544587842889161196570, -57, false) },
-    unsafe { Decimal::from_raw_parts(12897084248347162974810234147016917437, -58, false) },
+    unsafe { Decimal::from_raw_parts(23621833781030833300746567469515129095, -56, false) },
+    unsafe { Decimal::from_raw_parts(64210801521856135516771541362226454735, -56, false) },
+    unsafe { Decimal::from_raw_parts(17454305496765194050281862479081601611, -57, false) },
+    unsafe { Decimal::from_raw_parts(47445721460229655544587842889161196585, -57, false) },
+    unsafe { Decimal::from_raw_parts(12897084248347162974810234147016917447, -58, false) },
     // e^220
-    unsafe { Decimal::from_raw_parts(35057909752387477224025060891275483360, -58, false) },
-    unsafe { Decimal::from_raw_parts(95297279023672025386355634986304892255, -58, false) },
-    unsafe { Decimal::from_raw_parts(25904486187163901031830171287130712546, -59, false) },
-    unsafe { Decimal::from_raw_parts(70415694078135969991088372949671264959, -59, false) },
-    unsafe { Decimal::from_raw_parts(19140970165092820820108477320064452781, -60, false) },
+    unsafe { Decimal::from_raw_parts(35057909752387477224025060891275483343, -58, false) },
+    unsafe { Decimal::from_raw_parts(95297279023672025386355634986304892235, -58, false) },
+    unsafe { Decimal::from_raw_parts(25904486187163901031830171287130712530, -59, false) },
+    unsafe { Decimal::from_raw_parts(70415694078135969991088372949671264956, -59, false) },
+    unsafe { Decimal::from_raw_parts(19140970165092820820108477320064452775, -60, false) },
     // e^225
-    unsafe { Decimal::from_raw_parts(52030551378848545923020205358078977737, -60, false) },
-    unsafe { Decimal::from_raw_parts(14143370233782872265039837168370554989, -61, false) },
-    unsafe { Decimal::from_raw_parts(38445666299660540093457531706674996418, -61, false) },
-    unsafe { Decimal::from_raw_parts(10450615608536754863982177507098957249, -62, false) },
-    unsafe { Decimal::from_raw_parts(28407718504895927718534013347769901830, -62, false) },
+    unsafe { Decimal::from_raw_parts(52030551378848545923020205358078977704, -60, false) },
+    unsafe { Decimal::from_raw_parts(14143370233782872265039837168370554987, -61, false) },
+    unsafe { Decimal::from_raw_parts(38445666299660540093457531706674996414, -61, false) },
+    unsafe { Decimal::from_raw_parts(10450615608536754863982177507098957251, -62, false) },
+    unsafe { Decimal::from_raw_parts(28407718504895927718534013347769901828, -62, false) },
     // e^230
-    unsafe { Decimal::from_raw_parts(77220184999838357175621252140277020406, -62, false) },
-    unsafe { Decimal::from_raw_parts(20990622567530634724568039312619468559, -63, false) },
-    unsafe { Decimal::from_raw_parts(57058427893360872481970148326895352874, -63, false) },
+    unsafe { Decimal::from_raw_parts(77220184999838357175621252140277020356, -62, false) },
+    unsafe { Decimal::from_raw_parts(20990622567530634724568039312619468571, -63, false) },
+    unsafe { Decimal::from_raw_parts(57058427893360872481970148326895352861, -63, false) },
     unsafe { Decimal::from_raw_parts(15510088770296358097556054518881247548, -64, false) },
-    unsafe { Decimal::from_raw_parts(42160792462083288741186917596094351517, -64, false) },
+    unsafe { Decimal::from_raw_parts(42160792462083288741186917596094351497, -64, false) },
     // e^235
-    unsafe { Decimal::from_raw_parts(11460491602311409370637865042895610414, -65, false) },
-    unsafe { Decimal::from_raw_parts(31152846067770590954201464312400440172, -65, false) },
-    unsafe { Decimal::from_raw_parts(84682215370802619418949577677244718361, -65, false) },
-    unsafe { Decimal::from_raw_parts(23019012723610800962705119766260408375, -66, false) },
-    unsafe { Decimal::from_raw_parts(62572163995658794914917604846876973579, -66, false) },
+    unsafe { Decimal::from_raw_parts(11460491602311409370637865042895610408, -65, false) },
+    unsafe { Decimal::from_raw_parts(31152846067770590954201464312400440163, -65, false) },
+    unsafe { Decimal::from_raw_parts(84682215370802619418949577677244718306, -65, false) },
+    unsafe { Decimal::from_raw_parts(23019012723610800962705119766260408380, -66, false) },
+    unsafe { Decimal::from_raw_parts(62572163995658794914917604846876973543, -66, false) },
     // e^240
-    unsafe { Decimal::from_raw_parts(17008877635675862685398902860714557440, -67, false) },
-    unsafe { Decimal::from_raw_parts(46234922999541146273426274861568776275, -67, false) },
+    unsafe { Decimal::from_raw_parts(17008877635675862685398902860714557450, -67, false) },
+    unsafe { Decimal::from_raw_parts(46234922999541146273426274861568776258, -67, false) },
     unsafe { Decimal::from_raw_parts(12567955102985587136353369613287969585, -68, false) },
-    unsafe { Decimal::from_raw_parts(34163243977334849966907467619116852824, -68, false) },
-    unsafe { Decimal::from_raw_parts(92865325304802240908397570249090596499, -68, false) },
+    unsafe { Decimal::from_raw_parts(34163243977334849966907467619116852829, -68, false) },
+    unsafe { Decimal::from_raw_parts(92865325304802240908397570249090596517, -68, false) },
     // e^245
-    unsafe { Decimal::from_raw_parts(25243412626998187770632793234418799940, -69, false) },
-    unsafe { Decimal::from_raw_parts(68618709832262784296500189663439273040, -69, false) },
-    unsafe { Decimal::from_raw_parts(18652499202934394647893057141276968924, -70, false) },
-    unsafe { Decimal::from_raw_parts(50702749638683390134216749367456409844, -70, false) },
-    unsafe { Decimal::from_raw_parts(13782436299574148088857901819149382333, -71, false) },
+    unsafe { Decimal::from_raw_parts(25243412626998187770632793234418799926, -69, false) },
+    unsafe { Decimal::from_raw_parts(68618709832262784296500189663439272991, -69, false) },
+    unsafe { Decimal::from_raw_parts(18652499202934394647893057141276968936, -70, false) },
+    unsafe { Decimal::from_raw_parts(50702749638683390134216749367456409828, -70, false) },
+    unsafe { Decimal::from_raw_parts(13782436299574148088857901819149382340, -71, false) },
     // e^250
-    unsafe { Decimal::from_raw_parts(37464546145026732603499548122029201501, -71, false) },
-    unsafe { Decimal::from_raw_parts(10183919499749154121311809801154593781, -72, false) },
-    unsafe { Decimal::from_raw_parts(27682763318657855929985771603963318292, -72, false) },
-    unsafe { Decimal::from_raw_parts(75249552490640263726958791405721841505, -72, false) },
-    unsafe { Decimal::from_raw_parts(20454949113498251750794190253329225813, -73, false) },
+    unsafe { Decimal::from_raw_parts(37464546145026732603499548122029201458, -71, false) },
+    unsafe { Decimal::from_raw_parts(10183919499749154121311809801154593784, -72, false) },
+    unsafe { Decimal::from_raw_parts(27682763318657855929985771603963318300, -72, false) },
+    unsafe { Decimal::from_raw_parts(75249552490640263726958791405721841470, -72, false) },
+    unsafe { Decimal::from_raw_parts(20454949113498251750794190253329225805, -73, false) },
     // e^255
-    unsafe { Decimal::from_raw_parts(55602316477276754174041540473381702051, -73, false) },
-    unsafe { Decimal::from_raw_parts(15114276650041035425200896657072865078, -74, false) },
-    unsafe { Decimal::from_raw_parts(41084863568109398732746435014199662608, -74, false) },
+    unsafe { Decimal::from_raw_parts(55602316477276754174041540473381702041, -73, false) },
+    unsafe { Decimal::from_raw_parts(15114276650041035425200896657072865075, -74, false) },
+    unsafe { Decimal::from_raw_parts(41084863568109398732746435014199662588, -74, false) },
     unsafe { Decimal::from_raw_parts(11168023806191082975759894188368741636, -75, false) },
-    unsafe { Decimal::from_raw_parts(30357836172167242865270564060096681892, -75, false) },
+    unsafe { Decimal::from_raw_parts(30357836172167242865270564060096681882, -75, false) },
     // e^260
-    unsafe { Decimal::from_raw_parts(82521154418138915708209187078469436590, -75, false) },
-    unsafe { Decimal::from_raw_parts(22431575451828987090132598854038981998, -76, false) },
-    unsafe { Decimal::from_raw_parts(60975343934414732803540925731945597709, -76, false) },
-    unsafe { Decimal::from_raw_parts(16574816940096003310288868055969816163, -77, false) },
-    unsafe { Decimal::from_raw_parts(45055023698298121117106125112845233389, -77, false) },
+    unsafe { Decimal::from_raw_parts(82521154418138915708209187078469436554, -75, false) },
+    unsafe { Decimal::from_raw_parts(22431575451828987090132598854038982005, -76, false) },
+    unsafe { Decimal::from_raw_parts(60975343934414732803540925731945597721, -76, false) },
+    unsafe { Decimal::from_raw_parts(16574816940096003310288868055969816149, -77, false) },
+    unsafe { Decimal::from_raw_parts(45055023698298121117106125112845233382, -77, false) },
     // e^265
-    unsafe { Decimal::from_raw_parts(12247225219987543111692123050999620531, -78, false) },
-    unsafe { Decimal::from_raw_parts(33291409764537471210498902650647395181, -78, false) },
-    unsafe { Decimal::from_raw_parts(90495434206726229847410205869155592671, -78, false) },
-    unsafe { Decimal::from_raw_parts(24599209436265500385962442739613565585, -79, false) },
-    unsafe { Decimal::from_raw_parts(66867584005058783767836195501715462777, -79, false) },
+    unsafe { Decimal::from_raw_parts(12247225219987543111692123050999620526, -78, false) },
+    unsafe { Decimal::from_raw_parts(33291409764537471210498902650647395179, -78, false) },
+    unsafe { Decimal::from_raw_parts(90495434206726229847410205869155592694, -78, false) },
+    unsafe { Decimal::from_raw_parts(24599209436265500385962442739613565582, -79, false) },
+    unsafe { Decimal::from_raw_parts(66867584005058783767836195501715462755, -79, false) },
     // e^270
-    unsafe { Decimal::from_raw_parts(18176493851390999782546650445313340672, -80, false) },
-    unsafe { Decimal::from_raw_parts(49408832941333720129685111047602318635, -80, false) },
-    unsafe { Decimal::from_raw_parts(13430713274979613085859250297613421779, -81, false) },
-    unsafe { Decimal::from_raw_parts(36508463838620754258131757683218532187, -81, false) },
-    unsafe { Decimal::from_raw_parts(99240293837476957258975386473680449662, -81, false) },
+    unsafe { Decimal::from_raw_parts(18176493851390999782546650445313340664, -80, false) },
+    unsafe { Decimal::from_raw_parts(49408832941333720129685111047602318612, -80, false) },
+    unsafe { Decimal::from_raw_parts(13430713274979613085859250297613421776, -81, false) },
+    unsafe { Decimal::from_raw_parts(36508463838620754258131757683218532178, -81, false) },
+    unsafe { Decimal::from_raw_parts(99240293837476957258975386473680449664, -81, false) },
     // e^275
-    unsafe { Decimal::from_raw_parts(26976308738934978232765417912571366677, -82, false) },
-    unsafe { Decimal::from_raw_parts(73329209843947893397917976493127739665, -82, false) },
-    unsafe { Decimal::from_raw_parts(19932945861406369879404057817936726125, -83, false) },
-    unsafe { Decimal::from_raw_parts(54183364522718865591003756988762312406, -83, false) },
-    unsafe { Decimal::from_raw_parts(14728565518687920080874372478970627032, -84, false) },
+    unsafe { Decimal::from_raw_parts(26976308738934978232765417912571366660, -82, false) },
+    unsafe { Decimal::from_raw_parts(73329209843947893397917976493127739671, -82, false) },
+    unsafe { Decimal::from_raw_parts(19932945861406369879404057817936726113, -83, false) },
+    unsafe { Decimal::from_raw_parts(54183364522718865591003756988762312410, -83, false) },
+    unsafe { Decimal::from_raw_parts(14728565518687920080874372478970627035, -84, false) },
     // e^280
-    unsafe { Decimal::from_raw_parts(40036392008717845384002607853055449617, -84, false) },
-    unsafe { Decimal::from_raw_parts(10883019687436065167926658665346876179, -85, false) },
-    unsafe { Decimal::from_raw_parts(29583114655119494191648535413124937628, -85, false) },
-    unsafe { Decimal::from_raw_parts(80415242996231796059259460914427322527, -85, false) },
-    unsafe { Decimal::from_raw_parts(21859129376777539785144693723458114365, -86, false) },
+    unsafe { Decimal::from_raw_parts(40036392008717845384002607853055449602, -84, false) },
+    unsafe { Decimal::from_raw_parts(10883019687436065167926658665346876177, -85, false) },
+    unsafe { Decimal::from_raw_parts(29583114655119494191648535413124937617, -85, false) },
+    unsafe { Decimal::from_raw_parts(80415242996231796059259460914427322465, -85, false) },
+    unsafe { Decimal::from_raw_parts(21859129376777539785144693723458114363, -86, false) },
     // e^285
-    unsafe { Decimal::from_raw_parts(59419274170829680786039665041625326132, -86, false) },
-    unsafe { Decimal::from_raw_parts(16151833323879222366041833857187834774, -87, false) },
-    unsafe { Decimal::from_raw_parts(43905235020600150754042953190395882915, -87, false) },
-    unsafe { Decimal::from_raw_parts(11934680253072108439235558933754921818, -88, false) },
-    unsafe { Decimal::from_raw_parts(32441824460394911649740723321265334285, -88, false) },
+    unsafe { Decimal::from_raw_parts(59419274170829680786039665041625326135, -86, false) },
+    unsafe { Decimal::from_raw_parts(16151833323879222366041833857187834768, -87, false) },
+    unsafe { Decimal::from_raw_parts(43905235020600150754042953190395882895, -87, false) },
+    unsafe { Decimal::from_raw_parts(11934680253072108439235558933754921831, -88, false) },
+    unsafe { Decimal::from_raw_parts(32441824460394911649740723321265334268, -88, false) },
     // e^290
-    unsafe { Decimal::from_raw_parts(88186021912749658986094822427733469383, -88, false) },
+    unsafe { Decimal::from_raw_parts(88186021912749658986094822427733469359, -88, false) },
 ];
 
-/// Computes by Taylor series, not accurate values.
-static NATURAL_EXP_NEG: [Decimal; 9] = [
-    // e^-291
-    unsafe { Decimal::from_raw_parts(41716298478166806118243377939293045745, 164, false) },
-    unsafe { Decimal::from_raw_parts(15346568571889094399003486191226211569, 164, false) },
-    unsafe { Decimal::from_raw_parts(56456870701257797059912015304055553681, 165, false) },
-    unsafe { Decimal::from_raw_parts(20769322043867093362333818538068856442, 165, false) },
-    // e^-295
-    unsafe { Decimal::from_raw_parts(76406065870075445735958388880036815267, 166, false) },
-    unsafe { Decimal::from_raw_parts(28108220814391766921916452972683068317, 166, false) },
-    unsafe { Decimal::from_raw_parts(10340436565521946602575863724595250916, 166, false) },
-    unsafe { Decimal::from_raw_parts(38040340251929620404917847776950070293, 167, false) },
-    unsafe { Decimal::from_raw_parts(13994259113851392172977837187029463838, 167, false) },
+/// The next 19 digits of `e^0` to `e^290`, i.e. `e^n * 10^(s + 19) - m * 10^19` rounded half up,
+/// where `m` and `s` are the coefficient and the scale of `NATURAL_EXP[n]`.
+///
+/// Together with `NATURAL_EXP` they give `e^n` to 57 significant digits, so that the error of
+/// the table doesn't reach the 38 digits of [`Decimal::exp`].
+static NATURAL_EXP_LOW: [i64; 291] = [
+    // e^0
+    0,
+    -4275290630004042503,
+    -1968442944815267591,
+    -120921614458498556,
+    -2092629613859312742,
+    // e^5
+    3487667593878989047,
+    -4100102642870797386,
+    4221913483361314378,
+    -4403206086716429779,
+    1147608783161346250,
+    // e^10
+    3535126185567810742,
+    2610796957409686528,
+    209284478720770444,
+    4391306060589715572,
+    1248654337610224031,
+    // e^15
+    573854382003420663,
+    271982185663883978,
+    -1324326472774826202,
+    2163557861868194915,
+    3619733165166426195,
+    // e^20
+    4638988944847254354,
+    -855556261952425093,
+    2269306503727493493,
+    4938776403600697636,
+    -1298013807514694235,
+    // e^25
+    2235338133952787362,
+    2036100950697593892,
+    -744195716311191162,
+    206232950992287517,
+    -305766655609781436,
+    // e^30
+    244950054730549902,
+    -2353049097030197507,
+    -438048846476693449,
+    -774093945202102741,
+    3659238941108105783,
+    // e^35
+    -2379604986547384573,
+    -1113638321965226923,
+    -3246363055381776165,
+    -4577900948189922468,
+    -2981452998040134085,
+    // e^40
+    -1128382745444532763,
+    -663378603144720545,
+    4798405772500840104,
+    1975196238093817097,
+    -1988567792411741281,
+    // e^45
+    4116564975181542601,
+    -1828410789200121496,
+    1637082993044060811,
+    -4062001274678016245,
+    -1181026945621659752,
+    // e^50
+    2746910058384640190,
+    -3152392099362859829,
+    -2944025448834224092,
+    -1953286684508675195,
+    -4967410646409315928,
+    // e^55
+    -2923242734915908077,
+    -4703315250073273433,
+    -2784166551178225651,
+    3637170969308302467,
+    1111197329909678646,
+    // e^60
+    -195404044360416043,
+    -3704982323629118489,
+    -1930732033258959538,
+    -3672065909695176986,
+    3139184623579991439,
+    // e^65
+    -737637744834950868,
+    3490030370960407306,
+    3488527404497481641,
+    -2612386005434836395,
+    -2340079279158688065,
+    // e^70
+    741297338314051382,
+    4583593128908772567,
+    -536033037671967316,
+    3672449756171770789,
+    -2001692075006253618,
+    // e^75
+    2778368691600002339,
+    -2333518483188211912,
+    4152017524870885910,
+    465897778615881302,
+    -3026351384757897647,
+    // e^80
+    -1432711673146771297,
+    -60350787173457837,
+    53792193852777765,
+    3407692072049303419,
+    -3096777769591183979,
+    // e^85
+    -1371381152372596048,
+    -2246074867900260571,
+    -3862467403787898516,
+    -3037115799995518611,
+    -3401941362814847395,
+    // e^90
+    -2925357890023245376,
+    301458118233082705,
+    2874611320072683318,
+    4875641496469060955,
+    -1261124579034344060,
+    // e^95
+    4789717454412861870,
+    3779957125266027941,
+    -2328254278635539988,
+    -689874413591703712,
+    508260719933517340,
+    // e^100
+    -3888812262580775848,
+    783900839607076164,
+    -4606820944653162825,
+    -3592886936637645039,
+    -862457630251569313,
+    // e^105
+    -2274569188805952631,
+    -81988066705561206,
+    46305807338193435,
+    -39613497704806420,
+    -1662762736600435580,
+    // e^110
+    -4506705698846739385,
+    -3213659188754232103,
+    3657688707274792136,
+    2593546442296898154,
+    -4173473649439209959,
+    // e^115
+    740763204875080975,
+    -3801870354353127031,
+    2186842073167790906,
+    2248694954607649394,
+    -4284579739297220237,
+    // e^120
+    4460794157551327283,
+    1778657670235047700,
+    -1333342533270687411,
+    -3156299447869960250,
+    -4023095913909425085,
+    // e^125
+    -4371699227617674272,
+    4562040784413432277,
+    -2516157747493150284,
+    2817506308299888577,
+    616543808401826724,
+    // e^130
+    4532426973996326913,
+    -3148436625100259214,
+    -735348179776703782,
+    -3007981801454249818,
+    4569663250161872069,
+    // e^135
+    4687458532552810874,
+    -214313793647283482,
+    -4340022094973866110,
+    -881013696748035403,
+    -3390853414178727483,
+    // e^140
+    1244598822338744071,
+    2564840507091586992,
+    4728820060198171925,
+    -2287889103423345845,
+    -4503351499214952618,
+    // e^145
+    -4411878174584178863,
+    -3269273382683043640,
+    1856743678498997776,
+    3876512678688044225,
+    3867995979276353615,
+    // e^150
+    -2630993859781561766,
+    3548843387691201617,
+    -1649517301214164101,
+    -2164280639275760065,
+    732029587975281366,
+    // e^155
+    -3993299436036116611,
+    2471694319380414479,
+    46450461216031251,
+    2371195379617258171,
+    -4621971264754268985,
+    // e^160
+    -1793133623103815307,
+    -1598198743188699749,
+    3883842375249274007,
+    -1534822556266148592,
+    -3643488930255591996,
+    // e^165
+    -1569143520993123589,
+    1137617813031094742,
+    1740229342807759830,
+    2888525366479665000,
+    3538173267478033830,
+    // e^170
+    -2611559237542184140,
+    -4753271990114423258,
+    -3525640231019174945,
+    3843950266258086314,
+    4305237155652510576,
+    // e^175
+    -4062291790135960731,
+    2109642072433890753,
+    706509317174283403,
+    311683132981328952,
+    -4892750300387254125,
+    // e^180
+    -2187174787061535519,
+    -3957990661148484099,
+    733246562118734204,
+    1158507735672873879,
+    -2251549980375635504,
+    // e^185
+    -4482097125349042928,
+    2707763915470171723,
+    -3203880431029165921,
+    -2249250600928808869,
+    -4610861042348895075,
+    // e^190
+    2409295554394105338,
+    2965343053084797070,
+    2677334020855885690,
+    1867679168237243927,
+    3171901407362870981,
+    // e^195
+    -999823207301554431,
+    541476934628446270,
+    1222733208853995279,
+    4941497236864633489,
+    572606828420302028,
+    // e^200
+    -4312557147268071597,
+    -3945832105851967003,
+    -2912465606891032099,
+    -3324495228362276579,
+    -2207010249134976176,
+    // e^205
+    4869855501063981329,
+    3695373744735403975,
+    2813366165328834128,
+    4435323697941850135,
+    2455644779776861641,
+    // e^210
+    -4890063186914816421,
+    -8582766094034955,
+    -208695984223380765,
+    3527874126115084984,
+    1932899735117338762,
+    // e^215
+    81005294518651599,
+    2949460433831523990,
+    2615789472003255495,
+    2831265387766847491,
+    1128748398695863006,
+    // e^220
+    -4875416918850544838,
+    1323883129435410156,
+    -4023418841155733265,
+    -3758929036641883381,
+    -3777527885352703843,
+    // e^225
+    3526007931352159367,
+    2490270185006030196,
+    2688099773929816673,
+    3628464003348633997,
+    -2150588892532058077,
+    // e^230
+    -372514087674041671,
+    4246332806389881474,
+    -4442637141816039299,
+    -3942048455699494085,
+    -4327630222670325824,
+    // e^235
+    2416307390076406936,
+    -2048985571439726128,
+    -710082158468612798,
+    -3099494153626919624,
+    3283723402834938360,
+    // e^240
+    626505636290673531,
+    -2833247069509357390,
+    2478513230256012596,
+    -1037092613361563228,
+    902286179711556579,
+    // e^245
+    -4440593386822051876,
+    -3749170980246034510,
+    -2524440059397274215,
+    1762246505259046286,
+    4273908010091118046,
+    // e^250
+    883075428131591923,
+    -3101878247565488154,
+    -1309768351290877852,
+    -2110062237290689264,
+    1603082775806168570,
+    // e^255
+    288002933349011833,
+    624089828712071632,
+    3592795601947586959,
+    870988027244370754,
+    4688445030090854230,
+    // e^260
+    -2855425150339311643,
+    1061140035277771094,
+    -1203096808444209262,
+    4450398144561884269,
+    90338745628871050,
+    // e^265
+    -3612782959262360065,
+    2259656014899231535,
+    3210500432763560697,
+    4206207362176003714,
+    -1848714086551967512,
+    // e^270
+    2881830038245163051,
+    -2441897939687921744,
+    -1242951324989612220,
+    -4673478132878845801,
+    592855059879328973,
+    // e^275
+    -1793382106343544089,
+    1316588350926088858,
+    1009623426671361349,
+    3283849532704892327,
+    -3711075972368717191,
+    // e^280
+    4739376094206328003,
+    -3752541168709580871,
+    -599082967737016887,
+    -2926622907033680212,
+    -1267834292519333876,
+    // e^285
+    -3632341512485739747,
+    857780091651794236,
+    -3762555131185285528,
+    227727193799609021,
+    1240075822199245983,
+    // e^290
+    259427743268418366,
 ];
 
-pub(crate) type Buf = stack_buf::StackVec<u8, 256>;
+/// Count of the fractional digits of `e^b` in fixed point, where `-1 < b < 1` is the fraction part of an exponent.
+const EXP_SCALE: i16 = 48;
+
+const BUF_CAPACITY: usize = 256;
+
+pub(crate) type Buf = stack_buf::StackVec<u8, BUF_CAPACITY>;
+
+/// The longest output of `fmt_internal` without a precision: either `MAX_PRECISION` digits followed by
+/// `-MIN_SCALE` zeros, or a sign, "0." and the largest scale kept by `from_parts`.
+const MAX_FMT_LEN: usize = {
+    let int_len = MAX_PRECISION as usize + (-MIN_SCALE) as usize;
+    let frac_len = 3 + (MAX_SCALE as usize + MAX_PRECISION as usize - 1);
+    if int_len > frac_len {
+        int_len + 1
+    } else {
+        frac_len
+    }
+};
+
+// A change of the constants mustn't overflow the buffers of the formatters.
+const _: [(); 0] = [(); (MAX_FMT_LEN > BUF_CAPACITY) as usize];
+const _: [(); 0] = [(); (MAX_PRECISION > 40) as usize];
+
+/// Rounding mode used when digits have to be discarded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round to nearest, ties away from zero.
+    HalfUp,
+    /// Round to nearest, ties to the even neighbor.
+    HalfEven,
+    /// Round to nearest, ties toward zero.
+    HalfDown,
+    /// Round away from zero.
+    Up,
+    /// Round toward zero.
+    Down,
+    /// Round toward positive infinity.
+    Ceiling,
+    /// Round toward negative infinity.
+    Floor,
+}
+
+impl RoundingMode {
+    /// Returns `true` if a truncated magnitude must be incremented by one unit.
+    ///
+    /// `half` is the ordering of the discarded part against one half unit, `exact` tells
+    /// whether the discarded part is zero and `odd` whether the truncated magnitude is odd.
+    #[inline]
+    pub(crate) fn round_up(self, half: Ordering, exact: bool, odd: bool, negative: bool) -> bool {
+        match self {
+            RoundingMode::HalfUp => half != Ordering::Less,
+            RoundingMode::HalfEven => half == Ordering::Greater || (half == Ordering::Equal && odd),
+            RoundingMode::HalfDown => half == Ordering::Greater,
+            RoundingMode::Up => !exact,
+            RoundingMode::Down => false,
+            RoundingMode::Ceiling => !exact && !negative,
+            RoundingMode::Floor => !exact && negative,
+        }
+    }
+
+    /// Divides `val` by `10^e` and rounds the quotient with this mode.
+    #[inline]
+    pub(crate) fn div_pow10(self, val: u128, e: u32, negative: bool) -> u128 {
+        debug_assert!(e <= MAX_PRECISION);
+        let divisor = POWERS_10[e as usize].low();
+        let quotient = val / divisor;
+        let rem = val % divisor;
+        // `rem < 10^38`, so doubling it cannot overflow.
+        let half = (rem * 2).cmp(&divisor);
+        if self.round_up(half, rem == 0, quotient % 2 == 1, negative) {
+            quotient + 1
+        } else {
+            quotient
+        }
+    }
+}
 
 /// High precision decimal.
-#[derive(Copy, Clone, Debug, Eq)]
+///
+/// The `Debug` output shows the value and the scale of the representation, e.g. `Decimal("123.45", scale=4)`
+/// for `123.4500`, and the alternate form `{:#?}` shows the raw parts `int_val`, `scale` and `negative`, which
+/// [`Decimal::from_parts_str`] parses back.
+///
+/// # Layout
+///
+/// A `Decimal` is [`Decimal::SIZE_BYTES`] bytes long and aligned to [`Decimal::ALIGN_BYTES`] bytes, which is part
+/// of the semver contract, so unsafe code may rely on them, e.g. to size the slots of an arena. The order and the
+/// encoding of the fields are not, and may change in any release: exchange the value with [`Decimal::into_parts`]
+/// and [`Decimal::from_parts`], or with [`DecimalRaw`](crate::DecimalRaw) for a fixed byte layout.
+///
+/// ```
+/// use decimal_rs::Decimal;
+///
+/// assert_eq!(std::mem::size_of::<Decimal>(), Decimal::SIZE_BYTES);
+/// assert_eq!(std::mem::align_of::<Decimal>(), Decimal::ALIGN_BYTES);
+/// ```
+#[derive(Copy, Clone, Eq)]
 #[repr(C, packed(4))]
 pub struct Decimal {
     int_val: u128,
@@ -423,22 +874,73 @@ pub struct Decimal {
     _aligned: u8,
 }
 
+// The layout guarantees fail the build rather than the tests if a field changes.
+const _: [(); Decimal::SIZE_BYTES] = [(); core::mem::size_of::<Decimal>()];
+const _: [(); Decimal::ALIGN_BYTES] = [(); core::mem::align_of::<Decimal>()];
+
 impl Decimal {
+    /// The size of a `Decimal` in bytes, which is stable across semver-compatible releases.
+    pub const SIZE_BYTES: usize = 20;
+
+    /// The alignment of a `Decimal` in bytes, which is stable across semver-compatible releases.
+    pub const ALIGN_BYTES: usize = 4;
+
     /// Zero value, i.e. `0`.
     pub const ZERO: Decimal = unsafe { Decimal::from_raw_parts(0, 0, false) };
 
     /// i.e. `1`.
     pub const ONE: Decimal = unsafe { Decimal::from_raw_parts(1, 0, false) };
 
+    /// The largest value, i.e. `9.9999999999999999999999999999999999999E+125`.
+    ///
+    /// Arithmetic overflows and parsing fails at `1E+126`, so this is 38 nines at scale `MIN_SCALE + 38`,
+    /// rather than at scale `MIN_SCALE`.
+    pub const MAX: Decimal =
+        unsafe { Decimal::from_raw_parts(MAX_I128_REPR as u128, MIN_SCALE + MAX_PRECISION as i16, false) };
+
+    /// The smallest value, i.e. `-MAX`.
+    pub const MIN: Decimal =
+        unsafe { Decimal::from_raw_parts(MAX_I128_REPR as u128, MIN_SCALE + MAX_PRECISION as i16, true) };
+
+    /// The smallest positive value, i.e. `1E-130`.
+    pub const MIN_POSITIVE: Decimal = unsafe { Decimal::from_raw_parts(1, MAX_SCALE, false) };
+
+    /// Archimedes' constant π, rounded to 38 significant digits.
+    pub const PI: Decimal = Decimal::from_parts_const(31415926535897932384626433832795028842, 37, false);
+
+    /// Euler's number e, rounded to 38 significant digits.
+    pub const E: Decimal = Decimal::from_parts_const(27182818284590452353602874713526624978, 37, false);
+
+    /// ln(10), rounded to 38 significant digits.
+    pub const LN_10: Decimal = Decimal::from_parts_const(23025850929940456840179914546843642076, 37, false);
+
+    /// ln(2), rounded to 38 significant digits.
+    pub const LN_2: Decimal = Decimal::from_parts_const(69314718055994530941723212145817656808, 38, false);
+
+    /// √2, rounded to 38 significant digits.
+    pub const SQRT_2: Decimal = Decimal::from_parts_const(14142135623730950488016887242096980786, 37, false);
+
     /// i.e. `-1`.
-    const MINUS_ONE: Decimal = unsafe { Decimal::from_raw_parts(1, 0, true) };
+    pub(crate) const MINUS_ONE: Decimal = unsafe { Decimal::from_raw_parts(1, 0, true) };
 
     /// i.e. `2`.
-    const TWO: Decimal = unsafe { Decimal::from_raw_parts(2, 0, false) };
+    pub(crate) const TWO: Decimal = unsafe { Decimal::from_raw_parts(2, 0, false) };
+
+    /// The largest scale of a decimal.
+    pub(crate) const MAX_REPR_SCALE: i16 = MAX_SCALE + MAX_PRECISION as i16 - 1;
 
     /// i.e. `0.5`.
     const ZERO_POINT_FIVE: Decimal = unsafe { Decimal::from_raw_parts(5, 1, false) };
 
+    /// Creates a `Decimal` from parts as is, which may break the invariant that a zero is not negative.
+    ///
+    /// Every caller must rule out a negative zero. The callers are:
+    /// - the constants, which are not zero,
+    /// - `from_parts_unchecked`, which returns `Decimal::ZERO` for a zero mantissa,
+    /// - the integer conversions, which are negative only for a non-zero integer,
+    /// - `Neg for &Decimal`, which returns `Decimal::ZERO` for zero.
+    ///
+    /// All the other constructors, including `decode`, go through `from_parts_unchecked`.
     #[inline]
     pub(crate) const unsafe fn from_raw_parts(int_val: u128, scale: i16, negative: bool) -> Decimal {
         Decimal {
@@ -464,32 +966,284 @@ impl Decimal {
 
     /// Creates a `Decimal` from parts.
     ///
-    /// `int_val` has at most 38 tens digits, `scale` ranges from `[-126, 130]`.
+    /// `int_val` has at most 38 tens digits, failing with `Overflow` otherwise, and `scale` ranges from
+    /// `[MIN_SCALE, MAX_SCALE + MAX_PRECISION - 1]`, i.e. `[-126, 167]`, failing with `ScaleOutOfRange`
+    /// otherwise.
+    ///
+    /// The bound of the scale is asymmetric: the scale may exceed [`MAX_SCALE`] by up to 37, so that the
+    /// 38 digits of a mantissa can reach below `1e-130`, e.g. `12345678901234567890123456789012345678`
+    /// at scale 167 is about `1.2e-130`. The value itself isn't checked, so `1` at scale 167 is accepted
+    /// too.
+    ///
+    /// With [`Decimal::into_parts`], this is the stable interchange of a decimal, which doesn't depend on
+    /// its memory layout.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, DecimalConvertError, MAX_PRECISION, MAX_SCALE, MIN_SCALE};
+    ///
+    /// assert!(Decimal::from_parts(1, MAX_SCALE + MAX_PRECISION as i16 - 1, false).is_ok());
+    /// assert_eq!(
+    ///     Decimal::from_parts(1, MAX_SCALE + MAX_PRECISION as i16, false),
+    ///     Err(DecimalConvertError::ScaleOutOfRange)
+    /// );
+    /// assert_eq!(Decimal::from_parts(1, MIN_SCALE - 1, false), Err(DecimalConvertError::ScaleOutOfRange));
+    /// assert_eq!(Decimal::from_parts(u128::MAX, 0, false), Err(DecimalConvertError::Overflow));
+    /// ```
     #[inline]
     pub const fn from_parts(int_val: u128, scale: i16, negative: bool) -> Result<Decimal, DecimalConvertError> {
         if int_val > MAX_I128_REPR as u128 {
             return Err(DecimalConvertError::Overflow);
         }
 
-        if scale >= MAX_SCALE + MAX_PRECISION as i16 || scale < MIN_SCALE {
-            return Err(DecimalConvertError::Overflow);
+        if scale > Decimal::MAX_REPR_SCALE || scale < MIN_SCALE {
+            return Err(DecimalConvertError::ScaleOutOfRange);
         }
 
         Ok(unsafe { Decimal::from_parts_unchecked(int_val, scale, negative) })
     }
 
+    /// Returns `true` if [`Decimal::from_parts`] accepts `int_val` and `scale`, whatever the sign.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// assert!(Decimal::is_valid_parts(12345, 130));
+    /// assert!(Decimal::is_valid_parts(12345, 167));
+    /// assert!(!Decimal::is_valid_parts(12345, 168));
+    /// assert!(!Decimal::is_valid_parts(u128::MAX, 0));
+    /// ```
+    #[inline]
+    pub const fn is_valid_parts(int_val: u128, scale: i16) -> bool {
+        Decimal::from_parts(int_val, scale, false).is_ok()
+    }
+
+    /// Creates a `Decimal` from parts in a constant, failing to compile on invalid parts.
+    ///
+    /// The parts are validated as [`Decimal::from_parts`] does, and this panics on invalid parts at runtime.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// const RATE: Decimal = Decimal::from_parts_const(125, 3, false);
+    /// assert_eq!(RATE.to_string(), "0.125");
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use decimal_rs::Decimal;
+    ///
+    /// const TOO_LARGE: Decimal = Decimal::from_parts_const(u128::MAX, 0, false);
+    /// # let _ = TOO_LARGE;
+    /// ```
+    #[inline]
+    pub const fn from_parts_const(int_val: u128, scale: i16, negative: bool) -> Decimal {
+        match Decimal::from_parts(int_val, scale, negative) {
+            Ok(d) => d,
+            Err(_) => {
+                // `panic!` isn't allowed in a `const fn` yet, so an out-of-bounds index fails the
+                // constant evaluation instead.
+                let invalid_parts: [Decimal; 0] = [];
+                invalid_parts[int_val as usize]
+            }
+        }
+    }
+
     /// Consumes the `Decimal`, returning `(int_val, scale, negative)`.
+    ///
+    /// The parts don't depend on the memory layout and are stable across semver-compatible releases,
+    /// and [`Decimal::from_parts`] validates them back into an equal decimal.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n: Decimal = "-123.450".parse().unwrap();
+    /// let (int_val, scale, negative) = n.into_parts();
+    /// assert_eq!((int_val, scale, negative), (12345, 2, true));
+    /// assert_eq!(Decimal::from_parts(int_val, scale, negative), Ok(n));
+    /// ```
     #[inline]
     pub const fn into_parts(self) -> (u128, i16, bool) {
         (self.int_val, self.scale, self.negative)
     }
 
+    /// Creates a `Decimal` from a signed mantissa and a scale, i.e. `mantissa * 10^-scale`.
+    ///
+    /// `mantissa` has at most 38 tens digits, `scale` ranges from `[-126, 130]`.
+    #[inline]
+    pub const fn from_scaled_i128(mantissa: i128, scale: i16) -> Result<Decimal, DecimalConvertError> {
+        Decimal::from_parts(mantissa.unsigned_abs(), scale, mantissa < 0)
+    }
+
+    /// Returns the signed mantissa and the scale as stored, i.e. `(mantissa, scale)` where
+    /// `self = mantissa * 10^-scale`.
+    ///
+    /// The mantissa has at most 38 tens digits, so it always fits in an `i128`.
+    #[inline]
+    pub const fn to_scaled_i128(&self) -> (i128, i16) {
+        let mantissa = self.int_val as i128;
+        if self.negative {
+            (-mantissa, self.scale)
+        } else {
+            (mantissa, self.scale)
+        }
+    }
+
+    /// Returns the magnitudes of the integral part and of the fractional part at `frac_scale`, i.e.
+    /// `(int, frac)` where `|self| ≈ int + frac * 10^-frac_scale` and `frac < 10^frac_scale`.
+    ///
+    /// The fractional part is rounded to `frac_scale` digits with `mode`, which may carry into the
+    /// integral part, and padded with zeros if `frac_scale` is larger than the scale. The sign is given
+    /// by [`Decimal::is_sign_negative`]. Returns `None` if `frac_scale` is larger than 38 or the integral
+    /// part exceeds `u128`.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, RoundingMode};
+    ///
+    /// let ts: Decimal = "1700000000.1234565".parse().unwrap();
+    /// assert_eq!(ts.int_frac_parts(6, RoundingMode::HalfEven), Some((1700000000, 123456)));
+    /// assert_eq!(ts.int_frac_parts(6, RoundingMode::HalfUp), Some((1700000000, 123457)));
+    /// ```
+    #[inline]
+    pub fn int_frac_parts(&self, frac_scale: u8, mode: RoundingMode) -> Option<(u128, u128)> {
+        if frac_scale as u32 > MAX_PRECISION {
+            return None;
+        }
+
+        if self.scale <= 0 {
+            // `10^39` exceeds `u128`, and zero has scale 0
+            let e = -self.scale as u32;
+            if e > MAX_PRECISION {
+                return None;
+            }
+            let int = self.int_val.checked_mul(POWERS_10[e as usize].low())?;
+            return Some((int, 0));
+        }
+
+        // A single division splits the integral part and the fractional part at `self.scale`.
+        let scale = self.scale as u32;
+        let (int, rem) = if scale > MAX_PRECISION {
+            (0, self.int_val)
+        } else {
+            let divisor = POWERS_10[scale as usize].low();
+            (self.int_val / divisor, self.int_val % divisor)
+        };
+
+        let frac_scale = frac_scale as u32;
+        if frac_scale >= scale {
+            return Some((int, rem * POWERS_10[(frac_scale - scale) as usize].low()));
+        }
+
+        let e = scale - frac_scale;
+        let (frac, half, exact) = if e > MAX_PRECISION {
+            // `rem < 10^38`, which is less than half of `10^e`
+            (0, Ordering::Less, rem == 0)
+        } else {
+            let divisor = POWERS_10[e as usize].low();
+            let r = rem % divisor;
+            (rem / divisor, (r * 2).cmp(&divisor), r == 0)
+        };
+
+        // the last kept digit is in the integral part at `frac_scale` 0
+        let odd = if frac_scale == 0 { int % 2 == 1 } else { frac % 2 == 1 };
+        if !mode.round_up(half, exact, odd, self.negative) {
+            return Some((int, frac));
+        }
+
+        let frac = frac + 1;
+        if frac == POWERS_10[frac_scale as usize].low() {
+            Some((int.checked_add(1)?, 0))
+        } else {
+            Some((int, frac))
+        }
+    }
+
     /// Returns the precision, i.e. the count of significant digits in this decimal.
     #[inline]
     pub fn precision(&self) -> u8 {
         U256::from(self.int_val).count_digits() as u8
     }
 
+    /// Returns the exponent of the most significant digit, i.e. `floor(log10(|self|))`,
+    /// returning `None` if `self` is zero.
+    ///
+    /// This is the `logB` operation of IEEE 754, except that the logarithm of zero is
+    /// `None` rather than negative infinity.
+    #[inline]
+    pub fn logb(&self) -> Option<i16> {
+        if self.is_zero() {
+            return None;
+        }
+
+        Some(self.precision() as i16 - 1 - self.scale)
+    }
+
+    /// Returns the most significant digit of the absolute value, returning `None` if `self` is zero.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n: Decimal = "-0.0314".parse().unwrap();
+    /// assert_eq!(n.leading_digit(), Some(3));
+    /// assert_eq!(Decimal::ZERO.leading_digit(), None);
+    /// ```
+    #[inline]
+    pub fn leading_digit(&self) -> Option<u8> {
+        if self.is_zero() {
+            return None;
+        }
+
+        let precision = self.precision();
+        Some((self.int_val / POWERS_10[precision as usize - 1].low()) as u8)
+    }
+
+    /// Returns the digit of the absolute value at the position of `10^pos`, e.g. `0` for the units
+    /// and `-1` for the tenths, which is zero out of the stored digits.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n: Decimal = "-123.45".parse().unwrap();
+    /// assert_eq!(n.digit_at(2), 1);
+    /// assert_eq!(n.digit_at(0), 3);
+    /// assert_eq!(n.digit_at(-2), 5);
+    /// assert_eq!(n.digit_at(-3), 0);
+    /// ```
+    #[inline]
+    pub fn digit_at(&self, pos: i16) -> u8 {
+        // the index of the digit in `int_val`, counting from the least significant one
+        let index = pos as i32 + self.scale as i32;
+        if index < 0 || index >= MAX_PRECISION as i32 {
+            return 0;
+        }
+
+        (self.int_val / POWERS_10[index as usize].low() % 10) as u8
+    }
+
+    /// Computes `self * 10^n` exactly, returning `None` if overflow or underflow occurred.
+    ///
+    /// This is the `scaleB` operation of IEEE 754. The significant digits are kept unchanged,
+    /// so the result is never rounded: a result whose magnitude is out of the range of decimal
+    /// is `None` instead of being rounded to zero.
+    #[inline]
+    pub fn scaleb(&self, n: i16) -> Option<Decimal> {
+        if self.is_zero() {
+            return Some(Decimal::ZERO);
+        }
+
+        // the exponent of the most significant digit ranges from `[-130, 125]`
+        let exponent = self.precision() as i32 - 1 - self.scale as i32 + n as i32;
+        if exponent >= -(MIN_SCALE as i32) {
+            // overflow
+            return None;
+        }
+        if exponent < -(MAX_SCALE as i32) {
+            // underflow
+            return None;
+        }
+
+        let scale = (self.scale as i32 - n as i32) as i16;
+        Some(unsafe { Decimal::from_parts_unchecked(self.int_val, scale, self.negative) })
+    }
+
     #[inline(always)]
     pub(crate) const fn int_val(&self) -> u128 {
         self.int_val
@@ -520,6 +1274,16 @@ impl Decimal {
         self.int_val == 0
     }
 
+    /// Returns `true` if `self` keeps the invariants of the representation, i.e. the mantissa has at most
+    /// 38 digits and a zero is not negative.
+    ///
+    /// All safe constructors return canonical decimals, so this is meant for debug assertions on
+    /// decimals built by unsafe code.
+    #[inline]
+    pub const fn is_canonical(&self) -> bool {
+        self.int_val <= MAX_I128_REPR as u128 && !(self.int_val == 0 && self.negative)
+    }
+
     /// Returns `true` if the decimal has fractional portion.
     #[inline]
     pub fn has_fract(&self) -> bool {
@@ -563,17 +1327,11 @@ impl Decimal {
         [flags, abs_scale]
     }
 
-    /// Encodes `self` to `writer` as binary bytes.
-    /// Returns total size on success, which is not larger than [`MAX_BINARY_SIZE`].
-    fn internal_encode<W: io::Write, const COMPACT: bool>(&self, mut writer: W) -> std::io::Result<usize> {
+    /// Encodes `self` as binary bytes, returning the buffer and the size of the encoding in it.
+    fn internal_encode<const COMPACT: bool>(&self) -> ([u8; MAX_BINARY_SIZE], usize) {
+        let mut buf = [0; MAX_BINARY_SIZE];
         if self.is_zero() {
-            return if COMPACT {
-                writer.write_all(&[0])?;
-                Ok(1)
-            } else {
-                writer.write_all(&[0; 3])?;
-                Ok(3)
-            };
+            return (buf, if COMPACT { 1 } else { 3 });
         }
 
         let int_bytes: [u8; 16] = self.int_val.to_le_bytes();
@@ -583,28 +1341,25 @@ impl Decimal {
 
         if COMPACT && trailing_non_zeros <= 2 && self.scale == 0 && self.is_sign_positive() {
             debug_assert_ne!(trailing_non_zeros, 0);
-            return if trailing_non_zeros == 1 {
-                writer.write_all(&int_bytes[0..1])?;
-                Ok(1)
-            } else {
-                writer.write_all(&int_bytes[0..2])?;
-                Ok(2)
-            };
+            buf[..trailing_non_zeros].copy_from_slice(&int_bytes[..trailing_non_zeros]);
+            return (buf, trailing_non_zeros);
         }
 
-        let header = self.encode_header();
-        writer.write_all(&header)?;
-        writer.write_all(&int_bytes[0..trailing_non_zeros])?;
         let size = trailing_non_zeros + 2;
-
-        Ok(size)
+        buf[..2].copy_from_slice(&self.encode_header());
+        buf[2..size].copy_from_slice(&int_bytes[0..trailing_non_zeros]);
+        (buf, size)
     }
 
     /// Encodes `self` to `writer` as binary bytes.
     /// Returns total size on success, which is not larger than [`MAX_BINARY_SIZE`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     #[inline]
-    pub fn encode<W: io::Write>(&self, writer: W) -> std::io::Result<usize> {
-        self.internal_encode::<_, false>(writer)
+    pub fn encode<W: std::io::Write>(&self, mut writer: W) -> std::io::Result<usize> {
+        let (buf, size) = self.internal_encode::<false>();
+        writer.write_all(&buf[..size])?;
+        Ok(size)
     }
 
     /// Encodes `self` to `writer` as binary bytes.
@@ -612,16 +1367,112 @@ impl Decimal {
     ///
     /// The only different from [`Decimal::encode`] is it will compact encoded bytes
     /// when `self` is zero or small positive integer.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[inline]
+    pub fn compact_encode<W: std::io::Write>(&self, mut writer: W) -> std::io::Result<usize> {
+        let (buf, size) = self.internal_encode::<true>();
+        writer.write_all(&buf[..size])?;
+        Ok(size)
+    }
+
+    /// Encodes `self` to the start of `buf` as the bytes of [`Decimal::encode`], without `std`.
+    /// Returns total size on success, or `None` if `buf` is too short.
+    ///
+    /// A buffer of [`MAX_BINARY_SIZE`] bytes is always long enough.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, MAX_BINARY_SIZE};
+    ///
+    /// let n: Decimal = "-1.5".parse().unwrap();
+    /// let mut buf = [0; MAX_BINARY_SIZE];
+    /// let size = n.encode_to_slice(&mut buf).unwrap();
+    /// assert_eq!(buf[..size], [0x03, 1, 15]);
+    /// assert_eq!(n.encode_to_slice(&mut buf[..2]), None);
+    /// ```
     #[inline]
-    pub fn compact_encode<W: io::Write>(&self, writer: W) -> std::io::Result<usize> {
-        self.internal_encode::<_, true>(writer)
+    pub fn encode_to_slice(&self, buf: &mut [u8]) -> Option<usize> {
+        let (bytes, size) = self.internal_encode::<false>();
+        buf.get_mut(..size)?.copy_from_slice(&bytes[..size]);
+        Some(size)
+    }
+
+    /// Encodes `self` to the start of `buf` as the bytes of [`Decimal::compact_encode`], without `std`.
+    /// Returns total size on success, or `None` if `buf` is too short.
+    #[inline]
+    pub fn compact_encode_to_slice(&self, buf: &mut [u8]) -> Option<usize> {
+        let (bytes, size) = self.internal_encode::<true>();
+        buf.get_mut(..size)?.copy_from_slice(&bytes[..size]);
+        Some(size)
+    }
+
+    /// Encodes `self` into exactly [`MAX_BINARY_SIZE`] bytes, which are the bytes of [`Decimal::encode`]
+    /// padded with zeros, for fixed-size records.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, MAX_BINARY_SIZE};
+    ///
+    /// let n: Decimal = "-1.5".parse().unwrap();
+    /// let bytes: [u8; MAX_BINARY_SIZE] = n.to_bytes();
+    /// assert_eq!(bytes[..3], [0x03, 1, 15]);
+    /// assert!(bytes[3..].iter().all(|&b| b == 0));
+    /// assert_eq!(Decimal::from_bytes(&bytes), n);
+    /// ```
+    #[inline]
+    pub fn to_bytes(&self) -> [u8; MAX_BINARY_SIZE] {
+        // A zero has the header `[0, 0]`, as its scale is 0 and its sign is positive.
+        let mut bytes = [0; MAX_BINARY_SIZE];
+        bytes[..2].copy_from_slice(&self.encode_header());
+        bytes[2..].copy_from_slice(&self.int_val.to_le_bytes());
+        bytes
+    }
+
+    /// Decodes a `Decimal` from the bytes of [`Decimal::to_bytes`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` isn't a valid encoding, see [`Decimal::try_decode`].
+    #[inline]
+    pub fn from_bytes(bytes: &[u8; MAX_BINARY_SIZE]) -> Decimal {
+        Decimal::decode(bytes)
     }
 
     /// Decodes a `Decimal` from binary bytes.
+    ///
+    /// The bytes are validated as by [`Decimal::try_decode`], which should be preferred for bytes from
+    /// an untrusted source, e.g. read from disk or the network. Bytes past [`MAX_BINARY_SIZE`] are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` isn't a valid encoding.
     #[inline]
     pub fn decode(bytes: &[u8]) -> Decimal {
+        let len = bytes.len().min(MAX_BINARY_SIZE);
+        Decimal::try_decode(&bytes[..len]).expect("invalid decimal bytes")
+    }
+
+    /// Decodes a `Decimal` from binary bytes, the output of [`Decimal::encode`], [`Decimal::compact_encode`]
+    /// or [`Decimal::to_bytes`].
+    ///
+    /// Fails with `DecimalConvertError::Invalid` if `bytes` is empty, longer than [`MAX_BINARY_SIZE`] or has
+    /// unknown flags, and with `DecimalConvertError::Overflow` or `DecimalConvertError::ScaleOutOfRange` if
+    /// the integer or the scale is out of the range of [`Decimal::from_parts`].
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, DecimalConvertError};
+    ///
+    /// let n: Decimal = "-1.5".parse().unwrap();
+    /// assert_eq!(Decimal::try_decode(&n.to_bytes()), Ok(n));
+    /// assert_eq!(Decimal::try_decode(&[]), Err(DecimalConvertError::Invalid));
+    /// // a scale of -200
+    /// assert_eq!(Decimal::try_decode(&[0, 200, 1]), Err(DecimalConvertError::ScaleOutOfRange));
+    /// ```
+    #[inline]
+    pub fn try_decode(bytes: &[u8]) -> Result<Decimal, DecimalConvertError> {
         let len = bytes.len();
-        assert!(len > 0);
+        if len == 0 || len > MAX_BINARY_SIZE {
+            return Err(DecimalConvertError::Invalid);
+        }
 
         if len <= 2 {
             let int_val = if len == 1 {
@@ -630,12 +1481,16 @@ impl Decimal {
                 ((bytes[1] as u128) << 8) | (bytes[0] as u128)
             };
 
-            return unsafe { Decimal::from_parts_unchecked(int_val, 0, false) };
+            return Ok(unsafe { Decimal::from_parts_unchecked(int_val, 0, false) });
         }
 
         let flags = bytes[0];
         let abs_scale = bytes[1];
 
+        if flags & !(SIGN_MASK | SCALE_MASK) != 0 {
+            return Err(DecimalConvertError::Invalid);
+        }
+
         let negative = (flags & SIGN_MASK) == 1;
         let scale = if (flags & SCALE_MASK) != 0 {
             abs_scale as i16
@@ -644,14 +1499,11 @@ impl Decimal {
         };
 
         let mut int_bytes = [0; 16];
-        if len < MAX_BINARY_SIZE {
-            int_bytes[0..len - 2].copy_from_slice(&bytes[2..]);
-        } else {
-            int_bytes.copy_from_slice(&bytes[2..MAX_BINARY_SIZE]);
-        }
+        int_bytes[0..len - 2].copy_from_slice(&bytes[2..]);
         let int = u128::from_le_bytes(int_bytes);
 
-        unsafe { Decimal::from_parts_unchecked(int, scale, negative) }
+        // A zero with the sign bit set is decoded as the canonical zero.
+        Decimal::from_parts(int, scale, negative)
     }
 
     /// Computes the smallest integer that is greater than or equal to `self`.
@@ -704,25 +1556,164 @@ impl Decimal {
         unsafe { Decimal::from_parts_unchecked(int_val, 0, self.negative) }
     }
 
-    /// Truncate a value to have `scale` digits after the decimal point.
-    /// We allow negative `scale`, implying a truncation before the decimal
-    /// point.
+    /// Returns the half-open range `[floor(self), floor(self) + 1)` of the decimals with the same
+    /// floor as `self`, e.g. `[-2, -1)` for `-1.5`, suitable for [`BTreeMap::range`](std::collections::BTreeMap::range).
+    ///
+    /// This is [`Decimal::prefix_range`] with no fractional digit.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map: BTreeMap<Decimal, &str> = [("0.5", "a"), ("1", "b"), ("1.99", "c"), ("2", "d")]
+    ///     .iter()
+    ///     .map(|(k, v)| (k.parse().unwrap(), *v))
+    ///     .collect();
+    /// let (start, end) = "1.5".parse::<Decimal>().unwrap().integral_range();
+    /// assert_eq!(map.range(start..end).map(|(_, v)| *v).collect::<Vec<_>>(), ["b", "c"]);
+    /// ```
     #[inline]
-    pub fn trunc(&self, scale: i16) -> Decimal {
-        // Limit the scale value to avoid possible overflow in calculations
-        let real_scale = if !self.is_zero() {
-            scale.max(MIN_SCALE).min(MAX_SCALE + MAX_PRECISION as i16 - 1)
-        } else {
-            return Decimal::ZERO;
-        };
-
-        if self.scale <= real_scale {
-            return *self;
-        }
+    pub fn integral_range(&self) -> (Decimal, Decimal) {
+        self.prefix_range(0)
+    }
 
-        let e = self.scale - real_scale;
-        debug_assert!(e > 0);
-        if e > MAX_PRECISION as i16 {
+    /// Returns the half-open range `[start, end)` of the decimals which have the same floor as `self` at
+    /// `frac_digits` fractional digits, i.e. `start` is `self` rounded toward negative infinity to
+    /// `frac_digits` digits and `end` is `start + 10^-frac_digits`.
+    ///
+    /// For a negative `self`, the range is that of the floor, not of the truncation, e.g. `[-1.24, -1.23)`
+    /// for `-1.2345` at 2 digits. If `end` isn't representable, e.g. `start` has 38 digits at a smaller
+    /// scale, `end` is the next representable decimal after `start`, which gives the same range. `end`
+    /// may be beyond the range of parsing, e.g. `1E+126`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `end` is above `9.9999999999999999999999999999999999999E163`, which only happens for
+    /// decimals created by [`Decimal::from_parts`] beyond the range of parsing.
+    #[inline]
+    pub fn prefix_range(&self, frac_digits: u8) -> (Decimal, Decimal) {
+        let start = self.round_with_mode(frac_digits as i16, RoundingMode::Floor);
+        let end = start
+            .next_at_scale((frac_digits as i16).min(Decimal::MAX_REPR_SCALE))
+            .expect("the end of the range overflowed");
+        (start, end)
+    }
+
+    /// Returns `self + 10^-scale` if it's representable, otherwise the next representable decimal after
+    /// `self`, where `self` has at most `scale` fractional digits.
+    #[inline]
+    fn next_at_scale(&self, scale: i16) -> Option<Decimal> {
+        debug_assert!(self.scale <= scale && scale <= Decimal::MAX_REPR_SCALE);
+
+        if self.is_zero() {
+            return Decimal::from_parts(1, scale, false).ok();
+        }
+
+        // The exact sum at `scale`.
+        let e = (scale - self.scale) as u32;
+        if e <= MAX_PRECISION {
+            match self.int_val.checked_mul(POWERS_10[e as usize].low()) {
+                Some(int_val) if self.negative && int_val <= MAX_I128_REPR as u128 => {
+                    return Decimal::from_parts(int_val - 1, scale, true).ok();
+                }
+                Some(int_val) if !self.negative && int_val < MAX_I128_REPR as u128 => {
+                    return Decimal::from_parts(int_val + 1, scale, false).ok();
+                }
+                _ => {}
+            }
+        }
+
+        // The spacing of decimals around `self` is wider than `10^-scale`, so steps one unit of the
+        // finest scale of `self`.
+        let e = (MAX_PRECISION - self.precision() as u32).min((Decimal::MAX_REPR_SCALE - self.scale) as u32);
+        let int_val = self.int_val * POWERS_10[e as usize].low();
+        let scale = self.scale + e as i16;
+        if !self.negative {
+            if int_val < MAX_I128_REPR as u128 {
+                Decimal::from_parts(int_val + 1, scale, false).ok()
+            } else {
+                // `10^38` is `10^37` at the previous scale.
+                Decimal::from_parts(POWERS_10[MAX_PRECISION as usize - 1].low(), scale - 1, false).ok()
+            }
+        } else if int_val == POWERS_10[MAX_PRECISION as usize - 1].low() && scale < Decimal::MAX_REPR_SCALE {
+            // `-10^37` steps to `-(10^38 - 1)` at the next scale, rather than to `-(10^37 - 1)`.
+            Decimal::from_parts(MAX_I128_REPR as u128, scale + 1, true).ok()
+        } else {
+            Decimal::from_parts(int_val - 1, scale, true).ok()
+        }
+    }
+
+    /// Returns `self` as an integer at scale 0, failing with `DecimalConvertError::Inexact` if it has
+    /// a non-zero fractional digit, like `BigDecimal::toBigIntegerExact` of Java.
+    ///
+    /// A decimal with a negative scale is already an integer, and is returned as is, e.g. `1.2E+3` keeps
+    /// scale -2, since it may have no representation at scale 0 in 38 digits.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, DecimalConvertError};
+    ///
+    /// let n: Decimal = "12.000".parse().unwrap();
+    /// assert_eq!(n.to_integral_exact().unwrap().into_parts(), (12, 0, false));
+    /// assert_eq!("12.5".parse::<Decimal>().unwrap().to_integral_exact(), Err(DecimalConvertError::Inexact));
+    /// ```
+    #[inline]
+    pub fn to_integral_exact(&self) -> Result<Decimal, DecimalConvertError> {
+        if self.scale <= 0 {
+            return Ok(*self);
+        }
+
+        if self.is_zero() {
+            return Ok(Decimal::ZERO);
+        }
+
+        if self.scale > MAX_PRECISION as i16 {
+            return Err(DecimalConvertError::Inexact);
+        }
+
+        let divisor = POWERS_10[self.scale as usize].low();
+        if !self.int_val.is_multiple_of(divisor) {
+            return Err(DecimalConvertError::Inexact);
+        }
+
+        Ok(unsafe { Decimal::from_parts_unchecked(self.int_val / divisor, 0, self.negative) })
+    }
+
+    /// Rounds `self` to an integer with `mode`, like `BigDecimal::setScale(0, mode)` of Java.
+    ///
+    /// The result has scale 0, except a decimal with a negative scale, which is already an integer and
+    /// is returned as is, as [`Decimal::to_integral_exact`] does.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, RoundingMode};
+    ///
+    /// let n: Decimal = "-2.5".parse().unwrap();
+    /// assert_eq!(n.to_integral_value(RoundingMode::HalfEven), Decimal::from(-2));
+    /// assert_eq!(n.to_integral_value(RoundingMode::Floor), Decimal::from(-3));
+    /// ```
+    #[inline]
+    pub fn to_integral_value(&self, mode: RoundingMode) -> Decimal {
+        self.round_with_mode(0, mode)
+    }
+
+    /// Truncate a value to have `scale` digits after the decimal point.
+    /// We allow negative `scale`, implying a truncation before the decimal
+    /// point.
+    #[inline]
+    pub fn trunc(&self, scale: i16) -> Decimal {
+        // Limit the scale value to avoid possible overflow in calculations
+        let real_scale = if !self.is_zero() {
+            scale.max(MIN_SCALE).min(MAX_SCALE + MAX_PRECISION as i16 - 1)
+        } else {
+            return Decimal::ZERO;
+        };
+
+        if self.scale <= real_scale {
+            return *self;
+        }
+
+        let e = self.scale - real_scale;
+        debug_assert!(e > 0);
+        if e > MAX_PRECISION as i16 {
             return Decimal::ZERO;
         }
 
@@ -731,9 +1722,55 @@ impl Decimal {
         unsafe { Decimal::from_parts_unchecked(int_val, real_scale, self.negative) }
     }
 
+    /// Splits a value into its integer and fractional parts, both with the sign of `self`, as `modf` does.
+    ///
+    /// The integer part equals `self.trunc(0)`, and the fractional part keeps the scale of `self`.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n: Decimal = "-12.75".parse().unwrap();
+    /// assert_eq!(n.split(), (Decimal::from(-12), "-0.75".parse().unwrap()));
+    /// ```
+    #[inline]
+    pub fn split(&self) -> (Decimal, Decimal) {
+        if self.scale <= 0 {
+            return (*self, Decimal::ZERO);
+        }
+        if self.scale > MAX_PRECISION as i16 {
+            return (Decimal::ZERO, *self);
+        }
+
+        let divisor = POWERS_10[self.scale as usize].low();
+        let int_part = self.int_val / divisor;
+        let fract_part = self.int_val % divisor;
+        unsafe {
+            (
+                Decimal::from_parts_unchecked(int_part, 0, self.negative),
+                Decimal::from_parts_unchecked(fract_part, self.scale, self.negative),
+            )
+        }
+    }
+
+    /// Returns the fractional part of a value, with the sign of `self`.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n: Decimal = "-0.75".parse().unwrap();
+    /// assert_eq!(n.fract(), n);
+    /// assert_eq!(Decimal::from(3).fract(), Decimal::ZERO);
+    /// ```
+    #[inline]
+    pub fn fract(&self) -> Decimal {
+        self.split().1
+    }
+
     /// Round a value to have `scale` digits after the decimal point.
     /// We allow negative `scale`, implying rounding before the decimal
     /// point.
+    ///
+    /// It rounds half away from zero, the same as `round_with_mode(scale, RoundingMode::HalfUp)`.
     #[inline]
     pub fn round(&self, scale: i16) -> Decimal {
         // Limit the scale value to avoid possible overflow in calculations
@@ -758,6 +1795,97 @@ impl Decimal {
         unsafe { Decimal::from_parts_unchecked(int_val, real_scale, self.negative) }
     }
 
+    /// Rounds `self` to `digits` significant digits, returning `None` if the result overflowed.
+    #[inline]
+    fn round_to_digits(&self, digits: u8) -> Option<Decimal> {
+        let precision = self.precision();
+        if precision <= digits {
+            return Some(*self);
+        }
+
+        let mut result = self.round(self.scale - (precision - digits) as i16);
+        // rounding may carry into one more digit, e.g. 9.99 -> 10.0
+        if result.precision() > digits {
+            result = unsafe { Decimal::from_parts_unchecked(result.int_val / 10, result.scale - 1, result.negative) };
+            if result.logb()? >= -MIN_SCALE {
+                return None;
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Round a value to have `scale` digits after the decimal point using `mode`.
+    /// We allow negative `scale`, implying rounding before the decimal
+    /// point.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, RoundingMode};
+    ///
+    /// let n: Decimal = "2.5".parse().unwrap();
+    /// assert_eq!(n.round_with_mode(0, RoundingMode::HalfEven), Decimal::from(2));
+    /// assert_eq!(n.round_with_mode(0, RoundingMode::HalfUp), Decimal::from(3));
+    ///
+    /// let n: Decimal = "-1250".parse().unwrap();
+    /// assert_eq!(n.round_with_mode(-2, RoundingMode::HalfEven), Decimal::from(-1200));
+    /// assert_eq!(n.round_with_mode(-2, RoundingMode::Ceiling), Decimal::from(-1200));
+    /// assert_eq!(n.round_with_mode(-2, RoundingMode::Floor), Decimal::from(-1300));
+    /// ```
+    #[inline]
+    pub fn round_with_mode(&self, scale: i16, mode: RoundingMode) -> Decimal {
+        // Limit the scale value to avoid possible overflow in calculations
+        let real_scale = if !self.is_zero() {
+            scale.max(MIN_SCALE).min(MAX_SCALE + MAX_PRECISION as i16 - 1)
+        } else {
+            return Decimal::ZERO;
+        };
+
+        if self.scale <= real_scale {
+            return *self;
+        }
+
+        let e = self.scale - real_scale;
+        debug_assert!(e > 0);
+        let int_val = if e > MAX_PRECISION as i16 {
+            // The whole value is less than half a unit.
+            mode.round_up(Ordering::Less, false, false, self.negative) as u128
+        } else {
+            mode.div_pow10(self.int_val, e as u32, self.negative)
+        };
+
+        unsafe { Decimal::from_parts_unchecked(int_val, real_scale, self.negative) }
+    }
+
+    /// Returns `true` if both values are equal after rounding them to `scale` with `mode`.
+    #[inline]
+    pub fn eq_at_scale(&self, other: &Decimal, scale: i16, mode: RoundingMode) -> bool {
+        self.round_with_mode(scale, mode) == other.round_with_mode(scale, mode)
+    }
+
+    /// Compares both values after rounding them to `scale` with `mode`.
+    #[inline]
+    pub fn cmp_at_scale(&self, other: &Decimal, scale: i16, mode: RoundingMode) -> Ordering {
+        self.round_with_mode(scale, mode)
+            .cmp(&other.round_with_mode(scale, mode))
+    }
+
+    /// Compares `self` and `other`, returning `-1`, `0` or `1` if `self` is less than, equal to,
+    /// or greater than `other`.
+    ///
+    /// This is [`Ord::cmp`] as a plain integer, for C APIs and vectorized comparisons.
+    #[inline]
+    pub fn cmp_i8(&self, other: &Decimal) -> i8 {
+        self.cmp(other) as i8
+    }
+
+    /// Returns `self - other` after rounding both values to `scale` with `mode`,
+    /// returning `None` if overflow occurred.
+    #[inline]
+    pub fn differs_at_scale(&self, other: &Decimal, scale: i16, mode: RoundingMode) -> Option<Decimal> {
+        self.round_with_mode(scale, mode)
+            .checked_sub(other.round_with_mode(scale, mode))
+    }
+
     /// Do bounds checking and rounding according to `precision` and `scale`.
     ///
     /// Returns `true` if overflows.
@@ -844,12 +1972,178 @@ impl Decimal {
         unsafe { Decimal::from_parts_unchecked(int_val, current_scale, self.negative) }
     }
 
+    /// Normalize a `Decimal`'s scale to exactly `scale`, without changing its value.
+    ///
+    /// Unlike [`Decimal::normalize_to_scale`], which stops short of the requested scale silently, it returns
+    /// an error reporting the nearest reachable value and why it stopped. Zero always has scale 0.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, NormalizeError};
+    ///
+    /// let n: Decimal = "1.2300".parse().unwrap();
+    /// assert_eq!(n.try_normalize_to_scale(2).unwrap().scale(), 2);
+    /// assert_eq!(
+    ///     n.try_normalize_to_scale(1),
+    ///     Err(NormalizeError::NonZeroDigit { closest: "1.23".parse().unwrap() })
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_normalize_to_scale(&self, scale: i16) -> Result<Decimal, NormalizeError> {
+        if !(MIN_SCALE..=MAX_SCALE).contains(&scale) {
+            return Err(NormalizeError::ScaleOutOfRange);
+        }
+
+        let closest = self.normalize_to_scale(scale);
+        match closest.scale.cmp(&scale) {
+            _ if closest.is_zero() => Ok(closest),
+            Ordering::Equal => Ok(closest),
+            Ordering::Greater => Err(NormalizeError::NonZeroDigit { closest }),
+            Ordering::Less => Err(NormalizeError::MantissaFull { closest }),
+        }
+    }
+
+    /// Normalize a `Decimal`'s scale to exactly `scale`, rounding with `mode` when digits beyond `scale`
+    /// would be dropped, and padding with zeros otherwise.
+    ///
+    /// It fails only if the requested scale is out of range, or if the integral digits and `scale` make
+    /// more than 38 digits. Zero always has scale 0.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, RoundingMode};
+    ///
+    /// let n: Decimal = "1.235".parse().unwrap();
+    /// let rounded = n.normalize_to_scale_lossy(2, RoundingMode::HalfEven).unwrap();
+    /// assert_eq!(rounded.into_parts(), (124, 2, false));
+    /// let padded = n.normalize_to_scale_lossy(5, RoundingMode::HalfEven).unwrap();
+    /// assert_eq!(padded.into_parts(), (123500, 5, false));
+    /// ```
+    #[inline]
+    pub fn normalize_to_scale_lossy(&self, scale: i16, mode: RoundingMode) -> Result<Decimal, NormalizeError> {
+        if !(MIN_SCALE..=MAX_SCALE).contains(&scale) {
+            return Err(NormalizeError::ScaleOutOfRange);
+        }
+
+        self.round_with_mode(scale, mode).try_normalize_to_scale(scale)
+    }
+
+    /// Returns `self` with exactly `scale` digits after the decimal point, rounding half away from zero
+    /// when digits are dropped and padding with zeros otherwise.
+    ///
+    /// Unlike [`Decimal::round`], the scale of the result is `scale` even if its last digits are zeros.
+    /// It returns `None` only if `scale` is out of range, or if the result needs more than `MAX_PRECISION`
+    /// digits. Zero always has scale 0.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n: Decimal = "1.995".parse().unwrap();
+    /// assert_eq!(n.rescale(2).unwrap().into_parts(), (200, 2, false));
+    /// assert_eq!(n.rescale(5).unwrap().into_parts(), (199500, 5, false));
+    /// ```
+    #[inline]
+    pub fn rescale(&self, scale: i16) -> Option<Decimal> {
+        self.rescale_with_mode(scale, RoundingMode::HalfUp)
+    }
+
+    /// Returns `self` with exactly `scale` digits after the decimal point, rounding with `mode` when
+    /// digits are dropped, as [`Decimal::rescale`] does.
+    #[inline]
+    pub fn rescale_with_mode(&self, scale: i16, mode: RoundingMode) -> Option<Decimal> {
+        Decimal::round_parts_to_scale(U256::from(self.int_val), self.scale, self.negative, scale, mode)
+    }
+
+    /// Returns `self` with exactly `scale` digits after the decimal point, truncating the dropped digits
+    /// toward zero, as [`Decimal::rescale`] does.
+    #[inline]
+    pub fn with_scale_trunc(&self, scale: i16) -> Option<Decimal> {
+        self.rescale_with_mode(scale, RoundingMode::Down)
+    }
+
     /// Normalize a `Decimal`'s scale toward zero.
     #[inline]
     pub fn normalize(&self) -> Decimal {
         self.normalize_to_scale(0)
     }
 
+    /// Factors `self` as `mantissa * 10^exponent` where `mantissa` isn't a multiple of 10, returning
+    /// `(mantissa, exponent, negative)`.
+    ///
+    /// Unlike [`Decimal::normalize`], the trailing zeros of an integer are removed too, e.g. `1200` is
+    /// `(12, 2, false)`. Zero is `(0, 0, false)`.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// assert_eq!("-1.2300".parse::<Decimal>().unwrap().factor_pow10(), (123, -2, true));
+    /// assert_eq!("1200".parse::<Decimal>().unwrap().factor_pow10(), (12, 2, false));
+    /// ```
+    #[inline]
+    pub fn factor_pow10(&self) -> (u128, i32, bool) {
+        if self.is_zero() {
+            return (0, 0, false);
+        }
+
+        let (int_val, zeros) = strip_trailing_zeros(self.int_val);
+        (int_val, zeros as i32 - self.scale as i32, self.negative)
+    }
+
+    /// Checks if `self` is an integer multiple of `10^k`, e.g. `1200` is a multiple of `10^2` and `0.05`
+    /// of `10^-2`. Zero is a multiple of any power of ten.
+    #[inline]
+    pub fn is_multiple_of_pow10(&self, k: i32) -> bool {
+        self.is_zero() || self.factor_pow10().1 >= k
+    }
+
+    /// Maps `self` to a `u64` key, which preserves the order of decimals approximately,
+    /// i.e. `a < b` implies `a.sortable_u64() <= b.sortable_u64()`.
+    ///
+    /// The key is made up of the sign, the exponent and the top 54 bits of the normalized mantissa,
+    /// like a float mapped to a sortable integer. Equal decimals have equal keys, but different decimals
+    /// may collide if they share the first 15 significant digits, so equal keys require
+    /// a secondary full comparison.
+    #[inline]
+    pub fn sortable_u64(&self) -> u64 {
+        const SIGN_BIT: u64 = 1 << 63;
+        const MANTISSA_BITS: u32 = 54;
+        const EXP_BITS: u32 = 63 - MANTISSA_BITS;
+        // `from_parts` accepts a scale up to `MAX_SCALE + MAX_PRECISION - 1`, so the exponent is at least
+        // `1 - MAX_SCALE - MAX_PRECISION`, and the biased one is positive.
+        const EXP_BIAS: i32 = MAX_SCALE as i32 + MAX_PRECISION as i32;
+        // The largest biased exponent, of a mantissa of 38 digits at `MIN_SCALE`, fits in `EXP_BITS` bits.
+        const _: [(); 0] = [(); ((MAX_PRECISION as i32 - 1 - MIN_SCALE as i32 + EXP_BIAS) >> EXP_BITS) as usize];
+        const MIN_MANTISSA: u128 = 10u128.pow(MAX_PRECISION - 1);
+        // Divides the mantissa range `[0, 9 * 10^37)` into `2^54` buckets.
+        const BUCKET: u128 = 9 * MIN_MANTISSA / (1 << MANTISSA_BITS) + 1;
+
+        if self.is_zero() {
+            return SIGN_BIT;
+        }
+
+        let precision = self.precision() as u32;
+        let exp = precision as i32 - 1 - self.scale as i32;
+        let mantissa = self.int_val * POWERS_10[(MAX_PRECISION - precision) as usize].low();
+        let bits = ((exp + EXP_BIAS) as u64) << MANTISSA_BITS | ((mantissa - MIN_MANTISSA) / BUCKET) as u64;
+
+        if self.negative {
+            !(SIGN_BIT | bits)
+        } else {
+            SIGN_BIT | bits
+        }
+    }
+
+    /// Returns the range of [`Decimal::sortable_u64`] keys to scan for the decimals in `[self, other]`.
+    ///
+    /// The keys at both ends may be shared by decimals outside of the range, which should be filtered out
+    /// with a full comparison. The range is empty if `self > other`.
+    #[inline]
+    pub fn sortable_u64_range(&self, other: &Decimal) -> RangeInclusive<u64> {
+        if self > other {
+            return RangeInclusive::new(1, 0);
+        }
+
+        self.sortable_u64()..=other.sortable_u64()
+    }
+
     #[inline]
     fn rescale_cmp(&self, other: &Decimal) -> Ordering {
         debug_assert!(self.scale < other.scale);
@@ -865,7 +2159,7 @@ impl Decimal {
     }
 
     #[inline]
-    fn adjust_scale(int_val: U256, scale: i16, negative: bool) -> Option<Decimal> {
+    pub(crate) fn adjust_scale(int_val: U256, scale: i16, negative: bool) -> Option<Decimal> {
         let digits = int_val.count_digits();
         let s = scale as i32 - digits as i32;
 
@@ -880,17 +2174,80 @@ impl Decimal {
 
         if digits > MAX_PRECISION {
             let shift_scale = (digits - MAX_PRECISION) as i16;
-            return if shift_scale as u32 <= MAX_PRECISION {
+            let mut result = if shift_scale as u32 <= MAX_PRECISION {
                 let dividend = int_val + ROUNDINGS[shift_scale as usize].low();
-                let result = dividend / POWERS_10[shift_scale as usize].low();
-                Some(unsafe { Decimal::from_parts_unchecked(result.low(), scale - shift_scale, negative) })
+                (dividend / POWERS_10[shift_scale as usize].low()).low()
             } else {
                 let dividend = int_val + ROUNDINGS[shift_scale as usize];
-                let result = dividend / POWERS_10[shift_scale as usize];
-                Some(unsafe { Decimal::from_parts_unchecked(result.low(), scale - shift_scale, negative) })
+                (dividend / POWERS_10[shift_scale as usize]).low()
+            };
+            let mut scale = scale - shift_scale;
+
+            // rounding may carry into one more digit, e.g. 99..9.5 -> 100..0
+            if result > MAX_I128_REPR as u128 {
+                result /= 10;
+                scale -= 1;
+                if scale as i32 - (MAX_PRECISION as i32) < MIN_SCALE as i32 {
+                    return None;
+                }
+            }
+
+            return Some(unsafe { Decimal::from_parts_unchecked(result, scale, negative) });
+        }
+
+        Some(unsafe { Decimal::from_parts_unchecked(int_val.low(), scale, negative) })
+    }
+
+    /// Same as [`Decimal::adjust_scale`], but rounds the dropped digits with `mode`.
+    ///
+    /// A non-zero magnitude below the smallest one, i.e. `1E-130`, becomes zero or `1E-130` as rounded
+    /// by `mode`, rather than always zero.
+    #[inline]
+    pub(crate) fn adjust_scale_with_mode(
+        int_val: U256,
+        scale: i16,
+        negative: bool,
+        mode: RoundingMode,
+    ) -> Option<Decimal> {
+        let digits = int_val.count_digits();
+        let s = scale as i32 - digits as i32;
+
+        if s >= MAX_SCALE as i32 {
+            return if int_val != 0 && mode.round_up(Ordering::Less, false, false, negative) {
+                Some(unsafe { Decimal::from_parts_unchecked(1, MAX_SCALE, negative) })
+            } else {
+                Some(Decimal::ZERO)
             };
         }
 
+        if s < MIN_SCALE as i32 {
+            // overflow
+            return None;
+        }
+
+        if digits > MAX_PRECISION {
+            let shift_scale = digits - MAX_PRECISION;
+            let divisor = POWERS_10[shift_scale as usize];
+            let (quotient, rem) = int_val.div_rem(divisor);
+            let half = (rem * 2).cmp(&divisor);
+            let mut result = quotient.low();
+            if mode.round_up(half, rem == 0, result % 2 == 1, negative) {
+                result += 1;
+            }
+            let mut scale = scale - shift_scale as i16;
+
+            // rounding may carry into one more digit, e.g. 99..9.5 -> 100..0
+            if result > MAX_I128_REPR as u128 {
+                result /= 10;
+                scale -= 1;
+                if scale as i32 - (MAX_PRECISION as i32) < MIN_SCALE as i32 {
+                    return None;
+                }
+            }
+
+            return Some(unsafe { Decimal::from_parts_unchecked(result, scale, negative) });
+        }
+
         Some(unsafe { Decimal::from_parts_unchecked(int_val.low(), scale, negative) })
     }
 
@@ -1049,10 +2406,17 @@ impl Decimal {
 
     /// Add two decimals.
     /// returning `None` if overflow occurred.
+    ///
+    /// The result scale is the larger of the two operand scales. If the exact sum needs more than
+    /// `MAX_PRECISION` digits at that scale, it is rounded half away from zero and the scale is
+    /// reduced by the number of dropped digits. A zero result always has scale 0.
+    ///
+    /// Use [`Decimal::checked_add_stable`] to get `None` instead of a reduced scale.
     #[inline]
     pub fn checked_add(&self, other: impl AsRef<Decimal>) -> Option<Decimal> {
         let other = other.as_ref();
-        if self.negative != other.negative {
+        debug_assert!(self.is_canonical() && other.is_canonical());
+        let result = if self.negative != other.negative {
             if other.negative {
                 self.sub_internal(other, self.negative)
             } else {
@@ -1060,7 +2424,13 @@ impl Decimal {
             }
         } else {
             self.add_internal(other, self.negative)
+        };
+
+        if result.is_none() {
+            record_failure!(Add, Overflow, self, Some(other));
         }
+
+        result
     }
 
     /// Add two decimals.
@@ -1103,16 +2473,28 @@ impl Decimal {
 
     /// Subtract one decimal from another,
     /// returning `None` if overflow occurred.
+    ///
+    /// The result scale follows the same rules as [`Decimal::checked_add`], except that
+    /// subtracting zero, or subtracting from zero, returns the other operand with its scale unchanged.
+    ///
+    /// Use [`Decimal::checked_sub_stable`] to get `None` instead of a reduced scale.
     #[inline]
     pub fn checked_sub(&self, other: impl AsRef<Decimal>) -> Option<Decimal> {
         let other = other.as_ref();
-        if self.negative != other.negative {
+        debug_assert!(self.is_canonical() && other.is_canonical());
+        let result = if self.negative != other.negative {
             self.add_internal(other, self.negative)
         } else if self.negative {
             other.sub_internal(self, !self.negative)
         } else {
             self.sub_internal(other, self.negative)
+        };
+
+        if result.is_none() {
+            record_failure!(Sub, Overflow, self, Some(other));
         }
+
+        result
     }
 
     /// Subtract one decimal from another,
@@ -1135,9 +2517,16 @@ impl Decimal {
 
     /// Calculate the product of two decimals,
     /// returning `None` if overflow occurred.
+    ///
+    /// The result scale is the sum of the operand scales. If the exact product needs more than
+    /// `MAX_PRECISION` digits, it is rounded half away from zero and the scale is reduced by the
+    /// number of dropped digits. A zero result always has scale 0.
+    ///
+    /// Use [`Decimal::checked_mul_stable`] to get `None` instead of a reduced scale.
     #[inline]
     pub fn checked_mul(&self, other: impl AsRef<Decimal>) -> Option<Decimal> {
         let other = other.as_ref();
+        debug_assert!(self.is_canonical() && other.is_canonical());
 
         if self.is_zero() || other.is_zero() {
             return Some(Decimal::ZERO);
@@ -1150,38 +2539,332 @@ impl Decimal {
         if !int_val.is_decimal_overflowed() && scale == 0 {
             Some(unsafe { Decimal::from_parts_unchecked(int_val.low(), 0, negative) })
         } else {
-            Decimal::adjust_scale(int_val, scale, negative)
+            let result = Decimal::adjust_scale(int_val, scale, negative);
+            if result.is_none() {
+                record_failure!(Mul, Overflow, self, Some(other));
+            }
+            result
         }
     }
 
-    /// Calculate the product of two decimals,
-    /// # Safety
-    /// Make sure the result scale is scale and the result is not overflow.
+    /// Calculates `self + other` rounded once with `mode`.
     #[inline]
-    pub unsafe fn mul_unchecked<const DECIMAL_MODEL: u8>(&self, other: &Decimal, scale: i16) -> Decimal {
-        let negative = self.negative ^ other.negative;
-        let val = match DECIMAL_MODEL {
-            DECIMAL64 => ((self.int_val) as u64 * (other.int_val as u64)) as u128,
-            _ => self.int_val * other.int_val,
-        };
-        Decimal::from_parts_unchecked(val, scale, negative)
+    pub(crate) fn add_with_mode(&self, other: &Decimal, mode: RoundingMode) -> Option<Decimal> {
+        if self.is_zero() {
+            return Some(*other);
+        }
+        if other.is_zero() {
+            return Some(*self);
+        }
+
+        let (int_val, scale, negative) = self.add_parts(other)?;
+        Decimal::adjust_scale_with_mode(int_val, scale, negative, mode)
     }
 
-    /// Checked decimal division.
+    /// Returns the mantissa, scale and sign of `self + other` before rounding, where both are non-zero.
+    ///
+    /// The mantissa is exact, or ends with a sticky digit standing for the operand which is too small
+    /// to change the first 39 digits.
+    #[inline]
+    pub(crate) fn add_parts(&self, other: &Decimal) -> Option<(U256, i16, bool)> {
+        debug_assert!(!self.is_zero() && !other.is_zero());
+
+        let (coarse, fine) = if self.scale <= other.scale {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let e = (fine.scale as i32 - coarse.scale as i32) as usize;
+        let digits = coarse.precision() as usize;
+        let (coarse_int, fine_int, scale) = if digits + e <= POWERS_10.len() {
+            (POWERS_10[e] * coarse.int_val, U256::from(fine.int_val), fine.scale)
+        } else {
+            // `fine` is less than one unit in the 40th digit of `coarse`, so it only decides the direction
+            // of the rounding, as a sticky unit in that digit.
+            let k = 40 - digits;
+            (
+                POWERS_10[k] * coarse.int_val,
+                U256::from(1u128),
+                coarse.scale + k as i16,
+            )
+        };
+
+        if coarse.negative == fine.negative {
+            Some((coarse_int + fine_int, scale, coarse.negative))
+        } else if coarse_int >= fine_int {
+            Some((coarse_int.checked_sub(fine_int)?, scale, coarse.negative))
+        } else {
+            Some((fine_int.checked_sub(coarse_int)?, scale, fine.negative))
+        }
+    }
+
+    /// Calculates `self * other` rounded once with `mode`.
+    #[inline]
+    pub(crate) fn mul_with_mode(&self, other: &Decimal, mode: RoundingMode) -> Option<Decimal> {
+        if self.is_zero() || other.is_zero() {
+            return Some(Decimal::ZERO);
+        }
+
+        let int_val = U256::mul128(self.int_val, other.int_val);
+        Decimal::adjust_scale_with_mode(int_val, self.scale + other.scale, self.negative ^ other.negative, mode)
+    }
+
+    /// Calculate the product of two decimals,
+    /// # Safety
+    /// Make sure the result scale is scale and the result is not overflow.
+    #[inline]
+    pub unsafe fn mul_unchecked<const DECIMAL_MODEL: u8>(&self, other: &Decimal, scale: i16) -> Decimal {
+        let negative = self.negative ^ other.negative;
+        let val = match DECIMAL_MODEL {
+            DECIMAL64 => ((self.int_val) as u64 * (other.int_val as u64)) as u128,
+            _ => self.int_val * other.int_val,
+        };
+        Decimal::from_parts_unchecked(val, scale, negative)
+    }
+
+    /// Returns the larger scale of two addends, where a zero addend contributes no scale.
+    #[inline]
+    fn addend_scale(&self, other: &Decimal) -> i16 {
+        if self.is_zero() {
+            other.scale
+        } else if other.is_zero() {
+            self.scale
+        } else {
+            self.scale.max(other.scale)
+        }
+    }
+
+    /// Represents `self` at exactly `scale`, returning `None` if that would need
+    /// more than `MAX_PRECISION` digits or would drop a non-zero digit.
+    #[inline]
+    pub(crate) fn with_exact_scale(&self, scale: i16) -> Option<Decimal> {
+        if self.is_zero() {
+            return Some(Decimal::ZERO);
+        }
+
+        if self.scale == scale {
+            return Some(*self);
+        }
+
+        let e = (self.scale as i32 - scale as i32).unsigned_abs();
+        if e > MAX_PRECISION {
+            return None;
+        }
+
+        let int_val = if self.scale > scale {
+            let divisor = POWERS_10[e as usize].low();
+            if !self.int_val.is_multiple_of(divisor) {
+                return None;
+            }
+            self.int_val / divisor
+        } else {
+            let val = U256::mul128(self.int_val, POWERS_10[e as usize].low());
+            if val.is_decimal_overflowed() {
+                return None;
+            }
+            val.low()
+        };
+
+        Some(unsafe { Decimal::from_parts_unchecked(int_val, scale, self.negative) })
+    }
+
+    /// Add two decimals, keeping the result at the larger scale of the non-zero operands,
+    /// returning `None` if overflow occurred or the exact sum cannot be represented at that scale.
+    #[inline]
+    pub fn checked_add_stable(&self, other: impl AsRef<Decimal>) -> Option<Decimal> {
+        let other = other.as_ref();
+        let result = self.checked_add(other)?.with_exact_scale(self.addend_scale(other));
+        if result.is_none() {
+            record_failure!(Add, Inexact, self, Some(other));
+        }
+        result
+    }
+
+    /// Add two decimals, returning the sum and whether it was rounded,
+    /// or `None` if overflow occurred.
+    ///
+    /// The sum is the same as [`Decimal::checked_add`]. The flag is `true` if it differs from the
+    /// exact sum, e.g. when `other` is absorbed by `self` as told by [`Decimal::absorbs`].
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let big: Decimal = "1e30".parse().unwrap();
+    /// let small: Decimal = "1e-10".parse().unwrap();
+    /// assert_eq!(big.add_report(&small), Some((big, true)));
+    /// assert_eq!(big.add_report(&big), Some((big + big, false)));
+    /// ```
+    #[inline]
+    pub fn add_report(&self, other: &Decimal) -> Option<(Decimal, bool)> {
+        let sum = self.checked_add(other)?;
+        if self.is_zero() || other.is_zero() {
+            return Some((sum, false));
+        }
+
+        // The parts are exact, or end with a sticky digit which no 38-digit sum can match.
+        let inexact = match self.add_parts(other) {
+            Some((int_val, scale, negative)) => {
+                int_val != 0
+                    && (sum.negative != negative
+                        || U256::cmp_scaled(U256::from(sum.int_val), sum.scale as i32, int_val, scale as i32)
+                            != Ordering::Equal)
+            }
+            None => true,
+        };
+        Some((sum, inexact))
+    }
+
+    /// Returns `true` if adding a non-zero `other` to `self` leaves `self` unchanged, because `other`
+    /// is lost when the sum is rounded to `MAX_PRECISION` digits.
+    ///
+    /// Only the scales and a few leading digits are compared, without doing the addition.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let big: Decimal = "1e30".parse().unwrap();
+    /// assert!(big.absorbs(&"1e-10".parse().unwrap()));
+    /// assert!(!big.absorbs(&"1e-7".parse().unwrap()));
+    /// ```
+    #[inline]
+    pub fn absorbs(&self, other: &Decimal) -> bool {
+        if self.is_zero() || other.is_zero() {
+            return false;
+        }
+
+        // The sum is rounded at the unit `10^(e - 37)` of the last digit of `self`, where `10^e` is
+        // its leading digit, so `other` is absorbed if it is less than half of that unit. Subtracting
+        // from a power of ten makes the unit ten times smaller.
+        let precision = self.precision();
+        let e = precision as i32 - 1 - self.scale as i32;
+        let subtract = self.negative != other.negative;
+        let factor = if subtract && self.int_val == POWERS_10[precision as usize - 1].low() {
+            20
+        } else {
+            2
+        };
+
+        // Compares `factor * |other|` with the unit, i.e. `factor * other.int_val` with `10^k`.
+        let k = other.scale as i32 + e - 37;
+        let ord = if k <= 0 {
+            Ordering::Greater
+        } else if k as usize >= POWERS_10.len() {
+            Ordering::Less
+        } else {
+            U256::mul128(other.int_val, factor).cmp(&POWERS_10[k as usize])
+        };
+
+        // A tie is rounded away from zero, which keeps `self` only when subtracting.
+        match ord {
+            Ordering::Less => true,
+            Ordering::Equal => subtract,
+            Ordering::Greater => false,
+        }
+    }
+
+    /// Subtract one decimal from another, keeping the result at the larger scale of the non-zero operands,
+    /// returning `None` if overflow occurred or the exact difference cannot be represented at that scale.
+    #[inline]
+    pub fn checked_sub_stable(&self, other: impl AsRef<Decimal>) -> Option<Decimal> {
+        let other = other.as_ref();
+        let result = self.checked_sub(other)?.with_exact_scale(self.addend_scale(other));
+        if result.is_none() {
+            record_failure!(Sub, Inexact, self, Some(other));
+        }
+        result
+    }
+
+    /// Calculate the product of two decimals, keeping the result at the sum of the operand scales,
+    /// returning `None` if overflow occurred or the exact product cannot be represented at that scale.
+    #[inline]
+    pub fn checked_mul_stable(&self, other: impl AsRef<Decimal>) -> Option<Decimal> {
+        let other = other.as_ref();
+
+        if self.is_zero() || other.is_zero() {
+            return Some(Decimal::ZERO);
+        }
+
+        let prod = self.checked_mul(other)?;
+        if prod.is_zero() {
+            record_failure!(Mul, Underflow, self, Some(other));
+            return None;
+        }
+
+        let result = prod.with_exact_scale(self.scale + other.scale);
+        if result.is_none() {
+            record_failure!(Mul, Inexact, self, Some(other));
+        }
+        result
+    }
+
+    /// Add two decimals, returning `MAX` or `MIN` if overflow occurred.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// assert_eq!(Decimal::MAX.saturating_add(Decimal::MAX), Decimal::MAX);
+    /// assert_eq!(Decimal::MIN.saturating_add(Decimal::MIN), Decimal::MIN);
+    /// ```
+    #[inline]
+    pub fn saturating_add(&self, other: impl AsRef<Decimal>) -> Decimal {
+        // The sum of operands with different signs can't overflow, so it overflows toward the sign of `self`.
+        self.checked_add(other).unwrap_or_else(|| self.saturated(self.negative))
+    }
+
+    /// Subtract one decimal from another, returning `MAX` or `MIN` if overflow occurred.
+    #[inline]
+    pub fn saturating_sub(&self, other: impl AsRef<Decimal>) -> Decimal {
+        self.checked_sub(other).unwrap_or_else(|| self.saturated(self.negative))
+    }
+
+    /// Calculate the product of two decimals, returning `MAX` or `MIN` if overflow occurred.
+    #[inline]
+    pub fn saturating_mul(&self, other: impl AsRef<Decimal>) -> Decimal {
+        let other = other.as_ref();
+        self.checked_mul(other)
+            .unwrap_or_else(|| self.saturated(self.negative ^ other.negative))
+    }
+
+    #[inline]
+    fn saturated(&self, negative: bool) -> Decimal {
+        if negative {
+            Decimal::MIN
+        } else {
+            Decimal::MAX
+        }
+    }
+
+    /// Checked decimal division.
     /// Computes `self / other`, returning `None` if `other == 0` or the division results in overflow.
+    ///
+    /// A non-zero quotient is always rounded half up once to `MAX_PRECISION` significant digits,
+    /// trailing zeros included, so `1 / 2` has scale 38. A zero result always has scale 0.
     #[inline]
     pub fn checked_div(&self, other: impl AsRef<Decimal>) -> Option<Decimal> {
         let other = other.as_ref();
+        debug_assert!(self.is_canonical() && other.is_canonical());
 
         if other.is_zero() {
+            record_failure!(Div, DivisionByZero, self, Some(other));
             return None;
         }
 
+        let result = self.div_internal(other, other.precision());
+        if result.is_none() {
+            record_failure!(Div, Overflow, self, Some(other));
+        }
+        result
+    }
+
+    /// Computes `self / other`, where `other` is non-zero and has `other_precision` digits.
+    #[inline]
+    pub(crate) fn div_internal(&self, other: &Decimal, other_precision: u8) -> Option<Decimal> {
+        debug_assert!(!other.is_zero() && other_precision == other.precision());
+
         if self.is_zero() {
             return Some(Decimal::ZERO);
         }
 
-        let other_precision = other.precision();
         let self_precision = self.precision();
 
         let (self_int_val, shift_precision) = if other_precision > self_precision {
@@ -1192,19 +2875,184 @@ impl Decimal {
         };
 
         let negative = self.negative ^ other.negative;
-        let int_val = self_int_val.div128_round(other.int_val);
-        let scale = self.scale - other.scale + MAX_PRECISION as i16 + shift_precision as i16;
+        let (int_val, rounding_digits) = self_int_val.div128_round_once(other.int_val);
+        let scale = self.scale - other.scale + MAX_PRECISION as i16 + shift_precision as i16 + rounding_digits;
 
         Decimal::adjust_scale(int_val, scale, negative)
     }
 
+    /// Checked decimal division rounded with `mode`.
+    /// Computes `self / other`, returning `None` if `other == 0` or the division results in overflow.
+    ///
+    /// The exact quotient is rounded once to `MAX_PRECISION` significant digits, where
+    /// [`Decimal::checked_div`] always rounds half up.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, RoundingMode};
+    ///
+    /// let one = Decimal::from(1);
+    /// let three = Decimal::from(3);
+    /// assert_eq!(
+    ///     one.checked_div_with_rounding(three, RoundingMode::Up).unwrap().to_string(),
+    ///     "0.33333333333333333333333333333333333334"
+    /// );
+    /// ```
+    #[inline]
+    pub fn checked_div_with_rounding(&self, other: impl AsRef<Decimal>, mode: RoundingMode) -> Option<Decimal> {
+        let other = other.as_ref();
+        debug_assert!(self.is_canonical() && other.is_canonical());
+
+        if other.is_zero() {
+            record_failure!(Div, DivisionByZero, self, Some(other));
+            return None;
+        }
+
+        let result = self.div_with_mode(other, mode);
+        if result.is_none() {
+            record_failure!(Div, Overflow, self, Some(other));
+        }
+        result
+    }
+
+    /// Computes `self / other` rounded once with `mode`, where `other` is non-zero.
+    #[inline]
+    pub(crate) fn div_with_mode(&self, other: &Decimal, mode: RoundingMode) -> Option<Decimal> {
+        debug_assert!(!other.is_zero());
+
+        if self.is_zero() {
+            return Some(Decimal::ZERO);
+        }
+
+        let (int_val, scale, negative) = self.div_parts(other);
+        Decimal::adjust_scale_with_mode(int_val, scale, negative, mode)
+    }
+
+    /// Returns the mantissa, scale and sign of `self / other` before rounding, where both are non-zero.
+    ///
+    /// The mantissa has the first 39 or 40 digits of the quotient, followed by a sticky digit which
+    /// is non-zero if the remainder is.
+    #[inline]
+    pub(crate) fn div_parts(&self, other: &Decimal) -> (U256, i16, bool) {
+        debug_assert!(!self.is_zero() && !other.is_zero());
+
+        let shift = MAX_PRECISION + 1 + other.precision() as u32 - self.precision() as u32;
+        let dividend = POWERS_10[shift as usize] * self.int_val;
+        let (quotient, rem) = dividend.div_rem(other.int_val);
+
+        let int_val = quotient * 10 + (rem != 0) as u128;
+        let scale = self.scale - other.scale + shift as i16 + 1;
+        (int_val, scale, self.negative ^ other.negative)
+    }
+
+    /// Computes `self + other` and rounds the exact sum once to exactly `scale` digits after the decimal
+    /// point with `mode`, returning `None` if `scale` is out of range or the result needs more than
+    /// `MAX_PRECISION` digits.
+    ///
+    /// The result has the scale `scale` even if its last digits are zeros, e.g. for amounts of money,
+    /// except that zero always has scale 0. `Display` omits the trailing zeros of the fraction, and a
+    /// precision of `scale` shows them.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, RoundingMode};
+    ///
+    /// let a: Decimal = "1.10".parse().unwrap();
+    /// let b: Decimal = "2.20".parse().unwrap();
+    /// let sum = a.add_with_scale(b, 2, RoundingMode::HalfEven).unwrap();
+    /// assert_eq!(sum.into_parts(), (330, 2, false));
+    /// assert_eq!(format!("{:.*}", sum.scale() as usize, sum), "3.30");
+    /// ```
+    #[inline]
+    pub fn add_with_scale(&self, other: impl AsRef<Decimal>, scale: i16, mode: RoundingMode) -> Option<Decimal> {
+        let other = other.as_ref();
+        if self.is_zero() {
+            return other.rescale_with_mode(scale, mode);
+        }
+        if other.is_zero() {
+            return self.rescale_with_mode(scale, mode);
+        }
+
+        let (int_val, s, negative) = self.add_parts(other)?;
+        Decimal::round_parts_to_scale(int_val, s, negative, scale, mode)
+    }
+
+    /// Computes `self - other` and rounds the exact difference once to exactly `scale` digits after the
+    /// decimal point with `mode`, as [`Decimal::add_with_scale`] does.
+    #[inline]
+    pub fn sub_with_scale(&self, other: impl AsRef<Decimal>, scale: i16, mode: RoundingMode) -> Option<Decimal> {
+        self.add_with_scale(-other.as_ref(), scale, mode)
+    }
+
+    /// Computes `self * other` and rounds the exact product once to exactly `scale` digits after the
+    /// decimal point with `mode`, as [`Decimal::add_with_scale`] does.
+    #[inline]
+    pub fn mul_with_scale(&self, other: impl AsRef<Decimal>, scale: i16, mode: RoundingMode) -> Option<Decimal> {
+        let other = other.as_ref();
+        if self.is_zero() || other.is_zero() {
+            return Decimal::ZERO.rescale_with_mode(scale, mode);
+        }
+
+        let int_val = U256::mul128(self.int_val, other.int_val);
+        Decimal::round_parts_to_scale(
+            int_val,
+            self.scale + other.scale,
+            self.negative ^ other.negative,
+            scale,
+            mode,
+        )
+    }
+
+    /// Computes `self / other` and rounds the quotient once to exactly `scale` digits after the decimal
+    /// point with `mode`, as [`Decimal::add_with_scale`] does, returning `None` if `other` is zero.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, RoundingMode};
+    ///
+    /// let third = Decimal::from(1).div_with_scale(Decimal::from(3), 4, RoundingMode::HalfUp).unwrap();
+    /// assert_eq!(third.to_string(), "0.3333");
+    /// ```
+    #[inline]
+    pub fn div_with_scale(&self, other: impl AsRef<Decimal>, scale: i16, mode: RoundingMode) -> Option<Decimal> {
+        let other = other.as_ref();
+        if other.is_zero() {
+            record_failure!(Div, DivisionByZero, self, Some(other));
+            return None;
+        }
+        if self.is_zero() {
+            return Decimal::ZERO.rescale_with_mode(scale, mode);
+        }
+
+        let (int_val, s, negative) = self.div_parts(other);
+        Decimal::round_parts_to_scale(int_val, s, negative, scale, mode)
+    }
+
+    /// Rounds `int_val * 10^-s` once to exactly `scale` digits after the decimal point with `mode`.
+    #[inline]
+    pub(crate) fn round_parts_to_scale(
+        int_val: U256,
+        s: i16,
+        negative: bool,
+        scale: i16,
+        mode: RoundingMode,
+    ) -> Option<Decimal> {
+        if !(MIN_SCALE..=MAX_SCALE).contains(&scale) {
+            return None;
+        }
+        round_to_scale(int_val, s, negative, MAX_PRECISION as u8, scale, mode).ok()
+    }
+
     /// Checked decimal remainder.
     /// Computes `self % other`, returning None if rhs == 0 or the division results in overflow.
+    ///
+    /// The result scale is the larger of the two operand scales, and `self` is returned unchanged
+    /// when its scale exceeds the scale of `other` by more than `MAX_PRECISION`. A zero result always
+    /// has scale 0.
     #[inline]
     pub fn checked_rem(&self, other: impl AsRef<Decimal>) -> Option<Decimal> {
         let other = other.as_ref();
+        debug_assert!(self.is_canonical() && other.is_canonical());
 
         if other.is_zero() {
+            record_failure!(Rem, DivisionByZero, self, Some(other));
             return None;
         }
 
@@ -1247,18 +3095,199 @@ impl Decimal {
         }
     }
 
+    /// Checked Euclidean division.
+    /// Computes the integer `q` such that `self = q * other + r` with `0 <= r < |other|`,
+    /// returning `None` if `other == 0` or the division results in overflow.
+    ///
+    /// The quotient is rounded to `MAX_PRECISION` digits if it has more.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// assert_eq!(Decimal::from(-7).checked_div_euclid(Decimal::from(2)), Some(Decimal::from(-4)));
+    /// assert_eq!(Decimal::from(-7).checked_div_euclid(Decimal::from(-2)), Some(Decimal::from(4)));
+    /// ```
+    #[inline]
+    pub fn checked_div_euclid(&self, other: impl AsRef<Decimal>) -> Option<Decimal> {
+        let other = other.as_ref();
+        let rem = self.checked_rem_euclid(other)?;
+        Some(self.checked_sub(rem)?.checked_div(other)?.trunc(0))
+    }
+
+    /// Checked Euclidean remainder.
+    /// Computes the non-negative `r` such that `self = q * other + r` for an integer `q` and `r < |other|`,
+    /// returning `None` if `other == 0`.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// assert_eq!(Decimal::from(-7).checked_rem_euclid(Decimal::from(2)), Some(Decimal::from(1)));
+    /// assert_eq!(Decimal::from(7).checked_rem_euclid(Decimal::from(-2)), Some(Decimal::from(1)));
+    /// ```
+    #[inline]
+    pub fn checked_rem_euclid(&self, other: impl AsRef<Decimal>) -> Option<Decimal> {
+        let other = other.as_ref();
+        let rem = self.checked_rem(other)?;
+        if rem.is_sign_negative() && !rem.is_zero() {
+            rem.checked_add(other.abs())
+        } else {
+            Some(rem)
+        }
+    }
+
+    /// Computes the average of `self` and `other` rounded to `scale` with `mode`, returning `None` if
+    /// `scale` is out of range or the average needs more than `MAX_PRECISION` digits at `scale`.
+    ///
+    /// The sum is computed without overflow, even near [`Decimal::MAX`], and the result is clamped into
+    /// the range of both values, should the rounding push it out. So a binary search over the decimals
+    /// of one scale narrows at each step, instead of stalling when the average needs more digits.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, RoundingMode};
+    ///
+    /// let lo: Decimal = "1.20".parse().unwrap();
+    /// let hi: Decimal = "1.25".parse().unwrap();
+    /// let mid = lo.midpoint_at_scale(&hi, 2, RoundingMode::HalfEven).unwrap();
+    /// assert_eq!(mid.to_string(), "1.22");
+    /// ```
+    #[inline]
+    pub fn midpoint_at_scale(&self, other: &Decimal, scale: i16, mode: RoundingMode) -> Option<Decimal> {
+        if !(MIN_SCALE..=MAX_SCALE).contains(&scale) {
+            return None;
+        }
+
+        // `coarse` is kept exactly at the working scale, while the digits of `fine` below it are folded
+        // into a sticky bit.
+        let (coarse, fine) = if other.is_zero() || (!self.is_zero() && self.scale <= other.scale) {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let gap = fine.scale as i32 - coarse.scale as i32;
+        let w = if fine.is_zero() {
+            coarse.scale
+        } else if gap <= MAX_PRECISION as i32 {
+            // The exact sum has at most 77 digits.
+            fine.scale
+        } else {
+            // `|fine|` is less than a tenth of the last unit of `coarse`, so the average has more than
+            // 38 digits at a scale beyond `coarse.scale + 38`.
+            let w = coarse.scale.max(scale);
+            if w - coarse.scale > MAX_PRECISION as i16 {
+                return None;
+            }
+            w
+        };
+
+        let coarse_int = POWERS_10[(w - coarse.scale) as usize] * coarse.int_val;
+        let (fine_int, sticky) = if fine.is_zero() {
+            (U256::ZERO, false)
+        } else if fine.scale <= w {
+            (POWERS_10[(w - fine.scale) as usize] * fine.int_val, false)
+        } else if fine.scale - w > MAX_PRECISION as i16 {
+            (U256::ZERO, true)
+        } else {
+            let divisor = POWERS_10[(fine.scale - w) as usize].low();
+            (U256::from(fine.int_val / divisor), fine.int_val % divisor != 0)
+        };
+
+        let (sum, negative) = if coarse.negative == fine.negative {
+            (coarse_int + fine_int, coarse.negative)
+        } else if coarse_int >= fine_int {
+            (
+                coarse_int.checked_sub(fine_int).expect("coarse_int >= fine_int"),
+                coarse.negative,
+            )
+        } else {
+            (
+                fine_int.checked_sub(coarse_int).expect("fine_int > coarse_int"),
+                fine.negative,
+            )
+        };
+
+        // `|self + other| * 10^w` is `sum + f`, where `0 <= f < 1`, and `f != 0` if `sticky`.
+        let (sum, negative) = if !sticky || negative == fine.negative {
+            (sum, negative)
+        } else if sum == 0 {
+            (sum, fine.negative)
+        } else {
+            (sum - 1, negative)
+        };
+
+        let (quotient, half, exact) = if w < scale {
+            // `w` is the scale of `fine`, so there is no sticky bit.
+            let e = (scale - w) as u32;
+            if sum != 0 && sum.count_digits() + e > MAX_PRECISION + 1 {
+                return None;
+            }
+            let sum = if sum == 0 { sum } else { POWERS_10[e as usize] * sum };
+            (sum >> 1, Ordering::Less, true)
+        } else if ((w - scale) as usize) < POWERS_10.len() {
+            let divisor = POWERS_10[(w - scale) as usize] * 2;
+            let (quotient, rem) = sum.div_rem(divisor);
+            let half = match (rem << 1).cmp(&divisor) {
+                Ordering::Equal if sticky => Ordering::Greater,
+                ord => ord,
+            };
+            (quotient, half, rem == 0 && !sticky)
+        } else {
+            // `sum < 2 * 10^76`, which is less than a half unit at `scale`.
+            (U256::ZERO, Ordering::Less, sum == 0 && !sticky)
+        };
+
+        let quotient = if mode.round_up(half, exact, quotient.low() % 2 == 1, negative) {
+            quotient + 1
+        } else {
+            quotient
+        };
+        if quotient > MAX_I128_REPR as u128 {
+            return None;
+        }
+
+        let mid = Decimal::from_parts(quotient.low(), scale, negative).ok()?;
+        let (lo, hi) = if self <= other { (self, other) } else { (other, self) };
+        Some(if mid < *lo {
+            *lo
+        } else if mid > *hi {
+            *hi
+        } else {
+            mid
+        })
+    }
+
     /// Computes the square root of a decimal,
     /// returning None if `self` is negative or the results in overflow.
     #[inline]
     pub fn sqrt(&self) -> Option<Decimal> {
+        self.try_sqrt().ok()
+    }
+
+    /// Computes the square root of a decimal, returning [`DecimalMathError::DomainError`] if `self` is
+    /// negative or [`DecimalMathError::Overflow`] if the result overflowed.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, DecimalMathError};
+    ///
+    /// assert_eq!(Decimal::from(4).try_sqrt(), Ok(Decimal::from(2)));
+    /// assert_eq!(Decimal::from(-4).try_sqrt(), Err(DecimalMathError::DomainError));
+    /// ```
+    #[inline]
+    pub fn try_sqrt(&self) -> Result<Decimal, DecimalMathError> {
         if self.negative {
-            return None;
+            record_failure!(Sqrt, InvalidOperand, self, None);
+            return Err(DecimalMathError::DomainError);
         }
 
         if self.is_zero() {
-            return Some(Decimal::ZERO);
+            return Ok(Decimal::ZERO);
         }
 
+        self.sqrt_newton().ok_or(DecimalMathError::Overflow)
+    }
+
+    /// Computes the square root of a positive decimal with Newton's method.
+    #[inline]
+    fn sqrt_newton(&self) -> Option<Decimal> {
         let mut result = Decimal::ONE;
         let mut last = result;
 
@@ -1277,6 +3306,272 @@ impl Decimal {
         Some(result)
     }
 
+    /// Computes the square root of a decimal rounded half up to `scale`,
+    /// returning None if `self` is negative or the results in overflow.
+    ///
+    /// It agrees with `sqrt().round(scale)`, but stops the iterations as soon as the digits up to
+    /// `scale` are settled, which is faster for a small `scale`.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n = Decimal::from(2);
+    /// assert_eq!(n.sqrt_with_scale(4), Some("1.4142".parse().unwrap()));
+    /// assert_eq!(n.sqrt_with_scale(4), n.sqrt().map(|r| r.round(4)));
+    /// ```
+    #[inline]
+    pub fn sqrt_with_scale(&self, scale: i16) -> Option<Decimal> {
+        if self.negative {
+            record_failure!(Sqrt, InvalidOperand, self, None);
+            return None;
+        }
+
+        if self.is_zero() {
+            return Some(Decimal::ZERO);
+        }
+
+        // start from a power of 10 close to the root
+        let exp = self.logb()? / 2;
+        let mut result = Decimal::ONE.scaleb(exp)?;
+        let mut last = result;
+        let mut first = true;
+
+        loop {
+            let val = self.checked_div(result)?.normalize();
+            result = result.checked_add(val)?;
+            result = result.checked_mul(Decimal::ZERO_POINT_FIVE)?;
+
+            if result == last {
+                break;
+            }
+
+            // The iterations decrease from above the root after the first one, and each is above the
+            // root by less than its step, so the digits are settled once both ends round the same.
+            if !first && result < last {
+                let lower = result.checked_sub(last.checked_sub(result)?)?;
+                if lower.round(scale) == result.round(scale) {
+                    break;
+                }
+            }
+
+            first = false;
+            last = result;
+        }
+
+        Some(result.round(scale))
+    }
+
+    /// Computes the exact `n`-th root of a decimal, returning `None` if there's no decimal whose `n`-th power
+    /// is exactly `self`, or if `n` is zero.
+    ///
+    /// Unlike [`Decimal::sqrt`], nothing is rounded: e.g. the cube root of `0.000008` is `0.02`, while
+    /// `2` has no exact square root. A negative value has an exact root only for an odd `n`.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let x: Decimal = "0.000008".parse().unwrap();
+    /// assert_eq!(x.nth_root_exact(3), Some("0.02".parse().unwrap()));
+    /// assert_eq!(Decimal::from(2).nth_root_exact(2), None);
+    /// ```
+    #[inline]
+    pub fn nth_root_exact(&self, n: u32) -> Option<Decimal> {
+        if n == 0 {
+            return None;
+        }
+
+        if self.is_zero() || n == 1 {
+            return Some(*self);
+        }
+
+        if self.negative && n.is_multiple_of(2) {
+            return None;
+        }
+
+        // The normalized mantissa of the root isn't a multiple of 10, so neither is the mantissa of its
+        // power, which has to be the normalized mantissa of `self`.
+        let (int_val, exp, negative) = self.factor_pow10();
+        // `n` may not fit in `i32`.
+        let scale = -exp as i64;
+        if scale.rem_euclid(n as i64) != 0 {
+            return None;
+        }
+
+        let root = int_root(int_val, n);
+        if root.checked_pow(n) != Some(int_val) {
+            return None;
+        }
+
+        Some(unsafe { Decimal::from_parts_unchecked(root, (scale / n as i64) as i16, negative) })
+    }
+
+    /// Returns `true` if `self` is the `n`-th power of a decimal, see [`Decimal::nth_root_exact`].
+    #[inline]
+    pub fn is_perfect_power(&self, n: u32) -> bool {
+        self.nth_root_exact(n).is_some()
+    }
+
+    /// Estimates the `n`th root of the positive `self` slightly above it, so the iterations of
+    /// [`Decimal::nth_root`] decrease to it.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn nth_root_estimate(&self, n: u32) -> Option<Decimal> {
+        Decimal::try_from(f64::from(self).powf(1.0 / n as f64) * (1.0 + 1e-12)).ok()
+    }
+
+    /// Estimates the `n`th root of the positive `self` slightly above it, with [`Decimal::ln`] and
+    /// [`Decimal::exp`] as there is no `f64::powf` without `std`.
+    #[cfg(not(feature = "std"))]
+    #[inline]
+    fn nth_root_estimate(&self, n: u32) -> Option<Decimal> {
+        const ABOVE: Decimal = unsafe { Decimal::from_parts_unchecked(1_000_000_000_001, 12, false) };
+        self.ln()?.checked_div(Decimal::from(n))?.exp()?.checked_mul(ABOVE)
+    }
+
+    /// Computes the `n`-th root of a decimal, returning `None` if `n` is zero, or if `n` is even and
+    /// `self` is negative.
+    ///
+    /// The root is refined with Newton's method from an estimate in `f64`, and its error is less than
+    /// one unit in the last digit. An exact root such as the 5th root of `1e20` is exact, see
+    /// [`Decimal::nth_root_exact`].
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let x: Decimal = "1e20".parse().unwrap();
+    /// assert_eq!(x.nth_root(5), Some(Decimal::from(10000)));
+    /// assert_eq!(Decimal::from(-8).nth_root(3), Some(Decimal::from(-2)));
+    /// assert_eq!(Decimal::from(-8).nth_root(2), None);
+    /// ```
+    #[inline]
+    pub fn nth_root(&self, n: u32) -> Option<Decimal> {
+        if n == 0 {
+            return None;
+        }
+
+        if self.is_zero() || n == 1 {
+            return Some(*self);
+        }
+
+        if self.negative && n.is_multiple_of(2) {
+            return None;
+        }
+
+        if let Some(root) = self.nth_root_exact(n) {
+            return Some(root);
+        }
+
+        let val = self.abs();
+        let mut result = val.nth_root_estimate(n)?;
+        let n_val = Decimal::from(n);
+        let mut first = true;
+
+        loop {
+            // x' = x - (x - val / x^(n-1)) / n
+            let power = result.pow_u64(n as u64 - 1)?;
+            let delta = result.checked_sub(val.checked_div(power)?)?.checked_div(n_val)?;
+            let next = result.checked_sub(delta)?;
+
+            if !first && next >= result {
+                break;
+            }
+
+            first = false;
+            result = next;
+        }
+
+        if self.negative {
+            result = -result;
+        }
+
+        Some(result)
+    }
+
+    /// Computes the cube root of a decimal, which is negative for a negative `self`.
+    ///
+    /// It's the same as [`Decimal::nth_root`] with `n = 3`.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let expected: Decimal = "1.2599210498948731647672106072782283506".parse().unwrap();
+    /// assert_eq!(Decimal::from(2).cbrt(), Some(expected));
+    /// ```
+    #[inline]
+    pub fn cbrt(&self) -> Option<Decimal> {
+        self.nth_root(3)
+    }
+
+    /// Returns the length of the output of `Display`, i.e. `self.to_string().len()`, without formatting.
+    ///
+    /// The output is ASCII, so this is also the count of chars. Padding to a width isn't included.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n: Decimal = "-123.45".parse().unwrap();
+    /// assert_eq!(n.display_len(), 7);
+    /// ```
+    #[inline]
+    pub fn display_len(&self) -> usize {
+        if self.is_zero() {
+            return 1;
+        }
+
+        let sign = self.negative as usize;
+        let digits = self.precision() as usize;
+        if self.scale <= 0 {
+            return sign + digits + (-self.scale) as usize;
+        }
+
+        let scale = self.scale as usize;
+        if digits <= scale {
+            // "0." and the digits with leading zeros
+            return sign + 2 + scale;
+        }
+
+        // The trailing zeros of the fractional part are omitted, together with the point if all are zeros.
+        let frac_len = scale - (strip_trailing_zeros(self.int_val).1 as usize).min(scale);
+        sign + digits - scale + if frac_len > 0 { 1 + frac_len } else { 0 }
+    }
+
+    /// Returns the length of the output of `Display` with precision `prec`, i.e. `format!("{:.prec$}", self).len()`,
+    /// without formatting.
+    ///
+    /// The rounding to `prec` is taken into account, which may carry into a new integer digit.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n: Decimal = "99.995".parse().unwrap();
+    /// assert_eq!(n.display_len_with_precision(2), "100.00".len());
+    /// assert_eq!((-n).display_len_with_precision(0), "-100".len());
+    /// ```
+    #[inline]
+    pub fn display_len_with_precision(&self, prec: usize) -> usize {
+        if self.is_zero() {
+            return 1;
+        }
+
+        // The sign is kept even if the value is rounded to zero, e.g. "-0.00".
+        let sign = self.negative as usize;
+        let dec = self.round(prec.min(i16::MAX as usize) as i16);
+        let digits = dec.precision() as usize;
+        // The length saturates for a `prec` near `usize::MAX`, which can't be formatted anyway.
+        if dec.scale <= 0 {
+            let point = if prec != 0 { prec.saturating_add(1) } else { 0 };
+            return (sign + digits + (-dec.scale) as usize).saturating_add(point);
+        }
+
+        let scale = dec.scale as usize;
+        if digits <= scale {
+            // "0." and the digits with leading zeros
+            sign + 2 + scale
+        } else {
+            (sign + digits - scale + 1).saturating_add(prec)
+        }
+    }
+
     /// Formats the decimal, including sign and omitting integer zero in fractional.
     #[inline]
     pub fn simply_format<W: fmt::Write>(&self, w: W) -> Result<(), DecimalFormatError> {
@@ -1292,9 +3587,7 @@ impl Decimal {
         precision: Option<usize>,
         mut w: W,
     ) -> Result<(), DecimalFormatError> {
-        use std::fmt::Write;
-
-        const ZERO_BUF: [u8; 256] = [b'0'; 256];
+        use core::fmt::Write;
 
         if self.is_zero() {
             w.write_byte(b'0')?;
@@ -1302,7 +3595,7 @@ impl Decimal {
         }
 
         let dec = if let Some(prec) = precision {
-            self.round(prec as i16)
+            self.round(prec.min(i16::MAX as usize) as i16)
         } else {
             *self
         };
@@ -1315,11 +3608,11 @@ impl Decimal {
 
         if scale <= 0 {
             write!(w, "{}", dec.int_val())?;
-            w.write_bytes(&ZERO_BUF[..-scale as usize])?;
+            w.write_zeros(-scale as usize)?;
             if let Some(prec) = precision {
                 if prec != 0 {
                     w.write_byte(b'.')?;
-                    w.write_bytes(&ZERO_BUF[..prec])?;
+                    w.write_zeros(prec)?;
                 }
             }
         } else {
@@ -1333,7 +3626,7 @@ impl Decimal {
                     w.write_byte(b'0')?;
                 }
                 w.write_byte(b'.')?;
-                w.write_bytes(&ZERO_BUF[..scale as usize - len])?;
+                w.write_zeros(scale as usize - len)?;
                 if omit_frac_ending_zero {
                     let zero_num = digits.iter().rev().take_while(|ch| **ch == b'0').count();
                     w.write_bytes(&digits[0..len - zero_num])?;
@@ -1348,7 +3641,7 @@ impl Decimal {
                     let after_len = frac_digits.len();
                     if prec > after_len {
                         w.write_bytes(frac_digits)?;
-                        w.write_bytes(&ZERO_BUF[..prec - after_len])?;
+                        w.write_zeros(prec - after_len)?;
                     } else {
                         w.write_bytes(&frac_digits[0..prec])?;
                     }
@@ -1362,28 +3655,133 @@ impl Decimal {
             }
         }
 
-        Ok(())
+        Ok(())
+    }
+
+    /// Formats the absolute value in the scientific notation of `LowerExp` and `UpperExp`, e.g.
+    /// `1.2856e4`, with the significant digits or `precision` fractional digits rounded half away
+    /// from zero.
+    #[inline]
+    fn fmt_exp_internal<W: fmt::Write>(&self, upper: bool, precision: Option<usize>, mut w: W) -> fmt::Result {
+        let (mut int_val, exp, _) = self.factor_pow10();
+        let mut digits = if int_val == 0 {
+            1
+        } else {
+            U256::from(int_val).count_digits() as usize
+        };
+        let mut exp = exp + digits as i32 - 1;
+
+        if let Some(prec) = precision {
+            if prec + 1 < digits {
+                let divisor = POWERS_10[digits - 1 - prec].low();
+                let rem = int_val % divisor;
+                int_val /= divisor;
+                if rem >= divisor / 2 {
+                    int_val += 1;
+                }
+                digits = prec + 1;
+                if int_val == POWERS_10[digits].low() {
+                    int_val /= 10;
+                    exp += 1;
+                }
+            }
+        }
+
+        let frac_len = digits - 1;
+        let prec = precision.unwrap_or(frac_len);
+        let unit = POWERS_10[frac_len].low();
+        w.write_byte(b'0' + (int_val / unit) as u8)?;
+        if prec > 0 {
+            w.write_byte(b'.')?;
+        }
+        if frac_len > 0 {
+            write!(w, "{:01$}", int_val % unit, frac_len)?;
+        }
+        w.write_zeros(prec - frac_len)?;
+        w.write_byte(if upper { b'E' } else { b'e' })?;
+        write!(w, "{}", exp)
+    }
+
+    /// Implements `LowerHex`, `UpperHex`, `Octal` and `Binary` with digits in radix `2^bits`, padding as
+    /// `Display` does.
+    #[inline]
+    fn fmt_radix(&self, bits: usize, upper: bool, prefix: &str, f: &mut fmt::Formatter) -> fmt::Result {
+        // 10^126 < 2^419, so 7 limbs hold the integral part of any decimal.
+        const LIMBS: usize = 7;
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+        let int = self.round(0);
+        let mut limbs = [0u64; LIMBS];
+        limbs[0] = int.int_val as u64;
+        limbs[1] = (int.int_val >> 64) as u64;
+        for _ in 0..-int.scale.min(0) {
+            let mut carry = 0u128;
+            for limb in limbs.iter_mut() {
+                let p = *limb as u128 * 10 + carry;
+                *limb = p as u64;
+                carry = p >> 64;
+            }
+            debug_assert_eq!(carry, 0);
+        }
+
+        let sig_bits = match limbs.iter().rposition(|&limb| limb != 0) {
+            Some(i) => 64 * i + 64 - limbs[i].leading_zeros() as usize,
+            None => 1,
+        };
+        let len = sig_bits / bits + (sig_bits % bits != 0) as usize;
+
+        let mut buf = [0u8; LIMBS * 64];
+        for (i, b) in buf[..len].iter_mut().rev().enumerate() {
+            let mut digit = 0;
+            for bit in (i * bits..(i + 1) * bits).rev() {
+                let set = bit < LIMBS * 64 && limbs[bit / 64] & (1 << (bit % 64)) != 0;
+                digit = digit << 1 | set as usize;
+            }
+            *b = if upper {
+                DIGITS[digit].to_ascii_uppercase()
+            } else {
+                DIGITS[digit]
+            };
+        }
+
+        let str = unsafe { core::str::from_utf8_unchecked(&buf[..len]) };
+        f.pad_integral(int.is_sign_positive(), prefix, str)
+    }
+
+    /// Implements `LowerExp` and `UpperExp`, padding as `Display` does.
+    #[inline]
+    fn fmt_exp(&self, upper: bool, f: &mut fmt::Formatter) -> fmt::Result {
+        // A large precision may not fit in the buffer, e.g. `{:.300e}`.
+        if matches!(f.precision(), Some(prec) if MAX_FMT_LEN + 1 + prec > BUF_CAPACITY) {
+            let mut str = String::new();
+            self.fmt_exp_internal(upper, f.precision(), &mut str)?;
+            return f.pad_integral(self.is_sign_positive(), "", &str);
+        }
+
+        let mut buf = Buf::new();
+        self.fmt_exp_internal(upper, f.precision(), &mut buf)?;
+        let str = unsafe { core::str::from_utf8_unchecked(buf.as_slice()) };
+        f.pad_integral(self.is_sign_positive(), "", str)
     }
 
     #[inline]
     fn fmt_sci_internal<W: fmt::Write, const POSITIVE_EXP: bool, const MIN_SCALE: i16>(
         &self,
+        precision: i16,
         expect_scale: i16,
         mut exp: u16,
         mut w: W,
     ) -> Result<(), DecimalFormatError> {
         if expect_scale >= MIN_SCALE {
-            // Creates number part
-            let temp_scale = if POSITIVE_EXP {
-                expect_scale - exp as i16
-            } else {
-                expect_scale + exp as i16
-            };
-
-            let mut dec = self.round(temp_scale);
-
-            // Whether number carries or not
-            if dec.precision() > self.trunc(temp_scale).precision() {
+            // Creates number part, i.e. the significand with one integer digit, which is rounded in place,
+            // as the position to round the decimal itself may be out of the scale range.
+            let mut dec = *self;
+            dec.scale = precision - 1;
+            let mut dec = dec.round(expect_scale);
+
+            // Whether number carries or not, i.e. the significand is rounded up to 10
+            if dec.scale < precision - 1 && dec.int_val == POWERS_10[expect_scale as usize + 1].low() {
+                dec.scale += 1;
                 if POSITIVE_EXP {
                     exp += 1
                 } else {
@@ -1391,13 +3789,6 @@ impl Decimal {
                 }
             }
 
-            // This decimal only includes scientific notation number part
-            if POSITIVE_EXP {
-                dec.scale += exp as i16
-            } else {
-                dec.scale -= exp as i16
-            };
-
             // Supplies zero to fill expect scale
             dec.fmt_internal(true, true, true, Some(expect_scale as usize), &mut w)?;
 
@@ -1473,9 +3864,9 @@ impl Decimal {
 
             const MIN_SCALE: i16 = 1;
             if positive_exp {
-                self.fmt_sci_internal::<W, true, MIN_SCALE>(expect_scale, exp, w)?;
+                self.fmt_sci_internal::<W, true, MIN_SCALE>(precision, expect_scale, exp, w)?;
             } else {
-                self.fmt_sci_internal::<W, false, MIN_SCALE>(expect_scale, exp, w)?;
+                self.fmt_sci_internal::<W, false, MIN_SCALE>(precision, expect_scale, exp, w)?;
             }
         } else {
             self.fmt_internal(true, true, true, prec, w)?;
@@ -1505,20 +3896,19 @@ impl Decimal {
         let positive_exp = precision > self.scale;
 
         if self.is_zero() && expect_scale > 0 {
-            const ZERO_BUF: [u8; MAX_SCALE] = [b'0'; MAX_SCALE];
             if with_zero_before_dot {
                 w.write_bytes(b"0.")?;
             } else {
                 w.write_bytes(b" .")?;
             }
-            w.write_bytes(&ZERO_BUF[..expect_scale as usize - 1])?;
+            w.write_zeros(expect_scale as usize - 1)?;
         }
 
         const MIN_SCALE: i16 = 0;
         if positive_exp {
-            self.fmt_sci_internal::<W, true, MIN_SCALE>(expect_scale, exp, w)?;
+            self.fmt_sci_internal::<W, true, MIN_SCALE>(precision, expect_scale, exp, w)?;
         } else {
-            self.fmt_sci_internal::<W, false, MIN_SCALE>(expect_scale, exp, w)?;
+            self.fmt_sci_internal::<W, false, MIN_SCALE>(precision, expect_scale, exp, w)?;
         }
         Ok(())
     }
@@ -1540,14 +3930,16 @@ impl Decimal {
         let real_num = POWERS_10[(-integer.scale) as usize] * integer.int_val;
         if is_uppercase {
             if real_num.high() != 0 {
-                write!(&mut w, "{:X}", real_num.high())?;
+                write!(&mut w, "{:X}{:032X}", real_num.high(), real_num.low())?;
+            } else {
+                write!(&mut w, "{:X}", real_num.low())?;
             }
-            write!(&mut w, "{:X}", real_num.low())?;
         } else {
             if real_num.high() != 0 {
-                write!(&mut w, "{:x}", real_num.high())?;
+                write!(&mut w, "{:x}{:032x}", real_num.high(), real_num.low())?;
+            } else {
+                write!(&mut w, "{:x}", real_num.low())?;
             }
-            write!(&mut w, "{:x}", real_num.low())?;
         }
 
         Ok(())
@@ -1567,15 +3959,7 @@ impl Decimal {
         let use_sci = if self.scale <= 0 {
             precision - self.scale > MAX_WIDTH
         } else {
-            let mut int_val = self.int_val;
-            let mut zero_count = 0;
-            while int_val != 0 {
-                if int_val % 10 != 0 {
-                    break;
-                }
-                zero_count += 1;
-                int_val /= 10;
-            }
+            let zero_count = strip_trailing_zeros(self.int_val).1 as i16;
             self.scale - zero_count > MAX_WIDTH
         };
 
@@ -1599,6 +3983,194 @@ impl Decimal {
         Ok(())
     }
 
+    /// Returns the string for interchange with JavaScript, which follows the rules of ECMAScript
+    /// `Number.prototype.toString`, so that equal decimals always give the same string.
+    ///
+    /// The value is written with its significant digits `d1 d2 ... dk` without trailing zeros, and its
+    /// exponent `e`, i.e. `value = d1.d2...dk * 10^e`:
+    ///
+    /// - Zero is `0`, and `-0` is never produced.
+    /// - If `-6 <= e <= 20`, the plain notation is used, e.g. `123000`, `1.5` or `0.000001`, without
+    ///   trailing fractional zeros.
+    /// - Otherwise, the exponent notation `d1.d2...dke<exp>` is used, where the fraction is omitted if
+    ///   `k == 1`, the `e` is lowercase, and only a negative exponent has a sign, e.g. `1e21`, `1.5e-7`.
+    ///
+    /// The string can be parsed back exactly with [`str::parse`] if the magnitude of the value is in the
+    /// range of `str::parse`, from `1e-130` to [`Decimal::MAX`]. [`Decimal::from_parts`] accepts some
+    /// values outside of it, e.g. `1e-131` or `1e126`, whose string fails to parse with `Underflow` or
+    /// `Overflow`.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n: Decimal = "1.50E+25".parse().unwrap();
+    /// assert_eq!(n.to_interchange_string(), "1.5e25");
+    /// assert_eq!(n.to_interchange_string().parse::<Decimal>().unwrap(), n);
+    /// ```
+    pub fn to_interchange_string(&self) -> String {
+        if self.is_zero() {
+            return "0".to_owned();
+        }
+
+        let (int_val, exp, negative) = self.factor_pow10();
+        let digits = int_val.to_string();
+        let k = digits.len() as i32;
+        let exp = k - 1 + exp;
+
+        let mut s = String::with_capacity(k as usize + 24);
+        if negative {
+            s.push('-');
+        }
+
+        if (-6..=20).contains(&exp) {
+            if exp >= k - 1 {
+                s.push_str(&digits);
+                for _ in 0..exp - k + 1 {
+                    s.push('0');
+                }
+            } else if exp >= 0 {
+                let (int_part, frac_part) = digits.split_at(exp as usize + 1);
+                s.push_str(int_part);
+                s.push('.');
+                s.push_str(frac_part);
+            } else {
+                s.push_str("0.");
+                for _ in 0..-exp - 1 {
+                    s.push('0');
+                }
+                s.push_str(&digits);
+            }
+        } else {
+            let (first, rest) = digits.split_at(1);
+            s.push_str(first);
+            if !rest.is_empty() {
+                s.push('.');
+                s.push_str(rest);
+            }
+            s.push('e');
+            s.push_str(&exp.to_string());
+        }
+
+        s
+    }
+
+    /// Formats the decimal with an SI suffix, choosing the suffix which gives a mantissa in `[1, 1000)`,
+    /// rounded to at most `significant` significant digits, without trailing zeros.
+    ///
+    /// The suffixes are `n`, `u`, `m`, none, `k`, `M`, `B` and `T`, from 10^-9 to 10^12, so that the
+    /// mantissa is at least 1000 above 10^15, and below 1 under 10^-9. `significant` is clamped to `[1, 38]`.
+    /// The output can be parsed back with [`Decimal::parse_with_si_suffix`].
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let mut s = String::new();
+    /// "1534000".parse::<Decimal>().unwrap().format_with_si_suffix(2, &mut s).unwrap();
+    /// assert_eq!(s, "1.5M");
+    /// ```
+    #[inline]
+    pub fn format_with_si_suffix<W: fmt::Write>(&self, significant: u8, mut w: W) -> Result<(), DecimalFormatError> {
+        const SUFFIXES: [&str; 8] = ["n", "u", "m", "", "k", "M", "B", "T"];
+        const MIN_EXP: i16 = -9;
+        const MAX_EXP: i16 = 12;
+
+        let exp = match self.logb() {
+            Some(e) => e,
+            None => {
+                w.write_byte(b'0')?;
+                return Ok(());
+            }
+        };
+
+        let significant = significant.clamp(1, MAX_PRECISION as u8) as i16;
+        let mut group = (exp.div_euclid(3) * 3).clamp(MIN_EXP, MAX_EXP);
+        let mut mantissa = self.scaleb(-group).ok_or(DecimalFormatError::OutOfRange)?;
+        let mantissa_exp = mantissa.logb().ok_or(DecimalFormatError::OutOfRange)?;
+        mantissa = mantissa.round(significant - 1 - mantissa_exp);
+
+        // rounding may carry into the next group, e.g. 999.96 -> 1000.0
+        if mantissa.abs() >= Decimal::from(1000) && group < MAX_EXP {
+            group += 3;
+            mantissa = mantissa.scaleb(-3).ok_or(DecimalFormatError::OutOfRange)?;
+        }
+
+        write!(&mut w, "{}", mantissa.normalize())?;
+        w.write_str(SUFFIXES[((group - MIN_EXP) / 3) as usize])?;
+        Ok(())
+    }
+
+    /// Formats the decimal as `Display` with `precision` does, but with `group_sep` between every three
+    /// digits of the integral part, and `decimal_sep` as the decimal point.
+    ///
+    /// The trailing zeros of a negative scale are grouped too, while the fractional part isn't.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n: Decimal = "1234567.891".parse().unwrap();
+    /// let mut s = String::new();
+    /// n.format_with_separators(',', '.', None, &mut s).unwrap();
+    /// assert_eq!(s, "1,234,567.891");
+    ///
+    /// let mut s = String::new();
+    /// n.format_with_separators('.', ',', Some(2), &mut s).unwrap();
+    /// assert_eq!(s, "1.234.567,89");
+    /// ```
+    #[inline]
+    pub fn format_with_separators<W: fmt::Write>(
+        &self,
+        group_sep: char,
+        decimal_sep: char,
+        precision: Option<usize>,
+        w: W,
+    ) -> Result<(), DecimalFormatError> {
+        // A large precision may not fit in the buffer, e.g. `Some(300)`.
+        if matches!(precision, Some(prec) if MAX_FMT_LEN + 1 + prec > BUF_CAPACITY) {
+            let mut str = String::new();
+            self.fmt_internal(false, false, false, precision, &mut str)?;
+            return self.write_with_separators(str.as_bytes(), group_sep, decimal_sep, w);
+        }
+
+        let mut buf = Buf::new();
+        self.fmt_internal(false, false, false, precision, &mut buf)?;
+        self.write_with_separators(buf.as_slice(), group_sep, decimal_sep, w)
+    }
+
+    /// Writes the unsigned `digits` of `self` with the separators.
+    #[inline]
+    fn write_with_separators<W: fmt::Write>(
+        &self,
+        digits: &[u8],
+        group_sep: char,
+        decimal_sep: char,
+        mut w: W,
+    ) -> Result<(), DecimalFormatError> {
+        let point = digits.iter().position(|&c| c == b'.').unwrap_or(digits.len());
+        let (integral, fractional) = digits.split_at(point);
+
+        if self.is_sign_negative() {
+            w.write_byte(b'-')?;
+        }
+
+        let first_len = match integral.len() % 3 {
+            0 => integral.len().min(3),
+            n => n,
+        };
+        let (first, rest) = integral.split_at(first_len);
+        w.write_bytes(first)?;
+        for group in rest.chunks(3) {
+            w.write_char(group_sep)?;
+            w.write_bytes(group)?;
+        }
+
+        if let Some((_, fractional)) = fractional.split_first() {
+            w.write_char(decimal_sep)?;
+            w.write_bytes(fractional)?;
+        }
+
+        Ok(())
+    }
+
     /// Raise `self` to the power of `exponent`, where `self`
     /// is a decimal and `exponent` is an u64 integer,
     /// returning None if the result overflowed.
@@ -1664,17 +4236,18 @@ impl Decimal {
     }
 
     /// Raise `self` to the power of `exponent`, where `self` is
-    /// a decimal and `exponent` is an i64 integer, returning None
+    /// a decimal and `exponent` is an i64 integer, returning an error
     /// if `self == 0` at the same time `exponent` is negative or
-    /// the result overflowed.
+    /// the result overflowed or underflowed.
     #[inline]
-    fn pow_i64(&self, exponent: i64) -> Option<Decimal> {
+    fn pow_i64(&self, exponent: i64) -> Result<Decimal, DecimalMathError> {
         if exponent >= 0 {
-            return self.pow_u64(exponent as u64);
+            return self.pow_u64(exponent as u64).ok_or(DecimalMathError::Overflow);
         }
         // exponent is negative, example: 0^-3 is error
         if self.is_zero() {
-            return None;
+            record_failure!(Pow, DivisionByZero, self, Some(&Decimal::from(exponent)));
+            return Err(DecimalMathError::DomainError);
         }
 
         // Here use reciprocal value to calculate x^-y:
@@ -1687,7 +4260,8 @@ impl Decimal {
         // x and y in some ranges can be calculated quickly.
         let result = if x.pow_quick_range(y) {
             // x^y won't overflow, so can be calculated quickly
-            Decimal::ONE.checked_div(&x.pow_u64(y)?)?
+            let power = x.pow_u64(y).ok_or(DecimalMathError::Overflow)?;
+            Decimal::ONE.checked_div(power).ok_or(DecimalMathError::Overflow)?
         } else {
             // x^y maybe overflow, so calculate x^-y with x^(y/2)
 
@@ -1698,51 +4272,52 @@ impl Decimal {
 
             match x.pow_u64(y / 2) {
                 Some(p) => {
-                    let power = Decimal::ONE.checked_div(&p)?.checked_div(p)?;
-                    if y % 2 == 1 {
-                        power.checked_div(&x)?
+                    let power = Decimal::ONE.checked_div(p).and_then(|r| r.checked_div(p));
+                    let power = if y % 2 == 1 {
+                        power.and_then(|r| r.checked_div(x))
                     } else {
                         power
-                    }
+                    };
+                    power.ok_or(DecimalMathError::Overflow)?
                 }
-                // x^(y/2) is overflow, x^-y = 1 / x^(y/2) / x^(y/2) must be 0
-                None => Decimal::ZERO,
+                // x^(y/2) is overflow, x^-y = 1 / x^(y/2) / x^(y/2) is too small to be represented
+                None => return Err(DecimalMathError::Underflow),
             }
         };
 
-        Some(result)
+        Ok(result)
     }
 
     /// Raise `self` to the power of `exponent`, where `self`
     /// and `exponent` are both decimal, requires `exponent`
-    /// is an integer, only used in `checked_pow()`.
+    /// is an integer, only used in `pow_with_precision()`.
     #[inline]
-    fn pow_decimal_integral(&self, exponent: &Decimal) -> Option<Decimal> {
+    fn pow_decimal_integral(&self, exponent: &Decimal, digits: u8) -> Result<Decimal, DecimalMathError> {
         debug_assert!((exponent.int_val == exponent.normalize().int_val) && (exponent.scale() <= 0));
 
         if exponent.is_sign_negative() {
             // too small to calculate from pow_i64 accurately
             if *exponent < Decimal::from(i16::MIN) {
-                return self.pow_decimal(exponent);
+                return self.pow_decimal(exponent, digits);
             }
 
             self.pow_i64(-(exponent.int_val as i64))
         } else {
             // too big to calculate from pow_u64 accurately
             if *exponent > Decimal::from(u16::MAX) {
-                return self.pow_decimal(exponent);
+                return self.pow_decimal(exponent, digits);
             }
 
-            self.pow_u64(exponent.int_val as u64)
+            self.pow_u64(exponent.int_val as u64).ok_or(DecimalMathError::Overflow)
         }
     }
 
     /// Raise `self` to the power of `exponent`, where `self` and
-    /// `exponent` are both decimal, only used in `checked_pow()`,
+    /// `exponent` are both decimal, only used in `pow_with_precision()`,
     /// requires `self` is positive or `exponent` is an integer,
-    /// returning None if the result overflowed.
+    /// returning an error if the result overflowed or underflowed.
     #[inline]
-    fn pow_decimal(&self, exponent: &Decimal) -> Option<Decimal> {
+    fn pow_decimal(&self, exponent: &Decimal, digits: u8) -> Result<Decimal, DecimalMathError> {
         debug_assert!((*self > Decimal::ZERO) || (exponent.normalize().scale() <= 0));
 
         // For positive x:
@@ -1754,15 +4329,19 @@ impl Decimal {
         let x = self.abs();
         let b = *exponent;
 
-        let ln = x.ln()?;
-        let exp = ln.checked_mul(&b)?;
-        let mut result = exp.exp()?;
+        // The relative error of x^b is the absolute error of b * ln(x), which is less than 300
+        // unless x^b overflows, so keep 4 guard digits.
+        let working_digits = (digits + 4).min(MAX_PRECISION as u8);
 
-        if self.negative && b.checked_rem(&Decimal::TWO)? == Decimal::ONE {
+        let ln = x.try_ln_with_precision(working_digits)?;
+        let exp = ln.checked_mul(b).ok_or(DecimalMathError::Overflow)?;
+        let mut result = exp.try_exp_with_precision(working_digits)?;
+
+        if self.negative && b.checked_rem(Decimal::TWO) == Some(Decimal::ONE) {
             result = -result;
         }
 
-        Some(result)
+        Ok(result)
     }
 
     /// Raise `self` to the power of `exponent`, where `self` and `exponent`
@@ -1771,32 +4350,96 @@ impl Decimal {
     /// `exponent` is a fraction or the result overflowed.
     #[inline]
     pub fn checked_pow(&self, exponent: &Decimal) -> Option<Decimal> {
+        self.pow_with_precision(exponent, MAX_PRECISION as u8)
+    }
+
+    /// Raise `self` to the power of `exponent`, where `self` and `exponent` are both decimal.
+    ///
+    /// Unlike [`Decimal::checked_pow`], which returns None for every failure and zero for a result too
+    /// small to be represented, it returns:
+    /// - [`DecimalMathError::DomainError`] if `self == 0` at the same time `exponent` is negative, or
+    ///   `self` is negative at the same time `exponent` is a fraction.
+    /// - [`DecimalMathError::Overflow`] if the result is too large to be represented.
+    /// - [`DecimalMathError::Underflow`] if the result isn't zero, but too small to be represented.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, DecimalMathError};
+    ///
+    /// let ten = Decimal::from(10);
+    /// assert_eq!(Decimal::ZERO.try_pow(&Decimal::ZERO), Ok(Decimal::ONE));
+    /// assert_eq!(Decimal::ZERO.try_pow(&Decimal::from(-2)), Err(DecimalMathError::DomainError));
+    /// assert_eq!(ten.try_pow(&Decimal::from(200)), Err(DecimalMathError::Overflow));
+    /// assert_eq!(ten.try_pow(&Decimal::from(-200)), Err(DecimalMathError::Underflow));
+    /// assert_eq!(ten.checked_pow(&Decimal::from(-200)), Some(Decimal::ZERO));
+    /// ```
+    #[inline]
+    pub fn try_pow(&self, exponent: &Decimal) -> Result<Decimal, DecimalMathError> {
+        self.try_pow_with_precision(exponent, MAX_PRECISION as u8)
+    }
+
+    /// Raise `self` to the power of `exponent` to `digits` significant digits, returning None
+    /// in the same cases as [`Decimal::checked_pow`].
+    ///
+    /// A fractional power is computed as `e^(exponent * ln(self))` with [`Decimal::ln_with_precision`]
+    /// and [`Decimal::exp_with_precision`] at `digits + 4` digits. The result is rounded to `digits`
+    /// significant digits, and its error is less than one unit in the last digit.
+    ///
+    /// `digits` ranges from `[1, 38]`, and `pow_with_precision(exponent, 38)` is the same as
+    /// [`Decimal::checked_pow`].
+    #[inline]
+    pub fn pow_with_precision(&self, exponent: &Decimal, digits: u8) -> Option<Decimal> {
+        underflow_to_zero(self.try_pow_with_precision(exponent, digits))
+    }
+
+    /// Raise `self` to the power of `exponent` to `digits` significant digits, returning an error
+    /// in the same cases as [`Decimal::try_pow`].
+    #[inline]
+    fn try_pow_with_precision(&self, exponent: &Decimal, digits: u8) -> Result<Decimal, DecimalMathError> {
+        let digits = digits.max(1).min(MAX_PRECISION as u8);
+
         if exponent.is_zero() {
-            return Some(Decimal::ONE);
+            return Ok(Decimal::ONE);
         }
         if self.is_zero() {
             // exponent is negative, example: 0^-3 is error
             if exponent.is_sign_negative() {
-                return None;
+                record_failure!(Pow, DivisionByZero, self, Some(exponent));
+                return Err(DecimalMathError::DomainError);
             }
-            return Some(Decimal::ZERO);
+            return Ok(Decimal::ZERO);
         }
         if *self == Decimal::ONE {
-            return Some(Decimal::ONE);
-        }
-        if exponent == Decimal::ONE {
-            return Some(*self);
+            return Ok(Decimal::ONE);
         }
 
+        // base is negative and exponent is a fraction, example: (-3)^2.2 is error
         let exponent = exponent.normalize();
-        // exponent is an integer
-        if exponent.scale() <= 0 {
-            return self.pow_decimal_integral(&exponent);
+        if exponent.scale() > 0 && self.is_sign_negative() {
+            record_failure!(Pow, InvalidOperand, self, Some(&exponent));
+            return Err(DecimalMathError::DomainError);
         }
 
-        // base is negative and exponent is a fraction, example: (-3)^2.2 is error
-        if self.is_sign_negative() {
-            return None;
+        let result = self.pow_nonzero(&exponent, digits)?;
+        // a non-zero base never raises to zero
+        if result.is_zero() {
+            return Err(DecimalMathError::Underflow);
+        }
+
+        Ok(result)
+    }
+
+    /// Raise a non-zero `self` to the power of a normalized `exponent` to `digits` significant digits,
+    /// requires `self` is positive or `exponent` is an integer, only used in `try_pow_with_precision()`.
+    #[inline]
+    fn pow_nonzero(&self, exponent: &Decimal, digits: u8) -> Result<Decimal, DecimalMathError> {
+        if *exponent == Decimal::ONE {
+            return self.round_to_digits(digits).ok_or(DecimalMathError::Overflow);
+        }
+
+        // exponent is an integer
+        if exponent.scale() <= 0 {
+            let result = self.pow_decimal_integral(exponent, digits)?;
+            return result.round_to_digits(digits).ok_or(DecimalMathError::Overflow);
         }
 
         // Let n = a + b:
@@ -1806,40 +4449,80 @@ impl Decimal {
         // so calculate x^a and x^b is faster and more accurate.
 
         let x = *self;
-        let n = exponent;
+        let n = *exponent;
 
         let a = n.trunc(0);
-        let b = n.checked_sub(&a)?;
+        let b = n.checked_sub(a).ok_or(DecimalMathError::Overflow)?;
 
-        let power_a = x.pow_decimal_integral(&a)?;
-        let power_b = x.pow_decimal(&b)?;
+        let power_a = x.pow_decimal_integral(&a, digits)?;
+        let power_b = x.pow_decimal(&b, digits)?;
 
         // x^n = x^(a + b) = x^a * x^b
-        let result = power_a.checked_mul(&power_b)?;
-
-        Some(result)
+        let result = power_a.checked_mul(power_b).and_then(|r| r.round_to_digits(digits));
+        result.ok_or(DecimalMathError::Overflow)
     }
 
     /// Computes the natural logarithm of `self`,
     /// returning None if `self` is negative or `self == 0`.
     #[inline]
     pub fn ln(&self) -> Option<Decimal> {
+        self.ln_with_precision(MAX_PRECISION as u8)
+    }
+
+    /// Computes the natural logarithm of `self`,
+    /// returning [`DecimalMathError::DomainError`] if `self` is negative or `self == 0`.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, DecimalMathError};
+    ///
+    /// assert_eq!(Decimal::ONE.try_ln(), Ok(Decimal::ZERO));
+    /// assert_eq!(Decimal::ZERO.try_ln(), Err(DecimalMathError::DomainError));
+    /// ```
+    #[inline]
+    pub fn try_ln(&self) -> Result<Decimal, DecimalMathError> {
+        self.try_ln_with_precision(MAX_PRECISION as u8)
+    }
+
+    /// Computes the natural logarithm of `self` to `digits` significant digits,
+    /// returning None if `self` is negative or `self == 0`.
+    ///
+    /// The Taylor series stops once its terms fall below `10^-digits` relative to the sum,
+    /// which bounds the count of iterations. The result is rounded to `digits` significant digits,
+    /// and its error is less than one unit in the last digit.
+    ///
+    /// `digits` ranges from `[1, 38]`, and `ln_with_precision(38)` is the same as [`Decimal::ln`].
+    #[inline]
+    pub fn ln_with_precision(&self, digits: u8) -> Option<Decimal> {
+        self.try_ln_with_precision(digits).ok()
+    }
+
+    /// Computes the natural logarithm of `self` to `digits` significant digits,
+    /// returning [`DecimalMathError::DomainError`] if `self` is negative or `self == 0`.
+    #[inline]
+    fn try_ln_with_precision(&self, digits: u8) -> Result<Decimal, DecimalMathError> {
+        // ln(x) requires x > 0
+        if self.is_sign_negative() || self.is_zero() {
+            record_failure!(Ln, InvalidOperand, self, None);
+            return Err(DecimalMathError::DomainError);
+        }
+
+        self.ln_positive(digits).ok_or(DecimalMathError::Overflow)
+    }
+
+    /// Computes the natural logarithm of a positive `self` to `digits` significant digits.
+    #[inline]
+    fn ln_positive(&self, digits: u8) -> Option<Decimal> {
         const ZERO_POINT_ONE: Decimal = unsafe { Decimal::from_parts_unchecked(1, 1, false) };
         const ONE_POINT_ONE: Decimal = unsafe { Decimal::from_parts_unchecked(11, 1, false) };
         const TEN: Decimal = unsafe { Decimal::from_parts_unchecked(10, 0, false) };
         const LOWER_BOUND: Decimal = unsafe { Decimal::from_parts_unchecked(9047, 4, false) };
         // 1.2217
         const R: Decimal = unsafe { Decimal::from_parts_unchecked(12217, 4, false) };
-        const LN_10: Decimal =
-            unsafe { Decimal::from_parts_unchecked(23025850929940456840179914546843642076, 37, false) };
         // ln(1.2217)
         const LN_R: Decimal =
             unsafe { Decimal::from_parts_unchecked(2002433314278771112016301166984297937, 37, false) };
 
-        // ln(x) requires x > 0
-        if self.is_sign_negative() || self.is_zero() {
-            return None;
-        }
+        let digits = digits.max(1).min(MAX_PRECISION as u8);
 
         if *self == Decimal::ONE {
             return Some(Decimal::ZERO);
@@ -1908,70 +4591,240 @@ impl Decimal {
             if last == sum {
                 break;
             }
+
+            // the remaining terms are less than `term * y^2 / (1 - y^2)`, negligible at `digits`
+            if (digits as u32) < MAX_PRECISION && term.logb()? < sum.logb()? - digits as i16 {
+                break;
+            }
         }
 
         let ln_z = sum.checked_mul(&Decimal::TWO)?;
 
         // ln(x) = ln(z) + n1 * ln(10) + n2 * ln(R).
-        let mut result = ln_z.checked_add(&LN_10.checked_mul(&Decimal::from(n1))?)?;
+        let mut result = ln_z.checked_add(Decimal::LN_10.checked_mul(Decimal::from(n1))?)?;
         result = result.checked_add(&LN_R.checked_mul(&Decimal::from(n2))?)?;
-        Some(result)
+        result.round_to_digits(digits)
     }
 
-    /// Computes the nature exponential of `self`,
-    /// calculate with Taylor series, returning
-    /// None if the result overflowed.
+    /// Multiplies `self` by the 39-digit constant `(high * 10 + low) * 10^-scale`, rounding only once.
+    #[inline]
+    fn mul_constant(&self, high: u128, low: u128, scale: i16) -> Option<Decimal> {
+        // `self * (high * 10 + low)` would overflow, so divide it by 10,
+        // the dropped digit is far below the rounded one.
+        let product = U256::from(self.int_val) * high + U256::from(self.int_val) * low / 10;
+        Decimal::adjust_scale(product, self.scale + scale - 1, self.negative)
+    }
+
+    /// Computes the base 10 logarithm of `self`,
+    /// returning None if `self` is negative or `self == 0`.
+    ///
+    /// The result of an exact power of ten is exact, e.g. `1e37.log10() == 37`. Otherwise it's
+    /// computed as `e + ln(m) / ln(10)` for `self = m * 10^e`, from [`Decimal::ln`] and a precomputed
+    /// `1 / ln(10)`, so it isn't correctly rounded. The error of `ln(m)` carries over, and the absolute
+    /// error is less than `2E-37` plus half a unit in the last digit. That is up to about a hundred units
+    /// in the last digit for results close to zero, e.g. `1.1965.log10()` is 77 units off, and
+    /// `7.log10()` is 3 units off.
+    #[inline]
+    pub fn log10(&self) -> Option<Decimal> {
+        if self.is_sign_negative() || self.is_zero() {
+            record_failure!(Ln, InvalidOperand, self, None);
+            return None;
+        }
+
+        let (int_val, exp, _) = self.factor_pow10();
+        if int_val == 1 {
+            return Some(Decimal::from(exp));
+        }
+
+        // log10(x) = log10(m * 10^e) = e + ln(m) / ln(10), where m is in [1, 10),
+        // so the error of ln(10^e) is avoided, except in (0.1, 1) where the sum would cancel.
+        let e = self.logb()?;
+        let x = if e == -1 { *self } else { self.scaleb(-e)? };
+        // 1 / ln(10) = 0.434294481903251827651128918916605082294
+        let log_x = x.ln()?.mul_constant(43429448190325182765112891891660508229, 4, 39)?;
+        if e == -1 {
+            Some(log_x)
+        } else {
+            log_x.checked_add(Decimal::from(e))
+        }
+    }
+
+    /// Computes the base 2 logarithm of `self`,
+    /// returning None if `self` is negative or `self == 0`.
+    ///
+    /// The result of an integral power of two is exact, e.g. `1024.log2() == 10`. Otherwise it's
+    /// computed as `e * log2(10) + ln(m) / ln(2)` for `self = m * 10^e`, from [`Decimal::ln`] and
+    /// precomputed `1 / ln(2)` and `log2(10)`, so it isn't correctly rounded. The error of `ln(m)`
+    /// carries over, and the absolute error is less than `5E-37` plus one unit in the last digit,
+    /// e.g. `1.1965.log2()` is 25 units off.
+    #[inline]
+    pub fn log2(&self) -> Option<Decimal> {
+        if self.is_sign_negative() || self.is_zero() {
+            record_failure!(Ln, InvalidOperand, self, None);
+            return None;
+        }
+
+        let (int_val, exp, _) = self.factor_pow10();
+        if exp == 0 && int_val.is_power_of_two() {
+            return Some(Decimal::from(int_val.trailing_zeros()));
+        }
+
+        // log2(x) = log2(m * 10^e) = e * log2(10) + ln(m) / ln(2), where m is in [1, 10),
+        // except in (0.1, 10) where the sum would cancel.
+        let e = self.logb()?;
+        let x = if e == -1 { *self } else { self.scaleb(-e)? };
+        // 1 / ln(2) = 1.44269504088896340735992468100189213743
+        let log_x = x.ln()?.mul_constant(14426950408889634073599246810018921374, 3, 38)?;
+        if e == -1 || e == 0 {
+            Some(log_x)
+        } else {
+            // log2(10) = 3.32192809488736234787031942948939017586
+            let log_e = Decimal::from(e).mul_constant(33219280948873623478703194294893901758, 6, 38)?;
+            log_x.checked_add(log_e)
+        }
+    }
+
+    /// Computes the logarithm of `self` with respect to an arbitrary `base`,
+    /// returning None if `self` or `base` is negative or zero, or `base == 1`.
+    ///
+    /// A base of 10 or 2 is the same as [`Decimal::log10`] or [`Decimal::log2`], other bases are computed
+    /// as `ln(self) / ln(base)`.
+    #[inline]
+    pub fn log(&self, base: &Decimal) -> Option<Decimal> {
+        const TEN: Decimal = unsafe { Decimal::from_parts_unchecked(10, 0, false) };
+
+        if *base == TEN {
+            return self.log10();
+        }
+        if *base == Decimal::TWO {
+            return self.log2();
+        }
+
+        let ln_base = base.ln()?;
+        if ln_base.is_zero() {
+            return None;
+        }
+
+        self.ln()?.checked_div(ln_base)
+    }
+
+    /// Computes `e^self * 10^48`, requires `-1 < self < 1`.
+    ///
+    /// The Taylor series is accumulated in fixed point with 48 fractional digits,
+    /// so that the rounding errors of the terms don't reach the result digits, which the caller rounds.
+    /// If `digits` is less than 38, the series stops once the terms fall below `10^-(digits + 2)`.
     #[inline]
-    fn exp_decimal(&self) -> Option<Decimal> {
+    fn exp_fraction(&self, digits: u8) -> U256 {
+        const SCALE: usize = EXP_SCALE as usize;
+        const HALF_SCALE: usize = SCALE / 2;
+
+        debug_assert!(self.scale > MAX_PRECISION as i16 || self.int_val < POWERS_10[self.scale.max(0) as usize].low());
+
         // Taylor series:
         //   e^x = 1 + x + x^2 / 2! + x^3 / 3! + x^4 / 4! + ...
-        // Here use Taylor series to calculate e^x,
-        // start with the third term.
+        // |x| < 1, so every term is less than 10^SCALE in fixed point.
+        let x = if self.scale as usize <= SCALE {
+            POWERS_10[SCALE - self.scale as usize] * self.int_val
+        } else if self.scale as usize - SCALE <= MAX_PRECISION as usize {
+            let e = self.scale as usize - SCALE;
+            U256::from((self.int_val + ROUNDINGS[e].low()) / POWERS_10[e].low())
+        } else {
+            U256::from(0u128)
+        };
+
+        // x * term / 10^SCALE would overflow U256, so split x in two halves.
+        let x_high = (x / POWERS_10[HALF_SCALE].low()).low();
+        let x_low = (x % POWERS_10[HALF_SCALE].low()).low();
+
+        let mut positive = POWERS_10[SCALE];
+        let mut negative = U256::from(0u128);
+        if self.negative {
+            negative = x;
+        } else {
+            positive = positive + x;
+        }
+
+        // e^x > 0.36, and the remaining terms are less than the last one,
+        // so stopping at `10^-(digits + 2)` keeps the relative error below `10^-digits / 30`.
+        let threshold = if (digits as u32) < MAX_PRECISION {
+            POWERS_10[SCALE - digits as usize - 2]
+        } else {
+            U256::from(1u128)
+        };
 
-        let x = *self;
         let mut term = x;
-        let mut sum = Decimal::ONE.checked_add(&x)?;
-        let mut last;
-        let mut iter = 1;
+        let mut iter = 1u128;
         loop {
             iter += 1;
 
-            // Calculate latter term from former term by multiplying x over iter,
-            // Divide first then multiply to avoid the intermediate process to cross the boundary.
-            term = term.checked_div(&Decimal::from(iter))?.checked_mul(&x)?;
-
-            if term.is_zero() {
+            term = (term * x_high / POWERS_10[HALF_SCALE].low() + term * x_low / POWERS_10[SCALE]) / iter;
+            if term < threshold {
                 break;
             }
 
-            last = sum;
-            sum = sum.checked_add(&term)?;
-
-            if last == sum {
-                break;
+            if self.negative && iter % 2 == 1 {
+                negative = negative + term;
+            } else {
+                positive = positive + term;
             }
         }
 
-        Some(sum)
+        positive.checked_sub(negative).expect("e^x is positive")
     }
 
     /// Computes the nature exponential of `self`,
     /// returning None if the result overflowed.
     #[inline]
     pub fn exp(&self) -> Option<Decimal> {
-        // same as Oracle: e^291 will overflow, e^-300 is 0
+        self.exp_with_precision(MAX_PRECISION as u8)
+    }
+
+    /// Computes the nature exponential of `self`, returning [`DecimalMathError::Overflow`] if the result
+    /// is too large or [`DecimalMathError::Underflow`] if it's too small to be represented, where
+    /// [`Decimal::exp`] returns zero.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, DecimalMathError};
+    ///
+    /// assert_eq!(Decimal::from(300).try_exp(), Err(DecimalMathError::Overflow));
+    /// assert_eq!(Decimal::from(-301).try_exp(), Err(DecimalMathError::Underflow));
+    /// assert_eq!(Decimal::from(-301).exp(), Some(Decimal::ZERO));
+    /// ```
+    #[inline]
+    pub fn try_exp(&self) -> Result<Decimal, DecimalMathError> {
+        self.try_exp_with_precision(MAX_PRECISION as u8)
+    }
+
+    /// Computes the nature exponential of `self` to `digits` significant digits,
+    /// returning None if the result overflowed.
+    ///
+    /// The Taylor series stops once its terms fall below `10^-(digits + 2)`,
+    /// which bounds the count of iterations. The result is rounded to `digits` significant digits,
+    /// and its error is less than one unit in the last digit.
+    ///
+    /// `digits` ranges from `[1, 38]`, and `exp_with_precision(38)` is the same as [`Decimal::exp`].
+    #[inline]
+    pub fn exp_with_precision(&self, digits: u8) -> Option<Decimal> {
+        underflow_to_zero(self.try_exp_with_precision(digits))
+    }
+
+    /// Computes the nature exponential of `self` to `digits` significant digits,
+    /// returning an error if the result overflowed or underflowed.
+    #[inline]
+    fn try_exp_with_precision(&self, digits: u8) -> Result<Decimal, DecimalMathError> {
+        // same as Oracle: e^291 will overflow, e^-300 is too small to be represented
         const UPPER_BOUND: Decimal = unsafe { Decimal::from_parts_unchecked(291, 0, false) };
         const LOWER_BOUND: Decimal = unsafe { Decimal::from_parts_unchecked(300, 0, true) };
 
         if self.is_zero() {
-            return Some(Decimal::ONE);
+            return Ok(Decimal::ONE);
         }
         if *self >= UPPER_BOUND {
-            // overflow
-            return None;
+            record_failure!(Exp, Overflow, self, None);
+            return Err(DecimalMathError::Overflow);
         }
         if *self <= LOWER_BOUND {
-            return Some(Decimal::ZERO);
+            return Err(DecimalMathError::Underflow);
         }
 
         // Taylor series:
@@ -1984,38 +4837,153 @@ impl Decimal {
         // to reduce input into range -1 < b < 1 by getting rid of the integer part of x.
         //
         // Here use look-up table to get e^a,
-        // calculate e^a in advance when testing by using Taylor series,
-        // put it into array `NATURAL_EXP` and `NATURAL_EXP_NEG`.
+        // the values of e^a to 57 significant digits are put into arrays `NATURAL_EXP` and `NATURAL_EXP_LOW`.
         //
         // Here use Taylor series to calculate e^b,
         // b is the fraction part of x, so b is in (-1, 1)(this range approaches 0).
 
-        let x = *self;
-        let a = x.trunc(0);
-        let b = x.checked_sub(&a)?;
+        let digits = digits.max(1).min(MAX_PRECISION as u8);
+        let x = *self;
+        // `trunc` keeps a negative scale, e.g. the one of `1E+1`, so rescale `a` to index the table.
+        let a = x.trunc(0).normalize_to_scale(0);
+        let b = x.checked_sub(a).ok_or(DecimalMathError::Overflow)?;
+
+        // e^b * 10^48
+        let exp_b = if b.is_zero() {
+            POWERS_10[EXP_SCALE as usize]
+        } else {
+            b.exp_fraction(digits)
+        };
+
+        // e^x = e^(a + b) = e^a * e^b, where the extra digits are kept until the result is rounded once.
+        let n = a.int_val as usize;
+        let (val, scale) = if n == 0 {
+            (exp_b, EXP_SCALE)
+        } else if a.is_sign_positive() {
+            let (high, low, scale) = natural_exp_wide(n);
+            let exp_b = Uint::<8>::from_u256(exp_b);
+            let mut product = exp_b
+                .checked_mul(high)
+                .and_then(|p| p.checked_mul(POWERS_10[19].low()))
+                .expect("106 digits at most")
+                .add(&exp_b.checked_mul(low as u128).expect("68 digits at most"));
+            product.div_pow10(40);
+            (product.to_u256().expect("66 digits at most"), EXP_SCALE + scale - 40)
+        } else if n < UPPER_BOUND.int_val as usize {
+            // e^|a| won't overflow, so e^x = e^b / e^|a|
+            div_natural_exp(exp_b, EXP_SCALE, n)
+        } else {
+            // e^x = e^b / e^290 / e^(|a| - 290)
+            let (val, scale) = div_natural_exp(exp_b, EXP_SCALE, 290);
+            div_natural_exp(val, scale, n - 290)
+        };
+
+        // rounds half up to `digits` significant digits
+        let count = val.count_digits();
+        let (mut val, mut scale) = if count > digits as u32 {
+            let e = count - digits as u32;
+            let (truncated, _) = val.div_rem(POWERS_10[e as usize - 1]);
+            ((truncated + 5u128) / 10u128, scale - e as i16)
+        } else {
+            (val, scale)
+        };
+        // rounding may carry into one more digit, e.g. 9.99 -> 10.0
+        if val == POWERS_10[digits as usize] {
+            val = POWERS_10[digits as usize - 1];
+            scale -= 1;
+        }
+
+        match Decimal::adjust_scale(val, scale, false) {
+            Some(r) if r.is_zero() => Err(DecimalMathError::Underflow),
+            Some(r) => Ok(r),
+            None => {
+                record_failure!(Exp, Overflow, self, None);
+                Err(DecimalMathError::Overflow)
+            }
+        }
+    }
+}
+
+/// Returns `e^n * 10^(s + 19)` to 57 significant digits as `(high, low, s + 19)`, where the value is
+/// `high * 10^19 + low` and `s` is the scale of `NATURAL_EXP[n]`.
+#[inline]
+fn natural_exp_wide(n: usize) -> (u128, u64, i16) {
+    let exp = NATURAL_EXP[n];
+    let low = NATURAL_EXP_LOW[n];
+    if low < 0 {
+        (
+            exp.int_val - 1,
+            (POWERS_10[19].low() - low.unsigned_abs() as u128) as u64,
+            exp.scale + 19,
+        )
+    } else {
+        (exp.int_val, low as u64, exp.scale + 19)
+    }
+}
 
-        let exp_a = if a.is_sign_positive() {
-            NATURAL_EXP[a.int_val as usize]
-        } else if a.int_val < UPPER_BOUND.int_val {
-            // e^|a| won't overflow
-            Decimal::ONE.checked_div(&NATURAL_EXP[a.int_val as usize])?
-        } else {
-            // e^|a| will overflow
-            NATURAL_EXP_NEG[(a.int_val - UPPER_BOUND.int_val) as usize]
-        };
+/// Divides `val * 10^-scale` by `e^n`, where `val < 10^57`, returning the quotient truncated to
+/// at least 47 significant digits as `(val, scale)`.
+#[inline]
+fn div_natural_exp(val: U256, scale: i16, n: usize) -> (U256, i16) {
+    let (high, low, divisor_scale) = natural_exp_wide(n);
+    let divisor = POWERS_10[19] * high + low as u128;
+
+    // long division by 19 digits at a time, so that the partial dividends fit in `U256`
+    let (mut quotient, mut rem) = val.div_rem(divisor);
+    for _ in 0..3 {
+        let (q, r) = (rem * POWERS_10[19].low()).div_rem(divisor);
+        quotient = quotient * POWERS_10[19].low() + q.low();
+        rem = r;
+    }
 
-        let exp_b = if b.is_zero() {
-            // e^0 = 1, so e^x = e^a.
-            return Some(exp_a);
-        } else {
-            b.exp_decimal()?
-        };
+    (quotient, scale + 57 - divisor_scale)
+}
+
+/// Converts the result of a mathematical function to the one of the `Option` API, where a result
+/// too small to be represented is zero.
+#[inline]
+fn underflow_to_zero(result: Result<Decimal, DecimalMathError>) -> Option<Decimal> {
+    match result {
+        Ok(val) => Some(val),
+        Err(DecimalMathError::Underflow) => Some(Decimal::ZERO),
+        Err(_) => None,
+    }
+}
 
-        // e^x = e^(a + b) = e^a * e^b
-        let result = exp_a.checked_mul(&exp_b)?;
+/// Removes the trailing zeros of a non-zero `val`, returning the stripped value and the count of zeros.
+///
+/// The count is found by a binary search over the powers of ten, since it's less than 64.
+#[inline]
+pub(crate) fn strip_trailing_zeros(mut val: u128) -> (u128, u32) {
+    debug_assert_ne!(val, 0);
+
+    let mut zeros = 0;
+    for &k in [32, 16, 8, 4, 2, 1].iter() {
+        let divisor = POWERS_10[k as usize].low();
+        if val.is_multiple_of(divisor) {
+            val /= divisor;
+            zeros += k;
+        }
+    }
+    (val, zeros)
+}
 
-        Some(result)
+/// Computes the integer `n`-th root of `val`, i.e. the largest integer whose `n`-th power is at most `val`.
+#[inline]
+fn int_root(val: u128, n: u32) -> u128 {
+    debug_assert!(n >= 2);
+
+    // `val < 2^128`, so the root is less than `2^ceil(128 / n)`.
+    let mut low = 0u128;
+    let mut high = 1u128 << 128u32.div_ceil(n).min(64);
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        match mid.checked_pow(n) {
+            Some(p) if p <= val => low = mid,
+            _ => high = mid,
+        }
     }
+    low
 }
 
 trait WriteExt: fmt::Write {
@@ -2026,9 +4994,21 @@ trait WriteExt: fmt::Write {
 
     #[inline(always)]
     fn write_bytes(&mut self, bytes: &[u8]) -> fmt::Result {
-        let s = unsafe { std::str::from_utf8_unchecked(bytes) };
+        let s = unsafe { core::str::from_utf8_unchecked(bytes) };
         self.write_str(s)
     }
+
+    /// Writes `n` zeros in chunks, so any length is fine.
+    #[inline]
+    fn write_zeros(&mut self, mut n: usize) -> fmt::Result {
+        const ZEROS: [u8; 64] = [b'0'; 64];
+
+        while n > ZEROS.len() {
+            self.write_bytes(&ZEROS)?;
+            n -= ZEROS.len();
+        }
+        self.write_bytes(&ZEROS[..n])
+    }
 }
 
 impl<W: fmt::Write> WriteExt for W {}
@@ -2074,14 +5054,92 @@ impl AsRef<Decimal> for Decimal {
 impl fmt::Display for Decimal {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // A large precision may not fit in the buffer, e.g. `{:.300}`.
+        if matches!(f.precision(), Some(prec) if MAX_FMT_LEN + 1 + prec > BUF_CAPACITY) {
+            let mut str = String::new();
+            self.fmt_internal(false, false, false, f.precision(), &mut str)
+                .expect("failed to format decimal");
+            return f.pad_integral(self.is_sign_positive(), "", &str);
+        }
+
         let mut buf = Buf::new();
         self.fmt_internal(false, false, false, f.precision(), &mut buf)
             .expect("failed to format decimal");
-        let str = unsafe { std::str::from_utf8_unchecked(buf.as_slice()) };
+        let str = unsafe { core::str::from_utf8_unchecked(buf.as_slice()) };
         f.pad_integral(self.is_sign_positive(), "", str)
     }
 }
 
+/// Formats in scientific notation like the primitive floats, e.g. `1.2856e4`, and `0e0` for zero.
+///
+/// A precision, e.g. `{:.3e}`, is the count of fractional digits, rounded half away from zero.
+impl fmt::LowerExp for Decimal {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_exp(false, f)
+    }
+}
+
+/// Formats in scientific notation like [`fmt::LowerExp`], but with an upper case `E`.
+impl fmt::UpperExp for Decimal {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_exp(true, f)
+    }
+}
+
+/// Formats the integral value, rounded half away from zero, in hexadecimal with a `-` sign if it's
+/// negative, e.g. `-ff` for `-255.4`. The alternate flag `#` adds the `0x` prefix.
+impl fmt::LowerHex for Decimal {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_radix(4, false, "0x", f)
+    }
+}
+
+/// Formats as [`fmt::LowerHex`] does, but with upper case digits.
+impl fmt::UpperHex for Decimal {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_radix(4, true, "0x", f)
+    }
+}
+
+/// Formats as [`fmt::LowerHex`] does, but in octal with the `0o` prefix.
+impl fmt::Octal for Decimal {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_radix(3, false, "0o", f)
+    }
+}
+
+/// Formats as [`fmt::LowerHex`] does, but in binary with the `0b` prefix.
+impl fmt::Binary for Decimal {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_radix(1, false, "0b", f)
+    }
+}
+
+impl fmt::Debug for Decimal {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (int_val, scale, negative) = self.into_parts();
+        if f.alternate() {
+            f.debug_struct("Decimal")
+                .field("int_val", &int_val)
+                .field("scale", &scale)
+                .field("negative", &negative)
+                .finish()
+        } else {
+            f.write_str("Decimal(\"")?;
+            self.fmt_internal(true, false, true, None, &mut *f)
+                .map_err(|_| fmt::Error)?;
+            write!(f, "\", scale={})", scale)
+        }
+    }
+}
+
 impl Default for Decimal {
     #[inline]
     fn default() -> Self {
@@ -2187,11 +5245,24 @@ impl Hash for Decimal {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::Lcg;
 
     #[test]
     fn test_decimal_repr() {
         assert_eq!(std::mem::size_of::<Decimal>(), 20);
         assert_eq!(std::mem::align_of::<Decimal>(), 4);
+        assert_eq!(Decimal::SIZE_BYTES, 20);
+        assert_eq!(Decimal::ALIGN_BYTES, 4);
+
+        // a slice of decimals has no padding
+        assert_eq!(std::mem::size_of::<[Decimal; 3]>(), 3 * Decimal::SIZE_BYTES);
+
+        // the parts round trip whatever the representation
+        for val in crate::testing::corpus() {
+            let (int_val, scale, negative) = val.into_parts();
+            let back = Decimal::from_parts(int_val, scale, negative).unwrap();
+            assert_eq!(back.into_parts(), val.into_parts());
+        }
     }
 
     #[test]
@@ -2257,6 +5328,271 @@ mod tests {
         assert_display!(101, 98, false, "{:.10}", "0.0000000000");
     }
 
+    #[test]
+    fn test_lower_upper_exp() {
+        fn assert_exp(s: &str) {
+            let dec = s.parse::<Decimal>().unwrap();
+            let float = s.parse::<f64>().unwrap();
+            assert_eq!(format!("{:e}", dec), format!("{:e}", float), "{}", s);
+            assert_eq!(format!("{:E}", dec), format!("{:E}", float), "{}", s);
+            assert_eq!(format!("{:+e}", dec), format!("{:+e}", float), "{}", s);
+            assert_eq!(format!("{:>14e}", dec), format!("{:>14e}", float), "{}", s);
+            assert_eq!(format!("{:*<14E}", dec), format!("{:*<14E}", float), "{}", s);
+            assert_eq!(format!("{:014e}", dec), format!("{:014e}", float), "{}", s);
+            for &prec in [0, 1, 3, 6, 10].iter() {
+                assert_eq!(
+                    format!("{:.*e}", prec, dec),
+                    format!("{:.*e}", prec, float),
+                    "{} {}",
+                    s,
+                    prec
+                );
+                assert_eq!(
+                    format!("{:+012.*E}", prec, dec),
+                    format!("{:+012.*E}", prec, float),
+                    "{} {}",
+                    s,
+                    prec
+                );
+            }
+        }
+
+        assert_exp("0");
+        assert_exp("1");
+        assert_exp("-1");
+        assert_exp("12856");
+        assert_exp("-12856");
+        assert_exp("1.2856");
+        assert_exp("128.56");
+        assert_exp("0.0012856");
+        assert_exp("-0.00000012856");
+        assert_exp("1200");
+        assert_exp("1.200");
+        assert_exp("10");
+        assert_exp("0.1");
+        assert_exp("9.9999");
+        assert_exp("-99.96");
+        assert_exp("0.000999");
+        assert_exp("123456789.987654");
+        assert_exp("1e100");
+        assert_exp("-3.7e-100");
+        assert_exp("4.94065645841247E-126");
+        assert_exp("1e-130");
+
+        let dec = "12856".parse::<Decimal>().unwrap();
+        assert_eq!(format!("{:e}", dec), "1.2856e4");
+        assert_eq!(format!("{:E}", dec), "1.2856E4");
+        assert_eq!(format!("{:.3e}", dec), "1.286e4");
+        assert_eq!(format!("{:e}", Decimal::ZERO), "0e0");
+        assert_eq!(format!("{:.2e}", Decimal::ZERO), "0.00e0");
+        assert_eq!(
+            format!("{:e}", Decimal::MAX),
+            "9.9999999999999999999999999999999999999e125"
+        );
+        assert_eq!(format!("{:.1e}", Decimal::MAX), "1.0e126");
+        assert_eq!(format!("{:e}", Decimal::MIN_POSITIVE), "1e-130");
+        assert_eq!(
+            format!(
+                "{:e}",
+                "-0.99999999999999999999999999999999999999".parse::<Decimal>().unwrap()
+            ),
+            "-9.9999999999999999999999999999999999999e-1"
+        );
+
+        // rounds half away from zero, unlike the floats
+        assert_eq!(format!("{:.1e}", "0.125".parse::<Decimal>().unwrap()), "1.3e-1");
+        assert_eq!(format!("{:.0e}", "-25".parse::<Decimal>().unwrap()), "-3e1");
+        assert_eq!(format!("{:.0e}", "95".parse::<Decimal>().unwrap()), "1e2");
+
+        let s = format!("{:.300e}", dec);
+        assert_eq!(s.len(), 2 + 300 + 2);
+        assert!(s.starts_with("1.2856000") && s.ends_with("000e4"));
+    }
+
+    #[test]
+    fn test_fmt_radix() {
+        fn assert_radix(s: &str, int: i128) {
+            let dec = s.parse::<Decimal>().unwrap();
+            assert_eq!(
+                format!("{:x}", dec),
+                format!("{}{:x}", if int < 0 { "-" } else { "" }, int.unsigned_abs()),
+                "{}",
+                s
+            );
+            assert_eq!(
+                format!("{:X}", dec),
+                format!("{}{:X}", if int < 0 { "-" } else { "" }, int.unsigned_abs()),
+                "{}",
+                s
+            );
+            assert_eq!(
+                format!("{:o}", dec),
+                format!("{}{:o}", if int < 0 { "-" } else { "" }, int.unsigned_abs()),
+                "{}",
+                s
+            );
+            assert_eq!(
+                format!("{:b}", dec),
+                format!("{}{:b}", if int < 0 { "-" } else { "" }, int.unsigned_abs()),
+                "{}",
+                s
+            );
+            if int >= 0 {
+                assert_eq!(format!("{:08X}", dec), format!("{:08X}", int), "{}", s);
+                assert_eq!(format!("{:#010x}", dec), format!("{:#010x}", int), "{}", s);
+                assert_eq!(format!("{:#o}", dec), format!("{:#o}", int), "{}", s);
+                assert_eq!(format!("{:#b}", dec), format!("{:#b}", int), "{}", s);
+                assert_eq!(format!("{:>8x}", dec), format!("{:>8x}", int), "{}", s);
+                assert_eq!(format!("{:+x}", dec), format!("+{:x}", int), "{}", s);
+            }
+            if dec.is_sign_positive() {
+                let mut hex = String::new();
+                dec.format_to_hex(false, &mut hex).unwrap();
+                assert_eq!(format!("{:x}", dec), hex, "{}", s);
+            }
+        }
+
+        assert_radix("0", 0);
+        assert_radix("1", 1);
+        assert_radix("255", 255);
+        assert_radix("255.4", 255);
+        assert_radix("255.5", 256);
+        assert_radix("0.4", 0);
+        assert_radix("-0.4", 0);
+        assert_radix("-0.5", -1);
+        assert_radix("-255.4", -255);
+        assert_radix("48879", 0xbeef);
+        assert_radix("1e20", 100_000_000_000_000_000_000);
+        assert_radix("-1e20", -100_000_000_000_000_000_000);
+        assert_radix(
+            "99999999999999999999999999999999999999",
+            99_999_999_999_999_999_999_999_999_999_999_999_999,
+        );
+        assert_radix("85070591730234615865843651857942052863", (1 << 126) - 1);
+
+        let dec = "-255".parse::<Decimal>().unwrap();
+        assert_eq!(format!("{:08X}", dec), "-00000FF");
+        assert_eq!(format!("{:#08x}", dec), "-0x000ff");
+        assert_eq!(format!("{:>6X}", dec), "   -FF");
+
+        let dec = "7e75".parse::<Decimal>().unwrap();
+        let mut hex = String::new();
+        dec.format_to_hex(true, &mut hex).unwrap();
+        assert_eq!(format!("{:X}", dec), hex);
+        assert_eq!(hex, "F79DC0E8C518F31EB934B4522AD36A1D39F275C35E858000000000000000000");
+
+        assert_eq!(
+            format!("{:#x}", Decimal::MAX),
+            "0x5e8bb3105280fdffdb2872d49de1ec4ae8a66896556419e8537917130940e76fa77c22c040fdcc2935f0000000000000000000000"
+        );
+        assert_eq!(
+            format!("{:X}", Decimal::MIN),
+            "-5E8BB3105280FDFFDB2872D49DE1EC4AE8A66896556419E8537917130940E76FA77C22C040FDCC2935F0000000000000000000000"
+        );
+        assert_eq!(
+            format!("{:o}", Decimal::MAX),
+            "27505663040512007737773312071324473607542256424632113125310147502467442704604500716676473702130020176714122327600000000000000000000000000000"
+        );
+        let bin = format!("{:b}", Decimal::MAX);
+        assert_eq!(bin.len(), 419);
+        assert!(bin.starts_with("10111101") && bin.ends_with(&"0".repeat(88)));
+        assert_eq!(format!("{:x}", Decimal::MIN_POSITIVE), "0");
+    }
+
+    #[test]
+    fn test_display_len() {
+        fn assert_len(val: &Decimal) {
+            assert_eq!(val.display_len(), val.to_string().len(), "{:?}", val);
+            // `u16::MAX` is the largest precision of a format string, and beyond `i16::MAX`
+            for &prec in [0, 1, 2, 3, 5, 10, 38, 40, 100, 130, 200, 300, 40000, u16::MAX as usize].iter() {
+                assert_eq!(
+                    val.display_len_with_precision(prec),
+                    format!("{:.*}", prec, val).len(),
+                    "{:?} {}",
+                    val,
+                    prec
+                );
+            }
+            // saturates rather than overflows
+            let huge = val.display_len_with_precision(usize::MAX);
+            assert!(huge >= val.display_len_with_precision(u16::MAX as usize), "{:?}", val);
+        }
+
+        let mut values = crate::testing::corpus();
+        values.extend(crate::testing::corpus_random(0xd15, 2000));
+        // the parts which aren't normalized, e.g. "0.0100"
+        values.extend(
+            [
+                (100, 4),
+                (1500, 3),
+                (10, 1),
+                (99995, 3),
+                (5, 1),
+                (95, 2),
+                (1, 167),
+                (MAX_I128_REPR as u128, -126),
+            ]
+            .iter()
+            .map(|&(int_val, scale)| Decimal::from_parts(int_val, scale, false).unwrap()),
+        );
+        for val in values.iter() {
+            assert_len(val);
+            assert_len(&-val);
+        }
+    }
+
+    #[test]
+    fn test_debug() {
+        fn assert_debug(num: u128, scale: i16, negative: bool, expected: &str, expected_alternate: &str) {
+            let dec = Decimal::from_parts(num, scale, negative).unwrap();
+            assert_eq!(format!("{:?}", dec), expected);
+            assert_eq!(format!("{:#?}", dec), expected_alternate);
+        }
+
+        assert_debug(
+            1234500,
+            4,
+            false,
+            r#"Decimal("123.45", scale=4)"#,
+            "Decimal {\n    int_val: 1234500,\n    scale: 4,\n    negative: false,\n}",
+        );
+        assert_debug(
+            12345,
+            2,
+            true,
+            r#"Decimal("-123.45", scale=2)"#,
+            "Decimal {\n    int_val: 12345,\n    scale: 2,\n    negative: true,\n}",
+        );
+        assert_debug(
+            0,
+            5,
+            true,
+            r#"Decimal("0", scale=0)"#,
+            "Decimal {\n    int_val: 0,\n    scale: 0,\n    negative: false,\n}",
+        );
+        assert_debug(
+            15,
+            -3,
+            false,
+            r#"Decimal("15000", scale=-3)"#,
+            "Decimal {\n    int_val: 15,\n    scale: -3,\n    negative: false,\n}",
+        );
+        assert_debug(
+            1,
+            130,
+            true,
+            r#"Decimal("-0.0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001", scale=130)"#,
+            "Decimal {\n    int_val: 1,\n    scale: 130,\n    negative: true,\n}",
+        );
+        assert_debug(
+            MAX_I128_REPR as u128,
+            -88,
+            false,
+            r#"Decimal("999999999999999999999999999999999999990000000000000000000000000000000000000000000000000000000000000000000000000000000000000000", scale=-88)"#,
+            "Decimal {\n    int_val: 99999999999999999999999999999999999999,\n    scale: -88,\n    negative: false,\n}",
+        );
+    }
+
     #[test]
     fn test_precision() {
         fn assert_precision(val: &str, expected: u8) {
@@ -2274,6 +5610,78 @@ mod tests {
         assert_precision("99999999999999999999999999999999999999", 38);
     }
 
+    #[test]
+    fn test_leading_digit() {
+        fn assert_leading_digit(val: &str, expected: Option<u8>) {
+            let dec = val.parse::<Decimal>().unwrap();
+            assert_eq!(dec.leading_digit(), expected);
+        }
+
+        assert_leading_digit("0", None);
+        assert_leading_digit("-0.000", None);
+        assert_leading_digit("1", Some(1));
+        assert_leading_digit("10", Some(1));
+        assert_leading_digit("-0.0314", Some(3));
+        assert_leading_digit("987.6", Some(9));
+        assert_leading_digit("1E-130", Some(1));
+        assert_leading_digit("-2E-130", Some(2));
+        assert_leading_digit("9.9999999999999999999999999999999999999E125", Some(9));
+        assert_leading_digit("12345678901234567890123456789012345678", Some(1));
+        assert_leading_digit("0.00098765432109876543210987654321098765432", Some(9));
+
+        for val in crate::testing::corpus() {
+            let expected = val
+                .to_string()
+                .trim_start_matches(&['-', '0', '.'][..])
+                .bytes()
+                .next()
+                .map(|b| b - b'0');
+            assert_eq!(val.leading_digit(), expected, "{}", val);
+        }
+    }
+
+    #[test]
+    fn test_digit_at() {
+        fn assert_digit_at(val: &str, pos: i16, expected: u8) {
+            let dec = val.parse::<Decimal>().unwrap();
+            assert_eq!(dec.digit_at(pos), expected);
+        }
+
+        assert_digit_at("0", 0, 0);
+        assert_digit_at("-123.45", 3, 0);
+        assert_digit_at("-123.45", 2, 1);
+        assert_digit_at("-123.45", 1, 2);
+        assert_digit_at("-123.45", 0, 3);
+        assert_digit_at("-123.45", -1, 4);
+        assert_digit_at("-123.45", -2, 5);
+        assert_digit_at("-123.45", -3, 0);
+        assert_digit_at("1E-130", -130, 1);
+        assert_digit_at("1E-130", -129, 0);
+        assert_digit_at("1E-130", i16::MIN, 0);
+        assert_digit_at("1E125", 125, 1);
+        assert_digit_at("1E125", 124, 0);
+        assert_digit_at("1E125", i16::MAX, 0);
+        assert_digit_at("12345678901234567890123456789012345678", 37, 1);
+        assert_digit_at("12345678901234567890123456789012345678", 38, 0);
+        assert_digit_at("12345678901234567890123456789012345678", 0, 8);
+        assert_digit_at("12345678901234567890123456789012345678", -1, 0);
+        assert_digit_at("0.12345678901234567890123456789012345678", -38, 8);
+        assert_digit_at("0.12345678901234567890123456789012345678", -39, 0);
+
+        for val in crate::testing::corpus() {
+            let exp = match val.logb() {
+                Some(exp) => exp,
+                None => continue,
+            };
+            let digits: Vec<u8> = (0..val.precision() as i16).map(|i| val.digit_at(exp - i)).collect();
+            let (int_val, _, _) = val.into_parts();
+            let expected: Vec<u8> = int_val.to_string().bytes().map(|b| b - b'0').collect();
+            assert_eq!(digits, expected, "{}", val);
+            assert_eq!(val.digit_at(exp + 1), 0);
+        }
+    }
+
+    #[cfg(feature = "std")]
     #[test]
     fn test_encoding() {
         fn assert_encoding(num: &str) {
@@ -2297,6 +5705,15 @@ mod tests {
                 let decoded_num = Decimal::decode(&buf);
                 assert_eq!(decoded_num, num);
             }
+
+            // Fixed-size encode
+            {
+                let bytes = num.to_bytes();
+                assert_eq!(&bytes[..buf.len()], buf.as_slice());
+                assert!(bytes[buf.len()..].iter().all(|&b| b == 0));
+                assert_eq!(Decimal::from_bytes(&bytes).into_parts(), num.into_parts());
+                assert_eq!(Decimal::try_decode(&bytes), Ok(num));
+            }
         }
 
         assert_encoding("0");
@@ -2315,6 +5732,91 @@ mod tests {
         assert_encoding("-99999999999999999999999999999999999999");
         assert_encoding("184467440.73709551615");
         assert_encoding("-184467440.73709551615");
+
+        for num in crate::testing::corpus().iter() {
+            assert_encoding(&num.to_string());
+        }
+
+        assert_eq!(Decimal::ZERO.to_bytes(), [0; MAX_BINARY_SIZE]);
+        assert_eq!(
+            (-Decimal::MIN_POSITIVE).to_bytes()[..3],
+            [SIGN_MASK | SCALE_MASK, 130, 1]
+        );
+    }
+
+    #[test]
+    fn test_encode_to_slice() {
+        fn assert_encode_to_slice(num: &str) {
+            let num = num.parse::<Decimal>().unwrap();
+            let mut buf = [0; MAX_BINARY_SIZE];
+
+            let size = num.compact_encode_to_slice(&mut buf).unwrap();
+            assert_eq!(Decimal::decode(&buf[..size]).into_parts(), num.into_parts());
+            assert_eq!(num.compact_encode_to_slice(&mut buf[..size - 1]), None);
+
+            let size = num.encode_to_slice(&mut buf).unwrap();
+            assert_eq!(buf[..size], num.to_bytes()[..size]);
+            assert_eq!(Decimal::decode(&buf[..size]).into_parts(), num.into_parts());
+            assert_eq!(num.encode_to_slice(&mut buf[..size - 1]), None);
+        }
+
+        assert_encode_to_slice("0");
+        assert_encode_to_slice("-255");
+        assert_encode_to_slice("65536");
+        assert_encode_to_slice("-18446744073709551615");
+        assert_encode_to_slice("99999999999999999999999999999999999999");
+        assert_encode_to_slice("-184467440.73709551615");
+        assert_encode_to_slice("1e-130");
+        assert_encode_to_slice("-9.9e125");
+    }
+
+    #[test]
+    fn test_try_decode() {
+        fn assert_decode(bytes: &[u8], expected: Result<(u128, i16, bool), DecimalConvertError>) {
+            assert_eq!(Decimal::try_decode(bytes).map(|d| d.into_parts()), expected);
+        }
+
+        assert_decode(&[], Err(DecimalConvertError::Invalid));
+        assert_decode(&[7], Ok((7, 0, false)));
+        assert_decode(&[0xff, 0xff], Ok((65535, 0, false)));
+        assert_decode(&[SIGN_MASK | SCALE_MASK, 2, 15], Ok((15, 2, true)));
+        assert_decode(&[0, 126, 1], Ok((1, -126, false)));
+        assert_decode(&[SCALE_MASK, 167, 1], Ok((1, 167, false)));
+        assert_decode(&[SIGN_MASK, 3, 0], Ok((0, 0, false)));
+
+        // unknown flags
+        assert_decode(&[0x04, 0, 1], Err(DecimalConvertError::Invalid));
+        assert_decode(&[0x80 | SIGN_MASK, 0, 1], Err(DecimalConvertError::Invalid));
+        // scale out of range
+        assert_decode(&[0, 127, 1], Err(DecimalConvertError::ScaleOutOfRange));
+        assert_decode(&[SCALE_MASK, 168, 1], Err(DecimalConvertError::ScaleOutOfRange));
+        assert_decode(&[SCALE_MASK, 255, 0], Err(DecimalConvertError::ScaleOutOfRange));
+        // int_val above `MAX_I128_REPR`
+        let mut bytes = Decimal::MAX.to_bytes();
+        assert!(Decimal::try_decode(&bytes).is_ok());
+        bytes[2..].copy_from_slice(&(MAX_I128_REPR as u128 + 1).to_le_bytes());
+        assert_eq!(Decimal::try_decode(&bytes), Err(DecimalConvertError::Overflow));
+        assert_decode(
+            &[
+                0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            ],
+            Err(DecimalConvertError::Overflow),
+        );
+
+        // longer than `MAX_BINARY_SIZE`
+        let mut bytes = "-1.5".parse::<Decimal>().unwrap().to_bytes().to_vec();
+        assert_decode(&bytes, Ok((15, 1, true)));
+        bytes.push(0);
+        assert_decode(&bytes, Err(DecimalConvertError::Invalid));
+        // `decode` ignores the bytes past `MAX_BINARY_SIZE`
+        bytes.push(0xff);
+        assert_eq!(Decimal::decode(&bytes).into_parts(), (15, 1, true));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid decimal bytes")]
+    fn test_decode_invalid() {
+        Decimal::decode(&[0, 127, 1]);
     }
 
     #[test]
@@ -2324,6 +5826,7 @@ mod tests {
                 let l = $left.parse::<Decimal>().unwrap();
                 let r = $right.parse::<Decimal>().unwrap();
                 assert!(l $cmp r, "{} {} {}", l, stringify!($cmp),r);
+                assert_eq!(l.cmp_i8(&r), l.cmp(&r) as i8);
             }};
         }
 
@@ -2394,6 +5897,123 @@ mod tests {
         assert_abs("-123456.123456", "123456.123456");
     }
 
+    #[test]
+    fn test_to_integral() {
+        fn assert_exact(val: &str, expected: Result<(u128, i16, bool), DecimalConvertError>) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            assert_eq!(decimal.to_integral_exact().map(|d| d.into_parts()), expected, "{}", val);
+        }
+
+        assert_exact("0", Ok((0, 0, false)));
+        assert_exact("0.000", Ok((0, 0, false)));
+        assert_exact("1.0", Ok((1, 0, false)));
+        assert_exact("-12.000", Ok((12, 0, true)));
+        assert_exact(
+            "99999999999999999999999999999999999999",
+            Ok((MAX_I128_REPR as u128, 0, false)),
+        );
+        assert_exact("1.5", Err(DecimalConvertError::Inexact));
+        assert_exact("-0.001", Err(DecimalConvertError::Inexact));
+        assert_exact("1E-130", Err(DecimalConvertError::Inexact));
+        assert_exact(
+            "0.99999999999999999999999999999999999999",
+            Err(DecimalConvertError::Inexact),
+        );
+        // negative scales are kept
+        assert_exact("1.2E+3", Ok((12, -2, false)));
+        assert_exact("-1E+125", Ok((1, -125, true)));
+        // scale > precision
+        let decimal = Decimal::from_parts(10u128.pow(37), 37, false).unwrap();
+        assert_eq!(decimal.to_integral_exact().unwrap().into_parts(), (1, 0, false));
+        let decimal = Decimal::from_parts(10u128.pow(37), 39, false).unwrap();
+        assert_eq!(decimal.to_integral_exact(), Err(DecimalConvertError::Inexact));
+
+        // The table of `java.math.RoundingMode`.
+        let modes = [
+            RoundingMode::Up,
+            RoundingMode::Down,
+            RoundingMode::Ceiling,
+            RoundingMode::Floor,
+            RoundingMode::HalfUp,
+            RoundingMode::HalfDown,
+            RoundingMode::HalfEven,
+        ];
+        let table: &[(&str, [i32; 7])] = &[
+            ("5.5", [6, 5, 6, 5, 6, 5, 6]),
+            ("2.5", [3, 2, 3, 2, 3, 2, 2]),
+            ("1.6", [2, 1, 2, 1, 2, 2, 2]),
+            ("1.1", [2, 1, 2, 1, 1, 1, 1]),
+            ("1.0", [1, 1, 1, 1, 1, 1, 1]),
+            ("-1.0", [-1, -1, -1, -1, -1, -1, -1]),
+            ("-1.1", [-2, -1, -1, -2, -1, -1, -1]),
+            ("-1.6", [-2, -1, -1, -2, -2, -2, -2]),
+            ("-2.5", [-3, -2, -2, -3, -3, -2, -2]),
+            ("-5.5", [-6, -5, -5, -6, -6, -5, -6]),
+        ];
+        for (val, expected) in table.iter() {
+            let decimal = val.parse::<Decimal>().unwrap();
+            for (mode, expected) in modes.iter().zip(expected.iter()) {
+                let result = decimal.to_integral_value(*mode);
+                assert_eq!(result, Decimal::from(*expected), "{} {:?}", val, mode);
+                assert!(result.scale() <= 0);
+            }
+        }
+
+        let decimal = "1.2E+3".parse::<Decimal>().unwrap();
+        assert_eq!(
+            decimal.to_integral_value(RoundingMode::Up).into_parts(),
+            (12, -2, false)
+        );
+        let decimal = "0.00000000000000000000000000000000000000000001"
+            .parse::<Decimal>()
+            .unwrap();
+        assert_eq!(decimal.to_integral_value(RoundingMode::Up), Decimal::ONE);
+        assert_eq!(decimal.to_integral_value(RoundingMode::HalfUp), Decimal::ZERO);
+        assert_eq!((-decimal).to_integral_value(RoundingMode::Floor), -Decimal::ONE);
+    }
+
+    #[test]
+    fn test_split() {
+        fn assert_split(val: &str, int_part: &str, fract_part: &str) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            let int_part = int_part.parse::<Decimal>().unwrap();
+            let fract_part = fract_part.parse::<Decimal>().unwrap();
+            let (i, f) = decimal.split();
+            assert_eq!(i.into_parts(), int_part.into_parts(), "{}", val);
+            assert_eq!(f.into_parts(), fract_part.into_parts(), "{}", val);
+            assert_eq!(decimal.fract().into_parts(), fract_part.into_parts(), "{}", val);
+            assert_eq!(i, decimal.trunc(0), "{}", val);
+            assert_eq!(i + f, decimal, "{}", val);
+        }
+
+        assert_split("0", "0", "0");
+        assert_split("123", "123", "0");
+        assert_split("-123", "-123", "0");
+        assert_split("1.2e10", "1.2e10", "0");
+        assert_split("12.75", "12", "0.75");
+        assert_split("-12.75", "-12", "-0.75");
+        assert_split("-0.75", "0", "-0.75");
+        assert_split("1.50", "1", "0.50");
+        assert_split("-3.000", "-3", "-0.000");
+        assert_split("0.00000000000000000000000000000000000000001", "0", "1e-41");
+        assert_split("-1e-130", "0", "-1e-130");
+        assert_split(
+            "1234567890123456789.0123456789012345678",
+            "1234567890123456789",
+            "0.0123456789012345678",
+        );
+        assert_split(
+            "0.99999999999999999999999999999999999999",
+            "0",
+            "0.99999999999999999999999999999999999999",
+        );
+        assert_split(
+            "-9.9999999999999999999999999999999999999",
+            "-9",
+            "-0.9999999999999999999999999999999999999",
+        );
+    }
+
     #[test]
     fn test_trunc() {
         fn assert_trunc(val: &str, scale: i16, expected: &str) {
@@ -2483,6 +6103,233 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_round_with_mode() {
+        fn assert_round(val: &str, scale: i16, mode: RoundingMode, expected: &str) {
+            let decimal = val.parse::<Decimal>().unwrap().round_with_mode(scale, mode);
+            let expected = expected.parse::<Decimal>().unwrap();
+            assert_eq!(decimal, expected, "{} {} {:?}", val, scale, mode);
+        }
+
+        // ties to even
+        assert_round("2.5", 0, RoundingMode::HalfEven, "2");
+        assert_round("3.5", 0, RoundingMode::HalfEven, "4");
+        assert_round("-2.5", 0, RoundingMode::HalfEven, "-2");
+        assert_round("-3.5", 0, RoundingMode::HalfEven, "-4");
+        assert_round("2.51", 0, RoundingMode::HalfEven, "3");
+        assert_round("0.125", 2, RoundingMode::HalfEven, "0.12");
+        assert_round("0.135", 2, RoundingMode::HalfEven, "0.14");
+        assert_round("0.5", 0, RoundingMode::HalfEven, "0");
+        assert_round("250", -2, RoundingMode::HalfEven, "200");
+        assert_round("350", -2, RoundingMode::HalfEven, "400");
+        assert_round("-1250", -2, RoundingMode::HalfEven, "-1200");
+        assert_round("-1350", -2, RoundingMode::HalfEven, "-1400");
+        assert_round("5E+3", -4, RoundingMode::HalfEven, "0");
+        assert_round("1.5E+3", -3, RoundingMode::HalfEven, "2E+3");
+
+        // the other modes before the decimal point and on negative values
+        assert_round("1234", -2, RoundingMode::HalfUp, "1200");
+        assert_round("1250", -2, RoundingMode::HalfUp, "1300");
+        assert_round("1250", -2, RoundingMode::HalfDown, "1200");
+        assert_round("1251", -2, RoundingMode::HalfDown, "1300");
+        assert_round("1201", -2, RoundingMode::Up, "1300");
+        assert_round("-1201", -2, RoundingMode::Up, "-1300");
+        assert_round("1299", -2, RoundingMode::Down, "1200");
+        assert_round("-1299", -2, RoundingMode::Down, "-1200");
+        assert_round("1201", -2, RoundingMode::Ceiling, "1300");
+        assert_round("-1299", -2, RoundingMode::Ceiling, "-1200");
+        assert_round("1299", -2, RoundingMode::Floor, "1200");
+        assert_round("-1201", -2, RoundingMode::Floor, "-1300");
+        assert_round("-0.001", 2, RoundingMode::Floor, "-0.01");
+        assert_round("-0.001", 2, RoundingMode::Ceiling, "0");
+        assert_round("1E-130", 0, RoundingMode::Up, "1");
+        assert_round("1E-130", 0, RoundingMode::HalfUp, "0");
+        assert_round("9.99", 1, RoundingMode::Ceiling, "10");
+
+        // `round` is the same as `HalfUp`
+        for val in crate::testing::corpus().iter() {
+            for &scale in [-126, -10, -1, 0, 1, 2, 10, 37, 130, 167].iter() {
+                assert_eq!(val.round(scale), val.round_with_mode(scale, RoundingMode::HalfUp));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rescale() {
+        fn assert_rescale(
+            val: &str,
+            scale: i16,
+            expected: Option<(u128, i16, bool)>,
+            trunc: Option<(u128, i16, bool)>,
+        ) {
+            let val = val.parse::<Decimal>().unwrap();
+            assert_eq!(
+                val.rescale(scale).map(|d| d.into_parts()),
+                expected,
+                "{} {}",
+                val,
+                scale
+            );
+            assert_eq!(
+                val.with_scale_trunc(scale).map(|d| d.into_parts()),
+                trunc,
+                "{} {}",
+                val,
+                scale
+            );
+        }
+
+        assert_rescale("3.3", 2, Some((330, 2, false)), Some((330, 2, false)));
+        assert_rescale("1.995", 2, Some((200, 2, false)), Some((199, 2, false)));
+        assert_rescale("-1.995", 2, Some((200, 2, true)), Some((199, 2, true)));
+        assert_rescale("1.994", 2, Some((199, 2, false)), Some((199, 2, false)));
+        assert_rescale("9.99", 1, Some((100, 1, false)), Some((99, 1, false)));
+        assert_rescale("1.2300", 4, Some((12300, 4, false)), Some((12300, 4, false)));
+        assert_rescale("1250", -2, Some((13, -2, false)), Some((12, -2, false)));
+        assert_rescale("1250", 3, Some((1250000, 3, false)), Some((1250000, 3, false)));
+        assert_rescale("0.004", 2, Some((0, 0, false)), Some((0, 0, false)));
+        assert_rescale("0.005", 2, Some((1, 2, false)), Some((0, 0, false)));
+        assert_rescale("0", 5, Some((0, 0, false)), Some((0, 0, false)));
+        assert_rescale("1e-130", 130, Some((1, 130, false)), Some((1, 130, false)));
+        assert_rescale("1e-130", 0, Some((0, 0, false)), Some((0, 0, false)));
+        assert_rescale(
+            "1.5",
+            37,
+            Some((15 * 10u128.pow(36), 37, false)),
+            Some((15 * 10u128.pow(36), 37, false)),
+        );
+        assert_rescale("1.5", 38, None, None);
+        assert_rescale(
+            "99999999999999999999999999999999999999",
+            0,
+            Some((MAX_I128_REPR as u128, 0, false)),
+            Some((MAX_I128_REPR as u128, 0, false)),
+        );
+        assert_rescale("99999999999999999999999999999999999999", 1, None, None);
+        assert_rescale(
+            "99999999999999999999999999999999999999",
+            -1,
+            Some((10u128.pow(37), -1, false)),
+            Some((9999999999999999999999999999999999999, -1, false)),
+        );
+        assert_rescale("1", 131, None, None);
+        assert_rescale("1", -127, None, None);
+        assert_rescale("1", MIN_SCALE, Some((0, 0, false)), Some((0, 0, false)));
+
+        let n: Decimal = "2.5".parse().unwrap();
+        assert_eq!(
+            n.rescale_with_mode(0, RoundingMode::HalfEven).unwrap().into_parts(),
+            (2, 0, false)
+        );
+        assert_eq!(
+            n.rescale_with_mode(0, RoundingMode::Ceiling).unwrap().into_parts(),
+            (3, 0, false)
+        );
+        assert_eq!(
+            (-n).rescale_with_mode(0, RoundingMode::Floor).unwrap().into_parts(),
+            (3, 0, true)
+        );
+
+        // The value doesn't change when digits aren't dropped.
+        for val in crate::testing::corpus().iter() {
+            for &scale in [-126, -2, 0, 2, 10, 130].iter() {
+                if let Some(result) = val.rescale(scale) {
+                    assert!(result.is_zero() || result.scale() == scale, "{} {}", val, scale);
+                    assert_eq!(result, val.round(scale), "{} {}", val, scale);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_arith_with_scale() {
+        type Op = fn(&Decimal, &Decimal, i16, RoundingMode) -> Option<Decimal>;
+
+        fn assert_op(op: Op, a: &str, b: &str, scale: i16, mode: RoundingMode, expected: Option<&str>) {
+            let a = a.parse::<Decimal>().unwrap();
+            let b = b.parse::<Decimal>().unwrap();
+            let result = op(&a, &b, scale, mode);
+            let expected = expected.map(|e| e.parse::<Decimal>().unwrap());
+            assert_eq!(result, expected, "{} {} {} {:?}", a, b, scale, mode);
+            if let Some(result) = result {
+                if !result.is_zero() {
+                    assert_eq!(result.scale(), scale, "{} {} {} {:?}", a, b, scale, mode);
+                }
+            }
+        }
+
+        let add: Op = |a, b, scale, mode| a.add_with_scale(b, scale, mode);
+        let sub: Op = |a, b, scale, mode| a.sub_with_scale(b, scale, mode);
+        let mul: Op = |a, b, scale, mode| a.mul_with_scale(b, scale, mode);
+        let div: Op = |a, b, scale, mode| a.div_with_scale(b, scale, mode);
+
+        let sum = "1.10"
+            .parse::<Decimal>()
+            .unwrap()
+            .add_with_scale("2.20".parse::<Decimal>().unwrap(), 2, RoundingMode::HalfEven)
+            .unwrap();
+        assert_eq!(format!("{:.*}", sum.scale() as usize, sum), "3.30");
+        let third = Decimal::ONE
+            .div_with_scale(Decimal::from(3), 4, RoundingMode::HalfEven)
+            .unwrap();
+        assert_eq!(third.to_string(), "0.3333");
+
+        assert_op(add, "1.10", "2.20", 2, RoundingMode::HalfEven, Some("3.30"));
+        assert_op(add, "1.005", "0", 2, RoundingMode::HalfEven, Some("1.00"));
+        assert_op(add, "0", "1.005", 2, RoundingMode::HalfUp, Some("1.01"));
+        assert_op(
+            add,
+            "1e20",
+            "0.005",
+            2,
+            RoundingMode::HalfUp,
+            Some("100000000000000000000.01"),
+        );
+        assert_op(
+            add,
+            "1e20",
+            "1e-100",
+            0,
+            RoundingMode::Up,
+            Some("100000000000000000001"),
+        );
+        assert_op(
+            add,
+            "1e20",
+            "1e-100",
+            0,
+            RoundingMode::Down,
+            Some("100000000000000000000"),
+        );
+        assert_op(add, "1e20", "1e-100", 20, RoundingMode::Down, None);
+        assert_op(add, "1", "2", 131, RoundingMode::Down, None);
+        assert_op(add, "1.5", "-1.5", 2, RoundingMode::Down, Some("0"));
+        assert_op(sub, "5.00", "0.015", 2, RoundingMode::HalfEven, Some("4.98"));
+        assert_op(sub, "5.00", "0.015", 2, RoundingMode::Floor, Some("4.98"));
+        assert_op(sub, "-5.00", "0.015", 2, RoundingMode::Floor, Some("-5.02"));
+        assert_op(sub, "1250", "50", -2, RoundingMode::HalfEven, Some("1200"));
+        assert_op(mul, "19.99", "0.075", 2, RoundingMode::HalfUp, Some("1.50"));
+        assert_op(mul, "19.99", "0.075", 2, RoundingMode::Down, Some("1.49"));
+        assert_op(mul, "0", "0.075", 2, RoundingMode::Down, Some("0"));
+        assert_op(mul, "0", "0.075", -127, RoundingMode::Down, None);
+        assert_op(mul, "1e20", "1e20", 0, RoundingMode::Down, None);
+        assert_op(div, "1", "3", 4, RoundingMode::HalfUp, Some("0.3333"));
+        assert_op(div, "2", "3", 4, RoundingMode::Down, Some("0.6666"));
+        assert_op(div, "-2", "3", 4, RoundingMode::HalfEven, Some("-0.6667"));
+        assert_op(div, "10", "4", 3, RoundingMode::HalfEven, Some("2.500"));
+        assert_op(div, "1", "0", 4, RoundingMode::HalfEven, None);
+        assert_op(div, "0", "3", 4, RoundingMode::HalfEven, Some("0"));
+        assert_op(
+            div,
+            "1",
+            "3",
+            38,
+            RoundingMode::Down,
+            Some("0.33333333333333333333333333333333333333"),
+        );
+        assert_op(div, "4", "3", 38, RoundingMode::Down, None);
+    }
+
     #[test]
     fn test_round_with_precision() {
         fn assert(val: &str, precision: u8, scale: i16, expected: &str) {
@@ -2553,7 +6400,135 @@ mod tests {
             2,
             (99_9999_9999_9999_9999_9999_9999_9999_9999_9990_u128, -1),
         );
-        assert_normalize((12300, MIN_SCALE + 1), -100, (123000000000000000000000000000, -100));
+        assert_normalize((12300, MIN_SCALE + 1), -100, (123000000000000000000000000000, -100));
+    }
+
+    #[test]
+    fn test_try_normalize_to_scale() {
+        fn assert_normalize(val: &str, scale: i16, expected: Result<(u128, i16), NormalizeError>) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            let result = decimal.try_normalize_to_scale(scale);
+            assert_eq!(result.map(|d| (d.int_val, d.scale)), expected, "{}", val);
+        }
+        fn closest(val: &str) -> Decimal {
+            val.parse().unwrap()
+        }
+
+        assert_normalize("1.2300", 2, Ok((123, 2)));
+        assert_normalize("1.2300", 4, Ok((12300, 4)));
+        assert_normalize("1.2300", 6, Ok((1230000, 6)));
+        assert_normalize("-1200", -2, Ok((12, -2)));
+        assert_normalize("0", 5, Ok((0, 0)));
+        assert_normalize(
+            "1.2300",
+            1,
+            Err(NormalizeError::NonZeroDigit {
+                closest: closest("1.23"),
+            }),
+        );
+        assert_normalize(
+            "-1250",
+            -2,
+            Err(NormalizeError::NonZeroDigit {
+                closest: closest("-1250"),
+            }),
+        );
+        assert_normalize(
+            "123456789012345678901234567890123456",
+            3,
+            Err(NormalizeError::MantissaFull {
+                closest: closest("123456789012345678901234567890123456.00"),
+            }),
+        );
+        assert_normalize("1", 38, Err(NormalizeError::MantissaFull { closest: closest("1") }));
+        assert_normalize("1", 37, Ok((POWERS_10[37].low(), 37)));
+        assert_normalize("1", MIN_SCALE - 1, Err(NormalizeError::ScaleOutOfRange));
+        assert_normalize("1", MAX_SCALE + 1, Err(NormalizeError::ScaleOutOfRange));
+
+        if let Err(NormalizeError::MantissaFull { closest }) = Decimal::ONE.try_normalize_to_scale(38) {
+            assert_eq!(closest.scale(), 37);
+        } else {
+            panic!("expected MantissaFull");
+        }
+    }
+
+    #[test]
+    fn test_normalize_to_scale_lossy() {
+        fn assert_lossy(val: &str, scale: i16, mode: RoundingMode, expected: Result<(u128, i16), NormalizeError>) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            let result = decimal.normalize_to_scale_lossy(scale, mode);
+            assert_eq!(result.map(|d| (d.int_val, d.scale)), expected, "{}", val);
+        }
+
+        assert_lossy("1.235", 2, RoundingMode::HalfEven, Ok((124, 2)));
+        assert_lossy("1.245", 2, RoundingMode::HalfEven, Ok((124, 2)));
+        assert_lossy("1.245", 2, RoundingMode::HalfUp, Ok((125, 2)));
+        assert_lossy("-1.245", 2, RoundingMode::Floor, Ok((125, 2)));
+        assert_lossy("1.235", 5, RoundingMode::Down, Ok((123500, 5)));
+        assert_lossy("1.20", 1, RoundingMode::Up, Ok((12, 1)));
+        assert_lossy("-1250", -2, RoundingMode::HalfEven, Ok((12, -2)));
+        assert_lossy("0.004", 2, RoundingMode::Down, Ok((0, 0)));
+        assert_lossy("0.004", 2, RoundingMode::Up, Ok((1, 2)));
+        assert_lossy("1e-100", 100, RoundingMode::Down, Ok((1, 100)));
+        assert_lossy(
+            "99999999999999999999999999999999999999",
+            0,
+            RoundingMode::HalfUp,
+            Ok((99999999999999999999999999999999999999, 0)),
+        );
+        assert_lossy(
+            "9999999999999999999999999999999999999.9",
+            0,
+            RoundingMode::HalfUp,
+            Ok((10000000000000000000000000000000000000, 0)),
+        );
+        assert_lossy(
+            "123456789012345678901234567890123456",
+            3,
+            RoundingMode::HalfUp,
+            Err(NormalizeError::MantissaFull {
+                closest: "123456789012345678901234567890123456".parse().unwrap(),
+            }),
+        );
+        assert_lossy(
+            "1",
+            MIN_SCALE - 1,
+            RoundingMode::HalfUp,
+            Err(NormalizeError::ScaleOutOfRange),
+        );
+        assert_lossy(
+            "1",
+            MAX_SCALE + 1,
+            RoundingMode::HalfUp,
+            Err(NormalizeError::ScaleOutOfRange),
+        );
+
+        // Within bounds, the scale is hit exactly whenever the result fits in 38 digits.
+        let modes = [
+            RoundingMode::HalfUp,
+            RoundingMode::HalfEven,
+            RoundingMode::Floor,
+            RoundingMode::Up,
+        ];
+        for val in crate::testing::corpus().iter().filter(|v| !v.is_zero()) {
+            for scale in [-5, 0, 2, 10, 40].iter() {
+                for mode in modes.iter() {
+                    let rounded = val.round_with_mode(*scale, *mode);
+                    match val.normalize_to_scale_lossy(*scale, *mode) {
+                        Ok(d) => {
+                            assert_eq!(d, rounded);
+                            assert!(d.is_zero() || d.scale == *scale, "{} {}", val, scale);
+                        }
+                        Err(NormalizeError::MantissaFull { closest }) => {
+                            assert_eq!(closest, rounded);
+                            assert!(closest.scale < *scale);
+                            assert!(closest.precision() as i16 + scale - closest.scale > MAX_PRECISION as i16);
+                        }
+                        Err(e) => panic!("{} {}: {:?}", val, scale, e),
+                    }
+                }
+            }
+        }
     }
 
     #[test]
@@ -2577,6 +6552,205 @@ mod tests {
         assert_normalize((12300, MIN_SCALE + 1), (12300000000000000000000000000000000000, -92));
     }
 
+    #[test]
+    fn test_factor_pow10() {
+        fn assert_factor(val: &str, expected: (u128, i32, bool)) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            assert_eq!(decimal.factor_pow10(), expected, "{}", val);
+        }
+
+        assert_factor("0", (0, 0, false));
+        assert_factor("-0.000", (0, 0, false));
+        assert_factor("1", (1, 0, false));
+        assert_factor("-1.2300", (123, -2, true));
+        assert_factor("1200", (12, 2, false));
+        assert_factor("0.05", (5, -2, false));
+        assert_factor("1E-130", (1, -130, false));
+        assert_factor("-1E125", (1, 125, true));
+        assert_factor(
+            "99999999999999999999999999999999999999",
+            (MAX_I128_REPR as u128, 0, false),
+        );
+
+        // 0..=37 trailing zeros in the mantissa, at various scales
+        for zeros in 0..MAX_PRECISION {
+            for &scale in [-88i16, -1, 0, 1, 37, 38, 100, 129].iter() {
+                let int_val = 7 * 10u128.pow(zeros);
+                let decimal = Decimal::from_parts(int_val, scale, true).unwrap();
+                let (mantissa, exp, negative) = decimal.factor_pow10();
+                assert_eq!((mantissa, exp, negative), (7, zeros as i32 - scale as i32, true));
+                assert_eq!(strip_trailing_zeros(int_val), (7, zeros));
+                assert_eq!(strip_trailing_zeros(int_val + 10u128.pow(zeros)), (8, zeros));
+
+                // consistent with `normalize`, which only removes the trailing zeros of the fraction
+                let (n_int_val, n_scale, n_negative) = decimal.normalize().into_parts();
+                if exp <= 0 {
+                    assert_eq!((n_int_val, -(n_scale as i32), n_negative), (mantissa, exp, negative));
+                } else {
+                    assert_eq!(n_int_val, mantissa * 10u128.pow((exp + n_scale as i32) as u32));
+                }
+            }
+        }
+        assert_eq!(strip_trailing_zeros(u128::MAX), (u128::MAX, 0));
+        assert_eq!(strip_trailing_zeros(10u128.pow(38)), (1, 38));
+    }
+
+    #[test]
+    fn test_sortable_u64() {
+        fn key(val: &str) -> u64 {
+            val.parse::<Decimal>().unwrap().sortable_u64()
+        }
+
+        fn assert_monotonic(values: &[Decimal]) {
+            for a in values.iter() {
+                for b in values.iter() {
+                    if a < b {
+                        assert!(a.sortable_u64() <= b.sortable_u64(), "{} < {}", a, b);
+                    } else if a == b {
+                        assert_eq!(a.sortable_u64(), b.sortable_u64(), "{} == {}", a, b);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(key("0"), 1 << 63);
+        assert_eq!(key("1.00"), key("1"));
+        assert_eq!(key("-1200"), key("-1.2E3"));
+        assert!(key("-1E-130") < key("0"));
+        assert!(key("0") < key("1E-130"));
+
+        // collisions beyond the 15th significant digit
+        assert_eq!(key("1.00000000000000000001"), key("1"));
+        assert!(key("1.000000000000001") < key("1.000000000000002"));
+
+        // the mantissa resets at the exponent boundaries
+        let boundaries = [
+            "9.9999999999999999999999999999999999999",
+            "10",
+            "10.000000000000000000000000000000000001",
+            "99999999999999999999999999999999999999",
+            "1E38",
+            "0.099999999999999999999999999999999999999",
+            "0.1",
+            "9.9999999999999999999999999999999999999E-130",
+            "1E-129",
+            "1E-130",
+            "9.9999999999999999999999999999999999999E125",
+            "1E125",
+            "9E124",
+        ];
+        let mut values: Vec<Decimal> = boundaries.iter().map(|s| s.parse().unwrap()).collect();
+        // the extremes of `from_parts`, beyond the parsed range
+        let max_scale = MAX_SCALE + MAX_PRECISION as i16 - 1;
+        for &(int_val, scale) in [
+            (1, max_scale),
+            (MAX_I128_REPR as u128, max_scale),
+            (1, MAX_SCALE + 10),
+            (1, MIN_SCALE),
+            (MAX_I128_REPR as u128, MIN_SCALE),
+        ]
+        .iter()
+        {
+            values.push(Decimal::from_parts(int_val, scale, false).unwrap());
+        }
+        values.extend(values.clone().iter().map(|v| -v));
+        values.push(Decimal::ZERO);
+        assert_monotonic(&values);
+        let tiny = Decimal::from_parts(1, max_scale, false).unwrap();
+        assert!(Decimal::ZERO.sortable_u64() < tiny.sortable_u64());
+        assert!((-tiny).sortable_u64() < Decimal::ZERO.sortable_u64());
+        assert!(
+            Decimal::from_parts(1, MAX_SCALE + 10, false).unwrap().sortable_u64()
+                <= Decimal::from_parts(1, MAX_SCALE, false).unwrap().sortable_u64()
+        );
+        assert!(key("9.9999999999999999999999999999999999999") < key("10"));
+        assert!(key("-10") < key("-9.9999999999999999999999999999999999999"));
+
+        let mut values = crate::testing::corpus_random(0x5eed_0753, 300);
+        values.extend(crate::testing::corpus().into_iter().step_by(17));
+        assert_monotonic(&values);
+
+        let range = Decimal::from(-5).sortable_u64_range(&Decimal::from(12));
+        for val in ["-5", "-4.99", "0", "1E-130", "11.999", "12"].iter() {
+            assert!(range.contains(&key(val)), "{}", val);
+        }
+        for val in ["-5.0001", "12.0001", "1E100"].iter() {
+            assert!(!range.contains(&key(val)), "{}", val);
+        }
+        assert!(Decimal::from(12).sortable_u64_range(&Decimal::from(-5)).is_empty());
+        assert!(!Decimal::ONE.sortable_u64_range(&Decimal::ONE).is_empty());
+    }
+
+    #[test]
+    fn test_is_multiple_of_pow10() {
+        fn assert_multiple(val: &str, k: i32, expected: bool) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            assert_eq!(decimal.is_multiple_of_pow10(k), expected, "{} {}", val, k);
+        }
+
+        assert_multiple("0", 100, true);
+        assert_multiple("0", -100, true);
+        assert_multiple("1200", 2, true);
+        assert_multiple("1200", 3, false);
+        assert_multiple("-1200.00", 2, true);
+        assert_multiple("1", 0, true);
+        assert_multiple("1", 1, false);
+        assert_multiple("0.05", -2, true);
+        assert_multiple("0.05", -1, false);
+        assert_multiple("1.5", -1, true);
+        assert_multiple("1.5", 0, false);
+        assert_multiple("1E125", 125, true);
+        assert_multiple("1E125", 126, false);
+        assert_multiple("1E-130", -130, true);
+        assert_multiple("1E-130", -129, false);
+    }
+
+    #[test]
+    fn test_is_canonical() {
+        use std::convert::TryFrom;
+
+        assert!(Decimal::ZERO.is_canonical());
+        assert!(Decimal::ONE.is_canonical());
+        assert!(unsafe { Decimal::from_raw_parts(MAX_I128_REPR as u128, 130, true) }.is_canonical());
+        assert!(unsafe { Decimal::from_raw_parts(0, 2, false) }.is_canonical());
+        assert!(!unsafe { Decimal::from_raw_parts(0, 0, true) }.is_canonical());
+        assert!(!unsafe { Decimal::from_raw_parts(MAX_I128_REPR as u128 + 1, 0, false) }.is_canonical());
+
+        // the ingestion paths canonicalize a negative zero
+        let zero = unsafe { Decimal::from_parts_unchecked(0, 2, true) };
+        assert_eq!(zero.into_parts(), (0, 0, false));
+        let zero = Decimal::from_parts(0, -5, true).unwrap();
+        assert_eq!(zero.into_parts(), (0, 0, false));
+        for bytes in [
+            &[SIGN_MASK | SCALE_MASK, 2, 0][..],
+            &[SIGN_MASK, 3, 0, 0, 0][..],
+            &[SIGN_MASK, 0, 0][..],
+        ]
+        .iter()
+        {
+            let zero = Decimal::decode(bytes);
+            assert!(zero.is_canonical());
+            assert!(zero.is_sign_positive());
+            assert_eq!(zero.to_string(), "0");
+        }
+        let zero = Decimal::from_scaled_i128(0, 3).unwrap();
+        assert_eq!(zero.into_parts(), (0, 0, false));
+        assert!((-Decimal::ZERO).is_canonical());
+        assert!((-&Decimal::ZERO).is_canonical());
+        assert!(Decimal::from(-0i64).is_canonical());
+        assert!("-0.00".parse::<Decimal>().unwrap().is_canonical());
+        assert!(Decimal::try_from(-0.0f64).unwrap().is_canonical());
+
+        for val in crate::testing::corpus().iter() {
+            assert!(val.is_canonical());
+            assert!((-val).is_canonical());
+            assert!((val - val).is_canonical());
+            assert!((val * Decimal::ZERO).is_canonical());
+            assert!(val.trunc(0).is_canonical());
+            assert!(val.round(0).is_canonical());
+        }
+    }
+
     #[test]
     fn test_hash() {
         use std::collections::hash_map::DefaultHasher;
@@ -2593,6 +6767,74 @@ mod tests {
         assert_eq!(hash1.finish(), hash2.finish());
     }
 
+    #[test]
+    fn test_nth_root_exact() {
+        fn assert_root(val: &str, n: u32, expected: Option<&str>) {
+            let val = val.parse::<Decimal>().unwrap();
+            let root = val.nth_root_exact(n);
+            assert_eq!(root, expected.map(|e| e.parse::<Decimal>().unwrap()), "{} {}", val, n);
+            assert_eq!(val.is_perfect_power(n), root.is_some());
+            if let Some(root) = root {
+                assert_eq!(root.checked_pow(&Decimal::from(n)), Some(val));
+            }
+        }
+
+        assert_root("0.000001", 3, Some("0.01"));
+        assert_root("0.000008", 3, Some("0.02"));
+        assert_root("8E-6", 3, Some("0.02"));
+        assert_root("2", 2, None);
+        assert_root("4", 2, Some("2"));
+        assert_root("1.44", 2, Some("1.2"));
+        assert_root("1.440", 2, Some("1.2"));
+        assert_root("0.1", 2, None);
+        assert_root("100", 2, Some("10"));
+        assert_root("1000", 2, None);
+        assert_root("1E38", 2, Some("1E19"));
+        assert_root("1E-130", 2, Some("1E-65"));
+        assert_root("4E-130", 2, Some("2E-65"));
+        assert_root("1E-129", 2, None);
+        assert_root("99999999999999999980000000000000000001", 2, Some("9999999999999999999"));
+        assert_root("99999999999999999980000000000000000002", 2, None);
+        assert_root("1024", 10, Some("2"));
+        assert_root("1.024", 10, None);
+        assert_root("170141183460469231731687303715884105728", 127, None);
+        assert_root("1", 1000, Some("1"));
+        assert_root("10", 1000, None);
+        assert_root("1", u32::MAX, Some("1"));
+        assert_root("-1", u32::MAX, Some("-1"));
+        assert_root("10", u32::MAX, None);
+        assert_root("1E10", u32::MAX, None);
+        assert_root("1E-10", u32::MAX, None);
+
+        // negative bases
+        assert_root("-8", 3, Some("-2"));
+        assert_root("-0.000008", 3, Some("-0.02"));
+        assert_root("-8", 2, None);
+        assert_root("-16", 4, None);
+        assert_root("-32", 5, Some("-2"));
+
+        // trivial exponents
+        assert_root("0", 2, Some("0"));
+        assert_root("0", 7, Some("0"));
+        assert_root("2", 1, Some("2"));
+        assert_root("-2", 1, Some("-2"));
+        assert_root("2", 0, None);
+        assert_root("0", 0, None);
+    }
+
+    #[test]
+    fn test_int_root() {
+        for n in 2..130 {
+            for &val in [0u128, 1, 2, 3, 1 << 64, u128::MAX, u128::MAX >> 1, 10u128.pow(38)].iter() {
+                let root = int_root(val, n);
+                assert!(root.checked_pow(n).unwrap() <= val);
+                if let Some(p) = (root + 1).checked_pow(n) {
+                    assert!(p > val);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_sqrt() {
         fn assert_sqrt(val: &str, expected: &str) {
@@ -2656,6 +6898,131 @@ mod tests {
         assert_sqrt("1.0e-130", "1.0e-65");
     }
 
+    #[test]
+    fn test_nth_root() {
+        fn assert_nth_root(val: &str, n: u32, expected: &str) {
+            let num = val.parse::<Decimal>().unwrap();
+            let expected = expected.parse::<Decimal>().unwrap();
+            let root = num.nth_root(n).unwrap();
+
+            // within one unit in the 38th digit
+            let ulp = match expected.logb() {
+                Some(exp) => Decimal::ONE.scaleb(exp - MAX_PRECISION as i16 + 1).unwrap(),
+                None => Decimal::ZERO,
+            };
+            assert!(
+                root.checked_sub(expected).unwrap().abs() <= ulp,
+                "{} root of {}: {}",
+                n,
+                val,
+                root
+            );
+        }
+
+        assert_nth_root("0", 3, "0");
+        assert_nth_root("-1.5", 1, "-1.5");
+        assert_nth_root("2", 3, "1.2599210498948731647672106072782283506");
+        assert_nth_root("-2", 3, "-1.2599210498948731647672106072782283506");
+        assert_nth_root("3", 3, "1.4422495703074083823216383107801095884");
+        assert_nth_root("1e20", 5, "10000");
+        assert_nth_root("0.001", 3, "0.1");
+        assert_nth_root("-0.000008", 3, "-0.02");
+        assert_nth_root("10", 7, "1.3894954943731376371299852173530116221");
+        assert_nth_root(
+            "1e-130",
+            3,
+            "0.000000000000000000000000000000000000000000046415888336127788924100763509194465766",
+        );
+        assert_nth_root("9.9999999999999999999999999999999999999E125", 3, "1e42");
+        assert_nth_root("1e100", 9, "129154966501.48838754100755464720875430");
+        assert_nth_root("0.5", 2, "0.70710678118654752440084436210484903928");
+        assert_nth_root("123456.789", 4, "18.744710797034085374040170897363891865");
+        assert_nth_root("-27.000001", 3, "-3.0000000370370365797896756178011793279");
+        assert_nth_root("2", u32::MAX, "1.0000000001613859042472353451695542936");
+        assert_nth_root("0.5", u32::MAX, "0.99999999983861409577881006491594015976");
+        assert_nth_root("1E10", u32::MAX, "1.0000000053611237083151466575595695421");
+
+        // exact roots are exact
+        assert_eq!(
+            "1e20".parse::<Decimal>().unwrap().nth_root(5),
+            Some(Decimal::from(10000))
+        );
+        assert_eq!(
+            "-0.000008".parse::<Decimal>().unwrap().cbrt(),
+            Some("-0.02".parse().unwrap())
+        );
+
+        assert_eq!(Decimal::from(2).nth_root(0), None);
+        assert_eq!(Decimal::from(-2).nth_root(4), None);
+        assert_eq!(Decimal::from(2).cbrt(), Decimal::from(2).nth_root(3));
+        assert_eq!(Decimal::from(-27).cbrt(), Some(Decimal::from(-3)));
+
+        // the root has at most 38 significant digits, so its n-th power is within n units in the 37th digit
+        let values = crate::testing::corpus_random(0x5eed_0752, 300);
+        for val in values.iter().map(Decimal::abs).filter(|val| !val.is_zero()) {
+            // the 37th digit of tiny values is below the minimum exponent
+            let unit = match Decimal::ONE.scaleb(val.logb().unwrap() - MAX_PRECISION as i16 + 2) {
+                Some(unit) => unit,
+                None => continue,
+            };
+            for &n in [2u32, 3, 4, 5, 7].iter() {
+                let root = val.nth_root(n).unwrap();
+                let power = root.pow_u64(n as u64).unwrap();
+                let tolerance = unit.checked_mul(Decimal::from(n)).unwrap();
+                assert!(
+                    power.checked_sub(val).unwrap().abs() <= tolerance,
+                    "{} root of {}: {}",
+                    n,
+                    val,
+                    root
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt_with_scale() {
+        fn assert_sqrt_with_scale(val: &str, scale: i16, expected: &str) {
+            let num = val.parse::<Decimal>().unwrap();
+            let expected = expected.parse::<Decimal>().unwrap();
+            assert_eq!(num.sqrt_with_scale(scale), Some(expected));
+            assert_eq!(num.sqrt().unwrap().round(scale), expected);
+        }
+
+        assert_sqrt_with_scale("0", 2, "0");
+        assert_sqrt_with_scale("2", 0, "1");
+        assert_sqrt_with_scale("2", 4, "1.4142");
+        assert_sqrt_with_scale("2", 38, "1.4142135623730950488016887242096980786");
+        assert_sqrt_with_scale("2", -1, "0");
+        assert_sqrt_with_scale("0.0036", 1, "0.1");
+        assert_sqrt_with_scale("0.0001", 1, "0");
+        assert_sqrt_with_scale("1.44", 5, "1.2");
+        assert_sqrt_with_scale("152399025", 2, "12345");
+        assert_sqrt_with_scale("125348", 3, "354.045");
+        assert_sqrt_with_scale("1e100", 2, "1e50");
+        assert_sqrt_with_scale("1.01e100", -45, "1.00499e50");
+        assert_sqrt_with_scale("1e-130", 65, "1e-65");
+        assert_sqrt_with_scale("1e-130", 64, "0");
+        assert_sqrt_with_scale("1.01e-100", 55, "1.00499e-50");
+        assert_sqrt_with_scale("9.9999999999999999999999999999999999999E125", -60, "1e63");
+        assert!(Decimal::from(-2).sqrt_with_scale(2).is_none());
+
+        let values = crate::testing::corpus_random(0x5eed_0751, 500);
+        for val in values.iter().chain(crate::testing::corpus().iter()).map(Decimal::abs) {
+            let sqrt = val.sqrt().unwrap();
+            for &scale in [-10, 0, 2, 6, 20].iter() {
+                let scale = scale - sqrt.logb().unwrap_or(0);
+                assert_eq!(
+                    val.sqrt_with_scale(scale),
+                    Some(sqrt.round(scale)),
+                    "sqrt({}) to {}",
+                    val,
+                    scale
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_ceil_floor() {
         fn assert_ceil_floor(val: &str, expected_ceil: &str, expected_floor: &str) {
@@ -2683,6 +7050,104 @@ mod tests {
         assert_ceil_floor("-100e-2", "-1", "-1");
     }
 
+    #[test]
+    fn test_prefix_range() {
+        fn assert_range(val: &str, frac_digits: u8, start: &str, end: &str) {
+            let (s, e) = val.parse::<Decimal>().unwrap().prefix_range(frac_digits);
+            assert_eq!(s, start.parse::<Decimal>().unwrap(), "{} {}", val, frac_digits);
+            assert_eq!(e, end.parse::<Decimal>().unwrap(), "{} {}", val, frac_digits);
+        }
+
+        assert_range("1.5", 0, "1", "2");
+        assert_range("1", 0, "1", "2");
+        assert_range("-1.5", 0, "-2", "-1");
+        assert_range("-1", 0, "-1", "0");
+        assert_range("-0.5", 0, "-1", "0");
+        assert_range("0", 0, "0", "1");
+        assert_range("1.2345", 2, "1.23", "1.24");
+        assert_range("-1.2345", 2, "-1.24", "-1.23");
+        assert_range("1.99", 1, "1.9", "2");
+        assert_range("0", 6, "0", "0.000001");
+        assert_range("1E-130", 38, "0", "1E-38");
+        assert_range("-1E-130", 38, "-1E-38", "0");
+        assert_range("99.99", 0, "99", "100");
+        assert_range("-99.99", 0, "-100", "-99");
+
+        // the next representable decimal
+        assert_range("1E+40", 0, "1E+40", "1.0000000000000000000000000000000000001E+40");
+        assert_range("-1E+40", 0, "-1E+40", "-9.9999999999999999999999999999999999999E+39");
+        assert_range(
+            "1.2345678901234567890123456789012345678",
+            40,
+            "1.2345678901234567890123456789012345678",
+            "1.2345678901234567890123456789012345679",
+        );
+        assert_range(
+            "99999999999999999999999999999999999999",
+            0,
+            "99999999999999999999999999999999999999",
+            "100000000000000000000000000000000000000",
+        );
+
+        // beyond the range of parsing
+        let max = "9.9999999999999999999999999999999999999E125"
+            .parse::<Decimal>()
+            .unwrap();
+        assert_eq!(
+            max.integral_range(),
+            (max, Decimal::from_parts(1, -126, false).unwrap())
+        );
+        let range = Decimal::ZERO.prefix_range(200);
+        assert_eq!(range, (Decimal::ZERO, Decimal::from_parts(1, 167, false).unwrap()));
+        assert_range("1E-130", 200, "1E-130", "1.0000000000000000000000000000000000001E-130");
+
+        let (_, end) = "1".parse::<Decimal>().unwrap().integral_range();
+        assert_eq!(end, Decimal::TWO);
+    }
+
+    #[test]
+    fn test_prefix_range_btree() {
+        use std::collections::BTreeMap;
+
+        let mut values = crate::testing::corpus();
+        values.extend(crate::testing::corpus_random(0x7a9e, 2000));
+        values.extend(
+            [
+                "1.5", "1.99", "2", "-1.5", "-2", "-1", "1.23", "1.234", "1.24", "-1.24", "-1.235",
+            ]
+            .iter()
+            .map(|s| s.parse::<Decimal>().unwrap()),
+        );
+        let map: BTreeMap<Decimal, usize> = values.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+
+        for &frac_digits in [0u8, 2, 38, 200].iter() {
+            let floors: Vec<(Decimal, Decimal)> = map
+                .keys()
+                .map(|k| (*k, k.round_with_mode(frac_digits as i16, RoundingMode::Floor)))
+                .collect();
+
+            for val in values.iter().step_by(11) {
+                let (start, end) = val.prefix_range(frac_digits);
+                assert!(start <= *val && *val < end, "{} {}", val, frac_digits);
+
+                let in_range: Vec<Decimal> = map.range(start..end).map(|(k, _)| *k).collect();
+                let expected: Vec<Decimal> = floors.iter().filter(|(_, f)| *f == start).map(|(k, _)| *k).collect();
+                assert_eq!(in_range, expected, "{} {}", val, frac_digits);
+
+                // the same as truncation for non-negative values
+                if !val.is_sign_negative() {
+                    let trunc = val.trunc(frac_digits as i16);
+                    let expected: Vec<Decimal> = map
+                        .keys()
+                        .filter(|k| !k.is_sign_negative() && k.trunc(frac_digits as i16) == trunc)
+                        .copied()
+                        .collect();
+                    assert_eq!(in_range, expected, "{} {}", val, frac_digits);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_simply_format() {
         fn assert_fmt(input: &str, expected: &str) {
@@ -2879,7 +7344,7 @@ mod tests {
             assert_eq!(decimal, expected);
         }
         fn assert_pow_int(base: &str, exponent: i64, expected: &str) {
-            let decimal = base.parse::<Decimal>().unwrap().pow_i64(exponent).unwrap();
+            let decimal = underflow_to_zero(base.parse::<Decimal>().unwrap().pow_i64(exponent)).unwrap();
             let expected = expected.parse::<Decimal>().unwrap();
             assert_eq!(decimal, expected);
         }
@@ -2907,23 +7372,23 @@ mod tests {
         assert_pow_int("100", -9223372036854775808, "0");
         assert_pow_decimal("-3", "0", "1");
         assert_pow_decimal("3.333", "3", "37.025927037");
-        assert_pow_decimal("3.3", "2.2", "13.827086118044145328600539201031810464");
+        assert_pow_decimal("3.3", "2.2", "13.827086118044145328600539201031810465");
         assert_pow_decimal("2", "50.1", "1206709641626009.0372720478765230064730");
-        assert_pow_decimal("2", "-50.1", "0.00000000000000082869976795124193101335598234941507825");
+        assert_pow_decimal("2", "-50.1", "0.00000000000000082869976795124193101335598234941507824");
         assert_pow_decimal("123456", "2.2", "158974271527.98285353227767713306007512");
         assert_pow_decimal(
             "123456",
             "-12.2",
-            "0.0000000000000000000000000000000000000000000000000000000000000076480574247485409303800372083765338615",
+            "0.0000000000000000000000000000000000000000000000000000000000000076480574247485409303800372083765338616",
         );
         assert_pow_decimal("123456.789", "0.9999999", "123456.64426370977396175023229704225849");
         assert_pow_decimal(
             "234567890123456.789",
             "5.8822",
-            "3379043109285747020459941490972051546800000000000000000000000000000000000000000000000",
+            "3379043109285747020459941490972051546100000000000000000000000000000000000000000000000",
         );
-        assert_pow_decimal("0.9999999", "0.789", "0.99999992109999916760496639898664270396");
-        assert_pow_decimal("0.9999999", "123456.789", "0.98773021573686772017452509110356382471");
+        assert_pow_decimal("0.9999999", "0.789", "0.99999992109999916760496639898664270397");
+        assert_pow_decimal("0.9999999", "123456.789", "0.98773021573686772017452509110356382470");
         assert_pow_decimal(
             "0.9",
             "22222220000000000000000000000000000000000000000000000000000000",
@@ -2938,14 +7403,14 @@ mod tests {
         assert_pow_decimal(
             "1.0000000000000000000000000000000000001",
             "340282366920938463463374607431768211450",
-            "600171577097065.40413095725314413792835",
+            "600171577097065.40413095725314413792819",
         );
         assert_pow_decimal("100", "-170141183460469231731687303715884105720", "0");
         assert_pow_decimal("5", "-4188888888888888888444444444444444000000000000000000000000", "0");
         assert_pow_decimal(
             "1.000000000001",
             "1234567889",
-            "1.0012353302816452027366495735797849363",
+            "1.0012353302816452027366495735797849362",
         );
     }
 
@@ -2976,6 +7441,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_log() {
+        fn assert_close(actual: Option<Decimal>, expected: &str, units: u32) {
+            let actual = actual.unwrap();
+            let expected = expected.parse::<Decimal>().unwrap();
+
+            // within `units` units in the 38th digit
+            let ulp = match expected.logb() {
+                Some(exp) => Decimal::ONE.scaleb(exp - MAX_PRECISION as i16 + 1).unwrap(),
+                None => Decimal::ZERO,
+            };
+            assert!(
+                actual.checked_sub(expected).unwrap().abs() <= ulp.checked_mul(Decimal::from(units)).unwrap(),
+                "{} != {}",
+                actual,
+                expected
+            );
+        }
+
+        fn log10(val: &str) -> Option<Decimal> {
+            val.parse::<Decimal>().unwrap().log10()
+        }
+
+        fn log2(val: &str) -> Option<Decimal> {
+            val.parse::<Decimal>().unwrap().log2()
+        }
+
+        fn log(val: &str, base: &str) -> Option<Decimal> {
+            val.parse::<Decimal>().unwrap().log(&base.parse().unwrap())
+        }
+
+        // exact powers are exact
+        assert_eq!(log10("1e37"), Some(Decimal::from(37)));
+        assert_eq!(log10("1000.000"), Some(Decimal::from(3)));
+        assert_eq!(log10("0.001"), Some(Decimal::from(-3)));
+        assert_eq!(log10("1"), Some(Decimal::ZERO));
+        assert_eq!(log2("1024"), Some(Decimal::from(10)));
+        assert_eq!(
+            log2("170141183460469231731687303715884105728"),
+            Some(Decimal::from(127))
+        );
+        assert_eq!(log2("1"), Some(Decimal::ZERO));
+        assert_eq!(log("100", "10"), Some(Decimal::from(2)));
+        assert_eq!(log("8", "2"), Some(Decimal::from(3)));
+
+        assert_close(log10("2"), "0.30102999566398119521373889472449302677", 1);
+        assert_close(log10("13.3"), "1.1238516409670857922485497343495655114", 1);
+        assert_close(log10("0.000123456789"), "-3.9084850228307295524816663769404527415", 1);
+        assert_close(log10("99999999999999999999999999999999999999"), "38", 1);
+        assert_close(log10("1.5e100"), "100.17609125905568124208128900853062228", 1);
+        assert_close(log10("0.99999"), "-0.0000043429665339013793521486464083130412005", 1);
+        assert_close(log2("3"), "1.5849625007211561814537389439478165088", 1);
+        assert_close(log2("0.5"), "-1", 1);
+        assert_close(log2("1000"), "9.9657842846620870436109582884681705276", 1);
+        assert_close(log2("7e-120"), "-395.82401646442587763699636222149499030", 1);
+        // the error of ln(m) carries over
+        let assert_error_below = |actual: Option<Decimal>, expected: &str, bound: &str| {
+            let error = actual
+                .unwrap()
+                .checked_sub(expected.parse::<Decimal>().unwrap())
+                .unwrap();
+            assert!(
+                error.abs() < bound.parse::<Decimal>().unwrap(),
+                "{} {}",
+                error,
+                expected
+            );
+        };
+        assert_close(log10("7"), "0.84509804001425683071221625859263619348", 3);
+        assert_error_below(log10("1.1965"), "0.077912702949456156555429590091034321413", "2E-37");
+        assert_error_below(
+            log10("0.10000000000000082094568110455160536908"),
+            "-0.99999999999999643467820753988030416295",
+            "2.05E-37",
+        );
+        assert_error_below(log2("1.1965"), "0.25882039687641186750089600019788337591", "5E-37");
+        assert_error_below(
+            log2("0.10000000034931646167684485785759079132"),
+            "-3.3219280898477910870517083173541498543",
+            "6E-37",
+        );
+        // ln(self) / ln(base) rounds twice
+        assert_close(log("81", "3"), "4", 2);
+        assert_close(log("2", "7"), "0.35620718710802217651417707800129052930", 2);
+
+        assert_eq!(log10("0"), None);
+        assert_eq!(log2("-1"), None);
+        assert_eq!(log("2", "1"), None);
+        assert_eq!(log("2", "0"), None);
+    }
+
     #[test]
     fn test_exp() {
         fn assert_exp(exponent: &str, expected: &str) {
@@ -2984,60 +7540,350 @@ mod tests {
             assert_eq!(decimal, expected);
         }
 
-        assert_exp("1", "2.7182818284590452353602874713526624975");
+        assert_exp("1", "2.7182818284590452353602874713526624978");
         assert_exp("0.00000012", "1.0000001200000072000002880000086400002");
         assert_exp(
             "0.9999999999999999999999999999999999999",
-            "2.7182818284590452353602874713526624971",
+            "2.7182818284590452353602874713526624975",
         );
         assert_exp("-0.00000012", "0.99999988000000719999971200000863999979");
         assert_exp(
             "-0.9999999999999999999999999999999999999",
             "0.36787944117144232159552377016146086748",
         );
-        assert_exp("12.3456789", "229964.19456908213454430507162889547155");
-        assert_exp("-50.1", "0.00000000000000000000017452050324689209452230894746470912110");
-        assert_exp("259.11111", "33925423113202888041488548716222730394000000000000000000000000000000000000000000000000000000000000000000000000000");
-        assert_exp("290.123456", "997736847550168914657296864583252087210000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
+        assert_exp("12.3456789", "229964.19456908213454430507162889547153");
+        assert_exp("-50.1", "0.00000000000000000000017452050324689209452230894746470912117");
+        assert_exp("259.11111", "33925423113202888041488548716222730386000000000000000000000000000000000000000000000000000000000000000000000000000");
+        assert_exp("290.123456", "997736847550168914657296864583252086940000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
+
+        // the integer part of the exponent has a negative scale
+        assert_exp("1E+1", "22026.465794806716516957900645284244366");
+        assert_exp("2E+2", "7.2259737681257492581774770421893056974E+86");
+        assert_exp("-1E+1", "0.000045399929762484851535591515560550610238");
+        assert_eq!("1E+1".parse::<Decimal>().unwrap().cosh(), Decimal::from(10).cosh());
+    }
+
+    #[test]
+    fn test_try_math() {
+        fn parse(s: &str) -> Decimal {
+            s.parse().unwrap()
+        }
+
+        fn assert_try_pow(base: &str, exponent: &str, expected: Result<&str, DecimalMathError>) {
+            let result = parse(base).try_pow(&parse(exponent));
+            assert_eq!(result, expected.map(parse), "{}^{}", base, exponent);
+        }
+
+        // ln
+        assert_eq!(Decimal::ONE.try_ln(), Ok(Decimal::ZERO));
+        assert_eq!(parse("10").try_ln().ok(), parse("10").ln());
+        assert_eq!(Decimal::ZERO.try_ln(), Err(DecimalMathError::DomainError));
+        assert_eq!(parse("-1").try_ln(), Err(DecimalMathError::DomainError));
+
+        // sqrt
+        assert_eq!(Decimal::ZERO.try_sqrt(), Ok(Decimal::ZERO));
+        assert_eq!(parse("4").try_sqrt(), Ok(Decimal::TWO));
+        assert_eq!(parse("2").try_sqrt().ok(), parse("2").sqrt());
+        assert_eq!(parse("-4").try_sqrt(), Err(DecimalMathError::DomainError));
+
+        // exp
+        assert_eq!(Decimal::ZERO.try_exp(), Ok(Decimal::ONE));
+        assert_eq!(parse("290").try_exp().ok(), parse("290").exp());
+        assert_eq!(parse("-299").try_exp().ok(), parse("-299").exp());
+        assert_eq!(parse("300").try_exp(), Err(DecimalMathError::Overflow));
+        assert_eq!(parse("300").exp(), None);
+        assert_eq!(parse("-301").try_exp(), Err(DecimalMathError::Underflow));
+        assert_eq!(parse("-301").exp(), Some(Decimal::ZERO));
+
+        // pow
+        assert_try_pow("0", "0", Ok("1"));
+        assert_try_pow("0", "2", Ok("0"));
+        assert_try_pow("-8", "3", Ok("-512"));
+        assert_try_pow("2", "-2", Ok("0.25"));
+        assert_eq!(
+            parse("2").try_pow(&parse("0.5")).ok(),
+            parse("2").checked_pow(&parse("0.5"))
+        );
+        assert_try_pow("0", "-2", Err(DecimalMathError::DomainError));
+        assert_try_pow(
+            "-8",
+            "0.33333333333333333333333333333333333333",
+            Err(DecimalMathError::DomainError),
+        );
+        assert_try_pow("10", "126", Err(DecimalMathError::Overflow));
+        assert_try_pow("1E-100", "-2", Err(DecimalMathError::Overflow));
+        assert_try_pow("10", "-131", Err(DecimalMathError::Underflow));
+        assert_try_pow("0.1", "200", Err(DecimalMathError::Underflow));
+        assert_try_pow("3", "-1000", Err(DecimalMathError::Underflow));
+        assert_try_pow("3", "-1000.5", Err(DecimalMathError::Underflow));
+        assert_try_pow("3", "1000.5", Err(DecimalMathError::Overflow));
+
+        // the `Option` API is unchanged
+        for &(base, exponent) in [("10", "-131"), ("0.1", "200"), ("3", "-1000"), ("3", "-1000.5")].iter() {
+            assert_eq!(parse(base).checked_pow(&parse(exponent)), Some(Decimal::ZERO));
+        }
+        for &(base, exponent) in [("0", "-2"), ("-8", "0.5"), ("10", "126")].iter() {
+            assert_eq!(parse(base).checked_pow(&parse(exponent)), None);
+        }
     }
 
     #[test]
-    fn generate_exp_array() {
-        // [e^0, e^290]
-        for i in 0..291 {
-            let exponent = Decimal::from(i);
-            let result = exponent.exp_decimal().unwrap();
+    fn test_exp_tiny_argument() {
+        // the scale of the argument is beyond the table of powers of ten
+        for &s in ["1E-77", "1E-100", "-1E-90", "1.5E-80", "1E-130", "-9.9E-120"].iter() {
+            let x = s.parse::<Decimal>().unwrap();
+            assert_eq!(x.exp(), Some(Decimal::ONE), "{}", s);
+            assert_eq!(x.cosh(), Some(Decimal::ONE), "{}", s);
+            assert_eq!(x.tanh(), Some(x), "{}", s);
+            assert_eq!(Decimal::TWO.checked_pow(&x), Some(Decimal::ONE), "{}", s);
+        }
+    }
 
-            if i % 5 == 0 {
-                println!("// e^{}", i);
+    /// A fixed point number with 207 fractional digits, in little endian limbs of base `10^9`,
+    /// which computes the references of the exponential.
+    #[derive(Clone)]
+    struct Fixed(Vec<u64>);
+
+    impl Fixed {
+        const BASE: u64 = 1_000_000_000;
+        /// 23 fractional limbs and 15 integral limbs, enough for `e^290`.
+        const FRACTION_LIMBS: usize = 23;
+        const LIMBS: usize = 38;
+
+        fn from_int(val: u128) -> Fixed {
+            let mut limbs = vec![0; Fixed::LIMBS];
+            let mut val = val;
+            for limb in limbs[Fixed::FRACTION_LIMBS..].iter_mut() {
+                *limb = (val % Fixed::BASE as u128) as u64;
+                val /= Fixed::BASE as u128;
             }
-            println!(
-                "unsafe {{ Decimal::from_raw_parts({}, {}, {}) }},",
-                result.int_val(),
-                result.scale,
-                result.negative,
-            );
+            Fixed(limbs)
+        }
+
+        fn is_zero(&self) -> bool {
+            self.0.iter().all(|&limb| limb == 0)
+        }
+
+        fn mul_small(&mut self, val: u64) {
+            let mut carry = 0u128;
+            for limb in self.0.iter_mut() {
+                let t = *limb as u128 * val as u128 + carry;
+                *limb = (t % Fixed::BASE as u128) as u64;
+                carry = t / Fixed::BASE as u128;
+            }
+            assert_eq!(carry, 0);
+        }
+
+        fn div_small(&mut self, val: u64) {
+            let mut rem = 0u128;
+            for limb in self.0.iter_mut().rev() {
+                let t = rem * Fixed::BASE as u128 + *limb as u128;
+                *limb = (t / val as u128) as u64;
+                rem = t % val as u128;
+            }
+        }
+
+        fn add(&mut self, other: &Fixed) {
+            let mut carry = 0;
+            for (a, &b) in self.0.iter_mut().zip(other.0.iter()) {
+                let t = *a + b + carry;
+                *a = t % Fixed::BASE;
+                carry = t / Fixed::BASE;
+            }
+            assert_eq!(carry, 0);
+        }
+
+        /// Calculates `|self - other|`.
+        fn abs_diff(&self, other: &Fixed) -> Fixed {
+            let (a, b) = if self.0.iter().rev().ge(other.0.iter().rev()) {
+                (self, other)
+            } else {
+                (other, self)
+            };
+            let mut borrow = 0;
+            let limbs =
+                a.0.iter()
+                    .zip(b.0.iter())
+                    .map(|(&x, &y)| {
+                        let t = x + Fixed::BASE - y - borrow;
+                        borrow = 1 - t / Fixed::BASE;
+                        t % Fixed::BASE
+                    })
+                    .collect();
+            Fixed(limbs)
+        }
+
+        /// Calculates `self * other`, truncated to 207 fractional digits.
+        fn mul(&self, other: &Fixed) -> Fixed {
+            let mut product = vec![0u128; 2 * Fixed::LIMBS];
+            for (i, &a) in self.0.iter().enumerate() {
+                for (j, &b) in other.0.iter().enumerate() {
+                    product[i + j] += a as u128 * b as u128;
+                }
+            }
+            let mut carry = 0;
+            for limb in product.iter_mut() {
+                let t = *limb + carry;
+                *limb = t % Fixed::BASE as u128;
+                carry = t / Fixed::BASE as u128;
+            }
+            let limbs = product[Fixed::FRACTION_LIMBS..Fixed::FRACTION_LIMBS + Fixed::LIMBS]
+                .iter()
+                .map(|&limb| limb as u64)
+                .collect();
+            Fixed(limbs)
+        }
+
+        /// Computes `e^(int_val * 10^-scale)` by its Taylor series, requires `int_val < 10^scale <= 10^12`.
+        fn exp_fraction(int_val: u64, scale: u32, negative: bool) -> Fixed {
+            let mut positive = Fixed::from_int(1);
+            let mut negative_sum = Fixed::from_int(0);
+            let mut term = Fixed::from_int(1);
+            for k in 1.. {
+                term.mul_small(int_val);
+                term.div_small(10u64.pow(scale));
+                term.div_small(k);
+                if term.is_zero() {
+                    break;
+                }
+                if negative && k % 2 == 1 {
+                    negative_sum.add(&term);
+                } else {
+                    positive.add(&term);
+                }
+            }
+            positive.abs_diff(&negative_sum)
+        }
+
+        /// Computes `e^x` for `|x| < 300` as `e^b * (e^±1)^a`, where `a` and `b` are the integral and
+        /// the fraction parts of `x`.
+        fn exp(x: &Decimal) -> Fixed {
+            let (int_val, scale, negative) = x.into_parts();
+            let scale = scale.max(0) as u32;
+            let int_val = int_val as u64 * if x.scale < 0 { 10u64.pow(-x.scale as u32) } else { 1 };
+            let pow = 10u64.pow(scale);
+            let mut result = Fixed::exp_fraction(int_val % pow, scale, negative);
+            let e = Fixed::exp_fraction(1, 0, negative);
+            for _ in 0..int_val / pow {
+                result = result.mul(&e);
+            }
+            result
+        }
+    }
+
+    #[test]
+    fn test_exp_reference() {
+        fn assert_exp_close(x: Decimal) {
+            let result = x.exp().unwrap();
+            let (int_val, scale, _) = result.into_parts();
+
+            // less than one unit in the last digit
+            let mut result = Fixed::from_int(int_val);
+            let mut ulp = Fixed::from_int(1);
+            for _ in scale..0 {
+                result.mul_small(10);
+                ulp.mul_small(10);
+            }
+            for _ in 0..scale {
+                result.div_small(10);
+                ulp.div_small(10);
+            }
+            let diff = result.abs_diff(&Fixed::exp(&x));
+            assert!(diff.0.iter().rev().le(ulp.0.iter().rev()), "{} {}", x, x.exp().unwrap());
+        }
+
+        let mut rng = Lcg::new(0x713);
+        for i in 0..200 {
+            // x in (-299, 0) and (0, 290), with up to 12 fractional digits
+            let scale = (rng.next() % 13) as i16;
+            let bound = if i % 4 == 0 { 290 } else { 299 };
+            let int_val = (rng.next() as u128) % (bound * 10u128.pow(scale as u32));
+            assert_exp_close(Decimal::from_parts(int_val, scale, i % 4 != 0).unwrap());
         }
+
+        // e^290.5 is greater than the maximum decimal.
+        assert!("290.5".parse::<Decimal>().unwrap().exp().is_none());
     }
 
     #[test]
-    fn generate_exp_negative_array() {
-        // e^-291
-        const EXP_NEGATIVE_291: Decimal =
-            unsafe { Decimal::from_raw_parts(41716298478166806118243377939293045745, 164, false) };
-        // [e^-299, e^-291]
-        for i in 291..300 {
-            let result = EXP_NEGATIVE_291.checked_div(&NATURAL_EXP[(i - 291) as usize]).unwrap();
+    fn test_with_precision() {
+        fn assert_close(reduced: Option<Decimal>, full: Option<Decimal>, digits: u8) {
+            let reduced = reduced.unwrap();
+            let full = full.unwrap();
+            assert!(reduced.precision() <= digits, "{} {}", reduced, digits);
+            if digits as u32 == MAX_PRECISION || full.is_zero() {
+                assert_eq!(reduced, full);
+                return;
+            }
+
+            // less than one unit in the last digit
+            let ulp = Decimal::ONE.scaleb(full.logb().unwrap() - digits as i16 + 1).unwrap();
+            assert!((reduced - full).abs() < ulp, "{} {} {}", reduced, full, digits);
+        }
 
-            if i % 5 == 0 {
-                println!("// e^-{}", i);
+        let mut rng = Lcg::new(0x9e37_79b9_7f4a_7c15u64);
+
+        for _ in 0..200 {
+            // x in (0, 10^12), y in (-200, 200) and z in (-5, 5)
+            let x = Decimal::from_parts(
+                (rng.next() % 1_000_000_000_000) as u128 + 1,
+                (rng.next() % 13) as i16,
+                false,
+            )
+            .unwrap();
+            let y = Decimal::from_parts((rng.next() % 200_000_000) as u128, 6, rng.next() % 2 == 1).unwrap();
+            let z = Decimal::from_parts((rng.next() % 5_000_000) as u128, 6, rng.next() % 2 == 1).unwrap();
+
+            for &digits in [1, 5, 16, 25, 34, 38].iter() {
+                assert_close(x.ln_with_precision(digits), x.ln(), digits);
+                assert_close(y.exp_with_precision(digits), y.exp(), digits);
+                assert_close(x.pow_with_precision(&z, digits), x.checked_pow(&z), digits);
             }
-            println!(
-                "unsafe {{ Decimal::from_raw_parts({}, {}, {}) }},",
-                result.int_val(),
-                result.scale,
-                result.negative,
-            );
+        }
+
+        // the digits are limited to `[1, 38]`
+        let x = "2.5".parse::<Decimal>().unwrap();
+        assert_eq!(x.ln_with_precision(0), x.ln_with_precision(1));
+        assert_eq!(x.exp_with_precision(100), x.exp());
+        assert_eq!(x.pow_with_precision(&x, 100), x.checked_pow(&x));
+
+        // the rounding may carry into one more digit
+        assert_eq!(
+            "9.9999999999999999999999999999999999999E125"
+                .parse::<Decimal>()
+                .unwrap()
+                .pow_with_precision(&Decimal::ONE, 5),
+            None
+        );
+    }
+
+    #[test]
+    fn test_exp_table() {
+        fn assert_close(val: Decimal, expected: Decimal, ulps: u128) {
+            let scale = expected.scale + MAX_PRECISION as i16 - expected.precision() as i16;
+            let ulp = Decimal::from_parts(ulps, scale, false).unwrap();
+            let diff = (val - expected).abs();
+            assert!(diff <= ulp, "{} {} {}", val, expected, diff);
+        }
+
+        // e^i = e^(i - 1) * e
+        for i in 2..291 {
+            assert_close(NATURAL_EXP[i - 1] * NATURAL_EXP[1], NATURAL_EXP[i], 4);
+        }
+
+        // e^i / e^(i - 1) = e to 57 digits
+        let (high, low, e_scale) = natural_exp_wide(1);
+        let e = POWERS_10[19] * high + low as u128;
+        for i in 2..291 {
+            let (high, low, scale) = natural_exp_wide(i);
+            let (mut quotient, mut scale) = div_natural_exp(POWERS_10[19] * high + low as u128, scale, i - 1);
+            if quotient.count_digits() > 57 {
+                quotient = quotient / 10u128;
+                scale -= 1;
+            }
+            assert_eq!(scale, e_scale, "{}", i);
+            let diff = quotient.checked_sub(e).or_else(|| e.checked_sub(quotient)).unwrap();
+            assert!(diff <= 3u128, "{} {:?} {:?}", i, quotient, e);
         }
     }
 
@@ -3089,38 +7935,209 @@ mod tests {
             false,
             "a6067cc8b3051f61f39c31e697c47c18e3c0000000000",
         );
-        assert_fmt_hex(
-            "12345678901234567890123456789012345678e30",
-            true,
-            "753aaed77fe1aa5508b3e1db763b1a087e44a76fa433d81f80000000"
-                .to_uppercase()
-                .as_str(),
+        assert_fmt_hex(
+            "12345678901234567890123456789012345678e30",
+            true,
+            "753aaed77fe1aa5508b3e1db763b1a087e44a76fa433d81f80000000"
+                .to_uppercase()
+                .as_str(),
+        );
+        assert_fmt_hex(
+            "12345678901234567890123456789012345678e30",
+            false,
+            "753aaed77fe1aa5508b3e1db763b1a087e44a76fa433d81f80000000",
+        );
+        assert_fmt_hex("253.658", true, "FE");
+        assert_fmt_hex("253.658", false, "fe");
+        assert_fmt_hex("0", true, "0");
+        assert_fmt_hex("0", false, "0");
+        assert_fmt_hex("0.2", true, "0");
+        assert_fmt_hex("0.2", false, "0");
+        assert_fmt_hex("0.7", true, "1");
+        assert_fmt_hex("0.7", false, "1");
+        // the low 128 bits start with a zero digit
+        assert_fmt_hex("8e45", false, "166bb7f0435c9e717bb45005915000000000000");
+        // Max value
+        assert_fmt_hex(
+            "72370055773322622139731865630429942408e38",
+            true,
+            "fffffffffffffffffffffffffffffffe9e6c3ef3908c56c58cab20000000000"
+                .to_uppercase()
+                .as_str(),
+        );
+        assert_fmt_hex(
+            "72370055773322622139731865630429942408e38",
+            false,
+            "fffffffffffffffffffffffffffffffe9e6c3ef3908c56c58cab20000000000",
+        );
+    }
+
+    #[test]
+    fn test_format_with_separators() {
+        fn assert_fmt(input: &str, group_sep: char, decimal_sep: char, precision: Option<usize>, expected: &str) {
+            let mut s = String::new();
+            let num = input.parse::<Decimal>().unwrap();
+            num.format_with_separators(group_sep, decimal_sep, precision, &mut s)
+                .unwrap();
+            assert_eq!(s, expected, "{} {:?}", input, precision);
+        }
+
+        assert_fmt("1234567.891", ',', '.', None, "1,234,567.891");
+        assert_fmt("-1000", ',', '.', None, "-1,000");
+        assert_fmt("0.5", ',', '.', None, "0.5");
+        assert_fmt("0", ',', '.', None, "0");
+        assert_fmt("1", ',', '.', None, "1");
+        assert_fmt("12", ',', '.', None, "12");
+        assert_fmt("123", ',', '.', None, "123");
+        assert_fmt("1234", ',', '.', None, "1,234");
+        assert_fmt("-123456.000001", ',', '.', None, "-123,456.000001");
+        assert_fmt("0.0000012345", ',', '.', None, "0.0000012345");
+        assert_fmt("1234567.891", '.', ',', None, "1.234.567,891");
+        assert_fmt("1234567.891", ' ', ',', None, "1 234 567,891");
+        assert_fmt("1234567.891", '\u{a0}', ',', None, "1\u{a0}234\u{a0}567,891");
+        assert_fmt("1234567.891", '\'', '.', None, "1'234'567.891");
+
+        // negative scales
+        assert_fmt("12e5", ',', '.', None, "1,200,000");
+        assert_fmt("-1e9", ',', '.', None, "-1,000,000,000");
+        assert_fmt(
+            "9.9999999999999999999999999999999999999E125",
+            ',',
+            '.',
+            None,
+            "999,999,999,999,999,999,999,999,999,999,999,999,990,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000,000",
+        );
+
+        // precisions
+        assert_fmt("1234567.891", ',', '.', Some(2), "1,234,567.89");
+        assert_fmt("1234567.891", '.', ',', Some(0), "1.234.568");
+        assert_fmt("999999.999", ',', '.', Some(2), "1,000,000.00");
+        assert_fmt("1000", ',', '.', Some(3), "1,000.000");
+        assert_fmt("-0.001", ',', '.', Some(2), "-0.00");
+
+        // the same digits as `Display`
+        for val in crate::testing::corpus_random(0x772, 500).iter() {
+            for &precision in [None, Some(0), Some(2), Some(10), Some(300)].iter() {
+                let mut s = String::new();
+                val.format_with_separators('_', ',', precision, &mut s).unwrap();
+                let expected = match precision {
+                    Some(prec) => format!("{:.*}", prec, val),
+                    None => val.to_string(),
+                };
+                assert_eq!(s.replace('_', "").replace(',', "."), expected);
+                assert!(!s.split(',').nth(1).unwrap_or("").contains('_'), "{}", s);
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_with_si_suffix() {
+        fn assert_fmt_si(input: &str, significant: u8, expect: &str) {
+            let mut s = String::new();
+            let num = input.parse::<Decimal>().unwrap();
+            num.format_with_si_suffix(significant, &mut s).unwrap();
+            assert_eq!(s.as_str(), expect);
+        }
+
+        assert_fmt_si("0", 3, "0");
+        assert_fmt_si("1", 3, "1");
+        assert_fmt_si("-1", 3, "-1");
+        assert_fmt_si("999", 3, "999");
+        assert_fmt_si("1000", 3, "1k");
+        assert_fmt_si("1534000", 2, "1.5M");
+        assert_fmt_si("1534000", 38, "1.534M");
+        assert_fmt_si("-2250000000", 3, "-2.25B");
+        assert_fmt_si("7E12", 3, "7T");
+        assert_fmt_si("1.2345E17", 3, "123000T");
+        assert_fmt_si("0.5", 3, "500m");
+        assert_fmt_si("0.001", 3, "1m");
+        assert_fmt_si("0.0000033", 3, "3.3u");
+        assert_fmt_si("4.56E-9", 3, "4.56n");
+        assert_fmt_si("1E-12", 3, "0.001n");
+        assert_fmt_si("123456789.123456789", 5, "123.46M");
+        assert_fmt_si("123456789.123456789", 0, "100M");
+
+        // rounding carries into the next suffix
+        assert_fmt_si("999.96", 4, "1k");
+        assert_fmt_si("999960", 4, "1M");
+        assert_fmt_si("-999960000", 3, "-1B");
+        assert_fmt_si("999.5E12", 3, "1000T");
+        assert_fmt_si("0.99999E-9", 3, "1n");
+    }
+
+    #[test]
+    fn test_to_interchange_string() {
+        fn assert_interchange(input: &str, expect: &str) {
+            let num = input.parse::<Decimal>().unwrap();
+            let s = num.to_interchange_string();
+            assert_eq!(s, expect, "{}", input);
+            assert_eq!(s.parse::<Decimal>().unwrap(), num);
+        }
+
+        // The expected strings are those of ECMAScript `Number.prototype.toString`, without the `+`
+        // of positive exponents.
+        assert_interchange("0", "0");
+        assert_interchange("-0", "0");
+        assert_interchange("0.000", "0");
+        assert_interchange("1", "1");
+        assert_interchange("-1", "-1");
+        assert_interchange("1.50", "1.5");
+        assert_interchange("100", "100");
+        assert_interchange("1.23E5", "123000");
+        assert_interchange("0.1", "0.1");
+        assert_interchange("0.00123", "0.00123");
+        assert_interchange("12.34", "12.34");
+        assert_interchange("9.87654321E-5", "0.0000987654321");
+        assert_interchange("1E20", "100000000000000000000");
+        assert_interchange("1.5E20", "150000000000000000000");
+        assert_interchange("1E21", "1e21");
+        assert_interchange("-1.5E21", "-1.5e21");
+        assert_interchange("4.2E22", "4.2e22");
+        assert_interchange("1E125", "1e125");
+        assert_interchange("0.000001", "0.000001");
+        assert_interchange("0.0000015", "0.0000015");
+        assert_interchange("0.0000001", "1e-7");
+        assert_interchange("-1.23E-7", "-1.23e-7");
+        assert_interchange("1E-130", "1e-130");
+
+        // More significant digits than a double
+        assert_interchange("123456789012345678901", "123456789012345678901");
+        assert_interchange("1234567890123456789012", "1.234567890123456789012e21");
+        assert_interchange(
+            "12345678901234567890.123456789012345678",
+            "12345678901234567890.123456789012345678",
+        );
+        assert_interchange(
+            "-0.0000012345678901234567890123456789012345678",
+            "-0.0000012345678901234567890123456789012345678",
         );
-        assert_fmt_hex(
-            "12345678901234567890123456789012345678e30",
-            false,
-            "753aaed77fe1aa5508b3e1db763b1a087e44a76fa433d81f80000000",
+        assert_interchange(
+            "99999999999999999999999999999999999999",
+            "9.9999999999999999999999999999999999999e37",
         );
-        assert_fmt_hex("253.658", true, "FE");
-        assert_fmt_hex("253.658", false, "fe");
-        assert_fmt_hex("0", true, "0");
-        assert_fmt_hex("0", false, "0");
-        assert_fmt_hex("0.2", true, "0");
-        assert_fmt_hex("0.2", false, "0");
-        assert_fmt_hex("0.7", true, "1");
-        assert_fmt_hex("0.7", false, "1");
-        // Max value
-        assert_fmt_hex(
-            "72370055773322622139731865630429942408e38",
-            true,
-            "fffffffffffffffffffffffffffffffe9e6c3ef3908c56c58cab20000000000"
-                .to_uppercase()
-                .as_str(),
+        assert_interchange(
+            "-9.9999999999999999999999999999999999999E-100",
+            "-9.9999999999999999999999999999999999999e-100",
         );
-        assert_fmt_hex(
-            "72370055773322622139731865630429942408e38",
-            false,
-            "fffffffffffffffffffffffffffffffe9e6c3ef3908c56c58cab20000000000",
+
+        // The format written by other formatters is parsed back.
+        for input in ["1e21", "1E+21", "1e+21", "-1.5e-7", "0.000001"].iter() {
+            let num = input.parse::<Decimal>().unwrap();
+            assert_eq!(num.to_interchange_string().parse::<Decimal>().unwrap(), num);
+        }
+
+        // Out of the range of `str::parse`, the string is written but can't be parsed back.
+        let tiny = Decimal::from_parts(1, 131, false).unwrap();
+        assert_eq!(tiny.to_interchange_string(), "1e-131");
+        assert_eq!(
+            tiny.to_interchange_string().parse::<Decimal>(),
+            Err(crate::DecimalParseError::Underflow)
+        );
+        let huge = Decimal::from_parts(1, -126, false).unwrap();
+        assert_eq!(huge.to_interchange_string(), "1e126");
+        assert_eq!(
+            huge.to_interchange_string().parse::<Decimal>(),
+            Err(crate::DecimalParseError::Overflow)
         );
     }
 
@@ -3266,6 +8283,113 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_extreme() {
+        fn zeros(n: usize) -> String {
+            "0".repeat(n)
+        }
+
+        fn assert_fmt(dec: Decimal, display: &str, sci: &str, sci_forced: &str, json: &str) {
+            assert_eq!(dec.to_string(), display);
+            assert_eq!(
+                format!("{:?}", dec),
+                format!("Decimal(\"{}\", scale={})", display, dec.scale())
+            );
+
+            let mut s = String::new();
+            dec.simply_format(&mut s).unwrap();
+            assert_eq!(s, display.replacen("0.", ".", 1));
+
+            s.clear();
+            dec.format_with_sci(40, &mut s).unwrap();
+            assert_eq!(s, sci);
+
+            s.clear();
+            dec.format_with_sci_forced(5, true, &mut s).unwrap();
+            assert_eq!(s, sci_forced);
+
+            s.clear();
+            dec.format_to_json(&mut s).unwrap();
+            assert_eq!(s, json);
+            assert_eq!(dec.to_interchange_string(), json.to_lowercase().replace("e+", "e"));
+
+            // a large precision doesn't fit in the stack buffer of `Display`
+            let padded = format!("{:.300}", dec);
+            assert_eq!(padded.trim_end_matches('0').trim_end_matches('.'), display);
+            if dec.abs() >= Decimal::ONE {
+                assert_eq!(padded.len() - padded.find('.').unwrap() - 1, 300);
+            }
+        }
+
+        let nines = "9".repeat(38);
+        assert_fmt(
+            Decimal::MAX,
+            &format!("{}{}", nines, zeros(88)),
+            "1.000000000000000000000000000000000E+126",
+            "1.00000E+126",
+            "9.9999999999999999999999999999999999999E+125",
+        );
+        assert_fmt(
+            Decimal::MIN,
+            &format!("-{}{}", nines, zeros(88)),
+            "-1.00000000000000000000000000000000E+126",
+            "-1.00000E+126",
+            "-9.9999999999999999999999999999999999999E+125",
+        );
+        assert_fmt(
+            Decimal::MIN_POSITIVE,
+            &format!("0.{}1", zeros(129)),
+            "1.000000000000000000000000000000000E-130",
+            "1.00000E-130",
+            "1E-130",
+        );
+        assert_fmt(
+            "1e125".parse().unwrap(),
+            &format!("1{}", zeros(125)),
+            "1.000000000000000000000000000000000E+125",
+            "1.00000E+125",
+            "1E+125",
+        );
+        assert_fmt(
+            "-1e-130".parse().unwrap(),
+            &format!("-0.{}1", zeros(129)),
+            "-1.00000000000000000000000000000000E-130",
+            "-1.00000E-130",
+            "-1E-130",
+        );
+        assert_fmt(
+            Decimal::from_parts(149, 132, false).unwrap(),
+            &format!("0.{}149", zeros(129)),
+            "1.490000000000000000000000000000000E-130",
+            "1.49000E-130",
+            "1.49E-130",
+        );
+
+        // The widest parts accepted by `from_parts`, the significand of the scientific notation is rounded
+        // although the decimal itself can't be rounded at that position.
+        let max = MAX_I128_REPR as u128;
+        assert_fmt(
+            Decimal::from_parts(max, MIN_SCALE, true).unwrap(),
+            &format!("-{}{}", nines, zeros(126)),
+            "-1.00000000000000000000000000000000E+164",
+            "-1.00000E+164",
+            "-9.9999999999999999999999999999999999999E+163",
+        );
+        assert_fmt(
+            Decimal::from_parts(max, MAX_SCALE + MAX_PRECISION as i16 - 1, false).unwrap(),
+            &format!("0.{}{}", zeros(129), nines),
+            "1.000000000000000000000000000000000E-129",
+            "1.00000E-129",
+            "9.9999999999999999999999999999999999999E-130",
+        );
+
+        assert_eq!(format!("{:>8.300}", Decimal::ONE), format!("1.{}", zeros(300)));
+        assert_eq!(
+            format!("{:.200}", Decimal::MIN),
+            format!("-{}{}.{}", nines, zeros(88), zeros(200))
+        );
+    }
+
     #[test]
     fn test_unchecked_add() {
         fn assert_unchecked_add<const DECIMAL_MODEL: u8>(val1: &str, val2: &str, expected: &str, scale: i16) {
@@ -3649,4 +8773,898 @@ mod tests {
             false,
         );
     }
+
+    #[test]
+    fn test_result_scale() {
+        type Parts = (u128, i16, bool);
+
+        // Locks the result representation of every arithmetic operation,
+        // so that any change to the result scale has to be deliberate.
+        fn assert_scale(
+            val1: &str,
+            val2: &str,
+            add: Parts,
+            sub: Parts,
+            mul: Parts,
+            div: Option<Parts>,
+            rem: Option<Parts>,
+        ) {
+            let var1 = val1.parse::<Decimal>().unwrap();
+            let var2 = val2.parse::<Decimal>().unwrap();
+            assert_eq!(var1.checked_add(var2).unwrap().into_parts(), add, "{} + {}", val1, val2);
+            assert_eq!(var1.checked_sub(var2).unwrap().into_parts(), sub, "{} - {}", val1, val2);
+            assert_eq!(var1.checked_mul(var2).unwrap().into_parts(), mul, "{} * {}", val1, val2);
+            assert_eq!(
+                var1.checked_div(var2).map(|d| d.into_parts()),
+                div,
+                "{} / {}",
+                val1,
+                val2
+            );
+            assert_eq!(
+                var1.checked_rem(var2).map(|d| d.into_parts()),
+                rem,
+                "{} % {}",
+                val1,
+                val2
+            );
+        }
+
+        assert_scale(
+            "1.25",
+            "2.50",
+            (375, 2, false),
+            (125, 2, true),
+            (3125, 3, false),
+            Some((50000000000000000000000000000000000000, 38, false)),
+            Some((125, 2, false)),
+        );
+        assert_scale(
+            "1.25",
+            "-1.25",
+            (0, 0, false),
+            (250, 2, false),
+            (15625, 4, true),
+            Some((10000000000000000000000000000000000000, 37, true)),
+            Some((0, 0, false)),
+        );
+        assert_scale(
+            "1e5",
+            "0",
+            (100000, 0, false),
+            (1, -5, false),
+            (0, 0, false),
+            None,
+            None,
+        );
+        assert_scale(
+            "0",
+            "1e5",
+            (100000, 0, false),
+            (1, -5, true),
+            (0, 0, false),
+            Some((0, 0, false)),
+            Some((0, 0, false)),
+        );
+        assert_scale("1.5", "0", (15, 1, false), (15, 1, false), (0, 0, false), None, None);
+        assert_scale(
+            "1",
+            "3",
+            (4, 0, false),
+            (2, 0, true),
+            (3, 0, false),
+            Some((33333333333333333333333333333333333333, 38, false)),
+            Some((1, 0, false)),
+        );
+        assert_scale(
+            "99999999999999999999999999999999999999",
+            "1",
+            (10000000000000000000000000000000000000, -1, false),
+            (99999999999999999999999999999999999998, 0, false),
+            (99999999999999999999999999999999999999, 0, false),
+            Some((99999999999999999999999999999999999999, 0, false)),
+            Some((0, 0, false)),
+        );
+        assert_scale(
+            "0.99999999999999999999999999999999999999",
+            "0.01",
+            (10100000000000000000000000000000000000, 37, false),
+            (98999999999999999999999999999999999999, 38, false),
+            (99999999999999999999999999999999999999, 40, false),
+            Some((99999999999999999999999999999999999999, 36, false)),
+            Some((999999999999999999999999999999999999, 38, false)),
+        );
+        assert_scale(
+            "1e30",
+            "1e-10",
+            (10000000000000000000000000000000000000, 7, false),
+            (10000000000000000000000000000000000000, 7, false),
+            (1, -20, false),
+            Some((10000000000000000000000000000000000000, -3, false)),
+            Some((0, 0, false)),
+        );
+        assert_scale(
+            "1e76",
+            "123456789",
+            (10000000000000000000000000000000000000, -39, false),
+            (10000000000000000000000000000000000000, -39, false),
+            (123456789, -76, false),
+            Some((81000000737100006707610061039251555457, -30, false)),
+            Some((57605968, 0, false)),
+        );
+    }
+
+    #[test]
+    fn test_euclid() {
+        fn assert_euclid(val1: &str, val2: &str, div: &str, rem: &str) {
+            let var1 = val1.parse::<Decimal>().unwrap();
+            let var2 = val2.parse::<Decimal>().unwrap();
+            let div = div.parse::<Decimal>().unwrap();
+            let rem = rem.parse::<Decimal>().unwrap();
+            assert_eq!(var1.checked_div_euclid(var2), Some(div), "{} div_euclid {}", val1, val2);
+            assert_eq!(var1.checked_rem_euclid(var2), Some(rem), "{} rem_euclid {}", val1, val2);
+            assert_eq!(div * var2 + rem, var1, "{} {}", val1, val2);
+        }
+
+        assert_euclid("7", "2", "3", "1");
+        assert_euclid("-7", "2", "-4", "1");
+        assert_euclid("7", "-2", "-3", "1");
+        assert_euclid("-7", "-2", "4", "1");
+        assert_euclid("6", "2", "3", "0");
+        assert_euclid("-6", "2", "-3", "0");
+        assert_euclid("-6", "-2", "3", "0");
+        assert_euclid("0", "-2", "0", "0");
+        assert_euclid("1", "3", "0", "1");
+        assert_euclid("-1", "3", "-1", "2");
+        assert_euclid("-7.5", "0.5", "-15", "0");
+        assert_euclid("-7.6", "0.5", "-16", "0.4");
+        assert_euclid("7.6", "-0.5", "-15", "0.1");
+        assert_euclid("-7.6", "-0.5", "16", "0.4");
+        assert_euclid("-0.001", "1000", "-1", "999.999");
+        assert_euclid("-12.34", "1.233", "-11", "1.223");
+
+        // the quotient has more than 38 digits
+        let val = "-5e42".parse::<Decimal>().unwrap();
+        let three_tenths = "0.3".parse::<Decimal>().unwrap();
+        assert_eq!(
+            val.checked_div_euclid(three_tenths),
+            Some("-1.6666666666666666666666666666666666667e43".parse().unwrap())
+        );
+        assert_eq!(val.checked_rem_euclid(three_tenths), Some("0.1".parse().unwrap()));
+        let val = "-5e60".parse::<Decimal>().unwrap();
+        assert_eq!(
+            val.checked_div_euclid(Decimal::from(3)),
+            Some("-1.6666666666666666666666666666666666667e60".parse().unwrap())
+        );
+        assert_eq!(val.checked_rem_euclid(Decimal::from(3)), Some(Decimal::ONE));
+
+        assert_eq!(Decimal::ONE.checked_div_euclid(Decimal::ZERO), None);
+        assert_eq!(Decimal::ONE.checked_rem_euclid(Decimal::ZERO), None);
+    }
+
+    #[test]
+    fn test_midpoint_at_scale() {
+        use RoundingMode::*;
+
+        fn parse(s: &str) -> Decimal {
+            s.parse().unwrap()
+        }
+
+        fn assert_mid(a: &str, b: &str, scale: i16, mode: RoundingMode, expected: Option<&str>) {
+            let (a, b) = (parse(a), parse(b));
+            let expected = expected.map(parse);
+            assert_eq!(a.midpoint_at_scale(&b, scale, mode), expected);
+            assert_eq!(b.midpoint_at_scale(&a, scale, mode), expected);
+        }
+
+        assert_mid("1.20", "1.25", 2, HalfEven, Some("1.22"));
+        assert_mid("1.20", "1.25", 2, HalfUp, Some("1.23"));
+        assert_mid("1.20", "1.25", 2, Down, Some("1.22"));
+        assert_mid("-1.20", "-1.25", 2, HalfUp, Some("-1.23"));
+        assert_mid("-1.20", "-1.25", 2, Floor, Some("-1.23"));
+        assert_mid("-1.20", "-1.25", 2, Ceiling, Some("-1.22"));
+        assert_mid("1.20", "1.25", 3, Down, Some("1.225"));
+        assert_mid("0", "0", 5, HalfUp, Some("0"));
+        assert_mid("0", "7", -1, HalfUp, Some("0"));
+        assert_mid("0", "8", -1, HalfUp, Some("0"));
+        assert_mid("0", "1e-130", 130, HalfUp, Some("1e-130"));
+        assert_mid("0", "1e-130", 130, HalfDown, Some("0"));
+
+        // odd sums
+        assert_mid("1", "2", 0, HalfEven, Some("2"));
+        assert_mid("2", "3", 0, HalfEven, Some("2"));
+        assert_mid("2", "3", 0, HalfDown, Some("2"));
+        assert_mid("2", "3", 0, HalfUp, Some("3"));
+        assert_mid("-2", "3", 0, HalfUp, Some("1"));
+        assert_mid("-2", "3", 0, HalfEven, Some("0"));
+        assert_mid("-3", "2", 0, Floor, Some("-1"));
+
+        // near the extremes
+        let max = "9.9999999999999999999999999999999999999E125";
+        assert_mid(max, max, -88, HalfUp, Some(max));
+        assert_mid(max, &format!("-{}", max), 0, HalfUp, Some("0"));
+        assert_mid(max, "1", -88, HalfUp, Some("5E125"));
+        assert_mid(
+            max,
+            "-1",
+            -88,
+            HalfUp,
+            Some("4.9999999999999999999999999999999999999E125"),
+        );
+        assert_mid(max, "-1", -88, Up, Some("5E125"));
+        assert_mid(
+            max,
+            "-1",
+            -88,
+            Down,
+            Some("4.9999999999999999999999999999999999999E125"),
+        );
+        assert_mid(
+            max,
+            "9.9999999999999999999999999999999999998E125",
+            -88,
+            HalfUp,
+            Some(max),
+        );
+        assert_mid(
+            max,
+            "9.9999999999999999999999999999999999998E125",
+            -88,
+            HalfDown,
+            Some("9.9999999999999999999999999999999999998E125"),
+        );
+        assert_mid(max, "1", 0, HalfUp, None);
+        assert_mid(
+            "99999999999999999999999999999999999999",
+            "-0.99999999999999999999999999999999999999",
+            0,
+            HalfUp,
+            Some("49999999999999999999999999999999999999"),
+        );
+
+        // adjacent values at the scale
+        for &mode in [HalfUp, HalfEven, HalfDown, Up, Down, Ceiling, Floor].iter() {
+            let mid = parse("1.2344").midpoint_at_scale(&parse("1.2345"), 4, mode).unwrap();
+            assert!(mid == parse("1.2344") || mid == parse("1.2345"), "{:?}", mode);
+        }
+
+        // the digits far below the scale break the ties
+        assert_mid("1", "1e-100", 0, HalfDown, Some("1"));
+        assert_mid("1", "-1e-100", 0, HalfUp, Some("0"));
+        assert_mid("1", "1e-100", 0, HalfEven, Some("1"));
+        assert_mid("1", "-1e-100", 1, HalfUp, Some("0.5"));
+        assert_mid("1", "-1e-100", 1, Down, Some("0.4"));
+        assert_mid("-1e10", "1e-120", -10, Ceiling, Some("0"));
+        assert_mid("-1e10", "1e-120", -10, Floor, Some("-1e10"));
+        assert_mid("1", "1e-100", 38, HalfUp, Some("0.5"));
+        assert_mid("1", "1e-100", 39, HalfUp, None);
+
+        // clamped into the range of the operands
+        assert_mid("1.24", "1.26", 0, Up, Some("1.26"));
+        assert_mid("1.24", "1.26", 0, Down, Some("1.24"));
+        assert_mid("-1.24", "1.26", 0, Up, Some("1"));
+
+        assert_mid("1", "2", MAX_SCALE + 1, HalfUp, None);
+        assert_mid("1", "2", MIN_SCALE - 1, HalfUp, None);
+
+        // the same as the exact average of values with a few digits
+        let values = crate::testing::corpus_random(0x31d, 100);
+        let mut count = 0;
+        for (a, b) in values.iter().flat_map(|a| values.iter().map(move |b| (a, b))) {
+            if a.precision() > 30 || b.precision() > 30 {
+                continue;
+            }
+            let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+            let sum = match a.checked_add(b) {
+                // the sum isn't rounded
+                Some(sum)
+                    if sum.precision() < 38 && sum.checked_sub(a) == Some(*b) && sum.checked_sub(b) == Some(*a) =>
+                {
+                    sum
+                }
+                _ => continue,
+            };
+            let avg = sum.checked_mul(parse("0.5")).unwrap();
+            if avg.checked_add(avg) != Some(sum) {
+                continue;
+            }
+            for &scale in [-2, 0, 2, 5, 10].iter() {
+                for &mode in [HalfUp, HalfEven, HalfDown, Up, Down, Ceiling, Floor].iter() {
+                    let expected = avg.round_with_mode(scale, mode);
+                    // the digits at `scale`, as a coarser scale is kept by the rounding
+                    let digits = expected.precision() as i16 + (scale - expected.scale()).max(0);
+                    let expected = if expected.is_zero() || digits <= 38 {
+                        Some(expected.max(*lo).min(*hi))
+                    } else {
+                        None
+                    };
+                    assert_eq!(a.midpoint_at_scale(b, scale, mode), expected, "{} {} {}", a, b, scale);
+                }
+            }
+            count += 1;
+        }
+        assert!(count > 1000, "{}", count);
+    }
+
+    #[test]
+    fn test_midpoint_binary_search() {
+        // the smallest `x` at scale 6 in `[0, 100]` with `x * x >= 2`
+        let two = Decimal::from(2);
+        let mut lo = Decimal::ZERO;
+        let mut hi = Decimal::from(100);
+        let mut steps = 0;
+        while hi - lo > Decimal::from_parts(1, 6, false).unwrap() {
+            let mid = lo.midpoint_at_scale(&hi, 6, RoundingMode::HalfEven).unwrap();
+            assert!(mid != lo && mid != hi);
+            assert!(mid.scale() <= 6);
+            if mid * mid >= two {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+            steps += 1;
+            assert!(steps <= 30);
+        }
+        assert_eq!(hi, "1.414214".parse::<Decimal>().unwrap());
+    }
+
+    #[test]
+    fn test_absorbs() {
+        fn assert_absorbs(val1: &str, val2: &str, expected: bool) {
+            let var1 = val1.parse::<Decimal>().unwrap();
+            let var2 = val2.parse::<Decimal>().unwrap();
+            assert_eq!(var1.absorbs(&var2), expected, "{} absorbs {}", val1, val2);
+            let (sum, inexact) = var1.add_report(&var2).unwrap();
+            assert_eq!(!var2.is_zero() && sum == var1, expected, "{} + {}", val1, val2);
+            if expected {
+                assert!(inexact, "{} + {}", val1, val2);
+            }
+        }
+
+        assert_absorbs("1e30", "1e-10", true);
+        assert_absorbs("1e30", "-1e-10", true);
+        assert_absorbs("1e30", "1e-7", false);
+        assert_absorbs("0", "1e-10", false);
+        assert_absorbs("1e30", "0", false);
+
+        // 38 digits are kept, so the last digit of `1` is at `1e-37`
+        assert_absorbs("1", "1e-37", false);
+        assert_absorbs("1", "1e-38", true);
+        assert_absorbs("1", "1e-39", true);
+        assert_absorbs("1", "4.9e-38", true);
+        assert_absorbs("9", "1e-38", true);
+        assert_absorbs("9.9", "4.9e-38", true);
+        assert_absorbs("1.0000000000000000000000000000000000001", "1e-38", true);
+        assert_absorbs("1.0000000000000000000000000000000000001", "1e-37", false);
+
+        // half of the last unit is rounded away from zero
+        assert_absorbs("1", "5e-38", false);
+        assert_absorbs("1", "4.9999e-38", true);
+        assert_absorbs("1.5", "-5e-38", true);
+        assert_absorbs("1.5", "-5.0001e-38", false);
+
+        // below a power of ten the last unit is ten times smaller
+        assert_absorbs("1", "-5e-38", false);
+        assert_absorbs("1", "-5e-39", true);
+        assert_absorbs("1", "-5.1e-39", false);
+        assert_absorbs("-1e20", "5e-19", true);
+        assert_absorbs("-1e20", "5.1e-19", false);
+
+        // carries at the last digit
+        assert_absorbs("9.9999999999999999999999999999999999999", "4.9e-38", true);
+        assert_absorbs("9.9999999999999999999999999999999999999", "5e-38", false);
+        assert_absorbs("1e125", "1e-130", true);
+        assert_absorbs("1e-130", "1e125", false);
+    }
+
+    #[test]
+    fn test_add_report() {
+        fn assert_report(val1: &str, val2: &str, expected: &str, inexact: bool) {
+            let var1 = val1.parse::<Decimal>().unwrap();
+            let var2 = val2.parse::<Decimal>().unwrap();
+            let expected = expected.parse::<Decimal>().unwrap();
+            assert_eq!(var1.add_report(&var2), Some((expected, inexact)), "{} + {}", val1, val2);
+        }
+
+        assert_report("1.25", "2.5", "3.75", false);
+        assert_report("1.25", "-1.25", "0", false);
+        assert_report("0", "1e-130", "1e-130", false);
+        assert_report("1e30", "1e-10", "1e30", true);
+        assert_report("1e30", "-1e-7", "999999999999999999999999999999.99999990", false);
+        assert_report("99999999999999999999999999999999999999", "1", "1e38", false);
+        assert_report(
+            "99999999999999999999999999999999999999",
+            "2",
+            "1.0000000000000000000000000000000000000e38",
+            true,
+        );
+        assert_report(
+            "0.33333333333333333333333333333333333333",
+            "0.66666666666666666666666666666666666667",
+            "1",
+            false,
+        );
+        let max = "9.9999999999999999999999999999999999999e125"
+            .parse::<Decimal>()
+            .unwrap();
+        assert_eq!(max.add_report(&max), None);
+    }
+
+    #[test]
+    fn test_absorbs_consistency() {
+        let values = crate::testing::corpus();
+        for a in values.iter() {
+            for b in values.iter() {
+                let sum = match a.add_report(b) {
+                    Some((sum, inexact)) => {
+                        if a.absorbs(b) {
+                            assert!(inexact, "{} + {}", a, b);
+                        }
+                        sum
+                    }
+                    None => continue,
+                };
+                assert_eq!(a.absorbs(b), !b.is_zero() && sum == *a, "{} absorbs {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(
+            Decimal::MAX,
+            "9.9999999999999999999999999999999999999E125"
+                .parse::<Decimal>()
+                .unwrap()
+        );
+        assert_eq!("1E126".parse::<Decimal>(), Err(crate::DecimalParseError::Overflow));
+        assert_eq!(Decimal::MIN, -Decimal::MAX);
+        assert_eq!(Decimal::MIN_POSITIVE.into_parts(), (1, MAX_SCALE, false));
+        assert!(Decimal::MAX.is_canonical() && Decimal::MIN.is_canonical());
+        assert_eq!(Decimal::from_parts(1, MAX_SCALE, false), Ok(Decimal::MIN_POSITIVE));
+
+        for val in [
+            Decimal::MAX,
+            Decimal::MIN,
+            Decimal::MIN_POSITIVE,
+            -Decimal::MIN_POSITIVE,
+        ]
+        .iter()
+        {
+            let parsed = val.to_string().parse::<Decimal>().unwrap();
+            assert_eq!(parsed, *val);
+
+            let mut buf = [0; MAX_BINARY_SIZE];
+            let size = val.encode_to_slice(&mut buf).unwrap();
+            assert_eq!(Decimal::decode(&buf[..size]).into_parts(), val.into_parts());
+            let size = val.compact_encode_to_slice(&mut buf).unwrap();
+            assert_eq!(Decimal::decode(&buf[..size]).into_parts(), val.into_parts());
+        }
+
+        // Nothing is beyond the limits.
+        for val in crate::testing::corpus().iter() {
+            assert!(*val <= Decimal::MAX && *val >= Decimal::MIN, "{}", val);
+            assert!(val.is_zero() || val.abs() >= Decimal::MIN_POSITIVE, "{}", val);
+        }
+        assert_eq!(Decimal::MIN_POSITIVE.checked_div(Decimal::TWO), Some(Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_math_constants() {
+        fn assert_constant(val: Decimal, expected: &str) {
+            assert_eq!(val.to_string(), expected);
+            assert_eq!(val.precision(), 38, "{}", expected);
+            assert_eq!(expected.parse::<Decimal>().unwrap().into_parts(), val.into_parts());
+        }
+
+        assert_constant(Decimal::PI, "3.1415926535897932384626433832795028842");
+        assert_constant(Decimal::E, "2.7182818284590452353602874713526624978");
+        assert_constant(Decimal::LN_10, "2.3025850929940456840179914546843642076");
+        assert_constant(Decimal::LN_2, "0.69314718055994530941723212145817656808");
+        assert_constant(Decimal::SQRT_2, "1.4142135623730950488016887242096980786");
+
+        assert_eq!(Decimal::ONE.exp(), Some(Decimal::E));
+        assert_eq!(Decimal::from(10).ln(), Some(Decimal::LN_10));
+        assert_eq!(Decimal::from(2).sqrt(), Some(Decimal::SQRT_2));
+    }
+
+    #[test]
+    fn test_from_parts_bounds() {
+        fn assert_parts(int_val: u128, scale: i16, expected: Result<(), DecimalConvertError>) {
+            let result = Decimal::from_parts(int_val, scale, true);
+            assert_eq!(result.clone().map(|_| ()), expected, "{} {}", int_val, scale);
+            assert_eq!(
+                Decimal::is_valid_parts(int_val, scale),
+                expected.is_ok(),
+                "{} {}",
+                int_val,
+                scale
+            );
+            if let Ok(val) = result {
+                assert_eq!(val.into_parts(), (int_val, scale, true));
+            }
+        }
+
+        let max_scale = MAX_SCALE + MAX_PRECISION as i16 - 1;
+        assert_parts(1, MAX_SCALE, Ok(()));
+        assert_parts(1, MAX_SCALE + 1, Ok(()));
+        assert_parts(1, max_scale, Ok(()));
+        assert_parts(MAX_I128_REPR as u128, max_scale, Ok(()));
+        assert_parts(1, max_scale + 1, Err(DecimalConvertError::ScaleOutOfRange));
+        assert_parts(1, i16::MAX, Err(DecimalConvertError::ScaleOutOfRange));
+        assert_parts(1, MIN_SCALE, Ok(()));
+        assert_parts(1, MIN_SCALE - 1, Err(DecimalConvertError::ScaleOutOfRange));
+        assert_parts(1, i16::MIN, Err(DecimalConvertError::ScaleOutOfRange));
+        assert_parts(MAX_I128_REPR as u128, 0, Ok(()));
+        assert_parts(MAX_I128_REPR as u128 + 1, 0, Err(DecimalConvertError::Overflow));
+        assert_parts(u128::MAX, max_scale + 1, Err(DecimalConvertError::Overflow));
+
+        // the scale beyond `MAX_SCALE` keeps the digits below `MIN_POSITIVE`
+        let below_min = Decimal::from_parts(12345678901234567890123456789012345678, max_scale, false).unwrap();
+        assert_eq!(
+            below_min.to_string(),
+            format!("0.{}12345678901234567890123456789012345678", "0".repeat(129))
+        );
+        assert!(below_min > Decimal::MIN_POSITIVE);
+
+        // a zero is valid at any scale in range, and always normalized
+        assert_eq!(
+            Decimal::from_parts(0, max_scale, true).unwrap().into_parts(),
+            (0, 0, false)
+        );
+        assert_eq!(
+            Decimal::from_parts(0, max_scale + 1, false),
+            Err(DecimalConvertError::ScaleOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_from_parts_const() {
+        const HALF: Decimal = Decimal::from_parts_const(5, 1, false);
+        const NEG: Decimal = Decimal::from_parts_const(MAX_I128_REPR as u128, MIN_SCALE, true);
+        const ZERO: Decimal = Decimal::from_parts_const(0, MAX_SCALE, true);
+
+        assert_eq!(HALF.to_string(), "0.5");
+        assert_eq!(NEG.into_parts(), (MAX_I128_REPR as u128, MIN_SCALE, true));
+        assert_eq!(ZERO.into_parts(), (0, 0, false));
+        assert_eq!(
+            Decimal::from_parts_const(12345, -2, true),
+            Decimal::from_parts(12345, -2, true).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_parts_const_invalid() {
+        let _ = Decimal::from_parts_const(1, MIN_SCALE - 1, false);
+    }
+
+    #[test]
+    fn test_saturating() {
+        let one = Decimal::ONE;
+        // The unit of the last digit of `MAX`.
+        let unit = Decimal::from_parts(1, MIN_SCALE + MAX_PRECISION as i16, false).unwrap();
+
+        assert_eq!(Decimal::MAX.checked_add(unit), None);
+        assert_eq!(Decimal::MAX.saturating_add(unit), Decimal::MAX);
+        assert_eq!(Decimal::MAX.saturating_add(one), Decimal::MAX);
+        assert_eq!(Decimal::MIN.saturating_sub(one), Decimal::MIN);
+        assert_eq!(Decimal::MIN.saturating_sub(unit), Decimal::MIN);
+        assert_eq!(Decimal::MIN.saturating_add(-unit), Decimal::MIN);
+        assert_eq!(unit.saturating_add(Decimal::MAX), Decimal::MAX);
+        assert_eq!((-unit).saturating_sub(Decimal::MAX), Decimal::MIN);
+        assert_eq!(unit.saturating_sub(Decimal::MIN), Decimal::MAX);
+        assert_eq!(Decimal::MAX.saturating_sub(Decimal::MIN), Decimal::MAX);
+        assert_eq!(Decimal::MIN.saturating_sub(Decimal::MAX), Decimal::MIN);
+        assert_eq!(Decimal::MAX.saturating_add(Decimal::MIN), Decimal::ZERO);
+        assert_eq!(Decimal::MAX.saturating_sub(unit), Decimal::MAX - unit);
+
+        assert_eq!(Decimal::MAX.saturating_mul(Decimal::TWO), Decimal::MAX);
+        assert_eq!(Decimal::MAX.saturating_mul(-Decimal::TWO), Decimal::MIN);
+        assert_eq!(Decimal::MIN.saturating_mul(Decimal::MIN), Decimal::MAX);
+        assert_eq!(Decimal::MIN.saturating_mul(Decimal::MAX), Decimal::MIN);
+        assert_eq!(Decimal::MAX.saturating_mul(Decimal::ZERO), Decimal::ZERO);
+        assert_eq!(
+            Decimal::MAX.saturating_mul(Decimal::MIN_POSITIVE),
+            Decimal::MAX * Decimal::MIN_POSITIVE
+        );
+
+        // Without overflow, the results are the same as the checked ones.
+        let values = crate::testing::corpus_random(11, 200);
+        for a in values.iter() {
+            for b in values.iter() {
+                if let Some(sum) = a.checked_add(b) {
+                    assert_eq!(a.saturating_add(b).into_parts(), sum.into_parts());
+                }
+                if let Some(diff) = a.checked_sub(b) {
+                    assert_eq!(a.saturating_sub(b).into_parts(), diff.into_parts());
+                }
+                match a.checked_mul(b) {
+                    Some(prod) => assert_eq!(a.saturating_mul(b).into_parts(), prod.into_parts()),
+                    None => assert!(a.saturating_mul(b) == Decimal::MAX || a.saturating_mul(b) == Decimal::MIN),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stable_scale() {
+        fn assert_stable(
+            val1: &str,
+            val2: &str,
+            add: Option<(u128, i16)>,
+            sub: Option<(u128, i16)>,
+            mul: Option<(u128, i16)>,
+        ) {
+            let var1 = val1.parse::<Decimal>().unwrap();
+            let var2 = val2.parse::<Decimal>().unwrap();
+            let parts = |d: Decimal| (d.int_val(), d.scale());
+            assert_eq!(var1.checked_add_stable(var2).map(parts), add, "{} + {}", val1, val2);
+            assert_eq!(var1.checked_sub_stable(var2).map(parts), sub, "{} - {}", val1, val2);
+            assert_eq!(var1.checked_mul_stable(var2).map(parts), mul, "{} * {}", val1, val2);
+        }
+
+        assert_stable("1.25", "2.50", Some((375, 2)), Some((125, 2)), Some((3125, 3)));
+        assert_stable("1.25", "-1.25", Some((0, 0)), Some((250, 2)), Some((15625, 4)));
+        assert_stable("1.2", "2.34", Some((354, 2)), Some((114, 2)), Some((2808, 3)));
+        assert_stable("1e5", "0", Some((1, -5)), Some((1, -5)), Some((0, 0)));
+        assert_stable("0", "1e5", Some((1, -5)), Some((1, -5)), Some((0, 0)));
+        assert_stable("1.5", "0", Some((15, 1)), Some((15, 1)), Some((0, 0)));
+        assert_stable(
+            "99999999999999999999999999999999999999",
+            "1",
+            None,
+            Some((99999999999999999999999999999999999998, 0)),
+            Some((99999999999999999999999999999999999999, 0)),
+        );
+        assert_stable(
+            "0.99999999999999999999999999999999999999",
+            "0.01",
+            None,
+            Some((98999999999999999999999999999999999999, 38)),
+            Some((99999999999999999999999999999999999999, 40)),
+        );
+        assert_stable("1e30", "1e-10", None, None, Some((1, -20)));
+        assert_stable("1e-100", "1e-100", Some((2, 100)), Some((0, 0)), None);
+    }
+
+    const ROUNDING_MODES: [RoundingMode; 7] = [
+        RoundingMode::HalfUp,
+        RoundingMode::HalfEven,
+        RoundingMode::HalfDown,
+        RoundingMode::Up,
+        RoundingMode::Down,
+        RoundingMode::Ceiling,
+        RoundingMode::Floor,
+    ];
+
+    #[test]
+    fn test_eq_at_scale() {
+        fn assert_at_scale(val1: &str, val2: &str, scale: i16, mode: RoundingMode, cmp: Ordering, diff: &str) {
+            let var1 = val1.parse::<Decimal>().unwrap();
+            let var2 = val2.parse::<Decimal>().unwrap();
+            assert_eq!(var1.eq_at_scale(&var2, scale, mode), cmp == Ordering::Equal);
+            assert_eq!(var1.cmp_at_scale(&var2, scale, mode), cmp);
+            assert_eq!(
+                var1.differs_at_scale(&var2, scale, mode).unwrap().to_string(),
+                diff,
+                "{} - {} at {} {:?}",
+                val1,
+                val2,
+                scale,
+                mode
+            );
+        }
+
+        // Unrounded values differ but are equal at the business scale.
+        assert_at_scale("12.344999", "12.34", 2, RoundingMode::HalfUp, Ordering::Equal, "0");
+        assert_at_scale("12.345001", "12.35", 2, RoundingMode::HalfUp, Ordering::Equal, "0");
+        assert_at_scale("12.345", "12.35", 2, RoundingMode::HalfUp, Ordering::Equal, "0");
+        assert_at_scale("12.345", "12.34", 2, RoundingMode::HalfEven, Ordering::Equal, "0");
+        assert_at_scale("12.345", "12.34", 2, RoundingMode::HalfDown, Ordering::Equal, "0");
+        assert_at_scale("12.341", "12.35", 2, RoundingMode::Up, Ordering::Equal, "0");
+        assert_at_scale("12.349", "12.34", 2, RoundingMode::Down, Ordering::Equal, "0");
+        assert_at_scale("-12.349", "-12.34", 2, RoundingMode::Ceiling, Ordering::Equal, "0");
+        assert_at_scale("-12.341", "-12.35", 2, RoundingMode::Floor, Ordering::Equal, "0");
+        assert_at_scale("1234", "1249", -2, RoundingMode::HalfUp, Ordering::Equal, "0");
+        assert_at_scale("0.001", "0", 2, RoundingMode::HalfUp, Ordering::Equal, "0");
+        assert_at_scale("1e-100", "0", 2, RoundingMode::Down, Ordering::Equal, "0");
+
+        // Values that are not equal at the business scale.
+        assert_at_scale("12.345", "12.34", 2, RoundingMode::HalfUp, Ordering::Greater, "0.01");
+        assert_at_scale("12.34", "12.345", 2, RoundingMode::HalfUp, Ordering::Less, "-0.01");
+        assert_at_scale("1e-100", "0", 2, RoundingMode::Up, Ordering::Greater, "0.01");
+        assert_at_scale("-1e-100", "0", 2, RoundingMode::Floor, Ordering::Less, "-0.01");
+        assert_at_scale("-1e-100", "0", 2, RoundingMode::Ceiling, Ordering::Equal, "0");
+        assert_at_scale("1250", "1249", -2, RoundingMode::HalfUp, Ordering::Greater, "100");
+        assert_at_scale("99.995", "0", 2, RoundingMode::HalfUp, Ordering::Greater, "100");
+    }
+
+    #[test]
+    fn test_eq_at_scale_representation() {
+        // Equal values in different representations never separate after rounding,
+        // and rounding never reverses the order of two values.
+        let mut rng = Lcg::new(0x2545_f491_4f6c_dd1du64);
+
+        for _ in 0..500 {
+            let digits = rng.next() % 20;
+            let int_val = (rng.next() as u128 * rng.next() as u128) % POWERS_10[digits as usize + 1].low();
+            let scale = (rng.next() % 60) as i16 - 20;
+            let negative = rng.next() % 2 == 1;
+            let var1 = Decimal::from_parts(int_val, scale, negative).unwrap();
+
+            let padding = (rng.next() % (MAX_PRECISION as u64 - digits)) as i16;
+            let var2 =
+                Decimal::from_parts(int_val * POWERS_10[padding as usize].low(), scale + padding, negative).unwrap();
+            assert_eq!(var1, var2);
+
+            let var3 = Decimal::from_parts(
+                (rng.next() as u128) << 32,
+                (rng.next() % 60) as i16 - 20,
+                rng.next() % 2 == 1,
+            )
+            .unwrap();
+
+            for scale in -45..=45 {
+                for &mode in ROUNDING_MODES.iter() {
+                    assert!(
+                        var1.eq_at_scale(&var2, scale, mode),
+                        "{} {} {} {:?}",
+                        var1,
+                        var2,
+                        scale,
+                        mode
+                    );
+                    assert!(var1.differs_at_scale(&var2, scale, mode).unwrap().is_zero());
+
+                    let order = var1.cmp_at_scale(&var3, scale, mode);
+                    assert_ne!(
+                        order,
+                        var1.cmp(&var3).reverse(),
+                        "{} {} {} {:?}",
+                        var1,
+                        var3,
+                        scale,
+                        mode
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_scaled_i128() {
+        fn assert_scaled(val: &str, mantissa: i128, scale: i16) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            assert_eq!(decimal.to_scaled_i128(), (mantissa, scale));
+            assert_eq!(Decimal::from_scaled_i128(mantissa, scale).unwrap(), decimal);
+        }
+
+        assert_scaled("0", 0, 0);
+        assert_scaled("123.456", 123456, 3);
+        assert_scaled("-123.456", -123456, 3);
+        assert_scaled("1e-130", 1, 130);
+        assert_scaled("-1e125", -1, -125);
+        assert_scaled("99999999999999999999999999999999999999", MAX_I128_REPR, 0);
+        assert_scaled("-99999999999999999999999999999999999999", -MAX_I128_REPR, 0);
+
+        assert_eq!(
+            Decimal::from_scaled_i128(-1500, 3).unwrap().to_scaled_i128(),
+            (-1500, 3)
+        );
+        assert_eq!(Decimal::from_scaled_i128(0, 5).unwrap().to_scaled_i128(), (0, 0));
+        assert_eq!(
+            Decimal::from_scaled_i128(MAX_I128_REPR + 1, 0),
+            Err(DecimalConvertError::Overflow)
+        );
+        assert_eq!(
+            Decimal::from_scaled_i128(i128::MIN, 0),
+            Err(DecimalConvertError::Overflow)
+        );
+        assert_eq!(
+            Decimal::from_scaled_i128(1, MIN_SCALE - 1),
+            Err(DecimalConvertError::ScaleOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_int_frac_parts() {
+        fn assert_parts(val: &str, frac_scale: u8, mode: RoundingMode, expected: Option<(u128, u128)>) {
+            let decimal = val.parse::<Decimal>().unwrap();
+            assert_eq!(
+                decimal.int_frac_parts(frac_scale, mode),
+                expected,
+                "{} {}",
+                val,
+                frac_scale
+            );
+        }
+
+        assert_parts("0", 6, RoundingMode::HalfUp, Some((0, 0)));
+        assert_parts("0.000", 0, RoundingMode::Up, Some((0, 0)));
+        assert_parts("123.456", 3, RoundingMode::HalfUp, Some((123, 456)));
+        assert_parts("-123.456", 3, RoundingMode::HalfUp, Some((123, 456)));
+        assert_parts("1700000000.123456", 6, RoundingMode::Down, Some((1700000000, 123456)));
+
+        // zero padding
+        assert_parts("123.4", 6, RoundingMode::HalfUp, Some((123, 400000)));
+        assert_parts("123", 6, RoundingMode::HalfUp, Some((123, 0)));
+        assert_parts("1.5", 38, RoundingMode::HalfUp, Some((1, 5 * 10u128.pow(37))));
+        assert_parts("1E+38", 2, RoundingMode::HalfUp, Some((10u128.pow(38), 0)));
+        assert_parts(
+            "12345678901234567890123456789012345678E+1",
+            0,
+            RoundingMode::HalfUp,
+            Some((123456789012345678901234567890123456780, 0)),
+        );
+
+        // rounding and carry
+        assert_parts("123.4565", 3, RoundingMode::HalfUp, Some((123, 457)));
+        assert_parts("123.4565", 3, RoundingMode::HalfEven, Some((123, 456)));
+        assert_parts("123.4565", 3, RoundingMode::Down, Some((123, 456)));
+        assert_parts("123.4561", 3, RoundingMode::Up, Some((123, 457)));
+        assert_parts("0.9999995", 6, RoundingMode::HalfUp, Some((1, 0)));
+        assert_parts("0.9999995", 6, RoundingMode::Down, Some((0, 999999)));
+        assert_parts("-0.9999995", 6, RoundingMode::Ceiling, Some((0, 999999)));
+        assert_parts("-0.9999995", 6, RoundingMode::Floor, Some((1, 0)));
+        assert_parts("99.99", 1, RoundingMode::HalfUp, Some((100, 0)));
+        assert_parts("2.5", 0, RoundingMode::HalfEven, Some((2, 0)));
+        assert_parts("3.5", 0, RoundingMode::HalfEven, Some((4, 0)));
+        assert_parts("3.5", 0, RoundingMode::HalfDown, Some((3, 0)));
+        assert_parts("0.25", 1, RoundingMode::HalfEven, Some((0, 2)));
+        assert_parts("0.35", 1, RoundingMode::HalfEven, Some((0, 4)));
+
+        // scale > precision
+        assert_parts("0.00012", 6, RoundingMode::HalfUp, Some((0, 120)));
+        assert_parts("0.00012", 3, RoundingMode::HalfUp, Some((0, 0)));
+        assert_parts("0.00012", 3, RoundingMode::Up, Some((0, 1)));
+        assert_parts("1E-130", 38, RoundingMode::HalfUp, Some((0, 0)));
+        assert_parts("1E-130", 38, RoundingMode::Ceiling, Some((0, 1)));
+        assert_parts("-1E-130", 38, RoundingMode::Ceiling, Some((0, 0)));
+        assert_parts("-1E-130", 0, RoundingMode::Floor, Some((1, 0)));
+        assert_parts(
+            "0.99999999999999999999999999999999999999",
+            37,
+            RoundingMode::HalfUp,
+            Some((1, 0)),
+        );
+
+        // out of range
+        assert_parts("1E+39", 0, RoundingMode::HalfUp, None);
+        assert_parts(
+            "9.9999999999999999999999999999999999999E125",
+            0,
+            RoundingMode::HalfUp,
+            None,
+        );
+        assert_parts("1.5", 39, RoundingMode::HalfUp, None);
+
+        // consistent with rounding
+        let modes = [
+            RoundingMode::HalfUp,
+            RoundingMode::HalfEven,
+            RoundingMode::Up,
+            RoundingMode::Floor,
+        ];
+        for val in crate::testing::corpus().iter() {
+            for &frac_scale in [0u8, 2, 6, 38].iter() {
+                for &mode in modes.iter() {
+                    let rounded = val.round_with_mode(frac_scale as i16, mode).abs();
+                    match val.int_frac_parts(frac_scale, mode) {
+                        Some(parts) => {
+                            assert_eq!(
+                                rounded.int_frac_parts(frac_scale, RoundingMode::Down),
+                                Some(parts),
+                                "{} {} {:?}",
+                                val,
+                                frac_scale,
+                                mode
+                            );
+                        }
+                        None => assert!(rounded.logb() >= Some(38), "{}", val),
+                    }
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,287 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Diagnostics of arithmetic failures.
+//!
+//! Every checked operation that returns `None` records why it failed in a thread-local slot.
+//! Operations such as `checked_pow` and `ln` are built on the other checked operations, so the
+//! record identifies the innermost operation that failed, together with its operands:
+//!
+//! ```
+//! use decimal_rs::diagnostics::{self, FailureKind, Operation};
+//! use decimal_rs::Decimal;
+//!
+//! let x: Decimal = "1e100".parse().unwrap();
+//! diagnostics::clear();
+//! assert!(x.checked_mul(&x).is_none());
+//!
+//! let failure = diagnostics::last_failure().unwrap();
+//! assert_eq!(failure.op(), Operation::Mul);
+//! assert_eq!(failure.kind(), FailureKind::Overflow);
+//! assert_eq!(failure.lhs(), x);
+//! ```
+
+use crate::decimal::Decimal;
+use core::cell::Cell;
+
+/// The operation that failed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Operation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Sqrt,
+    Pow,
+    Ln,
+    Exp,
+}
+
+/// The reason an operation failed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FailureKind {
+    /// The result is too large to be represented.
+    Overflow,
+    /// The result is too small to be represented, and zero was not acceptable.
+    Underflow,
+    /// The divisor is zero.
+    DivisionByZero,
+    /// The operand is outside of the domain of the operation, e.g. the logarithm of a negative value.
+    InvalidOperand,
+    /// The exact result cannot be represented at the requested scale.
+    Inexact,
+}
+
+/// A record of an arithmetic failure.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FailureInfo {
+    op: Operation,
+    kind: FailureKind,
+    lhs: Decimal,
+    rhs: Option<Decimal>,
+}
+
+impl FailureInfo {
+    /// Returns the operation that failed.
+    #[inline]
+    pub fn op(&self) -> Operation {
+        self.op
+    }
+
+    /// Returns the reason of the failure.
+    #[inline]
+    pub fn kind(&self) -> FailureKind {
+        self.kind
+    }
+
+    /// Returns the first operand, i.e. `self` of the failed operation.
+    #[inline]
+    pub fn lhs(&self) -> Decimal {
+        self.lhs
+    }
+
+    /// Returns the second operand, or `None` for a unary operation.
+    #[inline]
+    pub fn rhs(&self) -> Option<Decimal> {
+        self.rhs
+    }
+}
+
+thread_local! {
+    static LAST_FAILURE: Cell<Option<FailureInfo>> = const { Cell::new(None) };
+}
+
+/// Returns the last arithmetic failure on the current thread.
+#[inline]
+pub fn last_failure() -> Option<FailureInfo> {
+    LAST_FAILURE.with(|f| f.get())
+}
+
+/// Clears the last arithmetic failure on the current thread.
+#[inline]
+pub fn clear() {
+    LAST_FAILURE.with(|f| f.set(None));
+}
+
+#[inline(never)]
+#[cold]
+pub(crate) fn record(op: Operation, kind: FailureKind, lhs: &Decimal, rhs: Option<&Decimal>) {
+    let info = FailureInfo {
+        op,
+        kind,
+        lhs: *lhs,
+        rhs: rhs.copied(),
+    };
+    LAST_FAILURE.with(|f| f.set(Some(info)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn assert_failure<F: FnOnce() -> Option<Decimal>>(
+        f: F,
+        op: Operation,
+        kind: FailureKind,
+        lhs: &str,
+        rhs: Option<&str>,
+    ) {
+        clear();
+        assert!(f().is_none());
+        let failure = last_failure().unwrap();
+        assert_eq!(failure.op(), op);
+        assert_eq!(failure.kind(), kind);
+        assert_eq!(failure.lhs(), parse(lhs));
+        assert_eq!(failure.rhs(), rhs.map(parse));
+    }
+
+    #[test]
+    fn test_failure_sites() {
+        let max = parse("9.9999999999999999999999999999999999999E125");
+        let min = parse("1E-130");
+        let zero = Decimal::ZERO;
+
+        assert_failure(
+            || max.checked_add(max),
+            Operation::Add,
+            FailureKind::Overflow,
+            "9.9999999999999999999999999999999999999E125",
+            Some("9.9999999999999999999999999999999999999E125"),
+        );
+        assert_failure(
+            || max.checked_sub(-max),
+            Operation::Sub,
+            FailureKind::Overflow,
+            "9.9999999999999999999999999999999999999E125",
+            Some("-9.9999999999999999999999999999999999999E125"),
+        );
+        assert_failure(
+            || max.checked_mul(max),
+            Operation::Mul,
+            FailureKind::Overflow,
+            "9.9999999999999999999999999999999999999E125",
+            Some("9.9999999999999999999999999999999999999E125"),
+        );
+        assert_failure(
+            || min.checked_mul_stable(min),
+            Operation::Mul,
+            FailureKind::Underflow,
+            "1E-130",
+            Some("1E-130"),
+        );
+        assert_failure(
+            || parse("1.5").checked_add_stable(min),
+            Operation::Add,
+            FailureKind::Inexact,
+            "1.5",
+            Some("1E-130"),
+        );
+        assert_failure(
+            || Decimal::ONE.checked_div(zero),
+            Operation::Div,
+            FailureKind::DivisionByZero,
+            "1",
+            Some("0"),
+        );
+        assert_failure(
+            || max.checked_div(min),
+            Operation::Div,
+            FailureKind::Overflow,
+            "9.9999999999999999999999999999999999999E125",
+            Some("1E-130"),
+        );
+        assert_failure(
+            || Decimal::ONE.checked_rem(zero),
+            Operation::Rem,
+            FailureKind::DivisionByZero,
+            "1",
+            Some("0"),
+        );
+        assert_failure(
+            || parse("-4").sqrt(),
+            Operation::Sqrt,
+            FailureKind::InvalidOperand,
+            "-4",
+            None,
+        );
+        assert_failure(
+            || zero.checked_pow(&parse("-3")),
+            Operation::Pow,
+            FailureKind::DivisionByZero,
+            "0",
+            Some("-3"),
+        );
+        assert_failure(
+            || parse("-3").checked_pow(&parse("2.2")),
+            Operation::Pow,
+            FailureKind::InvalidOperand,
+            "-3",
+            Some("2.2"),
+        );
+        assert_failure(|| zero.ln(), Operation::Ln, FailureKind::InvalidOperand, "0", None);
+        assert_failure(
+            || parse("-1").ln(),
+            Operation::Ln,
+            FailureKind::InvalidOperand,
+            "-1",
+            None,
+        );
+        assert_failure(
+            || parse("291").exp(),
+            Operation::Exp,
+            FailureKind::Overflow,
+            "291",
+            None,
+        );
+    }
+
+    #[test]
+    fn test_failure_origin() {
+        // The overflow of `checked_pow` originates from an internal multiplication.
+        clear();
+        assert!(parse("1e100").checked_pow(&parse("2")).is_none());
+        let failure = last_failure().unwrap();
+        assert_eq!(failure.op(), Operation::Mul);
+        assert_eq!(failure.kind(), FailureKind::Overflow);
+
+        // The overflow of `exp` inside `checked_pow` is recorded.
+        clear();
+        assert!(parse("10").checked_pow(&parse("200.5")).is_none());
+        assert!(last_failure().is_some());
+    }
+
+    #[test]
+    fn test_clear() {
+        clear();
+        assert!(Decimal::ONE.checked_div(Decimal::ZERO).is_none());
+        assert!(last_failure().is_some());
+        clear();
+        assert!(last_failure().is_none());
+
+        assert!(Decimal::ONE.checked_add(Decimal::ONE).is_some());
+        assert!(last_failure().is_none());
+
+        std::thread::spawn(|| {
+            assert!(Decimal::ONE.checked_div(Decimal::ZERO).is_none());
+        })
+        .join()
+        .unwrap();
+        assert!(last_failure().is_none());
+    }
+}
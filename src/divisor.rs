@@ -0,0 +1,209 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Repeated division by the same divisor.
+
+use crate::decimal::{Decimal, MAX_PRECISION};
+use crate::u256::{POWERS_10, U256};
+
+/// A divisor prepared for dividing many decimals, e.g. amounts divided by an exchange rate.
+///
+/// The precision of the divisor and a 256-bit reciprocal of its mantissa are computed once, so
+/// [`PreparedDivisor::divide`] replaces the wide division of [`Decimal::checked_div`] with two
+/// multiplications and a correction step. The quotients are the same as those of `checked_div`,
+/// scales included.
+///
+/// ```
+/// use decimal_rs::{Decimal, PreparedDivisor};
+///
+/// let rate: Decimal = "1.0873".parse().unwrap();
+/// let prepared = PreparedDivisor::new(&rate).unwrap();
+/// for amount in ["100", "-25.5", "0.01"].iter() {
+///     let amount: Decimal = amount.parse().unwrap();
+///     assert_eq!(prepared.divide(&amount), amount.checked_div(rate));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PreparedDivisor {
+    divisor: Decimal,
+    int_val: u128,
+    scale: i16,
+    negative: bool,
+    precision: u8,
+    // `floor((2^256 - 1) / int_val)`
+    reciprocal: U256,
+}
+
+impl PreparedDivisor {
+    /// Prepares `divisor`, returning `None` if it is zero.
+    #[inline]
+    pub fn new(divisor: &Decimal) -> Option<PreparedDivisor> {
+        if divisor.is_zero() {
+            return None;
+        }
+
+        let (int_val, scale, negative) = divisor.into_parts();
+        let reciprocal = U256::from_u128(u128::MAX, u128::MAX).div_rem(int_val).0;
+        Some(PreparedDivisor {
+            divisor: *divisor,
+            int_val,
+            scale,
+            negative,
+            precision: divisor.precision(),
+            reciprocal,
+        })
+    }
+
+    /// Returns the divisor.
+    #[inline]
+    pub fn divisor(&self) -> &Decimal {
+        &self.divisor
+    }
+
+    /// Computes `dividend / divisor`, returning `None` if overflow occurred.
+    ///
+    /// The result is the same as `dividend.checked_div(divisor)`.
+    #[inline]
+    pub fn divide(&self, dividend: &Decimal) -> Option<Decimal> {
+        if dividend.is_zero() {
+            return Some(Decimal::ZERO);
+        }
+
+        let dividend_precision = dividend.precision();
+        let (int_val, scale, negative) = dividend.into_parts();
+
+        // The same scaling as `checked_div`, so the scaled dividend is less than `10^76`.
+        let (scaled, shift_precision) = if self.precision > dividend_precision {
+            let p = MAX_PRECISION + (self.precision - dividend_precision) as u32;
+            (POWERS_10[p as usize] * int_val, self.precision - dividend_precision)
+        } else {
+            (U256::mul128(int_val, POWERS_10[MAX_PRECISION as usize].low()), 0)
+        };
+
+        let (quotient, rounding_digits) = self.div_round_once(scaled);
+        let scale = scale - self.scale + MAX_PRECISION as i16 + shift_precision as i16 + rounding_digits;
+
+        let result = Decimal::adjust_scale(quotient, scale, negative ^ self.negative);
+        if result.is_none() {
+            record_failure!(Div, Overflow, dividend, Some(&self.divisor));
+        }
+        result
+    }
+
+    /// Computes `dividend / int_val` rounded half up once, as `U256::div128_round_once` does.
+    #[inline]
+    fn div_round_once(&self, dividend: U256) -> (U256, i16) {
+        // As `reciprocal > 2^256 / int_val - 2` and `dividend < 2^255`, the estimate is at most one
+        // less than the truncated quotient, so a single correction suffices. Should it not, fall back to
+        // the exact division.
+        let mut quotient = dividend.mul_high(&self.reciprocal);
+        let mut rem = dividend.wrapping_sub(quotient * self.int_val);
+        if rem >= self.int_val {
+            quotient = quotient + 1;
+            rem = rem - self.int_val;
+            if rem >= self.int_val {
+                return dividend.div128_round_once(self.int_val);
+            }
+        }
+
+        U256::round_quotient(quotient, rem.low(), self.int_val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_prepared_divisor() {
+        fn assert_divide(dividend: &str, divisor: &str, expected: &str) {
+            let dividend = parse(dividend);
+            let prepared = PreparedDivisor::new(&parse(divisor)).unwrap();
+            assert_eq!(prepared.divide(&dividend), Some(parse(expected)));
+            assert_eq!(
+                prepared.divide(&dividend).map(Decimal::into_parts),
+                dividend.checked_div(parse(divisor)).map(Decimal::into_parts)
+            );
+        }
+
+        assert_divide("1", "3", "0.33333333333333333333333333333333333333");
+        assert_divide("2", "3", "0.66666666666666666666666666666666666667");
+        assert_divide("-2", "3", "-0.66666666666666666666666666666666666667");
+        assert_divide("2", "-3", "-0.66666666666666666666666666666666666667");
+        assert_divide("7.5", "2.5", "3");
+        assert_divide("100", "1.0873", "91.970937183849903430515956957601397958");
+        assert_divide("0", "7", "0");
+        assert_divide("12345", "1", "12345");
+        assert_divide(
+            "99999999999999999999999999999999999999",
+            "99999999999999999999999999999999999999",
+            "1",
+        );
+        assert_divide(
+            "1",
+            "99999999999999999999999999999999999999",
+            "1.0000000000000000000000000000000000000E-38",
+        );
+        assert_divide("1e-130", "1e10", "0");
+        assert_divide(
+            "999999999999999999E100",
+            "4294967295E100",
+            "232830643.70807973731031635247876782726",
+        );
+
+        assert!(PreparedDivisor::new(&Decimal::ZERO).is_none());
+        let prepared = PreparedDivisor::new(&parse("1e-10")).unwrap();
+        assert_eq!(prepared.divisor(), &parse("1e-10"));
+        assert_eq!(prepared.divide(&parse("1e120")), None);
+    }
+
+    #[test]
+    fn test_prepared_divisor_differential() {
+        let parts = |val: Option<Decimal>| val.map(Decimal::into_parts);
+
+        let mut values = crate::testing::corpus();
+        values.extend(crate::testing::corpus_random(0xd1, 2000));
+        let mut divisors: Vec<Decimal> = values.iter().step_by(7).copied().collect();
+        divisors.extend(
+            [
+                "1",
+                "3",
+                "7",
+                "-0.0725",
+                "1.0873",
+                "99999999999999999999999999999999999999",
+                "17014118346046923173168730371588410572",
+            ]
+            .iter()
+            .map(|s| parse(s)),
+        );
+
+        for divisor in divisors.iter().filter(|d| !d.is_zero()) {
+            let prepared = PreparedDivisor::new(divisor).unwrap();
+            for dividend in values.iter() {
+                assert_eq!(
+                    parts(prepared.divide(dividend)),
+                    parts(dividend.checked_div(divisor)),
+                    "{} / {}",
+                    dividend,
+                    divisor
+                );
+            }
+        }
+    }
+}
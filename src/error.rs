@@ -14,8 +14,9 @@
 
 //! Decimal error definitions.
 
-use std::fmt;
-use std::num::ParseFloatError;
+use crate::decimal::Decimal;
+use core::fmt;
+use core::num::ParseFloatError;
 
 /// An error which can be returned when parsing a decimal.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -28,6 +29,11 @@ pub enum DecimalParseError {
     Overflow,
     /// Decimal is underflow.
     Underflow,
+    /// Input exceeds the limits of [`ParseLimits`](crate::ParseLimits).
+    LimitExceeded,
+    /// Input has more significant digits than the precision, see
+    /// [`Decimal::from_str_exact`](crate::Decimal::from_str_exact).
+    Inexact,
 }
 
 impl fmt::Display for DecimalParseError {
@@ -38,6 +44,8 @@ impl fmt::Display for DecimalParseError {
             DecimalParseError::Invalid => write!(f, "invalid number"),
             DecimalParseError::Overflow => write!(f, "numeric overflow"),
             DecimalParseError::Underflow => write!(f, "numeric underflow"),
+            DecimalParseError::LimitExceeded => write!(f, "input exceeds the parse limits"),
+            DecimalParseError::Inexact => write!(f, "number has more significant digits than the precision"),
         }
     }
 }
@@ -49,6 +57,13 @@ pub enum DecimalConvertError {
     Invalid,
     /// Decimal is overflowed.
     Overflow,
+    /// Decimal has more significant digits than allowed.
+    TooManyDigits,
+    /// Decimal can't be converted without losing digits.
+    Inexact,
+    /// The scale of the parts of a decimal is out of range, see
+    /// [`Decimal::from_parts`](crate::Decimal::from_parts).
+    ScaleOutOfRange,
 }
 
 impl fmt::Display for DecimalConvertError {
@@ -57,6 +72,9 @@ impl fmt::Display for DecimalConvertError {
         match &self {
             DecimalConvertError::Invalid => write!(f, "invalid number"),
             DecimalConvertError::Overflow => write!(f, "numeric overflow"),
+            DecimalConvertError::TooManyDigits => write!(f, "too many significant digits"),
+            DecimalConvertError::Inexact => write!(f, "inexact conversion"),
+            DecimalConvertError::ScaleOutOfRange => write!(f, "scale out of range"),
         }
     }
 }
@@ -68,14 +86,18 @@ pub enum DecimalFormatError {
     Format(fmt::Error),
     /// Decimal is out of range.
     OutOfRange,
+    /// The pattern of [`Decimal::format_with_pattern`](crate::Decimal::format_with_pattern) is invalid.
+    InvalidPattern,
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for DecimalFormatError {
     #[inline]
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match &self {
             DecimalFormatError::Format(e) => Some(e),
             DecimalFormatError::OutOfRange => None,
+            DecimalFormatError::InvalidPattern => None,
         }
     }
 }
@@ -86,6 +108,88 @@ impl fmt::Display for DecimalFormatError {
         match &self {
             DecimalFormatError::Format(e) => write!(f, "{}", e),
             DecimalFormatError::OutOfRange => write!(f, "Data value out of range"),
+            DecimalFormatError::InvalidPattern => write!(f, "invalid number format pattern"),
+        }
+    }
+}
+
+/// An error which can be returned by a mathematical function, e.g. [`Decimal::try_pow`](crate::Decimal::try_pow).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum DecimalMathError {
+    /// The function is undefined for the operand, e.g. the logarithm of a negative value or `0^-2`.
+    DomainError,
+    /// The result is too large to be represented.
+    Overflow,
+    /// The result isn't zero, but too small to be represented.
+    Underflow,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecimalMathError {}
+
+impl fmt::Display for DecimalMathError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            DecimalMathError::DomainError => write!(f, "invalid argument for the function"),
+            DecimalMathError::Overflow => write!(f, "numeric overflow"),
+            DecimalMathError::Underflow => write!(f, "numeric underflow"),
+        }
+    }
+}
+
+/// An error which can be returned by an arithmetic operation of [`DecimalOps`](crate::arith::DecimalOps).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum DecimalArithError {
+    /// The divisor is zero.
+    DivisionByZero,
+    /// The result is too large to be represented.
+    Overflow,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecimalArithError {}
+
+impl fmt::Display for DecimalArithError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            DecimalArithError::DivisionByZero => write!(f, "division by zero"),
+            DecimalArithError::Overflow => write!(f, "numeric overflow"),
+        }
+    }
+}
+
+/// An error which can be returned by [`Decimal::try_normalize_to_scale`], reporting why the requested scale
+/// can't be reached.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum NormalizeError {
+    /// The requested scale is out of `[-126, 130]`.
+    ScaleOutOfRange,
+    /// A non-zero digit would be dropped, `closest` is the value at the nearest reachable scale.
+    NonZeroDigit { closest: Decimal },
+    /// The mantissa would exceed 38 digits, `closest` is the value at the nearest reachable scale.
+    MantissaFull { closest: Decimal },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NormalizeError {}
+
+impl fmt::Display for NormalizeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            NormalizeError::ScaleOutOfRange => write!(f, "scale is out of range"),
+            NormalizeError::NonZeroDigit { closest } => {
+                write!(
+                    f,
+                    "non-zero digit beyond the scale, stopped at scale {}",
+                    closest.scale()
+                )
+            }
+            NormalizeError::MantissaFull { closest } => {
+                write!(f, "too many digits for the scale, stopped at scale {}", closest.scale())
+            }
         }
     }
 }
@@ -94,8 +198,11 @@ impl From<DecimalParseError> for DecimalConvertError {
     #[inline]
     fn from(e: DecimalParseError) -> Self {
         match e {
-            DecimalParseError::Empty | DecimalParseError::Invalid => DecimalConvertError::Invalid,
+            DecimalParseError::Empty | DecimalParseError::Invalid | DecimalParseError::LimitExceeded => {
+                DecimalConvertError::Invalid
+            }
             DecimalParseError::Overflow | DecimalParseError::Underflow => DecimalConvertError::Overflow,
+            DecimalParseError::Inexact => DecimalConvertError::Inexact,
         }
     }
 }
@@ -0,0 +1,336 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decimals with a scale fixed by the type.
+//!
+//! A [`FixedScale<S>`] always has exactly `S` digits after the decimal point, so the type system
+//! tells amounts of scale 2 from rates of scale 8. Values of the same scale can be added and
+//! subtracted exactly, while values of different scales don't mix:
+//!
+//! ```compile_fail
+//! use decimal_rs::{Decimal, FixedScale};
+//! use std::convert::TryFrom;
+//!
+//! let amount = FixedScale::<2>::try_from(Decimal::from(1)).unwrap();
+//! let rate = FixedScale::<8>::try_from(Decimal::from(1)).unwrap();
+//! let sum = amount + rate;
+//! ```
+//!
+//! ```compile_fail
+//! use decimal_rs::{Decimal, FixedScale};
+//! use std::convert::TryFrom;
+//!
+//! let amount: FixedScale<2> = FixedScale::<8>::try_from(Decimal::from(1)).unwrap();
+//! ```
+//!
+//! The scale of a product can't be computed in the type on stable Rust, so [`FixedScale::mul`]
+//! takes the scale of the result as a parameter and rounds the exact product into it:
+//!
+//! ```
+//! use decimal_rs::{Decimal, FixedScale, RoundingMode};
+//! use std::convert::TryFrom;
+//!
+//! let amount = FixedScale::<2>::try_from("100.25".parse::<Decimal>().unwrap()).unwrap();
+//! let rate = FixedScale::<8>::try_from("1.08641234".parse::<Decimal>().unwrap()).unwrap();
+//! let exact: FixedScale<10> = amount.mul(rate, RoundingMode::HalfEven).unwrap();
+//! let converted: FixedScale<2> = amount.mul(rate, RoundingMode::HalfEven).unwrap();
+//! assert_eq!(exact.to_string(), "108.9128370850");
+//! assert_eq!(converted.to_string(), "108.91");
+//! ```
+
+use crate::context::round_to_scale;
+use crate::decimal::{Buf, Decimal, RoundingMode, MAX_PRECISION, MAX_SCALE, MIN_SCALE};
+use crate::error::DecimalConvertError;
+use crate::u256::U256;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::{Add, AddAssign, Deref, Neg, Sub, SubAssign};
+
+/// A decimal with exactly `S` digits after the decimal point, where `S` is in `MIN_SCALE..=MAX_SCALE`.
+///
+/// A negative `S` keeps `-S` zeros before the decimal point, e.g. `FixedScale<-3>` counts thousands.
+/// The value is read through `Deref<Target = Decimal>`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedScale<const S: i16>(Decimal);
+
+impl<const S: i16> FixedScale<S> {
+    /// The number of digits after the decimal point.
+    pub const SCALE: i16 = S;
+
+    /// Rounds `val` to `S` digits after the decimal point with `mode`.
+    ///
+    /// Fails with `DecimalConvertError::Overflow` if the result needs more than `MAX_PRECISION` digits,
+    /// or if `S` is out of range.
+    #[inline]
+    pub fn quantize(val: &Decimal, mode: RoundingMode) -> Result<FixedScale<S>, DecimalConvertError> {
+        let (int_val, scale, negative) = val.into_parts();
+        Self::round(U256::from(int_val), scale, negative, mode)
+    }
+
+    /// Returns the value as a decimal.
+    #[inline]
+    pub const fn value(&self) -> Decimal {
+        self.0
+    }
+
+    /// Adds two values, returning `None` if overflow occurred.
+    #[inline]
+    pub fn checked_add(&self, other: FixedScale<S>) -> Option<FixedScale<S>> {
+        self.0.checked_add_stable(other.0)?.with_exact_scale(S).map(FixedScale)
+    }
+
+    /// Subtracts one value from another, returning `None` if overflow occurred.
+    #[inline]
+    pub fn checked_sub(&self, other: FixedScale<S>) -> Option<FixedScale<S>> {
+        self.0.checked_sub_stable(other.0)?.with_exact_scale(S).map(FixedScale)
+    }
+
+    /// Multiplies two values, rounding the exact product of scale `S + B` once to the scale `C` with `mode`.
+    ///
+    /// Fails with `DecimalConvertError::Overflow` if the result needs more than `MAX_PRECISION` digits,
+    /// or if `C` is out of range.
+    #[inline]
+    pub fn mul<const B: i16, const C: i16>(
+        &self,
+        other: FixedScale<B>,
+        mode: RoundingMode,
+    ) -> Result<FixedScale<C>, DecimalConvertError> {
+        let int_val = U256::mul128(self.0.int_val(), other.0.int_val());
+        let negative = self.0.is_sign_negative() ^ other.0.is_sign_negative();
+        FixedScale::round(int_val, S + B, negative, mode)
+    }
+
+    #[inline]
+    fn round(
+        int_val: U256,
+        scale: i16,
+        negative: bool,
+        mode: RoundingMode,
+    ) -> Result<FixedScale<S>, DecimalConvertError> {
+        if !(MIN_SCALE..=MAX_SCALE).contains(&S) {
+            return Err(DecimalConvertError::Overflow);
+        }
+        round_to_scale(int_val, scale, negative, MAX_PRECISION as u8, S, mode).map(FixedScale)
+    }
+}
+
+impl<const S: i16> TryFrom<Decimal> for FixedScale<S> {
+    type Error = DecimalConvertError;
+
+    /// Represents `val` at exactly `S` digits after the decimal point.
+    ///
+    /// Fails with `DecimalConvertError::Inexact` if a non-zero digit would be dropped, or with
+    /// `DecimalConvertError::Overflow` if the result needs more than `MAX_PRECISION` digits.
+    #[inline]
+    fn try_from(val: Decimal) -> Result<Self, Self::Error> {
+        let result = FixedScale::quantize(&val, RoundingMode::Down)?;
+        if result.0 != val {
+            return Err(DecimalConvertError::Inexact);
+        }
+        Ok(result)
+    }
+}
+
+impl<const S: i16> From<FixedScale<S>> for Decimal {
+    #[inline]
+    fn from(val: FixedScale<S>) -> Self {
+        val.0
+    }
+}
+
+impl<const S: i16> Deref for FixedScale<S> {
+    type Target = Decimal;
+
+    #[inline]
+    fn deref(&self) -> &Decimal {
+        &self.0
+    }
+}
+
+impl<const S: i16> Add for FixedScale<S> {
+    type Output = FixedScale<S>;
+
+    #[inline]
+    fn add(self, other: FixedScale<S>) -> Self::Output {
+        match self.checked_add(other) {
+            Some(sum) => sum,
+            None => panic!("Addition overflowed"),
+        }
+    }
+}
+
+impl<const S: i16> Sub for FixedScale<S> {
+    type Output = FixedScale<S>;
+
+    #[inline]
+    fn sub(self, other: FixedScale<S>) -> Self::Output {
+        match self.checked_sub(other) {
+            Some(diff) => diff,
+            None => panic!("Subtraction overflowed"),
+        }
+    }
+}
+
+impl<const S: i16> AddAssign for FixedScale<S> {
+    #[inline]
+    fn add_assign(&mut self, other: FixedScale<S>) {
+        *self = *self + other;
+    }
+}
+
+impl<const S: i16> SubAssign for FixedScale<S> {
+    #[inline]
+    fn sub_assign(&mut self, other: FixedScale<S>) {
+        *self = *self - other;
+    }
+}
+
+impl<const S: i16> Neg for FixedScale<S> {
+    type Output = FixedScale<S>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        FixedScale(-self.0)
+    }
+}
+
+impl<const S: i16> fmt::Display for FixedScale<S> {
+    /// Formats the value with exactly `S` digits after the decimal point, or none if `S` is negative.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use core::fmt::Write;
+
+        let mut buf = Buf::new();
+        if self.0.is_zero() && S > 0 {
+            // a zero has no scale to pad from
+            write!(&mut buf, "0.{:0<1$}", "", S as usize).map_err(|_| fmt::Error)?;
+        } else {
+            write!(&mut buf, "{:.*}", S.max(0) as usize, self.0.abs()).map_err(|_| fmt::Error)?;
+        }
+        let str = unsafe { core::str::from_utf8_unchecked(buf.as_slice()) };
+        f.pad_integral(!self.0.is_sign_negative(), "", str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    type Amount = FixedScale<2>;
+    type Rate = FixedScale<8>;
+
+    #[test]
+    fn test_construct() {
+        fn assert_try_from(val: &str, expected: Result<(u128, i16), DecimalConvertError>) {
+            let result = Amount::try_from(parse(val)).map(|v| (v.int_val(), v.scale()));
+            assert_eq!(result, expected, "{}", val);
+        }
+
+        assert_try_from("1", Ok((100, 2)));
+        assert_try_from("-1.5", Ok((150, 2)));
+        assert_try_from("1.25", Ok((125, 2)));
+        assert_try_from("1.250", Ok((125, 2)));
+        assert_try_from("0", Ok((0, 0)));
+        assert_try_from("1.255", Err(DecimalConvertError::Inexact));
+        assert_try_from("1e36", Err(DecimalConvertError::Overflow));
+        assert_try_from("9.9e35", Ok((99 * 10u128.pow(36), 2)));
+
+        fn assert_quantize(val: &str, mode: RoundingMode, expected: &str) {
+            let result = Amount::quantize(&parse(val), mode).unwrap();
+            assert_eq!(result.to_string(), expected, "{} {:?}", val, mode);
+            assert!(result.is_zero() || result.scale() == 2);
+        }
+
+        assert_quantize("1.255", RoundingMode::HalfUp, "1.26");
+        assert_quantize("1.255", RoundingMode::HalfEven, "1.26");
+        assert_quantize("1.245", RoundingMode::HalfEven, "1.24");
+        assert_quantize("-1.255", RoundingMode::Floor, "-1.26");
+        assert_quantize("-1.255", RoundingMode::Down, "-1.25");
+        assert_quantize("0.004", RoundingMode::HalfUp, "0.00");
+        assert_quantize("1e-100", RoundingMode::Up, "0.01");
+        assert_quantize("12", RoundingMode::Up, "12.00");
+        assert_eq!(
+            Amount::quantize(&parse("1e36"), RoundingMode::Down),
+            Err(DecimalConvertError::Overflow)
+        );
+        assert_eq!(
+            FixedScale::<200>::quantize(&Decimal::ONE, RoundingMode::Down),
+            Err(DecimalConvertError::Overflow)
+        );
+
+        let thousands = FixedScale::<-3>::quantize(&parse("12345.6"), RoundingMode::HalfUp).unwrap();
+        assert_eq!(thousands.to_string(), "12000");
+        assert_eq!((thousands.int_val(), thousands.scale()), (12, -3));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Amount::try_from(parse("10.25")).unwrap();
+        let b = Amount::try_from(parse("0.75")).unwrap();
+        assert_eq!((a + b).to_string(), "11.00");
+        assert_eq!((a + b).scale(), 2);
+        assert_eq!((b - a).to_string(), "-9.50");
+        assert_eq!((a - a).to_string(), "0.00");
+        assert_eq!((-a).to_string(), "-10.25");
+
+        let mut c = a;
+        c += b;
+        c -= Amount::try_from(parse("1")).unwrap();
+        assert_eq!(c.to_string(), "10.00");
+        assert_eq!(Decimal::from(c), parse("10"));
+        assert_eq!(c.value(), parse("10"));
+
+        let max = Amount::try_from(parse("999999999999999999999999999999999999.99")).unwrap();
+        assert_eq!(max.checked_add(b), None);
+        assert_eq!((-max).checked_sub(b), None);
+        assert_eq!(
+            max.checked_sub(b).unwrap().to_string(),
+            "999999999999999999999999999999999999.24"
+        );
+
+        let rate = Rate::try_from(parse("1.08641234")).unwrap();
+        let exact: FixedScale<10> = a.mul(rate, RoundingMode::HalfEven).unwrap();
+        assert_eq!(exact.to_string(), "11.1357264850");
+        let rounded: Amount = a.mul(rate, RoundingMode::HalfEven).unwrap();
+        assert_eq!(rounded.to_string(), "11.14");
+        let rounded: Amount = (-a).mul(rate, RoundingMode::Ceiling).unwrap();
+        assert_eq!(rounded.to_string(), "-11.13");
+        let zero: Amount = a.mul(Rate::default(), RoundingMode::Up).unwrap();
+        assert_eq!(zero.to_string(), "0.00");
+
+        // the exact product has more than 38 digits, but is rounded only once
+        let big = Amount::try_from(parse("123456789012345678901234567890.12")).unwrap();
+        let rate = Rate::try_from(parse("0.50000001")).unwrap();
+        let product: Amount = big.mul(rate, RoundingMode::HalfUp).unwrap();
+        assert_eq!(product.to_string(), "61728395740740729574074072957.41");
+        let overflow: Result<FixedScale<10>, _> = big.mul(rate, RoundingMode::HalfUp);
+        assert_eq!(overflow, Err(DecimalConvertError::Overflow));
+    }
+
+    #[test]
+    fn test_display() {
+        let a = Amount::try_from(parse("1.5")).unwrap();
+        assert_eq!(a.to_string(), "1.50");
+        assert_eq!(format!("{:>8}", a), "    1.50");
+        assert_eq!(format!("{:+08}", -a), "-0001.50");
+        assert_eq!(Amount::default().to_string(), "0.00");
+        assert_eq!(Rate::try_from(parse("-2")).unwrap().to_string(), "-2.00000000");
+        assert_eq!(FixedScale::<0>::try_from(parse("7")).unwrap().to_string(), "7");
+        assert_eq!(FixedScale::<-2>::try_from(parse("700")).unwrap().to_string(), "700");
+    }
+}
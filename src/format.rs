@@ -0,0 +1,508 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reusable formatter of many decimals with the same options.
+
+use crate::decimal::Decimal;
+use crate::error::DecimalFormatError;
+use alloc::string::String;
+use core::fmt;
+
+/// Options of [`DecimalFormatter`], the same knobs as the format spec of `Display`.
+///
+/// The default options format a decimal as `{}` does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// The count of digits after the decimal point, as `{:.2}`.
+    pub precision: Option<usize>,
+    /// The minimum count of characters, as `{:10}`.
+    pub width: Option<usize>,
+    /// The character to pad to `width` with, as `{:*>10}`.
+    pub fill: char,
+    /// The alignment in `width`, `None` for the right alignment of numbers, as `{:<10}`.
+    pub align: Option<fmt::Alignment>,
+    /// Whether to write `+` before a non-negative value, as `{:+}`.
+    pub sign_plus: bool,
+    /// Whether to pad with zeros between the sign and the digits, as `{:010}`.
+    pub sign_aware_zero_pad: bool,
+}
+
+impl Default for FormatOptions {
+    #[inline]
+    fn default() -> Self {
+        FormatOptions {
+            precision: None,
+            width: None,
+            fill: ' ',
+            align: None,
+            sign_plus: false,
+            sign_aware_zero_pad: false,
+        }
+    }
+}
+
+/// Formats many decimals with the same [`FormatOptions`].
+///
+/// The output is byte-identical to `Display` with the same format spec, but the options are resolved
+/// once in [`DecimalFormatter::new`], and the digits go through a scratch buffer on the stack, so a
+/// formatter can be shared across threads.
+///
+/// ```
+/// use decimal_rs::{Decimal, DecimalFormatter, FormatOptions};
+///
+/// let formatter = DecimalFormatter::new(FormatOptions {
+///     precision: Some(2),
+///     width: Some(10),
+///     ..FormatOptions::default()
+/// });
+///
+/// let mut out = String::new();
+/// for val in ["1.005", "-12345.6"].iter() {
+///     let val: Decimal = val.parse().unwrap();
+///     formatter.format_into(&val, &mut out).unwrap();
+///     assert_eq!(out, format!("{:10.2}", val));
+///     out.clear();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DecimalFormatter {
+    options: FormatOptions,
+    /// The UTF-8 encoding of the fill character, and its length.
+    fill: [u8; 4],
+    fill_len: usize,
+    /// The share of the padding before the value in halves, 0 for the left, 1 for the center and 2 for
+    /// the right alignment.
+    pre_numerator: usize,
+}
+
+impl DecimalFormatter {
+    /// Creates a formatter with `options`.
+    #[inline]
+    pub fn new(options: FormatOptions) -> DecimalFormatter {
+        let mut fill = [0; 4];
+        let fill_len = options.fill.encode_utf8(&mut fill).len();
+        let pre_numerator = match options.align {
+            Some(fmt::Alignment::Left) => 0,
+            Some(fmt::Alignment::Center) => 1,
+            Some(fmt::Alignment::Right) | None => 2,
+        };
+
+        DecimalFormatter {
+            options,
+            fill,
+            fill_len,
+            pre_numerator,
+        }
+    }
+
+    /// Returns the options of the formatter.
+    #[inline]
+    pub fn options(&self) -> &FormatOptions {
+        &self.options
+    }
+
+    /// Appends the formatted `value` to `out`.
+    #[inline]
+    pub fn format_into(&self, value: &Decimal, out: &mut String) -> Result<(), DecimalFormatError> {
+        out.reserve(self.estimate_len(value));
+        self.format_to_writer(value, out)
+    }
+
+    /// Writes the formatted `value` to `w`.
+    #[inline]
+    pub fn format_to_writer<W: fmt::Write>(&self, value: &Decimal, mut w: W) -> Result<(), DecimalFormatError> {
+        let mut body = Body::new();
+        body.format(value, self.options.precision)?;
+        let digits = body.as_str();
+
+        let sign = if value.is_sign_negative() {
+            "-"
+        } else if self.options.sign_plus {
+            "+"
+        } else {
+            ""
+        };
+
+        let len = sign.len() + digits.len();
+        let width = match self.options.width {
+            Some(width) if width > len => width,
+            _ => {
+                w.write_str(sign)?;
+                w.write_str(digits)?;
+                return Ok(());
+            }
+        };
+
+        let pad = width - len;
+        if self.options.sign_aware_zero_pad {
+            w.write_str(sign)?;
+            write_repeated(&mut w, "0", pad)?;
+            w.write_str(digits)?;
+        } else {
+            let fill = unsafe { core::str::from_utf8_unchecked(&self.fill[..self.fill_len]) };
+            let pre = pad * self.pre_numerator / 2;
+            write_repeated(&mut w, fill, pre)?;
+            w.write_str(sign)?;
+            w.write_str(digits)?;
+            write_repeated(&mut w, fill, pad - pre)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns an upper bound of the length in bytes of the formatted `value`, for pre-allocating an
+    /// output buffer.
+    ///
+    /// It counts the digits of `value` without formatting it, so it includes the trailing zeros that
+    /// `Display` omits, and a carry of the rounding to `precision`.
+    #[inline]
+    pub fn estimate_len(&self, value: &Decimal) -> usize {
+        // the length without padding
+        let len = if value.is_zero() {
+            self.options.sign_plus as usize + 1
+        } else {
+            let digits = value.precision() as usize;
+            let scale = value.scale() as isize;
+            let sign = (value.is_sign_negative() || self.options.sign_plus) as usize;
+            match self.options.precision {
+                Some(prec) if scale <= 0 => sign + digits + (-scale) as usize + (prec > 0) as usize + prec,
+                // 1 more for a carry
+                Some(prec) => sign + (digits as isize - scale).max(1) as usize + 1 + (prec > 0) as usize + prec,
+                None if scale <= 0 => sign + digits + (-scale) as usize,
+                None => sign + digits.max(scale as usize + 1) + 1,
+            }
+        };
+
+        match self.options.width {
+            // the padding is less than `width` fill characters
+            Some(width) if !self.options.sign_aware_zero_pad => len.max(width * self.fill_len),
+            Some(width) => len.max(width),
+            None => len,
+        }
+    }
+}
+
+/// The maximum length of the formatted value without the sign and padding, the same as `Display`.
+const MAX_BODY_LEN: usize = 256;
+
+const ZEROS: [u8; MAX_BODY_LEN] = [b'0'; MAX_BODY_LEN];
+
+const DIGIT_PAIRS: &[u8; 200] = b"\
+    0001020304050607080910111213141516171819\
+    2021222324252627282930313233343536373839\
+    4041424344454647484950515253545556575859\
+    6061626364656667686970717273747576777879\
+    8081828384858687888990919293949596979899";
+
+/// The formatted digits of a value without the sign and padding, laid out as `Display` does.
+struct Body {
+    buf: [u8; MAX_BODY_LEN],
+    len: usize,
+}
+
+impl Body {
+    #[inline]
+    fn new() -> Body {
+        Body {
+            buf: [0; MAX_BODY_LEN],
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn as_str(&self) -> &str {
+        unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len]) }
+    }
+
+    #[inline]
+    fn push(&mut self, bytes: &[u8]) -> Result<(), DecimalFormatError> {
+        let end = self.len + bytes.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(DecimalFormatError::Format(fmt::Error))?
+            .copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+
+    #[inline]
+    fn push_zeros(&mut self, count: usize) -> Result<(), DecimalFormatError> {
+        self.push(ZEROS.get(..count).ok_or(DecimalFormatError::Format(fmt::Error))?)
+    }
+
+    /// Formats `value` rounded to `precision`, the same as `Decimal::fmt_internal` without the sign.
+    #[inline]
+    fn format(&mut self, value: &Decimal, precision: Option<usize>) -> Result<(), DecimalFormatError> {
+        if value.is_zero() {
+            return self.push(b"0");
+        }
+
+        let dec = match precision {
+            Some(prec) => value.round(prec as i16),
+            None => *value,
+        };
+        let (int_val, scale, _) = dec.into_parts();
+
+        let mut digit_buf = [0; 40];
+        let start = write_u128(int_val, &mut digit_buf);
+        let digits = &digit_buf[start..];
+
+        if scale <= 0 {
+            self.push(digits)?;
+            self.push_zeros(-scale as usize)?;
+            if let Some(prec) = precision {
+                if prec != 0 {
+                    self.push(b".")?;
+                    self.push_zeros(prec)?;
+                }
+            }
+            return Ok(());
+        }
+
+        let scale = scale as usize;
+        let len = digits.len();
+        if len <= scale {
+            self.push(b"0.")?;
+            self.push_zeros(scale - len)?;
+            return self.push(digits);
+        }
+
+        let (int_digits, frac_digits) = digits.split_at(len - scale);
+        self.push(int_digits)?;
+        match precision {
+            Some(prec) => {
+                self.push(b".")?;
+                if prec > frac_digits.len() {
+                    self.push(frac_digits)?;
+                    self.push_zeros(prec - frac_digits.len())?;
+                } else {
+                    self.push(&frac_digits[..prec])?;
+                }
+            }
+            None => {
+                let zero_num = frac_digits.iter().rev().take_while(|ch| **ch == b'0').count();
+                if zero_num < frac_digits.len() {
+                    self.push(b".")?;
+                    self.push(&frac_digits[..frac_digits.len() - zero_num])?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the digits of `val` at the end of `buf`, returning the index of the first digit.
+///
+/// `val` is split into chunks of 19 digits, so that the most of the divisions are on `u64`.
+#[inline]
+fn write_u128(mut val: u128, buf: &mut [u8; 40]) -> usize {
+    const CHUNK: u128 = 10_000_000_000_000_000_000;
+
+    let mut pos = buf.len();
+    while val > u64::MAX as u128 {
+        let quotient = val / CHUNK;
+        let chunk = (val - quotient * CHUNK) as u64;
+        val = quotient;
+        let start = write_u64(chunk, &mut buf[..pos]);
+        // pads the chunk with leading zeros
+        buf[pos - 19..start].copy_from_slice(&ZEROS[..start + 19 - pos]);
+        pos -= 19;
+    }
+
+    write_u64(val as u64, &mut buf[..pos])
+}
+
+/// Writes the digits of `val` at the end of `buf`, returning the index of the first digit.
+#[inline]
+fn write_u64(mut val: u64, buf: &mut [u8]) -> usize {
+    let mut pos = buf.len();
+    while val >= 100 {
+        let pair = (val % 100) as usize * 2;
+        val /= 100;
+        pos -= 2;
+        buf[pos..pos + 2].copy_from_slice(&DIGIT_PAIRS[pair..pair + 2]);
+    }
+
+    if val >= 10 {
+        let pair = val as usize * 2;
+        pos -= 2;
+        buf[pos..pos + 2].copy_from_slice(&DIGIT_PAIRS[pair..pair + 2]);
+    } else {
+        pos -= 1;
+        buf[pos] = b'0' + val as u8;
+    }
+
+    pos
+}
+
+/// Writes `s` `count` times.
+#[inline]
+fn write_repeated<W: fmt::Write>(w: &mut W, s: &str, count: usize) -> fmt::Result {
+    for _ in 0..count {
+        w.write_str(s)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(options: FormatOptions, val: &Decimal) -> String {
+        let formatter = DecimalFormatter::new(options);
+        let mut out = String::new();
+        formatter.format_into(val, &mut out).unwrap();
+        assert!(formatter.estimate_len(val) >= out.len(), "{:?} {:?}", options, out);
+        out
+    }
+
+    #[test]
+    fn test_write_u128() {
+        fn assert_digits(val: u128) {
+            let mut buf = [0; 40];
+            let start = write_u128(val, &mut buf);
+            assert_eq!(std::str::from_utf8(&buf[start..]).unwrap(), val.to_string());
+        }
+
+        assert_digits(0);
+        assert_digits(9);
+        assert_digits(10);
+        assert_digits(u64::MAX as u128);
+        assert_digits(u64::MAX as u128 + 1);
+        assert_digits(10_000_000_000_000_000_000_000_000_000_000_000_001);
+        assert_digits(99_999_999_999_999_999_999_999_999_999_999_999_999);
+        assert_digits(u128::MAX);
+    }
+
+    #[test]
+    fn test_format_into() {
+        fn assert_format(options: FormatOptions, val: &str, expected: &str) {
+            let val = val.parse::<Decimal>().unwrap();
+            assert_eq!(format(options, &val), expected);
+        }
+
+        let default = FormatOptions::default();
+        assert_format(default, "0", "0");
+        assert_format(default, "-1.50", "-1.5");
+        assert_format(default, "1E+10", "10000000000");
+        assert_format(default, "1E-10", "0.0000000001");
+
+        let options = FormatOptions {
+            precision: Some(2),
+            width: Some(10),
+            ..default
+        };
+        assert_format(options, "1.005", "      1.01");
+        assert_format(options, "-12345.6", " -12345.60");
+        assert_format(options, "123456789.999", "123456790.00");
+
+        let options = FormatOptions {
+            fill: '*',
+            align: Some(fmt::Alignment::Center),
+            width: Some(8),
+            sign_plus: true,
+            ..default
+        };
+        assert_format(options, "1.5", "**+1.5**");
+        assert_format(options, "-12.5", "*-12.5**");
+
+        let options = FormatOptions {
+            fill: '→',
+            align: Some(fmt::Alignment::Left),
+            width: Some(5),
+            ..default
+        };
+        assert_format(options, "1", "1→→→→");
+
+        let options = FormatOptions {
+            width: Some(8),
+            sign_aware_zero_pad: true,
+            ..default
+        };
+        assert_format(options, "-1.5", "-00001.5");
+    }
+
+    #[test]
+    fn test_display_parity() {
+        let aligns = [
+            None,
+            Some(fmt::Alignment::Left),
+            Some(fmt::Alignment::Center),
+            Some(fmt::Alignment::Right),
+        ];
+        let values = crate::testing::corpus();
+
+        for &precision in [None, Some(0), Some(2), Some(40)].iter() {
+            for &width in [None, Some(12), Some(60)].iter() {
+                for &fill in [' ', 'é'].iter() {
+                    for align in aligns.iter() {
+                        for &sign_plus in [false, true].iter() {
+                            for &sign_aware_zero_pad in [false, true].iter() {
+                                let options = FormatOptions {
+                                    precision,
+                                    width,
+                                    fill,
+                                    align: *align,
+                                    sign_plus,
+                                    sign_aware_zero_pad,
+                                };
+                                for val in values.iter() {
+                                    assert_eq!(format(options, val), display(&options, val), "{:?}", options);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Formats `val` with `Display` and the format spec of `options`.
+    fn display(options: &FormatOptions, val: &Decimal) -> String {
+        macro_rules! display {
+            ($fmt: literal) => {
+                match (options.width, options.precision) {
+                    (Some(w), Some(p)) => format!(concat!("{:", $fmt, "w$.p$}"), val, w = w, p = p),
+                    (Some(w), None) => format!(concat!("{:", $fmt, "w$}"), val, w = w),
+                    (None, Some(p)) => format!(concat!("{:", $fmt, ".p$}"), val, p = p),
+                    (None, None) => format!(concat!("{:", $fmt, "}"), val),
+                }
+            };
+        }
+
+        // the fill is a literal of the format spec, so it's replaced afterwards
+        let padded = match (options.align, options.sign_plus, options.sign_aware_zero_pad) {
+            (None, false, false) => display!(""),
+            (None, false, true) => display!("0"),
+            (None, true, false) => display!("+"),
+            (None, true, true) => display!("+0"),
+            (Some(fmt::Alignment::Left), false, false) => display!("\u{1}<"),
+            (Some(fmt::Alignment::Left), false, true) => display!("\u{1}<0"),
+            (Some(fmt::Alignment::Left), true, false) => display!("\u{1}<+"),
+            (Some(fmt::Alignment::Left), true, true) => display!("\u{1}<+0"),
+            (Some(fmt::Alignment::Center), false, false) => display!("\u{1}^"),
+            (Some(fmt::Alignment::Center), false, true) => display!("\u{1}^0"),
+            (Some(fmt::Alignment::Center), true, false) => display!("\u{1}^+"),
+            (Some(fmt::Alignment::Center), true, true) => display!("\u{1}^+0"),
+            (Some(fmt::Alignment::Right), false, false) => display!("\u{1}>"),
+            (Some(fmt::Alignment::Right), false, true) => display!("\u{1}>0"),
+            (Some(fmt::Alignment::Right), true, false) => display!("\u{1}>+"),
+            (Some(fmt::Alignment::Right), true, true) => display!("\u{1}>+0"),
+        };
+        padded
+            .replace(' ', &options.fill.to_string())
+            .replace('\u{1}', &options.fill.to_string())
+    }
+}
@@ -0,0 +1,510 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fractions, e.g. the mixed fraction `3 3/8` or the product with `254/10000`.
+
+use crate::decimal::{Decimal, RoundingMode, MAX_PRECISION};
+use crate::u256::{POWERS_10, U256};
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+
+/// The largest scale of a fractional part which may be approximated by a fraction other than `0/1`.
+///
+/// A fractional part of a larger scale is less than `10^(38 - 58)`, which is closer to `0/1` than to
+/// `1/q` for any `q <= u64::MAX`. And the distances compared by `best_fraction` are less than
+/// `10^57 * u64::MAX`, which fits in `U256`.
+const MAX_FRACTION_SCALE: i16 = 57;
+
+/// Returns `|num / den - p / q| * den * q`.
+#[inline]
+fn distance(num: U256, den: U256, p: u64, q: u64) -> U256 {
+    let x = num * q as u128;
+    let y = den * p as u128;
+    match x.cmp(&y) {
+        Ordering::Less => y.checked_sub(x),
+        _ => x.checked_sub(y),
+    }
+    .expect("no underflow")
+}
+
+/// Returns the fraction `p / q` closest to `num / den` with `q <= max_den`, preferring the smaller
+/// denominator on a tie, where `num < den`.
+///
+/// It walks the convergents of the continued fraction of `num / den`, i.e. down the Stern–Brocot
+/// tree, until the next denominator exceeds `max_den`. Then the closest fraction is either the last
+/// convergent or the semiconvergent with the largest denominator within `max_den`.
+#[inline]
+fn best_fraction(num: U256, den: U256, max_den: u64) -> (u64, u64) {
+    debug_assert!(num < den && max_den > 0);
+
+    let (mut p0, mut q0, mut p1, mut q1) = (0u64, 1u64, 1u64, 0u64);
+    let (mut n, mut d) = (num, den);
+    loop {
+        let (a, r) = n.div_rem(d);
+        if q1 != 0 && a > U256::from((max_den - q0) / q1) {
+            break;
+        }
+
+        let a = a.low() as u64;
+        let (p2, q2) = (p0 + a * p1, q0 + a * q1);
+        p0 = p1;
+        q0 = q1;
+        p1 = p2;
+        q1 = q2;
+
+        if r == 0 {
+            return (p1, q1);
+        }
+        n = d;
+        d = r;
+    }
+
+    let k = (max_den - q0) / q1;
+    let (p2, q2) = (p0 + k * p1, q0 + k * q1);
+    let d1 = distance(num, den, p1, q1) * q2 as u128;
+    let d2 = distance(num, den, p2, q2) * q1 as u128;
+    match d2.cmp(&d1) {
+        Ordering::Less => (p2, q2),
+        Ordering::Equal if q2 < q1 => (p2, q2),
+        _ => (p1, q1),
+    }
+}
+
+impl Decimal {
+    /// Returns the fraction `p / q` closest to the fractional part of `|self|` with `q <= max_den`,
+    /// where `p` may be equal to `q`.
+    #[inline]
+    fn best_fraction_part(&self, max_den: u64) -> (u64, u64) {
+        let scale = self.scale();
+        if self.is_zero() || scale <= 0 || scale > MAX_FRACTION_SCALE {
+            return (0, 1);
+        }
+
+        let den = POWERS_10[scale as usize];
+        let num = if scale <= MAX_PRECISION as i16 {
+            U256::from(self.int_val() % den.low())
+        } else {
+            U256::from(self.int_val())
+        };
+
+        best_fraction(num, den, max_den)
+    }
+
+    /// Splits `self` into a whole number and the closest fraction with a denominator of at most
+    /// `max_denominator`, i.e. `(whole, numerator, denominator)`, where `numerator < denominator`.
+    ///
+    /// `self` is approximately `whole + numerator / denominator` for a positive value, or
+    /// `whole - numerator / denominator` for a negative value, so the sign is on the whole part.
+    /// A fraction which is closest to one is carried into the whole part, and an integer has the
+    /// fraction `0/1`. On a tie, the fraction with the smaller denominator is chosen, e.g. `0.125` with
+    /// at most quarters is `0/1` rather than `1/4`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_denominator` is zero.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n: Decimal = "3.375".parse().unwrap();
+    /// assert_eq!(n.to_mixed_fraction(8), (Decimal::from(3), 3, 8));
+    /// assert_eq!(n.to_mixed_fraction(4), (Decimal::from(3), 1, 3));
+    /// ```
+    #[inline]
+    pub fn to_mixed_fraction(&self, max_denominator: u32) -> (Decimal, u32, u32) {
+        assert!(max_denominator > 0, "max_denominator is zero");
+
+        let whole = self.trunc(0);
+        let (p, q) = self.best_fraction_part(max_denominator as u64);
+        if p == q {
+            let whole = if self.is_sign_negative() {
+                whole - Decimal::ONE
+            } else {
+                whole + Decimal::ONE
+            };
+            return (whole, 0, 1);
+        }
+
+        (whole, p as u32, q as u32)
+    }
+
+    /// Formats `self` as a mixed fraction with a denominator of at most `max_denominator`, e.g.
+    /// `"3 3/8"`, `"-2 1/2"` or `"-1/4"`, and an integer without a fraction, e.g. `"3"`.
+    ///
+    /// See [`Decimal::to_mixed_fraction`] for the rounding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_denominator` is zero.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let n: Decimal = "-2.5".parse().unwrap();
+    /// assert_eq!(n.to_mixed_fraction_string(64), "-2 1/2");
+    /// ```
+    #[inline]
+    pub fn to_mixed_fraction_string(&self, max_denominator: u32) -> String {
+        let (whole, numer, denom) = self.to_mixed_fraction(max_denominator);
+        if numer == 0 {
+            whole.to_string()
+        } else if whole.is_zero() {
+            let sign = if self.is_sign_negative() { "-" } else { "" };
+            format!("{}{}/{}", sign, numer, denom)
+        } else {
+            format!("{} {}/{}", whole, numer, denom)
+        }
+    }
+
+    /// Returns the fraction `numerator / denominator` closest to `self` with a denominator of at most
+    /// `max_denom`, or `None` if the numerator overflowed `i128`.
+    ///
+    /// The fraction is found from the continued fraction of `self`, as [`Decimal::to_mixed_fraction`]
+    /// does, and the sign is on the numerator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_denom` is zero.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let pi: Decimal = "3.14159265".parse().unwrap();
+    /// assert_eq!(pi.best_rational(1000), Some((355, 113)));
+    /// assert_eq!((-pi).best_rational(10), Some((-22, 7)));
+    /// ```
+    #[inline]
+    pub fn best_rational(&self, max_denom: u64) -> Option<(i128, u64)> {
+        assert!(max_denom > 0, "max_denom is zero");
+
+        let whole = i128::try_from(self.trunc(0)).ok()?;
+        let (p, q) = self.best_fraction_part(max_denom);
+        let numer = whole.checked_mul(q as i128)?;
+        let numer = if self.is_sign_negative() {
+            numer.checked_sub(p as i128)?
+        } else {
+            numer.checked_add(p as i128)?
+        };
+
+        Some((numer, q))
+    }
+
+    /// Computes `self * numer / denom` rounded once with `mode`,
+    /// returning `None` if `denom` is zero or the result overflowed.
+    ///
+    /// The product is exact in 256 bits and the quotient is rounded to 38 significant digits, so a
+    /// conversion by an exact ratio, e.g. inches to meters, is as accurate as the mode allows.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, RoundingMode};
+    ///
+    /// let meters: Decimal = "1".parse().unwrap();
+    /// let inches = meters.mul_fraction(10000, 254, RoundingMode::Down).unwrap();
+    /// assert_eq!(inches, "39.370078740157480314960629921259842519".parse::<Decimal>().unwrap());
+    /// ```
+    #[inline]
+    pub fn mul_fraction(&self, numer: i128, denom: i128, mode: RoundingMode) -> Option<Decimal> {
+        if denom == 0 {
+            return None;
+        }
+        if self.is_zero() || numer == 0 {
+            return Some(Decimal::ZERO);
+        }
+
+        let negative = self.is_sign_negative() ^ (numer < 0) ^ (denom < 0);
+        let product = U256::mul128(self.int_val(), numer.unsigned_abs());
+        let denom = denom.unsigned_abs();
+
+        // The product is shifted, so the quotient has at least 38 digits, and the shifted product is
+        // less than `10^77`.
+        let product_digits = product.count_digits();
+        let denom_digits = U256::from(denom).count_digits();
+        let shift = (MAX_PRECISION + denom_digits).saturating_sub(product_digits);
+        let dividend = if shift > 0 {
+            product * POWERS_10[shift as usize]
+        } else {
+            product
+        };
+        let (quotient, rem) = dividend.div_rem(denom);
+        let scale = self.scale() + shift as i16;
+
+        // The rounding needs the digit after the 38th one, and a sticky digit after it keeps the
+        // remainder. A quotient of 40 digits or more has room for it in its last digit, which is
+        // dropped by the rounding.
+        let (int_val, scale) = if quotient.count_digits() < 40 {
+            let (digit, rem) = (rem * 10).div_rem(denom);
+            ((quotient * 10 + digit.low()) * 10 + (rem != 0) as u128, scale + 2)
+        } else if rem != 0 && quotient % 10 == 0 {
+            (quotient + 1, scale)
+        } else {
+            (quotient, scale)
+        };
+        Decimal::adjust_scale_with_mode(int_val, scale, negative, mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lcg;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_to_mixed_fraction() {
+        fn assert_fraction(val: &str, max_denominator: u32, expected: (&str, u32, u32), expected_str: &str) {
+            let val = parse(val);
+            let (whole, numer, denom) = val.to_mixed_fraction(max_denominator);
+            assert_eq!(
+                (whole, numer, denom),
+                (parse(expected.0), expected.1, expected.2),
+                "{}",
+                val
+            );
+            assert_eq!(val.to_mixed_fraction_string(max_denominator), expected_str);
+        }
+
+        assert_fraction("0", 8, ("0", 0, 1), "0");
+        assert_fraction("3", 8, ("3", 0, 1), "3");
+        assert_fraction("-3.000", 8, ("-3", 0, 1), "-3");
+        assert_fraction("1E10", 8, ("1E10", 0, 1), "10000000000");
+        assert_fraction("3.375", 8, ("3", 3, 8), "3 3/8");
+        assert_fraction("3.375", 64, ("3", 3, 8), "3 3/8");
+        assert_fraction("3.375", 2, ("3", 1, 2), "3 1/2");
+        assert_fraction("3.375", 1, ("3", 0, 1), "3");
+        assert_fraction("0.3333333333", 100, ("0", 1, 3), "1/3");
+        assert_fraction("0.33333333333333333333333333333333333333", 100, ("0", 1, 3), "1/3");
+        assert_fraction("-2.5", 64, ("-2", 1, 2), "-2 1/2");
+        assert_fraction("-0.25", 64, ("0", 1, 4), "-1/4");
+        assert_fraction("3.14159265358979323846", 10, ("3", 1, 7), "3 1/7");
+        assert_fraction("3.14159265358979323846", 1000, ("3", 16, 113), "3 16/113");
+        assert_fraction("0.015625", 64, ("0", 1, 64), "1/64");
+        assert_fraction("0.015625", 32, ("0", 0, 1), "0");
+        assert_fraction("0.0000001", u32::MAX, ("0", 1, 10_000_000), "1/10000000");
+        assert_fraction("1E-130", u32::MAX, ("0", 0, 1), "0");
+        assert_fraction("1.23E-50", u32::MAX, ("0", 0, 1), "0");
+
+        // carried into the whole part
+        assert_fraction("2.999", 8, ("3", 0, 1), "3");
+        assert_fraction("-2.999", 8, ("-3", 0, 1), "-3");
+        assert_fraction("-0.999", 8, ("-1", 0, 1), "-1");
+        assert_fraction("0.99999999999999999999999999999999999999", u32::MAX, ("1", 0, 1), "1");
+
+        // ties go to the smaller denominator
+        assert_fraction("0.125", 4, ("0", 0, 1), "0");
+        assert_fraction("0.25", 2, ("0", 0, 1), "0");
+        assert_fraction("-1.25", 2, ("-1", 0, 1), "-1");
+        assert_fraction("-1.75", 2, ("-2", 0, 1), "-2");
+        assert_fraction("0.875", 4, ("1", 0, 1), "1");
+        assert_fraction("0.0625", 8, ("0", 0, 1), "0");
+        assert_fraction("0.1875", 8, ("0", 1, 5), "1/5");
+    }
+
+    #[test]
+    fn test_best_rational() {
+        fn assert_rational(val: &str, max_denom: u64, expected: Option<(i128, u64)>) {
+            assert_eq!(parse(val).best_rational(max_denom), expected, "{} {}", val, max_denom);
+        }
+
+        assert_rational("3.14159265", 1000, Some((355, 113)));
+        assert_rational("-3.14159265", 1000, Some((-355, 113)));
+        assert_rational("3.14159265", 10, Some((22, 7)));
+        assert_rational("3.14159265", 1, Some((3, 1)));
+        assert_rational("0", 100, Some((0, 1)));
+        assert_rational("2", 100, Some((2, 1)));
+        assert_rational("-0.5", 100, Some((-1, 2)));
+        assert_rational("0.9999999", 100, Some((1, 1)));
+        assert_rational("-0.9999999", 100, Some((-1, 1)));
+        assert_rational("1E-30", 1000, Some((0, 1)));
+        assert_rational("0.0254", 10000, Some((127, 5000)));
+        assert_rational("0.33333333333333333333333333333333333333", u64::MAX, Some((1, 3)));
+        assert_rational(
+            "0.1234567890123456789",
+            u64::MAX,
+            Some((1234567890123456789, 10000000000000000000)),
+        );
+        assert_rational(
+            "-99999999999999999999999999999999999999",
+            1,
+            Some((-99999999999999999999999999999999999999, 1)),
+        );
+        assert_rational("1E+38", 1, Some((10i128.pow(38), 1)));
+        assert_rational("1E+39", 1, None);
+        assert_rational("1E+100", 1, None);
+        assert_rational("-1E+125", 1000, None);
+        assert_rational("1E+37", 10, Some((10i128.pow(37), 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "max_denom is zero")]
+    fn test_best_rational_zero_denominator() {
+        parse("1.5").best_rational(0);
+    }
+
+    #[test]
+    fn test_mul_fraction() {
+        const MODES: [RoundingMode; 7] = [
+            RoundingMode::HalfUp,
+            RoundingMode::HalfEven,
+            RoundingMode::HalfDown,
+            RoundingMode::Up,
+            RoundingMode::Down,
+            RoundingMode::Ceiling,
+            RoundingMode::Floor,
+        ];
+
+        fn assert_mul(val: &str, numer: i128, denom: i128, expected: [&str; 7]) {
+            for (mode, expected) in MODES.iter().zip(expected.iter()) {
+                let result = parse(val).mul_fraction(numer, denom, *mode);
+                assert_eq!(result, Some(parse(expected)), "{} {}/{} {:?}", val, numer, denom, mode);
+            }
+        }
+
+        fn assert_exact(val: &str, numer: i128, denom: i128, expected: &str) {
+            assert_mul(val, numer, denom, [expected; 7]);
+        }
+
+        // inches to meters are exact
+        assert_exact("1", 254, 10000, "0.0254");
+        assert_exact("12", 254, 10000, "0.3048");
+        assert_exact("-36", 254, 10000, "-0.9144");
+        assert_exact("1E-120", 254, 10000, "2.54E-122");
+        assert_exact("1E+100", -254, -10000, "2.54E+98");
+        assert_exact("0", 254, 10000, "0");
+        assert_exact("12.5", 0, 10000, "0");
+
+        // meters to inches are rounded
+        let up = "39.370078740157480314960629921259842520";
+        let down = "39.370078740157480314960629921259842519";
+        assert_mul("1", 10000, 254, [up, up, up, up, down, up, down]);
+        let up = "-39.370078740157480314960629921259842520";
+        let down = "-39.370078740157480314960629921259842519";
+        assert_mul("-1", 10000, 254, [up, up, up, up, down, down, up]);
+        assert_mul("1", -10000, 254, [up, up, up, up, down, down, up]);
+        let up = "3.9370078740157480314960629921259842520E+101";
+        let down = "3.9370078740157480314960629921259842519E+101";
+        assert_mul("1E+100", 10000, 254, [up, up, up, up, down, up, down]);
+
+        // a tie, and slightly above or below it
+        let val = "24691357802469135780246913578024691357";
+        let up = "12345678901234567890123456789012345679";
+        let down = "12345678901234567890123456789012345678";
+        let half = 5 * 10i128.pow(37);
+        let denom = 10i128.pow(38);
+        assert_mul(val, half, denom, [up, down, down, up, down, up, down]);
+        assert_mul(val, half + 1, denom, [up, up, up, up, down, up, down]);
+        assert_mul(val, half - 1, denom, [down, down, down, up, down, up, down]);
+
+        // the product exceeds 128 bits
+        assert_exact(
+            "99999999999999999999999999999999999999",
+            99999999999999999999999999999999999999,
+            99999999999999999999999999999999999999,
+            "99999999999999999999999999999999999999",
+        );
+        assert_exact(
+            "99999999999999999999999999999999999999",
+            i128::MAX,
+            i128::MAX,
+            "99999999999999999999999999999999999999",
+        );
+        let up = "-1.7014118346046923173168730371588410573E+38";
+        let down = "-1.7014118346046923173168730371588410572E+38";
+        assert_mul("3", i128::MIN, 3, [up, up, up, up, down, down, up]);
+
+        // the same as a multiplication by an integer
+        for val in crate::testing::corpus().iter() {
+            for &n in [3i128, -7, 254, 10000, 123456789012345678901234567].iter() {
+                let rhs = Decimal::try_from(n).unwrap();
+                assert_eq!(
+                    val.mul_fraction(n, 1, RoundingMode::HalfUp),
+                    val.checked_mul(rhs),
+                    "{} {}",
+                    val,
+                    n
+                );
+            }
+        }
+
+        // underflow and overflow
+        let zero = "0";
+        let min = "1E-130";
+        assert_mul("1E-130", 1, 3, [zero, zero, zero, min, zero, min, zero]);
+        assert_eq!(parse("1").mul_fraction(1, 0, RoundingMode::HalfUp), None);
+        assert_eq!(parse("0").mul_fraction(1, 0, RoundingMode::HalfUp), None);
+        assert_eq!(parse("9E+125").mul_fraction(2, 1, RoundingMode::HalfUp), None);
+        assert_eq!(
+            parse("1E+100").mul_fraction(1, 10i128.pow(30), RoundingMode::HalfUp),
+            Some(parse("1E+70"))
+        );
+        assert_eq!(
+            parse("1E-120").mul_fraction(1, 10i128.pow(30), RoundingMode::Down),
+            Some(Decimal::ZERO)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "max_denominator is zero")]
+    fn test_to_mixed_fraction_zero_denominator() {
+        parse("1.5").to_mixed_fraction(0);
+    }
+
+    #[test]
+    fn test_to_mixed_fraction_optimal() {
+        let mut rng = Lcg::new(0x5eedu64);
+
+        for _ in 0..2000 {
+            let scale = rng.next() % 18 + 1;
+            let den = 10u128.pow(scale as u32);
+            let num = rng.next() as u128 % den;
+            let max_denominator = (rng.next() % 64 + 1) as u32;
+
+            let val = Decimal::from_parts(num, scale as i16, false).unwrap();
+            let (whole, p, q) = val.to_mixed_fraction(max_denominator);
+            let (p, q) = if whole == Decimal::ONE {
+                (1, 1)
+            } else {
+                (p as u128, q as u128)
+            };
+
+            // `|num / den - p / q| * den * q`
+            let dist = |p: u128, q: u128| (num * q).max(p * den) - (num * q).min(p * den);
+            let best = dist(p, q);
+            for q2 in 1..=max_denominator as u128 {
+                let p_floor = num * q2 / den;
+                for p2 in [p_floor, p_floor + 1].iter() {
+                    let other = dist(*p2, q2);
+                    // `best / q <= other / q2`, and on a tie `q <= q2`
+                    assert!(
+                        best * q2 <= other * q,
+                        "{} {} {}/{} {}/{}",
+                        val,
+                        max_denominator,
+                        p,
+                        q,
+                        p2,
+                        q2
+                    );
+                    if best * q2 == other * q {
+                        assert!(q <= q2 || *p2 * q == p * q2);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,807 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Interval arithmetic with outward rounding.
+
+use crate::decimal::{Decimal, RoundingMode, MAX_PRECISION, MAX_SCALE};
+use crate::u256::{POWERS_10, U256};
+use core::cmp::Ordering;
+use core::fmt;
+
+/// Returns the mantissa of `val` widened to 38 digits and its scale, so that one unit of the mantissa
+/// is one unit in the last place of `val`.
+#[inline]
+fn widen(val: &Decimal) -> (u128, i16) {
+    let e = (MAX_PRECISION - val.precision() as u32).min((Decimal::MAX_REPR_SCALE - val.scale()).max(0) as u32);
+    (val.int_val() * POWERS_10[e as usize].low(), val.scale() + e as i16)
+}
+
+/// Returns the neighbor of a non-zero `val` at 38 digits, away from zero if `away` is `true`, otherwise
+/// toward zero, returning `None` if overflow occurred.
+#[inline]
+fn step_magnitude(val: &Decimal, away: bool) -> Option<Decimal> {
+    debug_assert!(!val.is_zero());
+
+    let (int_val, scale) = widen(val);
+    let (int_val, scale) = if away {
+        (U256::from(int_val + 1), scale)
+    } else {
+        // `10^37 - 0.1` is rounded down to `10^38 - 1` at the next scale, any other to `int_val - 1`.
+        (U256::mul128(int_val, 10) - 1, scale + 1)
+    };
+    Decimal::adjust_scale_with_mode(int_val, scale, val.is_sign_negative(), RoundingMode::Down)
+}
+
+/// Returns the smallest decimal at 38 digits greater than `val`.
+#[inline]
+fn next_up(val: &Decimal) -> Option<Decimal> {
+    if val.is_zero() {
+        return Some(unsafe { Decimal::from_parts_unchecked(1, MAX_SCALE, false) });
+    }
+    step_magnitude(val, !val.is_sign_negative())
+}
+
+/// Returns the largest decimal at 38 digits less than `val`.
+#[inline]
+fn next_down(val: &Decimal) -> Option<Decimal> {
+    if val.is_zero() {
+        return Some(unsafe { Decimal::from_parts_unchecked(1, MAX_SCALE, true) });
+    }
+    step_magnitude(val, val.is_sign_negative())
+}
+
+/// Returns the square root of a non-negative `val` rounded toward negative and positive infinity.
+#[inline]
+fn sqrt_bounds(val: &Decimal) -> Option<(Decimal, Decimal)> {
+    // Compares `r^2` with `val` exactly.
+    let cmp_square = |r: &Decimal| {
+        U256::cmp_scaled(
+            U256::mul128(r.int_val(), r.int_val()),
+            2 * r.scale() as i32,
+            U256::from(val.int_val()),
+            val.scale() as i32,
+        )
+    };
+
+    // `sqrt` is within a few units in the last place, so steps to the largest `lo` with `lo^2 <= val`.
+    let mut lo = val.sqrt()?;
+    while cmp_square(&lo) == Ordering::Greater {
+        lo = next_down(&lo)?;
+    }
+    let mut hi = next_up(&lo)?;
+    while cmp_square(&hi) != Ordering::Greater {
+        lo = hi;
+        hi = next_up(&lo)?;
+    }
+
+    if cmp_square(&lo) == Ordering::Equal {
+        Some((lo, lo))
+    } else {
+        Some((lo, hi))
+    }
+}
+
+/// The bound of the error of [`Decimal::exp`] and [`Decimal::ln`] in units in the last place.
+///
+/// Their error measured against references of 60 digits is below 20 units, e.g. `ln` of a value far
+/// from one sums a few rounded terms, so the bound leaves a wide margin.
+const FUNC_ERROR_ULPS: u128 = 100;
+
+/// Widens an approximation `val` of a transcendental function to the bounds of the exact value.
+#[inline]
+fn func_bounds(val: Decimal) -> Option<(Decimal, Decimal)> {
+    let error = unsafe { Decimal::from_parts_unchecked(FUNC_ERROR_ULPS, widen(&val).1, false) };
+    let lo = val.add_with_mode(&-error, RoundingMode::Floor)?;
+    let hi = val.add_with_mode(&error, RoundingMode::Ceiling)?;
+    Some((lo, hi))
+}
+
+/// A closed interval `[lo, hi]` of decimals, which contains the exact result of the operations on its
+/// endpoints.
+///
+/// Every operation rounds its lower endpoint toward negative infinity and its upper endpoint toward
+/// positive infinity, so the exact value is always in the interval. The basic operations round each
+/// endpoint once, so for points `a` and `b`, `a + b`, `a - b`, `a * b` and `a / b` are one unit in the
+/// last place wide at most.
+///
+/// ```
+/// use decimal_rs::{Decimal, DecimalInterval};
+///
+/// let one = DecimalInterval::point(Decimal::ONE);
+/// let three = DecimalInterval::point(Decimal::from(3));
+/// let third = one.checked_div(&three).unwrap();
+/// assert_eq!(third.lo().to_string(), "0.33333333333333333333333333333333333333");
+/// assert_eq!(third.hi().to_string(), "0.33333333333333333333333333333333333334");
+///
+/// let one_again = third.checked_mul(&three).unwrap();
+/// assert!(one_again.contains(&Decimal::ONE));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DecimalInterval {
+    lo: Decimal,
+    hi: Decimal,
+}
+
+impl DecimalInterval {
+    /// Creates the interval `[lo, hi]`, returning `None` if `lo > hi`.
+    #[inline]
+    pub fn new(lo: Decimal, hi: Decimal) -> Option<DecimalInterval> {
+        if lo > hi {
+            None
+        } else {
+            Some(DecimalInterval { lo, hi })
+        }
+    }
+
+    /// Creates the interval `[val, val]`.
+    #[inline]
+    pub const fn point(val: Decimal) -> DecimalInterval {
+        DecimalInterval { lo: val, hi: val }
+    }
+
+    /// Creates the interval `[center - |error|, center + |error|]`, rounded outward,
+    /// returning `None` if overflow occurred.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, DecimalInterval};
+    ///
+    /// let center: Decimal = "1.5".parse().unwrap();
+    /// let error: Decimal = "0.01".parse().unwrap();
+    /// let val = DecimalInterval::with_error(center, error).unwrap();
+    /// assert_eq!(val.lo(), "1.49".parse::<Decimal>().unwrap());
+    /// assert_eq!(val.hi(), "1.51".parse::<Decimal>().unwrap());
+    /// ```
+    #[inline]
+    pub fn with_error(center: Decimal, error: Decimal) -> Option<DecimalInterval> {
+        let error = error.abs();
+        let lo = center.add_with_mode(&-error, RoundingMode::Floor)?;
+        let hi = center.add_with_mode(&error, RoundingMode::Ceiling)?;
+        Some(DecimalInterval { lo, hi })
+    }
+
+    /// Returns the lower endpoint.
+    #[inline]
+    pub const fn lo(&self) -> Decimal {
+        self.lo
+    }
+
+    /// Returns the upper endpoint.
+    #[inline]
+    pub const fn hi(&self) -> Decimal {
+        self.hi
+    }
+
+    /// Returns `hi - lo` rounded toward positive infinity, returning `None` if overflow occurred.
+    #[inline]
+    pub fn width(&self) -> Option<Decimal> {
+        self.hi.add_with_mode(&-self.lo, RoundingMode::Ceiling)
+    }
+
+    /// Checks if `lo == hi`.
+    #[inline]
+    pub fn is_point(&self) -> bool {
+        self.lo == self.hi
+    }
+
+    /// Checks if `val` is in the interval.
+    #[inline]
+    pub fn contains(&self, val: &Decimal) -> bool {
+        self.lo <= *val && *val <= self.hi
+    }
+
+    /// Checks if `other` is a subset of the interval.
+    #[inline]
+    pub fn encloses(&self, other: &DecimalInterval) -> bool {
+        self.lo <= other.lo && other.hi <= self.hi
+    }
+
+    /// Computes `self + other`, returning `None` if overflow occurred.
+    #[inline]
+    pub fn checked_add(&self, other: &DecimalInterval) -> Option<DecimalInterval> {
+        let lo = self.lo.add_with_mode(&other.lo, RoundingMode::Floor)?;
+        let hi = self.hi.add_with_mode(&other.hi, RoundingMode::Ceiling)?;
+        Some(DecimalInterval { lo, hi })
+    }
+
+    /// Computes `self - other`, returning `None` if overflow occurred.
+    #[inline]
+    pub fn checked_sub(&self, other: &DecimalInterval) -> Option<DecimalInterval> {
+        self.checked_add(&-*other)
+    }
+
+    /// Computes `self * other`, returning `None` if overflow occurred.
+    #[inline]
+    pub fn checked_mul(&self, other: &DecimalInterval) -> Option<DecimalInterval> {
+        self.combine(other, Decimal::mul_with_mode)
+    }
+
+    /// Computes `self / other`, returning `None` if `other` contains zero or overflow occurred.
+    #[inline]
+    pub fn checked_div(&self, other: &DecimalInterval) -> Option<DecimalInterval> {
+        if other.contains(&Decimal::ZERO) {
+            return None;
+        }
+        self.combine(other, Decimal::div_with_mode)
+    }
+
+    /// Applies `op` to each pair of endpoints, rounded both ways, and returns the smallest interval
+    /// containing the results.
+    #[inline]
+    fn combine(
+        &self,
+        other: &DecimalInterval,
+        op: fn(&Decimal, &Decimal, RoundingMode) -> Option<Decimal>,
+    ) -> Option<DecimalInterval> {
+        let mut lo = op(&self.lo, &other.lo, RoundingMode::Floor)?;
+        let mut hi = op(&self.lo, &other.lo, RoundingMode::Ceiling)?;
+        for (a, b) in [(&self.lo, &other.hi), (&self.hi, &other.lo), (&self.hi, &other.hi)].iter() {
+            lo = lo.min(op(a, b, RoundingMode::Floor)?);
+            hi = hi.max(op(a, b, RoundingMode::Ceiling)?);
+        }
+        Some(DecimalInterval { lo, hi })
+    }
+
+    /// Computes the square root, returning `None` if `lo` is negative.
+    ///
+    /// The endpoints are the square roots of `lo` and `hi` rounded outward, so the square root of a
+    /// point is one unit in the last place wide at most.
+    #[inline]
+    pub fn sqrt(&self) -> Option<DecimalInterval> {
+        if self.lo.is_sign_negative() {
+            return None;
+        }
+        let (lo, _) = sqrt_bounds(&self.lo)?;
+        let (_, hi) = sqrt_bounds(&self.hi)?;
+        Some(DecimalInterval { lo, hi })
+    }
+
+    /// Computes the natural exponential, returning `None` if overflow occurred.
+    ///
+    /// The endpoints are the exponentials of `lo` and `hi` widened by the error bound of [`Decimal::exp`],
+    /// i.e. 100 units in the last place, except for the exact `e^0`.
+    #[inline]
+    pub fn exp(&self) -> Option<DecimalInterval> {
+        let bounds = |val: &Decimal| {
+            if val.is_zero() {
+                return Some((Decimal::ONE, Decimal::ONE));
+            }
+            let exp = val.exp()?;
+            if exp.is_zero() {
+                // `e^x` is below the smallest magnitude
+                return Some((Decimal::ZERO, next_up(&exp)?));
+            }
+            let (lo, hi) = func_bounds(exp)?;
+            // `e^x` is positive
+            Some((lo.max(Decimal::ZERO), hi))
+        };
+
+        let (lo, _) = bounds(&self.lo)?;
+        let (_, hi) = bounds(&self.hi)?;
+        Some(DecimalInterval { lo, hi })
+    }
+
+    /// Computes the natural logarithm, returning `None` if `lo` isn't positive.
+    ///
+    /// The endpoints are the logarithms of `lo` and `hi` widened by the error bound of [`Decimal::ln`],
+    /// i.e. 100 units in the last place, except for the exact `ln(1)`.
+    #[inline]
+    pub fn ln(&self) -> Option<DecimalInterval> {
+        let bounds = |val: &Decimal| {
+            if *val == Decimal::ONE {
+                return Some((Decimal::ZERO, Decimal::ZERO));
+            }
+            func_bounds(val.ln()?)
+        };
+
+        let (lo, _) = bounds(&self.lo)?;
+        let (_, hi) = bounds(&self.hi)?;
+        Some(DecimalInterval { lo, hi })
+    }
+}
+
+impl From<Decimal> for DecimalInterval {
+    #[inline]
+    fn from(val: Decimal) -> Self {
+        DecimalInterval::point(val)
+    }
+}
+
+impl core::ops::Neg for DecimalInterval {
+    type Output = DecimalInterval;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        DecimalInterval {
+            lo: -self.hi,
+            hi: -self.lo,
+        }
+    }
+}
+
+impl fmt::Display for DecimalInterval {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lcg;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    /// An exact signed value `int * 10^-scale`.
+    type Exact = (U256, i32, bool);
+
+    fn exact(val: &Decimal) -> Exact {
+        (U256::from(val.int_val()), val.scale() as i32, val.is_sign_negative())
+    }
+
+    /// Parses a reference value of up to 77 digits, with an optional exponent.
+    fn exact_str(s: &str) -> Exact {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let (mantissa, exp) = match s.find('E') {
+            Some(i) => (&s[..i], s[i + 1..].parse::<i32>().unwrap()),
+            None => (s, 0),
+        };
+        let mut int_val = U256::from(0u128);
+        let mut scale = -exp;
+        let mut fractional = false;
+        for b in mantissa.bytes() {
+            if b == b'.' {
+                fractional = true;
+                continue;
+            }
+            int_val = int_val * 10 + (b - b'0') as u128;
+            if fractional {
+                scale += 1;
+            }
+        }
+        (int_val, scale, negative)
+    }
+
+    fn cmp_exact(a: Exact, b: Exact) -> Ordering {
+        let a_negative = a.2 && a.0 != 0;
+        let b_negative = b.2 && b.0 != 0;
+        match (a_negative, b_negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => U256::cmp_scaled(a.0, a.1, b.0, b.1),
+            (true, true) => U256::cmp_scaled(b.0, b.1, a.0, a.1),
+        }
+    }
+
+    fn assert_contains(val: &DecimalInterval, expected: Exact) {
+        assert_ne!(
+            cmp_exact(exact(&val.lo()), expected),
+            Ordering::Greater,
+            "{} {:?}",
+            val,
+            expected
+        );
+        assert_ne!(
+            cmp_exact(exact(&val.hi()), expected),
+            Ordering::Less,
+            "{} {:?}",
+            val,
+            expected
+        );
+    }
+
+    /// Asserts the endpoints are equal or adjacent.
+    fn assert_tight(val: &DecimalInterval) {
+        assert!(val.is_point() || next_up(&val.lo()) == Some(val.hi()), "{}", val);
+    }
+
+    #[test]
+    fn test_next() {
+        fn assert_next(val: &str, down: &str, up: &str) {
+            let val = parse(val);
+            assert_eq!(next_down(&val), Some(parse(down)), "{}", val);
+            assert_eq!(next_up(&val), Some(parse(up)), "{}", val);
+        }
+
+        assert_next("0", "-1E-130", "1E-130");
+        assert_next(
+            "1",
+            "0.99999999999999999999999999999999999999",
+            "1.0000000000000000000000000000000000001",
+        );
+        assert_next(
+            "-1",
+            "-1.0000000000000000000000000000000000001",
+            "-0.99999999999999999999999999999999999999",
+        );
+        assert_next(
+            "99999999999999999999999999999999999999",
+            "99999999999999999999999999999999999998",
+            "1E38",
+        );
+        assert_next("1E-130", "0", "1.0000000000000000000000000000000000001E-130");
+        assert_next("-1E-130", "-1.0000000000000000000000000000000000001E-130", "0");
+
+        let max = parse("9.9999999999999999999999999999999999999E125");
+        assert_eq!(next_up(&max), None);
+        assert_eq!(next_down(&-max), None);
+    }
+
+    #[test]
+    fn test_directed_rounding() {
+        fn assert_rounding(result: Option<(Decimal, Decimal)>, lo: &str, hi: &str) {
+            assert_eq!(result, Some((parse(lo), parse(hi))));
+        }
+
+        let bounds = |a: &str, b: &str, op: fn(&Decimal, &Decimal, RoundingMode) -> Option<Decimal>| {
+            let (a, b) = (parse(a), parse(b));
+            Some((op(&a, &b, RoundingMode::Floor)?, op(&a, &b, RoundingMode::Ceiling)?))
+        };
+
+        assert_rounding(bounds("1.5", "2.25", Decimal::add_with_mode), "3.75", "3.75");
+        assert_rounding(
+            bounds("1E20", "1E-20", Decimal::add_with_mode),
+            "100000000000000000000",
+            "100000000000000000000.00000000000000001",
+        );
+        assert_rounding(
+            bounds("1E20", "-1E-20", Decimal::add_with_mode),
+            "99999999999999999999.999999999999999999",
+            "100000000000000000000",
+        );
+        assert_rounding(
+            bounds("1E100", "1E-100", Decimal::add_with_mode),
+            "1E100",
+            "1.0000000000000000000000000000000000001E100",
+        );
+        assert_rounding(
+            bounds("-1E100", "1E-100", Decimal::add_with_mode),
+            "-1E100",
+            "-9.9999999999999999999999999999999999999E99",
+        );
+        assert_rounding(bounds("1E-100", "-1E-100", Decimal::add_with_mode), "0", "0");
+        assert_rounding(
+            bounds("1", "3", Decimal::div_with_mode),
+            "0.33333333333333333333333333333333333333",
+            "0.33333333333333333333333333333333333334",
+        );
+        assert_rounding(
+            bounds("-2", "3", Decimal::div_with_mode),
+            "-0.66666666666666666666666666666666666667",
+            "-0.66666666666666666666666666666666666666",
+        );
+        assert_rounding(bounds("7.5", "2.5", Decimal::div_with_mode), "3", "3");
+        assert_rounding(bounds("1E-100", "1E-100", Decimal::mul_with_mode), "0", "1E-130");
+        assert_rounding(bounds("-1E-100", "1E-100", Decimal::mul_with_mode), "-1E-130", "0");
+        assert_rounding(
+            bounds(
+                "0.99999999999999999999999999999999999999",
+                "0.99999999999999999999999999999999999999",
+                Decimal::mul_with_mode,
+            ),
+            "0.99999999999999999999999999999999999998",
+            "0.99999999999999999999999999999999999999",
+        );
+        assert_eq!(bounds("1E100", "1E100", Decimal::mul_with_mode), None);
+
+        // rounding up carries past the maximum
+        let max = parse("9.9999999999999999999999999999999999999E125");
+        assert_eq!(max.add_with_mode(&parse("1E87"), RoundingMode::Up), None);
+        assert_eq!(max.add_with_mode(&parse("1E87"), RoundingMode::Down), Some(max));
+    }
+
+    #[test]
+    fn test_point_ops() {
+        let values = crate::testing::corpus_random(17, 400);
+        for pair in values.chunks(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let x = DecimalInterval::point(a);
+            let y = DecimalInterval::point(b);
+
+            let (a_int, a_scale, a_neg) = exact(&a);
+            let (b_int, b_scale, b_neg) = exact(&b);
+
+            if let Some(sum) = x.checked_add(&y) {
+                assert_tight(&sum);
+                if let Some(expected) = a.checked_add(b) {
+                    assert!(sum.contains(&expected), "{} {} {}", a, b, sum);
+                }
+                assert_eq!(x.checked_sub(&-y), Some(sum));
+            }
+
+            if let Some(product) = x.checked_mul(&y) {
+                assert_tight(&product);
+                let expected = (a_int * b_int.low(), a_scale + b_scale, a_neg ^ b_neg);
+                assert_contains(&product, expected);
+            }
+
+            if b.is_zero() {
+                assert_eq!(x.checked_div(&y), None);
+            } else if let Some(quotient) = x.checked_div(&y) {
+                assert_tight(&quotient);
+                // `lo * b <= a <= hi * b` for a positive `b`.
+                for (endpoint, order) in [(quotient.lo(), Ordering::Greater), (quotient.hi(), Ordering::Less)].iter() {
+                    let (q_int, q_scale, q_neg) = exact(endpoint);
+                    let times_b = (q_int * b_int.low(), q_scale + b_scale, q_neg ^ b_neg);
+                    let order = if b_neg { order.reverse() } else { *order };
+                    assert_ne!(
+                        cmp_exact(times_b, (a_int, a_scale, a_neg)),
+                        order,
+                        "{} {} {}",
+                        a,
+                        b,
+                        quotient
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_composed() {
+        /// A rational `num / den`, with a positive `den`.
+        #[derive(Copy, Clone)]
+        struct Ratio {
+            num: i128,
+            den: i128,
+        }
+
+        fn ratio(val: &Decimal) -> Ratio {
+            let num = val.int_val() as i128;
+            Ratio {
+                num: if val.is_sign_negative() { -num } else { num },
+                den: 10i128.pow(val.scale() as u32),
+            }
+        }
+
+        let mut rng = Lcg::new(0x1e7a_u64);
+        let mut random = || {
+            let int_val = (rng.next() % 1_000_000) as u128 + 1;
+            let scale = (rng.next() % 5) as i16;
+            Decimal::from_parts(int_val, scale, rng.next().is_multiple_of(2)).unwrap()
+        };
+
+        for _ in 0..2000 {
+            let (a, b, c, d, e) = (random(), random(), random(), random(), random());
+
+            // `(a + b) * c / d - e`
+            let (ra, rb, rc, rd, re) = (ratio(&a), ratio(&b), ratio(&c), ratio(&d), ratio(&e));
+            let sum = Ratio {
+                num: ra.num * rb.den + rb.num * ra.den,
+                den: ra.den * rb.den,
+            };
+            let product = Ratio {
+                num: sum.num * rc.num,
+                den: sum.den * rc.den,
+            };
+            let quotient = Ratio {
+                num: product.num * rd.den * rd.num.signum(),
+                den: product.den * rd.num.abs(),
+            };
+            let expected = Ratio {
+                num: quotient.num * re.den - re.num * quotient.den,
+                den: quotient.den * re.den,
+            };
+
+            let (a, b, c, d, e) = (
+                DecimalInterval::point(a),
+                DecimalInterval::point(b),
+                DecimalInterval::point(c),
+                DecimalInterval::point(d),
+                DecimalInterval::point(e),
+            );
+            let result = a
+                .checked_add(&b)
+                .and_then(|x| x.checked_mul(&c))
+                .and_then(|x| x.checked_div(&d))
+                .and_then(|x| x.checked_sub(&e))
+                .unwrap();
+
+            // `lo <= num / den` if `lo * den <= num`
+            for (endpoint, order) in [(result.lo(), Ordering::Greater), (result.hi(), Ordering::Less)].iter() {
+                let (int_val, scale, negative) = exact(endpoint);
+                let times_den = (int_val * expected.den as u128, scale, negative);
+                let num = (U256::from(expected.num.unsigned_abs()), 0, expected.num < 0);
+                assert_ne!(cmp_exact(times_den, num), *order, "{}", result);
+            }
+        }
+    }
+
+    #[test]
+    fn test_transcendental() {
+        fn assert_func(
+            func: fn(&DecimalInterval) -> Option<DecimalInterval>,
+            val: &str,
+            expected: &str,
+            max_units: u32,
+        ) {
+            let result = func(&DecimalInterval::point(parse(val))).unwrap();
+            assert_contains(&result, exact_str(expected));
+            let mut hi = result.lo();
+            for _ in 0..max_units {
+                hi = next_up(&hi).unwrap();
+            }
+            assert!(result.hi() <= hi, "{} {}", val, result);
+        }
+
+        assert_func(
+            DecimalInterval::sqrt,
+            "2",
+            "1.41421356237309504880168872420969807856967187537694807317668",
+            1,
+        );
+        assert_func(
+            DecimalInterval::sqrt,
+            "0.5",
+            "0.707106781186547524400844362104849039284835937688474036588340",
+            1,
+        );
+        assert_func(DecimalInterval::sqrt, "1E-20", "1E-10", 0);
+        assert_func(DecimalInterval::sqrt, "4", "2", 0);
+        assert_func(DecimalInterval::sqrt, "0", "0", 0);
+        assert_func(
+            DecimalInterval::sqrt,
+            "123456.789",
+            "351.364182864446216166582311675807703715914271812431919843183",
+            1,
+        );
+        assert_func(
+            DecimalInterval::sqrt,
+            "99999999999999999999999999999999999999",
+            "9999999999999999999.99999999999999999995000000000000000000000",
+            1,
+        );
+
+        assert_func(DecimalInterval::exp, "0", "1", 0);
+        assert_func(
+            DecimalInterval::exp,
+            "1",
+            "2.71828182845904523536028747135266249775724709369995957496697",
+            201,
+        );
+        assert_func(
+            DecimalInterval::exp,
+            "-1",
+            "0.367879441171442321595523770161460867445811131031767834507837",
+            201,
+        );
+        assert_func(
+            DecimalInterval::exp,
+            "0.5",
+            "1.64872127070012814684865078781416357165377610071014801157508",
+            201,
+        );
+        assert_func(
+            DecimalInterval::exp,
+            "10.25",
+            "28282.5419203349790898937457721502431568480651134016608148834",
+            201,
+        );
+        assert_func(
+            DecimalInterval::exp,
+            "-100",
+            "3.72007597602083596295969580386311833735889229237678196712061E-44",
+            201,
+        );
+        assert_func(
+            DecimalInterval::exp,
+            "-299.5",
+            "8.48794721251381770584628104447785080448387964995687717809732E-131",
+            1,
+        );
+        assert_func(
+            DecimalInterval::exp,
+            "1E-30",
+            "1.00000000000000000000000000000100000000000000000000000000000",
+            201,
+        );
+
+        assert_func(DecimalInterval::ln, "1", "0", 0);
+        assert_func(
+            DecimalInterval::ln,
+            "2",
+            "0.693147180559945309417232121458176568075500134360255254120680",
+            201,
+        );
+        assert_func(
+            DecimalInterval::ln,
+            "0.5",
+            "-0.693147180559945309417232121458176568075500134360255254120680",
+            201,
+        );
+        assert_func(
+            DecimalInterval::ln,
+            "10",
+            "2.30258509299404568401799145468436420760110148862877297603333",
+            201,
+        );
+        assert_func(
+            DecimalInterval::ln,
+            "1E-100",
+            "-230.258509299404568401799145468436420760110148862877297603333",
+            201,
+        );
+        assert_func(
+            DecimalInterval::ln,
+            "1.0000000000000000000000000000000000001",
+            "9.99999999999999999999999999999999999950000000000000000000000E-38",
+            201,
+        );
+        assert_func(
+            DecimalInterval::ln,
+            "99999999999999999999999999999999999999E88",
+            "290.125721717249756186266923290229890157728787567225394980199",
+            201,
+        );
+
+        let val = DecimalInterval::new(parse("2"), parse("4")).unwrap();
+        let sqrt = val.sqrt().unwrap();
+        assert_eq!(sqrt.lo(), parse("1.4142135623730950488016887242096980785"));
+        assert_eq!(sqrt.hi(), parse("2"));
+        let exp = val.exp().unwrap();
+        assert!(DecimalInterval::new(parse("7.389"), parse("54.599"))
+            .unwrap()
+            .encloses(&exp));
+        assert!(exp.encloses(&DecimalInterval::new(parse("7.38906"), parse("54.598")).unwrap()));
+        let ln = val.ln().unwrap();
+        assert!(DecimalInterval::new(parse("0.6931"), parse("1.3863"))
+            .unwrap()
+            .encloses(&ln));
+        assert!(ln.encloses(&DecimalInterval::new(parse("0.69315"), parse("1.3862")).unwrap()));
+
+        let negative = DecimalInterval::new(parse("-1"), parse("4")).unwrap();
+        assert_eq!(negative.sqrt(), None);
+        assert_eq!(negative.ln(), None);
+        assert_eq!(DecimalInterval::point(Decimal::ZERO).ln(), None);
+        assert_eq!(DecimalInterval::point(parse("300")).exp(), None);
+    }
+
+    #[test]
+    fn test_interval() {
+        assert_eq!(DecimalInterval::new(parse("2"), parse("1")), None);
+
+        let val = DecimalInterval::with_error(parse("1.5"), parse("-0.25")).unwrap();
+        assert_eq!(val, DecimalInterval::new(parse("1.25"), parse("1.75")).unwrap());
+        assert_eq!(val.width(), Some(parse("0.5")));
+        assert_eq!(val.to_string(), "[1.25, 1.75]");
+        assert!(val.contains(&parse("1.25")) && val.contains(&parse("1.75")));
+        assert!(!val.contains(&parse("1.76")));
+        assert!(val.encloses(&DecimalInterval::point(parse("1.5"))));
+        assert!(!DecimalInterval::point(parse("1.5")).encloses(&val));
+        assert!(!val.is_point());
+        assert_eq!(-val, DecimalInterval::new(parse("-1.75"), parse("-1.25")).unwrap());
+        assert_eq!(
+            DecimalInterval::from(Decimal::ONE),
+            DecimalInterval::point(Decimal::ONE)
+        );
+
+        let val = DecimalInterval::with_error(parse("1"), parse("1E-50")).unwrap();
+        assert_eq!(val.lo(), parse("0.99999999999999999999999999999999999999"));
+        assert_eq!(val.hi(), parse("1.0000000000000000000000000000000000001"));
+
+        let x = DecimalInterval::new(parse("-2"), parse("3")).unwrap();
+        let y = DecimalInterval::new(parse("-5"), parse("4")).unwrap();
+        assert_eq!(x.checked_add(&y), DecimalInterval::new(parse("-7"), parse("7")));
+        assert_eq!(x.checked_sub(&y), DecimalInterval::new(parse("-6"), parse("8")));
+        assert_eq!(x.checked_mul(&y), DecimalInterval::new(parse("-15"), parse("12")));
+        assert_eq!(x.checked_div(&y), None);
+        let z = DecimalInterval::new(parse("2"), parse("4")).unwrap();
+        assert_eq!(x.checked_div(&z), DecimalInterval::new(parse("-1"), parse("1.5")));
+    }
+}
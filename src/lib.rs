@@ -16,10 +16,69 @@
 //!
 //! ## Optional features
 //!
+//! ### `std`
+//!
+//! This default feature enables [`Decimal::encode`] and [`Decimal::compact_encode`] to a
+//! `std::io::Write`, and the `std::error::Error` implementations of the errors. Without it, the crate
+//! is `#![no_std]` and depends on `alloc`, and encodes to byte slices with [`Decimal::encode_to_slice`].
+//! The `diagnostics` and `postgres` features require it.
+//!
 //! ### `serde`
 //!
 //! When this optional dependency is enabled, `Decimal` implements the `serde::Serialize` and
-//! `serde::Deserialize` traits.
+//! `serde::Deserialize` traits, and so do the wrappers of [`units`]. The [`serde::tuple`] module serializes a decimal as a
+//! `(mantissa, scale)` tuple instead, for use with `#[serde(with = "decimal_rs::serde::tuple")]`.
+//!
+//! ### `diagnostics`
+//!
+//! When this optional feature is enabled, checked operations that return `None` record the
+//! reason of the failure, which can be retrieved with [`diagnostics::last_failure`].
+//!
+//! ### `subtle`
+//!
+//! When this optional dependency is enabled, `Decimal` implements `subtle::ConstantTimeEq`, with the
+//! timing of [`Decimal::ct_eq`].
+//!
+//! ### `pg-compat`
+//!
+//! When this optional feature is enabled, the [`pg_compat`] module parses and formats the text of
+//! the `numeric` type of PostgreSQL, including `NaN` and the column type modifiers.
+//!
+//! ### `postgres`
+//!
+//! When this optional feature is enabled, `Decimal` implements the `ToSql` and `FromSql` traits of the
+//! `postgres` crate for the binary format of the `numeric` type of PostgreSQL.
+//!
+//! ### `arbitrary`
+//!
+//! When this optional dependency is enabled, `Decimal` implements `arbitrary::Arbitrary` for fuzzing. The
+//! generated decimals are always valid, and often at the boundaries: zero, `MAX`, `MIN`, `MIN_POSITIVE` and
+//! powers of ten.
+//!
+//! ### `proptest`
+//!
+//! When this optional dependency is enabled, `Decimal` implements `proptest::arbitrary::Arbitrary`, so
+//! `any::<Decimal>()` is a strategy generating the decimals as above.
+//!
+//! ### `num-traits`
+//!
+//! When this optional dependency is enabled, `Decimal` implements `Zero`, `One`, `Num`, `Signed`,
+//! `FromPrimitive` and `ToPrimitive` of the `num-traits` crate, for generic numeric code.
+//!
+//! ### `rust_decimal`
+//!
+//! When this optional dependency is enabled, `Decimal` converts from and to `rust_decimal::Decimal`,
+//! without going through strings.
+//!
+//! ### `bigdecimal`
+//!
+//! When this optional dependency is enabled, `Decimal` converts from `&bigdecimal::BigDecimal`,
+//! rounding to 38 significant digits.
+//!
+//! ### `precision-76`
+//!
+//! When this optional feature is enabled, [`WideDecimal`] keeps up to 76 significant digits, twice
+//! the precision of `Decimal`, for parsing, formatting, comparison, addition and subtraction.
 //!
 //! ## Usage
 //!
@@ -59,28 +118,118 @@
 //! Decimal can be encoded to bytes and decoded from bytes.
 //!
 //! ```
+//! # #[cfg(feature = "std")]
+//! # {
 //! use decimal_rs::Decimal;
 //!
 //! let n1 = "123456789.987654321".parse::<Decimal>().unwrap();
 //! let mut  bytes = Vec::new();
 //! n1.encode(&mut bytes).unwrap();
-//! let n2 = Decimal::decode(&bytes);
+//! let n2 = Decimal::try_decode(&bytes).unwrap();
 //! assert_eq!(n1, n2);
+//! # }
 //! ```
+//!
+//! [`Decimal::to_bytes`] encodes to exactly [`MAX_BINARY_SIZE`] bytes for fixed-size records.
+//! [`Decimal::try_decode`] rejects invalid bytes, e.g. a scale out of range, while [`Decimal::decode`]
+//! panics on them.
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
+/// Records an arithmetic failure when the `diagnostics` feature is enabled.
+macro_rules! record_failure {
+    ($op: ident, $kind: ident, $lhs: expr, $rhs: expr) => {
+        #[cfg(feature = "diagnostics")]
+        $crate::diagnostics::record(
+            $crate::diagnostics::Operation::$op,
+            $crate::diagnostics::FailureKind::$kind,
+            $lhs,
+            $rhs,
+        );
+    };
+}
+
+pub mod agg;
+mod allocate;
+pub mod arith;
+pub mod batch;
+mod bid;
+mod context;
 mod convert;
+mod ct;
 mod decimal;
+mod divisor;
 mod error;
+mod fixed;
+mod format;
+mod fraction;
+mod interval;
 mod ops;
 mod parse;
+mod partition;
+mod pattern;
+mod product;
+mod quotient;
+mod raw;
+mod stream;
+mod trig;
 mod u256;
+mod uint;
+pub mod units;
+#[cfg(feature = "precision-76")]
+mod wide;
 
 #[cfg(feature = "serde")]
-mod serde;
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod serde;
+
+#[cfg(feature = "diagnostics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "diagnostics")))]
+pub mod diagnostics;
 
+#[cfg(feature = "pg-compat")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pg-compat")))]
+pub mod pg_compat;
+
+#[cfg(feature = "postgres")]
+mod postgres;
+
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+mod arbitrary;
+
+#[cfg(feature = "num-traits")]
+mod num_traits;
+
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal;
+
+#[cfg(feature = "bigdecimal")]
+mod bigdecimal;
+
+#[cfg(any(test, feature = "testing"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+
+pub use crate::context::{DecimalContext, NumericContext};
 pub use crate::decimal::{
-    Decimal, DECIMAL128, DECIMAL64, DECIMAL64_MAX_PRECISION, MAX_BINARY_SIZE, MAX_PRECISION, MAX_SCALE, MIN_SCALE,
+    Decimal, RoundingMode, DECIMAL128, DECIMAL64, DECIMAL64_MAX_PRECISION, MAX_BINARY_SIZE, MAX_PRECISION, MAX_SCALE,
+    MIN_SCALE,
+};
+pub use crate::divisor::PreparedDivisor;
+pub use crate::error::{
+    DecimalArithError, DecimalConvertError, DecimalFormatError, DecimalMathError, DecimalParseError, NormalizeError,
 };
-pub use crate::error::{DecimalConvertError, DecimalFormatError, DecimalParseError};
+pub use crate::fixed::FixedScale;
+pub use crate::format::{DecimalFormatter, FormatOptions};
+pub use crate::interval::DecimalInterval;
+pub use crate::parse::{ParseLimits, ParseOptions};
+pub use crate::product::WideProduct;
+pub use crate::quotient::QuotientDigits;
+pub use crate::raw::DecimalRaw;
+pub use crate::stream::DecimalParser;
+#[cfg(feature = "precision-76")]
+#[cfg_attr(docsrs, doc(cfg(feature = "precision-76")))]
+pub use crate::wide::WideDecimal;
@@ -0,0 +1,284 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! num-traits implementation.
+
+use crate::decimal::Decimal;
+use crate::error::DecimalParseError;
+use core::convert::TryFrom;
+use num_traits::{FromPrimitive, Num, One, Signed, ToPrimitive, Zero};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "num-traits")))]
+impl Zero for Decimal {
+    #[inline]
+    fn zero() -> Self {
+        Decimal::ZERO
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        Decimal::is_zero(self)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "num-traits")))]
+impl One for Decimal {
+    #[inline]
+    fn one() -> Self {
+        Decimal::ONE
+    }
+}
+
+/// Parses as [`Decimal::from_str_radix`] does.
+#[cfg_attr(docsrs, doc(cfg(feature = "num-traits")))]
+impl Num for Decimal {
+    type FromStrRadixErr = DecimalParseError;
+
+    #[inline]
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        Decimal::from_str_radix(s, radix)
+    }
+}
+
+/// `is_positive` and `is_negative` are both `false` for zero.
+#[cfg_attr(docsrs, doc(cfg(feature = "num-traits")))]
+impl Signed for Decimal {
+    #[inline]
+    fn abs(&self) -> Self {
+        Decimal::abs(self)
+    }
+
+    #[inline]
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self <= other {
+            Decimal::ZERO
+        } else {
+            self - other
+        }
+    }
+
+    #[inline]
+    fn signum(&self) -> Self {
+        if Decimal::is_zero(self) {
+            Decimal::ZERO
+        } else if self.is_sign_negative() {
+            -Decimal::ONE
+        } else {
+            Decimal::ONE
+        }
+    }
+
+    #[inline]
+    fn is_positive(&self) -> bool {
+        !Decimal::is_zero(self) && self.is_sign_positive()
+    }
+
+    #[inline]
+    fn is_negative(&self) -> bool {
+        !Decimal::is_zero(self) && self.is_sign_negative()
+    }
+}
+
+/// Returns `None` if the primitive value overflows a decimal, or is `NaN`.
+#[cfg_attr(docsrs, doc(cfg(feature = "num-traits")))]
+impl FromPrimitive for Decimal {
+    #[inline]
+    fn from_i64(n: i64) -> Option<Self> {
+        Some(Decimal::from(n))
+    }
+
+    #[inline]
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(Decimal::from(n))
+    }
+
+    #[inline]
+    fn from_i128(n: i128) -> Option<Self> {
+        Decimal::try_from(n).ok()
+    }
+
+    #[inline]
+    fn from_u128(n: u128) -> Option<Self> {
+        Decimal::try_from(n).ok()
+    }
+
+    #[inline]
+    fn from_f32(n: f32) -> Option<Self> {
+        Decimal::try_from(n).ok()
+    }
+
+    #[inline]
+    fn from_f64(n: f64) -> Option<Self> {
+        Decimal::try_from(n).ok()
+    }
+}
+
+/// Converts to an integer by rounding toward zero, like the primitive floats, and returns `None` on
+/// overflow.
+#[cfg_attr(docsrs, doc(cfg(feature = "num-traits")))]
+impl ToPrimitive for Decimal {
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        self.to_i64_trunc()
+    }
+
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        self.to_u64_trunc()
+    }
+
+    #[inline]
+    fn to_i128(&self) -> Option<i128> {
+        self.to_i128_trunc()
+    }
+
+    #[inline]
+    fn to_u128(&self) -> Option<u128> {
+        self.to_u128_trunc()
+    }
+
+    #[inline]
+    fn to_f32(&self) -> Option<f32> {
+        let val = f32::from(self);
+        if val.is_finite() {
+            Some(val)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn to_f64(&self) -> Option<f64> {
+        Some(f64::from(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn mean<T: Num + FromPrimitive + Copy>(values: &[T]) -> T {
+        let sum = values.iter().fold(T::zero(), |acc, &v| acc + v);
+        sum / T::from_usize(values.len()).unwrap()
+    }
+
+    #[test]
+    fn test_mean() {
+        let values = [parse("1.5"), parse("2.25"), parse("-0.75"), parse("5")];
+        assert_eq!(mean(&values), parse("2"));
+        assert_eq!(
+            mean(&[parse("1"), parse("2"), parse("2")]).to_string(),
+            "1.6666666666666666666666666666666666667"
+        );
+        assert_eq!(mean(&[1.5f64, 2.5]), 2.0);
+    }
+
+    #[test]
+    fn test_zero_one() {
+        assert_eq!(Decimal::zero(), parse("0"));
+        assert!(Zero::is_zero(&Decimal::zero()));
+        assert!(!Zero::is_zero(&parse("0.001")));
+        assert_eq!(Decimal::one(), parse("1"));
+        assert!(Decimal::one().is_one());
+    }
+
+    #[test]
+    fn test_from_str_radix() {
+        fn assert_radix(s: &str, radix: u32, expected: &str) {
+            assert_eq!(
+                Decimal::from_str_radix(s, radix).unwrap(),
+                parse(expected),
+                "{} {}",
+                s,
+                radix
+            );
+        }
+
+        fn assert_radix_err(s: &str, radix: u32, err: DecimalParseError) {
+            assert_eq!(Decimal::from_str_radix(s, radix).unwrap_err(), err, "{} {}", s, radix);
+        }
+
+        assert_radix("-123.456e-2", 10, "-1.23456");
+        assert_radix("ff", 16, "255");
+        assert_radix("-FF", 16, "-255");
+        assert_radix("+101", 2, "5");
+        assert_radix("zz", 36, "1295");
+        assert_radix("0", 8, "0");
+        assert_radix(
+            "4b3b4ca85a86c47a098a223fffffffff",
+            16,
+            "99999999999999999999999999999999999999",
+        );
+        assert_radix_err("", 10, DecimalParseError::Empty);
+        assert_radix_err("", 16, DecimalParseError::Empty);
+        assert_radix_err("-", 16, DecimalParseError::Empty);
+        assert_radix_err("1.5", 16, DecimalParseError::Invalid);
+        assert_radix_err("12", 2, DecimalParseError::Invalid);
+        assert_radix_err("1", 37, DecimalParseError::Invalid);
+        assert_radix_err("1", 1, DecimalParseError::Invalid);
+        assert_radix("4b3b4ca85a86c47a098a224000000000", 16, "1e38");
+        assert_radix_err("4b3b4ca85a86c47a098a224000000001", 16, DecimalParseError::Overflow);
+        assert_radix_err("1000000000000000000000000000000000", 16, DecimalParseError::Overflow);
+    }
+
+    #[test]
+    fn test_signed() {
+        assert_eq!(Signed::abs(&parse("-1.5")), parse("1.5"));
+        assert_eq!(parse("5").abs_sub(&parse("2.5")), parse("2.5"));
+        assert_eq!(parse("2.5").abs_sub(&parse("5")), parse("0"));
+        assert_eq!(parse("-0.01").signum(), parse("-1"));
+        assert_eq!(parse("0").signum(), parse("0"));
+        assert_eq!(parse("1e100").signum(), parse("1"));
+        assert!(Signed::is_positive(&parse("1e-130")));
+        assert!(Signed::is_negative(&parse("-1e-130")));
+        assert!(!Signed::is_positive(&Decimal::ZERO));
+        assert!(!Signed::is_negative(&Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_from_primitive() {
+        assert_eq!(Decimal::from_i8(-8), Some(parse("-8")));
+        assert_eq!(Decimal::from_u64(u64::MAX), Some(parse("18446744073709551615")));
+        assert_eq!(Decimal::from_i128(i128::MIN), None);
+        assert_eq!(Decimal::from_u128(u128::MAX), None);
+        assert_eq!(Decimal::from_f64(0.25), Some(parse("0.25")));
+        assert_eq!(Decimal::from_f32(-1.5), Some(parse("-1.5")));
+        assert_eq!(Decimal::from_f64(f64::NAN), None);
+        assert_eq!(Decimal::from_f64(f64::INFINITY), None);
+        assert_eq!(Decimal::from_f64(1e300), None);
+    }
+
+    #[test]
+    fn test_to_primitive() {
+        assert_eq!(parse("-2.9").to_i64(), Some(-2));
+        assert_eq!(parse("2.9").to_u8(), Some(2));
+        assert_eq!(parse("-0.5").to_u64(), Some(0));
+        assert_eq!(parse("-1").to_u64(), None);
+        assert_eq!(parse("9223372036854775807.9").to_i64(), Some(i64::MAX));
+        assert_eq!(parse("9223372036854775808").to_i64(), None);
+        assert_eq!(parse("1e20").to_i64(), None);
+        assert_eq!(parse("1e20").to_i128(), Some(100_000_000_000_000_000_000));
+        assert_eq!(parse("1e40").to_u128(), None);
+        assert_eq!(parse("256").to_u8(), None);
+        assert_eq!(parse("-1.5").to_f64(), Some(-1.5));
+        assert_eq!(Decimal::MAX.to_f64(), Some(1e126));
+        assert_eq!(parse("0.25").to_f32(), Some(0.25));
+        assert_eq!(parse("1e39").to_f32(), None);
+        assert_eq!(Decimal::MIN.to_f32(), None);
+    }
+}
@@ -15,9 +15,9 @@
 //! Ops implementation.
 
 use crate::decimal::Decimal;
-use std::convert::TryFrom;
-use std::iter::{Product, Sum};
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+use core::convert::TryFrom;
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
 
 impl Neg for Decimal {
     type Output = Decimal;
@@ -170,6 +170,39 @@ impl<'a> Product<&'a Decimal> for Decimal {
     }
 }
 
+/// Sums the decimals, returning `None` as soon as the sum overflows.
+///
+/// The sum of an iterator of `Result<Decimal, E>` into `Result<Decimal, E>` comes from std, which
+/// short-circuits at the first error, but panics if the sum overflows.
+impl Sum<Decimal> for Option<Decimal> {
+    #[inline(always)]
+    fn sum<I: Iterator<Item = Decimal>>(mut iter: I) -> Self {
+        iter.try_fold(Decimal::ZERO, |sum, val| sum.checked_add(val))
+    }
+}
+
+impl<'a> Sum<&'a Decimal> for Option<Decimal> {
+    #[inline(always)]
+    fn sum<I: Iterator<Item = &'a Decimal>>(mut iter: I) -> Self {
+        iter.try_fold(Decimal::ZERO, |sum, val| sum.checked_add(val))
+    }
+}
+
+/// Multiplies the decimals, returning `None` as soon as the product overflows.
+impl Product<Decimal> for Option<Decimal> {
+    #[inline(always)]
+    fn product<I: Iterator<Item = Decimal>>(mut iter: I) -> Self {
+        iter.try_fold(Decimal::ONE, |product, val| product.checked_mul(val))
+    }
+}
+
+impl<'a> Product<&'a Decimal> for Option<Decimal> {
+    #[inline(always)]
+    fn product<I: Iterator<Item = &'a Decimal>>(mut iter: I) -> Self {
+        iter.try_fold(Decimal::ONE, |product, val| product.checked_mul(val))
+    }
+}
+
 macro_rules! impl_arith_with_num {
     ($op: ident { $method: ident } $int: ty) => {
         impl $op<$int> for Decimal {
@@ -375,6 +408,8 @@ impl_arith_assign!(RemAssign { rem_assign });
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::decimal::RoundingMode;
+    use crate::testing;
 
     #[test]
     fn test_neg() {
@@ -443,6 +478,13 @@ mod tests {
         assert_add("0", "1e-90", "1e-90");
         assert_add("0", "-1e-127", "-1e-127");
         assert_add("0", "-1e-90", "-1e-90");
+
+        // rounding carries past the maximum
+        let max = "9.9999999999999999999999999999999999999E125"
+            .parse::<Decimal>()
+            .unwrap();
+        let half_ulp = "5E87".parse::<Decimal>().unwrap();
+        assert_eq!(max.checked_add(half_ulp), None);
     }
 
     #[test]
@@ -658,6 +700,61 @@ mod tests {
             "-0.12345678901234567890123456789012345678",
             "25.446900289022102624101133879320318304",
         );
+        // rounded once, not to 39 digits first
+        assert_div(
+            "999999999999999999E100",
+            "4294967295E100",
+            "232830643.70807973731031635247876782726",
+        );
+    }
+
+    #[test]
+    fn test_div_short_operands() {
+        // A quotient always has 38 significant digits, so even short operands shift the dividend by
+        // `10^38`, which fits in `u128` only for a dividend of at most 3.
+        fn assert_div_parts(val1: &str, val2: &str, expected: (u128, i16, bool)) {
+            let var1 = val1.parse::<Decimal>().unwrap();
+            let var2 = val2.parse::<Decimal>().unwrap();
+            assert_eq!((var1 / var2).into_parts(), expected, "{} / {}", val1, val2);
+        }
+
+        assert_div_parts("7.5", "2.5", (30000000000000000000000000000000000000, 37, false));
+        assert_div_parts("1", "3", (33333333333333333333333333333333333333, 38, false));
+        assert_div_parts("-0.02", "0.3", (66666666666666666666666666666666666667, 39, true));
+        assert_div_parts(
+            "8901.45",
+            "1901.23",
+            (46819427423299653382284100292968236352, 37, false),
+        );
+    }
+
+    #[test]
+    fn test_div_corpus() {
+        // `/` rounds the exact quotient once, half up, to the same representation.
+        fn assert_div_rounded(val1: &Decimal, val2: &Decimal) {
+            assert_eq!(
+                val1.checked_div(val2).map(|v| v.into_parts()),
+                val1.checked_div_with_rounding(val2, RoundingMode::HalfUp)
+                    .map(|v| v.into_parts()),
+                "{:?} / {:?}",
+                val1,
+                val2
+            );
+        }
+
+        let corpus = testing::corpus();
+        for val1 in corpus.iter() {
+            for val2 in corpus.iter().step_by(7) {
+                assert_div_rounded(val1, val2);
+                assert_div_rounded(val2, val1);
+            }
+        }
+
+        let random = testing::corpus_random(0x745, 20_000);
+        for pair in random.chunks(2) {
+            assert_div_rounded(&pair[0], &pair[1]);
+            assert_div_rounded(&pair[1], &pair[0]);
+        }
     }
 
     #[test]
@@ -764,4 +861,72 @@ mod tests {
         assert_product(&["-1", "-2", "-3", "-4", "-5"], "-120");
         assert_product(&["0", "0", "0", "0", "0"], "0");
     }
+
+    #[test]
+    fn test_checked_sum() {
+        fn assert_checked_sum(vals: &[&str], expected: Option<&str>) {
+            let vals: Vec<Decimal> = vals.iter().map(|val| val.parse::<Decimal>().unwrap()).collect();
+            let expected = expected.map(|e| e.parse::<Decimal>().unwrap());
+            assert_eq!(vals.iter().copied().sum::<Option<Decimal>>(), expected);
+            assert_eq!(vals.iter().sum::<Option<Decimal>>(), expected);
+            if let Some(expected) = expected {
+                assert_eq!(vals.iter().sum::<Decimal>(), expected);
+            }
+        }
+
+        assert_checked_sum(&[], Some("0"));
+        assert_checked_sum(&["1", "10", "100", "1000", "10000"], Some("11111"));
+        assert_checked_sum(&["-1.5", "0.25", "1.25"], Some("0"));
+        assert_checked_sum(&["9E125", "9E125", "-9E125"], None);
+        assert_checked_sum(&["-9E125", "-9E125"], None);
+
+        // short-circuits at the overflow
+        let mut count = 0;
+        let sum: Option<Decimal> = ["9E125", "9E125", "1", "1"]
+            .iter()
+            .map(|val| {
+                count += 1;
+                val.parse::<Decimal>().unwrap()
+            })
+            .sum();
+        assert_eq!(sum, None);
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_checked_product() {
+        fn assert_checked_product(vals: &[&str], expected: Option<&str>) {
+            let vals: Vec<Decimal> = vals.iter().map(|val| val.parse::<Decimal>().unwrap()).collect();
+            let expected = expected.map(|e| e.parse::<Decimal>().unwrap());
+            assert_eq!(vals.iter().copied().product::<Option<Decimal>>(), expected);
+            assert_eq!(vals.iter().product::<Option<Decimal>>(), expected);
+            if let Some(expected) = expected {
+                assert_eq!(vals.iter().product::<Decimal>(), expected);
+            }
+        }
+
+        assert_checked_product(&[], Some("1"));
+        assert_checked_product(&["1", "2", "3", "4", "5"], Some("120"));
+        assert_checked_product(&["-1", "-2", "-3", "-4", "-5"], Some("-120"));
+        assert_checked_product(&["1E100", "1E100", "1E-100"], None);
+        assert_checked_product(&["1E100", "0", "1E100"], Some("0"));
+    }
+
+    #[test]
+    fn test_result_sum() {
+        fn parse_all(vals: &[&str]) -> Result<Decimal, crate::DecimalParseError> {
+            vals.iter().map(|val| val.parse::<Decimal>()).sum()
+        }
+
+        assert_eq!(parse_all(&["1.5", "2.5", "-1"]), Ok(Decimal::from(3)));
+        assert_eq!(parse_all(&[]), Ok(Decimal::ZERO));
+        assert_eq!(
+            parse_all(&["1", "abc", "1E1000"]),
+            Err(crate::DecimalParseError::Invalid)
+        );
+
+        let product: Result<Decimal, crate::DecimalParseError> =
+            ["1.5", "2", "-1"].iter().map(|val| val.parse::<Decimal>()).product();
+        assert_eq!(product, Ok(Decimal::from(-3)));
+    }
 }
@@ -0,0 +1,170 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stable partition keys.
+//!
+//! The partition key is a part of the public contract: it must not change across crate versions or
+//! platforms, so the algorithm below is pinned by golden values in the tests.
+
+use crate::decimal::Decimal;
+
+/// The length of the canonical bytes of a decimal.
+const CANONICAL_LEN: usize = 19;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl Decimal {
+    /// Returns the canonical bytes of the decimal, which are equal for equal decimals.
+    ///
+    /// The trailing zeros of the mantissa are removed, adjusting the scale, and zero is `0` with scale `0`
+    /// and a positive sign. The bytes are the mantissa as 16 bytes in little-endian order, then the scale
+    /// as a two's complement `i16` in 2 bytes in little-endian order, then `1` for a negative sign or `0`
+    /// otherwise.
+    #[inline]
+    fn canonical_bytes(&self) -> [u8; CANONICAL_LEN] {
+        let (int_val, exp, negative) = self.factor_pow10();
+        let scale = -exp as i16;
+
+        let mut bytes = [0u8; CANONICAL_LEN];
+        bytes[..16].copy_from_slice(&int_val.to_le_bytes());
+        bytes[16..18].copy_from_slice(&scale.to_le_bytes());
+        bytes[18] = negative as u8;
+        bytes
+    }
+
+    /// Returns the partition of the decimal among `buckets` partitions, which is in `[0, buckets)`.
+    ///
+    /// The key is a function of the value only, so equal decimals such as `1.0` and `1` have the same key,
+    /// and it's stable across crate versions and platforms. It's computed as:
+    ///
+    /// 1. Take the canonical form `(mantissa, scale, negative)` with `value = ±mantissa * 10^-scale`, where
+    ///    the mantissa has no trailing zero. Zero is `(0, 0, false)`.
+    /// 2. Build 19 bytes: the mantissa as an unsigned 128-bit integer in little-endian order (16 bytes),
+    ///    the scale as a signed 16-bit integer in little-endian order (2 bytes), and `1` if negative or
+    ///    `0` otherwise (1 byte).
+    /// 3. Hash the bytes with 64-bit FNV-1a: `h = 0xcbf29ce484222325`, then for each byte `b`,
+    ///    `h = (h ^ b) * 0x100000001b3`, with wrapping multiplication.
+    /// 4. Mix the hash with the 64-bit finalizer of MurmurHash3: `h ^= h >> 33; h *= 0xff51afd7ed558ccd;
+    ///    h ^= h >> 33; h *= 0xc4ceb9fe1a85ec53; h ^= h >> 33`, with wrapping multiplications.
+    /// 5. The key is `h % buckets`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buckets` is zero.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let a: Decimal = "1.50".parse().unwrap();
+    /// let b: Decimal = "1.5".parse().unwrap();
+    /// assert_eq!(a.partition_key(16), b.partition_key(16));
+    /// assert!(a.partition_key(16) < 16);
+    /// ```
+    #[inline]
+    pub fn partition_key(&self, buckets: u32) -> u32 {
+        assert_ne!(buckets, 0, "buckets is zero");
+
+        let mut h = FNV_OFFSET_BASIS;
+        for b in self.canonical_bytes().iter() {
+            h = (h ^ *b as u64).wrapping_mul(FNV_PRIME);
+        }
+
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        h ^= h >> 33;
+
+        (h % buckets as u64) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryInto;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_canonical_bytes() {
+        fn assert_canonical(val: Decimal, int_val: u128, scale: i16, negative: bool) {
+            let bytes = val.canonical_bytes();
+            assert_eq!(u128::from_le_bytes(bytes[..16].try_into().unwrap()), int_val);
+            assert_eq!(i16::from_le_bytes(bytes[16..18].try_into().unwrap()), scale);
+            assert_eq!(bytes[18], negative as u8);
+        }
+
+        assert_canonical(Decimal::ZERO, 0, 0, false);
+        assert_canonical(Decimal::from_parts(0, 5, true).unwrap(), 0, 0, false);
+        assert_canonical(parse("1.500"), 15, 1, false);
+        assert_canonical(Decimal::from_parts(1500, 3, true).unwrap(), 15, 1, true);
+        assert_canonical(parse("1200"), 12, -2, false);
+        assert_canonical(parse("1E125"), 1, -125, false);
+        assert_canonical(parse("-1E-130"), 1, 130, true);
+    }
+
+    #[test]
+    fn test_partition_key_golden() {
+        // These values are a part of the public contract, computed independently from the documented
+        // algorithm. Never change them.
+        let golden: &[(&str, [u32; 6])] = &[
+            ("0", [0, 1, 6, 13, 413, 2330517913]),
+            ("1", [0, 1, 2, 11, 979, 4142502639]),
+            ("-1", [0, 1, 3, 9, 657, 1942151827]),
+            ("1.5", [0, 1, 4, 7, 159, 2272400109]),
+            ("-1.5", [0, 1, 6, 5, 733, 1799835638]),
+            ("0.001", [0, 1, 6, 9, 953, 1588048053]),
+            ("100", [0, 1, 6, 9, 145, 1058561960]),
+            ("123456789.987654321", [0, 0, 4, 14, 126, 1425211551]),
+            ("-0.00000000000000000001", [0, 1, 1, 9, 393, 4103373973]),
+            ("99999999999999999999999999999999999999", [0, 0, 1, 2, 586, 1025584951]),
+            (
+                "-9.9999999999999999999999999999999999999E125",
+                [0, 0, 1, 4, 932, 3939911607],
+            ),
+            ("1E-130", [0, 1, 1, 5, 933, 1834216663]),
+        ];
+        let buckets = [1, 2, 7, 16, 1000, u32::MAX];
+        for (s, keys) in golden.iter() {
+            let val = parse(s);
+            for (b, k) in buckets.iter().zip(keys.iter()) {
+                assert_eq!(val.partition_key(*b), *k, "{} {}", s, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_partition_key_equal() {
+        fn assert_same_key(values: &[&str]) {
+            let keys: Vec<u32> = values.iter().map(|v| parse(v).partition_key(1_000_003)).collect();
+            assert!(keys.windows(2).all(|w| w[0] == w[1]), "{:?} {:?}", values, keys);
+        }
+
+        assert_same_key(&["0", "-0", "0.000", "0E+10", "-0E-10"]);
+        assert_same_key(&["1", "1.0", "1.00000000000000000000000000000000000", "0.1E1"]);
+        assert_same_key(&["-1200", "-1.2E3", "-1200.000"]);
+        assert_same_key(&["1E+125", "10E124", "100000E120"]);
+        assert_ne!(parse("1").partition_key(u32::MAX), parse("-1").partition_key(u32::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "buckets is zero")]
+    fn test_partition_key_zero_buckets() {
+        Decimal::ONE.partition_key(0);
+    }
+}
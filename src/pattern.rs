@@ -0,0 +1,479 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Formatting with the number format models of `TO_CHAR` of Oracle.
+
+use crate::decimal::Decimal;
+use crate::error::DecimalFormatError;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Where the sign is written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SignMode {
+    /// `-` before a negative value, a blank before a non-negative one.
+    Default,
+    /// `S` before the digits, `-` or `+`.
+    Leading,
+    /// `S` after the digits, `-` or `+`.
+    Trailing,
+    /// `MI` after the digits, `-` or a blank.
+    Minus,
+    /// `PR`, a negative value in angle brackets, a non-negative one between blanks.
+    Brackets,
+}
+
+/// A compiled number format model.
+#[derive(Debug)]
+struct Pattern {
+    fill_mode: bool,
+    sign: SignMode,
+    dollar: bool,
+    /// The elements of the integral part, `9`, `0` or `,`.
+    integral: Vec<u8>,
+    /// The count of digits in the integral part.
+    int_digits: usize,
+    /// The count of integral digits which are always written, from the leftmost `0`.
+    int_zeros: usize,
+    point: bool,
+    /// The count of digits in the fractional part.
+    frac_digits: usize,
+    /// The count of fractional digits which fill mode keeps, up to the rightmost `0`.
+    frac_zeros: usize,
+    sci: bool,
+}
+
+/// Consumes `token` at the start of `s`, ignoring case.
+#[inline]
+fn eat(s: &mut &[u8], token: &[u8]) -> bool {
+    if s.len() >= token.len() && s[..token.len()].eq_ignore_ascii_case(token) {
+        *s = &s[token.len()..];
+        true
+    } else {
+        false
+    }
+}
+
+impl Pattern {
+    #[inline]
+    fn parse(pattern: &str) -> Result<Pattern, DecimalFormatError> {
+        let mut s = pattern.as_bytes();
+        let mut p = Pattern {
+            fill_mode: eat(&mut s, b"FM"),
+            sign: SignMode::Default,
+            dollar: false,
+            integral: Vec::new(),
+            int_digits: 0,
+            int_zeros: 0,
+            point: false,
+            frac_digits: 0,
+            frac_zeros: 0,
+            sci: false,
+        };
+
+        if eat(&mut s, b"S") {
+            p.sign = SignMode::Leading;
+        }
+        p.dollar = eat(&mut s, b"$");
+
+        while let Some(&c) = s.first() {
+            match c {
+                b'9' | b'0' => {
+                    if c == b'0' && p.int_zeros == 0 {
+                        p.int_zeros = p.int_digits + 1;
+                    }
+                    p.int_digits += 1;
+                    p.integral.push(c);
+                }
+                b',' | b'G' | b'g' => {
+                    // a group separator must be between digits
+                    if matches!(p.integral.last(), None | Some(b',')) {
+                        return Err(DecimalFormatError::InvalidPattern);
+                    }
+                    p.integral.push(b',');
+                }
+                _ => break,
+            }
+            s = &s[1..];
+        }
+        if p.integral.last() == Some(&b',') {
+            return Err(DecimalFormatError::InvalidPattern);
+        }
+        if p.int_zeros > 0 {
+            // from the position of the leftmost `0` to the right
+            p.int_zeros = p.int_digits - p.int_zeros + 1;
+        }
+
+        if eat(&mut s, b".") || eat(&mut s, b"D") {
+            p.point = true;
+            while let Some(&c) = s.first() {
+                match c {
+                    b'9' => p.frac_digits += 1,
+                    b'0' => {
+                        p.frac_digits += 1;
+                        p.frac_zeros = p.frac_digits;
+                    }
+                    _ => break,
+                }
+                s = &s[1..];
+            }
+        }
+
+        if eat(&mut s, b"EEEE") {
+            if p.int_digits == 0 || p.integral.contains(&b',') {
+                return Err(DecimalFormatError::InvalidPattern);
+            }
+            p.sci = true;
+        }
+
+        if p.sign == SignMode::Default {
+            if eat(&mut s, b"S") {
+                p.sign = SignMode::Trailing;
+            } else if eat(&mut s, b"MI") {
+                p.sign = SignMode::Minus;
+            } else if eat(&mut s, b"PR") {
+                p.sign = SignMode::Brackets;
+            }
+        }
+
+        if !s.is_empty() || p.int_digits + p.frac_digits == 0 || p.frac_digits > i16::MAX as usize {
+            return Err(DecimalFormatError::InvalidPattern);
+        }
+
+        Ok(p)
+    }
+
+    /// Returns the count of characters of the output without fill mode.
+    #[inline]
+    fn width(&self) -> usize {
+        let sign = if self.sign == SignMode::Brackets { 2 } else { 1 };
+        sign + self.dollar as usize + self.integral.len() + self.point as usize + self.frac_digits
+    }
+
+    #[inline]
+    fn format<W: fmt::Write>(&self, val: &Decimal, mut w: W) -> Result<(), DecimalFormatError> {
+        let mut body = String::new();
+        let mut blanks = 0;
+
+        let negative = if self.sci {
+            val.abs()
+                .format_with_sci_forced(self.frac_digits as i16, true, &mut body)?;
+            blanks += self.int_digits - 1;
+            val.is_sign_negative()
+        } else {
+            let rounded = val.round(self.frac_digits as i16);
+            let mut digits = String::new();
+            rounded
+                .abs()
+                .fmt_internal(false, false, false, Some(self.frac_digits), &mut digits)?;
+            let (int_str, frac_str) = match digits.find('.') {
+                Some(pos) => (&digits[..pos], &digits[pos + 1..]),
+                None => (digits.as_str(), ""),
+            };
+            let int_str = if int_str == "0" { "" } else { int_str };
+
+            if int_str.len() > self.int_digits {
+                for _ in 0..self.width() {
+                    w.write_char('#')?;
+                }
+                return Ok(());
+            }
+
+            let frac_len = if self.fill_mode {
+                frac_str.trim_end_matches('0').len().max(self.frac_zeros)
+            } else {
+                self.frac_digits
+            };
+
+            // a zero without fractional digits is written as `0`
+            let shown = int_str
+                .len()
+                .max(self.int_zeros)
+                .max((int_str.is_empty() && frac_len == 0) as usize);
+            let mut pos = self.int_digits;
+            for &c in self.integral.iter() {
+                if c == b',' {
+                    if body.is_empty() {
+                        blanks += 1;
+                    } else {
+                        body.push(',');
+                    }
+                    continue;
+                }
+
+                if pos > shown {
+                    blanks += 1;
+                } else if pos > int_str.len() {
+                    body.push('0');
+                } else {
+                    body.push(int_str.as_bytes()[int_str.len() - pos] as char);
+                }
+                pos -= 1;
+            }
+
+            if self.point {
+                body.push('.');
+                body.push_str(&frac_str[..frac_len.min(frac_str.len())]);
+                for _ in frac_str.len()..frac_len {
+                    body.push('0');
+                }
+            }
+
+            rounded.is_sign_negative()
+        };
+
+        let (prefix, suffix) = match (self.sign, negative) {
+            (SignMode::Default, true) => ("-", ""),
+            (SignMode::Default, false) => (" ", ""),
+            (SignMode::Leading, true) => ("-", ""),
+            (SignMode::Leading, false) => ("+", ""),
+            (SignMode::Trailing, true) => ("", "-"),
+            (SignMode::Trailing, false) => ("", "+"),
+            (SignMode::Minus, true) => ("", "-"),
+            (SignMode::Minus, false) => ("", " "),
+            (SignMode::Brackets, true) => ("<", ">"),
+            (SignMode::Brackets, false) => (" ", " "),
+        };
+
+        if !self.fill_mode {
+            for _ in 0..blanks {
+                w.write_char(' ')?;
+            }
+        }
+        if !self.fill_mode || prefix != " " {
+            w.write_str(prefix)?;
+        }
+        if self.dollar {
+            w.write_char('$')?;
+        }
+        w.write_str(&body)?;
+        if !self.fill_mode || suffix != " " {
+            w.write_str(suffix)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Decimal {
+    /// Formats the decimal with a number format model of `TO_CHAR` of Oracle, e.g. `'9,999.99'`.
+    ///
+    /// The elements of `pattern` are case-insensitive:
+    ///
+    /// | Element | Meaning |
+    /// |---------|---------|
+    /// | `9` | A digit, or a blank for a leading zero. A zero integral part is blank if there are fractional digits. |
+    /// | `0` | A digit, writing the leading zeros from this position. In the fractional part, a trailing zero kept by `FM`. |
+    /// | `,`, `G` | A group separator `,` between digits of the integral part, blank before the first written digit. |
+    /// | `.`, `D` | The decimal point `.`. |
+    /// | `$` | A dollar sign before the digits, after a leading sign. |
+    /// | `S` | A leading or trailing sign, `+` or `-`. |
+    /// | `MI` | A trailing sign, `-` or a blank. |
+    /// | `PR` | A negative value in angle brackets, a non-negative one between blanks. |
+    /// | `EEEE` | The scientific notation, e.g. `1.23E+03`, after the digits. |
+    /// | `FM` | Fill mode at the start, which drops the blanks and the trailing zeros at `9` of the fractional part. |
+    ///
+    /// The decimal is rounded half away from zero to the fractional digits. Without `S`, `MI` or `PR`,
+    /// `-` or a blank is written before the digits. Without `FM`, the output is padded with leading
+    /// blanks to the same width for every decimal.
+    ///
+    /// If the integral part has more digits than the pattern, the output is `#` repeated to that width,
+    /// as Oracle does, rather than an error. Fails with `DecimalFormatError::InvalidPattern` if `pattern`
+    /// isn't a valid format model.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// fn format(val: &str, pattern: &str) -> String {
+    ///     let mut s = String::new();
+    ///     val.parse::<Decimal>().unwrap().format_with_pattern(pattern, &mut s).unwrap();
+    ///     s
+    /// }
+    ///
+    /// assert_eq!(format("1234.5", "9,999.99"), " 1,234.50");
+    /// assert_eq!(format("-12.5", "0000.00"), "-0012.50");
+    /// assert_eq!(format("1.1", "FM99999.009"), "1.10");
+    /// assert_eq!(format("12345", "999"), "####");
+    /// ```
+    #[inline]
+    pub fn format_with_pattern<W: fmt::Write>(&self, pattern: &str, w: W) -> Result<(), DecimalFormatError> {
+        Pattern::parse(pattern)?.format(self, w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_fmt(val: &str, pattern: &str, expected: &str) {
+        let mut s = String::new();
+        val.parse::<Decimal>()
+            .unwrap()
+            .format_with_pattern(pattern, &mut s)
+            .unwrap();
+        assert_eq!(s, expected, "{} {}", val, pattern);
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_fmt("123", "999", " 123");
+        assert_fmt("-123", "999", "-123");
+        assert_fmt("5", "999", "   5");
+        assert_fmt("-5", "999", "  -5");
+        assert_fmt("0", "999", "   0");
+        assert_fmt("0.4", "999", "   0");
+        assert_fmt("0.5", "999", "   1");
+        assert_fmt("-0.5", "999", "  -1");
+        assert_fmt("-0.4", "999", "   0");
+        assert_fmt("0.5", "99.99", "   .50");
+        assert_fmt("-0.5", "99.99", "  -.50");
+        assert_fmt("0", "99.99", "   .00");
+        assert_fmt("0.5", "90.99", "  0.50");
+        assert_fmt("12", "0000.00", " 0012.00");
+        assert_fmt("12", "9099", "  012");
+        assert_fmt("1.005", "9.99", " 1.01");
+        assert_fmt("-1.005", "9.99", "-1.01");
+        assert_fmt("9.995", "9.99", "#####");
+        assert_fmt("12e3", "99999", " 12000");
+        assert_fmt("0.001", ".999", " .001");
+        assert_fmt("0.0005", ".999", " .001");
+        assert_fmt("1.5", ".99", "####");
+    }
+
+    #[test]
+    fn test_groups() {
+        assert_fmt("1234.5", "9,999.99", " 1,234.50");
+        assert_fmt("-1234.5", "9,999.99", "-1,234.50");
+        assert_fmt("12", "9,999", "    12");
+        assert_fmt("12", "0,000", " 0,012");
+        assert_fmt("1234567", "9G999G999", " 1,234,567");
+        assert_fmt("1234567.891", "999,999,999.99", "   1,234,567.89");
+        assert_fmt("123", "9,9,9", " 1,2,3");
+        assert_fmt("1234", "9,99", "#####");
+    }
+
+    #[test]
+    fn test_signs() {
+        assert_fmt("5", "S9999", "   +5");
+        assert_fmt("-5", "S9999", "   -5");
+        assert_fmt("5", "9999S", "   5+");
+        assert_fmt("-5", "9999S", "   5-");
+        assert_fmt("5", "9999MI", "   5 ");
+        assert_fmt("-5", "9999MI", "   5-");
+        assert_fmt("5", "9999PR", "    5 ");
+        assert_fmt("-5", "9999PR", "   <5>");
+        assert_fmt("-1.5", "s0.99", "-1.50");
+        assert_fmt("-1.5", "0.99mi", "1.50-");
+        assert_fmt("0", "S9", "+0");
+    }
+
+    #[test]
+    fn test_dollar() {
+        assert_fmt("1234.5", "$9,999.00", " $1,234.50");
+        assert_fmt("-1234.5", "$9,999.00", "-$1,234.50");
+        assert_fmt("5", "$9,999.00", "     $5.00");
+        assert_fmt("-5", "S$9999", "   -$5");
+        assert_fmt("-5", "$9999PR", "   <$5>");
+        assert_fmt("12345", "$9,999.00", "##########");
+    }
+
+    #[test]
+    fn test_fill_mode() {
+        assert_fmt("1.1", "FM99999.009", "1.10");
+        assert_fmt("1.1", "FM99999.999", "1.1");
+        assert_fmt("1", "FM99.99", "1.");
+        assert_fmt("1", "FM99.09", "1.0");
+        assert_fmt("-1.5", "FM9,999.99", "-1.5");
+        assert_fmt("1234.5", "fm9,999.99", "1,234.5");
+        assert_fmt("12", "FM0000", "0012");
+        assert_fmt("5", "FM9999MI", "5");
+        assert_fmt("-5", "FM9999MI", "5-");
+        assert_fmt("5", "FM9999PR", "5");
+        assert_fmt("-5", "FM9999PR", "<5>");
+        assert_fmt("5", "FMS9999", "+5");
+        assert_fmt("5", "FM$9,999.00", "$5.00");
+        assert_fmt("0", "FM99.99", "0.");
+        assert_fmt("0", "FM99.90", ".00");
+        assert_fmt("12345", "FM999", "####");
+    }
+
+    #[test]
+    fn test_sci() {
+        assert_fmt("1234", "9.99EEEE", " 1.23E+03");
+        assert_fmt("-1234", "9.99EEEE", "-1.23E+03");
+        assert_fmt("0.000123", "9.9EEEE", " 1.2E-04");
+        assert_fmt("9.99", "9.9EEEE", " 1.0E+01");
+        assert_fmt("0", "9.99EEEE", " 0.00E+00");
+        assert_fmt("1234", "9EEEE", " 1E+03");
+        assert_fmt("1234", "99.99EEEE", "  1.23E+03");
+        assert_fmt("1234", "FM99.99EEEE", "1.23E+03");
+        assert_fmt("1e125", "9.9eeee", " 1.0E+125");
+        assert_fmt("-1234", "S9.99EEEE", "-1.23E+03");
+        assert_fmt("-1234", "9.99EEEEMI", "1.23E+03-");
+    }
+
+    #[test]
+    fn test_large_values() {
+        assert_fmt(
+            "9.9999999999999999999999999999999999999E125",
+            "FM999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999",
+            "999999999999999999999999999999999999990000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        );
+        assert_fmt("1e-130", "FM0.9999", "0.");
+        assert_fmt(
+            "1e-130",
+            &format!("FM.{}9", "0".repeat(129)),
+            &format!(".{}1", "0".repeat(129)),
+        );
+        assert_fmt(
+            "1e-130",
+            &format!("FM.{}9", "0".repeat(128)),
+            &format!(".{}", "0".repeat(128)),
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        fn assert_invalid(pattern: &str) {
+            let mut s = String::new();
+            assert_eq!(
+                Decimal::ONE.format_with_pattern(pattern, &mut s),
+                Err(DecimalFormatError::InvalidPattern),
+                "{}",
+                pattern
+            );
+        }
+
+        assert_invalid("");
+        assert_invalid("FM");
+        assert_invalid("S");
+        assert_invalid(".");
+        assert_invalid("abc");
+        assert_invalid("9,");
+        assert_invalid(",9");
+        assert_invalid("9,,9");
+        assert_invalid("9.9,9");
+        assert_invalid("9.9.9");
+        assert_invalid("S9S");
+        assert_invalid("S9MI");
+        assert_invalid("9MI9");
+        assert_invalid("9FM");
+        assert_invalid("9$");
+        assert_invalid(".9EEEE");
+        assert_invalid("9,999EEEE");
+        assert_invalid("9EEE");
+        assert_invalid("9 ");
+    }
+}
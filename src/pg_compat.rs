@@ -0,0 +1,522 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Text input and output of the `numeric` type of PostgreSQL.
+//!
+//! [`parse_pg`] accepts the text `numeric_in` accepts, including `NaN` as a value, and keeps the
+//! display scale, i.e. the trailing zeros, as [`format_pg`] prints it. A column type modifier
+//! `numeric(p, s)` is applied with [`apply_typmod`], which rounds to the scale `s` and rejects the
+//! values that don't fit, and padded by [`format_pg`]:
+//!
+//! ```
+//! use decimal_rs::pg_compat::{apply_typmod, format_pg, parse_pg, PgNumeric};
+//!
+//! let val = parse_pg("  1.500 ").unwrap();
+//! assert_eq!(format_pg(&val, None), "1.500");
+//!
+//! let val = apply_typmod(&parse_pg("3.14159").unwrap(), (10, 4)).unwrap();
+//! assert_eq!(format_pg(&val, Some((10, 4))), "3.1416");
+//!
+//! let err = apply_typmod(&parse_pg("1234567").unwrap(), (10, 4)).unwrap_err();
+//! assert_eq!(err.to_string(), "numeric field overflow");
+//!
+//! assert_eq!(parse_pg("nan").unwrap(), PgNumeric::NaN);
+//! assert!(PgNumeric::NaN > parse_pg("1e125").unwrap());
+//! ```
+//!
+//! Unlike PostgreSQL, a value keeps at most 38 significant digits and its exponent ranges as
+//! [`Decimal`]'s does, so the infinities and the values out of that range are
+//! [`PgNumericError::OutOfRange`], and more digits are rounded half up. A zero has no display
+//! scale, so it prints as `0` unless a type modifier pads it.
+
+use crate::decimal::{Decimal, MAX_PRECISION, MAX_SCALE, MIN_SCALE};
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::cmp::Ordering;
+use core::fmt;
+
+/// The largest display scale of PostgreSQL, i.e. `NUMERIC_DSCALE_MAX`.
+const PG_DSCALE_MAX: i64 = 0x3FFF;
+
+/// The exponent of the smallest power of 10 PostgreSQL overflows at, i.e. `(NUMERIC_WEIGHT_MAX + 1) * DEC_DIGITS`.
+const PG_EXP_OVERFLOW: i64 = 131072;
+
+/// The exponent limit of the parser of PostgreSQL, i.e. `INT_MAX / 2`.
+const PG_EXP_LIMIT: i64 = i32::MAX as i64 / 2;
+
+/// A value of the `numeric` type of PostgreSQL.
+///
+/// `NaN` equals itself and sorts after all the other values, as it does in PostgreSQL.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PgNumeric {
+    /// A number.
+    Value(Decimal),
+    /// Not a number.
+    NaN,
+}
+
+impl PartialOrd for PgNumeric {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PgNumeric {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (PgNumeric::Value(a), PgNumeric::Value(b)) => a.cmp(b),
+            (PgNumeric::Value(_), PgNumeric::NaN) => Ordering::Less,
+            (PgNumeric::NaN, PgNumeric::Value(_)) => Ordering::Greater,
+            (PgNumeric::NaN, PgNumeric::NaN) => Ordering::Equal,
+        }
+    }
+}
+
+impl From<Decimal> for PgNumeric {
+    #[inline]
+    fn from(val: Decimal) -> Self {
+        PgNumeric::Value(val)
+    }
+}
+
+impl fmt::Display for PgNumeric {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_pg(self, None))
+    }
+}
+
+/// An error which can be returned by [`parse_pg`] and [`apply_typmod`].
+///
+/// The messages are the ones of PostgreSQL, except for [`PgNumericError::OutOfRange`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PgNumericError {
+    /// The text is not a numeric.
+    InvalidSyntax(String),
+    /// The value overflows the storage format of PostgreSQL.
+    Overflow,
+    /// The value is a numeric of PostgreSQL out of the range of `Decimal`, including the infinities.
+    OutOfRange(String),
+    /// The value doesn't fit the type modifier `numeric(precision, scale)`.
+    FieldOverflow {
+        /// The precision of the type modifier.
+        precision: u8,
+        /// The scale of the type modifier.
+        scale: i16,
+    },
+}
+
+impl PgNumericError {
+    /// Returns the detail message PostgreSQL reports with the error, if any.
+    #[inline]
+    pub fn detail(&self) -> Option<String> {
+        match *self {
+            PgNumericError::FieldOverflow { precision, scale } => {
+                let max_digits = precision as i32 - scale as i32;
+                let bound = if max_digits == 0 {
+                    "1".to_owned()
+                } else {
+                    format!("10^{}", max_digits)
+                };
+                Some(format!(
+                    "A field with precision {}, scale {} must round to an absolute value less than {}.",
+                    precision, scale, bound
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PgNumericError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PgNumericError::InvalidSyntax(s) => write!(f, "invalid input syntax for type numeric: \"{}\"", s),
+            PgNumericError::Overflow => write!(f, "value overflows numeric format"),
+            PgNumericError::OutOfRange(s) => write!(f, "value out of range for decimal: \"{}\"", s),
+            PgNumericError::FieldOverflow { .. } => write!(f, "numeric field overflow"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PgNumericError {}
+
+/// Checks if `b` is a white space of `isspace` in the C locale.
+#[inline]
+fn is_pg_space(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r' | b'\x0b' | b'\x0c')
+}
+
+/// Splits the leading ASCII digits off `s`.
+#[inline]
+fn split_digits(s: &[u8]) -> (&[u8], &[u8]) {
+    let len = s.iter().take_while(|b| b.is_ascii_digit()).count();
+    s.split_at(len)
+}
+
+/// Parses the exponent after `e` as `strtol` does, which skips white spaces and saturates,
+/// returning the exponent and the rest, or `None` if there's no digit.
+#[inline]
+fn parse_exponent(s: &[u8]) -> Option<(i64, &[u8])> {
+    let start = s.iter().take_while(|&&b| is_pg_space(b)).count();
+    let s = &s[start..];
+    let (negative, s) = match s.first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+
+    let (digits, rest) = split_digits(s);
+    if digits.is_empty() {
+        return None;
+    }
+
+    let exp = digits
+        .iter()
+        .fold(0i64, |exp, &b| exp.saturating_mul(10).saturating_add((b - b'0') as i64));
+    Some((if negative { -exp } else { exp }, rest))
+}
+
+/// Parses the text representation of a `numeric` of PostgreSQL.
+///
+/// The display scale of the text, i.e. the count of fractional digits including the trailing zeros
+/// after applying the exponent, is kept as the scale of the decimal.
+///
+/// ```
+/// use decimal_rs::pg_compat::{parse_pg, PgNumeric, PgNumericError};
+///
+/// assert_eq!(parse_pg(" -1.50e1 ").unwrap().to_string(), "-15.0");
+/// assert_eq!(parse_pg("NaN").unwrap(), PgNumeric::NaN);
+/// assert_eq!(
+///     parse_pg("1 2").unwrap_err().to_string(),
+///     "invalid input syntax for type numeric: \"1 2\""
+/// );
+/// assert!(matches!(parse_pg("1e1000"), Err(PgNumericError::OutOfRange(_))));
+/// ```
+#[inline]
+pub fn parse_pg(s: &str) -> Result<PgNumeric, PgNumericError> {
+    let invalid = || PgNumericError::InvalidSyntax(s.to_owned());
+
+    let bytes = s.as_bytes();
+    let start = bytes.iter().take_while(|&&b| is_pg_space(b)).count();
+    let end = bytes.len() - bytes[start..].iter().rev().take_while(|&&b| is_pg_space(b)).count();
+    let text = &bytes[start..end];
+
+    if text.eq_ignore_ascii_case(b"NaN") {
+        return Ok(PgNumeric::NaN);
+    }
+
+    let (negative, body) = match text.first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+
+    if body.eq_ignore_ascii_case(b"Infinity") || body.eq_ignore_ascii_case(b"inf") {
+        return Err(PgNumericError::OutOfRange(s.to_owned()));
+    }
+
+    let (integral, rest) = split_digits(body);
+    let (fractional, rest) = match rest.first() {
+        Some(b'.') => split_digits(&rest[1..]),
+        _ => (&rest[..0], rest),
+    };
+    if integral.is_empty() && fractional.is_empty() {
+        return Err(invalid());
+    }
+
+    let (exp, rest) = match rest.first() {
+        Some(b'e') | Some(b'E') => parse_exponent(&rest[1..]).ok_or_else(invalid)?,
+        _ => (0, rest),
+    };
+    if !rest.is_empty() {
+        return Err(invalid());
+    }
+    if exp >= PG_EXP_LIMIT || exp <= -PG_EXP_LIMIT {
+        return Err(PgNumericError::Overflow);
+    }
+
+    let dscale = (fractional.len() as i64 - exp).max(0);
+    if dscale > PG_DSCALE_MAX {
+        return Err(PgNumericError::Overflow);
+    }
+
+    let leading_zeros = integral
+        .iter()
+        .chain(fractional.iter())
+        .take_while(|&&b| b == b'0')
+        .count();
+    let significant = integral.len() + fractional.len() - leading_zeros;
+    if significant == 0 {
+        return Ok(PgNumeric::Value(Decimal::ZERO));
+    }
+
+    // the exponent of the most significant digit
+    let logb = integral.len() as i64 - leading_zeros as i64 - 1 + exp;
+    if logb >= PG_EXP_OVERFLOW {
+        return Err(PgNumericError::Overflow);
+    }
+    if logb >= -(MIN_SCALE as i64) || logb < -(MAX_SCALE as i64) {
+        return Err(PgNumericError::OutOfRange(s.to_owned()));
+    }
+
+    // The 39th significant digit is the last one which affects the rounding to 38 digits.
+    let count = significant.min(MAX_PRECISION as usize + 1);
+    let digits = integral
+        .iter()
+        .chain(fractional.iter())
+        .skip(leading_zeros)
+        .take(count)
+        .copied();
+    let point_from_right = (count as i64 - 1 - logb) as i16;
+    let val = Decimal::from_digits_iter(digits, point_from_right, negative)
+        .map_err(|_| PgNumericError::OutOfRange(s.to_owned()))?;
+
+    // pads the trailing zeros of the display scale if they fit
+    let (int_val, scale, negative) = val.into_parts();
+    let pad = dscale - scale as i64;
+    if pad > 0 && val.precision() as i64 + pad <= MAX_PRECISION as i64 {
+        let padded = int_val * 10u128.pow(pad as u32);
+        if let Ok(padded) = Decimal::from_parts(padded, dscale as i16, negative) {
+            return Ok(PgNumeric::Value(padded));
+        }
+    }
+
+    Ok(PgNumeric::Value(val))
+}
+
+/// Applies the type modifier `numeric(precision, scale)`, which rounds half away from zero to
+/// `scale` and fails with [`PgNumericError::FieldOverflow`] unless the result's absolute value is
+/// less than `10^(precision - scale)`.
+///
+/// ```
+/// use decimal_rs::pg_compat::{apply_typmod, parse_pg};
+///
+/// let val = apply_typmod(&parse_pg("-1234.5").unwrap(), (2, -2)).unwrap();
+/// assert_eq!(val.to_string(), "-1200");
+///
+/// let err = apply_typmod(&parse_pg("999.995").unwrap(), (5, 2)).unwrap_err();
+/// assert_eq!(
+///     err.detail().unwrap(),
+///     "A field with precision 5, scale 2 must round to an absolute value less than 10^3."
+/// );
+/// ```
+#[inline]
+pub fn apply_typmod(value: &PgNumeric, typmod: (u8, i16)) -> Result<PgNumeric, PgNumericError> {
+    let (precision, scale) = typmod;
+    let val = match value {
+        PgNumeric::Value(val) => val.round(scale),
+        PgNumeric::NaN => return Ok(PgNumeric::NaN),
+    };
+
+    if let Some(logb) = val.logb() {
+        if logb as i32 + 1 > precision as i32 - scale as i32 {
+            return Err(PgNumericError::FieldOverflow { precision, scale });
+        }
+    }
+
+    Ok(PgNumeric::Value(val))
+}
+
+/// Formats a value as the output of `numeric_out` of PostgreSQL, with the fractional digits of the
+/// display scale, which is the scale of the type modifier `numeric(precision, scale)` if any.
+///
+/// The value is rounded half away from zero to the scale of the type modifier, but its precision
+/// isn't checked, see [`apply_typmod`].
+///
+/// ```
+/// use decimal_rs::pg_compat::{format_pg, parse_pg, PgNumeric};
+///
+/// let val = parse_pg("1.5").unwrap();
+/// assert_eq!(format_pg(&val, None), "1.5");
+/// assert_eq!(format_pg(&val, Some((5, 2))), "1.50");
+/// assert_eq!(format_pg(&val, Some((3, 0))), "2");
+/// assert_eq!(format_pg(&PgNumeric::NaN, Some((5, 2))), "NaN");
+/// ```
+#[inline]
+pub fn format_pg(value: &PgNumeric, typmod: Option<(u8, i16)>) -> String {
+    let val = match value {
+        PgNumeric::Value(val) => val,
+        PgNumeric::NaN => return "NaN".to_owned(),
+    };
+
+    let (val, dscale) = match typmod {
+        Some((_, scale)) => (val.round(scale), scale.max(0) as usize),
+        None => (*val, val.scale().max(0) as usize),
+    };
+
+    let (int_val, scale, negative) = val.into_parts();
+    let digits = int_val.to_string();
+    let mut buf = String::with_capacity(digits.len() + dscale + 3);
+    if negative {
+        buf.push('-');
+    }
+
+    let frac_len = if scale <= 0 {
+        buf.push_str(&digits);
+        if int_val != 0 {
+            buf.push_str(&"0".repeat(-scale as usize));
+        }
+        0
+    } else {
+        let scale = scale as usize;
+        if digits.len() > scale {
+            let (int, frac) = digits.split_at(digits.len() - scale);
+            buf.push_str(int);
+            buf.push('.');
+            buf.push_str(frac);
+        } else {
+            buf.push_str("0.");
+            buf.push_str(&"0".repeat(scale - digits.len()));
+            buf.push_str(&digits);
+        }
+        scale
+    };
+
+    if dscale > frac_len {
+        if frac_len == 0 {
+            buf.push('.');
+        }
+        buf.push_str(&"0".repeat(dscale - frac_len));
+    }
+
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_format() {
+        fn assert_parse(s: &str, expected: &str) {
+            let val = parse_pg(s).unwrap();
+            assert_eq!(format_pg(&val, None), expected);
+            assert_eq!(parse_pg(expected).unwrap(), val);
+        }
+
+        assert_parse("0", "0");
+        assert_parse("-0.00", "0");
+        assert_parse("1.500", "1.500");
+        assert_parse("-.5", "-0.5");
+        assert_parse("5.", "5");
+        assert_parse("1.0e-2", "0.010");
+        assert_parse("15e3", "15000");
+        assert_parse("1e 5", "100000");
+        assert_parse("1E-130", &format!("0.{}1", "0".repeat(129)));
+        assert_parse("1E-100", &format!("0.{}1", "0".repeat(99)));
+        assert_parse(
+            "1.0000000000000000000000000000000000000E-100",
+            &format!("0.{}1{}", "0".repeat(99), "0".repeat(37)),
+        );
+        assert_parse(
+            "0.123456789012345678901234567890123456789",
+            "0.12345678901234567890123456789012345679",
+        );
+        assert_parse(
+            "9.99999999999999999999999999999999999994E125",
+            &format!("{}{}", "9".repeat(38), "0".repeat(88)),
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        fn assert_error(s: &str, expected: PgNumericError) {
+            assert_eq!(parse_pg(s).unwrap_err(), expected);
+        }
+
+        fn assert_invalid(s: &str) {
+            assert_error(s, PgNumericError::InvalidSyntax(s.to_owned()));
+        }
+
+        assert_invalid("");
+        assert_invalid(" ");
+        assert_invalid(".");
+        assert_invalid("-");
+        assert_invalid(".e3");
+        assert_invalid("1e");
+        assert_invalid("1e-+5");
+        assert_invalid("1 2");
+        assert_invalid("-NaN");
+        assert_invalid("infinit");
+
+        assert_error("1e1073741823", PgNumericError::Overflow);
+        assert_error("1e99999999999999999999999", PgNumericError::Overflow);
+        assert_error("1e131072", PgNumericError::Overflow);
+        assert_error("0e-16384", PgNumericError::Overflow);
+        assert_error("1.50e-16382", PgNumericError::Overflow);
+
+        assert_error("1e131071", PgNumericError::OutOfRange("1e131071".to_owned()));
+        assert_error("-Infinity", PgNumericError::OutOfRange("-Infinity".to_owned()));
+        assert_error("1E126", PgNumericError::OutOfRange("1E126".to_owned()));
+        assert_error("1E-131", PgNumericError::OutOfRange("1E-131".to_owned()));
+        assert_error(
+            "9.99999999999999999999999999999999999995E125",
+            PgNumericError::OutOfRange("9.99999999999999999999999999999999999995E125".to_owned()),
+        );
+    }
+
+    #[test]
+    fn test_typmod() {
+        fn assert_typmod(s: &str, typmod: (u8, i16), expected: Result<&str, PgNumericError>) {
+            let val = parse_pg(s).unwrap();
+            let actual = apply_typmod(&val, typmod).map(|val| format_pg(&val, Some(typmod)));
+            assert_eq!(actual, expected.map(str::to_owned));
+        }
+
+        let overflow = |precision, scale| PgNumericError::FieldOverflow { precision, scale };
+        assert_typmod("0", (5, 2), Ok("0.00"));
+        assert_typmod("-0.001", (5, 2), Ok("0.00"));
+        assert_typmod("-0.005", (5, 2), Ok("-0.01"));
+        assert_typmod("999.995", (5, 2), Err(overflow(5, 2)));
+        assert_typmod("1e38", (38, 0), Err(overflow(38, 0)));
+        assert_typmod("0.5", (38, 38), Ok("0.50000000000000000000000000000000000000"));
+        assert_typmod("1", (38, 38), Err(overflow(38, 38)));
+        assert_typmod("9950", (2, -2), Err(overflow(2, -2)));
+        assert_typmod("0.00999994", (3, 5), Err(overflow(3, 5)));
+        assert_typmod("1e-130", (20, 10), Ok("0.0000000000"));
+        assert_typmod("NaN", (1, 0), Ok("NaN"));
+
+        assert_eq!(
+            overflow(38, 38).detail().unwrap(),
+            "A field with precision 38, scale 38 must round to an absolute value less than 1."
+        );
+        assert_eq!(
+            overflow(3, 5).detail().unwrap(),
+            "A field with precision 3, scale 5 must round to an absolute value less than 10^-2."
+        );
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut values: Vec<PgNumeric> = ["NaN", "1", "-5", "0", "nan", "1e-130", "1.0"]
+            .iter()
+            .map(|s| parse_pg(s).unwrap())
+            .collect();
+        values.sort();
+
+        let sorted: Vec<String> = values.iter().map(PgNumeric::to_string).collect();
+        assert_eq!(
+            sorted,
+            ["-5", "0", &format!("0.{}1", "0".repeat(129)), "1", "1.0", "NaN", "NaN"]
+        );
+        assert_eq!(PgNumeric::NaN, PgNumeric::NaN);
+        assert_eq!(parse_pg("1").unwrap(), parse_pg("1.00").unwrap());
+    }
+}
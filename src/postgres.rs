@@ -0,0 +1,317 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! postgres implementation of the binary `numeric` type.
+//!
+//! A `numeric` is sent as four 16-bit integers, the number of digits, the weight of the first digit,
+//! the sign and the display scale, followed by the digits in base 10000, the first one multiplied by
+//! `10000^weight`. Trailing zero digits are omitted, so a negative scale only raises the weight.
+
+use crate::decimal::{Decimal, MAX_SCALE, MIN_SCALE};
+use crate::u256::{POWERS_10, U256};
+use bytes::{BufMut, BytesMut};
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use std::error::Error;
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xC000;
+const NUMERIC_PINF: u16 = 0xD000;
+const NUMERIC_NINF: u16 = 0xF000;
+
+/// The number of decimal digits of a base-10000 digit.
+const DEC_DIGITS: i32 = 4;
+
+/// The largest number of base-10000 digits of a decimal, i.e. 38 digits shifted by up to 3 digits.
+const MAX_NUMERIC_DIGITS: usize = 11;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "postgres")))]
+impl ToSql for Decimal {
+    #[inline]
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let (mut int_val, scale, negative) = self.into_parts();
+
+        // Shifts the mantissa left by `pad` digits, so that the scale is a multiple of 4 and the last digit
+        // in base 10000 is aligned to the decimal point.
+        let pad = (DEC_DIGITS - (scale as i32).rem_euclid(DEC_DIGITS)) % DEC_DIGITS;
+        let mut weight = -(scale as i32 + pad) / DEC_DIGITS;
+
+        let mut digits = [0i16; MAX_NUMERIC_DIGITS];
+        let mut len = 0;
+        let mut divisor = POWERS_10[(DEC_DIGITS - pad) as usize].low();
+        let mut multiplier = POWERS_10[pad as usize].low();
+        while int_val != 0 {
+            let digit = (int_val % divisor * multiplier) as i16;
+            int_val /= divisor;
+            divisor = 10000;
+            multiplier = 1;
+
+            // The trailing zero digits are omitted.
+            if digit == 0 && len == 0 {
+                weight += 1;
+            } else {
+                digits[len] = digit;
+                len += 1;
+            }
+        }
+
+        out.reserve(8 + 2 * len);
+        out.put_i16(len as i16);
+        out.put_i16(if len == 0 { 0 } else { (weight + len as i32 - 1) as i16 });
+        out.put_u16(if negative { NUMERIC_NEG } else { NUMERIC_POS });
+        out.put_u16(scale.max(0) as u16);
+        for &digit in digits[..len].iter().rev() {
+            out.put_i16(digit);
+        }
+
+        Ok(IsNull::No)
+    }
+
+    accepts!(NUMERIC);
+
+    to_sql_checked!();
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "postgres")))]
+impl<'a> FromSql<'a> for Decimal {
+    /// Converts a `numeric` to a decimal, keeping its display scale if the mantissa allows.
+    ///
+    /// More than 38 significant digits are rounded half up, as parsing does. `NaN`, the infinities and
+    /// the values out of the range of `Decimal` are errors.
+    #[inline]
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Decimal, Box<dyn Error + Sync + Send>> {
+        let read_u16 = |i: usize| u16::from_be_bytes([raw[i], raw[i + 1]]);
+
+        if raw.len() < 8 {
+            return Err("invalid numeric: too short".into());
+        }
+        let len = read_u16(0) as i16;
+        let weight = read_u16(2) as i16;
+        let sign = read_u16(4);
+        let dscale = read_u16(6);
+
+        let negative = match sign {
+            NUMERIC_POS => false,
+            NUMERIC_NEG => true,
+            NUMERIC_NAN => return Err("cannot convert NaN to Decimal".into()),
+            NUMERIC_PINF => return Err("cannot convert Infinity to Decimal".into()),
+            NUMERIC_NINF => return Err("cannot convert -Infinity to Decimal".into()),
+            _ => return Err("invalid numeric: unknown sign".into()),
+        };
+        if len < 0 || raw.len() != 8 + 2 * len as usize {
+            return Err("invalid numeric: length mismatch".into());
+        }
+
+        // Beyond 72 digits, the dropped digits can't change the rounding to 38 digits half up.
+        let mut int_val = U256::ZERO;
+        let mut used = 0;
+        for i in 0..len as usize {
+            let digit = read_u16(8 + 2 * i);
+            if digit >= 10000 {
+                return Err("invalid numeric: digit out of range".into());
+            }
+            if int_val < POWERS_10[72] {
+                int_val = int_val * 10000 + digit as u128;
+                used += 1;
+            }
+        }
+
+        if int_val == 0 {
+            return Ok(Decimal::ZERO);
+        }
+
+        let scale = (used - 1 - weight as i32) * DEC_DIGITS;
+        let exp = scale - int_val.count_digits() as i32;
+        if exp >= MAX_SCALE as i32 {
+            return Err("numeric underflow".into());
+        }
+        if exp < MIN_SCALE as i32 {
+            return Err("numeric overflow".into());
+        }
+
+        match Decimal::adjust_scale(int_val, scale as i16, negative) {
+            Some(val) => Ok(val.normalize_to_scale(dscale as i16)),
+            None => Err("numeric overflow".into()),
+        }
+    }
+
+    accepts!(NUMERIC);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(val: &str) -> Vec<u8> {
+        let mut buf = BytesMut::new();
+        let val = val.parse::<Decimal>().unwrap();
+        assert!(matches!(val.to_sql(&Type::NUMERIC, &mut buf), Ok(IsNull::No)));
+        buf.to_vec()
+    }
+
+    fn decode(raw: &[u8]) -> Result<Decimal, String> {
+        Decimal::from_sql(&Type::NUMERIC, raw).map_err(|e| e.to_string())
+    }
+
+    fn numeric(len: i16, weight: i16, sign: u16, dscale: u16, digits: &[i16]) -> Vec<u8> {
+        let mut buf = BytesMut::new();
+        buf.put_i16(len);
+        buf.put_i16(weight);
+        buf.put_u16(sign);
+        buf.put_u16(dscale);
+        for &digit in digits.iter() {
+            buf.put_i16(digit);
+        }
+        buf.to_vec()
+    }
+
+    #[test]
+    fn test_to_sql() {
+        fn assert_encode(val: &str, weight: i16, sign: u16, dscale: u16, digits: &[i16]) {
+            let expected = numeric(digits.len() as i16, weight, sign, dscale, digits);
+            assert_eq!(encode(val), expected, "{}", val);
+        }
+
+        assert_encode("0", 0, NUMERIC_POS, 0, &[]);
+        assert_encode("1", 0, NUMERIC_POS, 0, &[1]);
+        assert_encode("-12345.678", 1, NUMERIC_NEG, 3, &[1, 2345, 6780]);
+        assert_encode("0.00001", -2, NUMERIC_POS, 5, &[1000]);
+        assert_encode("1e10", 2, NUMERIC_POS, 0, &[100]);
+        assert_encode("1e-126", -32, NUMERIC_POS, 126, &[100]);
+        assert_encode("1e-130", -33, NUMERIC_POS, 130, &[100]);
+        assert_encode(
+            "99999999999999999999999999999999999999",
+            9,
+            NUMERIC_POS,
+            0,
+            &[99, 9999, 9999, 9999, 9999, 9999, 9999, 9999, 9999, 9999],
+        );
+        assert_encode(
+            "-9.9999999999999999999999999999999999999e125",
+            31,
+            NUMERIC_NEG,
+            0,
+            &[99, 9999, 9999, 9999, 9999, 9999, 9999, 9999, 9999, 9999],
+        );
+        // 41 digits after shifting the mantissa
+        assert_encode(
+            "0.00099999999999999999999999999999999999999",
+            -1,
+            NUMERIC_POS,
+            41,
+            &[9, 9999, 9999, 9999, 9999, 9999, 9999, 9999, 9999, 9999, 9000],
+        );
+        assert_encode(
+            "1.2345678901234567890123456789012345678",
+            0,
+            NUMERIC_POS,
+            37,
+            &[1, 2345, 6789, 123, 4567, 8901, 2345, 6789, 123, 4567, 8000],
+        );
+
+        // The display scale is the scale, trailing zeros included.
+        let mut buf = BytesMut::new();
+        Decimal::from_parts(1500, 3, false)
+            .unwrap()
+            .to_sql(&Type::NUMERIC, &mut buf)
+            .unwrap();
+        assert_eq!(buf.to_vec(), numeric(2, 0, NUMERIC_POS, 3, &[1, 5000]));
+        buf.clear();
+        Decimal::from_parts(10000, 4, true)
+            .unwrap()
+            .to_sql(&Type::NUMERIC, &mut buf)
+            .unwrap();
+        assert_eq!(buf.to_vec(), numeric(1, 0, NUMERIC_NEG, 4, &[1]));
+
+        assert!(<Decimal as ToSql>::accepts(&Type::NUMERIC));
+        assert!(!<Decimal as ToSql>::accepts(&Type::FLOAT8));
+    }
+
+    #[test]
+    fn test_from_sql() {
+        fn assert_decode(raw: Vec<u8>, expected: (u128, i16, bool)) {
+            assert_eq!(decode(&raw).map(Decimal::into_parts), Ok(expected));
+        }
+        fn assert_error(raw: Vec<u8>, msg: &str) {
+            assert_eq!(decode(&raw), Err(msg.to_owned()));
+        }
+
+        assert_decode(numeric(0, 0, NUMERIC_POS, 0, &[]), (0, 0, false));
+        assert_decode(numeric(0, 0, NUMERIC_POS, 2, &[]), (0, 0, false));
+        assert_decode(numeric(2, 0, NUMERIC_POS, 3, &[1, 5000]), (1500, 3, false));
+        assert_decode(numeric(1, 0, NUMERIC_NEG, 4, &[1]), (10000, 4, true));
+        assert_decode(numeric(1, 2, NUMERIC_POS, 0, &[100]), (10_000_000_000, 0, false));
+        assert_decode(numeric(1, 31, NUMERIC_POS, 0, &[1]), (POWERS_10[37].low(), -87, false));
+        assert_decode(numeric(1, -33, NUMERIC_POS, 130, &[100]), (1, 130, false));
+        // leading zero digits
+        assert_decode(numeric(3, 2, NUMERIC_POS, 0, &[0, 0, 7]), (7, 0, false));
+        // rounded to 38 digits
+        assert_decode(
+            numeric(
+                11,
+                10,
+                NUMERIC_POS,
+                0,
+                &[12, 3456, 7890, 1234, 5678, 9012, 3456, 7890, 1234, 5678, 9012],
+            ),
+            (12345678901234567890123456789012345679, -4, false),
+        );
+        // digits beyond 72 digits
+        let mut digits = vec![0; 32];
+        digits[0] = 5000;
+        digits[31] = 1;
+        assert_decode(
+            numeric(digits.len() as i16, -1, NUMERIC_POS, 128, &digits),
+            (5 * POWERS_10[37].low(), 38, false),
+        );
+
+        assert_error(numeric(0, 0, NUMERIC_NAN, 0, &[]), "cannot convert NaN to Decimal");
+        assert_error(
+            numeric(0, 0, NUMERIC_PINF, 0, &[]),
+            "cannot convert Infinity to Decimal",
+        );
+        assert_error(
+            numeric(0, 0, NUMERIC_NINF, 0, &[]),
+            "cannot convert -Infinity to Decimal",
+        );
+        assert_error(numeric(1, 32, NUMERIC_POS, 0, &[1]), "numeric overflow");
+        assert_error(numeric(1, i16::MAX, NUMERIC_POS, 0, &[1]), "numeric overflow");
+        assert_error(numeric(1, -34, NUMERIC_POS, 136, &[9999]), "numeric underflow");
+        assert_error(numeric(1, i16::MIN, NUMERIC_POS, 0x3FFF, &[1]), "numeric underflow");
+        assert_error(numeric(1, 0, 0x1234, 0, &[1]), "invalid numeric: unknown sign");
+        assert_error(numeric(2, 0, NUMERIC_POS, 0, &[1]), "invalid numeric: length mismatch");
+        assert_error(numeric(-1, 0, NUMERIC_POS, 0, &[]), "invalid numeric: length mismatch");
+        assert_error(
+            numeric(1, 0, NUMERIC_POS, 0, &[10000]),
+            "invalid numeric: digit out of range",
+        );
+        assert_error(vec![0; 7], "invalid numeric: too short");
+
+        assert!(<Decimal as FromSql>::accepts(&Type::NUMERIC));
+        assert!(!<Decimal as FromSql>::accepts(&Type::TEXT));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for val in crate::testing::corpus().iter() {
+            let mut buf = BytesMut::new();
+            val.to_sql(&Type::NUMERIC, &mut buf).unwrap();
+            let decoded = Decimal::from_sql(&Type::NUMERIC, &buf).unwrap();
+            assert_eq!(decoded, *val);
+            if val.scale() >= 0 {
+                assert_eq!(decoded.into_parts(), val.into_parts());
+            }
+        }
+    }
+}
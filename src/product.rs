@@ -0,0 +1,574 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Exact product of a few decimals.
+
+use crate::decimal::{Decimal, RoundingMode, MAX_PRECISION};
+use crate::u256::{POWERS_10, U256};
+use crate::uint::Uint;
+
+/// The accumulator of a product, which holds the product of `MAX_PRODUCT_FACTORS` mantissas less
+/// than `2^127`.
+type ProductUint = Uint<16>;
+
+impl Decimal {
+    /// The maximum count of factors of [`Decimal::product_exact`].
+    pub const MAX_PRODUCT_FACTORS: usize = 8;
+
+    /// Calculates the product of `factors` with a single rounding, returning `None` if overflow
+    /// occurred or there are more than [`Decimal::MAX_PRODUCT_FACTORS`] factors.
+    ///
+    /// Chained `checked_mul` calls round each intermediate product to 38 digits, so the rounding
+    /// errors compound. This accumulates the exact product of the mantissas in a wide integer, and
+    /// rounds it half up once at the end, as `checked_mul` does. So a product of two factors equals
+    /// `checked_mul` exactly. The product of no factors is one.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let third: Decimal = "0.33333333333333333333333333333333333333".parse().unwrap();
+    /// let three = Decimal::from(3);
+    /// let product = Decimal::product_exact(&[third, third, three, three]).unwrap();
+    /// assert_eq!(product, "0.99999999999999999999999999999999999998".parse::<Decimal>().unwrap());
+    /// assert_ne!(product, third * third * three * three);
+    /// ```
+    #[inline]
+    pub fn product_exact(factors: &[Decimal]) -> Option<Decimal> {
+        if factors.len() > Decimal::MAX_PRODUCT_FACTORS {
+            return None;
+        }
+
+        if factors.iter().any(|f| f.is_zero()) {
+            return Some(Decimal::ZERO);
+        }
+
+        let mut acc = ProductUint::one();
+        let mut scale = 0i32;
+        let mut negative = false;
+        for f in factors.iter() {
+            acc = acc.checked_mul(f.int_val())?;
+            scale += f.scale() as i32;
+            negative ^= f.is_sign_negative();
+        }
+
+        // Drops the lowest digits while the product is too wide to be rounded by `adjust_scale`. The
+        // product keeps more than 38 digits, and rounding half up depends only on the first dropped
+        // digit, so it's still rounded once.
+        let int_val = loop {
+            match acc.to_u256() {
+                Some(val) if val < POWERS_10[2 * MAX_PRECISION as usize] => break val,
+                _ => {
+                    acc.div_pow10(19);
+                    scale -= 19;
+                }
+            }
+        };
+
+        Decimal::adjust_scale(int_val, scale as i16, negative)
+    }
+}
+
+/// The count of significant digits of the quotient of [`WideProduct::div_round`] before its sticky
+/// digit, enough to round it to any scale once.
+const QUOTIENT_DIGITS: u32 = MAX_PRECISION + 3;
+
+/// The exact product of two decimals, created by [`Decimal::mul_wide`].
+///
+/// The product of two mantissas has up to 76 digits, which [`Decimal::checked_mul`] rounds to 38 digits.
+/// This keeps all of them, so the product can be divided or rounded to a scale once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WideProduct {
+    int_val: U256,
+    scale: i16,
+    negative: bool,
+}
+
+impl WideProduct {
+    /// Returns the scale of the product, i.e. the sum of the scales of the factors.
+    #[inline]
+    pub fn scale(&self) -> i16 {
+        self.scale
+    }
+
+    /// Returns `true` if the product is negative. A zero product is not negative.
+    #[inline]
+    pub fn is_sign_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Rounds the product half up to exactly `scale` digits after the decimal point, returning `None`
+    /// if `scale` is out of range or the result needs more than `MAX_PRECISION` digits.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let a: Decimal = "1.25".parse().unwrap();
+    /// let b: Decimal = "1.25".parse().unwrap();
+    /// let product = a.mul_wide(b);
+    /// assert_eq!(product.to_decimal_round(2).unwrap().to_string(), "1.56");
+    /// assert_eq!(product.to_decimal_round(4).unwrap().to_string(), "1.5625");
+    /// ```
+    #[inline]
+    pub fn to_decimal_round(&self, scale: i16) -> Option<Decimal> {
+        Decimal::round_parts_to_scale(self.int_val, self.scale, self.negative, scale, RoundingMode::HalfUp)
+    }
+
+    /// Divides the exact product by `divisor` and rounds the quotient half up once to exactly `scale`
+    /// digits after the decimal point, returning `None` if `divisor` is zero, `scale` is out of range or
+    /// the result needs more than `MAX_PRECISION` digits.
+    ///
+    /// This is a fused multiply-divide: `a.mul_wide(b).div_round(c, scale)` rounds `a * b / c` once,
+    /// while `a * b / c` rounds the product to 38 digits first.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let a: Decimal = "123456789.123456789123456789".parse().unwrap();
+    /// let b: Decimal = "987654321.987654321987654321".parse().unwrap();
+    /// assert_eq!(a.mul_wide(b).div_round(&a, 18), Some(b));
+    /// assert_ne!(a * b / a, b);
+    /// ```
+    #[inline]
+    pub fn div_round(&self, divisor: &Decimal, scale: i16) -> Option<Decimal> {
+        if divisor.is_zero() {
+            return None;
+        }
+
+        let (int_val, s) = self.div_parts(divisor.int_val());
+        let s = s - divisor.scale() as i32;
+        let negative = self.negative ^ divisor.is_sign_negative();
+        Decimal::round_parts_to_scale(int_val, s as i16, negative, scale, RoundingMode::HalfUp)
+    }
+
+    /// Returns the mantissa and the scale of the quotient of the mantissa by `divisor`, which is non-zero,
+    /// by long division.
+    ///
+    /// The mantissa has up to `QUOTIENT_DIGITS` digits of the quotient, followed by a sticky digit which is
+    /// non-zero if the remainder is. An exact quotient is returned as is.
+    #[inline]
+    fn div_parts(&self, divisor: u128) -> (U256, i32) {
+        let (mut quotient, rem) = self.int_val.div_rem(divisor);
+        let mut rem = rem.low();
+        let mut scale = self.scale as i32;
+
+        let digits = quotient.count_digits();
+        if digits > QUOTIENT_DIGITS {
+            let shift = digits - QUOTIENT_DIGITS;
+            let (q, r) = quotient.div_rem(POWERS_10[shift as usize]);
+            quotient = q;
+            rem |= (r != 0) as u128;
+            scale -= shift as i32;
+            return (quotient * 10 + (rem != 0) as u128, scale + 1);
+        }
+
+        // `rem < divisor < 10^38`, so `rem * 10^38` fits in a `U256`.
+        let mut digits = digits;
+        while rem != 0 && digits < QUOTIENT_DIGITS {
+            let e = MAX_PRECISION.min(QUOTIENT_DIGITS - digits);
+            let (q, r) = U256::mul128(rem, POWERS_10[e as usize].low()).div_rem(divisor);
+            quotient = quotient * POWERS_10[e as usize].low() + q.low();
+            rem = r.low();
+            scale += e as i32;
+            digits = quotient.count_digits();
+        }
+
+        if rem == 0 {
+            (quotient, scale)
+        } else {
+            (quotient * 10 + 1, scale + 1)
+        }
+    }
+}
+
+/// Represents `int_val * 10^-scale` at the coarser or equal scale `target`, returning the mantissa and
+/// `true` if non-zero digits were dropped.
+///
+/// A mantissa at a finer scale must have at most 76 digits at `target`.
+#[inline]
+fn align_to_scale(int_val: U256, scale: i32, target: i32) -> (U256, bool) {
+    if scale <= target {
+        (int_val * POWERS_10[(target - scale) as usize], false)
+    } else if scale - target > 2 * MAX_PRECISION as i32 {
+        (U256::ZERO, int_val != 0)
+    } else {
+        let (quotient, rem) = int_val.div_rem(POWERS_10[(scale - target) as usize]);
+        (quotient, rem != 0)
+    }
+}
+
+impl Decimal {
+    /// Calculates `self * mul + add` with a single rounding, returning `None` if overflow occurred.
+    ///
+    /// `self * mul + add` rounds the product to 38 digits before the addition. This adds the exact
+    /// product, and rounds the sum half up once at the end, as `checked_add` does.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let x: Decimal = "1.0000000000000000001".parse().unwrap();
+    /// let minus_one = Decimal::from(-1);
+    /// let exact: Decimal = "2.0000000000000000001E-19".parse().unwrap();
+    /// assert_eq!(x.mul_add(x, minus_one), Some(exact));
+    /// assert_eq!(x * x + minus_one, "2E-19".parse::<Decimal>().unwrap());
+    /// ```
+    #[inline]
+    pub fn mul_add(&self, mul: impl AsRef<Decimal>, add: impl AsRef<Decimal>) -> Option<Decimal> {
+        let mul = mul.as_ref();
+        let add = add.as_ref();
+
+        let product = self.mul_wide(mul);
+        if product.int_val == 0 {
+            return Some(*add);
+        }
+        if add.is_zero() {
+            return self.checked_mul(mul);
+        }
+
+        // The product has up to 76 digits and `add` up to 38. Both are aligned to the finer scale if they
+        // fit in 77 digits there. Otherwise the finer one is at least 2 digits below the other, which is
+        // aligned to 76 digits, and the digits of the finer one past that are a sticky digit.
+        let product_scale = product.scale as i32;
+        let add_scale = add.scale() as i32;
+        let top = (product.int_val.count_digits() as i32 - product_scale).max(add.precision() as i32 - add_scale);
+        let finer = product_scale.max(add_scale);
+        let mut scale = if finer + top <= 2 * MAX_PRECISION as i32 + 1 {
+            finer
+        } else {
+            2 * MAX_PRECISION as i32 - top
+        };
+
+        let (mut product_val, product_sticky) = align_to_scale(product.int_val, product_scale, scale);
+        let (mut add_val, add_sticky) = align_to_scale(U256::from(add.int_val()), add_scale, scale);
+        if product_sticky || add_sticky {
+            product_val = product_val * 10 + product_sticky as u128;
+            add_val = add_val * 10 + add_sticky as u128;
+            scale += 1;
+        }
+
+        let (mut int_val, negative) = if product.negative == add.is_sign_negative() {
+            (product_val + add_val, product.negative)
+        } else if product_val >= add_val {
+            (product_val.checked_sub(add_val)?, product.negative)
+        } else {
+            (add_val.checked_sub(product_val)?, add.is_sign_negative())
+        };
+
+        // A sum of 78 digits is folded into 76 digits and a sticky one, as `count_digits` needs less than
+        // `10^77`.
+        if int_val >= POWERS_10[2 * MAX_PRECISION as usize] * 10 {
+            let (quotient, rem) = int_val.div_rem(100u128);
+            int_val = quotient * 10 + (rem != 0) as u128;
+            scale -= 1;
+        }
+
+        let result = Decimal::adjust_scale(int_val, scale as i16, negative);
+        if result.is_none() {
+            record_failure!(Mul, Overflow, self, Some(mul));
+        }
+        result
+    }
+
+    /// Computes the exact product `self * other`, without rounding it to `MAX_PRECISION` digits.
+    ///
+    /// See [`WideProduct::div_round`] and [`WideProduct::to_decimal_round`] to round it once.
+    #[inline]
+    pub fn mul_wide(&self, other: impl AsRef<Decimal>) -> WideProduct {
+        let other = other.as_ref();
+        if self.is_zero() || other.is_zero() {
+            return WideProduct {
+                int_val: U256::ZERO,
+                scale: 0,
+                negative: false,
+            };
+        }
+
+        WideProduct {
+            int_val: U256::mul128(self.int_val(), other.int_val()),
+            scale: self.scale() + other.scale(),
+            negative: self.is_sign_negative() ^ other.is_sign_negative(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_product_exact() {
+        fn assert_product(factors: &[&str], expected: &str) {
+            let factors = factors.iter().map(|s| parse(s)).collect::<Vec<_>>();
+            assert_eq!(Decimal::product_exact(&factors), Some(parse(expected)), "{:?}", factors);
+        }
+
+        assert_product(&[], "1");
+        assert_product(&["-1.5"], "-1.5");
+        assert_product(&["2", "3", "4"], "24");
+        assert_product(&["0", "1E100", "1E100"], "0");
+        assert_product(&["-1.5", "-2", "-0.1"], "-0.3");
+        assert_product(&["1E50", "1E50", "1E-100", "1E25"], "1E25");
+        assert_product(&["1E-50", "1E-50", "1E-30"], "1E-130");
+        assert_product(&["1E-50", "1E-50", "1E-31"], "0");
+        assert_product(
+            &["0.99999999999999999999999999999999999999"; 8],
+            "0.99999999999999999999999999999999999992",
+        );
+        assert_product(
+            &["99999999999999999999999999999999999999"; 3],
+            "99999999999999999999999999999999999997E76",
+        );
+
+        // the pricing formula of price, fx rate, haircut and day count fraction, where the chained
+        // products round twice
+        let factors = [
+            parse("101.23456789012345678901234567890123457"),
+            parse("1.0873456789012345678901234567890123457"),
+            parse("0.97513"),
+            parse("0.24657534246575342465753424657534246575"),
+        ];
+        let chained = factors[0] * factors[1] * factors[2] * factors[3];
+        assert_eq!(chained, parse("26.467238393774143176728206470585982053"));
+        assert_eq!(
+            Decimal::product_exact(&factors),
+            Some(parse("26.467238393774143176728206470585982055"))
+        );
+    }
+
+    #[test]
+    fn test_product_exact_two() {
+        for a in crate::testing::corpus_random(7, 300).iter() {
+            for b in crate::testing::corpus_random(11, 30).iter() {
+                assert_eq!(Decimal::product_exact(&[*a, *b]), a.checked_mul(b), "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_add() {
+        fn assert_mul_add(a: &str, b: &str, c: &str, expected: Option<&str>) {
+            assert_eq!(
+                parse(a).mul_add(parse(b), parse(c)),
+                expected.map(parse),
+                "{} * {} + {}",
+                a,
+                b,
+                c
+            );
+        }
+
+        // interest accrual, where the product is rounded before the fee is added
+        let balance = parse("26388.494970017368233117341641353570");
+        let rate = parse("0.00198478");
+        let fee = parse("90.27");
+        assert_eq!(balance * rate + fee, parse("142.64535704659107212172663734292573867"));
+        assert_eq!(
+            balance.mul_add(rate, fee),
+            Some(parse("142.64535704659107212172663734292573866"))
+        );
+
+        let almost_one = "0.99999999999999999999999999999999999999";
+        assert_eq!(
+            parse(almost_one) * parse("5E-38") + Decimal::ONE,
+            parse("1.0000000000000000000000000000000000001")
+        );
+        assert_mul_add(almost_one, "5E-38", "1", Some("1"));
+        assert_mul_add(&format!("-{}", almost_one), "5E-38", "-1", Some("-1"));
+
+        // cancellation keeps the low digits of the product
+        assert_mul_add(
+            almost_one,
+            almost_one,
+            "-1",
+            Some("-1.99999999999999999999999999999999999999E-38"),
+        );
+        assert_mul_add(almost_one, almost_one, "-10", Some("-9"));
+        assert_mul_add(
+            almost_one,
+            almost_one,
+            "-0.1",
+            Some("0.89999999999999999999999999999999999998"),
+        );
+        assert_mul_add(
+            "1.2345678901234567890123456789012345678",
+            "-9.8765432109876543210987654321098765432",
+            "12.193263113702179522618503273362292333",
+            Some("-2.4386525547477518643499466543225118122E-29"),
+        );
+        assert_mul_add(
+            "3.1415926535897932384626433832795028842",
+            "2.7182818284590452353602874713526624978",
+            "-8.5397342226735670654635508695465744950",
+            Some("1.7689512235920941482361610088427615476E-37"),
+        );
+        assert_mul_add(
+            "12345678901234567890123456789012345678",
+            "98765432109876543210987654321098765432",
+            "-1219326311370217952261850327336229233322374638011112635269",
+            Some("1.2193263113702179510425240159684499336E75"),
+        );
+
+        // far apart
+        assert_mul_add("1E100", "1E-100", "1E-100", Some("1"));
+        assert_mul_add("1E60", "1E60", "1E-60", Some("1E120"));
+        assert_mul_add("1E-60", "1E-60", "1", Some("1"));
+        assert_mul_add("1E-100", "1E-31", "1E-130", Some("1.1E-130"));
+        assert_mul_add("1E-100", "1E-31", "-1E-130", Some("0"));
+
+        // the sum has 78 digits at the finer scale
+        let max = "99999999999999999999999999999999999999";
+        assert_mul_add(max, max, "99999999999999999999999999999999999999E38", Some("2E76"));
+
+        // overflow
+        let max = "9.9999999999999999999999999999999999999E125";
+        assert_mul_add(max, "1", "1E88", None);
+        assert_mul_add(max, "1", "4E87", Some(max));
+        assert_mul_add(max, "1", "5E87", None);
+        assert_mul_add("1E100", "1E30", "-1E125", None);
+        assert_eq!(parse("1E100").checked_mul(parse("1E26")), None);
+        assert_mul_add("1E100", "1E26", "-1E88", Some(max));
+
+        // zeros
+        assert_mul_add("0", "1E100", "-1.5", Some("-1.5"));
+        assert_mul_add("1E100", "0", "0", Some("0"));
+        assert_mul_add("-1.5", "2", "0", Some("-3"));
+        assert_mul_add("-1.5", "2", "3", Some("0"));
+    }
+
+    #[test]
+    fn test_mul_add_consistency() {
+        for a in crate::testing::corpus_random(19, 200).iter() {
+            for c in crate::testing::corpus_random(23, 50).iter() {
+                assert_eq!(a.mul_add(Decimal::ONE, c), a.checked_add(c), "{:?} {:?}", a, c);
+                assert_eq!(Decimal::ONE.mul_add(a, c), a.checked_add(c), "{:?} {:?}", a, c);
+                assert_eq!(a.mul_add(c, Decimal::ZERO), a.checked_mul(c), "{:?} {:?}", a, c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_wide_div_round() {
+        fn assert_div_round(a: &str, b: &str, c: &str, scale: i16, expected: Option<&str>) {
+            let result = parse(a).mul_wide(parse(b)).div_round(&parse(c), scale);
+            assert_eq!(result, expected.map(parse), "{} * {} / {}", a, b, c);
+            if let Some(result) = result {
+                assert!(result.is_zero() || result.scale() == scale, "{}", result);
+            }
+        }
+
+        // the product of 18 + 18 fractional digits is rounded before the division
+        let a = parse("123456789.123456789123456789");
+        let b = parse("987654321.987654321987654321");
+        assert_eq!(a * b / a, parse("987654321.98765432198765432100000000002"));
+        assert_eq!(a.mul_wide(b).div_round(&a, 18), Some(b));
+        assert_eq!(a.mul_wide(b).div_round(&b, 18), Some(a));
+
+        assert_div_round(
+            "1234.567890123456789012",
+            "-0.000987654321098765432109876",
+            "3",
+            30,
+            Some("-0.406442103790072650753836081816"),
+        );
+        assert_div_round("-2", "5", "-7", 37, Some("1.4285714285714285714285714285714285714"));
+        assert_div_round("1", "1", "3", 2, Some("0.33"));
+        assert_div_round("2", "1", "3", 0, Some("1"));
+        assert_div_round("1", "5", "2", 0, Some("3"));
+        assert_div_round("-1", "5", "2", 0, Some("-3"));
+        assert_div_round("1E100", "1E-100", "7E-51", 30, None);
+        assert_div_round(
+            "1E100",
+            "1E-100",
+            "7E-51",
+            -13,
+            Some("14285714285714285714285714285714285714E13"),
+        );
+        assert_div_round("1E-100", "1E-60", "3", 130, Some("0"));
+        assert_div_round("0", "1E100", "3", 2, Some("0"));
+        assert_div_round("1", "1", "0", 2, None);
+        assert_div_round("1", "1", "3", 131, None);
+
+        let max = "99999999999999999999999999999999999999";
+        assert_div_round(max, max, max, 0, Some(max));
+        assert_div_round(max, max, "99999999999999999999999999999999999998", 0, None);
+        assert_div_round(max, max, "1E38", 0, Some("99999999999999999999999999999999999998"));
+    }
+
+    #[test]
+    fn test_mul_wide_consistency() {
+        for a in crate::testing::corpus_random(13, 100).iter() {
+            for b in crate::testing::corpus_random(17, 20).iter() {
+                for &scale in [-5, 0, 2, 18, 40].iter() {
+                    let product = a.mul_wide(b);
+                    assert_eq!(
+                        product.to_decimal_round(scale),
+                        a.mul_with_scale(b, scale, RoundingMode::HalfUp),
+                        "{:?} {:?} {}",
+                        a,
+                        b,
+                        scale
+                    );
+                    assert_eq!(
+                        a.mul_wide(Decimal::ONE).div_round(b, scale),
+                        a.div_with_scale(b, scale, RoundingMode::HalfUp),
+                        "{:?} {:?} {}",
+                        a,
+                        b,
+                        scale
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_wide_parts() {
+        let product = parse("-1.5").mul_wide(parse("2.25"));
+        assert_eq!(product.scale(), 3);
+        assert!(product.is_sign_negative());
+        assert_eq!(product.to_decimal_round(3), Some(parse("-3.375")));
+        assert_eq!(product.to_decimal_round(2), Some(parse("-3.38")));
+
+        let zero = parse("-1.5").mul_wide(Decimal::ZERO);
+        assert_eq!(zero.scale(), 0);
+        assert!(!zero.is_sign_negative());
+        assert_eq!(zero.to_decimal_round(5), Some(Decimal::ZERO));
+        assert_eq!(zero.to_decimal_round(MAX_PRECISION as i16 + 131), None);
+    }
+
+    #[test]
+    fn test_product_exact_overflow() {
+        let big = parse("1E40");
+        assert_eq!(Decimal::product_exact(&[big, big, big, big]), None);
+        assert_eq!(Decimal::product_exact(&[big, big, big, parse("1E6")]), None);
+        assert_eq!(
+            Decimal::product_exact(&[big, big, big, parse("9.99E5")]),
+            Some(parse("9.99E125"))
+        );
+
+        let max = parse("9.9999999999999999999999999999999999999E125");
+        let tiny = parse("1E-130");
+        assert_eq!(
+            Decimal::product_exact(&[max, max, tiny, tiny]),
+            Some(parse("9.9999999999999999999999999999999999998E-9"))
+        );
+        assert_eq!(Decimal::product_exact(&[max, max, max, tiny]), None);
+
+        assert_eq!(Decimal::product_exact(&[Decimal::ONE; 8]), Some(Decimal::ONE));
+        assert_eq!(Decimal::product_exact(&[Decimal::ONE; 9]), None);
+    }
+}
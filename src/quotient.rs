@@ -0,0 +1,200 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Streaming quotient digits.
+
+use crate::decimal::Decimal;
+use crate::u256::{POWERS_10, U256};
+
+/// An iterator over the decimal digits of a quotient, created by [`Decimal::quotient_digits`].
+///
+/// The digits are yielded from the most significant one, without leading zeros, and
+/// the quotient equals `0.d1d2d3... * 10^point_position`. The iterator never ends:
+/// a terminating quotient yields zeros after its last significant digit.
+#[derive(Debug, Clone)]
+pub struct QuotientDigits {
+    int_part: u128,
+    int_digits: u32,
+    rem: u128,
+    divisor: u128,
+    point_position: i32,
+    negative: bool,
+}
+
+impl QuotientDigits {
+    /// Returns the position of the decimal point relative to the first yielded digit,
+    /// so that the quotient equals `0.d1d2d3... * 10^point_position`.
+    #[inline]
+    pub fn point_position(&self) -> i32 {
+        self.point_position
+    }
+
+    /// Returns `true` if the quotient is negative.
+    #[inline]
+    pub fn is_sign_negative(&self) -> bool {
+        self.negative
+    }
+}
+
+impl Iterator for QuotientDigits {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        if self.int_digits > 0 {
+            self.int_digits -= 1;
+            let digit = self.int_part / POWERS_10[self.int_digits as usize].low();
+            self.int_part -= digit * POWERS_10[self.int_digits as usize].low();
+            return Some(digit as u8);
+        }
+
+        // `rem < divisor < 10^38`, so `rem * 10` may exceed `u128`.
+        let (digit, rem) = U256::mul128(self.rem, 10).div_rem(self.divisor);
+        self.rem = rem.low();
+        Some(digit.low() as u8)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl Decimal {
+    /// Returns an iterator over the decimal digits of `self / other`, computed by long division,
+    /// returning `None` if `other` is zero.
+    ///
+    /// Unlike [`Decimal::checked_div`], the quotient is not limited to `MAX_PRECISION` digits.
+    /// The iterator is infinite, so use [`Iterator::take`] to get as many digits as needed.
+    #[inline]
+    pub fn quotient_digits(&self, other: &Decimal) -> Option<QuotientDigits> {
+        if other.is_zero() {
+            return None;
+        }
+
+        let exp = other.scale() as i32 - self.scale() as i32;
+
+        if self.is_zero() {
+            return Some(QuotientDigits {
+                int_part: 0,
+                int_digits: 1,
+                rem: 0,
+                divisor: other.int_val(),
+                point_position: 1,
+                negative: false,
+            });
+        }
+
+        let divisor = other.int_val();
+        let int_part = self.int_val() / divisor;
+        let mut rem = self.int_val() % divisor;
+
+        let (int_digits, point_position) = if int_part != 0 {
+            let digits = U256::from(int_part).count_digits();
+            (digits, digits as i32 + exp)
+        } else {
+            // Skip the leading zeros, so that the first yielded digit is non-zero.
+            let mut zeros = 0;
+            while let Some(val) = rem.checked_mul(10).filter(|v| *v < divisor) {
+                rem = val;
+                zeros += 1;
+            }
+            (0, exp - zeros)
+        };
+
+        Some(QuotientDigits {
+            int_part,
+            int_digits,
+            rem,
+            divisor,
+            point_position,
+            negative: self.is_sign_negative() != other.is_sign_negative(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(val1: &str, val2: &str, n: usize) -> (String, i32, bool) {
+        let var1 = val1.parse::<Decimal>().unwrap();
+        let var2 = val2.parse::<Decimal>().unwrap();
+        let iter = var1.quotient_digits(&var2).unwrap();
+        let point = iter.point_position();
+        let negative = iter.is_sign_negative();
+        let s = iter.take(n).map(|d| (b'0' + d) as char).collect();
+        (s, point, negative)
+    }
+
+    #[test]
+    fn test_quotient_digits() {
+        fn assert_digits(val1: &str, val2: &str, n: usize, expected: &str, point: i32, negative: bool) {
+            assert_eq!(digits(val1, val2, n), (expected.to_string(), point, negative));
+        }
+
+        assert_digits(
+            "1",
+            "7",
+            50,
+            "14285714285714285714285714285714285714285714285714",
+            0,
+            false,
+        );
+        assert_digits("1", "8", 8, "12500000", 0, false);
+        assert_digits("22", "7", 10, "3142857142", 1, false);
+        assert_digits("1", "3000", 5, "33333", -3, false);
+        assert_digits("0", "7", 5, "00000", 1, false);
+        assert_digits("0", "-7", 5, "00000", 1, false);
+        assert_digits("-1", "7", 6, "142857", 0, true);
+        assert_digits("1", "-7", 6, "142857", 0, true);
+        assert_digits("-1", "-7", 6, "142857", 0, false);
+        assert_digits("-12.5", "0.5", 4, "2500", 2, true);
+        assert_digits(
+            "99999999999999999999999999999999999999",
+            "1",
+            40,
+            "9999999999999999999999999999999999999900",
+            38,
+            false,
+        );
+        assert_digits(
+            "1",
+            "99999999999999999999999999999999999999",
+            80,
+            "10000000000000000000000000000000000000100000000000000000000000000000000000001000",
+            -37,
+            false,
+        );
+    }
+
+    #[test]
+    fn test_quotient_digits_extreme_scale() {
+        fn assert_point(val1: &str, val2: &str, point: i32) {
+            assert_eq!(digits(val1, val2, 1).1, point, "{} / {}", val1, val2);
+        }
+
+        assert_point("1e-130", "3", -130);
+        assert_point("1e-130", "1e125", -254);
+        assert_point("9e125", "1e-130", 256);
+        assert_point("1e125", "3e-130", 255);
+        assert_point("1.5e-130", "1.5e-130", 1);
+    }
+
+    #[test]
+    fn test_quotient_digits_zero_divisor() {
+        assert!(Decimal::ONE.quotient_digits(&Decimal::ZERO).is_none());
+        assert!(Decimal::ZERO.quotient_digits(&Decimal::ZERO).is_none());
+    }
+}
@@ -0,0 +1,286 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Raw parts of a decimal in a fixed byte order, for storage written by other languages.
+
+use crate::convert::MAX_I128_REPR;
+use crate::decimal::Decimal;
+use crate::DecimalConvertError;
+use core::convert::TryFrom;
+
+/// A decimal in a stable memory layout, e.g. for memory-mapped files.
+///
+/// The layout is part of the semver contract, and doesn't follow the internal representation of
+/// [`Decimal`]. It's `#[repr(C)]`, 20 bytes long and aligned to 1 byte, so that a slice of bytes of a
+/// suitable length may be viewed as a slice of `DecimalRaw` at any offset:
+///
+/// | offset | size | field         | content                                                      |
+/// |--------|------|---------------|--------------------------------------------------------------|
+/// | 0      | 16   | `mantissa_le` | the magnitude of the mantissa, as a little-endian `u128`     |
+/// | 16     | 2    | `scale_le`    | the scale, as a little-endian `i16`                          |
+/// | 18     | 1    | `flags`       | bit 0 is set for a negative value, the other bits are zero   |
+/// | 19     | 1    | `reserved`    | zero                                                         |
+///
+/// The value is `mantissa * 10^-scale`. The mantissa has at most 38 digits and the scale is in
+/// `[-126, 167]`, and a zero is read as [`Decimal::ZERO`] whatever its sign and scale.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DecimalRaw {
+    /// The magnitude of the mantissa, in little-endian byte order.
+    pub mantissa_le: [u8; 16],
+    /// The scale, in little-endian byte order.
+    pub scale_le: [u8; 2],
+    /// The flags, of which only [`DecimalRaw::FLAG_NEGATIVE`] is defined.
+    pub flags: u8,
+    /// Reserved, must be zero.
+    pub reserved: u8,
+}
+
+const _: [(); DecimalRaw::SIZE] = [(); core::mem::size_of::<DecimalRaw>()];
+const _: [(); 1] = [(); core::mem::align_of::<DecimalRaw>()];
+
+impl DecimalRaw {
+    /// The size of a `DecimalRaw` in bytes.
+    pub const SIZE: usize = 20;
+
+    /// The flag of a negative value.
+    pub const FLAG_NEGATIVE: u8 = 1;
+}
+
+impl From<Decimal> for DecimalRaw {
+    #[inline]
+    fn from(val: Decimal) -> Self {
+        let (mantissa_le, scale, negative) = val.to_raw_le();
+        DecimalRaw {
+            mantissa_le,
+            scale_le: scale.to_le_bytes(),
+            flags: if negative { DecimalRaw::FLAG_NEGATIVE } else { 0 },
+            reserved: 0,
+        }
+    }
+}
+
+impl TryFrom<DecimalRaw> for Decimal {
+    type Error = DecimalConvertError;
+
+    /// Validates `raw`, failing with `Invalid` on unknown flags or a non-zero reserved byte, and
+    /// otherwise as [`Decimal::from_raw_le`] does.
+    #[inline]
+    fn try_from(raw: DecimalRaw) -> Result<Self, Self::Error> {
+        if raw.flags & !DecimalRaw::FLAG_NEGATIVE != 0 || raw.reserved != 0 {
+            return Err(DecimalConvertError::Invalid);
+        }
+
+        let negative = raw.flags & DecimalRaw::FLAG_NEGATIVE != 0;
+        Decimal::from_raw_le(raw.mantissa_le, i16::from_le_bytes(raw.scale_le), negative)
+    }
+}
+
+/// Validates the mantissa and the scale of raw parts.
+#[inline]
+fn from_raw(mantissa: u128, scale: i16, negative: bool) -> Result<Decimal, DecimalConvertError> {
+    if mantissa > MAX_I128_REPR as u128 {
+        return Err(DecimalConvertError::TooManyDigits);
+    }
+
+    Decimal::from_parts(mantissa, scale, negative)
+}
+
+impl Decimal {
+    /// Creates a `Decimal` from the magnitude of the mantissa in little-endian byte order, the scale and
+    /// the sign, i.e. `mantissa * 10^-scale`.
+    ///
+    /// Fails with `TooManyDigits` if the mantissa has more than 38 digits, or `ScaleOutOfRange` if the
+    /// scale is out of `[-126, 167]`. A zero is [`Decimal::ZERO`], whatever its sign and scale.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let mut mantissa = [0; 16];
+    /// mantissa[..2].copy_from_slice(&[0x39, 0x30]);
+    /// let n = Decimal::from_raw_le(mantissa, 2, true).unwrap();
+    /// assert_eq!(n.to_string(), "-123.45");
+    /// assert_eq!(n.to_raw_le(), (mantissa, 2, true));
+    /// ```
+    #[inline]
+    pub fn from_raw_le(mantissa_le: [u8; 16], scale: i16, negative: bool) -> Result<Decimal, DecimalConvertError> {
+        from_raw(u128::from_le_bytes(mantissa_le), scale, negative)
+    }
+
+    /// Creates a `Decimal` from the magnitude of the mantissa in big-endian byte order, the scale and
+    /// the sign, as [`Decimal::from_raw_le`] does.
+    #[inline]
+    pub fn from_raw_be(mantissa_be: [u8; 16], scale: i16, negative: bool) -> Result<Decimal, DecimalConvertError> {
+        from_raw(u128::from_be_bytes(mantissa_be), scale, negative)
+    }
+
+    /// Creates a `Decimal` from the magnitude of the mantissa in native byte order, the scale and the
+    /// sign, as [`Decimal::from_raw_le`] does.
+    #[inline]
+    pub fn from_raw_ne(mantissa_ne: [u8; 16], scale: i16, negative: bool) -> Result<Decimal, DecimalConvertError> {
+        from_raw(u128::from_ne_bytes(mantissa_ne), scale, negative)
+    }
+
+    /// Returns the magnitude of the mantissa in little-endian byte order, the scale and the sign, which
+    /// [`Decimal::from_raw_le`] reads back.
+    #[inline]
+    pub fn to_raw_le(&self) -> ([u8; 16], i16, bool) {
+        let (int_val, scale, negative) = self.into_parts();
+        (int_val.to_le_bytes(), scale, negative)
+    }
+
+    /// Returns the magnitude of the mantissa in big-endian byte order, the scale and the sign, which
+    /// [`Decimal::from_raw_be`] reads back.
+    #[inline]
+    pub fn to_raw_be(&self) -> ([u8; 16], i16, bool) {
+        let (int_val, scale, negative) = self.into_parts();
+        (int_val.to_be_bytes(), scale, negative)
+    }
+
+    /// Returns the magnitude of the mantissa in native byte order, the scale and the sign, which
+    /// [`Decimal::from_raw_ne`] reads back.
+    #[inline]
+    pub fn to_raw_ne(&self) -> ([u8; 16], i16, bool) {
+        let (int_val, scale, negative) = self.into_parts();
+        (int_val.to_ne_bytes(), scale, negative)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a record as the C++ writer does: a little-endian mantissa, a little-endian scale, the
+    /// flags and a zero byte.
+    fn fixture(mantissa: u128, scale: i16, flags: u8) -> [u8; DecimalRaw::SIZE] {
+        let mut bytes = [0; DecimalRaw::SIZE];
+        bytes[..16].copy_from_slice(&mantissa.to_le_bytes());
+        bytes[16..18].copy_from_slice(&scale.to_le_bytes());
+        bytes[18] = flags;
+        bytes
+    }
+
+    /// Views a record as a `DecimalRaw`, as a memory-mapped file is.
+    fn view(bytes: &[u8; DecimalRaw::SIZE]) -> DecimalRaw {
+        unsafe { std::ptr::read(bytes.as_ptr() as *const DecimalRaw) }
+    }
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(std::mem::size_of::<DecimalRaw>(), DecimalRaw::SIZE);
+        assert_eq!(std::mem::align_of::<DecimalRaw>(), 1);
+
+        assert_eq!(
+            fixture(0x0102_0304, -2, 1),
+            [4, 3, 2, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xfe, 0xff, 1, 0]
+        );
+        assert_eq!(view(&fixture(12345, 2, 1)), DecimalRaw::from(parse("-123.45")));
+    }
+
+    #[test]
+    fn test_raw_fixtures() {
+        fn assert_fixture(mantissa: u128, scale: i16, flags: u8, expected: Result<&str, DecimalConvertError>) {
+            let raw = view(&fixture(mantissa, scale, flags));
+            let result = Decimal::try_from(raw);
+            assert_eq!(result, expected.map(parse), "{:?}", raw);
+            if let Ok(val) = result {
+                if !val.is_zero() {
+                    assert_eq!(DecimalRaw::from(val), raw);
+                }
+            }
+        }
+
+        assert_fixture(0, 0, 0, Ok("0"));
+        assert_fixture(12345, 2, 0, Ok("123.45"));
+        assert_fixture(12345, 2, 1, Ok("-123.45"));
+        assert_fixture(5, -3, 1, Ok("-5000"));
+        assert_fixture(1, 130, 0, Ok("1E-130"));
+        assert_fixture(
+            MAX_I128_REPR as u128,
+            -88,
+            0,
+            Ok("9.9999999999999999999999999999999999999E125"),
+        );
+
+        // zeros are canonical
+        assert_fixture(0, 0, 1, Ok("0"));
+        assert_fixture(0, 42, 1, Ok("0"));
+        assert_eq!(
+            Decimal::try_from(view(&fixture(0, 42, 1))).unwrap().into_parts(),
+            (0, 0, false)
+        );
+
+        // bounds of the mantissa and the scale
+        assert_fixture(MAX_I128_REPR as u128 + 1, 0, 0, Err(DecimalConvertError::TooManyDigits));
+        assert_fixture(u128::MAX, 0, 0, Err(DecimalConvertError::TooManyDigits));
+        assert_eq!(
+            Decimal::from_raw_le(1u128.to_le_bytes(), 167, false)
+                .unwrap()
+                .into_parts(),
+            (1, 167, false)
+        );
+        assert_fixture(1, 168, 0, Err(DecimalConvertError::ScaleOutOfRange));
+        assert_eq!(
+            Decimal::from_raw_le(1u128.to_le_bytes(), -126, false)
+                .unwrap()
+                .into_parts(),
+            (1, -126, false)
+        );
+        assert_fixture(1, -127, 0, Err(DecimalConvertError::ScaleOutOfRange));
+        assert_fixture(1, i16::MIN, 0, Err(DecimalConvertError::ScaleOutOfRange));
+
+        // unknown flags and a non-zero reserved byte
+        assert_fixture(1, 0, 2, Err(DecimalConvertError::Invalid));
+        assert_fixture(1, 0, 0x81, Err(DecimalConvertError::Invalid));
+        let mut bytes = fixture(1, 0, 0);
+        bytes[19] = 1;
+        assert_eq!(Decimal::try_from(view(&bytes)), Err(DecimalConvertError::Invalid));
+    }
+
+    #[test]
+    fn test_raw_endianness() {
+        for val in crate::testing::corpus().iter() {
+            let (int_val, scale, negative) = val.into_parts();
+
+            let (le, s, n) = val.to_raw_le();
+            assert_eq!((le, s, n), (int_val.to_le_bytes(), scale, negative));
+            assert_eq!(Decimal::from_raw_le(le, s, n).unwrap().into_parts(), val.into_parts());
+
+            let (be, s, n) = val.to_raw_be();
+            let mut reversed = le;
+            reversed.reverse();
+            assert_eq!(be, reversed);
+            assert_eq!(Decimal::from_raw_be(be, s, n).unwrap().into_parts(), val.into_parts());
+
+            let (ne, s, n) = val.to_raw_ne();
+            assert_eq!(Decimal::from_raw_ne(ne, s, n).unwrap().into_parts(), val.into_parts());
+            assert_eq!(
+                Decimal::try_from(DecimalRaw::from(*val)).unwrap().into_parts(),
+                val.into_parts()
+            );
+        }
+
+        let mut be = [0; 16];
+        be[15] = 0xff;
+        assert_eq!(Decimal::from_raw_be(be, 1, false), Ok(parse("25.5")));
+        assert_eq!(
+            Decimal::from_raw_le(be, 1, false),
+            Err(DecimalConvertError::TooManyDigits)
+        );
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions from and to `rust_decimal::Decimal`.
+
+use crate::decimal::Decimal;
+use crate::error::DecimalConvertError;
+use crate::u256::POWERS_10;
+use core::convert::TryFrom;
+
+/// The largest mantissa of a `rust_decimal::Decimal`, i.e. `2^96 - 1`.
+const MAX_MANTISSA: u128 = (1 << 96) - 1;
+
+/// The largest scale of a `rust_decimal::Decimal`.
+const MAX_SCALE: i32 = 28;
+
+/// Converts exactly, since a `rust_decimal::Decimal` has at most 29 digits and a scale in `[0, 28]`.
+#[cfg_attr(docsrs, doc(cfg(feature = "rust_decimal")))]
+impl From<rust_decimal::Decimal> for Decimal {
+    #[inline]
+    fn from(val: rust_decimal::Decimal) -> Self {
+        let mantissa = val.mantissa();
+        unsafe { Decimal::from_parts_unchecked(mantissa.unsigned_abs(), val.scale() as i16, mantissa < 0) }
+    }
+}
+
+/// Converts exactly, with the least scale which keeps every digit.
+///
+/// Fails with `Overflow` if the magnitude is from `2^96`, or with `Inexact` if the value has digits
+/// after the 28th fractional one, or more than 96 bits of mantissa at its scale.
+#[cfg_attr(docsrs, doc(cfg(feature = "rust_decimal")))]
+impl TryFrom<Decimal> for rust_decimal::Decimal {
+    type Error = DecimalConvertError;
+
+    #[inline]
+    fn try_from(val: Decimal) -> Result<Self, Self::Error> {
+        let (mut mantissa, exp, negative) = val.factor_pow10();
+        let scale = if exp >= 0 {
+            if exp > MAX_SCALE {
+                return Err(DecimalConvertError::Overflow);
+            }
+            mantissa = mantissa
+                .checked_mul(POWERS_10[exp as usize].low())
+                .ok_or(DecimalConvertError::Overflow)?;
+            0
+        } else {
+            -exp
+        };
+
+        if mantissa > MAX_MANTISSA || scale > MAX_SCALE {
+            return Err(match u128::try_from(val.trunc(0).abs()) {
+                Ok(integral) if integral <= MAX_MANTISSA => DecimalConvertError::Inexact,
+                _ => DecimalConvertError::Overflow,
+            });
+        }
+
+        let mantissa = if negative {
+            -(mantissa as i128)
+        } else {
+            mantissa as i128
+        };
+        rust_decimal::Decimal::try_from_i128_with_scale(mantissa, scale as u32)
+            .map_err(|_| DecimalConvertError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn rd(s: &str) -> rust_decimal::Decimal {
+        rust_decimal::Decimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_from_rust_decimal() {
+        fn assert_from(val: rust_decimal::Decimal, expected: &str) {
+            let n = Decimal::from(val);
+            assert_eq!(n, parse(expected), "{}", val);
+            assert_eq!(n.scale(), val.scale() as i16, "{}", val);
+            assert_eq!(rust_decimal::Decimal::try_from(n), Ok(val), "{}", val);
+        }
+
+        assert_from(rd("0"), "0");
+        assert_from(rd("1"), "1");
+        assert_from(rd("-1"), "-1");
+        assert_from(rd("123.4500"), "123.45");
+        assert_from(rd("-0.0000000000000000000000000001"), "-1e-28");
+        assert_from(rust_decimal::Decimal::MAX, "79228162514264337593543950335");
+        assert_from(rust_decimal::Decimal::MIN, "-79228162514264337593543950335");
+        assert_from(
+            rust_decimal::Decimal::from_i128_with_scale(MAX_MANTISSA as i128, 28),
+            "7.9228162514264337593543950335",
+        );
+        assert_from(
+            rust_decimal::Decimal::from_i128_with_scale(-(MAX_MANTISSA as i128), 28),
+            "-7.9228162514264337593543950335",
+        );
+
+        let negative_zero = rust_decimal::Decimal::from_parts(0, 0, 0, true, 5);
+        assert_eq!(Decimal::from(negative_zero).into_parts(), (0, 0, false));
+    }
+
+    #[test]
+    fn test_to_rust_decimal() {
+        fn assert_to(val: &str, expected: Result<&str, DecimalConvertError>) {
+            assert_eq!(rust_decimal::Decimal::try_from(parse(val)), expected.map(rd), "{}", val);
+        }
+
+        assert_to("0", Ok("0"));
+        assert_to("-123.45", Ok("-123.45"));
+        assert_to("1e28", Ok("10000000000000000000000000000"));
+        assert_to("-1e-28", Ok("-0.0000000000000000000000000001"));
+        assert_to("79228162514264337593543950335", Ok("79228162514264337593543950335"));
+        assert_to("-79228162514264337593543950335", Ok("-79228162514264337593543950335"));
+        assert_to("7.9228162514264337593543950335", Ok("7.9228162514264337593543950335"));
+
+        assert_to("79228162514264337593543950336", Err(DecimalConvertError::Overflow));
+        assert_to("-79228162514264337593543950336", Err(DecimalConvertError::Overflow));
+        assert_to("79228162514264337593543950336.5", Err(DecimalConvertError::Overflow));
+        assert_to("1e29", Err(DecimalConvertError::Overflow));
+        assert_to("1e100", Err(DecimalConvertError::Overflow));
+        assert_to("79228162514264337593543950335.5", Err(DecimalConvertError::Inexact));
+        assert_to("7.92281625142643375935439503355", Err(DecimalConvertError::Inexact));
+        assert_to("1e-29", Err(DecimalConvertError::Inexact));
+        assert_to("-1.5e-28", Err(DecimalConvertError::Inexact));
+        assert_eq!(
+            rust_decimal::Decimal::try_from(Decimal::MAX),
+            Err(DecimalConvertError::Overflow)
+        );
+        assert_eq!(
+            rust_decimal::Decimal::try_from(Decimal::MIN_POSITIVE),
+            Err(DecimalConvertError::Inexact)
+        );
+
+        // Every decimal which converts comes back unchanged.
+        for val in crate::testing::corpus().iter() {
+            if let Ok(converted) = rust_decimal::Decimal::try_from(*val) {
+                assert_eq!(Decimal::from(converted), *val, "{}", val);
+            }
+        }
+    }
+}
@@ -14,7 +14,9 @@
 
 //! serde implementation.
 
-use crate::decimal::{Buf, Decimal};
+use crate::decimal::{Buf, Decimal, MAX_BINARY_SIZE};
+use crate::fixed::FixedScale;
+use crate::units::{BasisPoints, PerMille, Percent};
 
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 impl serde::Serialize for Decimal {
@@ -23,16 +25,19 @@ impl serde::Serialize for Decimal {
     where
         S: serde::ser::Serializer,
     {
-        use std::io::Write;
+        use core::fmt::Write;
 
-        let mut buf = Buf::new();
         if serializer.is_human_readable() {
+            let mut buf = Buf::new();
             write!(&mut buf, "{}", self).map_err(serde::ser::Error::custom)?;
-            let str = unsafe { std::str::from_utf8_unchecked(buf.as_slice()) };
+            let str = unsafe { core::str::from_utf8_unchecked(buf.as_slice()) };
             str.serialize(serializer)
         } else {
-            self.encode(&mut buf).map_err(serde::ser::Error::custom)?;
-            buf.as_slice().serialize(serializer)
+            let mut bytes = [0; MAX_BINARY_SIZE];
+            let size = self
+                .encode_to_slice(&mut bytes)
+                .expect("an encoded decimal fits in MAX_BINARY_SIZE bytes");
+            bytes[..size].serialize(serializer)
         }
     }
 }
@@ -50,7 +55,7 @@ impl<'de> serde::Deserialize<'de> for Decimal {
             type Value = Decimal;
 
             #[inline]
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 write!(formatter, "a decimal")
             }
 
@@ -67,8 +72,7 @@ impl<'de> serde::Deserialize<'de> for Decimal {
             where
                 E: serde::de::Error,
             {
-                let n = Decimal::decode(v);
-                Ok(n)
+                Decimal::try_decode(v).map_err(serde::de::Error::custom)
             }
         }
 
@@ -80,6 +84,111 @@ impl<'de> serde::Deserialize<'de> for Decimal {
     }
 }
 
+macro_rules! impl_serde_unit {
+    ($($unit: ident),* $(,)?) => {
+        $(
+            #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+            impl serde::Serialize for $unit {
+                #[inline]
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: serde::ser::Serializer,
+                {
+                    self.0.serialize(serializer)
+                }
+            }
+
+            #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+            impl<'de> serde::Deserialize<'de> for $unit {
+                #[inline]
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::de::Deserializer<'de>,
+                {
+                    Decimal::deserialize(deserializer).map($unit)
+                }
+            }
+        )*
+    };
+}
+
+impl_serde_unit!(Percent, PerMille, BasisPoints);
+
+/// Serializes the text with exactly `S` digits after the decimal point in human-readable formats.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<const S: i16> serde::Serialize for FixedScale<S> {
+    #[inline]
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: serde::ser::Serializer,
+    {
+        use core::fmt::Write;
+
+        if serializer.is_human_readable() {
+            let mut buf = Buf::new();
+            write!(&mut buf, "{}", self).map_err(serde::ser::Error::custom)?;
+            let str = unsafe { core::str::from_utf8_unchecked(buf.as_slice()) };
+            str.serialize(serializer)
+        } else {
+            self.value().serialize(serializer)
+        }
+    }
+}
+
+/// Fails if the value has non-zero digits beyond `S` digits after the decimal point.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, const S: i16> serde::Deserialize<'de> for FixedScale<S> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        use core::convert::TryFrom;
+
+        let val = Decimal::deserialize(deserializer)?;
+        FixedScale::try_from(val).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serializes a decimal as a `(mantissa, scale)` tuple of `(i128, i16)`.
+///
+/// This keeps the exact representation of the decimal, e.g. for the schemas of Avro or Protobuf
+/// which store a decimal as an integer mantissa and a scale. Use it with `#[serde(with = "...")]`:
+///
+/// ```
+/// use decimal_rs::Decimal;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Price {
+///     #[serde(with = "decimal_rs::serde::tuple")]
+///     amount: Decimal,
+/// }
+/// ```
+pub mod tuple {
+    use crate::decimal::Decimal;
+    use serde::{Deserialize, Serialize};
+
+    /// Serializes `value` as a `(mantissa, scale)` tuple.
+    #[inline]
+    pub fn serialize<S>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        value.to_scaled_i128().serialize(serializer)
+    }
+
+    /// Deserializes a decimal from a `(mantissa, scale)` tuple.
+    #[inline]
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let (mantissa, scale) = <(i128, i16)>::deserialize(deserializer)?;
+        Decimal::from_scaled_i128(mantissa, scale).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,5 +205,111 @@ mod tests {
         let bin = bincode::serialize(&dec).unwrap();
         let bin_dec: Decimal = bincode::deserialize(&bin).unwrap();
         assert_eq!(bin_dec, dec);
+
+        // the scale byte after the length is out of range
+        let mut bin = bin;
+        bin[9] = 200;
+        assert!(bincode::deserialize::<Decimal>(&bin).is_err());
+    }
+
+    #[test]
+    fn test_serde_units() {
+        let pct = Percent("12.5".parse().unwrap());
+        let json = serde_json::to_string(&pct).unwrap();
+        assert_eq!(json, r#""12.5""#);
+        assert_eq!(serde_json::from_str::<Percent>(&json).unwrap(), pct);
+
+        let bp = BasisPoints("-0.25".parse().unwrap());
+        let bin = bincode::serialize(&bp).unwrap();
+        assert_eq!(bin, bincode::serialize(&bp.0).unwrap());
+        assert_eq!(bincode::deserialize::<BasisPoints>(&bin).unwrap(), bp);
+
+        let per_mille: PerMille = serde_json::from_str(r#""3""#).unwrap();
+        assert_eq!(per_mille, PerMille(Decimal::from(3)));
+        assert!(serde_json::from_str::<PerMille>(r#""3%""#).is_err());
+    }
+
+    #[test]
+    fn test_serde_fixed_scale() {
+        use std::convert::TryFrom;
+
+        let amount = FixedScale::<2>::try_from(Decimal::from(12)).unwrap();
+        let json = serde_json::to_string(&amount).unwrap();
+        assert_eq!(json, r#""12.00""#);
+        assert_eq!(serde_json::from_str::<FixedScale<2>>(&json).unwrap(), amount);
+
+        let rate = FixedScale::<8>::try_from("-0.5".parse::<Decimal>().unwrap()).unwrap();
+        assert_eq!(serde_json::to_string(&rate).unwrap(), r#""-0.50000000""#);
+        assert_eq!(serde_json::to_string(&FixedScale::<2>::default()).unwrap(), r#""0.00""#);
+        assert_eq!(
+            serde_json::to_string(&FixedScale::<-2>::try_from(Decimal::from(1200)).unwrap()).unwrap(),
+            r#""1200""#
+        );
+
+        let bin = bincode::serialize(&rate).unwrap();
+        assert_eq!(bin, bincode::serialize(&rate.value()).unwrap());
+        assert_eq!(bincode::deserialize::<FixedScale<8>>(&bin).unwrap(), rate);
+
+        assert_eq!(
+            serde_json::from_str::<FixedScale<2>>(r#""1.5""#).unwrap().to_string(),
+            "1.50"
+        );
+        assert!(serde_json::from_str::<FixedScale<2>>(r#""1.505""#).is_err());
+        let bin = bincode::serialize(&FixedScale::<8>::try_from("0.125".parse::<Decimal>().unwrap()).unwrap()).unwrap();
+        assert!(bincode::deserialize::<FixedScale<2>>(&bin).is_err());
+    }
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Tuple {
+        #[serde(with = "tuple")]
+        val: Decimal,
+    }
+
+    #[test]
+    fn test_serde_tuple() {
+        fn assert_round_trip(val: Decimal, json: &str) {
+            let tuple = Tuple { val };
+
+            let json_str = serde_json::to_string(&tuple).unwrap();
+            assert_eq!(json_str, json);
+            let json_tuple: Tuple = serde_json::from_str(&json_str).unwrap();
+            assert_eq!(json_tuple, tuple);
+            assert_eq!(json_tuple.val.scale(), tuple.val.scale());
+
+            let bin = bincode::serialize(&tuple).unwrap();
+            assert_eq!(bin.len(), 18);
+            let bin_tuple: Tuple = bincode::deserialize(&bin).unwrap();
+            assert_eq!(bin_tuple, tuple);
+            assert_eq!(bin_tuple.val.scale(), tuple.val.scale());
+        }
+
+        assert_round_trip(parse("0"), r#"{"val":[0,0]}"#);
+        assert_round_trip(parse("123.456"), r#"{"val":[123456,3]}"#);
+        assert_round_trip(parse("-123.456"), r#"{"val":[-123456,3]}"#);
+        assert_round_trip(
+            Decimal::from_parts(1234560, 4, true).unwrap(),
+            r#"{"val":[-1234560,4]}"#,
+        );
+        assert_round_trip(parse("1e-130"), r#"{"val":[1,130]}"#);
+        assert_round_trip(parse("-1e125"), r#"{"val":[-1,-125]}"#);
+        assert_round_trip(
+            parse("-9.9999999999999999999999999999999999999E125"),
+            r#"{"val":[-99999999999999999999999999999999999999,-88]}"#,
+        );
+        assert_round_trip(
+            parse("9.9999999999999999999999999999999999999E-93"),
+            r#"{"val":[99999999999999999999999999999999999999,130]}"#,
+        );
+    }
+
+    #[test]
+    fn test_serde_tuple_invalid() {
+        assert!(serde_json::from_str::<Tuple>(r#"{"val":[100000000000000000000000000000000000000,0]}"#).is_err());
+        assert!(serde_json::from_str::<Tuple>(r#"{"val":[1,-127]}"#).is_err());
+        assert!(serde_json::from_str::<Tuple>(r#"{"val":"1.5"}"#).is_err());
     }
 }
@@ -0,0 +1,419 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Incremental parsing of a decimal string received in chunks.
+
+use crate::decimal::Decimal;
+use crate::error::DecimalParseError;
+use crate::parse::DigitsAccumulator;
+
+/// The position of a [`DecimalParser`] in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Before the number, in the leading whitespaces.
+    Start,
+    /// After the sign of the number.
+    Sign,
+    /// In the integral digits.
+    Integral,
+    /// After the decimal point, in the fractional digits.
+    Fractional,
+    /// After the `e` of the exponent.
+    ExpStart,
+    /// After the sign of the exponent.
+    ExpSign,
+    /// In the digits of the exponent.
+    ExpDigits,
+    /// After the number, in the trailing whitespaces.
+    End,
+}
+
+/// An incremental parser of a decimal string split into chunks, e.g. across network frames, without
+/// concatenating them first.
+///
+/// The input is the same as [`str::parse`], including the whitespaces around the number and the
+/// exponent, and the result is the same, rounded to 38 significant digits. A chunk may end anywhere,
+/// e.g. right after the `.` or the `e`. The digits are accumulated as they come, so the memory used
+/// doesn't depend on the length of the input.
+///
+/// An error is returned as soon as the input can't be a decimal any more, and then for every
+/// following call.
+///
+/// ```
+/// use decimal_rs::{Decimal, DecimalParser};
+///
+/// let mut parser = DecimalParser::new();
+/// parser.push_bytes(b" -1234.5").unwrap();
+/// parser.push_bytes(b"678e").unwrap();
+/// parser.push_bytes(b"-2").unwrap();
+/// assert_eq!(parser.finish().unwrap(), "-12.345678".parse::<Decimal>().unwrap());
+///
+/// let mut parser = DecimalParser::new();
+/// assert!(parser.push_bytes(b"12x").is_err());
+/// assert!(parser.finish().is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DecimalParser {
+    state: State,
+    negative: bool,
+    digits: DigitsAccumulator,
+    frac_len: i64,
+    exp_negative: bool,
+    exp: i64,
+    // The count of the digits of the exponent from the first non-zero one.
+    exp_digits: usize,
+    error: Option<DecimalParseError>,
+}
+
+impl Default for DecimalParser {
+    #[inline]
+    fn default() -> Self {
+        DecimalParser::new()
+    }
+}
+
+impl DecimalParser {
+    /// Creates a parser expecting the start of the input.
+    #[inline]
+    pub fn new() -> DecimalParser {
+        DecimalParser {
+            state: State::Start,
+            negative: false,
+            digits: DigitsAccumulator::default(),
+            frac_len: 0,
+            exp_negative: false,
+            exp: 0,
+            exp_digits: 0,
+            error: None,
+        }
+    }
+
+    /// Parses the next chunk of the input.
+    #[inline]
+    pub fn push_bytes(&mut self, chunk: &[u8]) -> Result<(), DecimalParseError> {
+        if let Some(e) = &self.error {
+            return Err(e.clone());
+        }
+
+        for &b in chunk {
+            if let Err(e) = self.push_byte(b) {
+                self.error = Some(e.clone());
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn push_byte(&mut self, b: u8) -> Result<(), DecimalParseError> {
+        let d = b.wrapping_sub(b'0');
+        let is_digit = d <= 9;
+        let has_digits = self.digits.len() > 0;
+
+        self.state = match self.state {
+            State::Start if b.is_ascii_whitespace() => State::Start,
+            State::Start if b == b'+' || b == b'-' => {
+                self.negative = b == b'-';
+                State::Sign
+            }
+            State::Start | State::Sign | State::Integral if is_digit => {
+                self.digits.push(d);
+                State::Integral
+            }
+            State::Start | State::Sign | State::Integral if b == b'.' => State::Fractional,
+            State::Fractional if is_digit => {
+                self.digits.push(d);
+                self.frac_len += 1;
+                State::Fractional
+            }
+            State::Integral | State::Fractional if has_digits && (b == b'e' || b == b'E') => State::ExpStart,
+            State::ExpStart if b == b'+' || b == b'-' => {
+                self.exp_negative = b == b'-';
+                State::ExpSign
+            }
+            State::ExpStart | State::ExpSign | State::ExpDigits if is_digit => {
+                self.push_exp_digit(d)?;
+                State::ExpDigits
+            }
+            State::Integral | State::Fractional | State::ExpDigits | State::End
+                if has_digits && b.is_ascii_whitespace() =>
+            {
+                State::End
+            }
+            _ => return Err(self.unexpected_byte_error()),
+        };
+        Ok(())
+    }
+
+    /// The error of a byte which can't follow the input so far. As [`str::parse`] checks the range of
+    /// the number before the bytes after it, a number out of range fails with `Overflow` or `Underflow`.
+    #[inline]
+    fn unexpected_byte_error(&self) -> DecimalParseError {
+        match self.state {
+            State::Integral | State::Fractional | State::ExpDigits | State::End if self.digits.len() > 0 => {
+                match self.value() {
+                    Err(e @ DecimalParseError::Overflow) | Err(e @ DecimalParseError::Underflow) => e,
+                    _ => DecimalParseError::Invalid,
+                }
+            }
+            _ => DecimalParseError::Invalid,
+        }
+    }
+
+    /// Appends a digit to the exponent, which is ignored if the number is zero, failing as
+    /// [`str::parse`] does if it has more than 3 digits.
+    #[inline]
+    fn push_exp_digit(&mut self, d: u8) -> Result<(), DecimalParseError> {
+        if self.digits.is_zero() || (self.exp_digits == 0 && d == 0) {
+            return Ok(());
+        }
+
+        self.exp_digits += 1;
+        if self.exp_digits > 3 {
+            return Err(if self.exp_negative {
+                DecimalParseError::Underflow
+            } else {
+                DecimalParseError::Overflow
+            });
+        }
+        self.exp = self.exp * 10 + d as i64;
+        Ok(())
+    }
+
+    /// Completes the input and returns the decimal.
+    #[inline]
+    pub fn finish(self) -> Result<Decimal, DecimalParseError> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+
+        match self.state {
+            State::Start => Err(DecimalParseError::Empty),
+            State::Sign | State::ExpStart | State::ExpSign => Err(DecimalParseError::Invalid),
+            State::Fractional if self.digits.len() == 0 => Err(DecimalParseError::Invalid),
+            _ => self.value(),
+        }
+    }
+
+    /// The decimal of the digits and the exponent so far.
+    #[inline]
+    fn value(&self) -> Result<Decimal, DecimalParseError> {
+        let exp = if self.exp_negative { -self.exp } else { self.exp };
+        self.digits.finish(self.frac_len, exp, self.negative)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_chunks(chunks: &[&[u8]]) -> Result<Decimal, DecimalParseError> {
+        let mut parser = DecimalParser::new();
+        for chunk in chunks.iter() {
+            parser.push_bytes(chunk)?;
+        }
+        parser.finish()
+    }
+
+    fn assert_same(s: &str, result: Result<Decimal, DecimalParseError>, split: &str) {
+        let expected = s.parse::<Decimal>();
+        assert_eq!(result, expected, "{:?} {}", s, split);
+        if let (Ok(n), Ok(expected)) = (result, expected) {
+            assert_eq!(n.scale(), expected.scale(), "{:?} {}", s, split);
+        }
+    }
+
+    fn assert_splits(s: &str) {
+        let bytes = s.as_bytes();
+        assert_same(s, parse_chunks(&[bytes]), "whole");
+
+        for i in 0..=bytes.len() {
+            let (a, b) = bytes.split_at(i);
+            assert_same(s, parse_chunks(&[a, b]), &format!("at {}", i));
+            assert_same(
+                s,
+                parse_chunks(&[a, &[], b, &[]]),
+                &format!("at {} with empty chunks", i),
+            );
+        }
+
+        let chunks = bytes.chunks(1).collect::<Vec<_>>();
+        assert_same(s, parse_chunks(&chunks), "by byte");
+    }
+
+    #[test]
+    fn test_parse_boundary() {
+        let values = [
+            "100E-131",
+            "0.000012345E130",
+            "4.94065645841247E-126",
+            "1234.94065645841247E-126",
+            "12345678987654321999999E-132",
+            "10000000000000000000000000000000000000e88",
+            "0.999999999999999999999999999999999999995e-130",
+            "0.999999999999999999999999999999999999995e-131",
+            "999999999999999999999999999999999999995000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "999999999999999999999999999999999999994000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "1E-130",
+            "1E-131",
+            "1E125",
+            "1E126",
+        ];
+        for s in values.iter() {
+            assert_splits(s);
+        }
+    }
+
+    #[test]
+    fn test_parse_over_precision() {
+        let values = [
+            "999999999999999999999999999999999999999",
+            "900719925474099290071992547409929007112123123123123",
+            "0.123123123123123135555555555555555555555555555555",
+            "0.0000000123123123123123135555555555555565555551555555555",
+            "1231231231231231231231231255555555555555555555.123",
+            "123123.5555555555555555555555555555555555555555",
+            "123123.55555555555555555555555555555555000000000000",
+            "0.1000000000000000000000000000000000000000001",
+            "1.99999999999999999999999999999999999999999e-5",
+        ];
+        for s in values.iter() {
+            assert_splits(s);
+        }
+    }
+
+    #[test]
+    fn test_parse_valid() {
+        let values = [
+            "0",
+            "-0",
+            "   -0.000   ",
+            "00000.",
+            "+123.4500",
+            "-.5",
+            "1.",
+            "  \t1.5e3\n",
+            "1.5E+3",
+            "1.5e-0003",
+            "0e99999",
+            "-0.0e-99999",
+            "000001234567890.1234567890000",
+            "1e-3",
+        ];
+        for s in values.iter() {
+            assert_splits(s);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let values = [
+            "",
+            "   ",
+            "-",
+            "+",
+            " - 1",
+            ".",
+            "-.",
+            ".e1",
+            "e1",
+            "1e",
+            "1e+",
+            "1.e-",
+            "1.5x",
+            "1 2",
+            "1e5 5",
+            "1e5.5",
+            "1..2",
+            "--1",
+            "NaN",
+            "nan",
+            "-nan",
+            "inf",
+            "1e1000",
+            "1.5e-1000",
+            "1e0001000",
+            "3444605E763.",
+            "1e999x",
+            "-1E-999 .",
+            "9.9e125e",
+            "5E-131 5",
+        ];
+        for s in values.iter() {
+            assert_splits(s);
+        }
+    }
+
+    #[test]
+    fn test_error_is_kept() {
+        let mut parser = DecimalParser::new();
+        parser.push_bytes(b"12").unwrap();
+        assert_eq!(parser.push_bytes(b"3,4"), Err(DecimalParseError::Invalid));
+        assert_eq!(parser.push_bytes(b"5"), Err(DecimalParseError::Invalid));
+        assert_eq!(parser.finish(), Err(DecimalParseError::Invalid));
+
+        let mut parser = DecimalParser::new();
+        assert_eq!(parser.push_bytes(b"1e99"), Ok(()));
+        assert_eq!(parser.push_bytes(b"99"), Err(DecimalParseError::Overflow));
+        assert_eq!(parser.finish(), Err(DecimalParseError::Overflow));
+    }
+
+    #[test]
+    fn test_long_input() {
+        fn parse_repeated(head: &[u8], body: &[u8], count: usize, tail: &[u8]) -> Result<Decimal, DecimalParseError> {
+            let mut parser = DecimalParser::new();
+            parser.push_bytes(head)?;
+            for _ in 0..count {
+                parser.push_bytes(body)?;
+            }
+            parser.push_bytes(tail)?;
+            parser.finish()
+        }
+
+        assert_eq!(
+            parse_repeated(b"0.", b"0000000000", 1000, b"1e999"),
+            Err(DecimalParseError::Underflow)
+        );
+        assert_eq!(
+            parse_repeated(b"1", b"0000000000", 1000, b""),
+            Err(DecimalParseError::Overflow)
+        );
+        assert_eq!(
+            parse_repeated(b"1", b"0000000000", 1000, b"e-9900"),
+            Err(DecimalParseError::Underflow)
+        );
+        assert_eq!(
+            parse_repeated(b"12", b"3333333333", 1000, b".5"),
+            Err(DecimalParseError::Overflow)
+        );
+        assert_eq!(
+            parse_repeated(b"1", b"0000000000", 1000, b" x"),
+            Err(DecimalParseError::Overflow)
+        );
+        assert_eq!(
+            parse_repeated(b"1", b"0000000000", 10, b"e-99").unwrap(),
+            "1e1".parse::<Decimal>().unwrap()
+        );
+
+        let expected = format!("0.{}{}7", "0".repeat(100), "6".repeat(37));
+        assert_eq!(
+            parse_repeated(b"0.", b"0000000000", 10, b"6666666666666666666666666666666666666666").unwrap(),
+            expected.parse::<Decimal>().unwrap()
+        );
+        assert_eq!(
+            parse_repeated(b" 0.", b"0000000000", 1000, b"e123456 ").unwrap(),
+            Decimal::ZERO
+        );
+    }
+}
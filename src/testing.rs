@@ -0,0 +1,374 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structurally interesting decimals for property tests.
+//!
+//! All generated decimals are in the range reachable by parsing and arithmetic, i.e. the exponent of
+//! the most significant digit ranges from `[-130, 125]`, so they round-trip through strings.
+//!
+//! The fixed corpus and the pseudo random sequence of a seed are stable across versions, and pinned by
+//! tests. They are meant for tests only, not for cryptographic or statistical use.
+
+use crate::decimal::{Decimal, MAX_PRECISION};
+use crate::u256::POWERS_10;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// The range of the exponent of the most significant digit.
+const MIN_EXP: i16 = -130;
+const MAX_EXP: i16 = 125;
+
+/// The counts of mantissa digits of the fixed corpus, around the 32, 64 and 96 bits boundaries.
+const DIGITS: [u32; 11] = [1, 2, 9, 10, 18, 19, 20, 28, 29, 37, 38];
+
+/// The scales of the fixed corpus, before trailing zeros are appended.
+const SCALES: [i16; 17] = [-125, -100, -38, -20, -1, 0, 1, 2, 6, 18, 20, 38, 39, 76, 100, 129, 130];
+
+#[inline]
+fn pow10(e: u32) -> u128 {
+    POWERS_10[e as usize].low()
+}
+
+#[inline]
+fn count_digits(val: u128) -> u32 {
+    let mut digits = 1;
+    while digits < MAX_PRECISION && val >= pow10(digits) {
+        digits += 1;
+    }
+    digits
+}
+
+/// Creates a decimal from parts, returning `None` if it's out of the range reachable by parsing.
+#[inline]
+fn checked_parts(int_val: u128, scale: i16, negative: bool) -> Option<Decimal> {
+    let dec = Decimal::from_parts(int_val, scale, negative).ok()?;
+    match dec.logb() {
+        Some(exp) if !(MIN_EXP..=MAX_EXP).contains(&exp) => None,
+        _ => Some(dec),
+    }
+}
+
+/// Returns the fixed corpus of a few thousand decimals at the boundaries.
+///
+/// It covers each combination of:
+///
+/// - the mantissas `10^(d-1)` and `10^d - 1` for digit counts `d` around the 32, 64 and 96 bits
+///   boundaries up to 38, and `5`, `u32::MAX`, `i64::MAX`, `u64::MAX`, `2^96 - 1` and
+///   `12345678901234567890123456789012345678`,
+/// - scales from `-125` to `130`, including the extremes, and the scales giving the extreme exponents,
+/// - no trailing zero, one trailing zero and as many trailing zeros as fit in 38 digits, which give
+///   equal values with different representations,
+/// - both signs,
+///
+/// which is in the range reachable by parsing, including the extremes `±1E-130` and
+/// `±9.9999999999999999999999999999999999999E125`, after zero.
+///
+/// ```
+/// use decimal_rs::testing;
+///
+/// for val in testing::corpus() {
+///     assert_eq!(val.to_string().parse::<decimal_rs::Decimal>().unwrap(), val);
+/// }
+/// ```
+pub fn corpus() -> Vec<Decimal> {
+    let mut mantissas = Vec::new();
+    for &d in DIGITS.iter() {
+        mantissas.push(pow10(d - 1));
+        mantissas.push(pow10(d) - 1);
+    }
+    mantissas.extend_from_slice(&[
+        5,
+        u32::MAX as u128,
+        i64::MAX as u128,
+        u64::MAX as u128,
+        (1 << 96) - 1,
+        12345678901234567890123456789012345678,
+    ]);
+
+    let mut values = vec![Decimal::ZERO];
+    for &m in mantissas.iter() {
+        let digits = count_digits(m);
+        let mut zeros = vec![0, 1, MAX_PRECISION - digits];
+        zeros.retain(|&z| z <= MAX_PRECISION - digits);
+        zeros.dedup();
+
+        // the scales giving the extreme exponents
+        let top = digits as i16 - 1;
+        let mut scales = SCALES.to_vec();
+        scales.push(top - MAX_EXP);
+        scales.push(top - MIN_EXP);
+
+        for &scale in scales.iter() {
+            for &z in zeros.iter() {
+                for &negative in [false, true].iter() {
+                    if let Some(val) = checked_parts(m * pow10(z), scale + z as i16, negative) {
+                        values.push(val);
+                    }
+                }
+            }
+        }
+    }
+
+    values
+}
+
+/// A linear congruential generator, which is also the source of the random inputs of the unit tests.
+pub(crate) struct Lcg(u64);
+
+impl Lcg {
+    #[cfg(test)]
+    #[inline]
+    pub(crate) fn new(seed: u64) -> Lcg {
+        Lcg(seed)
+    }
+
+    #[inline]
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.0 >> 16
+    }
+
+    #[inline]
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    #[inline]
+    fn next_decimal(&mut self) -> Decimal {
+        const EDGE_DIGITS: [u32; 4] = [1, 19, 20, 38];
+        const EDGE_EXPS: [i16; 4] = [MIN_EXP, MIN_EXP + 1, MAX_EXP - 1, MAX_EXP];
+
+        loop {
+            let mut digits = if self.below(4) == 0 {
+                EDGE_DIGITS[self.below(4) as usize]
+            } else {
+                self.below(MAX_PRECISION as u64) as u32 + 1
+            };
+
+            let low = pow10(digits - 1);
+            let high = pow10(digits) - 1;
+            let mut int_val = match self.below(8) {
+                0 => low,
+                1 => high,
+                _ => {
+                    let r = (self.next() as u128) << 48 ^ (self.next() as u128) << 96 ^ self.next() as u128;
+                    low + r % (high - low + 1)
+                }
+            };
+
+            if self.below(4) == 0 {
+                let zeros = self.below((MAX_PRECISION - digits) as u64 + 1) as u32;
+                int_val *= pow10(zeros);
+                digits += zeros;
+            }
+
+            let top = digits as i16 - 1;
+            let scale = match self.below(4) {
+                0 => top - EDGE_EXPS[self.below(4) as usize],
+                1 => top - MAX_EXP + self.below((MAX_EXP - MIN_EXP) as u64 + 1) as i16,
+                _ => self.below(13) as i16 - 2,
+            };
+
+            if let Some(val) = checked_parts(int_val, scale, self.below(2) == 1) {
+                return val;
+            }
+        }
+    }
+
+    #[inline]
+    fn next_bounded(&mut self, max_precision: u32, min_scale: i16, max_scale: i16) -> Decimal {
+        let digits = self.below(max_precision as u64) as u32 + 1;
+        let r = (self.next() as u128) << 48 ^ (self.next() as u128) << 96 ^ self.next() as u128;
+        let int_val = r % pow10(digits);
+        let scale = min_scale + self.below((max_scale - min_scale) as u64 + 1) as i16;
+        Decimal::from_parts(int_val, scale, self.below(2) == 1).unwrap()
+    }
+}
+
+/// Returns `count` pseudo random decimals generated from `seed`, biased toward the edges.
+///
+/// The counts of mantissa digits are often `1`, `19`, `20` or `38`, mantissas are often
+/// `10^(d-1)` or `10^d - 1`, some have trailing zeros, and the exponents are often at the limits of
+/// the range, or small. The same seed always gives the same decimals.
+///
+/// ```
+/// use decimal_rs::testing;
+///
+/// let values = testing::corpus_random(42, 100);
+/// assert_eq!(values.len(), 100);
+/// assert_eq!(values, testing::corpus_random(42, 100));
+/// ```
+pub fn corpus_random(seed: u64, count: usize) -> Vec<Decimal> {
+    let mut rng = Lcg(seed);
+    (0..count).map(|_| rng.next_decimal()).collect()
+}
+
+/// Returns `count` pseudo random decimals generated from `seed`, with at most `max_precision` digits and
+/// a scale in `min_scale..=max_scale`.
+///
+/// Unlike [`corpus_random`], the counts of mantissa digits, the mantissas and the scales are uniformly
+/// distributed, e.g. for columns of money amounts. Zeros have scale 0 whatever the bounds. The same seed
+/// always gives the same decimals.
+///
+/// # Panics
+///
+/// Panics if `max_precision` is not in `[1, 38]`, `min_scale > max_scale`, or the bounds allow decimals
+/// out of the range reachable by parsing.
+///
+/// ```
+/// use decimal_rs::testing;
+///
+/// let amounts = testing::corpus_bounded(42, 100, 12, 2, 2);
+/// assert!(amounts.iter().all(|v| (v.is_zero() || v.scale() == 2) && v.precision() <= 12));
+/// ```
+pub fn corpus_bounded(seed: u64, count: usize, max_precision: u32, min_scale: i16, max_scale: i16) -> Vec<Decimal> {
+    assert!((1..=MAX_PRECISION).contains(&max_precision));
+    assert!(min_scale <= max_scale);
+    assert!(max_precision as i16 - 1 - min_scale <= MAX_EXP && -max_scale >= MIN_EXP);
+
+    let mut rng = Lcg(seed);
+    (0..count)
+        .map(|_| rng.next_bounded(max_precision, min_scale, max_scale))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fingerprint(values: &[Decimal]) -> u64 {
+        let mut h = 0xcbf2_9ce4_8422_2325u64;
+        for val in values.iter() {
+            let (int_val, scale, negative) = val.into_parts();
+            let bytes = int_val
+                .to_le_bytes()
+                .iter()
+                .chain(scale.to_le_bytes().iter())
+                .chain([negative as u8].iter())
+                .copied()
+                .collect::<Vec<u8>>();
+            for b in bytes {
+                h = (h ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        }
+        h
+    }
+
+    fn assert_valid(val: &Decimal) {
+        assert_eq!(
+            val.to_string().parse::<Decimal>().unwrap(),
+            *val,
+            "{:?}",
+            val.into_parts()
+        );
+        if let Some(exp) = val.logb() {
+            assert!((MIN_EXP..=MAX_EXP).contains(&exp));
+        }
+    }
+
+    #[test]
+    fn test_corpus() {
+        let corpus = corpus();
+        corpus.iter().for_each(assert_valid);
+
+        // the extremes
+        for s in [
+            "1E-130",
+            "-1E-130",
+            "9.9999999999999999999999999999999999999E125",
+            "-9.9999999999999999999999999999999999999E125",
+            "99999999999999999999999999999999999999",
+            "0.99999999999999999999999999999999999999",
+            "18446744073709551615",
+        ]
+        .iter()
+        {
+            let val = s.parse::<Decimal>().unwrap();
+            assert!(corpus.iter().any(|v| v.into_parts() == val.into_parts()), "{}", s);
+        }
+
+        // each digit count with and without trailing zeros, both signs
+        for &d in DIGITS.iter() {
+            for &negative in [false, true].iter() {
+                let has = |zeros: bool| {
+                    corpus.iter().any(|v| {
+                        let (int_val, _, n) = v.into_parts();
+                        n == negative && (int_val % 10 == 0) == zeros && v.normalize().precision() as u32 == d
+                    })
+                };
+                assert!(has(false), "{} {}", d, negative);
+                assert!(d == MAX_PRECISION || has(true), "{} {}", d, negative);
+            }
+        }
+
+        // the extreme scales
+        assert!(corpus.iter().any(|v| v.scale() == -125));
+        assert!(corpus.iter().any(|v| v.scale() > 130));
+    }
+
+    #[test]
+    fn test_corpus_golden() {
+        // Property tests depend on the corpus, so any change of it must be deliberate.
+        let corpus = corpus();
+        assert_eq!(corpus.len(), 2711);
+        assert_eq!(fingerprint(&corpus), 8487622558514163012);
+    }
+
+    #[test]
+    fn test_corpus_random() {
+        let values = corpus_random(0x5eed, 10_000);
+        values.iter().for_each(assert_valid);
+        assert_eq!(values, corpus_random(0x5eed, 10_000));
+        assert_ne!(values[..100], corpus_random(0x5eee, 100)[..]);
+        assert_eq!(values[..100], corpus_random(0x5eed, 100)[..]);
+
+        // biased toward the edges
+        let count = |f: &dyn Fn(&Decimal) -> bool| values.iter().filter(|v| f(v)).count();
+        assert!(count(&|v| v.precision() == 38) > 500);
+        assert!(count(&|v| v.logb() == Some(MIN_EXP)) > 200);
+        assert!(count(&|v| v.logb() == Some(MAX_EXP)) > 200);
+        assert!(count(&|v| v.int_val() % 10 == 0) > 1000);
+        assert!(count(&|v| v.is_sign_negative()) > 4000);
+        assert!(count(&|v| (-2..=10).contains(&v.scale())) > 3000);
+    }
+
+    #[test]
+    fn test_corpus_random_golden() {
+        assert_eq!(fingerprint(&corpus_random(42, 1000)), 5710113523336748520);
+    }
+
+    #[test]
+    fn test_corpus_bounded() {
+        let values = corpus_bounded(0x5eed, 10_000, 20, -4, 8);
+        values.iter().for_each(assert_valid);
+        assert_eq!(values, corpus_bounded(0x5eed, 10_000, 20, -4, 8));
+        assert!(values
+            .iter()
+            .all(|v| v.precision() <= 20 && (-4..=8).contains(&v.scale())));
+        assert!(values.iter().any(|v| v.scale() == -4));
+        assert!(values.iter().any(|v| v.scale() == 8));
+        assert!(values.iter().any(|v| v.is_sign_negative()));
+
+        let extremes = corpus_bounded(1, 1000, MAX_PRECISION, MAX_PRECISION as i16 - 1 - MAX_EXP, -MIN_EXP);
+        extremes.iter().for_each(assert_valid);
+    }
+
+    #[test]
+    fn test_corpus_bounded_golden() {
+        assert_eq!(fingerprint(&corpus_bounded(42, 1000, 12, 0, 4)), 12150719773459607032);
+    }
+}
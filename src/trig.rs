@@ -0,0 +1,716 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Trigonometric functions, their inverses and hyperbolic functions.
+
+use crate::decimal::{Decimal, MAX_PRECISION};
+use crate::u256::{POWERS_10, U256};
+
+/// π / 2, rounded to 38 significant digits.
+const FRAC_PI_2: Decimal = unsafe { Decimal::from_parts_unchecked(15707963267948966192313216916397514421, 37, false) };
+
+/// π / 4, rounded to 38 significant digits.
+const FRAC_PI_4: Decimal = unsafe { Decimal::from_parts_unchecked(78539816339744830961566084581987572105, 38, false) };
+
+/// The first 210 fractional digits of 1 / 2π.
+///
+/// The digits from the `e + 1`-th on are enough to reduce `m * 10^e` modulo 2π, since the earlier ones
+/// only add integers to `m * 10^e / 2π`. A decimal has an exponent of at most 126, and 76 digits are
+/// used from there.
+const FRAC_1_2PI_DIGITS: &[u8; 210] = b"\
+    1591549430918953357688837633725143620344596457404564487476673440588967976342265350901138027662530859\
+    5607284272675795803689291184611457865287796741073169983922923996693740907757307774639692530768871739\
+    2896217397";
+
+/// Returns the 38 digits of 1 / 2π from the `from`-th fractional digit as an integer, where the digits
+/// before the first one are zeros.
+#[inline]
+fn frac_1_2pi_digits(from: i32) -> u128 {
+    (from..from + MAX_PRECISION as i32).fold(0u128, |acc, i| {
+        let digit = if i >= 1 {
+            FRAC_1_2PI_DIGITS[i as usize - 1] - b'0'
+        } else {
+            0
+        };
+        acc * 10 + digit as u128
+    })
+}
+
+/// Reduces a positive `x` modulo π / 2, returning the quadrant in `0..4` and the remainder in
+/// `[0, π / 2)`.
+///
+/// `x = m * 10^e`, so the fraction of `x / 2π` is the fraction of `m * 0.c(e+1)c(e+2)...`, where `c(i)`
+/// are the digits of 1 / 2π. It's computed with 38 digits from 76 digits of 1 / 2π, so the remainder
+/// is within about `10^-37` of the exact one, whatever the magnitude of `x` is.
+#[inline]
+fn reduce(x: &Decimal) -> (u8, Decimal) {
+    debug_assert!(x.is_sign_positive());
+
+    let (m, scale, _) = x.into_parts();
+    let e = -(scale as i32);
+    let high = frac_1_2pi_digits(e + 1);
+    let low = frac_1_2pi_digits(e + 1 + MAX_PRECISION as i32);
+
+    // frac(m * 0.HL) * 10^38 = (m * H + m * L / 10^38) mod 10^38
+    let one = POWERS_10[MAX_PRECISION as usize].low();
+    let (_, high_frac) = U256::mul128(m, high).div_rem(one);
+    let (low_int, _) = U256::mul128(m, low).div_rem(one);
+    let frac = (high_frac.low() + low_int.low()) % one;
+
+    // 4 * frac = quadrant + t, where t is in [0, 1)
+    let frac = U256::from(frac) * 4u128;
+    let (quadrant, t) = frac.div_rem(one);
+    let t = unsafe { Decimal::from_parts_unchecked(t.low(), MAX_PRECISION as i16, false) };
+    let r = t.checked_mul(FRAC_PI_2).expect("no overflow");
+    (quadrant.low() as u8, r)
+}
+
+/// Computes `(sin(r), cos(r))` with Taylor series, where `r` is in `[0, π / 4]`.
+#[inline]
+fn sin_cos_series(r: &Decimal) -> Option<(Decimal, Decimal)> {
+    // Taylor series:
+    //   sin(r) = r - r^3 / 3! + r^5 / 5! - r^7 / 7! + ...
+    //   cos(r) = 1 - r^2 / 2! + r^4 / 4! - r^6 / 6! + ...
+    // r <= π / 4, so the terms decrease from the first ones.
+    let r_square = r.checked_mul(r)?;
+
+    let mut sin = *r;
+    let mut cos = Decimal::ONE;
+    let mut term = Decimal::ONE;
+    let mut n = 0u32;
+    loop {
+        // r^n / n! for the even n, and then the odd n
+        n += 1;
+        term = term.checked_mul(r_square)?.checked_div(Decimal::from(n * (n + 1)))?;
+        let odd_term = term.checked_mul(r)?.checked_div(Decimal::from(n + 2))?;
+        n += 1;
+
+        let (last_sin, last_cos) = (sin, cos);
+        if n % 4 == 2 {
+            cos = cos.checked_sub(term)?;
+            sin = sin.checked_sub(odd_term)?;
+        } else {
+            cos = cos.checked_add(term)?;
+            sin = sin.checked_add(odd_term)?;
+        }
+
+        if sin == last_sin && cos == last_cos {
+            break;
+        }
+    }
+
+    Some((sin, cos))
+}
+
+/// The upper bound of the argument of the arctangent Taylor series.
+const ATAN_SERIES_BOUND: Decimal = unsafe { Decimal::from_parts_unchecked(5, 2, false) };
+
+/// Computes `atan(t)` for `t` in `[0, 1]`.
+#[inline]
+fn atan_reduced(t: &Decimal) -> Option<Decimal> {
+    debug_assert!(t.is_sign_positive() && *t <= Decimal::ONE);
+
+    // halve the angle until the Taylor series converges fast:
+    //   atan(t) = 2 * atan(t / (1 + sqrt(1 + t^2)))
+    // it takes at most 4 steps from 1 to 0.05.
+    let mut t = *t;
+    let mut halvings = 0;
+    while t > ATAN_SERIES_BOUND {
+        let sqrt = t.checked_mul(t)?.checked_add(Decimal::ONE)?.sqrt()?;
+        t = t.checked_div(sqrt.checked_add(Decimal::ONE)?)?;
+        halvings += 1;
+    }
+
+    // Taylor series:
+    //   atan(t) = t - t^3 / 3 + t^5 / 5 - t^7 / 7 + ...
+    let t_square = t.checked_mul(t)?;
+    let mut sum = t;
+    let mut power = t;
+    let mut n = 1u32;
+    loop {
+        n += 2;
+        power = power.checked_mul(t_square)?;
+        let term = power.checked_div(Decimal::from(n))?;
+
+        let last = sum;
+        sum = if n % 4 == 3 {
+            sum.checked_sub(term)?
+        } else {
+            sum.checked_add(term)?
+        };
+
+        if sum == last {
+            break;
+        }
+    }
+
+    sum.checked_mul(Decimal::from(1u32 << halvings))
+}
+
+/// Computes `sinh(x)` with Taylor series, where `|x| < 1`.
+#[inline]
+fn sinh_series(x: &Decimal) -> Option<Decimal> {
+    // Taylor series:
+    //   sinh(x) = x + x^3 / 3! + x^5 / 5! + x^7 / 7! + ...
+    // the terms have the same sign, so nothing cancels even if x approaches 0.
+    let x_square = x.checked_mul(x)?;
+
+    let mut sum = *x;
+    let mut term = *x;
+    let mut n = 1u32;
+    loop {
+        term = term
+            .checked_mul(x_square)?
+            .checked_div(Decimal::from((n + 1) * (n + 2)))?;
+        n += 2;
+
+        let last = sum;
+        sum = sum.checked_add(term)?;
+        if sum == last {
+            break;
+        }
+    }
+
+    Some(sum)
+}
+
+impl Decimal {
+    /// Computes `(sin(self), cos(self))`.
+    #[inline]
+    fn sin_cos(&self) -> Option<(Decimal, Decimal)> {
+        if self.is_zero() {
+            return Some((Decimal::ZERO, Decimal::ONE));
+        }
+
+        let x = self.abs();
+        let (quadrant, r) = if x < FRAC_PI_2 { (0, x) } else { reduce(&x) };
+
+        // sin(r) = cos(π / 2 - r), and cos(r) = sin(π / 2 - r)
+        let (sin, cos) = if r <= FRAC_PI_4 {
+            sin_cos_series(&r)?
+        } else {
+            let (sin, cos) = sin_cos_series(&FRAC_PI_2.checked_sub(r)?)?;
+            (cos, sin)
+        };
+
+        let (sin, cos) = match quadrant {
+            0 => (sin, cos),
+            1 => (cos, -sin),
+            2 => (-sin, -cos),
+            _ => (-cos, sin),
+        };
+
+        if self.is_sign_negative() {
+            Some((-sin, cos))
+        } else {
+            Some((sin, cos))
+        }
+    }
+
+    /// Computes the sine of `self` in radians.
+    ///
+    /// `self` is reduced modulo π / 2 with 76 digits of 1 / 2π first, so that a huge argument, e.g.
+    /// `1e10`, is reduced correctly. The result is within `2 * 10^-37` of the exact one.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let x: Decimal = "0.5".parse().unwrap();
+    /// assert_eq!(x.sin().unwrap().round(10), "0.4794255386".parse::<Decimal>().unwrap());
+    /// ```
+    #[inline]
+    pub fn sin(&self) -> Option<Decimal> {
+        self.sin_cos().map(|(sin, _)| sin)
+    }
+
+    /// Computes the cosine of `self` in radians.
+    ///
+    /// `self` is reduced as [`Decimal::sin`] does, and the result is within `2 * 10^-37` of the exact one.
+    #[inline]
+    pub fn cos(&self) -> Option<Decimal> {
+        self.sin_cos().map(|(_, cos)| cos)
+    }
+
+    /// Computes the tangent of `self` in radians,
+    /// returning `None` if the result overflowed near an odd multiple of π / 2.
+    ///
+    /// It's `sin(self) / cos(self)`, so the relative error grows as `self` approaches an odd multiple
+    /// of π / 2.
+    #[inline]
+    pub fn tan(&self) -> Option<Decimal> {
+        let (sin, cos) = self.sin_cos()?;
+        if cos.is_zero() {
+            return None;
+        }
+
+        sin.checked_div(cos)
+    }
+
+    /// Computes the arcsine of `self` in radians, in `[-π / 2, π / 2]`,
+    /// returning `None` if `self` is out of `[-1, 1]`.
+    ///
+    /// It's `atan(self / sqrt((1 - self) * (1 + self)))`, so the result keeps at least 36 significant digits.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let x: Decimal = "0.5".parse().unwrap();
+    /// assert_eq!(x.asin().unwrap().round(10), "0.5235987756".parse::<Decimal>().unwrap());
+    /// assert_eq!(Decimal::from(2).asin(), None);
+    /// ```
+    #[inline]
+    pub fn asin(&self) -> Option<Decimal> {
+        let x = self.abs();
+        if x > Decimal::ONE {
+            return None;
+        }
+
+        let asin = if x == Decimal::ONE {
+            FRAC_PI_2
+        } else {
+            let cos = Decimal::ONE
+                .checked_sub(x)?
+                .checked_mul(Decimal::ONE.checked_add(x)?)?
+                .sqrt()?;
+            x.checked_div(cos)?.atan()?
+        };
+
+        if self.is_sign_negative() {
+            Some(-asin)
+        } else {
+            Some(asin)
+        }
+    }
+
+    /// Computes the arccosine of `self` in radians, in `[0, π]`,
+    /// returning `None` if `self` is out of `[-1, 1]`.
+    ///
+    /// It's `2 * atan(sqrt((1 - self) / (1 + self)))`, so the result keeps at least 36 significant digits,
+    /// even if `self` approaches 1.
+    #[inline]
+    pub fn acos(&self) -> Option<Decimal> {
+        if self.abs() > Decimal::ONE {
+            return None;
+        }
+        if *self == Decimal::MINUS_ONE {
+            return Some(Decimal::PI);
+        }
+
+        let t = Decimal::ONE
+            .checked_sub(self)?
+            .checked_div(Decimal::ONE.checked_add(self)?)?
+            .sqrt()?;
+        t.atan()?.checked_mul(Decimal::TWO)
+    }
+
+    /// Computes the arctangent of `self` in radians, in `(-π / 2, π / 2)`.
+    ///
+    /// `self` is reduced into `[0, 1]` with `atan(x) = π / 2 - atan(1 / x)`, and then into `[0, 0.05]`
+    /// by halving the angle, so the Taylor series converges fast for any decimal. The result keeps at
+    /// least 36 significant digits.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let pi_4 = Decimal::ONE.atan().unwrap();
+    /// assert_eq!(pi_4.round(10), "0.7853981634".parse::<Decimal>().unwrap());
+    /// ```
+    #[inline]
+    pub fn atan(&self) -> Option<Decimal> {
+        if self.is_zero() {
+            return Some(Decimal::ZERO);
+        }
+
+        let x = self.abs();
+        let atan = if x > Decimal::ONE {
+            FRAC_PI_2.checked_sub(atan_reduced(&Decimal::ONE.checked_div(x)?)?)?
+        } else {
+            atan_reduced(&x)?
+        };
+
+        if self.is_sign_negative() {
+            Some(-atan)
+        } else {
+            Some(atan)
+        }
+    }
+
+    /// Computes the four quadrant arctangent of `y` and `x` in radians, in `(-π, π]`.
+    ///
+    /// It's the angle of the point `(x, y)`, and `atan2(0, 0)` is 0.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let one = Decimal::ONE;
+    /// let angle = Decimal::atan2(&one, &-one).unwrap();
+    /// assert_eq!(angle.round(10), "2.3561944902".parse::<Decimal>().unwrap());
+    /// ```
+    #[inline]
+    pub fn atan2(y: &Decimal, x: &Decimal) -> Option<Decimal> {
+        if y.is_zero() {
+            return if x.is_sign_negative() && !x.is_zero() {
+                Some(Decimal::PI)
+            } else {
+                Some(Decimal::ZERO)
+            };
+        }
+
+        // divide the smaller magnitude by the larger one, so the quotient is in [0, 1] and never overflows
+        let (abs_y, abs_x) = (y.abs(), x.abs());
+        let angle = if x.is_zero() {
+            FRAC_PI_2
+        } else if abs_y <= abs_x {
+            atan_reduced(&abs_y.checked_div(abs_x)?)?
+        } else {
+            FRAC_PI_2.checked_sub(atan_reduced(&abs_x.checked_div(abs_y)?)?)?
+        };
+
+        let angle = if x.is_sign_negative() && !x.is_zero() {
+            Decimal::PI.checked_sub(angle)?
+        } else {
+            angle
+        };
+
+        if y.is_sign_negative() {
+            Some(-angle)
+        } else {
+            Some(angle)
+        }
+    }
+
+    /// Computes the hyperbolic sine of `self`,
+    /// returning `None` if `e^|self|` overflowed, i.e. `|self| >= 291`.
+    ///
+    /// It's computed with Taylor series for `|self| < 1`, so the result of a tiny `self` keeps all
+    /// the digits, and with `(e^self - e^-self) / 2` otherwise.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// let x: Decimal = "0.5".parse().unwrap();
+    /// assert_eq!(x.sinh().unwrap().round(10), "0.5210953055".parse::<Decimal>().unwrap());
+    /// ```
+    #[inline]
+    pub fn sinh(&self) -> Option<Decimal> {
+        let x = self.abs();
+        if x < Decimal::ONE {
+            return sinh_series(self);
+        }
+
+        let exp = x.exp()?;
+        let sinh = exp
+            .checked_sub(Decimal::ONE.checked_div(exp)?)?
+            .checked_div(Decimal::TWO)?;
+        if self.is_sign_negative() {
+            Some(-sinh)
+        } else {
+            Some(sinh)
+        }
+    }
+
+    /// Computes the hyperbolic cosine of `self`,
+    /// returning `None` if `e^|self|` overflowed, i.e. `|self| >= 291`.
+    ///
+    /// It's `(e^self + e^-self) / 2`.
+    #[inline]
+    pub fn cosh(&self) -> Option<Decimal> {
+        let exp = self.abs().exp()?;
+        exp.checked_add(Decimal::ONE.checked_div(exp)?)?
+            .checked_div(Decimal::TWO)
+    }
+
+    /// Computes the hyperbolic tangent of `self`.
+    ///
+    /// It's `sinh(self) / cosh(self)` for `|self| < 1`, and `(1 - e^-2|self|) / (1 + e^-2|self|)`
+    /// otherwise, which saturates to `±1` for a large `|self|` instead of overflowing.
+    ///
+    /// ```
+    /// use decimal_rs::Decimal;
+    ///
+    /// assert_eq!(Decimal::from(1000).tanh(), Some(Decimal::ONE));
+    /// ```
+    #[inline]
+    pub fn tanh(&self) -> Option<Decimal> {
+        let x = self.abs();
+        if x < Decimal::ONE {
+            return sinh_series(self)?.checked_div(self.cosh()?);
+        }
+
+        // e^-2|x| underflows to zero if 2|x| overflowed
+        let exp = match x.checked_mul(Decimal::TWO) {
+            Some(y) => (-y).exp()?,
+            None => Decimal::ZERO,
+        };
+        let tanh = Decimal::ONE
+            .checked_sub(exp)?
+            .checked_div(Decimal::ONE.checked_add(exp)?)?;
+        if self.is_sign_negative() {
+            Some(-tanh)
+        } else {
+            Some(tanh)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    /// Asserts that `actual` is within `10^-digits` of `expected`.
+    fn assert_close(actual: Option<Decimal>, expected: &str, digits: i16) {
+        let actual = actual.unwrap();
+        let expected = parse(expected);
+        let tolerance = Decimal::ONE.scaleb(-digits).unwrap();
+        assert!(
+            actual.checked_sub(expected).unwrap().abs() <= tolerance,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_frac_1_2pi_digits() {
+        assert_eq!(frac_1_2pi_digits(1), 15915494309189533576888376337251436203);
+        assert_eq!(frac_1_2pi_digits(-1), 159154943091895335768883763372514362);
+        assert_eq!(frac_1_2pi_digits(-40), 0);
+        assert_eq!(frac_1_2pi_digits(173), 77573077746396925307688717392896217397);
+    }
+
+    #[test]
+    fn test_sin() {
+        assert_eq!(Decimal::ZERO.sin(), Some(Decimal::ZERO));
+        assert_eq!(parse("1E-130").sin(), Some(parse("1E-130")));
+        assert_close(parse("0.5").sin(), "0.47942553860420300027328793521557138808", 37);
+        assert_close(parse("-0.5").sin(), "-0.47942553860420300027328793521557138808", 37);
+        assert_close(parse("1").sin(), "0.84147098480789650665250232163029899962", 37);
+        assert_close(parse("3").sin(), "0.14112000805986722210074480280811027985", 37);
+        assert_close(parse("100").sin(), "-0.50636564110975879365655761045978543207", 37);
+        assert_close(parse("-100").sin(), "0.50636564110975879365655761045978543207", 37);
+        assert_close(parse("1.5707963267948966192313216916397514421").sin(), "1", 37);
+        assert_close(parse("3.1415926535897932384626433832795028842").sin(), "0", 37);
+    }
+
+    #[test]
+    fn test_cos() {
+        assert_eq!(Decimal::ZERO.cos(), Some(Decimal::ONE));
+        assert_close(parse("0.5").cos(), "0.87758256189037271611628158260382965199", 37);
+        assert_close(parse("-0.5").cos(), "0.87758256189037271611628158260382965199", 37);
+        assert_close(parse("1").cos(), "0.54030230586813971740093660744297660373", 37);
+        assert_close(parse("2").cos(), "-0.41614683654714238699756822950076218977", 37);
+        assert_close(parse("100").cos(), "0.86231887228768393410193851395084253551", 37);
+        assert_close(parse("1.5707963267948966192313216916397514421").cos(), "0", 37);
+    }
+
+    #[test]
+    fn test_tan() {
+        assert_eq!(Decimal::ZERO.tan(), Some(Decimal::ZERO));
+        assert_close(parse("0.5").tan(), "0.54630248984379051325517946578028538330", 36);
+        assert_close(parse("-1").tan(), "-1.5574077246549022305069748074583601731", 36);
+        assert_close(parse("0.78539816339744830961566084581987572105").tan(), "1", 36);
+        assert_close(parse("4").tan(), "1.1578212823495775831373424182673239231", 36);
+
+        // near π / 2, the result is huge but in range
+        let tan = parse("1.5707963267948966192313216916397514").tan().unwrap();
+        assert!(tan > parse("1E+33"));
+    }
+
+    /// Asserts that `actual` is within `10^-digits` of `expected` relatively.
+    fn assert_relative(actual: Option<Decimal>, expected: &str, digits: i16) {
+        let expected = parse(expected);
+        let tolerance = expected.abs().scaleb(-digits).unwrap();
+        let actual = actual.unwrap();
+        assert!(
+            actual.checked_sub(expected).unwrap().abs() <= tolerance,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_atan() {
+        assert_eq!(Decimal::ZERO.atan(), Some(Decimal::ZERO));
+        assert_eq!(parse("1E-100").atan(), Some(parse("1E-100")));
+        assert_relative(parse("1").atan(), "0.78539816339744830961566084581987572105", 36);
+        assert_relative(parse("0.5").atan(), "0.46364760900080611621425623146121440203", 36);
+        assert_relative(parse("0.05").atan(), "0.049958395721942761410006287034844881491", 36);
+        assert_relative(parse("-2").atan(), "-1.1071487177940905030170654601785370401", 36);
+        assert_relative(parse("123.456").atan(), "1.5626964520979926418928515781144574462", 36);
+        assert_relative(parse("1e10").atan(), "1.5707963266948966192313216916400847754", 36);
+        assert_relative(parse("1e125").atan(), "1.5707963267948966192313216916397514421", 36);
+    }
+
+    #[test]
+    fn test_asin() {
+        assert_eq!(Decimal::ZERO.asin(), Some(Decimal::ZERO));
+        assert_eq!(parse("1.0000000000000000000000000000000000001").asin(), None);
+        assert_eq!(parse("-2").asin(), None);
+        assert_eq!(parse("1").asin(), Some(FRAC_PI_2));
+        assert_eq!(parse("-1").asin(), Some(-FRAC_PI_2));
+        assert_relative(parse("0.5").asin(), "0.52359877559829887307710723054658381403", 36);
+        assert_relative(parse("0.1").asin(), "0.10016742116155979634552317945269331857", 36);
+        assert_relative(
+            parse("-0.9999999999").asin(),
+            "-1.5707821846592727704297034743429381821",
+            36,
+        );
+        assert_relative(parse("1E-50").asin(), "1E-50", 36);
+    }
+
+    #[test]
+    fn test_acos() {
+        assert_eq!(parse("1.5").acos(), None);
+        assert_eq!(parse("-1.0000000000000000000000000000000000001").acos(), None);
+        assert_eq!(parse("1").acos(), Some(Decimal::ZERO));
+        assert_eq!(parse("-1").acos(), Some(Decimal::PI));
+        assert_relative(parse("0").acos(), "1.5707963267948966192313216916397514421", 36);
+        assert_relative(parse("0.5").acos(), "1.0471975511965977461542144610931676281", 36);
+        assert_relative(parse("-0.5").acos(), "2.0943951023931954923084289221863352561", 36);
+        assert_relative(
+            parse("0.9999999999").acos(),
+            "0.000014142135623848801618217296813259983317",
+            36,
+        );
+        assert_relative(
+            parse("-0.9999999999").acos(),
+            "3.1415785114541693896610251659826896242",
+            36,
+        );
+    }
+
+    #[test]
+    fn test_atan2() {
+        fn assert_atan2(y: &str, x: &str, expected: &str) {
+            assert_relative(Decimal::atan2(&parse(y), &parse(x)), expected, 36);
+        }
+
+        assert_eq!(Decimal::atan2(&Decimal::ZERO, &Decimal::ZERO), Some(Decimal::ZERO));
+        assert_eq!(Decimal::atan2(&Decimal::ZERO, &Decimal::ONE), Some(Decimal::ZERO));
+        assert_eq!(Decimal::atan2(&Decimal::ZERO, &Decimal::MINUS_ONE), Some(Decimal::PI));
+        assert_eq!(Decimal::atan2(&Decimal::ONE, &Decimal::ZERO), Some(FRAC_PI_2));
+        assert_eq!(Decimal::atan2(&Decimal::MINUS_ONE, &Decimal::ZERO), Some(-FRAC_PI_2));
+        assert_atan2("1", "1", "0.78539816339744830961566084581987572105");
+        assert_atan2("1", "-1", "2.3561944901923449288469825374596271631");
+        assert_atan2("-1", "-1", "-2.3561944901923449288469825374596271631");
+        assert_atan2("-1", "1", "-0.78539816339744830961566084581987572105");
+        assert_atan2("3", "-4", "2.4980915447965088516598341545621802462");
+        assert_atan2("1e-100", "-1", "3.1415926535897932384626433832795028842");
+        assert_atan2("1e100", "1e-100", "1.5707963267948966192313216916397514421");
+        assert_atan2("2", "4", "0.46364760900080611621425623146121440203");
+
+        // atan2(y, x) = atan(y / x) for a positive x
+        for val in crate::testing::corpus_random(0x5eed_0755, 200).iter() {
+            let expected = val.atan().unwrap();
+            assert_eq!(Decimal::atan2(val, &Decimal::ONE), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        // atan(tan(x)) = x in (-π / 2, π / 2)
+        for s in ["0.1", "0.5", "1", "1.5", "-0.7"].iter() {
+            let x = parse(s);
+            assert_close(x.tan().unwrap().atan(), s, 35);
+            assert_close(x.sin().unwrap().asin(), s, 35);
+        }
+    }
+
+    #[test]
+    fn test_sinh() {
+        assert_eq!(Decimal::ZERO.sinh(), Some(Decimal::ZERO));
+        assert_eq!(parse("1E-30").sinh(), Some(parse("1E-30")));
+        assert_relative(parse("1E-10").sinh(), "1.0000000000000000000016666666666666667E-10", 37);
+        assert_relative(parse("0.5").sinh(), "0.52109530549374736162242562641149155911", 36);
+        assert_relative(parse("-0.5").sinh(), "-0.52109530549374736162242562641149155911", 36);
+        assert_relative(parse("0.999").sinh(), "1.1736587003524518830611202090955342094", 36);
+        assert_relative(parse("1").sinh(), "1.1752011936438014568823818505956008152", 36);
+        assert_relative(parse("-3.5").sinh(), "-16.542627287634997624956731529012498224", 36);
+        assert_relative(parse("100").sinh(), "1.3440585709080677242063127757900067937E+43", 36);
+        assert_relative(parse("290").sinh(), "4.409301095637482949304741121386673468E+125", 36);
+        assert_eq!(parse("291").sinh(), None);
+        assert_eq!(parse("-291").sinh(), None);
+    }
+
+    #[test]
+    fn test_cosh() {
+        assert_eq!(Decimal::ZERO.cosh(), Some(Decimal::ONE));
+        assert_eq!(parse("1E-30").cosh(), Some(Decimal::ONE));
+        assert_relative(parse("1E-10").cosh(), "1.000000000000000000005", 36);
+        assert_relative(parse("0.5").cosh(), "1.1276259652063807852262251614026720125", 36);
+        assert_relative(parse("-0.5").cosh(), "1.1276259652063807852262251614026720125", 36);
+        assert_relative(parse("2").cosh(), "3.7621956910836314595622134777737461083", 36);
+        assert_relative(parse("-3.5").cosh(), "16.572824671057316125696517821376118069", 36);
+        assert_relative(parse("290").cosh(), "4.409301095637482949304741121386673468E+125", 36);
+        assert_eq!(parse("291").cosh(), None);
+        assert_eq!(parse("-291").cosh(), None);
+    }
+
+    #[test]
+    fn test_tanh() {
+        assert_eq!(Decimal::ZERO.tanh(), Some(Decimal::ZERO));
+        assert_eq!(parse("1E-30").tanh(), Some(parse("1E-30")));
+        assert_relative(parse("1E-10").tanh(), "9.9999999999999999999666666666666666667E-11", 37);
+        assert_relative(parse("0.5").tanh(), "0.46211715726000975850231848364367254873", 36);
+        assert_relative(parse("0.999").tanh(), "0.76117386166056994909582845457843030469", 36);
+        assert_relative(parse("1").tanh(), "0.76159415595576488811945828260479359041", 36);
+        assert_relative(parse("2").tanh(), "0.96402758007581688394641372410092315026", 36);
+        assert_relative(parse("-3.5").tanh(), "-0.99817789761119870928427335245061171735", 36);
+        assert_eq!(parse("100").tanh(), Some(Decimal::ONE));
+        assert_eq!(parse("1000").tanh(), Some(Decimal::ONE));
+        assert_eq!(parse("-1E+100").tanh(), Some(-Decimal::ONE));
+        assert_eq!(
+            parse("9.9999999999999999999999999999999999999E125").tanh(),
+            Some(Decimal::ONE)
+        );
+    }
+
+    #[test]
+    fn test_huge_argument() {
+        // reduced modulo 2π with more digits than a decimal holds
+        assert_close(parse("1e10").sin(), "-0.48750602508751069152779429434810604168", 36);
+        assert_close(parse("1e10").cos(), "0.87311962267685600117619134530769519619", 36);
+        assert_close(parse("1e22").sin(), "-0.85220084976718880177270589375302936826", 36);
+        assert_close(
+            parse("12345678901234567890").cos(),
+            "0.85637752178789054939872748043970213103",
+            36,
+        );
+        assert_close(parse("1e100").sin(), "-0.37237612366127668826208669555316429572", 36);
+        assert_close(
+            parse("9.9999999999999999999999999999999999999E125").sin(),
+            "-0.90892535023428189027547147687432562746",
+            36,
+        );
+    }
+
+    #[test]
+    fn test_identity() {
+        // sin^2 + cos^2 = 1
+        for val in crate::testing::corpus_random(0x5eed_0754, 500).iter() {
+            let (sin, cos) = val.sin_cos().unwrap();
+            let sum = sin
+                .checked_mul(sin)
+                .unwrap()
+                .checked_add(cos.checked_mul(cos).unwrap())
+                .unwrap();
+            assert_close(Some(sum), "1", 36);
+        }
+    }
+}
@@ -14,11 +14,11 @@
 
 //! Unsigned 256-bit integer.
 
-use crate::decimal::MAX_PRECISION;
-use std::cmp::Ordering;
-use std::ops::{Add, Div, Mul, Rem, Shl, Shr, Sub};
+use crate::decimal::{MAX_PRECISION, WIDE_MAX_PRECISION};
+use core::cmp::Ordering;
+use core::ops::{Add, Div, Mul, Rem, Shl, Shr, Sub};
 
-pub static POWERS_10: [U256; (MAX_PRECISION * 2 + 1) as usize] = [
+pub static POWERS_10: [U256; (WIDE_MAX_PRECISION + 1) as usize] = [
     U256::from_u128(1, 0),
     U256::from_u128(10, 0),
     U256::from_u128(100, 0),
@@ -116,7 +116,7 @@ pub static POWERS_10: [U256; (MAX_PRECISION * 2 + 1) as usize] = [
     ),
 ];
 
-pub static ROUNDINGS: [U256; (MAX_PRECISION * 2 + 1) as usize] = [
+pub static ROUNDINGS: [U256; (WIDE_MAX_PRECISION + 1) as usize] = [
     U256::from_u128(0, 0),
     U256::from_u128(5, 0),
     U256::from_u128(50, 0),
@@ -303,6 +303,23 @@ impl U256 {
         U256::from_u128(res.low(), high)
     }
 
+    #[inline(always)]
+    pub fn wrapping_sub(&self, other: U256) -> U256 {
+        self.overflowing_sub(other).0
+    }
+
+    /// Returns the high 256 bits of the 512-bit product `self * other`.
+    #[inline]
+    pub fn mul_high(&self, other: &U256) -> U256 {
+        let low_low = U256::mul128(self.low(), other.low());
+        let low_high = U256::mul128(self.low(), other.high());
+        let high_low = U256::mul128(self.high(), other.low());
+        let high_high = U256::mul128(self.high(), other.high());
+
+        let mid = U256::from(low_low.high()) + low_high.low() + high_low.low();
+        high_high + low_high.high() + high_low.high() + mid.high()
+    }
+
     #[inline]
     pub fn div_rem<T: Into<U256>>(&self, other: T) -> (U256, U256) {
         let other = other.into();
@@ -356,11 +373,71 @@ impl U256 {
         }
     }
 
+    /// Divides by `other` for a result rounded half up once to `MAX_PRECISION` digits, returning the
+    /// quotient and the count of digits appended for rounding.
+    ///
+    /// A quotient of at most `MAX_PRECISION` digits is rounded half up. A longer one is rounded again
+    /// by the caller, so it's followed by a rounding digit instead: `0` if the remainder is zero, `1` if
+    /// it's less than half of `other`, and `5` otherwise. Rounding that half up rounds the exact quotient.
+    #[inline]
+    pub fn div128_round_once(&self, other: u128) -> (U256, i16) {
+        let (quotient, rem) = self.div_rem(other);
+        U256::round_quotient(quotient, rem.low(), other)
+    }
+
+    /// Rounds the `quotient` and remainder `rem` of a division by `other`, see
+    /// [`U256::div128_round_once`].
+    #[inline]
+    pub fn round_quotient(quotient: U256, rem: u128, other: u128) -> (U256, i16) {
+        // rounding:
+        //    remain / other >= 1 / 2
+        // => other - remain <= remain
+        let half_up = rem != 0 && rem >= other - rem;
+        if quotient.is_decimal_overflowed() {
+            let digit = if rem == 0 {
+                0
+            } else if half_up {
+                5
+            } else {
+                1
+            };
+            (quotient * 10 + digit, 1)
+        } else {
+            (quotient + half_up as u128, 0)
+        }
+    }
+
     #[inline]
     pub fn cmp128(&self, other: u128) -> Ordering {
         self.partial_cmp(&other).unwrap()
     }
 
+    /// Compares `left * 10^-left_scale` with `right * 10^-right_scale` exactly, where both are less
+    /// than `10^77`.
+    #[inline]
+    pub fn cmp_scaled(left: U256, left_scale: i32, right: U256, right_scale: i32) -> Ordering {
+        // `val * 10^e`, or `None` if it's at least `10^77`.
+        let scale_up = |val: U256, e: i32| {
+            if val == 0 {
+                Some(val)
+            } else if e as usize >= POWERS_10.len() {
+                None
+            } else {
+                val.checked_mul(POWERS_10[e as usize])
+            }
+        };
+
+        match left_scale.cmp(&right_scale) {
+            Ordering::Equal => left.cmp(&right),
+            Ordering::Less => {
+                scale_up(left, right_scale - left_scale).map_or(Ordering::Greater, |left| left.cmp(&right))
+            }
+            Ordering::Greater => {
+                scale_up(right, left_scale - right_scale).map_or(Ordering::Less, |right| left.cmp(&right))
+            }
+        }
+    }
+
     #[inline(always)]
     pub fn add128(left: u128, right: u128) -> U256 {
         U256::from(left) + U256::from(right)
@@ -840,7 +917,7 @@ mod tests {
     fn generate_powers_10() {
         let mut val = U256::from(1u64);
         let ten = U256::from(10u64);
-        for i in 0..77 {
+        for i in 0..=WIDE_MAX_PRECISION {
             if i != 0 {
                 val = val.wrapping_mul(ten);
             };
@@ -852,7 +929,7 @@ mod tests {
     fn generate_roundings() {
         let mut val = U256::from(5u64);
         let ten = U256::from(10u64);
-        for i in 0..77 {
+        for i in 0..=WIDE_MAX_PRECISION {
             let v = if i == 0 {
                 U256::ZERO
             } else if i == 1 {
@@ -876,6 +953,14 @@ mod tests {
             assert_eq!(div, 10u128);
             prev = val;
         }
+
+        for (&rounding, &power) in ROUNDINGS.iter().zip(POWERS_10.iter()).skip(1) {
+            assert_eq!(rounding * 2u128, power);
+        }
+        assert_eq!(
+            POWERS_10[MAX_PRECISION as usize],
+            crate::convert::MAX_I128_REPR as u128 + 1
+        );
     }
 
     #[test]
@@ -953,6 +1038,20 @@ mod tests {
         assert!(U256::from_u128(2, 1).overflowing_mul(U256::from(u128::MAX)).1);
     }
 
+    #[test]
+    fn test_mul_high() {
+        let max = U256::from_u128(u128::MAX, u128::MAX);
+        assert_eq!(max.mul_high(&max), U256::from_u128(u128::MAX - 1, u128::MAX));
+        assert_eq!(max.mul_high(&U256::ONE), U256::ZERO);
+        assert_eq!(max.mul_high(&U256::from(2u128)), U256::ONE);
+        assert_eq!(U256::from_u128(0, 1).mul_high(&U256::from_u128(0, 1)), U256::ONE);
+        assert_eq!(U256::from(u128::MAX).mul_high(&U256::from(u128::MAX)), U256::ZERO);
+        assert_eq!(
+            U256::from_u128(0, 3).mul_high(&U256::from_u128(5, 7)),
+            U256::from_u128(21, 0)
+        );
+    }
+
     #[test]
     fn test_div_mod() {
         assert_eq!(U256::from_u128(3, 0) / U256::from_u128(2, 0), U256::from(1u128));
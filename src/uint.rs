@@ -0,0 +1,220 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Unsigned integers wider than `U256`, for the intermediates of exact products and wide sums.
+
+#[cfg(any(feature = "precision-76", test))]
+use crate::decimal::WIDE_MAX_PRECISION;
+use crate::u256::{POWERS_10, U256};
+#[cfg(any(feature = "precision-76", test))]
+use core::cmp::Ordering;
+
+/// `10^19`, the largest power of 10 in a `u64`.
+const POW10_19: u64 = 10_000_000_000_000_000_000;
+
+/// An unsigned integer of `64 * LIMBS` bits in little endian limbs, where `LIMBS >= 4`.
+#[derive(Copy, Clone)]
+pub(crate) struct Uint<const LIMBS: usize> {
+    limbs: [u64; LIMBS],
+}
+
+impl<const LIMBS: usize> Uint<LIMBS> {
+    #[inline]
+    pub(crate) fn one() -> Uint<LIMBS> {
+        let mut limbs = [0; LIMBS];
+        limbs[0] = 1;
+        Uint { limbs }
+    }
+
+    #[inline]
+    pub(crate) fn from_u256(val: U256) -> Uint<LIMBS> {
+        let mut limbs = [0; LIMBS];
+        limbs[0] = val.low() as u64;
+        limbs[1] = (val.low() >> 64) as u64;
+        limbs[2] = val.high() as u64;
+        limbs[3] = (val.high() >> 64) as u64;
+        Uint { limbs }
+    }
+
+    /// Returns the value as `U256` if it fits.
+    #[inline]
+    pub(crate) fn to_u256(self) -> Option<U256> {
+        if self.limbs[4..].iter().any(|&limb| limb != 0) {
+            return None;
+        }
+
+        let low = self.limbs[0] as u128 | (self.limbs[1] as u128) << 64;
+        let high = self.limbs[2] as u128 | (self.limbs[3] as u128) << 64;
+        Some(U256::from_u128(low, high))
+    }
+
+    /// Calculates `self * val`, returning `None` if overflow occurred.
+    #[inline]
+    pub(crate) fn checked_mul(&self, val: u128) -> Option<Uint<LIMBS>> {
+        let factor = [val as u64, (val >> 64) as u64];
+        // A zero high limb of `val` mustn't reach beyond the highest limb.
+        let factor = if factor[1] == 0 { &factor[..1] } else { &factor[..] };
+        let mut result = [0u64; LIMBS];
+
+        for (i, &a) in self.limbs.iter().enumerate() {
+            if a == 0 {
+                continue;
+            }
+
+            let mut carry = 0u128;
+            let mut k = i;
+            for &b in factor.iter() {
+                let t = a as u128 * b as u128 + *result.get(k)? as u128 + carry;
+                result[k] = t as u64;
+                carry = t >> 64;
+                k += 1;
+            }
+            while carry != 0 {
+                let t = *result.get(k)? as u128 + carry;
+                result[k] = t as u64;
+                carry = t >> 64;
+                k += 1;
+            }
+        }
+
+        Some(Uint { limbs: result })
+    }
+
+    /// Divides by `val`, returning the remainder.
+    #[inline]
+    pub(crate) fn div_small(&mut self, val: u64) -> u64 {
+        let mut rem = 0u128;
+        for limb in self.limbs.iter_mut().rev() {
+            let n = rem << 64 | *limb as u128;
+            *limb = (n / val as u128) as u64;
+            rem = n % val as u128;
+        }
+        rem as u64
+    }
+
+    /// Divides by `10^e`, dropping the remainder.
+    #[inline]
+    pub(crate) fn div_pow10(&mut self, mut e: u32) {
+        while e >= 19 {
+            self.div_small(POW10_19);
+            e -= 19;
+        }
+        self.div_small(POWERS_10[e as usize].low() as u64);
+    }
+
+    /// Calculates `self + other`, where the sum fits.
+    #[inline]
+    pub(crate) fn add(mut self, other: &Uint<LIMBS>) -> Uint<LIMBS> {
+        let mut carry = false;
+        for (a, &b) in self.limbs.iter_mut().zip(other.limbs.iter()) {
+            let (t, c1) = a.overflowing_add(b);
+            let (t, c2) = t.overflowing_add(carry as u64);
+            *a = t;
+            carry = c1 || c2;
+        }
+        debug_assert!(!carry);
+        self
+    }
+}
+
+/// The operations of the sums of `WideDecimal`.
+#[cfg(any(feature = "precision-76", test))]
+impl<const LIMBS: usize> Uint<LIMBS> {
+    /// Multiplies by `val`, where the product fits.
+    #[inline]
+    pub(crate) fn mul_small(&mut self, val: u64) {
+        let mut carry = 0u128;
+        for limb in self.limbs.iter_mut() {
+            let t = *limb as u128 * val as u128 + carry;
+            *limb = t as u64;
+            carry = t >> 64;
+        }
+        debug_assert_eq!(carry, 0);
+    }
+
+    /// Multiplies by `10^e`, where the product fits.
+    #[inline]
+    pub(crate) fn mul_pow10(&mut self, mut e: u32) {
+        while e >= 19 {
+            self.mul_small(POW10_19);
+            e -= 19;
+        }
+        self.mul_small(POWERS_10[e as usize].low() as u64);
+    }
+
+    /// Calculates `self - other`, where `self >= other`.
+    #[inline]
+    pub(crate) fn sub(mut self, other: &Uint<LIMBS>) -> Uint<LIMBS> {
+        let mut borrow = false;
+        for (a, &b) in self.limbs.iter_mut().zip(other.limbs.iter()) {
+            let (t, b1) = a.overflowing_sub(b);
+            let (t, b2) = t.overflowing_sub(borrow as u64);
+            *a = t;
+            borrow = b1 || b2;
+        }
+        debug_assert!(!borrow);
+        self
+    }
+
+    #[inline]
+    pub(crate) fn cmp(&self, other: &Uint<LIMBS>) -> Ordering {
+        self.limbs.iter().rev().cmp(other.limbs.iter().rev())
+    }
+
+    #[inline]
+    pub(crate) fn count_digits(&self) -> u32 {
+        let mut val = *self;
+        let mut digits = 0;
+        loop {
+            match val.to_u256() {
+                Some(val) if val < POWERS_10[WIDE_MAX_PRECISION as usize] => return digits + val.count_digits(),
+                _ => {
+                    val.div_small(POW10_19);
+                    digits += 19;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uint() {
+        type U512 = Uint<8>;
+
+        let max = POWERS_10[76] - 1u128;
+        let mut val = U512::from_u256(max);
+        assert_eq!(val.count_digits(), 76);
+        val.mul_pow10(77);
+        assert_eq!(val.count_digits(), 153);
+        assert_eq!(val.to_u256(), None);
+        let sum = val.add(&U512::one());
+        assert_eq!(sum.cmp(&val), Ordering::Greater);
+        assert_eq!(sum.sub(&val).to_u256(), Some(U256::ONE));
+        val.div_pow10(77);
+        assert_eq!(val.to_u256(), Some(max));
+        assert_eq!(val.div_small(10), 9);
+
+        let mut product = Uint::<4>::one();
+        for _ in 0..3 {
+            product = product.checked_mul(u64::MAX as u128 + 1).unwrap();
+        }
+        assert_eq!(product.to_u256(), Some(U256::from_u128(0, 1 << 64)));
+        assert!(product.checked_mul(u64::MAX as u128 + 1).is_none());
+        assert_eq!(product.checked_mul(0).unwrap().to_u256(), Some(U256::ZERO));
+    }
+}
@@ -0,0 +1,378 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed wrappers of relative units, i.e. percent, per mille and basis points.
+//!
+//! Each wrapper holds the count of its unit, e.g. `Percent(5)` is `5%`, i.e. the ratio `0.05`.
+//! Values of the same unit can be added and subtracted, while values of different units must be
+//! converted explicitly with `TryFrom`, which shifts the scale exactly:
+//!
+//! ```
+//! use decimal_rs::units::{BasisPoints, Percent};
+//! use decimal_rs::Decimal;
+//! use std::convert::TryFrom;
+//!
+//! let pct = Percent(Decimal::from(1));
+//! let bp = BasisPoints::try_from(pct).unwrap();
+//! assert_eq!(bp, BasisPoints(Decimal::from(100)));
+//! assert_eq!(BasisPoints(Decimal::from(25)) + bp, BasisPoints(Decimal::from(125)));
+//! ```
+//!
+//! Mixing units doesn't compile:
+//!
+//! ```compile_fail
+//! use decimal_rs::units::{BasisPoints, Percent};
+//! use decimal_rs::Decimal;
+//!
+//! let sum = Percent(Decimal::from(1)) + BasisPoints(Decimal::from(100));
+//! ```
+//!
+//! ```compile_fail
+//! use decimal_rs::units::{BasisPoints, Percent};
+//! use decimal_rs::Decimal;
+//!
+//! let bp: BasisPoints = Percent(Decimal::from(1));
+//! ```
+//!
+//! ```compile_fail
+//! use decimal_rs::units::PerMille;
+//! use decimal_rs::Decimal;
+//!
+//! let sum = PerMille(Decimal::from(1)) + Decimal::from(1);
+//! ```
+
+use crate::decimal::Decimal;
+use crate::error::DecimalConvertError;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+/// Computes `val * 10^n` exactly.
+///
+/// Fails with `DecimalConvertError::Overflow` if the result is too large, or with
+/// `DecimalConvertError::Inexact` if it's too small to be represented without rounding.
+#[inline]
+fn shift(val: &Decimal, n: i16) -> Result<Decimal, DecimalConvertError> {
+    val.scaleb(n).ok_or(if n > 0 {
+        DecimalConvertError::Overflow
+    } else {
+        DecimalConvertError::Inexact
+    })
+}
+
+/// Computes `amount * value * 10^-exp` with a single rounding.
+#[inline]
+fn apply(value: &Decimal, amount: &Decimal, exp: i16) -> Option<Decimal> {
+    if value.is_zero() || amount.is_zero() {
+        return Some(Decimal::ZERO);
+    }
+
+    // Shifts the scale of an operand, so the product is rounded only once. A scale beyond the
+    // representation of both operands means both are less than `1E-125`, so the product is zero.
+    let with_scale = |val: &Decimal| {
+        let (int_val, scale, negative) = val.into_parts();
+        Decimal::from_parts(int_val, scale + exp, negative).ok()
+    };
+    if let Some(ratio) = with_scale(value) {
+        amount.checked_mul(ratio)
+    } else if let Some(amount) = with_scale(amount) {
+        amount.checked_mul(value)
+    } else {
+        Some(Decimal::ZERO)
+    }
+}
+
+macro_rules! impl_unit {
+    ($(#[$attr: meta])* $unit: ident, $exp: expr, $symbol: expr) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $unit(pub Decimal);
+
+        impl $unit {
+            /// The exponent of the unit, i.e. one unit is the ratio `10^-EXP`.
+            pub const EXP: i16 = $exp;
+
+            /// Returns the count of units.
+            #[inline]
+            pub const fn value(&self) -> Decimal {
+                self.0
+            }
+
+            /// Applies the ratio to `amount`, i.e. computes `amount * value / 10^EXP` with a single rounding,
+            /// returning `None` if overflow occurred.
+            #[inline]
+            pub fn apply_to(&self, amount: &Decimal) -> Option<Decimal> {
+                apply(&self.0, amount, Self::EXP)
+            }
+
+            /// Adds two values of the unit, returning `None` if overflow occurred.
+            #[inline]
+            pub fn checked_add(&self, other: $unit) -> Option<$unit> {
+                self.0.checked_add(other.0).map($unit)
+            }
+
+            /// Subtracts one value of the unit from another, returning `None` if overflow occurred.
+            #[inline]
+            pub fn checked_sub(&self, other: $unit) -> Option<$unit> {
+                self.0.checked_sub(other.0).map($unit)
+            }
+        }
+
+        impl TryFrom<Decimal> for $unit {
+            type Error = DecimalConvertError;
+
+            /// Converts a ratio, e.g. `0.05`, to the count of units.
+            #[inline]
+            fn try_from(ratio: Decimal) -> Result<Self, Self::Error> {
+                shift(&ratio, $unit::EXP).map($unit)
+            }
+        }
+
+        impl TryFrom<$unit> for Decimal {
+            type Error = DecimalConvertError;
+
+            /// Converts the count of units to a ratio, e.g. `0.05`.
+            #[inline]
+            fn try_from(val: $unit) -> Result<Self, Self::Error> {
+                shift(&val.0, -$unit::EXP)
+            }
+        }
+
+        impl Add for $unit {
+            type Output = $unit;
+
+            #[inline]
+            fn add(self, other: $unit) -> Self::Output {
+                $unit(self.0 + other.0)
+            }
+        }
+
+        impl Sub for $unit {
+            type Output = $unit;
+
+            #[inline]
+            fn sub(self, other: $unit) -> Self::Output {
+                $unit(self.0 - other.0)
+            }
+        }
+
+        impl AddAssign for $unit {
+            #[inline]
+            fn add_assign(&mut self, other: $unit) {
+                self.0 += &other.0;
+            }
+        }
+
+        impl SubAssign for $unit {
+            #[inline]
+            fn sub_assign(&mut self, other: $unit) {
+                self.0 -= &other.0;
+            }
+        }
+
+        impl Neg for $unit {
+            type Output = $unit;
+
+            #[inline]
+            fn neg(self) -> Self::Output {
+                $unit(-self.0)
+            }
+        }
+
+        impl fmt::Display for $unit {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}{}", self.0, $symbol)
+            }
+        }
+    };
+}
+
+macro_rules! impl_unit_convert {
+    ($from: ident => $to: ident) => {
+        impl TryFrom<$from> for $to {
+            type Error = DecimalConvertError;
+
+            #[inline]
+            fn try_from(val: $from) -> Result<Self, Self::Error> {
+                shift(&val.0, $to::EXP - $from::EXP).map($to)
+            }
+        }
+    };
+}
+
+impl_unit!(
+    /// A count of percent, i.e. the ratio `value / 100`.
+    Percent,
+    2,
+    "%"
+);
+
+impl_unit!(
+    /// A count of per mille, i.e. the ratio `value / 1000`.
+    PerMille,
+    3,
+    "‰"
+);
+
+impl_unit!(
+    /// A count of basis points, i.e. the ratio `value / 10000`.
+    BasisPoints,
+    4,
+    "bp"
+);
+
+impl_unit_convert!(Percent => PerMille);
+impl_unit_convert!(Percent => BasisPoints);
+impl_unit_convert!(PerMille => Percent);
+impl_unit_convert!(PerMille => BasisPoints);
+impl_unit_convert!(BasisPoints => Percent);
+impl_unit_convert!(BasisPoints => PerMille);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decimal::RoundingMode;
+
+    fn parse(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_convert() {
+        fn assert_convert(pct: &str, per_mille: &str, bp: &str, ratio: &str) {
+            let pct = Percent(parse(pct));
+            let per_mille = PerMille(parse(per_mille));
+            let bp = BasisPoints(parse(bp));
+            let ratio = parse(ratio);
+
+            assert_eq!(PerMille::try_from(pct), Ok(per_mille));
+            assert_eq!(BasisPoints::try_from(pct), Ok(bp));
+            assert_eq!(Percent::try_from(per_mille), Ok(pct));
+            assert_eq!(BasisPoints::try_from(per_mille), Ok(bp));
+            assert_eq!(Percent::try_from(bp), Ok(pct));
+            assert_eq!(PerMille::try_from(bp), Ok(per_mille));
+
+            assert_eq!(Percent::try_from(ratio), Ok(pct));
+            assert_eq!(PerMille::try_from(ratio), Ok(per_mille));
+            assert_eq!(BasisPoints::try_from(ratio), Ok(bp));
+            assert_eq!(Decimal::try_from(pct), Ok(ratio));
+            assert_eq!(Decimal::try_from(per_mille), Ok(ratio));
+            assert_eq!(Decimal::try_from(bp), Ok(ratio));
+        }
+
+        assert_convert("0", "0", "0", "0");
+        assert_convert("1", "10", "100", "0.01");
+        assert_convert("-12.5", "-125", "-1250", "-0.125");
+        assert_convert("0.0001", "0.001", "0.01", "0.000001");
+        assert_convert(
+            "12345678901234567890.123456789012345678",
+            "123456789012345678901.23456789012345678",
+            "1234567890123456789012.3456789012345678",
+            "123456789012345678.90123456789012345678",
+        );
+
+        // the conversions are exact scale shifts, which keep the digits
+        let bp = BasisPoints::try_from(Percent(parse("1.00"))).unwrap();
+        assert_eq!(bp, BasisPoints(Decimal::from(100)));
+        assert_eq!(bp.value().into_parts(), (1, -2, false));
+        let pct = Percent::try_from(BasisPoints(parse("1"))).unwrap();
+        assert_eq!(pct.value().into_parts(), (1, 2, false));
+
+        // out of range
+        assert_eq!(
+            BasisPoints::try_from(Percent(parse("1E124"))),
+            Err(DecimalConvertError::Overflow)
+        );
+        assert_eq!(Percent::try_from(parse("1E124")), Err(DecimalConvertError::Overflow));
+        assert_eq!(
+            Percent::try_from(BasisPoints(parse("1E-129"))),
+            Err(DecimalConvertError::Inexact)
+        );
+        assert_eq!(
+            Decimal::try_from(PerMille(parse("1E-128"))),
+            Err(DecimalConvertError::Inexact)
+        );
+        assert_eq!(Decimal::try_from(Percent(parse("1E-128"))), Ok(parse("1E-130")));
+    }
+
+    #[test]
+    fn test_arith() {
+        let a = Percent(parse("1.5"));
+        let b = Percent(parse("0.25"));
+        assert_eq!(a + b, Percent(parse("1.75")));
+        assert_eq!(a - b, Percent(parse("1.25")));
+        assert_eq!(-a, Percent(parse("-1.5")));
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+
+        let max = BasisPoints(parse("9.9999999999999999999999999999999999999E125"));
+        assert_eq!(max.checked_add(max), None);
+        assert_eq!((-max).checked_sub(max), None);
+        assert_eq!(max.checked_sub(max), Some(BasisPoints(Decimal::ZERO)));
+
+        assert_eq!(Percent(parse("12.5")).to_string(), "12.5%");
+        assert_eq!(PerMille(parse("-3")).to_string(), "-3‰");
+        assert_eq!(BasisPoints(parse("25")).to_string(), "25bp");
+    }
+
+    #[test]
+    fn test_apply_to() {
+        fn assert_apply(amount: &str, pct: &str, expected: &str) {
+            let amount = parse(amount);
+            let expected = parse(expected);
+            let pct = Percent(parse(pct));
+            assert_eq!(pct.apply_to(&amount), Some(expected));
+
+            let per_mille = PerMille::try_from(pct).unwrap();
+            assert_eq!(per_mille.apply_to(&amount), Some(expected));
+            let bp = BasisPoints::try_from(pct).unwrap();
+            assert_eq!(bp.apply_to(&amount), Some(expected));
+        }
+
+        assert_apply("0", "5", "0");
+        assert_apply("200", "0", "0");
+        assert_apply("200", "5", "10");
+        assert_apply("-200", "5", "-10");
+        assert_apply("1234.56", "0.01", "0.123456");
+        assert_apply("1E-100", "1E-28", "1E-130");
+        assert_apply("1E-100", "4E-29", "0");
+        assert_apply("1E100", "1E25", "1E123");
+        // the exact product has 48 digits, which is rounded once to 38 digits
+        assert_apply(
+            "1234567890.123456789012345678",
+            "9876543.2109876543211",
+            "121932631137021.79522620027422360920590",
+        );
+        assert_eq!(Percent(parse("1E28")).apply_to(&parse("1E100")), None);
+
+        // the operands beyond the representation
+        let tiny = Decimal::from_parts(12345678901234567890123456789012345678, 167, false).unwrap();
+        assert_eq!(
+            BasisPoints(tiny).apply_to(&parse("1E100")),
+            Some(parse("1.2345678901234567890123456789012345678E-34"))
+        );
+        assert_eq!(BasisPoints(tiny).apply_to(&tiny), Some(Decimal::ZERO));
+
+        // rounding the intermediate product to the scale of the amount rounds twice
+        let amount = parse("44.4951");
+        let pct = Percent(parse("1"));
+        let single = pct.apply_to(&amount).unwrap().round_with_mode(2, RoundingMode::HalfUp);
+        assert_eq!(single, parse("0.44"));
+        let naive = (amount * pct.value()).round_with_mode(2, RoundingMode::HalfUp) / Decimal::from(100);
+        assert_eq!(naive.round_with_mode(2, RoundingMode::HalfUp), parse("0.45"));
+    }
+}
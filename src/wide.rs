@@ -0,0 +1,587 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decimal with maximum precision of 76.
+
+use crate::decimal::{Decimal, MAX_PRECISION, MAX_SCALE, MIN_SCALE, WIDE_MAX_PRECISION};
+use crate::error::DecimalParseError;
+use crate::parse::{eat_whitespaces, extract_nan, parse_decimal, Parts, Sign};
+use crate::u256::{POWERS_10, U256};
+use crate::uint::Uint;
+use alloc::string::String;
+use core::cmp::Ordering;
+use core::fmt;
+use core::fmt::Write;
+use core::ops::{Add, Neg, Sub};
+use core::str::FromStr;
+
+/// An unsigned 512-bit integer, which holds two mantissas aligned to the same scale and their sum.
+type U512 = Uint<8>;
+
+/// High precision decimal with maximum precision of 76, i.e. twice the precision of [`Decimal`], in
+/// the same range of magnitudes.
+///
+/// It supports parsing, formatting, comparison, addition and subtraction, which round half up to 76
+/// significant digits as `Decimal` does to 38.
+///
+/// ```
+/// use decimal_rs::WideDecimal;
+///
+/// let a: WideDecimal = "1234567890123456789012345678901234567.89".parse().unwrap();
+/// let b: WideDecimal = "0.000000000000000000000000000000000000001".parse().unwrap();
+/// let sum = a + b;
+/// assert_eq!(sum.to_string(), "1234567890123456789012345678901234567.890000000000000000000000000000000000001");
+/// assert!(sum > a);
+/// ```
+#[derive(Copy, Clone)]
+pub struct WideDecimal {
+    int_val: U256,
+    scale: i16,
+    negative: bool,
+}
+
+impl WideDecimal {
+    /// Maximum precision of `WideDecimal`.
+    pub const MAX_PRECISION: u32 = WIDE_MAX_PRECISION;
+
+    /// Zero value, i.e. `0`.
+    pub const ZERO: WideDecimal = WideDecimal {
+        int_val: U256::ZERO,
+        scale: 0,
+        negative: false,
+    };
+
+    /// Rounds `int_val * 10^-scale` half up to at most 76 digits, returning `None` if overflow occurred.
+    #[inline]
+    fn adjust_scale(int_val: U512, scale: i32, negative: bool) -> Option<WideDecimal> {
+        let digits = int_val.count_digits();
+        let (int_val, scale) = if digits > WIDE_MAX_PRECISION {
+            let shift = digits - WIDE_MAX_PRECISION;
+            let mut rounding = U512::from_u256(U256::from(5u128));
+            rounding.mul_pow10(shift - 1);
+            let mut rounded = int_val.add(&rounding);
+            rounded.div_pow10(shift);
+            let rounded = rounded.to_u256().expect("77 digits at most");
+
+            // rounding may carry into one more digit, e.g. 99..9.5 -> 100..0
+            if rounded == POWERS_10[WIDE_MAX_PRECISION as usize] {
+                (POWERS_10[WIDE_MAX_PRECISION as usize - 1], scale - shift as i32 - 1)
+            } else {
+                (rounded, scale - shift as i32)
+            }
+        } else {
+            (int_val.to_u256().expect("76 digits at most"), scale)
+        };
+
+        if int_val == 0 {
+            return Some(WideDecimal::ZERO);
+        }
+
+        let s = scale - int_val.count_digits() as i32;
+        if s >= MAX_SCALE as i32 {
+            return Some(WideDecimal::ZERO);
+        }
+        if s < MIN_SCALE as i32 {
+            // overflow
+            return None;
+        }
+
+        Some(WideDecimal {
+            int_val,
+            scale: scale as i16,
+            negative,
+        })
+    }
+
+    /// Returns the scale, i.e. the count of decimal digits in the fractional part.
+    /// A negative scale means a multiplication by `10^-scale`.
+    #[inline]
+    pub const fn scale(&self) -> i16 {
+        self.scale
+    }
+
+    /// Returns the count of significant digits of the mantissa.
+    #[inline]
+    pub fn precision(&self) -> u8 {
+        self.int_val.count_digits() as u8
+    }
+
+    /// Checks if `self` is zero.
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.int_val == 0
+    }
+
+    /// Checks if `self` is negative.
+    #[inline]
+    pub const fn is_sign_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Checks if `self` is positive or zero.
+    #[inline]
+    pub const fn is_sign_positive(&self) -> bool {
+        !self.negative
+    }
+
+    /// Rounds `self` half up to 38 significant digits, returning `None` if overflow occurred.
+    ///
+    /// ```
+    /// use decimal_rs::{Decimal, WideDecimal};
+    ///
+    /// let n: WideDecimal = "0.123456789012345678901234567890123456785".parse().unwrap();
+    /// let expected: Decimal = "0.12345678901234567890123456789012345679".parse().unwrap();
+    /// assert_eq!(n.to_decimal(), Some(expected));
+    /// ```
+    #[inline]
+    pub fn to_decimal(&self) -> Option<Decimal> {
+        Decimal::adjust_scale(self.int_val, self.scale, self.negative)
+    }
+
+    /// Add two decimals, returning `None` if overflow occurred.
+    ///
+    /// The result scale is the larger of the two operand scales. If the exact sum needs more than
+    /// 76 digits at that scale, it is rounded half away from zero and the scale is reduced by the
+    /// number of dropped digits. A zero result always has scale 0.
+    #[inline]
+    pub fn checked_add(&self, other: &WideDecimal) -> Option<WideDecimal> {
+        if self.is_zero() {
+            return Some(*other);
+        }
+        if other.is_zero() {
+            return Some(*self);
+        }
+
+        let (coarse, fine) = if self.scale <= other.scale {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        let mut coarse_int = U512::from_u256(coarse.int_val);
+        let mut e = (fine.scale as i32 - coarse.scale as i32) as u32;
+        if e > WIDE_MAX_PRECISION + 1 {
+            let widen = WIDE_MAX_PRECISION - coarse.precision() as u32;
+            e -= widen;
+            if e > WIDE_MAX_PRECISION + 1 {
+                // `fine` is less than a hundredth of the last unit of `coarse` widened to 76 digits.
+                return Some(*coarse);
+            }
+            coarse_int.mul_pow10(widen);
+        }
+        coarse_int.mul_pow10(e);
+
+        let fine_int = U512::from_u256(fine.int_val);
+        let (int_val, negative) = if coarse.negative == fine.negative {
+            (coarse_int.add(&fine_int), coarse.negative)
+        } else if coarse_int.cmp(&fine_int) == Ordering::Less {
+            (fine_int.sub(&coarse_int), fine.negative)
+        } else {
+            (coarse_int.sub(&fine_int), coarse.negative)
+        };
+
+        WideDecimal::adjust_scale(int_val, fine.scale as i32, negative)
+    }
+
+    /// Subtract one decimal from another, returning `None` if overflow occurred.
+    ///
+    /// The result is rounded as [`WideDecimal::checked_add`] does.
+    #[inline]
+    pub fn checked_sub(&self, other: &WideDecimal) -> Option<WideDecimal> {
+        self.checked_add(&-*other)
+    }
+}
+
+impl From<Decimal> for WideDecimal {
+    #[inline]
+    fn from(val: Decimal) -> Self {
+        let (int_val, scale, negative) = val.into_parts();
+        WideDecimal {
+            int_val: U256::from(int_val),
+            scale,
+            negative,
+        }
+    }
+}
+
+/// Parses a string slice and creates a wide decimal, as [`Decimal`] is parsed with 76 significant
+/// digits kept.
+#[inline]
+fn parse_str(s: &str) -> Result<WideDecimal, DecimalParseError> {
+    let s = eat_whitespaces(s.as_bytes());
+    if s.is_empty() {
+        return Err(DecimalParseError::Empty);
+    }
+
+    let (is_nan, s) = extract_nan(s);
+    if is_nan {
+        return Err(DecimalParseError::Invalid);
+    }
+
+    let (
+        Parts {
+            sign,
+            integral,
+            fractional,
+            exp,
+        },
+        s,
+    ) = parse_decimal(s)?;
+    if s.iter().any(|n| !n.is_ascii_whitespace()) {
+        return Err(DecimalParseError::Invalid);
+    }
+
+    // The digits from the first non-zero one, the first 76 of which are kept.
+    let mut digits = integral
+        .iter()
+        .chain(fractional.iter())
+        .skip_while(|&&b| b == b'0')
+        .peekable();
+    if digits.peek().is_none() {
+        return Ok(WideDecimal::ZERO);
+    }
+
+    let mut int_val = U256::ZERO;
+    let mut dropped = 0i32;
+    let mut carry = false;
+    for (i, &b) in digits.enumerate() {
+        if i < WIDE_MAX_PRECISION as usize {
+            int_val = int_val * 10 + (b - b'0') as u128;
+        } else {
+            if i == WIDE_MAX_PRECISION as usize {
+                carry = b > b'4';
+            }
+            dropped += 1;
+        }
+    }
+
+    let mut scale = fractional.len() as i32 - exp as i32 - dropped;
+    int_val = int_val + carry as u128;
+    if int_val == POWERS_10[WIDE_MAX_PRECISION as usize] {
+        int_val = POWERS_10[WIDE_MAX_PRECISION as usize - 1];
+        scale -= 1;
+    }
+
+    let s = scale - int_val.count_digits() as i32;
+    if s >= MAX_SCALE as i32 {
+        return Err(DecimalParseError::Underflow);
+    }
+    if s < MIN_SCALE as i32 {
+        return Err(DecimalParseError::Overflow);
+    }
+
+    Ok(WideDecimal {
+        int_val,
+        scale: scale as i16,
+        negative: sign == Sign::Negative,
+    })
+}
+
+impl FromStr for WideDecimal {
+    type Err = DecimalParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_str(s)
+    }
+}
+
+impl fmt::Display for WideDecimal {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = String::with_capacity(WIDE_MAX_PRECISION as usize + 2);
+        let (high, low) = self.int_val.div_rem(POWERS_10[MAX_PRECISION as usize]);
+        if high == 0 {
+            write!(buf, "{}", low.low())?;
+        } else {
+            write!(buf, "{}{:038}", high.low(), low.low())?;
+        }
+
+        // The same layout as `Decimal`, which drops the trailing zeros of a fractional part only after
+        // an integral part.
+        let len = buf.len();
+        if self.scale <= 0 {
+            buf.push_str(&"0".repeat(-self.scale as usize));
+        } else if len <= self.scale as usize {
+            let zeros = "0".repeat(self.scale as usize - len);
+            buf.insert_str(0, &zeros);
+            buf.insert_str(0, "0.");
+        } else {
+            let int_len = len - self.scale as usize;
+            let frac_len = buf[int_len..].trim_end_matches('0').len();
+            buf.truncate(int_len + frac_len);
+            if frac_len != 0 {
+                buf.insert(int_len, '.');
+            }
+        }
+
+        f.pad_integral(!self.negative || self.is_zero(), "", &buf)
+    }
+}
+
+impl fmt::Debug for WideDecimal {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WideDecimal(\"{}\", scale={})", self, self.scale)
+    }
+}
+
+impl PartialEq for WideDecimal {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for WideDecimal {}
+
+impl PartialOrd for WideDecimal {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for WideDecimal {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => U256::cmp_scaled(self.int_val, self.scale as i32, other.int_val, other.scale as i32),
+            (true, true) => U256::cmp_scaled(other.int_val, other.scale as i32, self.int_val, self.scale as i32),
+        }
+    }
+}
+
+impl Neg for WideDecimal {
+    type Output = WideDecimal;
+
+    #[inline]
+    fn neg(mut self) -> Self::Output {
+        if !self.is_zero() {
+            self.negative = !self.negative;
+        }
+        self
+    }
+}
+
+impl Add for WideDecimal {
+    type Output = WideDecimal;
+
+    #[inline]
+    fn add(self, other: WideDecimal) -> Self::Output {
+        match self.checked_add(&other) {
+            Some(sum) => sum,
+            None => panic!("Addition overflowed"),
+        }
+    }
+}
+
+impl Sub for WideDecimal {
+    type Output = WideDecimal;
+
+    #[inline]
+    fn sub(self, other: WideDecimal) -> Self::Output {
+        match self.checked_sub(&other) {
+            Some(diff) => diff,
+            None => panic!("Subtraction overflowed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{corpus, corpus_random};
+
+    fn wide(s: &str) -> WideDecimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_format() {
+        fn assert_parse(s: &str, expected: &str) {
+            let n = wide(s);
+            assert_eq!(n.to_string(), expected);
+            assert_eq!(wide(expected), n);
+        }
+
+        assert_parse("0", "0");
+        assert_parse("-0.000", "0");
+        assert_parse("0E9999", "0");
+        assert_parse("1", "1");
+        assert_parse("  -1.50  ", "-1.5");
+        assert_parse(
+            "1E75",
+            "1000000000000000000000000000000000000000000000000000000000000000000000000000",
+        );
+        assert_parse("1E-130", &format!("0.{}1", "0".repeat(129)));
+        assert_parse("9.99E125", &format!("999{}", "0".repeat(123)));
+        assert_parse(
+            "1234567890123456789012345678901234567890123456789012345678901234567890123456",
+            "1234567890123456789012345678901234567890123456789012345678901234567890123456",
+        );
+        assert_parse(
+            "-0.1234567890123456789012345678901234567890123456789012345678901234567890123456",
+            "-0.1234567890123456789012345678901234567890123456789012345678901234567890123456",
+        );
+        assert_parse(
+            "12345678901234567890123456789012345678.90123456789012345678901234567890123456",
+            "12345678901234567890123456789012345678.90123456789012345678901234567890123456",
+        );
+
+        // the 77th significant digit rounds half up
+        assert_parse(
+            "1234567890123456789012345678901234567890123456789012345678901234567890123456.4999",
+            "1234567890123456789012345678901234567890123456789012345678901234567890123456",
+        );
+        assert_parse(
+            "1234567890123456789012345678901234567890123456789012345678901234567890123456.5",
+            "1234567890123456789012345678901234567890123456789012345678901234567890123457",
+        );
+        assert_parse(
+            "0.99999999999999999999999999999999999999999999999999999999999999999999999999995",
+            "1",
+        );
+        assert_parse(
+            "-99999999999999999999999999999999999999999999999999999999999999999999999999995",
+            "-100000000000000000000000000000000000000000000000000000000000000000000000000000",
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        fn assert_error(s: &str, err: DecimalParseError) {
+            assert_eq!(s.parse::<WideDecimal>().unwrap_err(), err);
+        }
+
+        assert_error("", DecimalParseError::Empty);
+        assert_error("   ", DecimalParseError::Empty);
+        assert_error("NaN", DecimalParseError::Invalid);
+        assert_error("1.2.3", DecimalParseError::Invalid);
+        assert_error("12a", DecimalParseError::Invalid);
+        assert_error("1E126", DecimalParseError::Overflow);
+        assert_error("1E-131", DecimalParseError::Underflow);
+        assert_error(
+            "9.9999999999999999999999999999999999999999999999999999999999999999999999999999E125",
+            DecimalParseError::Overflow,
+        );
+    }
+
+    #[test]
+    fn test_decimal_conversion() {
+        for &d in corpus().iter() {
+            let n = WideDecimal::from(d);
+            assert_eq!(n.to_string(), d.to_string());
+            assert_eq!(n.to_decimal(), Some(d));
+            assert_eq!(wide(&d.to_string()), n);
+        }
+
+        let n = wide("9.99999999999999999999999999999999999999E125");
+        assert_eq!(n.to_decimal(), None);
+    }
+
+    #[test]
+    fn test_cmp() {
+        let decimals = corpus();
+        let mut others: Vec<Decimal> = decimals.iter().step_by(17).cloned().collect();
+        others.extend(corpus_random(0x5eed_0076, 50));
+        for &a in decimals.iter() {
+            for &b in others.iter() {
+                assert_eq!(
+                    WideDecimal::from(a).cmp(&WideDecimal::from(b)),
+                    a.cmp(&b),
+                    "{} cmp {}",
+                    a,
+                    b
+                );
+            }
+        }
+
+        let a = wide("1.000000000000000000000000000000000000000000000000000000000000000000000000001");
+        let b = wide("1.000000000000000000000000000000000000000000000000000000000000000000000000002");
+        assert!(a < b);
+        assert!(-a > -b);
+        assert_eq!(wide("1.0"), wide("1.000"));
+        assert_eq!(wide("-0"), wide("0"));
+    }
+
+    #[test]
+    fn test_add_sub() {
+        fn assert_add(a: &str, b: &str, sum: &str) {
+            assert_eq!((wide(a) + wide(b)).to_string(), sum);
+            assert_eq!((wide(sum) - wide(b)), wide(a));
+        }
+
+        assert_add("1", "2", "3");
+        assert_add("1.5", "-2.25", "-0.75");
+        assert_add("1E75", "-1", &"9".repeat(75));
+        assert_add(
+            "12345678901234567890123456789012345678",
+            "0.90123456789012345678901234567890123456",
+            "12345678901234567890123456789012345678.90123456789012345678901234567890123456",
+        );
+        assert_add(
+            "0.1",
+            "1E-75",
+            "0.100000000000000000000000000000000000000000000000000000000000000000000000001",
+        );
+
+        // rounds half up to 76 digits
+        assert_eq!(
+            (wide(&"9".repeat(76)) + wide("0.5")).to_string(),
+            format!("1{}", "0".repeat(76))
+        );
+        assert_eq!((wide("1") + wide("4.9E-76")).to_string(), "1");
+        assert_eq!(
+            (wide("1") + wide("5E-76")).to_string(),
+            "1.000000000000000000000000000000000000000000000000000000000000000000000000001"
+        );
+        assert_eq!((wide("1") + wide("1E-130")).to_string(), "1");
+        assert_eq!((wide("-1E-130") + wide("1E-130")).to_string(), "0");
+
+        let max = wide(&format!("{}E50", "9".repeat(76)));
+        assert_eq!(max.checked_add(&wide("1E50")), None);
+        assert_eq!(max.checked_add(&wide("4E49")), Some(max));
+        assert_eq!((-max).checked_sub(&wide("1E50")), None);
+    }
+
+    #[test]
+    fn test_add_sub_consistency() {
+        fn assert_consistent(wide: Option<WideDecimal>, expected: Option<Decimal>) {
+            match (wide, expected) {
+                // `Decimal` keeps an exact result below `1E-130`, which is zero here.
+                (Some(wide), Some(d)) if d.scale() as i32 - d.precision() as i32 >= MAX_SCALE as i32 => {
+                    assert!(wide.is_zero(), "{:?} vs {:?}", wide, d)
+                }
+                (Some(wide), expected) if wide.precision() < 76 => assert_eq!(wide.to_decimal(), expected),
+                _ => {}
+            }
+        }
+
+        // The sum of two decimals has fewer than 76 digits unless their scales are far apart, in which
+        // case both are rounded to 38 digits and agree.
+        let decimals = corpus();
+        let mut others: Vec<Decimal> = decimals.iter().step_by(17).cloned().collect();
+        others.extend(corpus_random(0x5eed_0077, 50));
+        for &a in decimals.iter() {
+            for &b in others.iter() {
+                let (wa, wb) = (WideDecimal::from(a), WideDecimal::from(b));
+                assert_consistent(wa.checked_add(&wb), a.checked_add(b));
+                assert_consistent(wa.checked_sub(&wb), a.checked_sub(b));
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! IEEE 754 test vectors.
+
+use decimal_rs::Decimal;
+
+const VECTORS: &str = include_str!("data/ieee754.decTest");
+
+fn parse(s: &str) -> Decimal {
+    s.parse().unwrap()
+}
+
+fn is_signal(result: &str) -> bool {
+    matches!(result, "Overflow" | "Underflow" | "Division_by_zero")
+}
+
+#[test]
+fn test_ieee754_vectors() {
+    let mut count = 0;
+
+    for line in VECTORS.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("--") {
+            continue;
+        }
+
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let arrow = tokens.iter().position(|t| *t == "->").unwrap();
+        let (id, op, operands, result) = (tokens[0], tokens[1], &tokens[2..arrow], tokens[arrow + 1]);
+
+        match op {
+            "scaleb" => {
+                let x = parse(operands[0]);
+                let n = operands[1].parse::<i16>().unwrap();
+                let actual = x.scaleb(n);
+                if is_signal(result) {
+                    assert_eq!(actual, None, "{}", id);
+                } else {
+                    let actual = actual.unwrap();
+                    assert_eq!(actual, parse(result), "{}", id);
+                    // scaleB is exact, the significant digits are kept unchanged
+                    assert_eq!(actual.precision(), x.precision(), "{}", id);
+                }
+            }
+            "logb" => {
+                let actual = parse(operands[0]).logb();
+                if is_signal(result) {
+                    assert_eq!(actual, None, "{}", id);
+                } else {
+                    assert_eq!(actual, Some(result.parse::<i16>().unwrap()), "{}", id);
+                }
+            }
+            _ => panic!("{}: unknown operation {}", id, op),
+        }
+
+        count += 1;
+    }
+
+    assert!(count > 0);
+}
@@ -0,0 +1,103 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The API available without `std`, run with `cargo test --no-default-features --test no_std`.
+//!
+//! Only `core` is used here: formatting goes to a fixed buffer and encoding to a byte slice.
+//!
+//! The features that don't need `std` must build without it as well:
+//!
+//! ```text
+//! cargo build --no-default-features --features testing
+//! cargo build --no-default-features --features pg-compat
+//! cargo build --no-default-features --features precision-76
+//! ```
+
+use core::fmt::{self, Write};
+use decimal_rs::{Decimal, MAX_BINARY_SIZE};
+
+/// A `fmt::Write` into a fixed buffer.
+struct FixedBuf {
+    buf: [u8; 256],
+    len: usize,
+}
+
+impl FixedBuf {
+    fn new() -> FixedBuf {
+        FixedBuf { buf: [0; 256], len: 0 }
+    }
+
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+}
+
+impl Write for FixedBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+fn parse(s: &str) -> Decimal {
+    s.parse().unwrap()
+}
+
+fn assert_display(n: &Decimal, expected: &str) {
+    let mut buf = FixedBuf::new();
+    write!(buf, "{}", n).unwrap();
+    assert_eq!(buf.as_str(), expected);
+}
+
+#[test]
+fn test_parse_add_format() {
+    let sum = parse("123456789.987654321") + parse("987654321.123456789");
+    assert_display(&sum, "1111111111.11111111");
+    assert_display(&(parse("1") / parse("3")), "0.33333333333333333333333333333333333333");
+    assert_display(&(parse("-1.5") * parse("2")), "-3");
+
+    let mut buf = FixedBuf::new();
+    write!(buf, "{:.3}", parse("2.5")).unwrap();
+    assert_eq!(buf.as_str(), "2.500");
+}
+
+#[test]
+fn test_math() {
+    assert_display(&parse("2").sqrt().unwrap(), "1.4142135623730950488016887242096980786");
+    assert_eq!(parse("1e20").nth_root(5), Some(parse("10000")));
+    assert_eq!(parse("-8").nth_root(3), Some(parse("-2")));
+    assert_eq!(
+        parse("10").nth_root(3),
+        Some(parse("2.1544346900318837217592935665193504953"))
+    );
+}
+
+#[test]
+fn test_encode_decode() {
+    for s in ["0", "-1.5", "99999999999999999999999999999999999999", "1e-130"].iter() {
+        let n = parse(s);
+        let mut buf = [0; MAX_BINARY_SIZE];
+
+        let size = n.encode_to_slice(&mut buf).unwrap();
+        assert_eq!(Decimal::try_decode(&buf[..size]), Ok(n));
+
+        let size = n.compact_encode_to_slice(&mut buf).unwrap();
+        assert_eq!(Decimal::try_decode(&buf[..size]), Ok(n));
+    }
+}
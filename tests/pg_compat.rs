@@ -0,0 +1,112 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conformance with the numeric text input and output of PostgreSQL.
+
+#![cfg(feature = "pg-compat")]
+
+use decimal_rs::pg_compat::{apply_typmod, format_pg, parse_pg, PgNumeric, PgNumericError};
+use decimal_rs::Decimal;
+
+const VECTORS: &str = include_str!("data/pg_numeric.txt");
+
+fn parse_typmod(s: &str) -> Option<(u8, i16)> {
+    if s == "-" {
+        return None;
+    }
+
+    let (precision, scale) = s.split_at(s.find(',').unwrap());
+    Some((precision.parse().unwrap(), scale[1..].parse().unwrap()))
+}
+
+/// Checks if PostgreSQL accepted a value, which is out of the range of decimal.
+fn is_out_of_range(output: &str) -> bool {
+    output.starts_with("ACCEPTED") || output.ends_with("Infinity") || output.parse::<Decimal>().is_err()
+}
+
+fn check_sort(line: &str) {
+    let tokens: Vec<&str> = line.split_whitespace().skip(1).collect();
+    let arrow = tokens.iter().position(|t| *t == "->").unwrap();
+
+    let mut values: Vec<PgNumeric> = tokens[..arrow].iter().map(|s| parse_pg(s).unwrap()).collect();
+    values.sort();
+    let sorted: Vec<String> = values.iter().map(PgNumeric::to_string).collect();
+    assert_eq!(sorted, &tokens[arrow + 1..], "{}", line);
+}
+
+#[test]
+fn test_pg_numeric_vectors() {
+    let mut count = 0;
+
+    for line in VECTORS.lines() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with("--") {
+            continue;
+        }
+
+        if line.starts_with("sort ") {
+            check_sort(line);
+            count += 1;
+            continue;
+        }
+
+        let end = line[1..].find('\'').unwrap() + 1;
+        let input = line[1..end].replace("\\n", "\n").replace("\\r", "\r");
+        let (typmod, result) = line[end + 1..].split_at(line[end + 1..].find("->").unwrap());
+        let typmod = parse_typmod(typmod.trim());
+        let result = result[2..].trim();
+
+        let actual = parse_pg(&input).and_then(|val| match typmod {
+            Some(typmod) => apply_typmod(&val, typmod),
+            None => Ok(val),
+        });
+
+        if let Some(error) = result.strip_prefix("ERROR ") {
+            let (message, detail) = match error.find(" | ") {
+                Some(pos) => (&error[..pos], Some(&error[pos + 3..])),
+                None => (error, None),
+            };
+            match actual {
+                // the infinities don't fit any type modifier
+                Err(PgNumericError::OutOfRange(_)) => {
+                    assert!(detail.unwrap().ends_with("cannot hold an infinite value."), "{}", line)
+                }
+                Err(e) => {
+                    assert_eq!(e.to_string(), message, "{}", line);
+                    assert_eq!(e.detail().as_deref(), detail, "{}", line);
+                }
+                Ok(val) => panic!("{}: got {}", line, val),
+            }
+        } else {
+            match actual {
+                Err(PgNumericError::OutOfRange(_)) => assert!(is_out_of_range(result), "{}", line),
+                Err(e) => panic!("{}: got {}", line, e),
+                Ok(val) => {
+                    let output = format_pg(&val, typmod);
+                    if typmod.is_none() && val == PgNumeric::Value(Decimal::ZERO) {
+                        // a zero has no display scale
+                        assert!(result.bytes().all(|b| b == b'0' || b == b'.'), "{}", line);
+                        assert_eq!(output, "0", "{}", line);
+                    } else {
+                        assert_eq!(output, result, "{}", line);
+                    }
+                }
+            }
+        }
+
+        count += 1;
+    }
+
+    assert!(count > 0);
+}
@@ -0,0 +1,68 @@
+// Copyright 2021 CoD Technologies Corp.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conformance with the numeric binary format of PostgreSQL.
+
+#![cfg(feature = "postgres")]
+
+use bytes::BytesMut;
+use decimal_rs::Decimal;
+use postgres_types::{FromSql, ToSql, Type};
+
+const VECTORS: &str = include_str!("data/pg_numeric_send.txt");
+
+fn from_hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_pg_numeric_send_vectors() {
+    let mut count = 0;
+
+    for line in VECTORS.lines() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with("--") {
+            continue;
+        }
+
+        let end = line[1..].find('\'').unwrap() + 1;
+        let input = &line[1..end];
+        let rest = line[end + 1..].trim_start();
+        let arrow = rest.find(" -> ").unwrap();
+        let raw = from_hex(&rest[..arrow]);
+        let result = &rest[arrow + " -> ".len()..];
+
+        let decoded = Decimal::from_sql(&Type::NUMERIC, &raw);
+        if let Some(message) = result.strip_prefix("ERROR ") {
+            let err = decoded.unwrap_err().to_string();
+            assert!(err.contains(message), "{}: {}", line, err);
+        } else if result == "=" {
+            let val = input.parse::<Decimal>().unwrap();
+            assert_eq!(decoded.unwrap(), val, "{}", line);
+
+            let mut buf = BytesMut::new();
+            val.to_sql(&Type::NUMERIC, &mut buf).unwrap();
+            assert_eq!(&buf[..], &raw[..], "{}", line);
+        } else {
+            assert_eq!(decoded.unwrap(), result.parse::<Decimal>().unwrap(), "{}", line);
+        }
+
+        count += 1;
+    }
+
+    assert!(count > 40);
+}